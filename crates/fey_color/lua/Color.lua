@@ -186,6 +186,22 @@ function Color.oklab(l, a, b) end
 ---@nodiscard
 function Color.to_oklab(color) end
 
+---The color of a light source at a temperature in Kelvin, such as `1900` for candlelight, `6500`
+---for daylight, or `10000` for a clear blue sky.
+---@param kelvin number
+---@return Color
+---@nodiscard
+function Color.temperature(kelvin) end
+
+---Correct a color lit by a `source` temperature light so it looks lit by a `target` temperature
+---light instead.
+---@param color Color
+---@param source number Temperature in Kelvin.
+---@param target number Temperature in Kelvin.
+---@return Color
+---@nodiscard
+function Color.white_balance(color, source, target) end
+
 ---Lerp between two colors by a factor of `t`.
 ---@param from Color
 ---@param to Color
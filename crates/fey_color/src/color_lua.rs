@@ -116,6 +116,16 @@ impl LuaModule for ColorModule {
                 Ok((l, a, b))
             })?,
         )?;
+        m.set(
+            "temperature",
+            lua.create_function(|_, kelvin: f32| Ok(Rgba8::from_temperature(kelvin)))?,
+        )?;
+        m.set(
+            "white_balance",
+            lua.create_function(|_, (col, source, target): (Rgba8, f32, f32)| {
+                Ok(col.white_balance(source, target))
+            })?,
+        )?;
         m.set(
             "lerp",
             lua.create_function(|_, (a, b, t): (Rgba8, Rgba8, f32)| {
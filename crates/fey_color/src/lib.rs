@@ -9,6 +9,7 @@ mod hsv;
 mod oklab;
 mod rgb;
 mod rgba;
+mod temperature;
 
 #[cfg(feature = "lua")]
 mod color_lua;
@@ -22,6 +23,7 @@ pub use hsv::*;
 pub use oklab::*;
 pub use rgb::*;
 pub use rgba::*;
+pub use temperature::*;
 
 #[cfg(feature = "lua")]
 pub use color_lua::*;
@@ -1,6 +1,7 @@
 use crate::{Channel, FromRgb, FromRgba, Grey, GreyAlpha, Rgb, ToRgba, abgr};
 
 use bytemuck::{Pod, Zeroable};
+use fey_math::Interp;
 use serde::de::Error;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt::{Debug, Display, Formatter};
@@ -278,6 +279,55 @@ impl Rgba<u8> {
     }
 }
 
+impl Rgba<u8> {
+    /// Interpolate each channel of the colors as floats, rounding and clamping the results.
+    #[inline]
+    fn interp_channels<const N: usize>(colors: [Self; N], f: impl Fn([f32; N]) -> f32) -> Self {
+        let ch = |get: fn(&Self) -> u8| {
+            f(colors.map(|c| get(&c) as f32)).round().clamp(0.0, 255.0) as u8
+        };
+        Self::new(ch(|c| c.r), ch(|c| c.g), ch(|c| c.b), ch(|c| c.a))
+    }
+}
+
+impl Interp for Rgba<u8> {
+    type Factor = f32;
+
+    #[inline]
+    fn lerp(self, target: Self, t: f32) -> Self {
+        Self::interp_channels([self, target], |[a, b]| a.lerp(b, t))
+    }
+
+    #[inline]
+    fn quad_bezier(self, control: Self, target: Self, t: f32) -> Self {
+        Self::interp_channels([self, control, target], |[a, b, c]| a.quad_bezier(b, c, t))
+    }
+
+    #[inline]
+    fn cubic_bezier(self, control1: Self, control2: Self, target: Self, t: f32) -> Self {
+        Self::interp_channels([self, control1, control2, target], |[a, b, c, d]| {
+            a.cubic_bezier(b, c, d, t)
+        })
+    }
+
+    #[inline]
+    fn hermite(self, tangent1: f32, target: Self, tangent2: f32, t: f32) -> Self {
+        Self::interp_channels([self, target], |[a, b]| a.hermite(tangent1, b, tangent2, t))
+    }
+
+    #[inline]
+    fn catmull_rom(self, control1: Self, control2: Self, target: Self, t: f32) -> Self {
+        Self::interp_channels([self, control1, control2, target], |[a, b, c, d]| {
+            a.catmull_rom(b, c, d, t)
+        })
+    }
+
+    #[inline]
+    fn smooth_step(self, target: Self, t: f32) -> Self {
+        Self::interp_channels([self, target], |[a, b]| a.smooth_step(b, t))
+    }
+}
+
 impl Debug for Rgba<u8> {
     #[inline]
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
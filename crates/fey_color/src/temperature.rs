@@ -0,0 +1,133 @@
+use crate::{Channel, Rgb, Rgba};
+use serde::{Deserialize, Serialize};
+
+impl<T: Channel> Rgb<T> {
+    /// The color of a black-body light source at `kelvin` degrees, such as `1900` for
+    /// candlelight, `6500` for daylight, or `10000` for a clear blue sky. Temperatures are
+    /// clamped to `1000-40000`.
+    pub fn from_temperature(kelvin: f32) -> Self {
+        // Tanner Helland's fit of the black-body curve
+        let t = kelvin.clamp(1000.0, 40000.0) / 100.0;
+        let r = match t <= 66.0 {
+            true => 255.0,
+            false => 329.69873 * (t - 60.0).powf(-0.13320476),
+        };
+        let g = match t <= 66.0 {
+            true => 99.4708 * t.ln() - 161.11957,
+            false => 288.12216 * (t - 60.0).powf(-0.07551485),
+        };
+        let b = match t {
+            t if t >= 66.0 => 255.0,
+            t if t <= 19.0 => 0.0,
+            t => 138.51773 * (t - 10.0).ln() - 305.0448,
+        };
+        let [r, g, b] = [r, g, b].map(|c| (c / 255.0).clamp(0.0, 1.0).to_channel());
+        Self::new(r, g, b)
+    }
+
+    /// Correct the color as if it was lit by a `source` temperature light, so it looks like
+    /// it was lit by a `target` temperature light instead.
+    #[inline]
+    pub fn white_balance(self, source_kelvin: f32, target_kelvin: f32) -> Self {
+        WhiteBalance::new(source_kelvin, target_kelvin).apply(self)
+    }
+}
+
+impl<T: Channel> Rgba<T> {
+    /// The opaque color of a black-body light source at `kelvin` degrees. See
+    /// [`Rgb::from_temperature`].
+    #[inline]
+    pub fn from_temperature(kelvin: f32) -> Self {
+        Rgb::from_temperature(kelvin).with_a(T::CHANNEL_MAX)
+    }
+
+    /// Correct the color as if it was lit by a `source` temperature light, so it looks like
+    /// it was lit by a `target` temperature light instead. Alpha is unchanged.
+    #[inline]
+    pub fn white_balance(self, source_kelvin: f32, target_kelvin: f32) -> Self {
+        WhiteBalance::new(source_kelvin, target_kelvin).apply_rgba(self)
+    }
+}
+
+/// Per-channel gains that shift colors from one light temperature to another.
+///
+/// Building this once and applying it to many colors is cheaper than calling
+/// [`Rgb::white_balance`] for each of them, which makes it suited to tinting a whole scene
+/// as the time of day changes.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WhiteBalance {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Default for WhiteBalance {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl WhiteBalance {
+    /// A white balance that leaves colors unchanged.
+    pub const IDENTITY: Self = Self {
+        r: 1.0,
+        g: 1.0,
+        b: 1.0,
+    };
+
+    /// Shift colors lit by a `source` temperature light to look like they were lit by a
+    /// `target` temperature light. For example, `new(6500.0, 3000.0)` warms daylight colors
+    /// into evening light.
+    pub fn new(source_kelvin: f32, target_kelvin: f32) -> Self {
+        let src = Rgb::<f32>::from_temperature(source_kelvin);
+        let dst = Rgb::<f32>::from_temperature(target_kelvin);
+        let gain = |s: f32, d: f32| d / s.max(1.0 / 255.0);
+        let gains = Self {
+            r: gain(src.r, dst.r),
+            g: gain(src.g, dst.g),
+            b: gain(src.b, dst.b),
+        };
+
+        // keep the brightest channel at full strength so colors aren't blown out
+        let max = gains.r.max(gains.g).max(gains.b);
+        gains.scaled(1.0 / max)
+    }
+
+    /// Shift along the green-magenta axis, which temperature alone doesn't cover. Positive
+    /// tints push colors towards magenta, negative towards green.
+    #[inline]
+    pub fn with_tint(self, tint: f32) -> Self {
+        Self {
+            g: self.g * (1.0 - tint).max(0.0),
+            ..self
+        }
+    }
+
+    /// Multiply every gain by `scale`.
+    #[inline]
+    pub fn scaled(self, scale: f32) -> Self {
+        Self {
+            r: self.r * scale,
+            g: self.g * scale,
+            b: self.b * scale,
+        }
+    }
+
+    /// Apply the white balance to a color.
+    #[inline]
+    pub fn apply<T: Channel>(&self, col: Rgb<T>) -> Rgb<T> {
+        let gain = |c: T, g: f32| (c.to_channel::<f32>() * g).clamp(0.0, 1.0).to_channel();
+        Rgb::new(
+            gain(col.r, self.r),
+            gain(col.g, self.g),
+            gain(col.b, self.b),
+        )
+    }
+
+    /// Apply the white balance to a color, leaving its alpha unchanged.
+    #[inline]
+    pub fn apply_rgba<T: Channel>(&self, col: Rgba<T>) -> Rgba<T> {
+        self.apply(Rgb::new(col.r, col.g, col.b)).with_a(col.a)
+    }
+}
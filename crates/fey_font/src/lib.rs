@@ -201,6 +201,14 @@ impl Glyph<'_> {
         }
     }
 
+    /// Shift the glyph by a fractional pixel amount before rasterizing it. This is used to
+    /// pre-rasterize glyphs at subpixel positions so they don't blur when drawn between pixels.
+    #[inline]
+    pub fn with_subpixel_offset(mut self, offset: Vec2<f32>) -> Self {
+        self.glyph.position = ab_glyph::point(offset.x, offset.y);
+        self
+    }
+
     /// How much to horizontally offset the glyph from the cursor position.
     #[inline]
     pub fn left_side_bearing(&self) -> f32 {
@@ -256,6 +264,146 @@ impl Glyph<'_> {
             GreyAlpha8::new(a, a)
         })
     }
+
+    /// Rasterize the glyph as a signed distance field, where each pixel stores how far it is
+    /// from the glyph's edge. The edge is at `128`, values above it are inside the glyph, and
+    /// values fade out to `0` at `spread` pixels outside of it. The image is padded by
+    /// `spread` pixels on each side so the field isn't cut off.
+    ///
+    /// Because the distances can be interpolated, the glyph can be drawn scaled up or rotated
+    /// without blurring or pixelating, as long as it's drawn with a shader that thresholds it.
+    pub fn rasterize_sdf(&self, spread: f32) -> Option<RasterizedGlyph<GreyAlpha8>> {
+        // the distances are measured on an oversampled outline so edges between pixels are
+        // resolved, then averaged back down
+        const OVERSAMPLE: u32 = 4;
+        let spread = spread.max(1.0);
+        let mut glyph = self.glyph.clone();
+        glyph.scale = ab_glyph::PxScale::from(self.font.pt_size * OVERSAMPLE as f32);
+        glyph.position = ab_glyph::point(
+            glyph.position.x * OVERSAMPLE as f32,
+            glyph.position.y * OVERSAMPLE as f32,
+        );
+        let outlined = match &self.font.font {
+            FontData::Ref(f) => f.as_scaled(glyph.scale).outline_glyph(glyph),
+            FontData::Vec(f) => f.as_scaled(glyph.scale).outline_glyph(glyph),
+        }?;
+
+        // size of the output image, and the oversampled grid that covers it
+        let bounds = outlined.px_bounds();
+        let pad = spread.ceil() as u32;
+        let w = (bounds.width() as u32).div_ceil(OVERSAMPLE) + pad * 2;
+        let h = (bounds.height() as u32).div_ceil(OVERSAMPLE) + pad * 2;
+        let (hw, hh) = ((w * OVERSAMPLE) as usize, (h * OVERSAMPLE) as usize);
+        let hpad = (pad * OVERSAMPLE) as usize;
+
+        let mut inside = vec![false; hw * hh];
+        outlined.draw(|x, y, a| {
+            inside[(y as usize + hpad) * hw + x as usize + hpad] = a > 0.5;
+        });
+
+        // squared distance from each sample to the nearest sample on the other side of the edge
+        let to_inside = distance_transform(&inside, hw, hh, |i| i);
+        let to_outside = distance_transform(&inside, hw, hh, |i| !i);
+
+        let scale = 1.0 / (OVERSAMPLE * OVERSAMPLE) as f32;
+        let mut image = Image::new_vec((w, h), GreyAlpha8::TRANSPARENT);
+        for y in 0..h {
+            for x in 0..w {
+                let mut dist = 0.0;
+                for sy in 0..OVERSAMPLE {
+                    for sx in 0..OVERSAMPLE {
+                        let i =
+                            (y * OVERSAMPLE + sy) as usize * hw + (x * OVERSAMPLE + sx) as usize;
+                        // samples are half a sample from the edge they border
+                        dist += match inside[i] {
+                            true => 0.5 - to_outside[i].sqrt(),
+                            false => to_inside[i].sqrt() - 0.5,
+                        };
+                    }
+                }
+                let dist = dist * scale / OVERSAMPLE as f32;
+                let v = ((0.5 - dist / (spread * 2.0)).clamp(0.0, 1.0) * 255.0).round() as u8;
+                image.set(x, y, GreyAlpha8::new(v, v));
+            }
+        }
+
+        let min = vec2(bounds.min.x, bounds.min.y) / OVERSAMPLE as f32 - Vec2::splat(pad as f32);
+        Some(RasterizedGlyph {
+            image,
+            offset: vec2(min.x, -min.y),
+        })
+    }
+}
+
+/// Squared euclidean distance from every cell to the nearest cell where `target` is true,
+/// using the separable algorithm from Felzenszwalb & Huttenlocher's "Distance Transforms of
+/// Sampled Functions".
+fn distance_transform(
+    cells: &[bool],
+    w: usize,
+    h: usize,
+    target: impl Fn(bool) -> bool,
+) -> Vec<f32> {
+    const FAR: f32 = 1e20;
+    let mut dist: Vec<f32> = cells
+        .iter()
+        .map(|&c| if target(c) { 0.0 } else { FAR })
+        .collect();
+    let mut f = vec![0.0; w.max(h)];
+    let mut d = vec![0.0; w.max(h)];
+    let mut v = vec![0; w.max(h)];
+    let mut z = vec![0.0; w.max(h) + 1];
+
+    for x in 0..w {
+        for y in 0..h {
+            f[y] = dist[y * w + x];
+        }
+        transform_1d(&f[..h], &mut d[..h], &mut v, &mut z);
+        for y in 0..h {
+            dist[y * w + x] = d[y];
+        }
+    }
+    for y in 0..h {
+        f[..w].copy_from_slice(&dist[y * w..(y + 1) * w]);
+        transform_1d(&f[..w], &mut d[..w], &mut v, &mut z);
+        dist[y * w..(y + 1) * w].copy_from_slice(&d[..w]);
+    }
+    dist
+}
+
+/// One dimensional squared distance transform, finding the lower envelope of the parabolas
+/// rooted at each of `f`'s values.
+fn transform_1d(f: &[f32], d: &mut [f32], v: &mut [usize], z: &mut [f32]) {
+    let n = f.len();
+    if n == 0 {
+        return;
+    }
+    let intersect = |q: usize, p: usize| {
+        ((f[q] + (q * q) as f32) - (f[p] + (p * p) as f32)) / (2 * q - 2 * p) as f32
+    };
+    let mut k = 0;
+    v[0] = 0;
+    z[0] = f32::NEG_INFINITY;
+    z[1] = f32::INFINITY;
+    for q in 1..n {
+        let mut s = intersect(q, v[k]);
+        while s <= z[k] {
+            k -= 1;
+            s = intersect(q, v[k]);
+        }
+        k += 1;
+        v[k] = q;
+        z[k] = s;
+        z[k + 1] = f32::INFINITY;
+    }
+    k = 0;
+    for (q, d) in d.iter_mut().enumerate() {
+        while z[k + 1] < q as f32 {
+            k += 1;
+        }
+        let p = v[k];
+        *d = (q as f32 - p as f32).powi(2) + f[p];
+    }
 }
 
 /// A rasterized glyph with a drawing offset.
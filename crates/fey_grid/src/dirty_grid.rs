@@ -0,0 +1,162 @@
+use crate::{Grid, GridMut};
+use fey_math::{RectU, rect};
+
+/// Wraps a mutable grid and tracks which region of it has been modified.
+///
+/// Every mutable access, including through [views](crate::View), rows and columns, marks the
+/// touched cells as dirty. This is useful when a grid backs a texture, such as a minimap or a
+/// tilemap, so only the changed region needs to be re-uploaded. Fast paths that borrow whole
+/// rows as slices mark the entire row.
+///
+/// ```ignore
+/// let mut tiles = DirtyGrid::new(VecGrid::new(64, 64));
+/// tiles.set(3, 4, Rgba8::RED);
+/// if let Some(region) = tiles.take_dirty() {
+///     texture.write_grid_region(&tiles, region);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirtyGrid<G> {
+    grid: G,
+    dirty: Option<RectU>,
+}
+
+impl<G: Grid> DirtyGrid<G> {
+    /// Wrap the grid. It starts with no dirty region.
+    #[inline]
+    pub fn new(grid: G) -> Self {
+        Self { grid, dirty: None }
+    }
+
+    /// The wrapped grid.
+    #[inline]
+    pub fn inner(&self) -> &G {
+        &self.grid
+    }
+
+    /// Unwrap the grid, discarding the dirty region.
+    #[inline]
+    pub fn into_inner(self) -> G {
+        self.grid
+    }
+
+    /// The smallest region containing every cell modified since the last clear, or `None`
+    /// if nothing was modified.
+    #[inline]
+    pub fn dirty_rect(&self) -> Option<RectU> {
+        self.dirty
+    }
+
+    /// If any cells were modified since the last clear.
+    #[inline]
+    pub fn is_dirty(&self) -> bool {
+        self.dirty.is_some()
+    }
+
+    /// Return the dirty region and clear it.
+    #[inline]
+    pub fn take_dirty(&mut self) -> Option<RectU> {
+        self.dirty.take()
+    }
+
+    /// Clear the dirty region without returning it.
+    #[inline]
+    pub fn clear_dirty(&mut self) {
+        self.dirty = None;
+    }
+
+    /// Mark a region as dirty. The region is clipped to the grid's bounds.
+    pub fn mark_dirty(&mut self, region: impl Into<RectU>) {
+        let RectU { x, y, w, h } = region.into();
+        let right = x.saturating_add(w).min(self.grid.width());
+        let bottom = y.saturating_add(h).min(self.grid.height());
+        if x >= right || y >= bottom {
+            return;
+        }
+        let region = rect(x, y, right - x, bottom - y);
+        self.dirty = Some(match self.dirty {
+            Some(dirty) => dirty.conflate(&region),
+            None => region,
+        });
+    }
+
+    /// Mark the entire grid as dirty.
+    #[inline]
+    pub fn mark_all_dirty(&mut self) {
+        self.mark_dirty(rect(0, 0, self.grid.width(), self.grid.height()));
+    }
+}
+
+impl<G: Grid> Grid for DirtyGrid<G> {
+    type Item = G::Item;
+    type Root = Self;
+
+    #[inline]
+    fn root(&self) -> &Self::Root {
+        self
+    }
+
+    #[inline]
+    fn root_x(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn root_y(&self) -> u32 {
+        0
+    }
+
+    #[inline]
+    fn width(&self) -> u32 {
+        self.grid.width()
+    }
+
+    #[inline]
+    fn height(&self) -> u32 {
+        self.grid.height()
+    }
+
+    #[inline]
+    fn get(&self, x: u32, y: u32) -> Option<&Self::Item> {
+        self.grid.get(x, y)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked(&self, x: u32, y: u32) -> &Self::Item {
+        unsafe { self.grid.get_unchecked(x, y) }
+    }
+
+    #[inline]
+    fn row_slice(&self, y: u32) -> Option<&[Self::Item]> {
+        self.grid.row_slice(y)
+    }
+}
+
+impl<G: GridMut> GridMut for DirtyGrid<G> {
+    type RootMut = Self;
+
+    #[inline]
+    fn root_mut(&mut self) -> &mut Self::RootMut {
+        self
+    }
+
+    #[inline]
+    fn get_mut(&mut self, x: u32, y: u32) -> Option<&mut Self::Item> {
+        if x < self.grid.width() && y < self.grid.height() {
+            self.mark_dirty(rect(x, y, 1, 1));
+        }
+        self.grid.get_mut(x, y)
+    }
+
+    #[inline]
+    unsafe fn get_unchecked_mut(&mut self, x: u32, y: u32) -> &mut Self::Item {
+        self.mark_dirty(rect(x, y, 1, 1));
+        unsafe { self.grid.get_unchecked_mut(x, y) }
+    }
+
+    #[inline]
+    fn row_slice_mut(&mut self, y: u32) -> Option<&mut [Self::Item]> {
+        self.mark_dirty(rect(0, y, self.grid.width(), 1));
+        self.grid.row_slice_mut(y)
+    }
+}
@@ -0,0 +1,110 @@
+use fey_math::Vec2U;
+
+/// Transforms from octant-local coordinates into grid coordinates, as `[xx, xy, yx, yy]`.
+const OCTANTS: [[i32; 4]; 8] = [
+    [1, 0, 0, 1],
+    [0, 1, 1, 0],
+    [0, -1, 1, 0],
+    [-1, 0, 0, 1],
+    [-1, 0, 0, -1],
+    [0, -1, -1, 0],
+    [0, 1, -1, 0],
+    [1, 0, 0, -1],
+];
+
+/// Find all cells visible from `origin` within `radius` cells, using recursive shadowcasting.
+///
+/// The grid is `size` cells large, and `is_opaque(x, y)` should return `true` for cells that
+/// block sight. `visit(x, y)` is called for each visible cell, including the origin and any
+/// opaque cells that are seen (so walls are revealed). Cells on the border between octants may
+/// be visited more than once.
+pub fn field_of_view(
+    size: Vec2U,
+    origin: Vec2U,
+    radius: u32,
+    is_opaque: impl FnMut(u32, u32) -> bool,
+    visit: impl FnMut(u32, u32),
+) {
+    if origin.x >= size.x || origin.y >= size.y {
+        return;
+    }
+    let mut fov = Fov {
+        size,
+        origin,
+        radius: radius as i32,
+        is_opaque,
+        visit,
+    };
+    (fov.visit)(origin.x, origin.y);
+    for oct in &OCTANTS {
+        fov.cast(1, 1.0, 0.0, oct);
+    }
+}
+
+struct Fov<O, V> {
+    size: Vec2U,
+    origin: Vec2U,
+    radius: i32,
+    is_opaque: O,
+    visit: V,
+}
+
+impl<O: FnMut(u32, u32) -> bool, V: FnMut(u32, u32)> Fov<O, V> {
+    /// The cell at the octant-local offset, if it is inside the grid.
+    fn cell(&self, dx: i32, dy: i32, oct: &[i32; 4]) -> Option<(u32, u32)> {
+        let x = self.origin.x as i32 + dx * oct[0] + dy * oct[1];
+        let y = self.origin.y as i32 + dx * oct[2] + dy * oct[3];
+        (x >= 0 && y >= 0 && (x as u32) < self.size.x && (y as u32) < self.size.y)
+            .then_some((x as u32, y as u32))
+    }
+
+    /// Scan rows of the octant outwards, between the start and end slopes, recursing into the
+    /// gaps left by any opaque cells.
+    fn cast(&mut self, row: i32, mut start: f32, end: f32, oct: &[i32; 4]) {
+        if start < end {
+            return;
+        }
+        let radius_sq = self.radius * self.radius;
+        let mut next_start = start;
+        for j in row..=self.radius {
+            let dy = -j;
+            let mut blocked = false;
+            for dx in -j..=0 {
+                let l_slope = (dx as f32 - 0.5) / (dy as f32 + 0.5);
+                let r_slope = (dx as f32 + 0.5) / (dy as f32 - 0.5);
+                if start < r_slope {
+                    continue;
+                } else if end > l_slope {
+                    break;
+                }
+
+                // cells outside the grid block sight
+                let opaque = match self.cell(dx, dy, oct) {
+                    Some((x, y)) => {
+                        if dx * dx + dy * dy <= radius_sq {
+                            (self.visit)(x, y);
+                        }
+                        (self.is_opaque)(x, y)
+                    }
+                    None => true,
+                };
+
+                if blocked {
+                    if opaque {
+                        next_start = r_slope;
+                    } else {
+                        blocked = false;
+                        start = next_start;
+                    }
+                } else if opaque && j < self.radius {
+                    blocked = true;
+                    self.cast(j + 1, start, l_slope, oct);
+                    next_start = r_slope;
+                }
+            }
+            if blocked {
+                break;
+            }
+        }
+    }
+}
@@ -2,7 +2,7 @@ use crate::{
     ArrGrid, Col, ColsIter, Coord, CoordComponent, GridBuf, GridIter, GridMut, Row, RowsIter,
     VecGrid, View,
 };
-use fey_math::{RectU, Vec2U, rect, vec2};
+use fey_math::{Interp, RectU, Vec2F, Vec2U, rect, vec2};
 use std::fmt::{Debug, Write};
 use std::hash::{Hash, Hasher};
 
@@ -295,6 +295,50 @@ pub trait Grid {
         writeln!(f)
     }
 
+    /// Sample the grid at a fractional position, blending between the 4 nearest values. Each
+    /// cell is sampled exactly at its coordinate, and positions outside the grid are clamped
+    /// to its edges. Returns `None` if the grid is empty.
+    ///
+    /// Useful for smoothly reading heightmaps and flow fields.
+    #[inline]
+    fn sample_bilinear(&self, pos: Vec2F) -> Option<Self::Item>
+    where
+        Self::Item: Interp<Factor = f32> + Copy,
+    {
+        bilinear(self, pos, |val| *val)
+    }
+
+    /// Estimate the gradient of a numeric grid at a fractional position, pointing towards
+    /// increasing values. Positions are sampled like [`sample_bilinear`](Self::sample_bilinear).
+    /// Returns `None` if the grid is empty.
+    ///
+    /// On a heightmap this points uphill, so moving against it flows downhill.
+    #[inline]
+    fn gradient(&self, pos: Vec2F) -> Option<Vec2F>
+    where
+        Self::Item: Into<f32> + Copy,
+    {
+        if self.width() == 0 || self.height() == 0 {
+            return None;
+        }
+
+        // central differences, which become one-sided at the edges
+        let max = vec2(self.width() as f32 - 1.0, self.height() as f32 - 1.0);
+        let sample = |p: Vec2F| bilinear(self, p, |val| (*val).into());
+        let diff = |a: Vec2F, b: Vec2F, dist: f32| -> Option<f32> {
+            Some(match dist > 0.0 {
+                true => (sample(b)? - sample(a)?) / dist,
+                false => 0.0,
+            })
+        };
+        let (x0, x1) = ((pos.x - 1.0).max(0.0), (pos.x + 1.0).min(max.x));
+        let (y0, y1) = ((pos.y - 1.0).max(0.0), (pos.y + 1.0).min(max.y));
+        Some(vec2(
+            diff(vec2(x0, pos.y), vec2(x1, pos.y), x1 - x0)?,
+            diff(vec2(pos.x, y0), vec2(pos.x, y1), y1 - y0)?,
+        ))
+    }
+
     fn hash_grid<H: Hasher>(&self, hasher: &mut H)
     where
         Self: Sized,
@@ -314,6 +358,30 @@ pub trait Grid {
     }
 }
 
+/// Bilinearly sample a grid, converting each value with `f`.
+fn bilinear<G, T, F>(grid: &G, pos: Vec2F, f: F) -> Option<T>
+where
+    G: Grid + ?Sized,
+    T: Interp<Factor = f32> + Copy,
+    F: Fn(&G::Item) -> T,
+{
+    if grid.width() == 0 || grid.height() == 0 {
+        return None;
+    }
+    let max = vec2((grid.width() - 1) as f32, (grid.height() - 1) as f32);
+    let pos = pos.clamp(Vec2F::ZERO, max);
+    let (x0, y0) = (pos.x.floor() as u32, pos.y.floor() as u32);
+    let (x1, y1) = (
+        (x0 + 1).min(grid.width() - 1),
+        (y0 + 1).min(grid.height() - 1),
+    );
+    let (tx, ty) = (pos.x.fract(), pos.y.fract());
+    let get = |x, y| f(grid.get(x, y).unwrap());
+    let top = get(x0, y0).lerp(get(x1, y0), tx);
+    let bottom = get(x0, y1).lerp(get(x1, y1), tx);
+    Some(top.lerp(bottom, ty))
+}
+
 impl<T, const W: usize, const H: usize> Grid for [[T; W]; H] {
     type Item = T;
     type Root = Self;
@@ -1,13 +1,17 @@
 use crate::{Coord, CoordComponent, Grid, GridIter, GridMut};
 use fey_math::Vec2U;
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Formatter};
 use std::marker::PhantomData;
 use std::ops::{Index, IndexMut};
 
 /// A grid implementation for different storage types.
+#[derive(Serialize, Deserialize)]
+#[serde(bound(serialize = "S: Serialize", deserialize = "S: Deserialize<'de>"))]
 pub struct GridBuf<T, S = Vec<T>> {
     pub(crate) size: Vec2U,
     pub(crate) store: S,
+    #[serde(skip)]
     pub(crate) marker: PhantomData<T>,
 }
 
@@ -9,6 +9,8 @@ mod col;
 mod col_iter;
 mod cols_iter;
 mod coord;
+mod dirty_grid;
+mod fov;
 mod grid;
 mod grid_buf;
 mod grid_iter;
@@ -22,6 +24,8 @@ pub use col::*;
 pub use col_iter::*;
 pub use cols_iter::*;
 pub use coord::*;
+pub use dirty_grid::*;
+pub use fov::*;
 pub use grid::*;
 pub use grid_buf::*;
 pub use grid_iter::*;
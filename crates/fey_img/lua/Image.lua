@@ -13,6 +13,7 @@
 ---     |"rgba8"
 ---     |"rgba16"
 ---     |"rgba32f"
+---     |"indexed8"
 
 ---@class Image: ImageMethods
 
@@ -156,4 +157,32 @@ function methods.draw_part(self, src, src_x, src_y, src_w, src_h, dst_x, dst_y)
 ---@nodiscard
 function methods.sub_image(self, x, y, w, h) end
 
+---Tone-map a floating point HDR image into a displayable `"rgba8"` image.
+---@param self Image
+---@param exposure number? Brightness adjustment in stops, where each stop doubles the brightness. Defaults to `0`.
+---@param op "clamp"|"reinhard"|"reinhard_extended"|"aces"|nil Defaults to `"aces"`.
+---@param white number? White point for `"reinhard_extended"`. Defaults to `4`.
+---@return Image
+---@nodiscard
+function methods.tonemap(self, exposure, op, white) end
+
+---The palette of an `"indexed8"` image, or `nil` if the image isn't indexed.
+---@param self Image
+---@return integer[]?
+---@nodiscard
+function methods.palette(self) end
+
+---Replace the palette of an `"indexed8"` image, which can have at most 256 colors.
+---@param self Image
+---@param palette integer[]
+function methods.set_palette(self, palette) end
+
+---Rotate the palette entries `start` to `end` (inclusive) of an `"indexed8"` image by `steps`,
+---for palette-cycling effects.
+---@param self Image
+---@param start integer
+---@param end_ integer
+---@param steps integer
+function methods.rotate_palette(self, start, end_, steps) end
+
 return module
\ No newline at end of file
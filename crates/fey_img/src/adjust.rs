@@ -0,0 +1,172 @@
+use crate::Image;
+use fey_color::{FromRgb, HslF, HsvF, Rgb, Rgba8, ToRgb};
+
+/// A lookup table that remaps the values of an 8-bit color channel.
+///
+/// Curves can be applied to images with [`Image::apply_curve`] and
+/// [`Image::apply_curves`], which is handy for generating color variants
+/// of sprites at pack time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Curve(pub [u8; 256]);
+
+impl Default for Curve {
+    #[inline]
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+impl Curve {
+    /// A curve that leaves all values unchanged.
+    pub const IDENTITY: Self = {
+        let mut table = [0; 256];
+        let mut i = 0;
+        while i < 256 {
+            table[i] = i as u8;
+            i += 1;
+        }
+        Self(table)
+    };
+
+    /// Create a curve from a function that maps values in the range `0-1`.
+    /// Results are clamped to `0-1`.
+    pub fn from_fn(mut f: impl FnMut(f32) -> f32) -> Self {
+        Self(std::array::from_fn(|i| {
+            let val = f(i as f32 / 255.0).clamp(0.0, 1.0);
+            (val * 255.0).round() as u8
+        }))
+    }
+
+    /// Create a curve that linearly interpolates between `(input, output)` points in the
+    /// range `0-1`. The points do not need to be sorted. Values before the first point or
+    /// after the last are held flat, and an empty list of points gives the identity curve.
+    pub fn from_points(points: &[(f32, f32)]) -> Self {
+        if points.is_empty() {
+            return Self::IDENTITY;
+        }
+        let mut points = points.to_vec();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self::from_fn(|x| {
+            let i = points.partition_point(|p| p.0 < x);
+            match (i.checked_sub(1).map(|i| points[i]), points.get(i)) {
+                (Some(a), Some(b)) if b.0 > a.0 => a.1 + (b.1 - a.1) * (x - a.0) / (b.0 - a.0),
+                (_, Some(b)) => b.1,
+                (Some(a), None) => a.1,
+                (None, None) => x,
+            }
+        })
+    }
+
+    /// Create a curve that adjusts brightness, contrast and gamma.
+    ///
+    /// - `brightness` is added to values, where `0` is unchanged.
+    /// - `contrast` scales values away from the midpoint, where `1` is unchanged.
+    /// - `gamma` brightens midtones when above `1` and darkens them when below, where `1` is
+    ///   unchanged.
+    pub fn adjust(brightness: f32, contrast: f32, gamma: f32) -> Self {
+        let inv_gamma = 1.0 / gamma.max(0.0001);
+        Self::from_fn(|x| (x.powf(inv_gamma) - 0.5) * contrast + 0.5 + brightness)
+    }
+
+    /// Create a levels curve. Input values are remapped so `in_black` becomes black and
+    /// `in_white` becomes white, then `gamma` is applied and the result is remapped to the
+    /// range `out_black..=out_white`.
+    pub fn levels(
+        in_black: f32,
+        in_white: f32,
+        gamma: f32,
+        out_black: f32,
+        out_white: f32,
+    ) -> Self {
+        let range = (in_white - in_black).max(0.0001);
+        let inv_gamma = 1.0 / gamma.max(0.0001);
+        Self::from_fn(|x| {
+            let x = ((x - in_black) / range).clamp(0.0, 1.0).powf(inv_gamma);
+            out_black + (out_white - out_black) * x
+        })
+    }
+
+    /// Create a curve that inverts values.
+    #[inline]
+    pub fn invert() -> Self {
+        Self::from_fn(|x| 1.0 - x)
+    }
+
+    /// Remap a value.
+    #[inline]
+    pub fn get(&self, val: u8) -> u8 {
+        self.0[val as usize]
+    }
+
+    /// Create a curve that applies this curve and then `next`.
+    #[inline]
+    pub fn then(&self, next: &Curve) -> Self {
+        Self(self.0.map(|val| next.get(val)))
+    }
+}
+
+/// Color adjustments for RGBA images.
+///
+/// These expect straight (non-premultiplied) alpha, so should be
+/// applied before calling [`premultiply`](Image::premultiply).
+impl<S: AsMut<[u8]>> Image<Rgba8, S> {
+    /// Adjust the brightness, contrast and gamma of the image's color channels.
+    /// See [`Curve::adjust`] for how the parameters work.
+    #[inline]
+    pub fn adjust(&mut self, brightness: f32, contrast: f32, gamma: f32) {
+        self.apply_curve(&Curve::adjust(brightness, contrast, gamma));
+    }
+
+    /// Remap the image's color channels with the curve. Alpha is unchanged.
+    #[inline]
+    pub fn apply_curve(&mut self, curve: &Curve) {
+        for p in self.pixels_mut() {
+            p.r = curve.get(p.r);
+            p.g = curve.get(p.g);
+            p.b = curve.get(p.b);
+        }
+    }
+
+    /// Remap each of the image's channels with their own curve.
+    pub fn apply_curves(&mut self, r: &Curve, g: &Curve, b: &Curve, a: &Curve) {
+        for p in self.pixels_mut() {
+            p.r = r.get(p.r);
+            p.g = g.get(p.g);
+            p.b = b.get(p.b);
+            p.a = a.get(p.a);
+        }
+    }
+
+    /// Shift the image's colors in [HSV](HsvF) space. `hue` is rotated by degrees, while
+    /// `saturation` and `value` are multipliers where `1` is unchanged.
+    pub fn shift_hsv(&mut self, hue: f32, saturation: f32, value: f32) {
+        self.map_rgb(|rgb| {
+            let mut c = HsvF::from_rgb(rgb);
+            c.h = (c.h + hue).rem_euclid(360.0);
+            c.s = (c.s * saturation).clamp(0.0, 1.0);
+            c.v = (c.v * value).clamp(0.0, 1.0);
+            c.to_rgb()
+        });
+    }
+
+    /// Shift the image's colors in [HSL](HslF) space. `hue` is rotated by degrees, while
+    /// `saturation` and `lightness` are multipliers where `1` is unchanged.
+    pub fn shift_hsl(&mut self, hue: f32, saturation: f32, lightness: f32) {
+        self.map_rgb(|rgb| {
+            let mut c = HslF::from_rgb(rgb);
+            c.h = (c.h + hue).rem_euclid(360.0);
+            c.s = (c.s * saturation).clamp(0.0, 1.0);
+            c.l = (c.l * lightness).clamp(0.0, 1.0);
+            c.to_rgb()
+        });
+    }
+
+    fn map_rgb(&mut self, mut f: impl FnMut(Rgb<u8>) -> Rgb<u8>) {
+        for p in self.pixels_mut() {
+            if p.a > 0 {
+                let Rgb { r, g, b } = f(Rgb::new(p.r, p.g, p.b));
+                (p.r, p.g, p.b) = (r, g, b);
+            }
+        }
+    }
+}
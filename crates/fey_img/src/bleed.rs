@@ -0,0 +1,71 @@
+use crate::Image;
+use fey_color::Rgba8;
+use fey_grid::Grid;
+
+impl<S: AsMut<[u8]> + AsRef<[u8]>> Image<Rgba8, S> {
+    /// Bleed the color of visible pixels outwards into neighboring fully transparent
+    /// pixels, repeated `iterations` times. Each transparent pixel takes the average color
+    /// of its filled neighbors, while its alpha stays zero.
+    ///
+    /// When a texture is filtered, transparent pixels next to visible ones get blended in,
+    /// and if they are black this causes dark halos around the edges of sprites. Bleeding
+    /// fixes this. Only useful for straight (non-premultiplied) alpha, since premultiplied
+    /// transparent pixels must stay black.
+    pub fn bleed_alpha(&mut self, iterations: u32) {
+        let w = self.width() as usize;
+        let h = self.height() as usize;
+        let mut filled: Vec<bool> = self.pixels().iter().map(|p| p.a > 0).collect();
+        let mut next = Vec::new();
+        for _ in 0..iterations {
+            next.clear();
+            let pixels = self.pixels();
+            for y in 0..h {
+                for x in 0..w {
+                    let i = y * w + x;
+                    if filled[i] {
+                        continue;
+                    }
+                    let (mut r, mut g, mut b, mut n) = (0u32, 0u32, 0u32, 0u32);
+                    for (dx, dy) in NEIGHBORS {
+                        let (nx, ny) = (x as isize + dx, y as isize + dy);
+                        if nx < 0 || ny < 0 || nx >= w as isize || ny >= h as isize {
+                            continue;
+                        }
+                        let j = ny as usize * w + nx as usize;
+                        if filled[j] {
+                            let p = pixels[j];
+                            r += p.r as u32;
+                            g += p.g as u32;
+                            b += p.b as u32;
+                            n += 1;
+                        }
+                    }
+                    if n == 0 {
+                        continue;
+                    }
+                    let avg = |c: u32| (c / n) as u8;
+                    next.push((i, Rgba8::new(avg(r), avg(g), avg(b), 0)));
+                }
+            }
+            if next.is_empty() {
+                break;
+            }
+            let pixels = self.pixels_mut();
+            for &(i, p) in &next {
+                pixels[i] = p;
+                filled[i] = true;
+            }
+        }
+    }
+}
+
+const NEIGHBORS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
@@ -1,9 +1,9 @@
 use crate::{
     ImageError, ImageFormat, ImageGrey8, ImageGrey16, ImageGrey32F, ImageGreyAlpha8,
-    ImageGreyAlpha16, ImageGreyAlpha32F, ImageRgb8, ImageRgb16, ImageRgb32F, ImageRgba8,
-    ImageRgba16, ImageRgba32F,
+    ImageGreyAlpha16, ImageGreyAlpha32F, ImageIndexed, ImageRgb8, ImageRgb16, ImageRgb32F,
+    ImageRgba8, ImageRgba16, ImageRgba32F,
 };
-use fey_color::ToRgba;
+use fey_color::{Rgba8, ToRgba};
 use fey_grid::Grid;
 use fey_math::{Vec2U, vec2};
 use png::{BitDepth, ColorType, Decoder};
@@ -34,6 +34,7 @@ pub enum DynImage {
     Rgba8(ImageRgba8),
     Rgba16(ImageRgba16),
     Rgba32F(ImageRgba32F),
+    Indexed(ImageIndexed),
 }
 
 impl DynImage {
@@ -52,6 +53,7 @@ impl DynImage {
             Self::Rgba8(_) => ImageFormat::Rgba8,
             Self::Rgba16(_) => ImageFormat::Rgba16,
             Self::Rgba32F(_) => ImageFormat::Rgba32F,
+            Self::Indexed(_) => ImageFormat::Indexed8,
         }
     }
 
@@ -70,10 +72,12 @@ impl DynImage {
             Self::Rgba8(img) => img.size(),
             Self::Rgba16(img) => img.size(),
             Self::Rgba32F(img) => img.size(),
+            Self::Indexed(img) => img.size(),
         }
     }
 
-    /// The raw bytes of the image.
+    /// The raw bytes of the image. For [`Indexed`](Self::Indexed) images these are the
+    /// palette indices.
     pub fn bytes(&self) -> &[u8] {
         match self {
             Self::Grey8(img) => img.bytes(),
@@ -88,6 +92,7 @@ impl DynImage {
             Self::Rgba8(img) => img.bytes(),
             Self::Rgba16(img) => img.bytes(),
             Self::Rgba32F(img) => img.bytes(),
+            Self::Indexed(img) => img.bytes(),
         }
     }
 
@@ -109,14 +114,16 @@ impl DynImage {
             Self::Rgba8(img) => img,
             Self::Rgba16(img) => img.map(|p| p.to_rgba()),
             Self::Rgba32F(img) => img.map(|p| p.to_rgba()),
+            Self::Indexed(img) => img.to_rgba8(),
         }
     }
 
-    /// Load a PNG or QOI file.
+    /// Load a PNG, QOI or Radiance HDR file.
     pub fn load_file<P: AsRef<Path>>(path: P) -> Result<Self, ImageError> {
         match path.as_ref().extension() {
             Some(ext) if ext.to_str() == Some("png") => Self::load_png_from_file(path),
             Some(ext) if ext.to_str() == Some("qoi") => Self::load_qoi_from_file(path),
+            Some(ext) if ext.to_str() == Some("hdr") => Self::load_hdr_from_file(path),
             ext => Err(ImageError::UnsupportedExtension(
                 ext.and_then(OsStr::to_str).unwrap_or("").to_string(),
             )),
@@ -133,7 +140,8 @@ impl DynImage {
         Self::load_png(Cursor::new(bytes))
     }
 
-    /// Load a PNG image.
+    /// Load a PNG image. Indexed PNGs are loaded as [`Indexed`](Self::Indexed) images, keeping
+    /// their palette.
     pub fn load_png<R: Read + Seek>(r: R) -> Result<Self, ImageError> {
         let decoder = Decoder::new(BufReader::new(r));
         let mut reader = decoder.read_info()?;
@@ -149,36 +157,38 @@ impl DynImage {
 
         let size = vec2(info.width, info.height);
 
+        if info.color_type == ColorType::Indexed {
+            let bits = info.bit_depth as usize;
+            let line_size = info.line_size;
+            let info = reader.info();
+            let rgb = info.palette.as_ref().map_or(&[][..], |p| p.as_ref());
+            let trns = info.trns.as_ref().map_or(&[][..], |t| t.as_ref());
+            let palette = rgb
+                .chunks_exact(3)
+                .enumerate()
+                .map(|(i, c)| Rgba8::new(c[0], c[1], c[2], trns.get(i).copied().unwrap_or(255)))
+                .collect();
+
+            // unpack indices with fewer than 8 bits, which are packed from the high bits down
+            let mask = ((1u16 << bits) - 1) as u8;
+            let (w, h) = (size.x as usize, size.y as usize);
+            let mut indices = Vec::with_capacity(w * h);
+            for row in buf.chunks(line_size).take(h) {
+                indices.extend((0..w).map(|x| {
+                    let bit = x * bits;
+                    (row[bit / 8] >> (8 - bits - bit % 8)) & mask
+                }));
+            }
+            return Ok(ImageIndexed::from_raw(size, indices, palette).into());
+        }
+
         match info.bit_depth {
             BitDepth::Eight => Ok(match info.color_type {
                 ColorType::Grayscale => ImageGrey8::from_raw(size, buf).into(),
                 ColorType::GrayscaleAlpha => ImageGreyAlpha8::from_raw(size, buf).into(),
                 ColorType::Rgb => ImageRgb8::from_raw(size, buf).into(),
                 ColorType::Rgba => ImageRgba8::from_raw(size, buf).into(),
-                ColorType::Indexed => {
-                    let info = reader.info();
-                    let pal = info.palette.as_ref().unwrap().as_ref();
-                    match info.trns.as_ref() {
-                        Some(trns) => ImageRgba8::from_raw(
-                            size,
-                            buf.into_iter()
-                                .map(|i| i as usize)
-                                .map(|i| [pal[i * 3], pal[i * 3 + 1], pal[i * 3 + 2], trns[i]])
-                                .flatten()
-                                .collect(),
-                        )
-                        .into(),
-                        None => ImageRgb8::from_raw(
-                            size,
-                            buf.into_iter()
-                                .map(|i| i as usize)
-                                .map(|i| [pal[i * 3], pal[i * 3 + 1], pal[i * 3 + 2]])
-                                .flatten()
-                                .collect(),
-                        )
-                        .into(),
-                    }
-                }
+                ColorType::Indexed => unreachable!(),
             }),
             BitDepth::Sixteen => {
                 let buf = buf
@@ -207,6 +217,7 @@ impl DynImage {
             Self::Rgba8(img) => img.premultiply(),
             Self::Rgba16(img) => img.premultiply(),
             Self::Rgba32F(img) => img.premultiply(),
+            Self::Indexed(img) => img.premultiply(),
             _ => {}
         }
     }
@@ -235,3 +246,4 @@ impl_from!(ImageRgb32F, Rgb32F);
 impl_from!(ImageRgba8, Rgba8);
 impl_from!(ImageRgba16, Rgba16);
 impl_from!(ImageRgba32F, Rgba32F);
+impl_from!(ImageIndexed, Indexed);
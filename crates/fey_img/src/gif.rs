@@ -0,0 +1,219 @@
+use crate::{ImageError, ImageRgba8};
+use fey_color::Rgba8;
+use fey_grid::Grid;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Pixels with alpha below this are written as transparent, the rest as opaque.
+const ALPHA_THRESHOLD: u8 = 128;
+
+/// Largest LZW code a GIF can use.
+const MAX_CODE: u16 = 4095;
+
+/// Save frames of an animation as a looping GIF.
+///
+/// Each frame is paired with how long it is shown in seconds. All frames must be the same size.
+/// GIFs only support fully transparent or opaque pixels, and at most 255 colors per frame;
+/// frames with more colors are reduced to a fixed palette.
+pub fn save_gif<'a, W, S>(
+    mut w: W,
+    frames: impl IntoIterator<Item = (&'a ImageRgba8<S>, f32)>,
+) -> Result<(), ImageError>
+where
+    W: Write,
+    S: AsRef<[u8]> + 'a,
+{
+    let mut frames = frames.into_iter().peekable();
+    let size = frames
+        .peek()
+        .ok_or(ImageError::InvalidGif("there must be at least one frame"))?
+        .0
+        .size();
+    let (Ok(width), Ok(height)) = (u16::try_from(size.x), u16::try_from(size.y)) else {
+        return Err(ImageError::InvalidGif("frames must be at most 65535x65535"));
+    };
+
+    // header and logical screen, without a global color table
+    w.write_all(b"GIF89a")?;
+    w.write_all(&width.to_le_bytes())?;
+    w.write_all(&height.to_le_bytes())?;
+    w.write_all(&[0, 0, 0])?;
+
+    // loop forever
+    w.write_all(b"\x21\xFF\x0BNETSCAPE2.0\x03\x01\x00\x00\x00")?;
+
+    for (image, duration) in frames {
+        if image.size() != size {
+            return Err(ImageError::InvalidGif("frames must all be the same size"));
+        }
+        let (palette, indices) = palettize(image.pixels());
+
+        // graphic control: clear to transparent between frames, and index 0 is transparent
+        let delay = ((duration * 100.0).round() as u16).max(2);
+        w.write_all(&[0x21, 0xF9, 0x04, (2 << 2) | 1])?;
+        w.write_all(&delay.to_le_bytes())?;
+        w.write_all(&[0, 0])?;
+
+        // image descriptor with a local color table
+        let bits = (palette.len().max(2) as u32)
+            .next_power_of_two()
+            .trailing_zeros() as u8;
+        w.write_all(&[0x2C, 0, 0, 0, 0])?;
+        w.write_all(&width.to_le_bytes())?;
+        w.write_all(&height.to_le_bytes())?;
+        w.write_all(&[0x80 | (bits - 1)])?;
+        for i in 0..(1usize << bits) {
+            let c = palette.get(i).copied().unwrap_or(Rgba8::TRANSPARENT);
+            w.write_all(&[c.r, c.g, c.b])?;
+        }
+
+        // compressed pixels, in sub-blocks of up to 255 bytes
+        let min_code_size = bits.max(2);
+        w.write_all(&[min_code_size])?;
+        for block in lzw_encode(&indices, min_code_size).chunks(255) {
+            w.write_all(&[block.len() as u8])?;
+            w.write_all(block)?;
+        }
+        w.write_all(&[0])?;
+    }
+
+    w.write_all(&[0x3B])?;
+    Ok(())
+}
+
+/// Save frames of an animation as a looping GIF file. See [`save_gif`].
+#[inline]
+pub fn save_gif_to_file<'a, P, S>(
+    path: P,
+    frames: impl IntoIterator<Item = (&'a ImageRgba8<S>, f32)>,
+) -> Result<(), ImageError>
+where
+    P: AsRef<Path>,
+    S: AsRef<[u8]> + 'a,
+{
+    save_gif(BufWriter::new(File::create(path)?), frames)
+}
+
+/// Build a palette for the pixels, where index `0` is transparent, and map each pixel to it.
+fn palettize(pixels: &[Rgba8]) -> (Vec<Rgba8>, Vec<u8>) {
+    let opaque = |px: Rgba8| Rgba8::new(px.r, px.g, px.b, 255);
+
+    // use the exact colors if there are few enough of them
+    let mut palette = vec![Rgba8::TRANSPARENT];
+    let mut lookup = HashMap::new();
+    let mut exact = true;
+    for &px in pixels.iter().filter(|px| px.a >= ALPHA_THRESHOLD) {
+        let px = opaque(px);
+        if let Entry::Vacant(entry) = lookup.entry(px) {
+            if palette.len() == 256 {
+                exact = false;
+                break;
+            }
+            entry.insert(palette.len() as u8);
+            palette.push(px);
+        }
+    }
+    if exact {
+        let indices = pixels
+            .iter()
+            .map(|&px| match px.a >= ALPHA_THRESHOLD {
+                true => lookup[&opaque(px)],
+                false => 0,
+            })
+            .collect();
+        return (palette, indices);
+    }
+
+    // otherwise fall back on a 6x7x6 color cube
+    let levels = |n: u8| (0..n).map(move |i| (i as u32 * 255 / (n as u32 - 1)) as u8);
+    let palette = std::iter::once(Rgba8::TRANSPARENT)
+        .chain(levels(6).flat_map(|r| {
+            levels(7).flat_map(move |g| levels(6).map(move |b| Rgba8::new(r, g, b, 255)))
+        }))
+        .collect();
+    let step = |c: u8, n: u32| (c as u32 * (n - 1) + 127) / 255;
+    let indices = pixels
+        .iter()
+        .map(|&px| match px.a >= ALPHA_THRESHOLD {
+            true => (1 + step(px.r, 6) * 42 + step(px.g, 7) * 6 + step(px.b, 6)) as u8,
+            false => 0,
+        })
+        .collect();
+    (palette, indices)
+}
+
+/// Compress palette indices with GIF's variable-width LZW.
+fn lzw_encode(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+    let clear = 1u16 << min_code_size;
+    let end = clear + 1;
+    let mut out = BitWriter::default();
+    let mut codes: HashMap<(u16, u8), u16> = HashMap::new();
+    let mut next = end + 1;
+    let mut width = min_code_size + 1;
+
+    out.write(clear, width);
+    let mut iter = indices.iter().copied();
+    let Some(first) = iter.next() else {
+        out.write(end, width);
+        return out.finish();
+    };
+    let mut prefix = first as u16;
+    for idx in iter {
+        if let Some(&code) = codes.get(&(prefix, idx)) {
+            prefix = code;
+            continue;
+        }
+        out.write(prefix, width);
+        if next <= MAX_CODE {
+            codes.insert((prefix, idx), next);
+            if next == 1 << width {
+                width += 1;
+            }
+            next += 1;
+        } else {
+            out.write(clear, width);
+            codes.clear();
+            next = end + 1;
+            width = min_code_size + 1;
+        }
+        prefix = idx as u16;
+    }
+    out.write(prefix, width);
+
+    // the decoder adds one more code after reading the last prefix
+    if next == 1 << width && width < 12 {
+        width += 1;
+    }
+    out.write(end, width);
+    out.finish()
+}
+
+/// Packs codes least-significant bit first.
+#[derive(Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    acc: u32,
+    len: u8,
+}
+
+impl BitWriter {
+    fn write(&mut self, code: u16, width: u8) {
+        self.acc |= (code as u32) << self.len;
+        self.len += width;
+        while self.len >= 8 {
+            self.bytes.push(self.acc as u8);
+            self.acc >>= 8;
+            self.len -= 8;
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.len > 0 {
+            self.bytes.push(self.acc as u8);
+        }
+        self.bytes
+    }
+}
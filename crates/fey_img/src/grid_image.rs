@@ -0,0 +1,53 @@
+use crate::{Image, Pixel};
+use fey_color::Grey;
+use fey_grid::{Grid, VecGrid};
+
+/// Conversions between grids of values and greyscale images, so heightmaps and masks can be
+/// authored in image editors and round-tripped.
+///
+/// ```ignore
+/// let img = DynImage::load_png_from_file("heights.png")?.to_rgba8();
+/// let heights = VecGrid::<u8>::from_image_channel(&img, 0);
+/// heights.to_image().save_png_to_file("heights_copy.png")?;
+/// ```
+pub trait GridImage: Sized {
+    /// The greyscale pixel type the grid converts to.
+    type Pixel: Pixel;
+
+    /// Create a grid from one channel of an image, such as `0` for red or `3` for alpha.
+    /// Panics if the image doesn't have the channel.
+    fn from_image_channel<Px, S>(img: &Image<Px, S>, channel: usize) -> Self
+    where
+        Px: Pixel<Channel = <Self::Pixel as Pixel>::Channel>,
+        S: AsRef<[Px::Channel]>;
+
+    /// Create a greyscale image where each pixel is the value of its cell.
+    fn to_image(&self) -> Image<Self::Pixel>;
+}
+
+impl<C: Copy> GridImage for VecGrid<C>
+where
+    Grey<C>: Pixel<Channel = C>,
+{
+    type Pixel = Grey<C>;
+
+    fn from_image_channel<Px, S>(img: &Image<Px, S>, channel: usize) -> Self
+    where
+        Px: Pixel<Channel = C>,
+        S: AsRef<[C]>,
+    {
+        assert!(
+            channel < Px::NUM_CHANNELS,
+            "image only has {} channels",
+            Px::NUM_CHANNELS
+        );
+        VecGrid::new_from(img.size(), |p| {
+            img.get(p.x, p.y).unwrap().as_slice()[channel]
+        })
+    }
+
+    #[inline]
+    fn to_image(&self) -> Image<Grey<C>> {
+        Image::from_raw(self.size(), self.as_slice().to_vec())
+    }
+}
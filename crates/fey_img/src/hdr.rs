@@ -0,0 +1,166 @@
+use crate::{DynImage, ImageError, ImageRgba32F};
+use fey_math::vec2;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// The most pixels an HDR image can have, which keeps a corrupt header from allocating
+/// gigabytes of memory.
+const MAX_PIXELS: usize = 1 << 26;
+
+impl DynImage {
+    /// Load a Radiance HDR (`.hdr`) image. This will always return a `DynImage::Rgba32F`
+    /// holding linear color values, which can go above `1.0`.
+    pub fn load_hdr(mut r: impl Read) -> Result<Self, ImageError> {
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)?;
+        decode_hdr(&bytes).map(Self::Rgba32F)
+    }
+
+    /// Load a Radiance HDR image from file.
+    #[inline]
+    pub fn load_hdr_from_file(path: impl AsRef<Path>) -> Result<Self, ImageError> {
+        Self::load_hdr(BufReader::new(File::open(path)?))
+    }
+
+    /// Load a Radiance HDR image from in-memory bytes.
+    #[inline]
+    pub fn load_hdr_from_memory(bytes: &[u8]) -> Result<Self, ImageError> {
+        decode_hdr(bytes).map(Self::Rgba32F)
+    }
+}
+
+fn decode_hdr(bytes: &[u8]) -> Result<ImageRgba32F, ImageError> {
+    let mut lines = bytes.split(|&b| b == b'\n');
+    let mut pos = 0;
+    let mut next_line = || {
+        let line = lines.next()?;
+        pos += line.len() + 1;
+        Some(line)
+    };
+
+    // header, ending with an empty line
+    if !next_line().is_some_and(|l| l.starts_with(b"#?")) {
+        return Err(ImageError::InvalidHdr("missing #? signature"));
+    }
+    let mut exposure = 1.0;
+    loop {
+        let line = next_line().ok_or(ImageError::InvalidHdr("unterminated header"))?;
+        if line.is_empty() {
+            break;
+        }
+        if let Some(format) = line.strip_prefix(b"FORMAT=")
+            && format != b"32-bit_rle_rgbe"
+        {
+            return Err(ImageError::InvalidHdr("only the RGBE format is supported"));
+        }
+        if let Some(value) = line.strip_prefix(b"EXPOSURE=")
+            && let Some(value) = std::str::from_utf8(value)
+                .ok()
+                .and_then(|v| v.trim().parse::<f32>().ok())
+                .filter(|&v| v > 0.0)
+        {
+            exposure *= value;
+        }
+    }
+
+    // resolution, only the standard top-to-bottom, left-to-right orientation is supported
+    let res = next_line().ok_or(ImageError::InvalidHdr("missing resolution"))?;
+    let res = std::str::from_utf8(res).map_err(|_| ImageError::InvalidHdr("invalid resolution"))?;
+    let (w, h) = match res.split_whitespace().collect::<Vec<_>>()[..] {
+        ["-Y", h, "+X", w] => (w.parse::<u32>().ok(), h.parse::<u32>().ok()),
+        _ => return Err(ImageError::InvalidHdr("unsupported orientation")),
+    };
+    let (Some(w), Some(h)) = (w, h) else {
+        return Err(ImageError::InvalidHdr("invalid resolution"));
+    };
+
+    // every scanline takes at least 4 bytes, so a header claiming more than that can't be
+    // trusted, and the size must be checked before allocating anything
+    let mut data = bytes.get(pos..).unwrap_or(&[]);
+    let pixels = (w as usize)
+        .checked_mul(h as usize)
+        .filter(|&n| n <= MAX_PIXELS && w as usize <= MAX_PIXELS)
+        .ok_or(ImageError::InvalidHdr("image is too large"))?;
+    if h as usize > data.len() / 4 {
+        return Err(ImageError::InvalidHdr("truncated pixel data"));
+    }
+    let mut scanline = vec![[0u8; 4]; w as usize];
+    let mut channels = Vec::with_capacity(pixels * 4);
+    let scale = 1.0 / exposure;
+    for _ in 0..h {
+        data = read_scanline(data, &mut scanline)?;
+        channels.extend(scanline.iter().flat_map(|&rgbe| {
+            let [r, g, b] = rgbe_to_rgb(rgbe).map(|c| c * scale);
+            [r, g, b, 1.0]
+        }));
+    }
+    Ok(ImageRgba32F::from_raw(vec2(w, h), channels))
+}
+
+/// Read one scanline of RGBE pixels, returning the remaining data.
+fn read_scanline<'a>(data: &'a [u8], out: &mut [[u8; 4]]) -> Result<&'a [u8], ImageError> {
+    const TRUNCATED: ImageError = ImageError::InvalidHdr("truncated pixel data");
+    let w = out.len();
+
+    // adaptive run-length encoding, where each channel is stored separately
+    if (8..=0x7FFF).contains(&w) && data.len() >= 4 && data[0..2] == [2, 2] && data[2] & 0x80 == 0 {
+        if ((data[2] as usize) << 8 | data[3] as usize) != w {
+            return Err(ImageError::InvalidHdr("scanline width mismatch"));
+        }
+        let mut data = &data[4..];
+        for channel in 0..4 {
+            let mut x = 0;
+            while x < w {
+                let (&count, rest) = data.split_first().ok_or(TRUNCATED)?;
+                if count > 128 {
+                    let run = (count - 128) as usize;
+                    let &value = rest.first().ok_or(TRUNCATED)?;
+                    let px = out.get_mut(x..x + run).ok_or(TRUNCATED)?;
+                    px.iter_mut().for_each(|p| p[channel] = value);
+                    data = &rest[1..];
+                    x += run;
+                } else {
+                    let count = count as usize;
+                    let values = rest.get(..count).ok_or(TRUNCATED)?;
+                    let px = out.get_mut(x..x + count).ok_or(TRUNCATED)?;
+                    px.iter_mut().zip(values).for_each(|(p, &v)| p[channel] = v);
+                    data = &rest[count..];
+                    x += count;
+                }
+            }
+        }
+        return Ok(data);
+    }
+
+    // flat pixels, optionally with the old run-length encoding that repeats the previous pixel
+    let mut data = data;
+    let mut x = 0;
+    let mut shift = 0;
+    while x < w {
+        let (px, rest) = data.split_first_chunk::<4>().ok_or(TRUNCATED)?;
+        data = rest;
+        if px[0..3] == [1, 1, 1] && x > 0 {
+            let run = (px[3] as usize).checked_shl(shift).ok_or(TRUNCATED)?;
+            let prev = out[x - 1];
+            out.get_mut(x..x + run).ok_or(TRUNCATED)?.fill(prev);
+            x += run;
+            shift += 8;
+        } else {
+            out[x] = *px;
+            x += 1;
+            shift = 0;
+        }
+    }
+    Ok(data)
+}
+
+/// Convert an RGBE pixel, which shares an exponent between channels, to linear RGB.
+#[inline]
+fn rgbe_to_rgb([r, g, b, e]: [u8; 4]) -> [f32; 3] {
+    if e == 0 {
+        return [0.0; 3];
+    }
+    let f = 2f32.powi(e as i32 - (128 + 8));
+    [r, g, b].map(|c| c as f32 * f)
+}
@@ -15,6 +15,12 @@ pub enum ImageError {
     #[error("{0}")]
     Qoi(#[from] qoi::Error),
 
+    #[error("invalid GIF: {0}")]
+    InvalidGif(&'static str),
+
+    #[error("invalid HDR: {0}")]
+    InvalidHdr(&'static str),
+
     #[error("unsupported PNG bit-depth: {0}")]
     UnsupportedBitDepth(usize),
 
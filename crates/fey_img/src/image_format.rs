@@ -15,6 +15,7 @@ pub enum ImageFormat {
     Rgba8,
     Rgba16,
     Rgba32F,
+    Indexed8,
 }
 
 impl ImageFormat {
@@ -22,7 +23,7 @@ impl ImageFormat {
     #[inline]
     pub const fn num_channels(self) -> usize {
         match self {
-            Self::Grey8 | Self::Grey16 | Self::Grey32F => 1,
+            Self::Grey8 | Self::Grey16 | Self::Grey32F | Self::Indexed8 => 1,
             Self::GreyAlpha8 | Self::GreyAlpha16 | Self::GreyAlpha32F => 2,
             Self::Rgb8 | Self::Rgb16 | Self::Rgb32F => 3,
             Self::Rgba8 | Self::Rgba16 | Self::Rgba32F => 4,
@@ -33,7 +34,7 @@ impl ImageFormat {
     #[inline]
     pub const fn bit_depth(self) -> usize {
         match self {
-            Self::Grey8 | Self::GreyAlpha8 | Self::Rgb8 | Self::Rgba8 => 8,
+            Self::Grey8 | Self::GreyAlpha8 | Self::Rgb8 | Self::Rgba8 | Self::Indexed8 => 8,
             Self::Grey16 | Self::GreyAlpha16 | Self::Rgb16 | Self::Rgba16 => 16,
             Self::Grey32F | Self::GreyAlpha32F | Self::Rgb32F | Self::Rgba32F => 32,
         }
@@ -43,7 +44,7 @@ impl ImageFormat {
     #[inline]
     pub const fn byte_depth(self) -> usize {
         match self {
-            Self::Grey8 | Self::GreyAlpha8 | Self::Rgb8 | Self::Rgba8 => 1,
+            Self::Grey8 | Self::GreyAlpha8 | Self::Rgb8 | Self::Rgba8 | Self::Indexed8 => 1,
             Self::Grey16 | Self::GreyAlpha16 | Self::Rgb16 | Self::Rgba16 => 2,
             Self::Grey32F | Self::GreyAlpha32F | Self::Rgb32F | Self::Rgba32F => 4,
         }
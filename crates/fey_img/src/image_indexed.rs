@@ -0,0 +1,269 @@
+use crate::{ImageError, ImageRgba8, Pixel};
+use fey_color::Rgba8;
+use fey_grid::{Grid, GridMut, VecGrid};
+use fey_math::Vec2U;
+use png::{BitDepth, ColorType, Encoder};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+/// An image where each pixel is an 8-bit index into a palette of up to 256 colors.
+///
+/// Because the colors are only looked up when the image is converted, changing the palette
+/// recolors every pixel that uses it. This allows classic palette-cycling effects such as
+/// flowing water or flickering fire, see [`PaletteCycle`].
+///
+/// Indices without a palette entry are treated as transparent.
+#[derive(Debug, Clone)]
+pub struct ImageIndexed {
+    indices: VecGrid<u8>,
+    palette: Vec<Rgba8>,
+}
+
+impl ImageIndexed {
+    /// Create an image where every pixel is index `0`.
+    #[inline]
+    pub fn new(size: impl Into<Vec2U>, palette: Vec<Rgba8>) -> Self {
+        Self::from_grid(VecGrid::new_with(size, || 0), palette)
+    }
+
+    /// Create an image from row-major indices. Panics if there isn't one index per pixel.
+    #[inline]
+    pub fn from_raw(size: impl Into<Vec2U>, indices: Vec<u8>, palette: Vec<Rgba8>) -> Self {
+        let size = size.into();
+        assert_eq!(indices.len(), size.x as usize * size.y as usize);
+        Self::from_grid(VecGrid::with_store(size, indices), palette)
+    }
+
+    /// Create an image from a grid of indices.
+    #[inline]
+    pub fn from_grid(indices: VecGrid<u8>, palette: Vec<Rgba8>) -> Self {
+        debug_assert!(palette.len() <= 256, "palettes have at most 256 colors");
+        Self { indices, palette }
+    }
+
+    /// Size of the image.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.indices.size()
+    }
+
+    /// Width of the image.
+    #[inline]
+    pub fn width(&self) -> u32 {
+        self.indices.width()
+    }
+
+    /// Height of the image.
+    #[inline]
+    pub fn height(&self) -> u32 {
+        self.indices.height()
+    }
+
+    /// The palette index of every pixel.
+    #[inline]
+    pub fn indices(&self) -> &VecGrid<u8> {
+        &self.indices
+    }
+
+    /// Mutable access to the palette index of every pixel.
+    #[inline]
+    pub fn indices_mut(&mut self) -> &mut VecGrid<u8> {
+        &mut self.indices
+    }
+
+    /// The raw indices, one byte per pixel.
+    #[inline]
+    pub fn bytes(&self) -> &[u8] {
+        self.indices.as_slice()
+    }
+
+    /// The image's palette.
+    #[inline]
+    pub fn palette(&self) -> &[Rgba8] {
+        &self.palette
+    }
+
+    /// Mutable access to the image's palette.
+    #[inline]
+    pub fn palette_mut(&mut self) -> &mut Vec<Rgba8> {
+        &mut self.palette
+    }
+
+    /// Replace the image's palette.
+    #[inline]
+    pub fn set_palette(&mut self, palette: Vec<Rgba8>) {
+        debug_assert!(palette.len() <= 256, "palettes have at most 256 colors");
+        self.palette = palette;
+    }
+
+    /// The palette index of the pixel at `(x, y)`.
+    #[inline]
+    pub fn index(&self, x: u32, y: u32) -> Option<u8> {
+        self.indices.get(x, y).copied()
+    }
+
+    /// The color of the pixel at `(x, y)`.
+    #[inline]
+    pub fn color(&self, x: u32, y: u32) -> Option<Rgba8> {
+        self.index(x, y).map(|i| self.lookup(i))
+    }
+
+    /// The palette color at the index, or transparent if the palette has no such color.
+    #[inline]
+    pub fn lookup(&self, index: u8) -> Rgba8 {
+        self.palette
+            .get(index as usize)
+            .copied()
+            .unwrap_or(Rgba8::TRANSPARENT)
+    }
+
+    /// The index of the palette color closest to `color`. Returns `0` if the palette is empty.
+    pub fn nearest_index(&self, color: Rgba8) -> u8 {
+        let dist = |c: &Rgba8| {
+            let d = |a: u8, b: u8| (a as i32 - b as i32).pow(2);
+            d(c.r, color.r) + d(c.g, color.g) + d(c.b, color.b) + d(c.a, color.a)
+        };
+        self.palette
+            .iter()
+            .enumerate()
+            .min_by_key(|(_, c)| dist(c))
+            .map_or(0, |(i, _)| i as u8)
+    }
+
+    /// Set the pixel at `(x, y)` to the palette color closest to `color`.
+    #[inline]
+    pub fn set_color(&mut self, x: u32, y: u32, color: Rgba8) {
+        let index = self.nearest_index(color);
+        if let Some(i) = self.indices.get_mut(x, y) {
+            *i = index;
+        }
+    }
+
+    /// Convert the image to RGBA by looking up every pixel in the palette.
+    pub fn to_rgba8(&self) -> ImageRgba8 {
+        let colors: Vec<Rgba8> = (0..=255).map(|i| self.lookup(i)).collect();
+        ImageRgba8::from_raw(
+            self.size(),
+            self.bytes()
+                .iter()
+                .flat_map(|&i| {
+                    let c = colors[i as usize];
+                    [c.r, c.g, c.b, c.a]
+                })
+                .collect(),
+        )
+    }
+
+    /// Premultiply the palette's colors.
+    #[inline]
+    pub fn premultiply(&mut self) {
+        for c in &mut self.palette {
+            *c = c.premultiply();
+        }
+    }
+
+    /// Rotate a range of the palette by `steps` entries. Positive steps move each color
+    /// towards the end of the range, wrapping around to its start. The range is clipped to
+    /// the palette's length.
+    #[inline]
+    pub fn rotate_palette(&mut self, range: Range<usize>, steps: i32) {
+        rotate(&mut self.palette, range, steps);
+    }
+
+    /// Reset the palette to `base`, then apply every cycle at `time` seconds.
+    pub fn apply_cycles(&mut self, base: &[Rgba8], cycles: &[PaletteCycle], time: f32) {
+        self.palette.clear();
+        self.palette.extend_from_slice(base);
+        for cycle in cycles {
+            cycle.apply(&mut self.palette, time);
+        }
+    }
+
+    /// Save the image as an indexed PNG.
+    pub fn save_png<W: Write>(&self, w: W) -> Result<(), ImageError> {
+        let mut enc = Encoder::new(w, self.width(), self.height());
+        enc.set_depth(BitDepth::Eight);
+        enc.set_color(ColorType::Indexed);
+        let mut palette = self.palette.clone();
+        if palette.is_empty() {
+            palette.push(Rgba8::TRANSPARENT);
+        }
+        enc.set_palette(
+            palette
+                .iter()
+                .flat_map(|c| [c.r, c.g, c.b])
+                .collect::<Vec<_>>(),
+        );
+        if palette.iter().any(|c| c.a < 255) {
+            enc.set_trns(palette.iter().map(|c| c.a).collect::<Vec<_>>());
+        }
+        let mut writer = enc.write_header()?;
+        writer.write_image_data(self.bytes())?;
+        Ok(())
+    }
+
+    /// Save the image as an indexed PNG file.
+    #[inline]
+    pub fn save_png_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), ImageError> {
+        self.save_png(BufWriter::new(File::create(path)?))
+    }
+}
+
+/// Animates a range of palette entries by rotating them at a fixed rate.
+///
+/// ```ignore
+/// let base = img.palette().to_vec();
+/// let water = PaletteCycle::new(16, 23, 8.0);
+/// // every frame
+/// img.apply_cycles(&base, &[water], time.since_startup());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct PaletteCycle {
+    /// First palette index in the cycle.
+    pub start: u8,
+
+    /// Last palette index in the cycle (inclusive).
+    pub end: u8,
+
+    /// How many entries the colors move per second. Negative rates cycle in reverse.
+    pub rate: f32,
+}
+
+impl PaletteCycle {
+    /// Create a cycle over the palette indices `start..=end`.
+    #[inline]
+    pub const fn new(start: u8, end: u8, rate: f32) -> Self {
+        Self { start, end, rate }
+    }
+
+    /// The range of palette indices in the cycle.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.start as usize..self.end as usize + 1
+    }
+
+    /// How many entries the colors have moved at `time` seconds, wrapped to the cycle's length.
+    #[inline]
+    pub fn offset(&self, time: f32) -> i32 {
+        let len = self.range().len().max(1) as i32;
+        ((time * self.rate).floor() as i64).rem_euclid(len as i64) as i32
+    }
+
+    /// Rotate the cycle's range of `palette` to its state at `time` seconds.
+    #[inline]
+    pub fn apply(&self, palette: &mut [Rgba8], time: f32) {
+        rotate(palette, self.range(), self.offset(time));
+    }
+}
+
+/// Rotate a range of the palette, clipped to its length.
+fn rotate(palette: &mut [Rgba8], range: Range<usize>, steps: i32) {
+    let end = range.end.min(palette.len());
+    if let Some(colors) = palette.get_mut(range.start..end)
+        && !colors.is_empty()
+    {
+        colors.rotate_right(steps.rem_euclid(colors.len() as i32) as usize);
+    }
+}
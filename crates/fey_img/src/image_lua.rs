@@ -1,6 +1,6 @@
-use crate::{DynImage, Image, ImageFormat};
+use crate::{DynImage, Image, ImageFormat, ImageIndexed, Tonemap};
 use fey_color::{Channel, Grey, GreyAlpha, Rgb, Rgba, Rgba8, ToRgb, ToRgba, grey, grey_alpha};
-use fey_grid::{Grid, GridMut};
+use fey_grid::{Grid, GridMut, VecGrid};
 use fey_lua::{LuaModule, UserDataOf};
 use fey_math::{Numeric, Rect, RectF};
 use mlua::prelude::{LuaError, LuaResult};
@@ -59,6 +59,9 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                 ImageFormat::Rgba8 => DynImage::Rgba8(Image::new_vec(size, Rgba::TRANSPARENT)),
                 ImageFormat::Rgba16 => DynImage::Rgba16(Image::new_vec(size, Rgba::TRANSPARENT)),
                 ImageFormat::Rgba32F => DynImage::Rgba32F(Image::new_vec(size, Rgba::TRANSPARENT)),
+                ImageFormat::Indexed8 => {
+                    DynImage::Indexed(ImageIndexed::new(size, vec![Rgba::TRANSPARENT; 256]))
+                }
             })
         });
         methods.add_function("clone", |_, this: DynImageRef| Ok(this.clone()));
@@ -105,6 +108,7 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                 DynImage::Rgba8(img) => img.get(x, y).copied(),
                 DynImage::Rgba16(img) => img.get(x, y).copied().map(ToRgba::to_rgba),
                 DynImage::Rgba32F(img) => img.get(x, y).copied().map(ToRgba::to_rgba),
+                DynImage::Indexed(img) => img.color(x, y),
             }
             .ok_or_else(|| LuaError::runtime(format!("no pixel at ({x}, {y})"))))
         });
@@ -125,6 +129,7 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                     DynImage::Rgba8(img) => _ = img.set(x, y, col),
                     DynImage::Rgba16(img) => _ = img.set(x, y, col.to_rgba()),
                     DynImage::Rgba32F(img) => _ = img.set(x, y, col.to_rgba()),
+                    DynImage::Indexed(img) => img.set_color(x, y, col),
                 }
                 Ok(())
             },
@@ -150,6 +155,10 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                 DynImage::Rgba8(img) => _ = img.view_mut(x, y, w, h).fill(col),
                 DynImage::Rgba16(img) => _ = img.view_mut(x, y, w, h).fill(col.to_rgba()),
                 DynImage::Rgba32F(img) => _ = img.view_mut(x, y, w, h).fill(col.to_rgba()),
+                DynImage::Indexed(img) => {
+                    let i = img.nearest_index(col);
+                    _ = img.indices_mut().view_mut(x, y, w, h).fill(i)
+                }
             }
         }
 
@@ -186,11 +195,32 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
             dx: u32,
             dy: u32,
         ) {
+            // indexed images are drawn from and into by their colors
+            let converted;
+            let src = match src.deref() {
+                DynImage::Indexed(img) => {
+                    converted = DynImage::Rgba8(img.to_rgba8());
+                    &converted
+                }
+                src => src,
+            };
+            if let DynImage::Indexed(dst) = dst.deref_mut() {
+                let src = src.clone().to_rgba8();
+                for y in 0..h {
+                    for x in 0..w {
+                        if let Some(&col) = src.get(sx + x, sy + y) {
+                            dst.set_color(dx + x, dy + y, col);
+                        }
+                    }
+                }
+                return;
+            }
+
             macro_rules! draw {
                 ($($dst:ident = $f:ident),*) => {
                     match dst.deref_mut() {
                         $(
-                        DynImage::$dst(dst) => match src.deref() {
+                        DynImage::$dst(dst) => match src {
                             DynImage::Grey8(src) => {
                                 _ = dst
                                     .view_mut(dx, dy, w, h)
@@ -251,8 +281,10 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                                     .view_mut(dx, dy, w, h)
                                     .draw_mapped(&src.view(sx, sy, w, w), |c| c.$f())
                             }
+                            DynImage::Indexed(_) => unreachable!(),
                         }
                         )*
+                        DynImage::Indexed(_) => unreachable!(),
                     }
                 }
             }
@@ -337,9 +369,78 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M, module: bool) {
                     DynImage::Rgba32F(img) => {
                         DynImage::Rgba32F(Image::from_grid(&img.view(x, y, w, h)))
                     }
+                    DynImage::Indexed(img) => {
+                        let view = img.indices().view(x, y, w, h);
+                        let indices =
+                            VecGrid::new_from(view.size(), |p| *view.get(p.x, p.y).unwrap());
+                        DynImage::Indexed(ImageIndexed::from_grid(indices, img.palette().to_vec()))
+                    }
                 })
             },
         );
+
+        methods.add_function(
+            "tonemap",
+            |_,
+             (this, exposure, op, white): (
+                DynImageRef,
+                Option<f32>,
+                Option<BorrowedStr>,
+                Option<f32>,
+            )| {
+                let op = match op.as_deref().unwrap_or("aces") {
+                    "clamp" => Tonemap::Clamp,
+                    "reinhard" => Tonemap::Reinhard,
+                    "reinhard_extended" => Tonemap::ReinhardExtended {
+                        white: white.unwrap_or(4.0),
+                    },
+                    "aces" => Tonemap::Aces,
+                    op => {
+                        return Err(LuaError::runtime(format!(
+                            "invalid tonemap operator [{op}]"
+                        )));
+                    }
+                };
+                let exposure = exposure.unwrap_or(0.0);
+                Ok(DynImage::Rgba8(match this.deref() {
+                    DynImage::Rgb32F(img) => img.map(|p| p.to_rgba()).tonemap(exposure, op),
+                    DynImage::Rgba32F(img) => img.tonemap(exposure, op),
+                    _ => return Err(LuaError::runtime("image is not a floating point image")),
+                }))
+            },
+        );
+
+        methods.add_function("palette", |_, this: DynImageRef| {
+            Ok(match this.deref() {
+                DynImage::Indexed(img) => Some(img.palette().to_vec()),
+                _ => None,
+            })
+        });
+
+        methods.add_function(
+            "set_palette",
+            |_, (mut this, palette): (DynImageMut, Vec<Rgba8>)| {
+                let DynImage::Indexed(img) = this.deref_mut() else {
+                    return Err(LuaError::runtime("image is not indexed"));
+                };
+                if palette.len() > 256 {
+                    return Err(LuaError::runtime("palettes have at most 256 colors"));
+                }
+                img.set_palette(palette);
+                Ok(())
+            },
+        );
+
+        methods.add_function(
+            "rotate_palette",
+            |_, (mut this, start, end, steps): (DynImageMut, usize, usize, i32)| {
+                let DynImage::Indexed(img) = this.deref_mut() else {
+                    return Err(LuaError::runtime("image is not indexed"));
+                };
+                img.rotate_palette(start..end + 1, steps);
+                Ok(())
+            },
+        );
     }
 }
 
@@ -358,6 +459,7 @@ impl ImageFormat {
             Self::Rgba8 => "rgba8",
             Self::Rgba16 => "rgba16",
             Self::Rgba32F => "rgba32f",
+            Self::Indexed8 => "indexed8",
         }
     }
 }
@@ -379,6 +481,7 @@ impl FromLua for ImageFormat {
             "rgba8" => Self::Rgba8,
             "rgba16" => Self::Rgba16,
             "rgba32f" => Self::Rgba32F,
+            "indexed8" => Self::Indexed8,
             s => return Err(LuaError::runtime(format!("invalid image format [{s}]"))),
         })
     }
@@ -1,23 +1,36 @@
 //! Image encoding, decoding, and manipulation.
 
+mod adjust;
+mod bleed;
 mod dyn_image;
+mod gif;
+mod grid_image;
+mod hdr;
 mod image;
 mod image_error;
 mod image_format;
+mod image_indexed;
 mod pixel;
 mod png;
 mod qoi_impl;
+mod tonemap;
+mod trace;
 
 #[cfg(feature = "lua")]
 mod image_lua;
 
+pub use adjust::*;
 pub use dyn_image::*;
+pub use gif::*;
+pub use grid_image::*;
 pub use image::*;
 pub use image_error::*;
 pub use image_format::*;
+pub use image_indexed::*;
 pub use pixel::*;
 pub use png::*;
 pub use qoi_impl::*;
+pub use tonemap::*;
 
 #[cfg(feature = "lua")]
 pub use image_lua::*;
@@ -0,0 +1,82 @@
+use crate::{Image, ImageRgba8};
+use fey_color::{Rgba, Rgba32F};
+use fey_grid::Grid;
+use serde::{Deserialize, Serialize};
+
+/// An operator that maps HDR colors, which can be brighter than `1.0`, into the displayable
+/// `0-1` range.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum Tonemap {
+    /// Clip values above `1.0`.
+    Clamp,
+
+    /// Reinhard's operator `c / (1 + c)`, which compresses highlights smoothly but can look
+    /// washed out.
+    Reinhard,
+
+    /// Extended Reinhard, where values at or above the white point map to `1.0`.
+    ReinhardExtended { white: f32 },
+
+    /// A fit of the ACES filmic curve, with higher contrast and saturated highlights.
+    #[default]
+    Aces,
+}
+
+impl Tonemap {
+    /// Map a linear HDR channel value into the `0-1` range.
+    #[inline]
+    pub fn apply(self, c: f32) -> f32 {
+        let c = c.max(0.0);
+        let mapped = match self {
+            Self::Clamp => c,
+            Self::Reinhard => c / (1.0 + c),
+            Self::ReinhardExtended { white } => {
+                let white = white.max(f32::EPSILON);
+                c * (1.0 + c / (white * white)) / (1.0 + c)
+            }
+            // Krzysztof Narkowicz's approximation
+            Self::Aces => (c * (2.51 * c + 0.03)) / (c * (2.43 * c + 0.59) + 0.14),
+        };
+        mapped.clamp(0.0, 1.0)
+    }
+}
+
+impl<S: AsRef<[f32]>> Image<Rgba32F, S> {
+    /// Tone-map the linear HDR image into a displayable sRGB image. The image is first
+    /// brightened or darkened by `exposure` stops, where each stop doubles the brightness.
+    pub fn tonemap(&self, exposure: f32, op: Tonemap) -> ImageRgba8 {
+        let scale = exposure.exp2();
+        let channels = self.pixels().iter().flat_map(|&Rgba { r, g, b, a }| {
+            let [r, g, b] = [r, g, b].map(|c| to_u8(encode_srgb(op.apply(c * scale))));
+            [r, g, b, to_u8(a.clamp(0.0, 1.0))]
+        });
+        ImageRgba8::from_raw(self.size(), channels.collect())
+    }
+}
+
+impl<S: AsMut<[f32]>> Image<Rgba32F, S> {
+    /// Brighten or darken the image by `stops`, where each stop doubles the brightness.
+    pub fn apply_exposure(&mut self, stops: f32) {
+        let scale = stops.exp2();
+        for p in self.pixels_mut() {
+            p.r *= scale;
+            p.g *= scale;
+            p.b *= scale;
+        }
+    }
+}
+
+/// Encode a linear `0-1` value with the sRGB transfer function.
+#[inline]
+fn encode_srgb(c: f32) -> f32 {
+    if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+#[inline]
+fn to_u8(c: f32) -> u8 {
+    (c * 255.0).round() as u8
+}
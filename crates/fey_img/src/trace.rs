@@ -0,0 +1,108 @@
+use crate::{Image, Pixel};
+use fey_grid::Grid;
+use fey_math::{Numeric, PolylineF, Vec2, vec2};
+use std::collections::HashMap;
+
+impl<Px: Pixel, S: AsRef<[Px::Channel]>> Image<Px, S> {
+    /// Trace the outlines of the regions of pixels where `inside` returns true, using marching
+    /// squares. Each outline is returned as a closed polyline (its first and last points are
+    /// the same) in pixel coordinates, wound clockwise around solid regions and
+    /// counter-clockwise around holes.
+    ///
+    /// Outlines follow pixel edges, but cut diagonally across corners. Use
+    /// [`Polyline::simplify`](fey_math::Polyline::simplify) to reduce their point count.
+    pub fn trace_outlines(&self, mut inside: impl FnMut(&Px) -> bool) -> Vec<PolylineF> {
+        let (w, h) = (self.width() as i32, self.height() as i32);
+        let mask: Vec<bool> = self.pixels().iter().map(&mut inside).collect();
+        let get = |x: i32, y: i32| x >= 0 && y >= 0 && x < w && y < h && mask[(y * w + x) as usize];
+
+        // segments map from their start to their end point, in doubled coordinates so that
+        // the edge midpoints they connect are integers
+        let mut segments = HashMap::new();
+        for cy in -1..h {
+            for cx in -1..w {
+                let corners = [
+                    get(cx, cy),
+                    get(cx + 1, cy),
+                    get(cx + 1, cy + 1),
+                    get(cx, cy + 1),
+                ];
+                let case = corners
+                    .iter()
+                    .fold(0, |case, &inside| (case << 1) | inside as usize);
+                let base = vec2(cx * 2 + 1, cy * 2 + 1);
+                for &(a, b, corner) in CASES[case] {
+                    let (a, b) = (base + EDGES[a], base + EDGES[b]);
+                    let c = base + CORNERS[corner];
+                    // orient the segment so that the inside is on its right
+                    let right = (b - a).cross(c - a) > 0;
+                    let (a, b) = if right == corners[corner] {
+                        (a, b)
+                    } else {
+                        (b, a)
+                    };
+                    segments.insert(a, b);
+                }
+            }
+        }
+
+        let mut outlines = Vec::new();
+        while let Some(&start) = segments.keys().next() {
+            let mut outline = PolylineF::new();
+            let mut p = start;
+            outline.push(p.to_f32() / 2.0);
+            while let Some(next) = segments.remove(&p) {
+                outline.push(next.to_f32() / 2.0);
+                p = next;
+            }
+            outlines.push(outline);
+        }
+        outlines
+    }
+}
+
+/// Offsets of the top, right, bottom and left edge midpoints from a cell's top-left corner.
+const EDGES: [Vec2<i32>; 4] = [
+    Vec2 { x: 1, y: 0 },
+    Vec2 { x: 2, y: 1 },
+    Vec2 { x: 1, y: 2 },
+    Vec2 { x: 0, y: 1 },
+];
+
+/// Offsets of the top-left, top-right, bottom-right and bottom-left corners of a cell.
+const CORNERS: [Vec2<i32>; 4] = [
+    Vec2 { x: 0, y: 0 },
+    Vec2 { x: 2, y: 0 },
+    Vec2 { x: 2, y: 2 },
+    Vec2 { x: 0, y: 2 },
+];
+
+const T: usize = 0;
+const R: usize = 1;
+const B: usize = 2;
+const L: usize = 3;
+const TL: usize = 0;
+const TR: usize = 1;
+const BR: usize = 2;
+const BL: usize = 3;
+
+/// For each combination of inside corners (top-left is the highest bit), the edges each segment
+/// connects and the corner it cuts off, which is used to orient it. Saddles are kept separate.
+const CASES: [&[(usize, usize, usize)]; 16] = [
+    &[],
+    &[(L, B, BL)],
+    &[(B, R, BR)],
+    &[(L, R, BL)],
+    &[(T, R, TR)],
+    &[(L, B, BL), (T, R, TR)],
+    &[(T, B, TR)],
+    &[(T, L, TL)],
+    &[(T, L, TL)],
+    &[(T, B, TL)],
+    &[(T, L, TL), (B, R, BR)],
+    &[(T, R, TR)],
+    &[(L, R, TL)],
+    &[(B, R, BR)],
+    &[(L, B, BL)],
+    &[],
+];
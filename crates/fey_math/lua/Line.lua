@@ -117,13 +117,20 @@ function methods.right_norm(self) end
 ---@nodiscard
 function methods.project_onto_axis(self, axis) end
 
----Project the point onto this line.
+---Project the point onto the infinite line passing through `start` and `end`.
 ---@param self Line
 ---@param p Vec2
 ---@return Vec2
 ---@nodiscard
 function methods.project_point(self, p) end
 
+---Project the point onto this line, clamped between `start` and `end`, giving the nearest point on the line segment.
+---@param self Line
+---@param p Vec2
+---@return Vec2
+---@nodiscard
+function methods.project_point_clamped(self, p) end
+
 ---Check if the ray hits this line.
 ---@param self Line
 ---@param ray Ray
@@ -2,8 +2,8 @@ use crate::{
     Angle, Direction, Float, Line, Num, Polygon, Polygonal, Projection, Radians, Ray, RayHit, Rect,
     Shape, Vec2, extract_on, impl_approx, impl_casts, line, overlaps_on, rect, vec2,
 };
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 use serde::{Deserialize, Serialize};
-use std::ops::{Add, AddAssign, Sub, SubAssign};
 
 pub type CircleF = Circle<f32>;
 pub type CircleI = Circle<i32>;
@@ -113,7 +113,7 @@ impl<T: Float> Circle<T> {
     ) -> impl Iterator<Item = Vec2<T>> + '_ {
         let step = Radians(T::TAU / count);
         let mut angle = angle.to_radians();
-        std::iter::from_fn(move || {
+        core::iter::from_fn(move || {
             (count > T::ZERO).then(|| {
                 let p = self.center + angle.norm() * self.radius;
                 angle = angle + step;
@@ -123,6 +123,29 @@ impl<T: Float> Circle<T> {
         })
     }
 
+    /// Iterate over `count + 1` points evenly distributed along an arc of the circle's
+    /// perimeter, starting at `angle` and sweeping `sweep` radians. Positive sweeps go in the
+    /// direction of increasing angle, which is clockwise when the y-axis points down.
+    pub fn iter_arc_points_n(
+        &self,
+        count: T,
+        angle: impl Angle<T>,
+        sweep: T,
+    ) -> impl Iterator<Item = Vec2<T>> + '_ {
+        let count = T::max(count, T::ONE);
+        let step = Radians(sweep / count);
+        let mut angle = angle.to_radians();
+        let mut remaining = count + T::ONE;
+        core::iter::from_fn(move || {
+            (remaining > T::ZERO).then(|| {
+                let p = self.center + angle.norm() * self.radius;
+                angle += step;
+                remaining -= T::ONE;
+                p
+            })
+        })
+    }
+
     /// Iterate over points along the perimeter of the circle, each `seg_len` apart.
     #[inline]
     pub fn iter_hull_points(
@@ -164,7 +187,7 @@ impl<T: Float> Circle<T> {
         let step = Radians(T::TAU / count);
         let mut angle = angle.to_radians();
         let mut prev = self.center + angle.norm() * self.radius;
-        std::iter::from_fn(move || {
+        core::iter::from_fn(move || {
             (count > T::ZERO).then(|| {
                 angle = angle + step;
                 let curr = self.center + angle.norm() * self.radius;
@@ -369,7 +392,12 @@ impl<T: Float> Shape<T> for Circle<T> {
         let sqr_rad = (self.radius + circ.radius) * (self.radius + circ.radius);
         (sqr_dist < sqr_rad).then(|| {
             let d = T::sqrt(sqr_rad) - T::sqrt(sqr_dist);
-            offset.norm() * d
+            // circles with the same center can be pushed out in any direction
+            if sqr_dist == T::ZERO {
+                Vec2::Y_AXIS * d
+            } else {
+                offset.norm() * d
+            }
         })
     }
 
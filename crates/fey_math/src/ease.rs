@@ -0,0 +1,327 @@
+//! Robert Penner's easing functions, which map a linear progress `t` from `0` to `1` onto a
+//! curve that also starts at `0` and ends at `1`. Some, such as [`back_out`] and
+//! [`elastic_out`], overshoot in between.
+//!
+//! Use [`Ease`] to refer to them by name, such as from data files.
+
+#[cfg(not(feature = "std"))]
+use crate::LibmExt;
+use core::f32::consts::{PI, TAU};
+use serde::{Deserialize, Serialize};
+
+/// An easing function.
+pub type EaseFn = fn(f32) -> f32;
+
+/// No easing.
+#[inline]
+pub fn linear(t: f32) -> f32 {
+    t
+}
+
+#[inline]
+pub fn quad_in(t: f32) -> f32 {
+    t * t
+}
+
+#[inline]
+pub fn quad_out(t: f32) -> f32 {
+    1.0 - quad_in(1.0 - t)
+}
+
+#[inline]
+pub fn quad_in_out(t: f32) -> f32 {
+    in_out(t, quad_in)
+}
+
+#[inline]
+pub fn cubic_in(t: f32) -> f32 {
+    t * t * t
+}
+
+#[inline]
+pub fn cubic_out(t: f32) -> f32 {
+    1.0 - cubic_in(1.0 - t)
+}
+
+#[inline]
+pub fn cubic_in_out(t: f32) -> f32 {
+    in_out(t, cubic_in)
+}
+
+#[inline]
+pub fn quart_in(t: f32) -> f32 {
+    t * t * t * t
+}
+
+#[inline]
+pub fn quart_out(t: f32) -> f32 {
+    1.0 - quart_in(1.0 - t)
+}
+
+#[inline]
+pub fn quart_in_out(t: f32) -> f32 {
+    in_out(t, quart_in)
+}
+
+#[inline]
+pub fn quint_in(t: f32) -> f32 {
+    t * t * t * t * t
+}
+
+#[inline]
+pub fn quint_out(t: f32) -> f32 {
+    1.0 - quint_in(1.0 - t)
+}
+
+#[inline]
+pub fn quint_in_out(t: f32) -> f32 {
+    in_out(t, quint_in)
+}
+
+#[inline]
+pub fn sine_in(t: f32) -> f32 {
+    1.0 - (t * PI * 0.5).cos()
+}
+
+#[inline]
+pub fn sine_out(t: f32) -> f32 {
+    (t * PI * 0.5).sin()
+}
+
+#[inline]
+pub fn sine_in_out(t: f32) -> f32 {
+    -((PI * t).cos() - 1.0) * 0.5
+}
+
+#[inline]
+pub fn expo_in(t: f32) -> f32 {
+    if t <= 0.0 {
+        0.0
+    } else {
+        2f32.powf(10.0 * t - 10.0)
+    }
+}
+
+#[inline]
+pub fn expo_out(t: f32) -> f32 {
+    1.0 - expo_in(1.0 - t)
+}
+
+#[inline]
+pub fn expo_in_out(t: f32) -> f32 {
+    in_out(t, expo_in)
+}
+
+#[inline]
+pub fn circ_in(t: f32) -> f32 {
+    1.0 - (1.0 - t * t).max(0.0).sqrt()
+}
+
+#[inline]
+pub fn circ_out(t: f32) -> f32 {
+    1.0 - circ_in(1.0 - t)
+}
+
+#[inline]
+pub fn circ_in_out(t: f32) -> f32 {
+    in_out(t, circ_in)
+}
+
+/// Pulls back before moving forward.
+#[inline]
+pub fn back_in(t: f32) -> f32 {
+    const C1: f32 = 1.70158;
+    const C3: f32 = C1 + 1.0;
+    C3 * t * t * t - C1 * t * t
+}
+
+/// Overshoots the end before settling back onto it.
+#[inline]
+pub fn back_out(t: f32) -> f32 {
+    1.0 - back_in(1.0 - t)
+}
+
+#[inline]
+pub fn back_in_out(t: f32) -> f32 {
+    in_out(t, back_in)
+}
+
+/// Winds up with growing springy oscillations.
+#[inline]
+pub fn elastic_in(t: f32) -> f32 {
+    if t <= 0.0 || t >= 1.0 {
+        return t.clamp(0.0, 1.0);
+    }
+    -(2f32.powf(10.0 * t - 10.0)) * ((t * 10.0 - 10.75) * TAU / 3.0).sin()
+}
+
+/// Springs past the end and oscillates before settling onto it.
+#[inline]
+pub fn elastic_out(t: f32) -> f32 {
+    1.0 - elastic_in(1.0 - t)
+}
+
+#[inline]
+pub fn elastic_in_out(t: f32) -> f32 {
+    in_out(t, elastic_in)
+}
+
+#[inline]
+pub fn bounce_in(t: f32) -> f32 {
+    1.0 - bounce_out(1.0 - t)
+}
+
+/// Bounces off the end a few times before settling onto it.
+#[inline]
+pub fn bounce_out(t: f32) -> f32 {
+    const N: f32 = 7.5625;
+    const D: f32 = 2.75;
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+#[inline]
+pub fn bounce_in_out(t: f32) -> f32 {
+    in_out(t, bounce_in)
+}
+
+/// Ease in over the first half with `f`, and out over the second half with its mirror.
+#[inline]
+fn in_out(t: f32, f: EaseFn) -> f32 {
+    if t < 0.5 {
+        f(t * 2.0) * 0.5
+    } else {
+        1.0 - f((1.0 - t) * 2.0) * 0.5
+    }
+}
+
+/// An easing function, by name. Serializes in snake case, such as `"quad_out"`.
+#[derive(
+    Debug, Default, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Ease {
+    #[default]
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    QuartIn,
+    QuartOut,
+    QuartInOut,
+    QuintIn,
+    QuintOut,
+    QuintInOut,
+    SineIn,
+    SineOut,
+    SineInOut,
+    ExpoIn,
+    ExpoOut,
+    ExpoInOut,
+    CircIn,
+    CircOut,
+    CircInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+}
+
+impl Ease {
+    /// Every easing function.
+    pub const ALL: [Self; 31] = [
+        Self::Linear,
+        Self::QuadIn,
+        Self::QuadOut,
+        Self::QuadInOut,
+        Self::CubicIn,
+        Self::CubicOut,
+        Self::CubicInOut,
+        Self::QuartIn,
+        Self::QuartOut,
+        Self::QuartInOut,
+        Self::QuintIn,
+        Self::QuintOut,
+        Self::QuintInOut,
+        Self::SineIn,
+        Self::SineOut,
+        Self::SineInOut,
+        Self::ExpoIn,
+        Self::ExpoOut,
+        Self::ExpoInOut,
+        Self::CircIn,
+        Self::CircOut,
+        Self::CircInOut,
+        Self::BackIn,
+        Self::BackOut,
+        Self::BackInOut,
+        Self::ElasticIn,
+        Self::ElasticOut,
+        Self::ElasticInOut,
+        Self::BounceIn,
+        Self::BounceOut,
+        Self::BounceInOut,
+    ];
+
+    /// The easing function.
+    #[inline]
+    pub const fn func(self) -> EaseFn {
+        match self {
+            Self::Linear => linear,
+            Self::QuadIn => quad_in,
+            Self::QuadOut => quad_out,
+            Self::QuadInOut => quad_in_out,
+            Self::CubicIn => cubic_in,
+            Self::CubicOut => cubic_out,
+            Self::CubicInOut => cubic_in_out,
+            Self::QuartIn => quart_in,
+            Self::QuartOut => quart_out,
+            Self::QuartInOut => quart_in_out,
+            Self::QuintIn => quint_in,
+            Self::QuintOut => quint_out,
+            Self::QuintInOut => quint_in_out,
+            Self::SineIn => sine_in,
+            Self::SineOut => sine_out,
+            Self::SineInOut => sine_in_out,
+            Self::ExpoIn => expo_in,
+            Self::ExpoOut => expo_out,
+            Self::ExpoInOut => expo_in_out,
+            Self::CircIn => circ_in,
+            Self::CircOut => circ_out,
+            Self::CircInOut => circ_in_out,
+            Self::BackIn => back_in,
+            Self::BackOut => back_out,
+            Self::BackInOut => back_in_out,
+            Self::ElasticIn => elastic_in,
+            Self::ElasticOut => elastic_out,
+            Self::ElasticInOut => elastic_in_out,
+            Self::BounceIn => bounce_in,
+            Self::BounceOut => bounce_out,
+            Self::BounceInOut => bounce_in_out,
+        }
+    }
+
+    /// Ease the value.
+    #[inline]
+    pub fn apply(self, t: f32) -> f32 {
+        self.func()(t)
+    }
+}
@@ -0,0 +1,173 @@
+//! Shape generators and invariant checks for fuzzing and property testing the overlap,
+//! extraction, and raycasting code.
+//!
+//! Enabled by the `fuzzing` feature. Each check panics with a description of the broken
+//! invariant, so they can be called from fuzz targets and property tests alike:
+//!
+//! ```ignore
+//! fuzz_target!(|data: &[u8]| fey_math::fuzzing::fuzz_overlap(data));
+//! ```
+
+use crate::{
+    Circle, DynShape, DynShapeF, Polygon, Quad, Ray, RayF, Rect, Shape, Triangle, Vec2F, vec2,
+};
+use arbitrary::{Result, Unstructured};
+
+/// How far apart shapes may be after extraction, and how far off raycast hits may be, to
+/// allow for rounding.
+pub const EPSILON: f32 = 1e-3;
+
+/// The largest coordinate generated, which keeps rounding errors small enough to check.
+pub const MAX_COORD: f32 = 100.0;
+
+/// Generate a coordinate from `-MAX_COORD` to `MAX_COORD`, in steps of `0.1` so that shapes
+/// often share points and edges.
+pub fn coord(u: &mut Unstructured) -> Result<f32> {
+    let max = (MAX_COORD * 10.0) as i32;
+    Ok(u.int_in_range(-max..=max)? as f32 / 10.0)
+}
+
+/// Generate a point.
+pub fn point(u: &mut Unstructured) -> Result<Vec2F> {
+    Ok(vec2(coord(u)?, coord(u)?))
+}
+
+/// Generate a length from `0` to `MAX_COORD / 2`, which can be zero.
+pub fn length(u: &mut Unstructured) -> Result<f32> {
+    Ok(coord(u)?.abs() / 2.0)
+}
+
+/// Generate a convex polygon by placing points around a circle, in clockwise order. Points
+/// can land on top of each other, and the radius can be zero, so it may be degenerate.
+pub fn convex_points(u: &mut Unstructured, min: usize, max: usize) -> Result<Vec<Vec2F>> {
+    let center = point(u)?;
+    let radius = length(u)?;
+    let count = u.int_in_range(min..=max)?;
+    let mut angles = (0..count)
+        .map(|_| Ok(u.int_in_range(0..=359u16)? as f32))
+        .collect::<Result<Vec<_>>>()?;
+    angles.sort_by(f32::total_cmp);
+    Ok(angles
+        .into_iter()
+        .map(|deg| {
+            let (sin, cos) = deg.to_radians().sin_cos();
+            center + vec2(cos, sin) * radius
+        })
+        .collect())
+}
+
+/// Generate a circle, triangle, rectangle, quad, or polygon.
+pub fn shape(u: &mut Unstructured) -> Result<DynShapeF> {
+    Ok(match u.int_in_range(0..=4)? {
+        0 => DynShape::Circle(Circle::new(point(u)?, length(u)?)),
+        1 => {
+            let p = convex_points(u, 3, 3)?;
+            DynShape::Triangle(Triangle([p[0], p[1], p[2]]))
+        }
+        2 => DynShape::Rect(Rect::new(coord(u)?, coord(u)?, length(u)?, length(u)?)),
+        3 => {
+            let p = convex_points(u, 4, 4)?;
+            DynShape::Quad(Quad([p[0], p[1], p[2], p[3]]))
+        }
+        _ => DynShape::Polygon(Polygon::from_vec(convex_points(u, 3, 12)?)),
+    })
+}
+
+/// Generate a ray with a normalized direction.
+pub fn ray(u: &mut Unstructured) -> Result<RayF> {
+    let origin = point(u)?;
+    let angle = (u.int_in_range(0..=3599u16)? as f32 / 10.0).to_radians();
+    let (sin, cos) = angle.sin_cos();
+    Ok(Ray::new(origin, vec2(cos, sin)))
+}
+
+/// The shape moved by an amount.
+pub fn translate(shape: &DynShapeF, amount: Vec2F) -> DynShapeF {
+    match shape {
+        DynShape::Circle(sh) => DynShape::Circle(*sh + amount),
+        DynShape::Triangle(sh) => DynShape::Triangle(Triangle(sh.0.map(|p| p + amount))),
+        DynShape::Rect(sh) => DynShape::Rect(*sh + amount),
+        DynShape::Quad(sh) => DynShape::Quad(Quad(sh.0.map(|p| p + amount))),
+        DynShape::Polygon(sh) => {
+            let mut sh = sh.clone();
+            sh.translate(amount);
+            DynShape::Polygon(sh)
+        }
+    }
+}
+
+/// Check that `a` overlaps `b` exactly when `b` overlaps `a`.
+pub fn check_overlap_symmetry(a: &DynShapeF, b: &DynShapeF) {
+    assert_eq!(
+        a.overlaps(b),
+        b.overlaps(a),
+        "overlap is not symmetric:\n{a:?}\n{b:?}"
+    );
+}
+
+/// Check that `a` can be extracted from `b` exactly when they overlap, and that moving `a` by
+/// the push-out vector separates them.
+pub fn check_extraction(a: &DynShapeF, b: &DynShapeF) {
+    let overlaps = a.overlaps(b);
+    let push = a.extract_from(b);
+    assert_eq!(
+        overlaps,
+        push.is_some(),
+        "overlap and extraction disagree:\n{a:?}\n{b:?}"
+    );
+    let Some(push) = push else {
+        return;
+    };
+    assert!(
+        push.x.is_finite() && push.y.is_finite(),
+        "push-out {push:?} is not finite:\n{a:?}\n{b:?}"
+    );
+
+    // push slightly further, since shapes that touch exactly may still count as overlapping
+    let moved = translate(a, push + push.norm_safe() * EPSILON);
+    assert!(
+        !moved.overlaps(b),
+        "push-out {push:?} does not resolve the overlap:\n{a:?}\n{b:?}"
+    );
+}
+
+/// Check that a raycast hit is in front of the ray, has a unit normal, and lands on the
+/// surface of the shape.
+pub fn check_raycast(shape: &DynShapeF, ray: &RayF) {
+    let Some(hit) = shape.raycast(ray) else {
+        return;
+    };
+    assert!(
+        hit.distance.is_finite() && hit.distance >= -EPSILON,
+        "hit {hit:?} is behind the ray:\n{shape:?}\n{ray:?}"
+    );
+    assert!(
+        (hit.normal.len() - 1.0).abs() <= EPSILON,
+        "hit {hit:?} normal is not normalized:\n{shape:?}\n{ray:?}"
+    );
+    let p = ray.point(hit.distance);
+    let surface = shape.project_point(p);
+    assert!(
+        surface.dist(p) <= EPSILON * MAX_COORD,
+        "hit {hit:?} at {p:?} is not on the surface:\n{shape:?}\n{ray:?}"
+    );
+}
+
+/// Generate two shapes from the data and check their overlap and extraction invariants.
+pub fn fuzz_overlap(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let (Ok(a), Ok(b)) = (shape(&mut u), shape(&mut u)) else {
+        return;
+    };
+    check_overlap_symmetry(&a, &b);
+    check_extraction(&a, &b);
+}
+
+/// Generate a shape and ray from the data and check the raycast invariants.
+pub fn fuzz_raycast(data: &[u8]) {
+    let mut u = Unstructured::new(data);
+    let (Ok(shape), Ok(ray)) = (shape(&mut u), ray(&mut u)) else {
+        return;
+    };
+    check_raycast(&shape, &ray);
+}
@@ -0,0 +1,188 @@
+use crate::{Affine2, Float, Mat2, Radians, Vec2, vec2};
+use alloc::vec::Vec;
+
+/// Result of [`solve_two_bone`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TwoBoneSolution<T> {
+    /// World angle of the upper bone.
+    pub upper: Radians<T>,
+
+    /// World angle of the lower bone.
+    pub lower: Radians<T>,
+
+    /// Position of the joint between the two bones.
+    pub joint: Vec2<T>,
+
+    /// Position of the end of the lower bone.
+    pub end: Vec2<T>,
+
+    /// If the end reached the target. If not, the chain is pointed straight at it.
+    pub reached: bool,
+}
+
+impl<T: Float> TwoBoneSolution<T> {
+    /// Angle of the lower bone relative to the upper bone.
+    #[inline]
+    pub fn lower_local(&self) -> Radians<T> {
+        Radians(self.lower.0 - self.upper.0)
+    }
+}
+
+/// Analytically solve a two-bone chain (eg. an arm or leg) starting at `root` so that its end
+/// reaches the target. If `bend_positive` is true, the joint bends in the direction of positive
+/// rotation (clockwise in a Y-down coordinate system).
+pub fn solve_two_bone<T: Float>(
+    root: Vec2<T>,
+    upper_len: T,
+    lower_len: T,
+    target: Vec2<T>,
+    bend_positive: bool,
+) -> TwoBoneSolution<T> {
+    let to_target = target - root;
+    let dist = to_target.len();
+    let base = T::atan2(to_target.y, to_target.x);
+    let max = upper_len + lower_len;
+    let min = T::abs(upper_len - lower_len);
+    let reached = dist <= max && dist >= min;
+    let dist = T::clamp(dist, min, max);
+
+    // law of cosines gives the angle between the upper bone and the line to the target
+    let (upper, lower) = if dist > T::ZERO && upper_len > T::ZERO {
+        let cos_a = (upper_len * upper_len + dist * dist - lower_len * lower_len)
+            / (T::TWO * upper_len * dist);
+        let a = acos(cos_a);
+        let a = if bend_positive { -a } else { a };
+        let upper = base + a;
+        let (sin, cos) = T::sin_cos(upper);
+        let joint = root + vec2(cos, sin) * upper_len;
+        let (sin, cos) = T::sin_cos(base);
+        let to_end = root + vec2(cos, sin) * dist - joint;
+        (upper, T::atan2(to_end.y, to_end.x))
+    } else {
+        (base, base)
+    };
+
+    let (sin, cos) = T::sin_cos(upper);
+    let joint = root + vec2(cos, sin) * upper_len;
+    let (sin, cos) = T::sin_cos(lower);
+    let end = joint + vec2(cos, sin) * lower_len;
+    TwoBoneSolution {
+        upper: Radians(upper),
+        lower: Radians(lower),
+        joint,
+        end,
+        reached,
+    }
+}
+
+/// Solve a chain of joints using FABRIK (forward and backward reaching inverse kinematics), so
+/// the last joint reaches the target. The first joint stays fixed and the distances between
+/// joints are preserved. Returns true if the target was reached within `tolerance`.
+pub fn solve_fabrik<T: Float>(
+    joints: &mut [Vec2<T>],
+    target: Vec2<T>,
+    iterations: u32,
+    tolerance: T,
+) -> bool {
+    let n = joints.len();
+    if n < 2 {
+        return false;
+    }
+    let lengths: Vec<T> = joints.windows(2).map(|w| w[0].dist(w[1])).collect();
+    let root = joints[0];
+    let total = lengths.iter().fold(T::ZERO, |a, &b| a + b);
+
+    // if the target is out of reach, stretch straight towards it
+    if root.dist(target) >= total {
+        let dir = (target - root).norm_safe();
+        for i in 1..n {
+            joints[i] = joints[i - 1] + dir * lengths[i - 1];
+        }
+        return false;
+    }
+
+    for _ in 0..iterations {
+        if joints[n - 1].dist(target) <= tolerance {
+            return true;
+        }
+
+        // backward pass: pin the end to the target
+        joints[n - 1] = target;
+        for i in (0..n - 1).rev() {
+            let dir = (joints[i] - joints[i + 1]).norm_safe();
+            joints[i] = joints[i + 1] + dir * lengths[i];
+        }
+
+        // forward pass: pin the root back in place
+        joints[0] = root;
+        for i in 1..n {
+            let dir = (joints[i] - joints[i - 1]).norm_safe();
+            joints[i] = joints[i - 1] + dir * lengths[i - 1];
+        }
+    }
+    joints[n - 1].dist(target) <= tolerance
+}
+
+/// Rotate the bone's world transform around its origin so that its local X axis points at the
+/// target, preserving its scale. Useful for aiming turrets, heads or arms.
+#[inline]
+pub fn aim_bone<T: Float>(bone: &mut Affine2<T>, target: Vec2<T>) {
+    let x_axis = bone.matrix.x_axis;
+    let to_target = target - bone.translation;
+    if x_axis.sqr_len() == T::ZERO || to_target.sqr_len() == T::ZERO {
+        return;
+    }
+    let current = T::atan2(x_axis.y, x_axis.x);
+    let desired = T::atan2(to_target.y, to_target.x);
+    bone.matrix = Mat2::rotation(Radians(desired - current)) * bone.matrix;
+}
+
+/// Solve a two-bone chain of world transforms, where `lower` is attached at the end of `upper`
+/// and is `lower_len` long along its X axis. Both bones are rotated so the end of `lower`
+/// reaches the target, such as placing a foot on the ground. Returns true if it was reached.
+pub fn solve_two_bone_transforms<T: Float>(
+    upper: &mut Affine2<T>,
+    lower: &mut Affine2<T>,
+    lower_len: T,
+    target: Vec2<T>,
+    bend_positive: bool,
+) -> bool {
+    let root = upper.translation;
+    let upper_len = root.dist(lower.translation);
+    let sol = solve_two_bone(root, upper_len, lower_len, target, bend_positive);
+    aim_bone(upper, sol.joint);
+    lower.translation = sol.joint;
+    aim_bone(lower, sol.end);
+    sol.reached
+}
+
+/// Solve a chain of world transforms with FABRIK, where each bone is attached at the end of the
+/// previous one and the last bone is `tip_len` long along its X axis. Returns true if the tip
+/// reached the target within `tolerance`.
+pub fn solve_fabrik_transforms<T: Float>(
+    bones: &mut [Affine2<T>],
+    tip_len: T,
+    target: Vec2<T>,
+    iterations: u32,
+    tolerance: T,
+) -> bool {
+    let Some(last) = bones.last() else {
+        return false;
+    };
+    let tip = last.translation + last.matrix.x_axis.norm_safe() * tip_len;
+    let mut joints: Vec<Vec2<T>> = bones.iter().map(|b| b.translation).collect();
+    joints.push(tip);
+    let reached = solve_fabrik(&mut joints, target, iterations, tolerance);
+    for (i, bone) in bones.iter_mut().enumerate() {
+        bone.translation = joints[i];
+        aim_bone(bone, joints[i + 1]);
+    }
+    reached
+}
+
+/// Arc cosine, clamping the input to avoid NaN from rounding errors.
+#[inline]
+fn acos<T: Float>(x: T) -> T {
+    let x = T::clamp(x, T::NEG_ONE, T::ONE);
+    T::atan2(T::sqrt(T::ONE - x * x), x)
+}
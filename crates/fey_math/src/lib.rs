@@ -1,4 +1,11 @@
 //! Vectors, matrices, directions, and geometry.
+//!
+//! Without the default `std` feature, the crate builds without the standard library, using
+//! [`libm`] for its floating point functions. It still needs an allocator for paths and polygons.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
 
 mod affine2;
 mod affine3;
@@ -8,13 +15,21 @@ mod circle;
 mod degrees;
 mod direction;
 mod dyn_shape;
+pub mod ease;
+#[cfg(feature = "fuzzing")]
+pub mod fuzzing;
+mod ik;
+#[cfg(not(feature = "std"))]
+mod libm_ext;
 mod line;
 pub mod macros;
 mod mat2;
 mod mat3;
 mod mat4;
 mod octal;
+mod path;
 mod polygon;
+mod polyline;
 mod projection;
 mod quad;
 mod radians;
@@ -26,6 +41,7 @@ mod shape;
 mod traits;
 mod transform;
 mod triangle;
+mod triangulate;
 mod vec2;
 mod vec3;
 mod vec4;
@@ -41,13 +57,19 @@ pub use circle::*;
 pub use degrees::*;
 pub use direction::*;
 pub use dyn_shape::*;
+pub use ease::{Ease, EaseFn};
+pub use ik::*;
+#[cfg(not(feature = "std"))]
+pub(crate) use libm_ext::*;
 pub use line::*;
 pub(crate) use macros::*;
 pub use mat2::*;
 pub use mat3::*;
 pub use mat4::*;
 pub use octal::*;
+pub use path::*;
 pub use polygon::*;
+pub use polyline::*;
 pub use projection::*;
 pub use quad::*;
 pub use radians::*;
@@ -59,6 +81,7 @@ pub use shape::*;
 pub use traits::*;
 pub use transform::*;
 pub use triangle::*;
+pub use triangulate::*;
 pub use vec2::*;
 pub use vec3::*;
 pub use vec4::*;
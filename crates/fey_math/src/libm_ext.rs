@@ -0,0 +1,64 @@
+//! Floating point methods that `core` does not provide, backed by [`libm`] for builds
+//! without `std`.
+//!
+//! With the standard library, the inherent methods on `f32` and `f64` take priority, so this is
+//! only imported when building without it.
+
+use libm::Libm;
+
+pub(crate) trait LibmExt: Sized {
+    fn round(self) -> Self;
+    fn floor(self) -> Self;
+    fn ceil(self) -> Self;
+    fn trunc(self) -> Self;
+    fn sqrt(self) -> Self;
+    fn exp(self) -> Self;
+    fn powf(self, n: Self) -> Self;
+    fn sin(self) -> Self;
+    fn cos(self) -> Self;
+    fn sin_cos(self) -> (Self, Self);
+    fn atan2(self, x: Self) -> Self;
+}
+
+macro_rules! impl_libm_ext {
+    ($($name:ident)*) => {
+        $(
+            impl LibmExt for $name {
+                #[inline]
+                fn round(self) -> Self { Libm::<$name>::round(self) }
+
+                #[inline]
+                fn floor(self) -> Self { Libm::<$name>::floor(self) }
+
+                #[inline]
+                fn ceil(self) -> Self { Libm::<$name>::ceil(self) }
+
+                #[inline]
+                fn trunc(self) -> Self { Libm::<$name>::trunc(self) }
+
+                #[inline]
+                fn sqrt(self) -> Self { Libm::<$name>::sqrt(self) }
+
+                #[inline]
+                fn exp(self) -> Self { Libm::<$name>::exp(self) }
+
+                #[inline]
+                fn powf(self, n: Self) -> Self { Libm::<$name>::pow(self, n) }
+
+                #[inline]
+                fn sin(self) -> Self { Libm::<$name>::sin(self) }
+
+                #[inline]
+                fn cos(self) -> Self { Libm::<$name>::cos(self) }
+
+                #[inline]
+                fn sin_cos(self) -> (Self, Self) { Libm::<$name>::sincos(self) }
+
+                #[inline]
+                fn atan2(self, x: Self) -> Self { Libm::<$name>::atan2(self, x) }
+            }
+        )*
+    };
+}
+
+impl_libm_ext!(f32 f64);
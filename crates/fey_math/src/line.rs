@@ -2,7 +2,7 @@ use crate::{
     Float, Num, Projection, Ray, Rect, Vec2, impl_approx, impl_bytemuck, impl_casts, impl_interp,
     impl_serde, rect, vec2,
 };
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 pub type LineF = Line<f32>;
 pub type LineI = Line<i32>;
@@ -144,11 +144,24 @@ impl<T: Float> Line<T> {
         Projection { min, max }
     }
 
-    /// Project the point onto this line.
+    /// Project the point onto the infinite line passing through `start` and `end`.
     #[inline]
     pub fn project_point(&self, p: Vec2<T>) -> Vec2<T> {
         let norm = self.norm();
-        self.start + norm * p.dot(norm)
+        self.start + norm * (p - self.start).dot(norm)
+    }
+
+    /// Project the point onto this line, clamped between `start` and `end`, giving the
+    /// nearest point on the line segment.
+    #[inline]
+    pub fn project_point_clamped(&self, p: Vec2<T>) -> Vec2<T> {
+        let vec = self.vector();
+        let sqr_len = vec.sqr_len();
+        if sqr_len == T::ZERO {
+            return self.start;
+        }
+        let t = T::clamp((p - self.start).dot(vec) / sqr_len, T::ZERO, T::ONE);
+        self.start + vec * t
     }
 
     /// Check if the ray hits this line.
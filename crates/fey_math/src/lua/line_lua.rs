@@ -51,6 +51,9 @@ impl LuaModule for LineModule {
                 line.project_onto_axis(axis)
             })?;
             members.method("project_point", |line, p: Vec2F| line.project_point(p))?;
+            members.method("project_point_clamped", |line, p: Vec2F| {
+                line.project_point_clamped(p)
+            })?;
             members.method_ext("rayhit", |lua, line, ray: Temp<RayF>| {
                 ray.read(lua, |_, ray| Ok(line.rayhit(ray)))
             })?;
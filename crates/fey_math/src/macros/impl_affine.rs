@@ -74,7 +74,7 @@ macro_rules! impl_affine {
             }
         }
 
-        impl<T: $crate::Float> std::ops::Mul<$name<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::Mul<$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -83,7 +83,7 @@ macro_rules! impl_affine {
             }
         }
 
-        impl<T: $crate::Float> std::ops::Mul<$name<T>> for &$name<T> {
+        impl<T: $crate::Float> core::ops::Mul<$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -92,7 +92,7 @@ macro_rules! impl_affine {
             }
         }
 
-        impl<T: $crate::Float> std::ops::Mul<&$name<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::Mul<&$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -101,7 +101,7 @@ macro_rules! impl_affine {
             }
         }
 
-         impl<T: $crate::Float> std::ops::Mul<&$name<T>> for &$name<T> {
+         impl<T: $crate::Float> core::ops::Mul<&$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -19,7 +19,7 @@ macro_rules! impl_angle_op {
         $($rhs:ident)*
     ) => {
         $(
-        impl<T: $crate::Float> std::ops::$op_ty<$crate::$rhs<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::$op_ty<$crate::$rhs<T>> for $name<T> {
             type Output = $name<T>;
             #[inline]
             fn $op_fn(self, rhs: $crate::$rhs<T>) -> Self::Output {
@@ -27,7 +27,7 @@ macro_rules! impl_angle_op {
             }
         }
 
-        impl<T: $crate::Float> std::ops::$op_ty<$crate::$rhs<T>> for &$name<T> {
+        impl<T: $crate::Float> core::ops::$op_ty<$crate::$rhs<T>> for &$name<T> {
             type Output = $name<T>;
             #[inline]
             fn $op_fn(self, rhs: $crate::$rhs<T>) -> Self::Output {
@@ -35,7 +35,7 @@ macro_rules! impl_angle_op {
             }
         }
 
-        impl<T: $crate::Float> std::ops::$op_ty<&$crate::$rhs<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::$op_ty<&$crate::$rhs<T>> for $name<T> {
             type Output = $name<T>;
             #[inline]
             fn $op_fn(self, rhs: &$crate::$rhs<T>) -> Self::Output {
@@ -43,7 +43,7 @@ macro_rules! impl_angle_op {
             }
         }
 
-        impl<T: $crate::Float> std::ops::$op_ty<&$crate::$rhs<T>> for &$name<T> {
+        impl<T: $crate::Float> core::ops::$op_ty<&$crate::$rhs<T>> for &$name<T> {
             type Output = $name<T>;
             #[inline]
             fn $op_fn(self, rhs: &$crate::$rhs<T>) -> Self::Output {
@@ -51,7 +51,7 @@ macro_rules! impl_angle_op {
             }
         }
 
-        impl<T: $crate::Float> std::ops::$assign_ty<$crate::$rhs<T>>
+        impl<T: $crate::Float> core::ops::$assign_ty<$crate::$rhs<T>>
             for $name<T>
         {
             #[inline]
@@ -60,7 +60,7 @@ macro_rules! impl_angle_op {
             }
         }
 
-        impl<T: $crate::Float> std::ops::$assign_ty<&$crate::$rhs<T>>
+        impl<T: $crate::Float> core::ops::$assign_ty<&$crate::$rhs<T>>
             for $name<T>
         {
             #[inline]
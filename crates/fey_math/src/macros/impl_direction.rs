@@ -31,10 +31,10 @@ macro_rules! impl_direction {
             }
         }
 
-        impl std::fmt::Display for $name {
+        impl core::fmt::Display for $name {
             #[inline]
-            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                std::fmt::Display::fmt(self.to_str(), f)
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                core::fmt::Display::fmt(self.to_str(), f)
             }
         }
     };
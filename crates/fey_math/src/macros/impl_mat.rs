@@ -42,7 +42,7 @@ macro_rules! impl_mat {
             pub const IDENTITY: Self = $short($($vec::$c),*);
         }
 
-        impl<T: $crate::Float> std::ops::Mul<$name<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::Mul<$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -51,7 +51,7 @@ macro_rules! impl_mat {
             }
         }
 
-        impl<T: $crate::Float> std::ops::Mul<$name<T>> for &$name<T> {
+        impl<T: $crate::Float> core::ops::Mul<$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -60,7 +60,7 @@ macro_rules! impl_mat {
             }
         }
 
-        impl<T: $crate::Float> std::ops::Mul<&$name<T>> for $name<T> {
+        impl<T: $crate::Float> core::ops::Mul<&$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -69,7 +69,7 @@ macro_rules! impl_mat {
             }
         }
 
-         impl<T: $crate::Float> std::ops::Mul<&$name<T>> for &$name<T> {
+         impl<T: $crate::Float> core::ops::Mul<&$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
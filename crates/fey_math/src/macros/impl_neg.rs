@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! impl_neg {
     ($name:ident $($p:ident)*) => {
-        impl<T: std::ops::Neg<Output = T>> std::ops::Neg for $name<T> {
+        impl<T: core::ops::Neg<Output = T>> core::ops::Neg for $name<T> {
             type Output = $name<T>;
 
             #[inline]
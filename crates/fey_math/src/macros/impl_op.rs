@@ -8,7 +8,7 @@ macro_rules! impl_op {
         $assign_fn:ident
         $($p:tt)*
     ) => {
-        impl<T: std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<$name<T>> for $name<T> {
+        impl<T: core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -17,7 +17,7 @@ macro_rules! impl_op {
             }
         }
 
-        impl<T: Copy + std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<$name<T>> for &$name<T> {
+        impl<T: Copy + core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -26,7 +26,7 @@ macro_rules! impl_op {
             }
         }
 
-        impl<T: Copy + std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<&$name<T>> for $name<T> {
+        impl<T: Copy + core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<&$name<T>> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -35,7 +35,7 @@ macro_rules! impl_op {
             }
         }
 
-        impl<T: Copy + std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<&$name<T>> for &$name<T> {
+        impl<T: Copy + core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<&$name<T>> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -44,14 +44,14 @@ macro_rules! impl_op {
             }
         }
 
-        impl<T: std::ops::$assign_ty<T>> std::ops::$assign_ty<$name<T>> for $name<T> {
+        impl<T: core::ops::$assign_ty<T>> core::ops::$assign_ty<$name<T>> for $name<T> {
             #[inline]
             fn $assign_fn(&mut self, rhs: $name<T>) {
                 $(self.$p.$assign_fn(rhs.$p);)*
             }
         }
 
-        impl<T: Copy + std::ops::$assign_ty<T>> std::ops::$assign_ty<&$name<T>> for $name<T> {
+        impl<T: Copy + core::ops::$assign_ty<T>> core::ops::$assign_ty<&$name<T>> for $name<T> {
             #[inline]
             fn $assign_fn(&mut self, rhs: &$name<T>) {
                 $(self.$p.$assign_fn(rhs.$p);)*
@@ -8,7 +8,7 @@ macro_rules! impl_op_scalar {
         $assign_fn:ident
         $($p:tt)*
     ) => {
-        impl<T: Copy + std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<T> for $name<T> {
+        impl<T: Copy + core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<T> for $name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -17,7 +17,7 @@ macro_rules! impl_op_scalar {
             }
         }
 
-        impl<T: Copy + std::ops::$op_ty<T, Output = T>> std::ops::$op_ty<T> for &$name<T> {
+        impl<T: Copy + core::ops::$op_ty<T, Output = T>> core::ops::$op_ty<T> for &$name<T> {
             type Output = $name<T>;
 
             #[inline]
@@ -26,14 +26,14 @@ macro_rules! impl_op_scalar {
             }
         }
 
-        impl<T: Copy + std::ops::$assign_ty<T>> std::ops::$assign_ty<T> for $name<T> {
+        impl<T: Copy + core::ops::$assign_ty<T>> core::ops::$assign_ty<T> for $name<T> {
             #[inline]
             fn $assign_fn(&mut self, rhs: T) {
                 $(self.$p.$assign_fn(rhs);)*
             }
         }
 
-        impl<T: Copy + std::ops::$assign_ty<T>> std::ops::$assign_ty<&T> for $name<T> {
+        impl<T: Copy + core::ops::$assign_ty<T>> core::ops::$assign_ty<&T> for $name<T> {
             #[inline]
             fn $assign_fn(&mut self, rhs: &T) {
                 $(self.$p.$assign_fn(*rhs);)*
@@ -1,5 +1,5 @@
 use crate::{Angle, Float, Mat2, Mat4, Num, Signed, Vec2, Vec3, impl_mat, vec2, vec3};
-use std::ops::Mul;
+use core::ops::Mul;
 
 pub type Mat3F = Mat3<f32>;
 
@@ -1,5 +1,5 @@
 use crate::{Angle, Float, Mat2, Mat3, Num, Vec2, Vec3, Vec4, impl_mat, vec2, vec3, vec4};
-use std::ops::{Add, Mul, Sub};
+use core::ops::{Add, Mul, Sub};
 
 pub type Mat4F = Mat4<f32>;
 
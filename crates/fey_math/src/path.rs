@@ -0,0 +1,341 @@
+use crate::{Float, Polyline, Quad, Vec2, quad};
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+pub type PathF = Path<f32>;
+
+/// A single drawing command in a [`Path`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PathCmd<T> {
+    /// Start a new subpath at the point.
+    MoveTo(Vec2<T>),
+
+    /// Draw a straight line to the point.
+    LineTo(Vec2<T>),
+
+    /// Draw a quadratic bézier curve with a control point to the end point.
+    QuadTo(Vec2<T>, Vec2<T>),
+
+    /// Draw a cubic bézier curve with two control points to the end point.
+    CubicTo(Vec2<T>, Vec2<T>, Vec2<T>),
+
+    /// Close the current subpath by connecting it back to its start.
+    Close,
+}
+
+/// How overlapping or self-intersecting areas of a path decide whether they are filled.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum FillRule {
+    /// Areas are filled if the path winds around them a non-zero amount of times.
+    #[default]
+    NonZero,
+
+    /// Areas are filled if the path crosses over them an odd amount of times.
+    EvenOdd,
+}
+
+impl FillRule {
+    /// Whether an area with the given winding number is filled.
+    #[inline]
+    pub fn is_inside(self, winding: i32) -> bool {
+        match self {
+            Self::NonZero => winding != 0,
+            Self::EvenOdd => winding % 2 != 0,
+        }
+    }
+}
+
+/// A vector path made up of lines and bézier curves, which can be filled or stroked.
+///
+/// ```
+/// # use fey_math::*;
+/// let mut path = PathF::new();
+/// path.move_to(vec2(0.0, 0.0))
+///     .line_to(vec2(100.0, 0.0))
+///     .quad_to(vec2(100.0, 100.0), vec2(0.0, 100.0))
+///     .close();
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Path<T> {
+    cmds: Vec<PathCmd<T>>,
+}
+
+impl<T: Float> Path<T> {
+    /// Create a new empty path.
+    #[inline]
+    pub fn new() -> Self {
+        Self { cmds: Vec::new() }
+    }
+
+    /// The path's commands.
+    #[inline]
+    pub fn cmds(&self) -> &[PathCmd<T>] {
+        &self.cmds
+    }
+
+    /// If the path has no commands.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty()
+    }
+
+    /// Remove all commands from the path.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.cmds.clear();
+    }
+
+    /// Add a command to the path.
+    #[inline]
+    pub fn push(&mut self, cmd: PathCmd<T>) -> &mut Self {
+        self.cmds.push(cmd);
+        self
+    }
+
+    /// Start a new subpath at the point.
+    #[inline]
+    pub fn move_to(&mut self, p: Vec2<T>) -> &mut Self {
+        self.push(PathCmd::MoveTo(p))
+    }
+
+    /// Draw a straight line to the point.
+    #[inline]
+    pub fn line_to(&mut self, p: Vec2<T>) -> &mut Self {
+        self.push(PathCmd::LineTo(p))
+    }
+
+    /// Draw a quadratic bézier curve to `p` using the control point `ctrl`.
+    #[inline]
+    pub fn quad_to(&mut self, ctrl: Vec2<T>, p: Vec2<T>) -> &mut Self {
+        self.push(PathCmd::QuadTo(ctrl, p))
+    }
+
+    /// Draw a cubic bézier curve to `p` using the control points `ctrl1` and `ctrl2`.
+    #[inline]
+    pub fn cubic_to(&mut self, ctrl1: Vec2<T>, ctrl2: Vec2<T>, p: Vec2<T>) -> &mut Self {
+        self.push(PathCmd::CubicTo(ctrl1, ctrl2, p))
+    }
+
+    /// Close the current subpath by connecting it back to its start.
+    #[inline]
+    pub fn close(&mut self) -> &mut Self {
+        self.push(PathCmd::Close)
+    }
+
+    /// Transform all points in the path.
+    pub fn transform_by(&self, mut f: impl FnMut(Vec2<T>) -> Vec2<T>) -> Self {
+        let cmds = self
+            .cmds
+            .iter()
+            .map(|cmd| match *cmd {
+                PathCmd::MoveTo(p) => PathCmd::MoveTo(f(p)),
+                PathCmd::LineTo(p) => PathCmd::LineTo(f(p)),
+                PathCmd::QuadTo(c, p) => PathCmd::QuadTo(f(c), f(p)),
+                PathCmd::CubicTo(c1, c2, p) => PathCmd::CubicTo(f(c1), f(c2), f(p)),
+                PathCmd::Close => PathCmd::Close,
+            })
+            .collect();
+        Self { cmds }
+    }
+
+    /// Flatten the path's curves into line segments, returning a polyline for each subpath.
+    /// Closed subpaths end with a copy of their first point. The `tolerance` is the maximum
+    /// distance the segments are allowed to stray from the true curves.
+    pub fn flatten(&self, tolerance: T) -> Vec<Polyline<T>> {
+        let tolerance = T::max(tolerance, T::EIGHTH * T::EIGHTH * T::EIGHTH);
+        let mut subpaths = Vec::new();
+        let mut curr = Polyline::new();
+        let mut pos = Vec2::ZERO;
+        let mut start = Vec2::ZERO;
+        for cmd in &self.cmds {
+            match *cmd {
+                PathCmd::MoveTo(p) => {
+                    if curr.len() > 1 {
+                        subpaths.push(core::mem::replace(&mut curr, Polyline::new()));
+                    }
+                    curr.clear();
+                    curr.push(p);
+                    start = p;
+                    pos = p;
+                }
+                PathCmd::LineTo(p) => {
+                    if curr.is_empty() {
+                        curr.push(pos);
+                    }
+                    curr.push(p);
+                    pos = p;
+                }
+                PathCmd::QuadTo(c, p) => {
+                    if curr.is_empty() {
+                        curr.push(pos);
+                    }
+                    let dd = (pos - c * T::TWO + p).len();
+                    let n = curve_segments(T::sqrt(dd * T::EIGHTH / tolerance));
+                    for i in 1..=n {
+                        let t = T::from_usize(i) / T::from_usize(n);
+                        let u = T::ONE - t;
+                        curr.push(pos * (u * u) + c * (T::TWO * u * t) + p * (t * t));
+                    }
+                    pos = p;
+                }
+                PathCmd::CubicTo(c1, c2, p) => {
+                    if curr.is_empty() {
+                        curr.push(pos);
+                    }
+                    let dd = T::max((pos - c1 * T::TWO + c2).len(), (c1 - c2 * T::TWO + p).len());
+                    let n = curve_segments(T::sqrt(dd * T::THREE / (T::FOUR * tolerance)));
+                    for i in 1..=n {
+                        let t = T::from_usize(i) / T::from_usize(n);
+                        let u = T::ONE - t;
+                        curr.push(
+                            pos * (u * u * u)
+                                + c1 * (T::THREE * u * u * t)
+                                + c2 * (T::THREE * u * t * t)
+                                + p * (t * t * t),
+                        );
+                    }
+                    pos = p;
+                }
+                PathCmd::Close => {
+                    if curr.len() > 1 {
+                        curr.push(start);
+                        subpaths.push(core::mem::replace(&mut curr, Polyline::new()));
+                    }
+                    curr.clear();
+                    pos = start;
+                }
+            }
+        }
+        if curr.len() > 1 {
+            subpaths.push(curr);
+        }
+        subpaths
+    }
+
+    /// Tessellate the filled area of the path into quads. All subpaths are treated as closed,
+    /// and edges with infinite or NaN coordinates are skipped.
+    pub fn fill(&self, rule: FillRule, tolerance: T) -> Vec<Quad<T>> {
+        // collect all non-horizontal edges, oriented top-to-bottom with their winding direction
+        let finite = |p: Vec2<T>| T::is_finite(p.x) && T::is_finite(p.y);
+        let mut edges: Vec<(Vec2<T>, Vec2<T>, i32)> = Vec::new();
+        for sub in self.flatten(tolerance) {
+            let points = sub.points();
+            let len = points.len();
+            for i in 0..len {
+                let a = points[i];
+                let b = points[(i + 1) % len];
+                if !finite(a) || !finite(b) {
+                    continue;
+                }
+                if a.y < b.y {
+                    edges.push((a, b, 1));
+                } else if a.y > b.y {
+                    edges.push((b, a, -1));
+                }
+            }
+        }
+
+        // edge table, sorted by the top of each edge so they can be activated in order
+        edges.sort_by(|(a1, ..), (a2, ..)| T::total_cmp(&a1.y, &a2.y));
+
+        // split the shape into horizontal slabs at every vertex and edge crossing, so that
+        // within any one slab the edges never cross each other
+        let mut ys: Vec<T> = edges.iter().flat_map(|(a, b, _)| [a.y, b.y]).collect();
+        for (i, &(a1, b1, _)) in edges.iter().enumerate() {
+            for &(a2, b2, _) in &edges[(i + 1)..] {
+                if a2.y >= b1.y {
+                    break;
+                }
+                if let Some(y) = crossing_y(a1, b1, a2, b2) {
+                    ys.push(y);
+                }
+            }
+        }
+        ys.sort_by(T::total_cmp);
+        ys.dedup();
+
+        let x_at = |(a, b, _): &(Vec2<T>, Vec2<T>, i32), y: T| {
+            a.x + (b.x - a.x) * ((y - a.y) / (b.y - a.y))
+        };
+
+        // walk each slab left-to-right, emitting the spans that are inside the shape
+        let mut quads = Vec::new();
+        let mut next_edge = 0;
+        let mut edge_list: Vec<&(Vec2<T>, Vec2<T>, i32)> = Vec::new();
+        let mut active: Vec<(T, T, T, i32)> = Vec::new();
+        for slab in ys.windows(2) {
+            let (y0, y1) = (slab[0], slab[1]);
+            let mid = (y0 + y1) * T::HALF;
+
+            // add edges that start in this slab, and drop the ones that ended above it
+            while let Some(edge) = edges.get(next_edge).filter(|(a, ..)| a.y <= y0) {
+                edge_list.push(edge);
+                next_edge += 1;
+            }
+            edge_list.retain(|(_, b, _)| b.y >= y1);
+
+            active.clear();
+            active.extend(
+                edge_list
+                    .iter()
+                    .map(|e| (x_at(e, mid), x_at(e, y0), x_at(e, y1), e.2)),
+            );
+            active.sort_by(|a, b| T::total_cmp(&a.0, &b.0));
+
+            let mut winding = 0;
+            for pair in active.windows(2) {
+                let (l, r) = (pair[0], pair[1]);
+                winding += l.3;
+                if rule.is_inside(winding) {
+                    quads.push(quad(
+                        Vec2::new(l.1, y0),
+                        Vec2::new(r.1, y0),
+                        Vec2::new(r.2, y1),
+                        Vec2::new(l.2, y1),
+                    ));
+                }
+            }
+        }
+        quads
+    }
+
+    /// Tessellate the outline of the path into quads, `width` units thick. If a dash pattern
+    /// is provided, it is applied to every subpath (see [`Polyline::dashed`]).
+    pub fn stroke(&self, width: T, tolerance: T, dashes: &[T], dash_offset: T) -> Vec<Quad<T>> {
+        let mut quads = Vec::new();
+        for sub in self.flatten(tolerance) {
+            if dashes.is_empty() {
+                sub.stroke_into(width, &mut quads);
+            } else {
+                for dash in sub.dashed(dashes, dash_offset) {
+                    dash.stroke_into(width, &mut quads);
+                }
+            }
+        }
+        quads
+    }
+}
+
+/// The y-position where two edges cross, if they properly intersect.
+fn crossing_y<T: Float>(a1: Vec2<T>, b1: Vec2<T>, a2: Vec2<T>, b2: Vec2<T>) -> Option<T> {
+    let d1 = b1 - a1;
+    let d2 = b2 - a2;
+    let denom = d1.cross(d2);
+    if denom == T::ZERO {
+        return None;
+    }
+    let diff = a2 - a1;
+    let t = diff.cross(d2) / denom;
+    let u = diff.cross(d1) / denom;
+    (t > T::ZERO && t < T::ONE && u > T::ZERO && u < T::ONE).then(|| a1.y + d1.y * t)
+}
+
+/// Most segments a single curve is flattened into, so huge curves can't exhaust memory.
+const MAX_CURVE_SEGMENTS: usize = 1024;
+
+/// How many segments to flatten a curve into, from the ideal (fractional) count.
+#[inline]
+fn curve_segments<T: Float>(n: T) -> usize {
+    // NaN becomes 0 and infinity the maximum, so both are clamped too
+    T::ceil(n).to_usize().clamp(1, MAX_CURVE_SEGMENTS)
+}
@@ -1,4 +1,5 @@
 use crate::{Float, Line, Num, Numeric, Quad, Rect, Triangle, Vec2, line};
+use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
 pub type PolygonF = Polygon<f32>;
@@ -227,7 +228,7 @@ impl<T: Num> Extend<Vec2<T>> for Polygon<T> {
 
 impl<T> IntoIterator for Polygon<T> {
     type Item = Vec2<T>;
-    type IntoIter = std::vec::IntoIter<Vec2<T>>;
+    type IntoIter = alloc::vec::IntoIter<Vec2<T>>;
 
     #[inline]
     fn into_iter(self) -> Self::IntoIter {
@@ -0,0 +1,326 @@
+use crate::{Float, Line, Num, Quad, Rect, Vec2, line, quad};
+use alloc::vec;
+use alloc::vec::Vec;
+use serde::{Deserialize, Serialize};
+
+pub type PolylineF = Polyline<f32>;
+
+/// An open chain of connected line segments.
+#[repr(transparent)]
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Polyline<T>(Vec<Vec2<T>>);
+
+impl<T: Num> Polyline<T> {
+    /// Creates a new empty polyline.
+    #[inline]
+    pub fn new() -> Self {
+        Self(Vec::new())
+    }
+
+    /// Creates a new empty polyline with at least the specified `capacity`.
+    #[inline]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self(Vec::with_capacity(capacity))
+    }
+
+    /// Create a new polyline using the vector of points.
+    #[inline]
+    pub fn from_vec(vec: Vec<Vec2<T>>) -> Self {
+        Self(vec)
+    }
+
+    /// How many points the polyline has.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// If the polyline has no points.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Add a point to the end of the polyline.
+    #[inline]
+    pub fn push(&mut self, p: Vec2<T>) {
+        self.0.push(p);
+    }
+
+    /// Remove the last point from the polyline and return it.
+    #[inline]
+    pub fn pop(&mut self) -> Option<Vec2<T>> {
+        self.0.pop()
+    }
+
+    /// Reference to the polyline's points.
+    #[inline]
+    pub fn points(&self) -> &[Vec2<T>] {
+        self.0.as_slice()
+    }
+
+    /// Mutable reference to the polyline's points.
+    #[inline]
+    pub fn points_mut(&mut self) -> &mut [Vec2<T>] {
+        self.0.as_mut_slice()
+    }
+
+    /// Remove all points from the polyline.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Convert the polyline into a `Vec` of points.
+    #[inline]
+    pub fn to_vec(self) -> Vec<Vec2<T>> {
+        self.0
+    }
+
+    /// An iterator over all the polyline's segments.
+    #[inline]
+    pub fn segments(&self) -> impl Iterator<Item = Line<T>> + '_ {
+        self.0.windows(2).map(|w| line(w[0], w[1]))
+    }
+
+    /// Rectangular bounds of the polyline.
+    #[inline]
+    pub fn bounds(&self) -> Option<Rect<T>> {
+        let (first, rest) = self.0.split_first()?;
+        let (min, max) = rest
+            .iter()
+            .fold((*first, *first), |(min, max), p| (min.min(*p), max.max(*p)));
+        Some(Rect::pos_size(min, max - min))
+    }
+}
+
+impl<T: Float> Polyline<T> {
+    /// Total length of all the polyline's segments.
+    #[inline]
+    pub fn length(&self) -> T {
+        self.segments().fold(T::ZERO, |len, seg| len + seg.len())
+    }
+
+    /// Find the point `dist` units along the polyline, along with the normalized direction of
+    /// the segment it lies on. Returns `None` if `dist` is negative or beyond the polyline's end.
+    pub fn sample(&self, dist: T) -> Option<(Vec2<T>, Vec2<T>)> {
+        if dist < T::ZERO {
+            return None;
+        }
+        let mut remaining = dist;
+        for seg in self.segments() {
+            let len = seg.len();
+            if len <= T::ZERO {
+                continue;
+            }
+            if remaining <= len {
+                let dir = seg.vector() / len;
+                return Some((seg.start + dir * remaining, dir));
+            }
+            remaining -= len;
+        }
+        None
+    }
+
+    /// Split the polyline into dashes. The `pattern` alternates between the lengths of dashes
+    /// and the gaps between them, and `phase` is how far into the pattern the polyline starts.
+    pub fn dashed(&self, pattern: &[T], phase: T) -> Vec<Self> {
+        let total = pattern
+            .iter()
+            .fold(T::ZERO, |sum, len| sum + T::max(*len, T::ZERO));
+        if total <= T::ZERO || self.len() < 2 {
+            return vec![self.clone()];
+        }
+
+        // find where in the pattern we start
+        let mut phase = phase % total;
+        if phase < T::ZERO {
+            phase += total;
+        }
+        let mut idx = 0;
+        while phase >= T::max(pattern[idx], T::ZERO) {
+            phase -= T::max(pattern[idx], T::ZERO);
+            idx = (idx + 1) % pattern.len();
+        }
+        let mut remaining = T::max(pattern[idx], T::ZERO) - phase;
+
+        let mut dashes = Vec::new();
+        let mut curr = Self::new();
+        if idx % 2 == 0 {
+            curr.push(self.0[0]);
+        }
+        for seg in self.segments() {
+            let len = seg.len();
+            if len <= T::ZERO {
+                continue;
+            }
+            let dir = seg.vector() / len;
+            let mut pos = T::ZERO;
+            while len - pos > remaining {
+                pos += remaining;
+                curr.push(seg.start + dir * pos);
+                if idx % 2 == 0 {
+                    dashes.push(core::mem::replace(&mut curr, Polyline::new()));
+                }
+                idx = (idx + 1) % pattern.len();
+                remaining = T::max(pattern[idx], T::ZERO);
+            }
+            remaining -= len - pos;
+            if idx % 2 == 0 {
+                curr.push(seg.end);
+            }
+        }
+        if curr.len() > 1 {
+            dashes.push(curr);
+        }
+        dashes
+    }
+
+    /// Tessellate the polyline into quads, `width` units thick, with beveled joins.
+    pub fn stroke(&self, width: T) -> Vec<Quad<T>> {
+        let mut quads = Vec::new();
+        self.stroke_into(width, &mut quads);
+        quads
+    }
+
+    /// Tessellate the polyline into quads, `width` units thick, with beveled joins. The quads
+    /// are appended to the provided vector.
+    pub fn stroke_into(&self, width: T, quads: &mut Vec<Quad<T>>) {
+        let half = width * T::HALF;
+        let mut prev: Option<Vec2<T>> = None;
+        let mut first: Option<(Vec2<T>, Vec2<T>)> = None;
+        for seg in self.segments() {
+            let len = seg.len();
+            if len <= T::ZERO {
+                continue;
+            }
+            let dir = seg.vector() / len;
+            let n = dir.turn_left() * half;
+            quads.push(quad(seg.start + n, seg.end + n, seg.end - n, seg.start - n));
+            if let Some(prev_n) = prev {
+                quads.push(bevel(seg.start, prev_n, n, dir.dot(prev_n)));
+            } else {
+                first = Some((dir, n));
+            }
+            prev = Some(n);
+        }
+
+        // join the ends of closed polylines together
+        let closed = self.len() > 2 && self.0.first() == self.0.last();
+        if let (true, Some(prev_n), Some((dir, n))) = (closed, prev, first) {
+            quads.push(bevel(self.0[0], prev_n, n, dir.dot(prev_n)));
+        }
+    }
+
+    /// Simplify the polyline by removing points that deviate less than `tolerance` from the
+    /// simplified shape, using the Ramer-Douglas-Peucker algorithm. The first and last points
+    /// are always kept, so closed polylines stay closed.
+    pub fn simplify(&self, tolerance: T) -> Self {
+        if self.len() < 3 {
+            return self.clone();
+        }
+        let mut keep = vec![false; self.len()];
+        keep[0] = true;
+        keep[self.len() - 1] = true;
+        let mut stack = vec![(0, self.len() - 1)];
+        while let Some((start, end)) = stack.pop() {
+            let (a, b) = (self.0[start], self.0[end]);
+            let (index, dist) = (start + 1..end)
+                .map(|i| (i, seg_dist(a, b, self.0[i])))
+                .fold(
+                    (start, T::ZERO),
+                    |max, cur| if cur.1 > max.1 { cur } else { max },
+                );
+            if dist > tolerance {
+                keep[index] = true;
+                stack.push((start, index));
+                stack.push((index, end));
+            }
+        }
+        self.0
+            .iter()
+            .zip(keep)
+            .filter_map(|(p, keep)| keep.then_some(*p))
+            .collect()
+    }
+
+    #[inline]
+    pub fn transform_in_place_by(&mut self, mut f: impl FnMut(Vec2<T>) -> Vec2<T>) {
+        for p in self.points_mut() {
+            *p = f(*p);
+        }
+    }
+
+    #[inline]
+    pub fn transform_by(&self, f: impl FnMut(Vec2<T>) -> Vec2<T>) -> Self {
+        self.0.iter().copied().map(f).collect()
+    }
+}
+
+/// Distance from `p` to the line segment `a-b`.
+#[inline]
+fn seg_dist<T: Float>(a: Vec2<T>, b: Vec2<T>, p: Vec2<T>) -> T {
+    let ab = b - a;
+    let sqr_len = ab.sqr_len();
+    if sqr_len <= T::ZERO {
+        return p.dist(a);
+    }
+    let t = T::clamp((p - a).dot(ab) / sqr_len, T::ZERO, T::ONE);
+    p.dist(a + ab * t)
+}
+
+/// A bevel filling the outer gap where two stroked segments meet at `p`.
+#[inline]
+fn bevel<T: Float>(p: Vec2<T>, prev_n: Vec2<T>, n: Vec2<T>, turn: T) -> Quad<T> {
+    let (a, b) = if turn > T::ZERO {
+        (p - prev_n, p - n)
+    } else {
+        (p + prev_n, p + n)
+    };
+    quad(p, a, b, p)
+}
+
+impl<T: Num> FromIterator<Vec2<T>> for Polyline<T> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Vec2<T>>>(iter: I) -> Self {
+        Self(Vec::from_iter(iter))
+    }
+}
+
+impl<T: Num> Extend<Vec2<T>> for Polyline<T> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Vec2<T>>>(&mut self, iter: I) {
+        self.0.extend(iter);
+    }
+}
+
+impl<T> IntoIterator for Polyline<T> {
+    type Item = Vec2<T>;
+    type IntoIter = alloc::vec::IntoIter<Vec2<T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<T> AsRef<[Vec2<T>]> for Polyline<T> {
+    #[inline]
+    fn as_ref(&self) -> &[Vec2<T>] {
+        self.0.as_ref()
+    }
+}
+
+impl<T: Num, const S: usize> From<[Vec2<T>; S]> for Polyline<T> {
+    #[inline]
+    fn from(value: [Vec2<T>; S]) -> Self {
+        Self(Vec::from(value))
+    }
+}
+
+impl<T: Num> From<Vec<Vec2<T>>> for Polyline<T> {
+    #[inline]
+    fn from(value: Vec<Vec2<T>>) -> Self {
+        Self::from_vec(value)
+    }
+}
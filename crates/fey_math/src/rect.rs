@@ -3,8 +3,8 @@ use crate::{
     impl_approx, impl_bytemuck, impl_casts, impl_interp, impl_serde, impl_tuple_arr, line,
     overlaps_on, vec2,
 };
-use std::fmt::{Display, Formatter};
-use std::ops::{Add, AddAssign, Sub, SubAssign};
+use core::fmt::{Display, Formatter};
+use core::ops::{Add, AddAssign, Sub, SubAssign};
 
 use super::Quad;
 
@@ -361,6 +361,58 @@ impl<T: Num> Rect<T> {
         }
         rect
     }
+
+    /// Shrink the rectangle by the amount on every side. Its size won't go below zero.
+    #[inline]
+    pub fn inset(&self, amount: impl Into<Vec2<T>>) -> Self {
+        let amount = amount.into();
+        rect(
+            self.x + T::min(amount.x, self.w / T::TWO),
+            self.y + T::min(amount.y, self.h / T::TWO),
+            self.w - T::min(self.w, amount.x + amount.x),
+            self.h - T::min(self.h, amount.y + amount.y),
+        )
+    }
+
+    /// Cut a strip off the top of the rectangle, returning the strip. The strip is never
+    /// taller than the rectangle was.
+    #[inline]
+    pub fn cut_top(&mut self, amount: T) -> Self {
+        let amount = T::clamp(amount, T::ZERO, self.h);
+        let strip = rect(self.x, self.y, self.w, amount);
+        self.y += amount;
+        self.h -= amount;
+        strip
+    }
+
+    /// Cut a strip off the bottom of the rectangle, returning the strip. The strip is never
+    /// taller than the rectangle was.
+    #[inline]
+    pub fn cut_bottom(&mut self, amount: T) -> Self {
+        let amount = T::clamp(amount, T::ZERO, self.h);
+        self.h -= amount;
+        rect(self.x, self.y + self.h, self.w, amount)
+    }
+
+    /// Cut a strip off the left of the rectangle, returning the strip. The strip is never
+    /// wider than the rectangle was.
+    #[inline]
+    pub fn cut_left(&mut self, amount: T) -> Self {
+        let amount = T::clamp(amount, T::ZERO, self.w);
+        let strip = rect(self.x, self.y, amount, self.h);
+        self.x += amount;
+        self.w -= amount;
+        strip
+    }
+
+    /// Cut a strip off the right of the rectangle, returning the strip. The strip is never
+    /// wider than the rectangle was.
+    #[inline]
+    pub fn cut_right(&mut self, amount: T) -> Self {
+        let amount = T::clamp(amount, T::ZERO, self.w);
+        self.w -= amount;
+        rect(self.x + self.w, self.y, amount, self.h)
+    }
 }
 
 impl<T: Signed> Rect<T> {
@@ -405,7 +457,7 @@ impl<T: Float> Rect<T> {
 
 impl<T: Display> Display for Rect<T> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.x.fmt(f)?;
         f.write_str(", ")?;
         self.y.fmt(f)?;
@@ -446,10 +498,10 @@ impl<T: Float> Shape<T> for Rect<T> {
     #[inline]
     fn project_point(&self, p: Vec2<T>) -> Vec2<T> {
         let projections = [
-            self.top_edge().project_point(p),
-            self.right_edge().project_point(p),
-            self.bottom_edge().project_point(p),
-            self.left_edge().project_point(p),
+            self.top_edge().project_point_clamped(p),
+            self.right_edge().project_point_clamped(p),
+            self.bottom_edge().project_point_clamped(p),
+            self.left_edge().project_point_clamped(p),
         ];
         // Find which edge projected point is nearest to the origin.
         let (i, _) = projections
@@ -572,6 +624,14 @@ impl<T: Float> Rect<T> {
     pub fn map_pos(&self, pos: Vec2<T>, target: &Rect<T>) -> Vec2<T> {
         target.min_pos() + target.size() * ((pos - self.min_pos()) / self.size().abs())
     }
+
+    /// Place a rectangle of the size inside this one. `align` is where it goes on each axis,
+    /// from `0` (left/top) to `1` (right/bottom), so `(0.5, 0.5)` centers it.
+    #[inline]
+    pub fn align(&self, size: impl Into<Vec2<T>>, align: impl Into<Vec2<T>>) -> Self {
+        let size = size.into();
+        Rect::pos_size(self.top_left() + (self.size() - size) * align.into(), size)
+    }
 }
 
 // ---------- ADD ----------
@@ -690,7 +750,7 @@ impl<T: Copy + SubAssign<T>> SubAssign<&Vec2<T>> for Rect<T> {
 
 macro_rules! impl_ops {
     ($op:ident $op_fn:ident $assign:ident $assign_fn:ident) => {
-        impl<T: Num> std::ops::$op<T> for Rect<T> {
+        impl<T: Num> core::ops::$op<T> for Rect<T> {
             type Output = Rect<T>;
 
             #[inline]
@@ -704,7 +764,7 @@ macro_rules! impl_ops {
             }
         }
 
-        impl<T: Num> std::ops::$op<T> for &Rect<T> {
+        impl<T: Num> core::ops::$op<T> for &Rect<T> {
             type Output = Rect<T>;
 
             #[inline]
@@ -718,7 +778,7 @@ macro_rules! impl_ops {
             }
         }
 
-        impl<T: Num> std::ops::$op<Vec2<T>> for Rect<T> {
+        impl<T: Num> core::ops::$op<Vec2<T>> for Rect<T> {
             type Output = Rect<T>;
 
             #[inline]
@@ -732,7 +792,7 @@ macro_rules! impl_ops {
             }
         }
 
-        impl<T: Num> std::ops::$op<Vec2<T>> for &Rect<T> {
+        impl<T: Num> core::ops::$op<Vec2<T>> for &Rect<T> {
             type Output = Rect<T>;
 
             #[inline]
@@ -746,7 +806,7 @@ macro_rules! impl_ops {
             }
         }
 
-        impl<T: Num> std::ops::$assign<T> for Rect<T> {
+        impl<T: Num> core::ops::$assign<T> for Rect<T> {
             #[inline]
             fn $assign_fn(&mut self, rhs: T) {
                 self.x.$assign_fn(rhs);
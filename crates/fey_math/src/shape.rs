@@ -162,7 +162,7 @@ impl<T: Float, S: AsRef<[Vec2<T>]>> Shape<T> for S {
         let mut min_proj = Vec2::ZERO;
         for i in 0..arr.len() {
             let edge = line(arr[i], arr[(i + 1) % arr.len()]);
-            let proj = edge.project_point(p);
+            let proj = edge.project_point_clamped(p);
             let dist = proj.sqr_dist(p);
             if dist < min_dist {
                 min_dist = dist;
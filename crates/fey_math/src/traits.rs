@@ -1,7 +1,10 @@
 //! Helper traits for scalar and math types.
 
-use std::fmt::{Debug, Display};
-use std::ops::{
+#[cfg(not(feature = "std"))]
+use crate::LibmExt;
+use core::cmp::Ordering;
+use core::fmt::{Debug, Display};
+use core::ops::{
     Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Sub, SubAssign,
 };
 
@@ -306,6 +309,16 @@ pub trait Float: Signed {
 
     /// Returns e^(x), (the exponential function).
     fn exp(x: Self) -> Self;
+
+    /// Returns `true` if the number is neither infinite nor NaN.
+    fn is_finite(x: Self) -> bool;
+
+    /// Compare two numbers with a total ordering, which places NaN above and below every
+    /// other number, so it can be used for sorting.
+    fn total_cmp(a: &Self, b: &Self) -> Ordering;
+
+    /// Converts an integer to the nearest number.
+    fn from_usize(x: usize) -> Self;
 }
 
 macro_rules! impl_num {
@@ -439,14 +452,14 @@ macro_rules! impl_float {
 
         $(
             impl Float for $name {
-                const PI: Self = std::$name::consts::PI;
-                const PI_OVER_2: Self = std::$name::consts::FRAC_PI_2;
-                const PI_OVER_4: Self = std::$name::consts::FRAC_PI_4;
-                const TAU: Self = std::$name::consts::TAU;
-                const SQRT_2: Self = std::$name::consts::SQRT_2;
-                const ONE_OVER_SQRT_2: Self = std::$name::consts::FRAC_1_SQRT_2;
-                const NEG_ONE_OVER_SQRT_2: Self = -std::$name::consts::FRAC_1_SQRT_2;
-                const NEG_SQRT_2: Self = -std::$name::consts::SQRT_2;
+                const PI: Self = core::$name::consts::PI;
+                const PI_OVER_2: Self = core::$name::consts::FRAC_PI_2;
+                const PI_OVER_4: Self = core::$name::consts::FRAC_PI_4;
+                const TAU: Self = core::$name::consts::TAU;
+                const SQRT_2: Self = core::$name::consts::SQRT_2;
+                const ONE_OVER_SQRT_2: Self = core::$name::consts::FRAC_1_SQRT_2;
+                const NEG_ONE_OVER_SQRT_2: Self = -core::$name::consts::FRAC_1_SQRT_2;
+                const NEG_SQRT_2: Self = -core::$name::consts::SQRT_2;
                 const EIGHTH: Self = 0.125;
                 const QUARTER: Self = 0.25;
                 const HALF: Self = 0.5;
@@ -491,6 +504,15 @@ macro_rules! impl_float {
 
                 #[inline]
                 fn exp(x: Self) -> Self { x.exp() }
+
+                #[inline]
+                fn is_finite(x: Self) -> bool { x.is_finite() }
+
+                #[inline]
+                fn total_cmp(a: &Self, b: &Self) -> Ordering { a.total_cmp(b) }
+
+                #[inline]
+                fn from_usize(x: usize) -> Self { x as Self }
             }
         )*
     };
@@ -0,0 +1,64 @@
+use crate::{Float, Vec2};
+use alloc::vec::Vec;
+
+/// Triangulate a simple polygon (convex or concave, without holes) using ear clipping.
+/// The points can be in either winding order. Returns the indices of each triangle's points.
+pub fn triangulate<T: Float>(points: &[Vec2<T>]) -> Vec<[usize; 3]> {
+    let n = points.len();
+    if n < 3 {
+        return Vec::new();
+    }
+
+    // make sure we walk the polygon in a consistent winding order
+    let area = (0..n).fold(T::ZERO, |sum, i| sum + points[i].cross(points[(i + 1) % n]));
+    let mut remaining: Vec<usize> = (0..n).collect();
+    if area < T::ZERO {
+        remaining.reverse();
+    }
+
+    let mut tris = Vec::with_capacity(n - 2);
+    let mut i = 0;
+    let mut misses = 0;
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let a = remaining[(i + len - 1) % len];
+        let b = remaining[i % len];
+        let c = remaining[(i + 1) % len];
+        if is_ear(points, &remaining, a, b, c) {
+            tris.push([a, b, c]);
+            remaining.remove(i % len);
+            misses = 0;
+        } else {
+            i += 1;
+            misses += 1;
+
+            // the polygon is degenerate or self-intersecting, so fall back to a fan
+            if misses > len {
+                break;
+            }
+        }
+    }
+    for k in 1..(remaining.len() - 1) {
+        tris.push([remaining[0], remaining[k], remaining[k + 1]]);
+    }
+    tris
+}
+
+fn is_ear<T: Float>(points: &[Vec2<T>], remaining: &[usize], a: usize, b: usize, c: usize) -> bool {
+    let (pa, pb, pc) = (points[a], points[b], points[c]);
+    if (pb - pa).cross(pc - pb) <= T::ZERO {
+        return false;
+    }
+    !remaining.iter().any(|&i| {
+        let p = points[i];
+        i != a
+            && i != b
+            && i != c
+            && p != pa
+            && p != pb
+            && p != pc
+            && (pb - pa).cross(p - pa) >= T::ZERO
+            && (pc - pb).cross(p - pb) >= T::ZERO
+            && (pa - pc).cross(p - pc) >= T::ZERO
+    })
+}
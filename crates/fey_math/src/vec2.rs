@@ -1,6 +1,6 @@
 use crate::{Float, Num, Signed, Vec3, impl_vec, vec3};
+use core::fmt::{Display, Formatter};
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
-use std::fmt::{Display, Formatter};
 
 pub type Vec2F = Vec2<f32>;
 pub type Vec2I = Vec2<i32>;
@@ -147,7 +147,7 @@ impl<T: Float> Vec2<T> {
 
 impl<T: Display> Display for Vec2<T> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.x.fmt(f)?;
         f.write_str(", ")?;
         self.y.fmt(f)
@@ -1,5 +1,5 @@
 use crate::{Num, Signed, Vec2, Vec4, impl_vec, vec2, vec4};
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
 pub type Vec3F = Vec3<f32>;
 pub type Vec3I = Vec3<i32>;
@@ -153,7 +153,7 @@ impl<T: Signed> Vec3<T> {
 
 impl<T: Display> Display for Vec3<T> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.x.fmt(f)?;
         f.write_str(", ")?;
         self.y.fmt(f)?;
@@ -1,5 +1,5 @@
 use crate::{Num, Vec2, Vec3, impl_vec, vec2, vec3};
-use std::fmt::{Display, Formatter};
+use core::fmt::{Display, Formatter};
 
 pub type Vec4F = Vec4<f32>;
 pub type Vec4I = Vec4<i32>;
@@ -151,7 +151,7 @@ impl<T: Num> Vec4<T> {
 
 impl<T: Display> Display for Vec4<T> {
     #[inline]
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
         self.x.fmt(f)?;
         f.write_str(", ")?;
         self.y.fmt(f)?;
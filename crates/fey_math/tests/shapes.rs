@@ -0,0 +1,54 @@
+//! Property tests for the shape overlap, extraction, and raycasting invariants.
+//!
+//! These use the generators from the `fuzzing` feature, so run them with:
+//!
+//! ```text
+//! cargo test -p fey_math --features fuzzing
+//! ```
+
+#![cfg(feature = "fuzzing")]
+
+use arbitrary::Unstructured;
+use fey_math::fuzzing::{self, check_extraction, check_overlap_symmetry, check_raycast};
+use fey_math::{DynShapeF, RayF};
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::test_runner::FileFailurePersistence;
+
+fn shape() -> impl Strategy<Value = DynShapeF> {
+    vec(any::<u8>(), 64).prop_map(|data| fuzzing::shape(&mut Unstructured::new(&data)).unwrap())
+}
+
+fn ray() -> impl Strategy<Value = RayF> {
+    vec(any::<u8>(), 8).prop_map(|data| fuzzing::ray(&mut Unstructured::new(&data)).unwrap())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig {
+        cases: 2000,
+        failure_persistence: Some(Box::new(FileFailurePersistence::WithSource(
+            "proptest-regressions"
+        ))),
+        ..ProptestConfig::default()
+    })]
+
+    #[test]
+    fn overlap_is_symmetric(a in shape(), b in shape()) {
+        check_overlap_symmetry(&a, &b);
+    }
+
+    #[test]
+    fn extraction_from_self(a in shape()) {
+        check_extraction(&a, &a);
+    }
+
+    #[test]
+    fn extraction_resolves_overlap(a in shape(), b in shape()) {
+        check_extraction(&a, &b);
+    }
+
+    #[test]
+    fn raycast_hits_surface(shape in shape(), ray in ray()) {
+        check_raycast(&shape, &ray);
+    }
+}
@@ -1,9 +1,15 @@
 //! A 2D rectangle packer.
 
 mod item;
+mod pack_heuristic;
+mod pack_report;
 mod packed;
+mod packed_pages;
 mod rect_packer;
 
 pub use item::*;
+pub use pack_heuristic::*;
+pub use pack_report::*;
 pub use packed::*;
+pub use packed_pages::*;
 pub use rect_packer::*;
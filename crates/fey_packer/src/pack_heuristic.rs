@@ -0,0 +1,35 @@
+/// The method a [`RectPacker`](crate::RectPacker) uses to place items.
+///
+/// Different heuristics suit different sets of items, so tooling can try them all with
+/// [`RectPacker::pack_best`](crate::RectPacker::pack_best) and keep the tightest result.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub enum PackHeuristic {
+    /// Grow a binary tree of free space from the largest item, expanding right or down as
+    /// needed. Fast, and works well when items are similarly sized.
+    #[default]
+    Tree,
+
+    /// Place each item in the free rectangle it leaves the least area in.
+    BestAreaFit,
+
+    /// Place each item in the free rectangle it fits most snugly along its shorter side.
+    BestShortSideFit,
+
+    /// Place each item as low and then as far left as possible.
+    BottomLeft,
+
+    /// Place each item on top of a skyline of placed items, keeping the skyline as low as
+    /// possible. Fast and good for items of similar heights, such as glyphs.
+    Skyline,
+}
+
+impl PackHeuristic {
+    /// All the heuristics.
+    pub const ALL: [Self; 5] = [
+        Self::Tree,
+        Self::BestAreaFit,
+        Self::BestShortSideFit,
+        Self::BottomLeft,
+        Self::Skyline,
+    ];
+}
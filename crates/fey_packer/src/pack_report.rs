@@ -0,0 +1,35 @@
+/// Statistics about how well a set of items was packed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct PackReport {
+    /// How many pages the items were packed into.
+    pub pages: usize,
+
+    /// Total area of all the pages.
+    pub page_area: u64,
+
+    /// Total area of the packed items, not including padding or spacing.
+    pub used_area: u64,
+}
+
+impl PackReport {
+    /// How much of the page area is covered by items, from `0` to `1`.
+    #[inline]
+    pub fn occupancy(&self) -> f32 {
+        match self.page_area {
+            0 => 0.0,
+            area => self.used_area as f32 / area as f32,
+        }
+    }
+
+    /// Area of the pages not covered by items.
+    #[inline]
+    pub fn wasted_area(&self) -> u64 {
+        self.page_area - self.used_area
+    }
+
+    /// If this packing is better than the other, having fewer pages, or less wasted area.
+    #[inline]
+    pub fn is_better_than(&self, other: &Self) -> bool {
+        (self.pages, self.wasted_area()) < (other.pages, other.wasted_area())
+    }
+}
@@ -4,4 +4,8 @@ use fey_math::Vec2U;
 pub struct Packed<T> {
     pub data: T,
     pub pos: Vec2U,
+
+    /// The page the item was packed into, which is always `0` unless
+    /// [`RectPacker::pack_pages`](crate::RectPacker::pack_pages) was used.
+    pub page: usize,
 }
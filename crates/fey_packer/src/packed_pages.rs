@@ -0,0 +1,14 @@
+use crate::{PackReport, Packed};
+use fey_math::Vec2U;
+
+/// Items packed across one or more pages.
+pub struct PackedPages<T> {
+    /// The size of each page.
+    pub pages: Vec<Vec2U>,
+
+    /// The packed items, in no particular order.
+    pub items: Vec<Packed<T>>,
+
+    /// Statistics about the packing.
+    pub report: PackReport,
+}
@@ -1,5 +1,5 @@
-use crate::{Item, Packed};
-use fey_math::{RectU, Vec2U};
+use crate::{Item, PackHeuristic, PackReport, Packed, PackedPages};
+use fey_math::{RectU, Vec2U, vec2};
 
 /// A rectangle packer.
 pub struct RectPacker {
@@ -14,6 +14,9 @@ pub struct RectPacker {
 
     /// Spacing to include between items.
     pub spacing: u32,
+
+    /// The method used to place items.
+    pub heuristic: PackHeuristic,
 }
 
 impl Default for RectPacker {
@@ -29,12 +32,14 @@ impl RectPacker {
     /// - `power_of_two = true`
     /// - `padding = 0`
     /// - `spacing = 0`
+    /// - `heuristic = Tree`
     pub const fn new() -> Self {
         Self {
             max_size: 4096,
             power_of_two: true,
             padding: 0,
             spacing: 0,
+            heuristic: PackHeuristic::Tree,
         }
     }
 
@@ -63,37 +68,125 @@ impl RectPacker {
         self
     }
 
+    /// Set the method used to place items.
+    pub const fn with_heuristic(mut self, heuristic: PackHeuristic) -> Self {
+        self.heuristic = heuristic;
+        self
+    }
+
     /// Pack a collection of rectangles.
     ///
     /// On success, this function will return a list of all the packed
     /// items and their pack locations, and the size of the rectangle
     /// that they were all able to fit in.
-    pub fn pack<T>(&self, mut items: Vec<Item<T>>) -> Option<(Vec2U, Vec<Packed<T>>)> {
-        // sort the items by height before packing
-        items.sort_by_key(|item| item.size.x.max(item.size.x));
+    pub fn pack<T>(&self, items: Vec<Item<T>>) -> Option<(Vec2U, Vec<Packed<T>>)> {
+        let pages = self.pack_pages(items)?;
+        match pages.pages.len() {
+            0 => Some((Vec2U::ZERO, pages.items)),
+            1 => Some((pages.pages[0], pages.items)),
+            _ => None,
+        }
+    }
 
-        let mut packed = Vec::new();
-        let mut nodes = Vec::new();
-        let extra = Vec2U::splat(self.padding * 2 + self.spacing);
+    /// Pack a collection of rectangles, starting new pages whenever they don't all fit in
+    /// one. Fails only if an item is too large to fit on a page by itself.
+    pub fn pack_pages<T>(&self, items: Vec<Item<T>>) -> Option<PackedPages<T>> {
+        let sizes: Vec<Vec2U> = items.iter().map(|item| item.size).collect();
+        let layout = self.layout(self.heuristic, &sizes)?;
+        Some(layout.apply(items))
+    }
 
-        // fetch the largest item to pack
-        if let Some(largest) = items.last() {
-            // if the largest item is larger than our max size, don't bother packing
-            if largest.size.x + self.padding * 2 > self.max_size
-                || largest.size.y + self.padding * 2 > self.max_size
+    /// Pack a collection of rectangles with each of the heuristics, returning the best
+    /// result (the one with the fewest pages, and then the least wasted area) and the
+    /// heuristic that produced it.
+    pub fn pack_best<T>(
+        &self,
+        items: Vec<Item<T>>,
+        heuristics: &[PackHeuristic],
+    ) -> Option<(PackHeuristic, PackedPages<T>)> {
+        let sizes: Vec<Vec2U> = items.iter().map(|item| item.size).collect();
+        let mut best: Option<(PackHeuristic, Layout)> = None;
+        for &heuristic in heuristics {
+            let Some(layout) = self.layout(heuristic, &sizes) else {
+                continue;
+            };
+            if best
+                .as_ref()
+                .is_none_or(|(_, b)| layout.report.is_better_than(&b.report))
             {
+                best = Some((heuristic, layout));
+            }
+        }
+        best.map(|(heuristic, layout)| (heuristic, layout.apply(items)))
+    }
+
+    /// Find where each item goes, without moving the items themselves.
+    fn layout(&self, heuristic: PackHeuristic, sizes: &[Vec2U]) -> Option<Layout> {
+        let extra = Vec2U::splat(self.padding * 2 + self.spacing);
+
+        // if any item is larger than our max size, don't bother packing
+        if sizes.iter().any(|size| {
+            size.x + self.padding * 2 > self.max_size || size.y + self.padding * 2 > self.max_size
+        }) {
+            return None;
+        }
+
+        // pack the largest items first, with ties broken by index so results are deterministic
+        let padded: Vec<Vec2U> = sizes.iter().map(|&size| size + extra).collect();
+        let mut order: Vec<usize> = (0..sizes.len()).collect();
+        order.sort_by_key(|&i| {
+            let size = padded[i];
+            (std::cmp::Reverse((size.x.max(size.y), size.x * size.y)), i)
+        });
+
+        let mut layout = Layout {
+            pages: Vec::new(),
+            places: vec![(0, Vec2U::ZERO); sizes.len()],
+            report: PackReport::default(),
+        };
+        while !order.is_empty() {
+            let (size, placed, leftover) = match heuristic {
+                PackHeuristic::Tree => self.tree_page(&order, &padded),
+                _ => self.fixed_page(heuristic, &order, &padded),
+            };
+
+            // an item that can't be placed on an empty page will never fit
+            if placed.is_empty() {
                 return None;
             }
 
-            // if it will fit, make the root node
-            packed.reserve(items.len());
-            nodes.reserve(items.len() * 3);
-            nodes.push(Node::new(RectU::sized(largest.size + extra)));
-        } else {
-            // if we have no items to pack, return successfully
-            return Some((Vec2U::ZERO, packed));
+            let page = layout.pages.len();
+            for (i, pos) in placed {
+                layout.places[i] = (page, pos + Vec2U::splat(self.padding));
+            }
+            let size = if self.power_of_two {
+                size.map(|x| x.next_power_of_two())
+            } else {
+                size
+            };
+            layout.pages.push(size);
+            order = leftover;
         }
 
+        layout.report = PackReport {
+            pages: layout.pages.len(),
+            page_area: layout
+                .pages
+                .iter()
+                .map(|size| size.x as u64 * size.y as u64)
+                .sum(),
+            used_area: sizes.iter().map(|size| size.x as u64 * size.y as u64).sum(),
+        };
+        Some(layout)
+    }
+
+    /// Pack as many items as fit into a page that grows from the largest item.
+    fn tree_page(&self, order: &[usize], sizes: &[Vec2U]) -> PageResult {
+        let mut placed = Vec::new();
+        let mut leftover = Vec::new();
+        let mut nodes = Vec::with_capacity(order.len() * 3);
+        nodes.push(Node::new(RectU::sized(sizes[order[0]])));
+
         let mut root: usize = 0;
 
         fn new_node(nodes: &mut Vec<Node>, x: u32, y: u32, w: u32, h: u32) -> usize {
@@ -116,8 +209,8 @@ impl RectPacker {
             }
         }
 
-        while let Some(item) = items.pop() {
-            let size = item.size + extra;
+        for &i in order {
+            let size = sizes[i];
 
             let node = match find(&nodes, root, &size) {
                 Some(node) => node,
@@ -127,7 +220,8 @@ impl RectPacker {
                     let can_grow_d = size.x <= root_rect.w && root_rect.h + size.y < self.max_size;
                     let can_grow_r = size.y <= root_rect.h && root_rect.w + size.x < self.max_size;
                     if !can_grow_d && !can_grow_r {
-                        return None;
+                        leftover.push(i);
+                        continue;
                     }
 
                     let should_grow_r = can_grow_r && root_rect.h >= root_rect.w + size.x;
@@ -170,20 +264,213 @@ impl RectPacker {
                 size.y,
             ));
 
-            packed.push(Packed {
-                data: item.data,
-                pos: node_rect.top_left() + Vec2U::splat(self.padding),
+            placed.push((i, node_rect.top_left()));
+        }
+
+        (nodes[root].rect.size(), placed, leftover)
+    }
+
+    /// Pack as many items as fit into the smallest fixed-size page that holds them all,
+    /// doubling the page size until it does or it reaches the maximum size.
+    fn fixed_page(&self, heuristic: PackHeuristic, order: &[usize], sizes: &[Vec2U]) -> PageResult {
+        let area: u64 = order
+            .iter()
+            .map(|&i| sizes[i].x as u64 * sizes[i].y as u64)
+            .sum();
+        let side = (area as f64).sqrt().ceil() as u32;
+        let largest = order.iter().fold(Vec2U::ZERO, |max, &i| max.max(sizes[i]));
+        let mut bin = largest
+            .max(Vec2U::splat(side))
+            .map(|x| x.next_power_of_two().min(self.max_size));
+        loop {
+            let (placed, leftover) = match heuristic {
+                PackHeuristic::Skyline => skyline(bin, order, sizes),
+                _ => max_rects(heuristic, bin, order, sizes),
+            };
+            if leftover.is_empty() || (bin.x == self.max_size && bin.y == self.max_size) {
+                let size = placed
+                    .iter()
+                    .fold(Vec2U::ZERO, |max, &(i, pos)| max.max(pos + sizes[i]));
+                return (size, placed, leftover);
+            }
+            if bin.x <= bin.y && bin.x < self.max_size {
+                bin.x = (bin.x * 2).min(self.max_size);
+            } else {
+                bin.y = (bin.y * 2).min(self.max_size);
+            }
+        }
+    }
+}
+
+/// The size of a page, the items placed on it, and the items that didn't fit.
+type PageResult = (Vec2U, Vec<(usize, Vec2U)>, Vec<usize>);
+
+/// The page and position of every item.
+struct Layout {
+    pages: Vec<Vec2U>,
+    places: Vec<(usize, Vec2U)>,
+    report: PackReport,
+}
+
+impl Layout {
+    fn apply<T>(self, items: Vec<Item<T>>) -> PackedPages<T> {
+        PackedPages {
+            items: items
+                .into_iter()
+                .zip(self.places)
+                .map(|(item, (page, pos))| Packed {
+                    data: item.data,
+                    pos,
+                    page,
+                })
+                .collect(),
+            pages: self.pages,
+            report: self.report,
+        }
+    }
+}
+
+/// Place items into the bin by tracking the maximal free rectangles left between them.
+fn max_rects(
+    heuristic: PackHeuristic,
+    bin: Vec2U,
+    order: &[usize],
+    sizes: &[Vec2U],
+) -> (Vec<(usize, Vec2U)>, Vec<usize>) {
+    let mut placed = Vec::new();
+    let mut leftover = Vec::new();
+    let mut free = vec![RectU::sized(bin)];
+    for &i in order {
+        let size = sizes[i];
+        let best = free
+            .iter()
+            .filter(|f| size.x <= f.w && size.y <= f.h)
+            .map(|f| {
+                let (dw, dh) = (f.w - size.x, f.h - size.y);
+                let score = match heuristic {
+                    PackHeuristic::BestAreaFit => (
+                        f.w as u64 * f.h as u64 - size.x as u64 * size.y as u64,
+                        dw.min(dh) as u64,
+                    ),
+                    PackHeuristic::BestShortSideFit => (dw.min(dh) as u64, dw.max(dh) as u64),
+                    _ => ((f.y + size.y) as u64, f.x as u64),
+                };
+                (score, f.top_left())
+            })
+            .min_by_key(|&(score, pos)| (score, pos.y, pos.x));
+        let Some((_, pos)) = best else {
+            leftover.push(i);
+            continue;
+        };
+        placed.push((i, pos));
+
+        // split every free rectangle the item overlaps into the parts left around it
+        let used = RectU::pos_size(pos, size);
+        let mut j = 0;
+        while j < free.len() {
+            let f = free[j];
+            if !f.overlaps(&used) {
+                j += 1;
+                continue;
+            }
+            free.swap_remove(j);
+            if used.x > f.x {
+                free.push(RectU::new(f.x, f.y, used.x - f.x, f.h));
+            }
+            if used.right() < f.right() {
+                free.push(RectU::new(used.right(), f.y, f.right() - used.right(), f.h));
+            }
+            if used.y > f.y {
+                free.push(RectU::new(f.x, f.y, f.w, used.y - f.y));
+            }
+            if used.bottom() < f.bottom() {
+                free.push(RectU::new(
+                    f.x,
+                    used.bottom(),
+                    f.w,
+                    f.bottom() - used.bottom(),
+                ));
+            }
+        }
+
+        // remove free rectangles that are inside of others
+        let mut j = 0;
+        while j < free.len() {
+            let contained = free.iter().enumerate().any(|(k, other)| {
+                k != j && other.contains_rect(&free[j]) && (free[j] != *other || k < j)
             });
+            if contained {
+                free.swap_remove(j);
+            } else {
+                j += 1;
+            }
         }
+    }
+    (placed, leftover)
+}
+
+/// Place items into the bin on top of a skyline, as low as possible.
+fn skyline(bin: Vec2U, order: &[usize], sizes: &[Vec2U]) -> (Vec<(usize, Vec2U)>, Vec<usize>) {
+    let mut placed = Vec::new();
+    let mut leftover = Vec::new();
 
-        let size = if self.power_of_two {
-            nodes[root].rect.size().map(|x| x.next_power_of_two())
-        } else {
-            nodes[root].rect.size()
+    // each segment is `(x, y, width)`, sorted by x and covering the whole bin width
+    let mut segs = vec![(0, 0, bin.x)];
+    for &i in order {
+        let size = sizes[i];
+
+        // find the segment to start at that puts the item's top the lowest
+        let mut best: Option<(u32, u32, usize)> = None;
+        for start in 0..segs.len() {
+            let x = segs[start].0;
+            if x + size.x > bin.x {
+                break;
+            }
+            let mut y = 0;
+            let mut covered = 0;
+            for seg in &segs[start..] {
+                if covered >= size.x {
+                    break;
+                }
+                y = y.max(seg.1);
+                covered += seg.2;
+            }
+            if y + size.y <= bin.y && best.is_none_or(|(by, bx, _)| (y, x) < (by, bx)) {
+                best = Some((y, x, start));
+            }
+        }
+        let Some((y, x, start)) = best else {
+            leftover.push(i);
+            continue;
         };
+        placed.push((i, vec2(x, y)));
 
-        Some((size, packed))
+        // raise the skyline under the item, trimming the segments it covers
+        segs.insert(start, (x, y + size.y, size.x));
+        let right = x + size.x;
+        let j = start + 1;
+        while j < segs.len() && segs[j].0 < right {
+            let (sx, sy, sw) = segs[j];
+            if sx + sw <= right {
+                segs.remove(j);
+            } else {
+                segs[j] = (right, sy, sx + sw - right);
+                break;
+            }
+        }
+
+        // merge neighboring segments of the same height
+        let mut j = 0;
+        while j + 1 < segs.len() {
+            if segs[j].1 == segs[j + 1].1 {
+                segs[j].2 += segs[j + 1].2;
+                segs.remove(j + 1);
+            } else {
+                j += 1;
+            }
+        }
     }
+    (placed, leftover)
 }
 
 struct Node {
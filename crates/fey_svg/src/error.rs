@@ -0,0 +1,21 @@
+use std::str::Utf8Error;
+use thiserror::Error;
+
+/// An SVG parsing error.
+#[derive(Debug, Error)]
+pub enum SvgError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Utf8(#[from] Utf8Error),
+
+    #[error("{0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("no root <svg> element")]
+    MissingRoot,
+
+    #[error("invalid path data at byte {0}")]
+    InvalidPathData(usize),
+}
@@ -0,0 +1,18 @@
+//! SVG file loader.
+//!
+//! Loads the static shapes of an SVG file (paths, rects, circles, ellipses,
+//! lines, polylines and polygons) along with their fills, strokes and
+//! transforms. The shapes can be used as vector [paths](fey_math::Path), or
+//! the whole file can be rasterized into an [image](fey_img::ImageRgba8) at
+//! any scale.
+//!
+//! Text, gradients, patterns, filters, masks and clip paths are not supported.
+
+mod error;
+mod parse;
+mod path_data;
+mod raster;
+mod svg;
+
+pub use error::*;
+pub use svg::*;
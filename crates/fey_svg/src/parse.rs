@@ -0,0 +1,512 @@
+use crate::path_data::{Lexer, parse_path_data};
+use crate::{SvgError, SvgFill, SvgShape, SvgStroke};
+use fey_color::Rgba8;
+use fey_math::{Affine2F, FillRule, PathF, Vec2F, affine2, mat2, vec2};
+use quick_xml::events::BytesStart;
+
+/// Magic number for approximating a quarter circle with a cubic bézier.
+const KAPPA: f32 = 0.552_284_8;
+
+/// An element's attributes, with any inline `style` declarations applied on top.
+pub(crate) struct Attrs(Vec<(String, String)>);
+
+impl Attrs {
+    pub fn from_elem(elem: &BytesStart) -> Result<Self, SvgError> {
+        let mut attrs = Vec::new();
+        let mut style = None;
+        for attr in elem.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+            let value = attr.unescape_value()?.into_owned();
+            if key == "style" {
+                style = Some(value);
+            } else {
+                attrs.push((key, value));
+            }
+        }
+        if let Some(style) = style {
+            for decl in style.split(';') {
+                if let Some((key, value)) = decl.split_once(':') {
+                    attrs.push((key.trim().to_string(), value.trim().to_string()));
+                }
+            }
+        }
+        Ok(Self(attrs))
+    }
+
+    /// Get the value of an attribute. Later declarations override earlier ones.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get an attribute as a length, ignoring units.
+    pub fn len(&self, key: &str) -> Option<f32> {
+        self.get(key).and_then(parse_len)
+    }
+
+    /// Get an attribute as a length, ignoring units, or zero if it isn't present.
+    pub fn len_or_zero(&self, key: &str) -> f32 {
+        self.len(key).unwrap_or(0.0)
+    }
+}
+
+/// Inherited presentation state.
+#[derive(Debug, Clone)]
+pub(crate) struct Style {
+    transform: Affine2F,
+    fill: Option<Rgba8>,
+    fill_rule: FillRule,
+    fill_opacity: f32,
+    stroke: Option<Rgba8>,
+    stroke_width: f32,
+    stroke_opacity: f32,
+    dashes: Vec<f32>,
+    dash_offset: f32,
+    opacity: f32,
+    visible: bool,
+}
+
+impl Style {
+    pub fn root(transform: Affine2F) -> Self {
+        Self {
+            transform,
+            fill: Some(Rgba8::BLACK),
+            fill_rule: FillRule::NonZero,
+            fill_opacity: 1.0,
+            stroke: None,
+            stroke_width: 1.0,
+            stroke_opacity: 1.0,
+            dashes: Vec::new(),
+            dash_offset: 0.0,
+            opacity: 1.0,
+            visible: true,
+        }
+    }
+
+    /// Create a child style by applying the element's attributes to this one.
+    pub fn with_attrs(&self, attrs: &Attrs) -> Self {
+        let mut style = self.clone();
+        if let Some(transform) = attrs.get("transform") {
+            style.transform = style.transform * parse_transform(transform);
+        }
+        if let Some(fill) = attrs.get("fill") {
+            style.fill = parse_paint(fill, style.fill);
+        }
+        match attrs.get("fill-rule") {
+            Some("evenodd") => style.fill_rule = FillRule::EvenOdd,
+            Some("nonzero") => style.fill_rule = FillRule::NonZero,
+            _ => {}
+        }
+        if let Some(opacity) = attrs.get("fill-opacity").and_then(parse_opacity) {
+            style.fill_opacity = opacity;
+        }
+        if let Some(stroke) = attrs.get("stroke") {
+            style.stroke = parse_paint(stroke, style.stroke);
+        }
+        if let Some(width) = attrs.len("stroke-width") {
+            style.stroke_width = width;
+        }
+        if let Some(opacity) = attrs.get("stroke-opacity").and_then(parse_opacity) {
+            style.stroke_opacity = opacity;
+        }
+        if let Some(dashes) = attrs.get("stroke-dasharray") {
+            style.dashes = parse_dashes(dashes);
+        }
+        if let Some(offset) = attrs.len("stroke-dashoffset") {
+            style.dash_offset = offset;
+        }
+        if let Some(opacity) = attrs.get("opacity").and_then(parse_opacity) {
+            style.opacity *= opacity;
+        }
+        match attrs.get("visibility") {
+            Some("hidden" | "collapse") => style.visible = false,
+            Some("visible") => style.visible = true,
+            _ => {}
+        }
+        style
+    }
+
+    /// Create a shape from the path using this style, or `None` if it wouldn't be visible.
+    pub fn shape(&self, path: PathF) -> Option<SvgShape> {
+        if !self.visible {
+            return None;
+        }
+
+        let fill = self
+            .fill
+            .map(|color| SvgFill {
+                color: with_opacity(color, self.fill_opacity * self.opacity),
+                rule: self.fill_rule,
+            })
+            .filter(|fill| fill.color.a > 0);
+
+        // strokes are scaled along with the shape
+        let scale = self.transform.matrix.determinant().abs().sqrt();
+        let stroke = self
+            .stroke
+            .map(|color| SvgStroke {
+                color: with_opacity(color, self.stroke_opacity * self.opacity),
+                width: self.stroke_width * scale,
+                dashes: self.dashes.iter().map(|d| d * scale).collect(),
+                dash_offset: self.dash_offset * scale,
+            })
+            .filter(|stroke| stroke.color.a > 0 && stroke.width > 0.0);
+
+        if fill.is_none() && stroke.is_none() {
+            return None;
+        }
+
+        let path = path.transform_by(|p| self.transform.transform_pos2(p));
+        Some(SvgShape { path, fill, stroke })
+    }
+}
+
+/// Elements whose contents are not drawn directly.
+pub(crate) fn skip_element(name: &[u8]) -> bool {
+    matches!(
+        name,
+        b"defs"
+            | b"symbol"
+            | b"clipPath"
+            | b"mask"
+            | b"pattern"
+            | b"marker"
+            | b"linearGradient"
+            | b"radialGradient"
+            | b"filter"
+            | b"style"
+            | b"script"
+            | b"text"
+            | b"title"
+            | b"desc"
+            | b"metadata"
+            | b"foreignObject"
+    )
+}
+
+/// Get the document size from the root element, and the transform mapping its view box onto it.
+pub(crate) fn view_box_transform(attrs: &Attrs) -> (Vec2F, Affine2F) {
+    let view_box = attrs
+        .get("viewBox")
+        .map(parse_numbers)
+        .filter(|vb| vb.len() == 4 && vb[2] > 0.0 && vb[3] > 0.0);
+    let width = attrs.len("width").filter(|w| *w > 0.0);
+    let height = attrs.len("height").filter(|h| *h > 0.0);
+
+    let Some(vb) = view_box else {
+        let size = vec2(width.unwrap_or(100.0), height.unwrap_or(100.0));
+        return (size, Affine2F::IDENTITY);
+    };
+
+    // missing dimensions are taken from the view box, preserving its aspect ratio
+    let size = match (width, height) {
+        (Some(w), Some(h)) => vec2(w, h),
+        (Some(w), None) => vec2(w, w * vb[3] / vb[2]),
+        (None, Some(h)) => vec2(h * vb[2] / vb[3], h),
+        (None, None) => vec2(vb[2], vb[3]),
+    };
+
+    let scale = vec2(size.x / vb[2], size.y / vb[3]);
+    let transform = if attrs.get("preserveAspectRatio") == Some("none") {
+        Affine2F::scale(scale)
+    } else {
+        // the default behavior: fit the view box inside the document and center it
+        let s = scale.x.min(scale.y);
+        let offset = (size - vec2(vb[2], vb[3]) * s) * 0.5;
+        Affine2F::translation(offset) * Affine2F::scale_of(s)
+    };
+    let transform = transform * Affine2F::translation(vec2(-vb[0], -vb[1]));
+    (size, transform)
+}
+
+/// Build the path for a shape element, or `None` if the element isn't a shape.
+pub(crate) fn shape_path(name: &[u8], attrs: &Attrs) -> Result<Option<PathF>, SvgError> {
+    let mut path = PathF::new();
+    match name {
+        b"path" => {
+            if let Some(d) = attrs.get("d") {
+                parse_path_data(d, &mut path)?;
+            }
+        }
+        b"rect" => {
+            let pos = vec2(attrs.len_or_zero("x"), attrs.len_or_zero("y"));
+            let size = vec2(attrs.len_or_zero("width"), attrs.len_or_zero("height"));
+            if size.x <= 0.0 || size.y <= 0.0 {
+                return Ok(None);
+            }
+            let (rx, ry) = match (attrs.len("rx"), attrs.len("ry")) {
+                (Some(rx), Some(ry)) => (rx, ry),
+                (Some(r), None) | (None, Some(r)) => (r, r),
+                (None, None) => (0.0, 0.0),
+            };
+            let r = vec2(rx.clamp(0.0, size.x * 0.5), ry.clamp(0.0, size.y * 0.5));
+            rect_path(&mut path, pos, size, r);
+        }
+        b"circle" => {
+            let center = vec2(attrs.len_or_zero("cx"), attrs.len_or_zero("cy"));
+            let r = attrs.len_or_zero("r");
+            if r <= 0.0 {
+                return Ok(None);
+            }
+            ellipse_path(&mut path, center, vec2(r, r));
+        }
+        b"ellipse" => {
+            let center = vec2(attrs.len_or_zero("cx"), attrs.len_or_zero("cy"));
+            let r = vec2(attrs.len_or_zero("rx"), attrs.len_or_zero("ry"));
+            if r.x <= 0.0 || r.y <= 0.0 {
+                return Ok(None);
+            }
+            ellipse_path(&mut path, center, r);
+        }
+        b"line" => {
+            path.move_to(vec2(attrs.len_or_zero("x1"), attrs.len_or_zero("y1")))
+                .line_to(vec2(attrs.len_or_zero("x2"), attrs.len_or_zero("y2")));
+        }
+        b"polyline" | b"polygon" => {
+            let nums = attrs.get("points").map(parse_numbers).unwrap_or_default();
+            let mut points = nums.chunks_exact(2).map(|p| vec2(p[0], p[1]));
+            if let Some(first) = points.next() {
+                path.move_to(first);
+                for p in points {
+                    path.line_to(p);
+                }
+                if name == b"polygon" {
+                    path.close();
+                }
+            }
+        }
+        _ => return Ok(None),
+    }
+    Ok((!path.is_empty()).then_some(path))
+}
+
+fn rect_path(path: &mut PathF, pos: Vec2F, size: Vec2F, r: Vec2F) {
+    let (x0, y0) = (pos.x, pos.y);
+    let (x1, y1) = (pos.x + size.x, pos.y + size.y);
+    if r.x <= 0.0 || r.y <= 0.0 {
+        path.move_to(vec2(x0, y0))
+            .line_to(vec2(x1, y0))
+            .line_to(vec2(x1, y1))
+            .line_to(vec2(x0, y1))
+            .close();
+        return;
+    }
+    let k = r * (1.0 - KAPPA);
+    path.move_to(vec2(x0 + r.x, y0))
+        .line_to(vec2(x1 - r.x, y0))
+        .cubic_to(vec2(x1 - k.x, y0), vec2(x1, y0 + k.y), vec2(x1, y0 + r.y))
+        .line_to(vec2(x1, y1 - r.y))
+        .cubic_to(vec2(x1, y1 - k.y), vec2(x1 - k.x, y1), vec2(x1 - r.x, y1))
+        .line_to(vec2(x0 + r.x, y1))
+        .cubic_to(vec2(x0 + k.x, y1), vec2(x0, y1 - k.y), vec2(x0, y1 - r.y))
+        .line_to(vec2(x0, y0 + r.y))
+        .cubic_to(vec2(x0, y0 + k.y), vec2(x0 + k.x, y0), vec2(x0 + r.x, y0))
+        .close();
+}
+
+fn ellipse_path(path: &mut PathF, c: Vec2F, r: Vec2F) {
+    let k = r * KAPPA;
+    path.move_to(vec2(c.x + r.x, c.y))
+        .cubic_to(
+            vec2(c.x + r.x, c.y + k.y),
+            vec2(c.x + k.x, c.y + r.y),
+            vec2(c.x, c.y + r.y),
+        )
+        .cubic_to(
+            vec2(c.x - k.x, c.y + r.y),
+            vec2(c.x - r.x, c.y + k.y),
+            vec2(c.x - r.x, c.y),
+        )
+        .cubic_to(
+            vec2(c.x - r.x, c.y - k.y),
+            vec2(c.x - k.x, c.y - r.y),
+            vec2(c.x, c.y - r.y),
+        )
+        .cubic_to(
+            vec2(c.x + k.x, c.y - r.y),
+            vec2(c.x + r.x, c.y - k.y),
+            vec2(c.x + r.x, c.y),
+        )
+        .close();
+}
+
+/// Parse a length, ignoring its units.
+fn parse_len(s: &str) -> Option<f32> {
+    Lexer::new(s).number()
+}
+
+/// Parse a list of numbers separated by whitespace and/or commas.
+fn parse_numbers(s: &str) -> Vec<f32> {
+    let mut lexer = Lexer::new(s);
+    let mut nums = Vec::new();
+    while let Some(n) = lexer.number() {
+        nums.push(n);
+    }
+    nums
+}
+
+fn parse_opacity(s: &str) -> Option<f32> {
+    let s = s.trim();
+    let value = match s.strip_suffix('%') {
+        Some(pct) => pct.trim().parse::<f32>().ok()? / 100.0,
+        None => s.parse::<f32>().ok()?,
+    };
+    Some(value.clamp(0.0, 1.0))
+}
+
+fn parse_dashes(s: &str) -> Vec<f32> {
+    if s.trim() == "none" {
+        return Vec::new();
+    }
+    let mut dashes = parse_numbers(s);
+    if dashes.iter().any(|d| *d < 0.0) || dashes.iter().all(|d| *d == 0.0) {
+        return Vec::new();
+    }
+
+    // an odd number of values is repeated to make it even
+    if dashes.len() % 2 == 1 {
+        dashes.extend_from_within(..);
+    }
+    dashes
+}
+
+fn with_opacity(color: Rgba8, opacity: f32) -> Rgba8 {
+    let a = (color.a as f32 * opacity).round().clamp(0.0, 255.0) as u8;
+    Rgba8::new(color.r, color.g, color.b, a)
+}
+
+/// Parse a `fill` or `stroke` value. Unsupported paints (eg. gradients) fall back to the
+/// provided value if they specify one, otherwise they are treated as `none`.
+fn parse_paint(s: &str, inherited: Option<Rgba8>) -> Option<Rgba8> {
+    let s = s.trim();
+    match s {
+        "none" => None,
+        "inherit" => inherited,
+        "currentColor" => Some(Rgba8::BLACK),
+        _ if s.starts_with("url(") => s
+            .split_once(')')
+            .and_then(|(_, fallback)| parse_color(fallback.trim())),
+        _ => parse_color(s),
+    }
+}
+
+fn parse_color(s: &str) -> Option<Rgba8> {
+    if let Some(hex) = s.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..(i + 1))?, 16).ok();
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..(i + 2))?, 16).ok();
+        return match hex.len() {
+            3 => Some(Rgba8::new(
+                digit(0)? * 17,
+                digit(1)? * 17,
+                digit(2)? * 17,
+                255,
+            )),
+            4 => Some(Rgba8::new(
+                digit(0)? * 17,
+                digit(1)? * 17,
+                digit(2)? * 17,
+                digit(3)? * 17,
+            )),
+            6 => Some(Rgba8::new(byte(0)?, byte(2)?, byte(4)?, 255)),
+            8 => Some(Rgba8::new(byte(0)?, byte(2)?, byte(4)?, byte(6)?)),
+            _ => None,
+        };
+    }
+
+    if let Some(args) = s
+        .strip_prefix("rgba(")
+        .or_else(|| s.strip_prefix("rgb("))
+        .and_then(|s| s.strip_suffix(')'))
+    {
+        let mut parts = args
+            .split(|c: char| c == ',' || c == '/' || c.is_whitespace())
+            .filter(|p| !p.is_empty());
+        let mut channel = |max: f32| -> Option<u8> {
+            let part = parts.next()?;
+            let value = match part.strip_suffix('%') {
+                Some(pct) => pct.parse::<f32>().ok()? / 100.0 * 255.0,
+                None => part.parse::<f32>().ok()? * (255.0 / max),
+            };
+            Some(value.round().clamp(0.0, 255.0) as u8)
+        };
+        let r = channel(255.0)?;
+        let g = channel(255.0)?;
+        let b = channel(255.0)?;
+        let a = channel(1.0).unwrap_or(255);
+        return Some(Rgba8::new(r, g, b, a));
+    }
+
+    let packed = match s.to_ascii_lowercase().as_str() {
+        "transparent" => return Some(Rgba8::new(0, 0, 0, 0)),
+        "black" => 0x000000,
+        "silver" => 0xc0c0c0,
+        "gray" | "grey" => 0x808080,
+        "white" => 0xffffff,
+        "maroon" => 0x800000,
+        "red" => 0xff0000,
+        "purple" => 0x800080,
+        "fuchsia" | "magenta" => 0xff00ff,
+        "green" => 0x008000,
+        "lime" => 0x00ff00,
+        "olive" => 0x808000,
+        "yellow" => 0xffff00,
+        "navy" => 0x000080,
+        "blue" => 0x0000ff,
+        "teal" => 0x008080,
+        "aqua" | "cyan" => 0x00ffff,
+        "orange" => 0xffa500,
+        "brown" => 0xa52a2a,
+        "pink" => 0xffc0cb,
+        "gold" => 0xffd700,
+        "indigo" => 0x4b0082,
+        "violet" => 0xee82ee,
+        "darkgray" | "darkgrey" => 0xa9a9a9,
+        "lightgray" | "lightgrey" => 0xd3d3d3,
+        _ => return None,
+    };
+    Some(fey_color::rgb(packed))
+}
+
+/// Parse a list of transform functions, eg. `translate(10 20) rotate(45)`.
+fn parse_transform(s: &str) -> Affine2F {
+    let mut result = Affine2F::IDENTITY;
+    let mut rest = s;
+    while let Some((name, after)) = rest.split_once('(') {
+        let Some((args, after)) = after.split_once(')') else {
+            break;
+        };
+        rest = after;
+        let a = parse_numbers(args);
+        let arg = |i: usize| a.get(i).copied().unwrap_or(0.0);
+        let matrix = match (
+            name.trim_matches(|c: char| c == ',' || c.is_whitespace()),
+            a.len(),
+        ) {
+            ("matrix", 6) => affine2(mat2(vec2(a[0], a[1]), vec2(a[2], a[3])), vec2(a[4], a[5])),
+            ("translate", 1 | 2) => Affine2F::translation(vec2(arg(0), arg(1))),
+            ("scale", 1) => Affine2F::scale_of(a[0]),
+            ("scale", 2) => Affine2F::scale(vec2(a[0], a[1])),
+            ("rotate", 1 | 3) => {
+                let (sin, cos) = a[0].to_radians().sin_cos();
+                let rot = affine2(mat2(vec2(cos, sin), vec2(-sin, cos)), Vec2F::ZERO);
+                let pivot = vec2(arg(1), arg(2));
+                Affine2F::translation(pivot) * rot * Affine2F::translation(-pivot)
+            }
+            ("skewX", 1) => affine2(
+                mat2(vec2(1.0, 0.0), vec2(a[0].to_radians().tan(), 1.0)),
+                Vec2F::ZERO,
+            ),
+            ("skewY", 1) => affine2(
+                mat2(vec2(1.0, a[0].to_radians().tan()), vec2(0.0, 1.0)),
+                Vec2F::ZERO,
+            ),
+            _ => continue,
+        };
+        result = result * matrix;
+    }
+    result
+}
@@ -0,0 +1,266 @@
+use crate::SvgError;
+use fey_math::{PathF, Vec2F, vec2};
+use std::f32::consts::{FRAC_PI_2, TAU};
+
+/// Tokenizer for the numbers and commands in SVG attributes.
+pub(crate) struct Lexer<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(s: &'a str) -> Self {
+        Self {
+            bytes: s.as_bytes(),
+            pos: 0,
+        }
+    }
+
+    fn skip_separators(&mut self) {
+        while self
+            .bytes
+            .get(self.pos)
+            .is_some_and(|b| b.is_ascii_whitespace() || *b == b',')
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_separators();
+        self.bytes.get(self.pos).copied()
+    }
+
+    fn eat_digits(&mut self) -> usize {
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_digit) {
+            self.pos += 1;
+        }
+        self.pos - start
+    }
+
+    /// Read the next number, or `None` if the next token isn't one.
+    pub fn number(&mut self) -> Option<f32> {
+        self.skip_separators();
+        let start = self.pos;
+        if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+            self.pos += 1;
+        }
+        let mut digits = self.eat_digits();
+        if self.bytes.get(self.pos) == Some(&b'.') {
+            self.pos += 1;
+            digits += self.eat_digits();
+        }
+        if digits == 0 {
+            self.pos = start;
+            return None;
+        }
+
+        // only consume the exponent if it's well-formed, so units like "em" are left alone
+        if matches!(self.bytes.get(self.pos), Some(b'e' | b'E')) {
+            let mark = self.pos;
+            self.pos += 1;
+            if matches!(self.bytes.get(self.pos), Some(b'+' | b'-')) {
+                self.pos += 1;
+            }
+            if self.eat_digits() == 0 {
+                self.pos = mark;
+            }
+        }
+
+        let s = std::str::from_utf8(&self.bytes[start..self.pos]).ok()?;
+        s.parse().ok()
+    }
+
+    fn num(&mut self) -> Result<f32, SvgError> {
+        self.number().ok_or(SvgError::InvalidPathData(self.pos))
+    }
+
+    fn point(&mut self, origin: Vec2F) -> Result<Vec2F, SvgError> {
+        let x = self.num()?;
+        let y = self.num()?;
+        Ok(origin + vec2(x, y))
+    }
+
+    /// Read a single-digit arc flag, which may not be separated from what follows it.
+    fn flag(&mut self) -> Result<bool, SvgError> {
+        let flag = match self.peek() {
+            Some(b'0') => false,
+            Some(b'1') => true,
+            _ => return Err(SvgError::InvalidPathData(self.pos)),
+        };
+        self.pos += 1;
+        Ok(flag)
+    }
+}
+
+/// Parse the contents of a path's `d` attribute into the path.
+pub(crate) fn parse_path_data(d: &str, path: &mut PathF) -> Result<(), SvgError> {
+    let mut lex = Lexer::new(d);
+    let mut cmd = None::<u8>;
+    let mut pos = Vec2F::ZERO;
+    let mut start = Vec2F::ZERO;
+
+    // the last control point, for reflecting in smooth curves
+    let mut last_cubic_ctrl = None::<Vec2F>;
+    let mut last_quad_ctrl = None::<Vec2F>;
+
+    while let Some(next) = lex.peek() {
+        // commands can be omitted if they are repeated
+        if next.is_ascii_alphabetic() {
+            lex.pos += 1;
+            cmd = Some(next);
+        } else if cmd.is_none() {
+            return Err(SvgError::InvalidPathData(lex.pos));
+        }
+        let c = cmd.unwrap();
+        let rel = c.is_ascii_lowercase();
+        let origin = if rel { pos } else { Vec2F::ZERO };
+
+        let (mut cubic_ctrl, mut quad_ctrl) = (None, None);
+        match c.to_ascii_uppercase() {
+            b'M' => {
+                pos = lex.point(origin)?;
+                start = pos;
+                path.move_to(pos);
+
+                // subsequent pairs are implicit line-tos
+                cmd = Some(if rel { b'l' } else { b'L' });
+            }
+            b'L' => {
+                pos = lex.point(origin)?;
+                path.line_to(pos);
+            }
+            b'H' => {
+                let x = lex.num()?;
+                pos.x = if rel { pos.x + x } else { x };
+                path.line_to(pos);
+            }
+            b'V' => {
+                let y = lex.num()?;
+                pos.y = if rel { pos.y + y } else { y };
+                path.line_to(pos);
+            }
+            b'C' => {
+                let c1 = lex.point(origin)?;
+                let c2 = lex.point(origin)?;
+                pos = lex.point(origin)?;
+                path.cubic_to(c1, c2, pos);
+                cubic_ctrl = Some(c2);
+            }
+            b'S' => {
+                let c1 = last_cubic_ctrl.map_or(pos, |c| pos * 2.0 - c);
+                let c2 = lex.point(origin)?;
+                pos = lex.point(origin)?;
+                path.cubic_to(c1, c2, pos);
+                cubic_ctrl = Some(c2);
+            }
+            b'Q' => {
+                let ctrl = lex.point(origin)?;
+                pos = lex.point(origin)?;
+                path.quad_to(ctrl, pos);
+                quad_ctrl = Some(ctrl);
+            }
+            b'T' => {
+                let ctrl = last_quad_ctrl.map_or(pos, |c| pos * 2.0 - c);
+                pos = lex.point(origin)?;
+                path.quad_to(ctrl, pos);
+                quad_ctrl = Some(ctrl);
+            }
+            b'A' => {
+                let r = vec2(lex.num()?, lex.num()?);
+                let angle = lex.num()?;
+                let large = lex.flag()?;
+                let sweep = lex.flag()?;
+                let end = lex.point(origin)?;
+                arc_to(path, pos, r, angle, large, sweep, end);
+                pos = end;
+            }
+            b'Z' => {
+                path.close();
+                pos = start;
+
+                // a close can't be implicitly repeated
+                cmd = None;
+            }
+            _ => return Err(SvgError::InvalidPathData(lex.pos - 1)),
+        }
+        last_cubic_ctrl = cubic_ctrl;
+        last_quad_ctrl = quad_ctrl;
+    }
+    Ok(())
+}
+
+/// Approximate an elliptical arc with cubic béziers.
+/// See: https://www.w3.org/TR/SVG11/implnote.html#ArcImplementationNotes
+fn arc_to(
+    path: &mut PathF,
+    from: Vec2F,
+    radius: Vec2F,
+    angle: f32,
+    large: bool,
+    sweep: bool,
+    to: Vec2F,
+) {
+    if from == to {
+        return;
+    }
+    let (mut rx, mut ry) = (radius.x.abs(), radius.y.abs());
+    if rx == 0.0 || ry == 0.0 {
+        path.line_to(to);
+        return;
+    }
+
+    // transform the endpoints into the ellipse's space
+    let (sin, cos) = angle.to_radians().sin_cos();
+    let half = (from - to) * 0.5;
+    let p = vec2(cos * half.x + sin * half.y, -sin * half.x + cos * half.y);
+
+    // scale up the radii if they are too small to reach between the points
+    let lambda = (p.x * p.x) / (rx * rx) + (p.y * p.y) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+
+    // find the center
+    let num = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+    let den = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+    let sign = if large == sweep { -1.0 } else { 1.0 };
+    let coef = sign * (num / den).max(0.0).sqrt();
+    let cp = vec2(coef * rx * p.y / ry, -coef * ry * p.x / rx);
+    let mid = (from + to) * 0.5;
+    let center = vec2(cos * cp.x - sin * cp.y, sin * cp.x + cos * cp.y) + mid;
+
+    // find the start angle and the sweep
+    let angle_between = |u: Vec2F, v: Vec2F| u.cross(v).atan2(u.dot(v));
+    let u = vec2((p.x - cp.x) / rx, (p.y - cp.y) / ry);
+    let v = vec2((-p.x - cp.x) / rx, (-p.y - cp.y) / ry);
+    let theta = angle_between(Vec2F::X_AXIS, u);
+    let mut delta = angle_between(u, v);
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if sweep && delta < 0.0 {
+        delta += TAU;
+    }
+
+    // split the arc into segments of at most 90°
+    let count = (delta.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = delta / count as f32;
+    let k = 4.0 / 3.0 * (step / 4.0).tan();
+    let point_at = |t: f32| {
+        let (s, c) = t.sin_cos();
+        center + vec2(rx * c * cos - ry * s * sin, rx * c * sin + ry * s * cos)
+    };
+    let tangent_at = |t: f32| {
+        let (s, c) = t.sin_cos();
+        vec2(-rx * s * cos - ry * c * sin, -rx * s * sin + ry * c * cos)
+    };
+    for i in 0..count {
+        let t0 = theta + step * i as f32;
+        let t1 = t0 + step;
+        let p0 = point_at(t0);
+        let p1 = if i + 1 == count { to } else { point_at(t1) };
+        path.cubic_to(p0 + tangent_at(t0) * k, p1 - tangent_at(t1) * k, p1);
+    }
+}
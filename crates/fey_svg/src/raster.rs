@@ -0,0 +1,70 @@
+use crate::Svg;
+use fey_color::Rgba8;
+use fey_img::ImageRgba8;
+use fey_math::{FillRule, PathCmd, PathF, vec2};
+use tiny_skia::{Paint, PathBuilder, Pixmap, Stroke, StrokeDash, Transform};
+
+impl Svg {
+    /// Rasterize the document into an image. The image will be the document's size
+    /// multiplied by `scale`, rounded up. Returns `None` if the scaled size is invalid or
+    /// too large to allocate.
+    pub fn rasterize(&self, scale: f32) -> Option<ImageRgba8> {
+        let w = (self.size.x * scale).ceil().max(1.0) as u32;
+        let h = (self.size.y * scale).ceil().max(1.0) as u32;
+        let mut pixmap = Pixmap::new(w, h)?;
+        let transform = Transform::from_scale(scale, scale);
+
+        for shape in &self.shapes {
+            let Some(path) = to_skia_path(&shape.path) else {
+                continue;
+            };
+            if let Some(fill) = &shape.fill {
+                let rule = match fill.rule {
+                    FillRule::NonZero => tiny_skia::FillRule::Winding,
+                    FillRule::EvenOdd => tiny_skia::FillRule::EvenOdd,
+                };
+                pixmap.fill_path(&path, &paint(fill.color), rule, transform, None);
+            }
+            if let Some(stroke) = &shape.stroke {
+                let stroke_style = Stroke {
+                    width: stroke.width,
+                    dash: StrokeDash::new(stroke.dashes.clone(), stroke.dash_offset),
+                    ..Default::default()
+                };
+                pixmap.stroke_path(&path, &paint(stroke.color), &stroke_style, transform, None);
+            }
+        }
+
+        // tiny-skia works with premultiplied colors
+        let channels = pixmap
+            .pixels()
+            .iter()
+            .flat_map(|p| {
+                let c = p.demultiply();
+                [c.red(), c.green(), c.blue(), c.alpha()]
+            })
+            .collect();
+        Some(ImageRgba8::from_raw(vec2(w, h), channels))
+    }
+}
+
+fn paint(color: Rgba8) -> Paint<'static> {
+    let mut paint = Paint::default();
+    paint.set_color_rgba8(color.r, color.g, color.b, color.a);
+    paint.anti_alias = true;
+    paint
+}
+
+fn to_skia_path(path: &PathF) -> Option<tiny_skia::Path> {
+    let mut builder = PathBuilder::new();
+    for cmd in path.cmds() {
+        match *cmd {
+            PathCmd::MoveTo(p) => builder.move_to(p.x, p.y),
+            PathCmd::LineTo(p) => builder.line_to(p.x, p.y),
+            PathCmd::QuadTo(c, p) => builder.quad_to(c.x, c.y, p.x, p.y),
+            PathCmd::CubicTo(c1, c2, p) => builder.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y),
+            PathCmd::Close => builder.close(),
+        }
+    }
+    builder.finish()
+}
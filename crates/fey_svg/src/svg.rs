@@ -0,0 +1,138 @@
+use crate::SvgError;
+use crate::parse::{Attrs, Style, shape_path, skip_element, view_box_transform};
+use fey_color::Rgba8;
+use fey_math::{FillRule, PathF, Vec2F};
+use quick_xml::Reader;
+use quick_xml::events::Event;
+use std::path::Path;
+
+/// A parsed SVG file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svg {
+    /// Size of the document.
+    pub size: Vec2F,
+
+    /// All visible shapes in the document, in drawing order.
+    pub shapes: Vec<SvgShape>,
+}
+
+/// A filled and/or stroked shape in an SVG file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgShape {
+    /// The shape's outline, in document space.
+    pub path: PathF,
+
+    /// How the shape is filled, if at all.
+    pub fill: Option<SvgFill>,
+
+    /// How the shape is stroked, if at all.
+    pub stroke: Option<SvgStroke>,
+}
+
+/// The fill style of an [`SvgShape`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SvgFill {
+    /// Fill color, with opacity applied.
+    pub color: Rgba8,
+
+    /// Rule determining which areas of the path are inside.
+    pub rule: FillRule,
+}
+
+/// The stroke style of an [`SvgShape`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgStroke {
+    /// Stroke color, with opacity applied.
+    pub color: Rgba8,
+
+    /// Thickness of the stroke, in document space.
+    pub width: f32,
+
+    /// Alternating dash and gap lengths. If empty, the stroke is solid.
+    pub dashes: Vec<f32>,
+
+    /// How far into the dash pattern the stroke starts.
+    pub dash_offset: f32,
+}
+
+impl Svg {
+    /// Load an SVG file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, SvgError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parse an SVG file from its bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SvgError> {
+        Self::parse(std::str::from_utf8(bytes)?)
+    }
+
+    /// Parse an SVG file from its source text.
+    pub fn parse(src: &str) -> Result<Self, SvgError> {
+        let mut reader = Reader::from_str(src);
+        let mut size = None;
+        let mut shapes = Vec::new();
+        let mut stack: Vec<Style> = Vec::new();
+
+        // how deep we are inside of an element whose contents are being ignored
+        let mut skip_depth = 0usize;
+
+        loop {
+            let (elem, is_start) = match reader.read_event()? {
+                Event::Start(elem) => (elem, true),
+                Event::Empty(elem) => (elem, false),
+                Event::End(_) => {
+                    if skip_depth > 0 {
+                        skip_depth -= 1;
+                    } else {
+                        stack.pop();
+                    }
+                    continue;
+                }
+                Event::Eof => break,
+                _ => continue,
+            };
+
+            if skip_depth > 0 {
+                skip_depth += is_start as usize;
+                continue;
+            }
+
+            let name = elem.local_name();
+            let name = name.as_ref();
+            let attrs = Attrs::from_elem(&elem)?;
+
+            // the root element sets up the document size and view box
+            let parent = match (stack.last(), size) {
+                (Some(parent), _) => parent.clone(),
+                (None, None) if name == b"svg" => {
+                    let (doc_size, transform) = view_box_transform(&attrs);
+                    size = Some(doc_size);
+                    Style::root(transform)
+                }
+                (None, _) => return Err(SvgError::MissingRoot),
+            };
+
+            if skip_element(name) || attrs.get("display") == Some("none") {
+                skip_depth += is_start as usize;
+                continue;
+            }
+
+            let style = parent.with_attrs(&attrs);
+            if let Some(shape) = shape_path(name, &attrs)?.and_then(|path| style.shape(path)) {
+                shapes.push(shape);
+            }
+            if is_start {
+                stack.push(style);
+            }
+        }
+
+        let size = size.ok_or(SvgError::MissingRoot)?;
+        Ok(Self { size, shapes })
+    }
+
+    /// Iterate over the paths of all the document's shapes.
+    #[inline]
+    pub fn paths(&self) -> impl Iterator<Item = &PathF> {
+        self.shapes.iter().map(|shape| &shape.path)
+    }
+}
@@ -0,0 +1,88 @@
+//! Micro-benchmarks for batching geometry in [`Draw`] and uploading it through the buffer
+//! cache. Each iteration draws and renders a whole frame offscreen.
+//!
+//! Run with `cargo bench -p kero`. They are skipped if no graphics device is available.
+
+use criterion::{Criterion, criterion_group, criterion_main};
+use kero::prelude::*;
+use std::hint::black_box;
+
+const FRAME_SIZE: Vec2U = vec2(1280, 720);
+
+/// Benchmark drawing a frame, if a graphics device is available.
+fn bench_frame(c: &mut Criterion, name: &str, mut f: impl FnMut(&mut Draw)) {
+    let Some(mut draw) = Draw::new_headless() else {
+        eprintln!("skipping {name}: no graphics device available");
+        return;
+    };
+    c.bench_function(name, |b| {
+        b.iter(|| {
+            draw.begin_headless_frame(FRAME_SIZE);
+            f(&mut draw);
+            black_box(draw.end_headless_frame())
+        })
+    });
+}
+
+/// Position of the `i`th item in a grid filling the frame.
+#[inline]
+fn grid_pos(i: usize, cols: usize, spacing: f32) -> Vec2F {
+    vec2((i % cols) as f32, (i / cols) as f32) * spacing
+}
+
+fn quads(c: &mut Criterion) {
+    // every quad shares a single batch
+    bench_frame(c, "quads_10k", |draw| {
+        for i in 0..10_000 {
+            let pos = grid_pos(i, 160, 8.0);
+            draw.rect(RectF::new(pos.x, pos.y, 6.0, 6.0), Rgba8::WHITE);
+        }
+    });
+}
+
+fn lines(c: &mut Criterion) {
+    bench_frame(c, "lines_10k", |draw| {
+        for i in 0..10_000 {
+            let pos = grid_pos(i, 160, 8.0);
+            draw.line(LineF::new(pos, pos + vec2(6.0, 6.0)), Rgba8::WHITE);
+        }
+    });
+}
+
+fn circles(c: &mut Criterion) {
+    bench_frame(c, "circles_1k", |draw| {
+        for i in 0..1_000 {
+            let pos = grid_pos(i, 50, 24.0);
+            draw.circle(CircleF::new(pos, 10.0), Rgba8::WHITE, None);
+        }
+    });
+}
+
+fn batch_breaks(c: &mut Criterion) {
+    // alternating the blend mode flushes the batch on every quad
+    bench_frame(c, "batch_breaks_1k", |draw| {
+        for i in 0..1_000 {
+            let pos = grid_pos(i, 50, 24.0);
+            draw.set_blend_mode(match i % 2 {
+                0 => BlendMode::Normal,
+                _ => BlendMode::Add,
+            });
+            draw.rect(RectF::new(pos.x, pos.y, 20.0, 20.0), Rgba8::WHITE);
+        }
+    });
+}
+
+fn layers(c: &mut Criterion) {
+    // the same quads spread across layers, which are batched separately
+    bench_frame(c, "layers_8x1k", |draw| {
+        for i in 0..8_000 {
+            let pos = grid_pos(i, 160, 8.0);
+            draw.set_layer(i % 8);
+            draw.rect(RectF::new(pos.x, pos.y, 6.0, 6.0), Rgba8::WHITE);
+        }
+        draw.set_layer(0);
+    });
+}
+
+criterion_group!(benches, quads, lines, circles, batch_breaks, layers);
+criterion_main!(benches);
@@ -0,0 +1,50 @@
+//! Detects the git commit of the project being built, which `build_info!` uses when the game
+//! doesn't provide its own.
+
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    // the target folder is usually inside the game's project, so running git from there finds
+    // the game's repository rather than the one kero was downloaded from
+    let Some(out_dir) = std::env::var_os("OUT_DIR") else {
+        return;
+    };
+    let git = |args: &[&str]| git(&out_dir, args);
+    let Some(hash) = git(&["rev-parse", "--short", "HEAD"]) else {
+        return;
+    };
+    println!("cargo:rustc-env=KERO_DETECTED_GIT_HASH={hash}");
+
+    // the commit date, so rebuilding the same commit gives the same build information
+    if let Some(date) = git(&["log", "-1", "--format=%cs"]) {
+        println!("cargo:rustc-env=KERO_DETECTED_BUILD_DATE={date}");
+    }
+
+    // rerun whenever a commit is made or checked out
+    let mut watch = vec!["HEAD".to_string(), "packed-refs".to_string()];
+    watch.extend(git(&["symbolic-ref", "-q", "HEAD"]));
+    for name in watch {
+        if let Some(path) = git(&["rev-parse", "--git-path", &name])
+            .and_then(|path| Path::new(&out_dir).join(path).canonicalize().ok())
+        {
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+    }
+}
+
+/// Run a git command in the folder, returning its trimmed output if it succeeded.
+fn git(dir: &OsString, args: &[&str]) -> Option<String> {
+    let out = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .ok()?;
+    let out = String::from_utf8(out.stdout)
+        .ok()
+        .filter(|_| out.status.success())?;
+    Some(out.trim().to_string()).filter(|out| !out.is_empty())
+}
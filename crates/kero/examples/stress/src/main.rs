@@ -0,0 +1,202 @@
+//! Draws a lot of sprites, lines, or text every frame and prints rendering stats once per
+//! second, so changes to batching can be measured.
+//!
+//! ```text
+//! cargo run --release -p stress -- sprites 100000
+//! cargo run --release -p stress -- lines 10000
+//! cargo run --release -p stress -- text 1000
+//! ```
+//!
+//! Press `1`, `2`, or `3` to switch tests, and `Up` or `Down` to double or halve the count.
+
+use kero::prelude::*;
+use std::time::{Duration, Instant};
+
+fn main() -> Result<(), GameError> {
+    let mut args = std::env::args().skip(1);
+    let test = match args.next() {
+        Some(name) => Test::parse(&name).ok_or_else(|| {
+            GameError::custom(format!(
+                "unknown test [{name}], expected sprites, lines, or text"
+            ))
+        })?,
+        None => Test::Sprites,
+    };
+    let count = match args.next().map(|arg| arg.parse()) {
+        Some(Ok(count)) => count,
+        Some(Err(_)) => return Err(GameError::custom("count must be a number")),
+        None => test.default_count(),
+    };
+
+    kero::new_game()
+        .with_default_logger()
+        .with_title("Stress Test")
+        .with_size(1280, 720)
+        .with_gpu_timing(true)
+        .run::<StressTest>((test, count))
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Test {
+    Sprites,
+    Lines,
+    Text,
+}
+
+impl Test {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "sprites" => Some(Self::Sprites),
+            "lines" => Some(Self::Lines),
+            "text" => Some(Self::Text),
+            _ => None,
+        }
+    }
+
+    fn default_count(self) -> usize {
+        match self {
+            Self::Sprites => 100_000,
+            Self::Lines => 10_000,
+            Self::Text => 1_000,
+        }
+    }
+}
+
+pub struct StressTest {
+    test: Test,
+    count: usize,
+    sprite: Texture,
+    font: Font,
+    _font_texture: Texture,
+    time: f32,
+    frames: u32,
+    render_time: Duration,
+    report_timer: f32,
+}
+
+impl Game for StressTest {
+    type Config = (Test, usize);
+
+    fn new(ctx: &Context, (test, count): Self::Config) -> Result<Self, GameError>
+    where
+        Self: Sized,
+    {
+        // a small checkered sprite, so every sprite samples the same texture
+        let pixels: Vec<Rgba8> = (0..64)
+            .map(|i| match (i % 8 + i / 8) % 2 {
+                0 => Rgba8::WHITE,
+                _ => Rgba8::new(120, 200, 255, 255),
+            })
+            .collect();
+        let sprite = ctx.graphics.create_texture(vec2(8, 8), &pixels);
+
+        let (font, _font_texture) = Font::from_ttf_bytes(
+            &ctx.graphics,
+            include_bytes!("../../text/assets/NotoSans-Regular.ttf"),
+            16.0,
+            false,
+            Charset::BASIC_LATIN,
+        )?
+        .ok_or_else(|| GameError::custom("failed to load font"))?;
+
+        println!("running {test:?} test with {count} items");
+        Ok(Self {
+            test,
+            count,
+            sprite,
+            font,
+            _font_texture,
+            time: 0.0,
+            frames: 0,
+            render_time: Duration::ZERO,
+            report_timer: 0.0,
+        })
+    }
+
+    fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        self.time += ctx.time.delta();
+
+        // switch tests and counts
+        let test = [
+            (Key::Digit1, Test::Sprites),
+            (Key::Digit2, Test::Lines),
+            (Key::Digit3, Test::Text),
+        ]
+        .into_iter()
+        .find_map(|(key, test)| ctx.keyboard.pressed(key).then_some(test));
+        if let Some(test) = test {
+            self.test = test;
+            self.count = test.default_count();
+            println!("running {test:?} test with {} items", self.count);
+        }
+        if ctx.keyboard.pressed(Key::ArrowUp) {
+            self.count *= 2;
+            println!("count: {}", self.count);
+        }
+        if ctx.keyboard.pressed(Key::ArrowDown) {
+            self.count = (self.count / 2).max(1);
+            println!("count: {}", self.count);
+        }
+
+        // print the stats of the last second
+        self.report_timer += ctx.time.unscaled_delta();
+        if self.report_timer >= 1.0 && self.frames > 0 {
+            let stats = ctx.graphics.frame_stats();
+            let cpu = self.render_time.as_secs_f64() * 1000.0 / self.frames as f64;
+            let gpu = stats.gpu_time().map_or("n/a".to_string(), |t| {
+                format!("{:.3}ms", t.as_secs_f64() * 1000.0)
+            });
+            let (test, count, fps) = (self.test, self.count, ctx.time.fps());
+            println!("{test:?} x{count}: {fps} fps, draw {cpu:.3}ms, gpu {gpu}");
+            println!(
+                "  passes {}, calls {}, vertices {}, indices {}",
+                stats.passes, stats.draw_calls, stats.vertices, stats.indices,
+            );
+            self.report_timer = 0.0;
+            self.frames = 0;
+            self.render_time = Duration::ZERO;
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        let start = Instant::now();
+        let size = ctx.window.size().to_f32();
+        let t = self.time;
+
+        match self.test {
+            Test::Sprites => {
+                for i in 0..self.count {
+                    let seed = i as f32;
+                    let x = (seed * 12.9898 + t * 40.0 * (1.0 + (seed * 0.37).sin())) % size.x;
+                    let y = (seed * 78.233 + t * 25.0 * (1.0 + (seed * 0.71).cos())) % size.y;
+                    draw.texture_at(&self.sprite, vec2(x, y));
+                }
+            }
+            Test::Lines => {
+                let center = size * 0.5;
+                let radius = size.y * 0.45;
+                for i in 0..self.count {
+                    let angle = i as f32 / self.count as f32 * std::f32::consts::TAU + t * 0.2;
+                    let dir = vec2(angle.cos(), angle.sin());
+                    let len = radius * (0.5 + 0.5 * (angle * 7.0 + t).sin());
+                    let color = Rgba8::new((i % 256) as u8, 180, 255, 255);
+                    draw.line(LineF::new(center, center + dir * len), color);
+                }
+            }
+            Test::Text => {
+                let cols = 8;
+                let cell = vec2(size.x / cols as f32, 18.0);
+                for i in 0..self.count {
+                    let pos = vec2((i % cols) as f32, (i / cols) as f32) * cell;
+                    let pos = vec2(pos.x, (pos.y + t * 20.0) % size.y.max(1.0));
+                    draw.text("Thinking meat!", pos, &self.font, Rgba8::WHITE, None);
+                }
+            }
+        }
+
+        self.render_time += start.elapsed();
+        self.frames += 1;
+        Ok(())
+    }
+}
@@ -1,5 +1,4 @@
 use kero::prelude::*;
-use std::collections::HashMap;
 use std::path::Path;
 
 fn main() -> Result<(), GameError> {
@@ -12,8 +11,7 @@ fn main() -> Result<(), GameError> {
 }
 
 pub struct TexturePackerExample {
-    tex: Texture,
-    subs: HashMap<String, SubTexture>,
+    atlas: TextureAtlas<String>,
 }
 
 impl Game for TexturePackerExample {
@@ -52,27 +50,33 @@ impl Game for TexturePackerExample {
             }
         }
 
-        let (tex, subs) = packer.pack(&ctx.graphics).unwrap();
+        let atlas = packer.pack(&ctx.graphics).unwrap();
 
-        Ok(Self { tex, subs })
+        // register the atlas with the built-in debug overlay, which can be toggled with tab
+        ctx.graphics.atlas_debug().add_atlas("atlas", &atlas);
+
+        Ok(Self { atlas })
     }
 
-    fn update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+    fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        if ctx.keyboard.pressed(Key::Tab) {
+            ctx.graphics.atlas_debug().toggle();
+        }
         Ok(())
     }
 
     fn render(&mut self, _ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
         draw.push_scale_of(2.0);
 
-        let tex_size = RectF::sized(self.tex.size().to_f32());
+        let tex_size = RectF::sized(self.atlas.texture().size().to_f32());
 
         draw.rect(tex_size, rgb(0x476c6c));
 
-        for sub in self.subs.values() {
+        for sub in self.atlas.subs() {
             draw.rect_outline(sub.rect, Rgba8::WHITE);
         }
 
-        for sub in self.subs.values() {
+        for sub in self.atlas.subs() {
             draw.subtexture_at(sub, sub.rect.top_left() - sub.offset);
         }
 
@@ -3,6 +3,16 @@
 ---@class AppModule
 local App = {}
 
+---@class BuildInfo
+---@field name string The game's name.
+---@field version string The game's version.
+---@field git_hash string? The git commit the game was built from, if known.
+---@field build_date string? The date the game was built, if known.
+---@field profile string The build profile, `debug` or `release`.
+---@field target string The platform the game was built for, such as `windows-x86_64`.
+---@field kero_version string The version of kero the game was built with.
+---@field label string A one-line summary, such as `game 1.2.0 (release, 3f9c2ab, 2026-10-16)`.
+
 ---Quit the app.
 function App.quit() end
 
@@ -15,6 +25,21 @@ function App.restart() end
 ---If `restart()` was called and the app is scheduled to restart.
 function App.restart_requested() end
 
+---The refresh rate of the display the window is on in hertz, if it is known.
+---@return number?
+---@nodiscard
+function App.refresh_rate() end
+
+---If the refresh rate changed this frame, such as from the window moving to another monitor.
+---@return boolean
+---@nodiscard
+function App.refresh_rate_changed() end
+
+---The video mode the window is displayed with.
+---@return VideoMode?
+---@nodiscard
+function App.video_mode() end
+
 ---Path to the game's cache directory.
 ---
 --- |Platform | Example                                              |
@@ -75,4 +100,16 @@ function App.data_local_dir() end
 ---@return string
 function App.preferences_dir() end
 
+---How the game was built, so testers can say exactly which build they were playing.
+---@return BuildInfo
+---@nodiscard
+function App.build_info() end
+
+---Capture a bug report with the player's description of the problem. At the end of the frame, a
+---screenshot, recent logs, system information, and settings are zipped up and saved to the
+---`bug_reports` folder in the data directory.
+---@param description string
+---@return string path Where the report will be saved.
+function App.capture_report(description) end
+
 return App
\ No newline at end of file
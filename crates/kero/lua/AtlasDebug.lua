@@ -0,0 +1,58 @@
+---@meta
+
+---A debug overlay that shows atlas pages with every packed rectangle outlined and labeled,
+---to diagnose packing and bleeding issues. While enabled, the current page is drawn over
+---everything else at the end of each frame.
+---@class AtlasDebugModule
+local AtlasDebug = {}
+
+---If the overlay is drawn.
+---@return boolean
+---@nodiscard
+function AtlasDebug.enabled() end
+
+---Set if the overlay is drawn.
+---@param enabled boolean
+function AtlasDebug.set_enabled(enabled) end
+
+---Show the overlay if hidden, otherwise hide it.
+function AtlasDebug.toggle() end
+
+---Set the font used to label pages and rectangles. Without one, only outlines are drawn.
+---@param font Font?
+function AtlasDebug.set_font(font) end
+
+---Register a page, replacing any existing page with the same name.
+---@param name string
+---@param texture Texture
+---@param rects table<string, Rect> names of everything packed into the texture, and their rectangles in pixels
+function AtlasDebug.add_page(name, texture, rects) end
+
+---Remove the page with the name.
+---@param name string
+function AtlasDebug.remove_page(name) end
+
+---Remove all pages.
+function AtlasDebug.clear() end
+
+---How many pages are registered.
+---@return integer
+---@nodiscard
+function AtlasDebug.num_pages() end
+
+---Index of the page being shown, starting at `0`.
+---@return integer
+---@nodiscard
+function AtlasDebug.page() end
+
+---Set which page is shown.
+---@param page integer
+function AtlasDebug.set_page(page) end
+
+---Show the next page, wrapping around to the first.
+function AtlasDebug.next_page() end
+
+---Show the previous page, wrapping around to the last.
+function AtlasDebug.prev_page() end
+
+return AtlasDebug
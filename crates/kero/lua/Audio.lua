@@ -0,0 +1,310 @@
+---@meta
+
+---Handle to a sound that is playing, which can be used to control it while it plays.
+---Dropping the handle does not stop the sound.
+---@class (exact) Playback: PlaybackMethods
+
+---The game's audio mixer. Every sound is mixed on its channel, and each channel has its own
+---volume on top of the master volume.
+---@class AudioModule
+local Audio = {}
+
+---@class PlaybackMethods
+local playback = {}
+
+---The sample rate audio is mixed at, in hertz.
+---@return integer
+---@nodiscard
+function Audio.sample_rate() end
+
+---The volume all channels are multiplied by.
+---@return number
+---@nodiscard
+function Audio.master_volume() end
+
+---Set the volume all channels are multiplied by.
+---@param volume number
+function Audio.set_master_volume(volume) end
+
+---Volume of the channel.
+---@param channel AudioChannel
+---@return number
+---@nodiscard
+function Audio.volume(channel) end
+
+---Set the volume of the channel.
+---@param channel AudioChannel
+---@param volume number
+function Audio.set_volume(channel, volume) end
+
+---Play a sound, by default once on the `SFX` channel.
+---@param sound Sound
+---@param channel AudioChannel?
+---@param volume number?
+---@param pan number? stereo pan from `-1.0` (left) to `1.0` (right)
+---@param looping boolean?
+---@return Playback
+function Audio.play(sound, channel, volume, pan, looping) end
+
+---Play a sound emitted from a position in the world. It gets quieter the further it is from the
+---listener, and pans towards the side it is on.
+---@param sound Sound
+---@param pos Vec2
+---@param channel AudioChannel?
+---@param volume number?
+---@param looping boolean?
+---@return Playback
+function Audio.play_at(sound, pos, channel, volume, looping) end
+
+---Where positional sounds are heard from, usually the center of the camera.
+---@return Vec2
+---@nodiscard
+function Audio.listener() end
+
+---Move where positional sounds are heard from. Call this every frame with the center of the
+---camera so sounds pan and fade as it moves.
+---@param pos Vec2
+function Audio.set_listener(pos) end
+
+---Distance from the listener within which positional sounds play at full volume.
+---@return number
+---@nodiscard
+function Audio.min_distance() end
+
+---Set the distance from the listener within which positional sounds play at full volume.
+---@param distance number
+function Audio.set_min_distance(distance) end
+
+---Distance from the listener at which positional sounds become silent.
+---@return number
+---@nodiscard
+function Audio.max_distance() end
+
+---Set the distance from the listener at which positional sounds become silent.
+---@param distance number
+function Audio.set_max_distance(distance) end
+
+---Loop a sound on the `MUSIC` channel, stopping any music that was already playing.
+---@param sound Sound
+---@param volume number?
+---@return Playback
+function Audio.play_music(sound, volume) end
+
+---Loop a sound on the `MUSIC` channel, fading it in over `duration` seconds while any music
+---that was already playing fades out.
+---@param sound Sound
+---@param duration number
+---@param volume number?
+---@return Playback
+function Audio.crossfade_music(sound, duration, volume) end
+
+---Loop several layers (stems) of a track together on the `MUSIC` channel, stopping any music
+---that was already playing. Each stem gets its own playback, so their volumes can be mixed
+---while they play. The stems should have the same length and sample rate.
+---@param stems Sound[]
+---@param volume number?
+---@return Playback[]
+function Audio.play_music_stems(stems, volume) end
+
+---The music that is playing.
+---@return Playback?
+---@nodiscard
+function Audio.music() end
+
+---Every stem of the music that is playing.
+---@return Playback[]
+---@nodiscard
+function Audio.music_stems() end
+
+---Stop the music that is playing.
+function Audio.stop_music() end
+
+---Fade the music that is playing to silence over `duration` seconds, then stop it.
+---@param duration number
+function Audio.fade_out_music(duration) end
+
+---Stop every sound on the channel.
+---@param channel AudioChannel
+function Audio.stop_channel(channel) end
+
+---Stop every sound.
+function Audio.stop_all() end
+
+---How many sounds are playing.
+---@return integer
+---@nodiscard
+function Audio.playing_count() end
+
+---Root mean square level of the mixed audio this frame.
+---@return number
+---@nodiscard
+function Audio.rms() end
+
+---Loudest sample of the mixed audio this frame.
+---@return number
+---@nodiscard
+function Audio.peak() end
+
+---Amplitude of each frequency band of the mixed audio this frame, lowest first.
+---@return number[]
+---@nodiscard
+function Audio.bands() end
+
+---If a beat started this frame.
+---@return boolean
+---@nodiscard
+function Audio.beat() end
+
+---Estimated tempo of the music in beats per minute, or `0` if it isn't known yet.
+---@return number
+---@nodiscard
+function Audio.tempo() end
+
+---How many frequency bands the audio is split into. Defaults to `8`.
+---@return integer
+---@nodiscard
+function Audio.band_count() end
+
+---Set how many frequency bands the audio is split into.
+---@param count integer
+function Audio.set_band_count(count) end
+
+---How far the bass must rise above its recent average to count as a beat. Defaults to `1.5`,
+---and lower values detect more beats.
+---@return number
+---@nodiscard
+function Audio.beat_sensitivity() end
+
+---Set how far the bass must rise above its recent average to count as a beat.
+---@param sensitivity number
+function Audio.set_beat_sensitivity(sensitivity) end
+
+---The sound being played.
+---@param self Playback
+---@return Sound
+---@nodiscard
+function playback.sound(self) end
+
+---The channel the sound is playing on.
+---@param self Playback
+---@return AudioChannel
+---@nodiscard
+function playback.channel(self) end
+
+---If the sound hasn't finished or been stopped. Paused sounds are still playing.
+---@param self Playback
+---@return boolean
+---@nodiscard
+function playback.is_playing(self) end
+
+---Stop the sound. It can't be resumed afterwards.
+---@param self Playback
+function playback.stop(self) end
+
+---If the sound is paused.
+---@param self Playback
+---@return boolean
+---@nodiscard
+function playback.paused(self) end
+
+---Pause or resume the sound.
+---@param self Playback
+---@param paused boolean
+function playback.set_paused(self, paused) end
+
+---Volume of the sound, where `1.0` is its original volume.
+---@param self Playback
+---@return number
+---@nodiscard
+function playback.volume(self) end
+
+---Set the volume of the sound, where `1.0` is its original volume. This cancels any fade in
+---progress.
+---@param self Playback
+---@param volume number
+function playback.set_volume(self, volume) end
+
+---Smoothly change the volume over `duration` seconds.
+---@param self Playback
+---@param volume number
+---@param duration number
+function playback.fade_to(self, volume, duration) end
+
+---Fade the sound to silence over `duration` seconds, then stop it.
+---@param self Playback
+---@param duration number
+function playback.fade_out(self, duration) end
+
+---If the volume is fading.
+---@param self Playback
+---@return boolean
+---@nodiscard
+function playback.is_fading(self) end
+
+---Stereo panning, from `-1.0` (left) to `1.0` (right).
+---@param self Playback
+---@return number
+---@nodiscard
+function playback.pan(self) end
+
+---Set the stereo panning, from `-1.0` (left) to `1.0` (right).
+---@param self Playback
+---@param pan number
+function playback.set_pan(self, pan) end
+
+---Playback speed, which also changes the pitch. `1.0` is the original speed.
+---@param self Playback
+---@return number
+---@nodiscard
+function playback.speed(self) end
+
+---Set the playback speed, which also changes the pitch. `1.0` is the original speed.
+---@param self Playback
+---@param speed number
+function playback.set_speed(self, speed) end
+
+---If the sound starts over when it reaches the end.
+---@param self Playback
+---@return boolean
+---@nodiscard
+function playback.looping(self) end
+
+---Set if the sound starts over when it reaches the end.
+---@param self Playback
+---@param looping boolean
+function playback.set_looping(self, looping) end
+
+---The range of frames that repeats when the sound is looping, from `start` up to (but not
+---including) `finish`. Frames before the region play once as an intro.
+---@param self Playback
+---@return integer start
+---@return integer finish
+---@nodiscard
+function playback.loop_region(self) end
+
+---Set the range of frames that repeats when the sound is looping, such as to play an intro once
+---before looping the rest of a track. Frames are counted at the sound's sample rate, so loop
+---points are sample-accurate. Empty regions loop the whole sound.
+---@param self Playback
+---@param start integer
+---@param finish integer
+function playback.set_loop_region(self, start, finish) end
+
+---Where the sound is being emitted from in the world, or `nil` if it isn't positional.
+---@param self Playback
+---@return Vec2?
+---@nodiscard
+function playback.emitter_pos(self) end
+
+---Move where the sound is being emitted from, making it positional if it wasn't already.
+---@param self Playback
+---@param pos Vec2
+function playback.set_emitter_pos(self, pos) end
+
+---Current playback position in seconds.
+---@param self Playback
+---@return number
+---@nodiscard
+function playback.position(self) end
+
+return Audio
@@ -0,0 +1,13 @@
+---@meta
+
+---A mixer channel that sounds play on, each with its own volume.
+---@enum AudioChannel
+local AudioChannel = {
+    SFX = 0,
+    MUSIC = 1,
+    AMBIENCE = 2,
+    VOICE = 3,
+    UI = 4,
+}
+
+return AudioChannel
@@ -0,0 +1,157 @@
+---@meta
+
+---A 2D camera that looks at a point in the world, drawing it to a viewport on the screen.
+---The camera's position is drawn at the center of its viewport, scaled by its zoom and rotated
+---by its rotation.
+---@class (exact) Camera2D: Camera2DMethods
+
+---@class Camera2DModule : Camera2DMethods
+local module = {}
+
+---@class Camera2DMethods
+local methods = {}
+
+---Create a camera looking at the origin of the world, drawing to the viewport.
+---@param viewport Rect
+---@return Camera2D
+---@nodiscard
+function module.new(viewport) end
+
+---The point in the world at the center of the viewport.
+---@param self Camera2D
+---@return Vec2
+---@nodiscard
+function methods.pos(self) end
+
+---Move the camera to look at a point in the world.
+---@param self Camera2D
+---@param pos Vec2
+function methods.set_pos(self, pos) end
+
+---How much the world is scaled, where `2.0` draws everything twice as big.
+---@param self Camera2D
+---@return number
+---@nodiscard
+function methods.zoom(self) end
+
+---Set how much the world is scaled.
+---@param self Camera2D
+---@param zoom number
+function methods.set_zoom(self, zoom) end
+
+---Rotation of the camera in radians. The world is drawn rotated the opposite way.
+---@param self Camera2D
+---@return number
+---@nodiscard
+function methods.rotation(self) end
+
+---Set the rotation of the camera in radians.
+---@param self Camera2D
+---@param radians number
+function methods.set_rotation(self, radians) end
+
+---Where the camera draws to, in screen space.
+---@param self Camera2D
+---@return Rect
+---@nodiscard
+function methods.viewport(self) end
+
+---Set where the camera draws to, in screen space.
+---@param self Camera2D
+---@param viewport Rect
+function methods.set_viewport(self, viewport) end
+
+---The bounds the camera is kept within when it follows a target, if any.
+---@param self Camera2D
+---@return Rect?
+---@nodiscard
+function methods.bounds(self) end
+
+---Set the bounds the camera is kept within when it follows a target, or `nil` for none.
+---@param self Camera2D
+---@param bounds Rect?
+function methods.set_bounds(self, bounds) end
+
+---How much trauma the camera's shake has, from `0` to `1`.
+---@param self Camera2D
+---@return number
+---@nodiscard
+function methods.trauma(self) end
+
+---Set the trauma of the camera's shake, clamped from `0` to `1`.
+---@param self Camera2D
+---@param trauma number
+function methods.set_trauma(self, trauma) end
+
+---Add trauma to the camera's shake, such as `0.3` for a hit or `0.8` for an explosion. The
+---camera shakes by the square of its trauma, which decays over time.
+---@param self Camera2D
+---@param amount number
+function methods.add_trauma(self, amount) end
+
+---Configure the camera's shake. By default, it offsets up to 16 pixels and rotates up to `0.1`
+---radians at full trauma, changes direction 15 times per second, and loses 1 trauma per second.
+---@param self Camera2D
+---@param max_offset Vec2 furthest offset at full trauma, in screen pixels
+---@param max_rotation number furthest rotation at full trauma, in radians
+---@param frequency number? how many times per second the shake changes direction
+---@param decay number? how much trauma is removed every second
+function methods.set_shake(self, max_offset, max_rotation, frequency, decay) end
+
+---Advance the camera's shake. Call this once per frame.
+---@param self Camera2D
+---@param delta_time number
+function methods.update(self, delta_time) end
+
+---Convert a position in the world to a position on the screen.
+---@param self Camera2D
+---@param pos Vec2
+---@return Vec2
+---@nodiscard
+function methods.world_to_screen(self, pos) end
+
+---Convert a position on the screen to a position in the world, such as to find which tile the
+---mouse is over.
+---@param self Camera2D
+---@param pos Vec2
+---@return Vec2
+---@nodiscard
+function methods.screen_to_world(self, pos) end
+
+---The area of the world that is visible in the viewport. If the camera is rotated, this is the
+---bounding box of the visible area.
+---@param self Camera2D
+---@return Rect
+---@nodiscard
+function methods.visible_rect(self) end
+
+---Start drawing in world space. Drawing is clipped to the viewport. Must be followed by `pop`.
+---@param self Camera2D
+function methods.apply(self) end
+
+---Finish drawing in world space, started with `apply`.
+---@param self Camera2D
+function methods.pop(self) end
+
+---Smoothly move towards the target. Higher `speed` catches up faster, closing about 63% of the
+---distance every `1 / speed` seconds, regardless of the frame rate.
+---@param self Camera2D
+---@param target Vec2
+---@param speed number
+---@param delta_time number
+function methods.follow(self, target, speed, delta_time) end
+
+---Smoothly move towards the target only once it leaves a dead zone of `size` world units around
+---the center of the view, so small movements don't move the camera.
+---@param self Camera2D
+---@param target Vec2
+---@param size Vec2
+---@param speed number
+---@param delta_time number
+function methods.follow_deadzone(self, target, size, speed, delta_time) end
+
+---Move the camera so it shows nothing outside of its bounds, if it has any.
+---@param self Camera2D
+function methods.clamp_to_bounds(self) end
+
+return module
@@ -1,6 +1,6 @@
 ---@meta
 
----@alias BlendMode "normal"|"add"|"subtract"|"multiply"
+---@alias BlendMode "normal"|"add"|"subtract"|"multiply"|"replace"
 ---@alias Topology "triangles"|"lines"|"points"
 
 ---@class DrawModule
@@ -24,6 +24,23 @@ function Draw.set_layer(layer) end
 ---@param shader Shader?
 function Draw.set_shader(shader) end
 
+---Set the material future drawing methods will use. This switches to the material's shader
+---and then assigns all the parameters the material has set. If they already have those
+---values, the current batch is not interrupted.
+---@param material Material
+function Draw.set_material(material) end
+
+---The post material of the current layer.
+---@return Material?
+---@nodiscard
+function Draw.layer_post(self) end
+
+---Set a post material for the current layer. When the current surface is finished, everything
+---drawn to the layer is rendered offscreen and then drawn over the surface through the
+---material's shader, at the layer's depth. Reset when the surface changes.
+---@param material Material?
+function Draw.set_layer_post(material) end
+
 ---Set an `i32` parameter.
 ---@param name string
 ---@param value integer
@@ -69,6 +86,12 @@ function Draw.set_param_mat3(name, value) end
 ---@param value Mat4
 function Draw.set_param_mat4(name, value) end
 
+---Set a struct or array parameter declared as `var<uniform>`, from a list of floats that
+---matches the WGSL type's layout and size.
+---@param name string
+---@param value number[]
+function Draw.set_param_block(name, value) end
+
 ---Set a `texture_2d<f32>` parameter.
 ---@param name string
 ---@param value Texture
@@ -110,6 +133,14 @@ function Draw.clip_rect(self) end
 ---@param value Rect
 function Draw.set_clip_rect(value) end
 
+---Push a clip rectangle to the stack. It is transformed by the current transform and
+---intersected with the current clip rectangle, so nested regions stay inside their parents.
+---@param rect Rect
+function Draw.push_clip_rect(rect) end
+
+---Pop a clip rectangle off the top of the stack, restoring the previous one.
+function Draw.pop_clip_rect(self) end
+
 ---The current transform.
 ---@return Affine2
 ---@nodiscard
@@ -170,6 +201,34 @@ function Draw.textured_quad(texture, quad, color, mode, flip_x, flip_y) end
 ---@param flip_y boolean?
 function Draw.texture_at(texture, pos, color, mode, flip_x, flip_y) end
 
+---Draw a quad filled with the region of a texture specified by `uv_rect`, in normalized texture
+---coordinates. Coordinates outside of `0..1` wrap or clamp depending on the main sampler.
+---@param texture Texture
+---@param quad Quad
+---@param uv_rect Rect
+---@param color Color?
+---@param mode ColorMode?
+function Draw.textured_quad_uv(texture, quad, uv_rect, color, mode) end
+
+---Fill a rectangle by repeating a texture at its native size. The `uv_scale` multiplies the
+---texture coordinates, and `uv_offset` scrolls them in units of the texture's size.
+---@param texture Texture
+---@param dst Rect
+---@param uv_scale Vec2?
+---@param uv_offset Vec2?
+---@param color Color?
+---@param mode ColorMode?
+function Draw.texture_tiled(texture, dst, uv_scale, uv_offset, color, mode) end
+
+---Draw a textured polygon, with a texture coordinate for each of its points. The polygon can be
+---concave, and will be triangulated internally.
+---@param texture Texture
+---@param points Vec2[]
+---@param uvs Vec2[]
+---@param color Color?
+---@param mode ColorMode?
+function Draw.textured_polygon(texture, points, uvs, color, mode) end
+
 ---Draw a single point.
 ---@param point Vec2
 ---@param color Color
@@ -211,6 +270,25 @@ function Draw.line_obj(line, color) end
 ---@param loops boolean
 function Draw.lines(points, color, loops) end
 
+---Draw a dashed line. Animating the `phase` makes the dashes crawl along the line. If
+---`dash_len` is zero, the line will be dotted instead.
+---@param from Vec2
+---@param to Vec2
+---@param dash_len number
+---@param gap_len number
+---@param color Color
+---@param phase number?
+function Draw.line_dashed(from, to, dash_len, gap_len, color, phase) end
+
+---Draw dashed lines connecting the series of points into a chain, optionally looping to the start.
+---@param points Vec2[]
+---@param dash_len number
+---@param gap_len number
+---@param color Color
+---@param loops boolean
+---@param phase number?
+function Draw.lines_dashed(points, dash_len, gap_len, color, loops, phase) end
+
 ---Draw a filled triangle.
 ---@param a Vec2
 ---@param b Vec2
@@ -287,6 +365,14 @@ function Draw.rect_outline(x, y, w, h, color) end
 ---@param color Color
 function Draw.rect_obj_outline(rect, color) end
 
+---Draw a dashed rectangle outline. Animating the `phase` creates "marching ants".
+---@param rect Rect
+---@param dash_len number
+---@param gap_len number
+---@param color Color
+---@param phase number?
+function Draw.rect_outline_dashed(rect, dash_len, gap_len, color, phase) end
+
 ---Draw a filled polygon.
 ---@param poly Polygon
 ---@param color Color
@@ -297,6 +383,14 @@ function Draw.polygon(poly, color) end
 ---@param color Color
 function Draw.polygon_outline(poly, color) end
 
+---Draw a dashed polygon outline.
+---@param poly Polygon
+---@param dash_len number
+---@param gap_len number
+---@param color Color
+---@param phase number?
+function Draw.polygon_outline_dashed(poly, dash_len, gap_len, color, phase) end
+
 ---Draw a filled circle.
 ---@param x number
 ---@param y number
@@ -339,6 +433,32 @@ function Draw.circle_outline(center, radius, color, seg_count) end
 ---@param seg_count integer?
 function Draw.circle_obj_outline(circ, color, seg_count) end
 
+---Draw a dashed circle outline.
+---@param circ Circle
+---@param dash_len number
+---@param gap_len number
+---@param color Color
+---@param phase number?
+---@param seg_count integer?
+function Draw.circle_outline_dashed(circ, dash_len, gap_len, color, phase, seg_count) end
+
+---Draw a pie-shaped fill of a circle, sweeping clockwise from an angle.
+---@param circ Circle
+---@param radians number
+---@param t number How far around the circle to fill, from `0` to `1`.
+---@param color Color
+---@param seg_count integer?
+function Draw.radial_fill(circ, radians, t, color, seg_count) end
+
+---Draw a ring inside the edge of a circle, sweeping clockwise from an angle.
+---@param circ Circle
+---@param thickness number
+---@param radians number
+---@param t number How far around the circle to fill, from `0` to `1`.
+---@param color Color
+---@param seg_count integer?
+function Draw.radial_ring(circ, thickness, radians, t, color, seg_count) end
+
 ---Draw a subtexture.
 ---@param sub SubTexture
 ---@param dst Quad
@@ -357,6 +477,17 @@ function Draw.subtextured_quad(sub, dst, color, mode, flip_x, flip_y) end
 ---@param flip_y boolean?
 function Draw.subtexture_at(sub, pos, color, mode, flip_x, flip_y) end
 
+---Draw a subtexture rotated and scaled around a pivot, which is placed at `pos`. The pivot is
+---relative to the top-left of the subtexture's untrimmed frame.
+---@param sub SubTexture
+---@param pos Vec2
+---@param rotation number
+---@param pivot Vec2?
+---@param scale Vec2|number?
+---@param color Color?
+---@param mode ColorMode?
+function Draw.subtexture_rotated(sub, pos, rotation, pivot, scale, color, mode) end
+
 ---Draw text with the provided font and size.Methods
 ---@param text string
 ---@param pos Vec2
@@ -374,6 +505,29 @@ function Draw.text(text, pos, font, size, color) end
 ---@param size number?
 function Draw.text(text, x, y, font, size, color) end
 
+---Draw text wrapped to `max_width` and aligned, with the top-left at `pos`. The
+---`line_spacing` is multiplied by the font's size to get the distance between lines.
+---@param text string
+---@param pos Vec2
+---@param font Font
+---@param max_width number?
+---@param align TextAlign?
+---@param line_spacing number?
+---@param color Color?
+function Draw.text_layout(text, pos, font, max_width, align, line_spacing, color) end
+
+---Draw text along a path, with each glyph's baseline resting on the path and rotated to
+---follow it. The text starts `offset` units along the path, `spacing` is added between each
+---glyph, and any glyphs that don't fit on the path are not drawn.
+---@param text string
+---@param points Vec2[]
+---@param font Font
+---@param color Color?
+---@param size number?
+---@param offset number?
+---@param spacing number?
+function Draw.text_on_path(text, points, font, color, size, offset, spacing) end
+
 ---Draw a custom set of vertices & indices.
 ---@param texture Texture?
 ---@param topology Topology
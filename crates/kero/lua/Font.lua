@@ -1,5 +1,9 @@
 ---@meta
 
+---@alias SubpixelMode "off"|"snap"|integer
+
+---@alias TextAlign "left"|"center"|"right"|"justify"
+
 ---@class (exact) Font: FontMethods
 
 ---@class FontModule: FontMethods
@@ -15,14 +19,25 @@ local methods = {}
 ---@nodiscard
 function module.new(size, pixelated) end
 
----Load and rasterize a font from a TTF file.
+---Load and rasterize a font from a TTF file. Smooth fonts can optionally be snapped to whole
+---pixels (`"snap"`) or pre-rasterized at a number of horizontal subpixel phases (eg. `4`).
 ---@param path string
 ---@param size number
 ---@param pixelated boolean
 ---@param chars string?
+---@param subpixel SubpixelMode?
+---@return Font
+---@nodiscard
+function module.from_ttf_file(path, size, pixelated, chars, subpixel) end
+
+---Load a font from a TTF file, rasterizing its glyphs as signed distance fields so text stays
+---sharp when it's scaled up or rotated.
+---@param path string
+---@param size number
+---@param chars string?
 ---@return Font
 ---@nodiscard
-function module.from_ttf_file(path, size, pixelated, chars) end
+function module.from_ttf_file_sdf(path, size, chars) end
 
 ---The font's baked size.
 ---@param self Font
@@ -36,6 +51,23 @@ function methods.size(self) end
 ---@nodiscard
 function methods.pixelated(self) end
 
+---If the font's glyphs are signed distance fields.
+---@param self Font
+---@return boolean
+---@nodiscard
+function methods.sdf(self) end
+
+---How glyphs are positioned when drawn at fractional pixel coordinates.
+---@param self Font
+---@return SubpixelMode
+---@nodiscard
+function methods.subpixel(self) end
+
+---Set how glyphs are positioned when drawn at fractional pixel coordinates.
+---@param self Font
+---@param subpixel SubpixelMode
+function methods.set_subpixel(self, subpixel) end
+
 ---Set the character's render glyph.
 ---@param self Font
 ---@param chr string
@@ -66,4 +98,14 @@ function methods.set_kerning(self, left, right, kerning) end
 ---@nodiscard
 function methods.kerning(self, left, right) end
 
+---Size of the text when wrapped to `max_width` and aligned, as drawn by `Draw.text_layout`.
+---@param self FontMethods
+---@param text string
+---@param max_width number?
+---@param align TextAlign?
+---@param line_spacing number?
+---@return Vec2
+---@nodiscard
+function methods.layout_size(self, text, max_width, align, line_spacing) end
+
 return module
\ No newline at end of file
@@ -107,4 +107,38 @@ function methods.axis(self, axis) end
 ---@nodiscard
 function methods.axis_changed(self, axis) end
 
+---If the gamepad supports rumble.
+---@param self Gamepad
+---@return boolean
+---@nodiscard
+function methods.rumble_supported(self) end
+
+---Rumble the gamepad's strong (low frequency) and weak (high frequency) motors for the
+---duration, with strengths from `0.0` to `1.0`. This replaces any rumble already playing.
+---@param self Gamepad
+---@param strong number
+---@param weak number
+---@param duration number Duration in seconds.
+function methods.rumble(self, strong, weak, duration) end
+
+---Stop any rumble that is playing.
+---@param self Gamepad
+function methods.stop_rumble(self) end
+
+---Play a vibration preset, on top of any already playing.
+---@param self Gamepad
+---@param preset "small_hit"|"big_explosion"|"heartbeat"|"tick"
+---@param intensity number? From `0.0` to `1.0`, defaults to `1.0`.
+function methods.vibrate(self, preset, intensity) end
+
+---Stop all vibrations playing on the gamepad.
+---@param self Gamepad
+function methods.stop_vibrations(self) end
+
+---If any vibrations are playing on the gamepad.
+---@param self Gamepad
+---@return boolean
+---@nodiscard
+function methods.is_vibrating(self) end
+
 return module
\ No newline at end of file
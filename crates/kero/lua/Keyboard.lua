@@ -33,11 +33,39 @@ function Keyboard.repeated(key) end
 ---@nodiscard
 function Keyboard.pressed_or_repeated(key) end
 
+---The key value, `1.0` if it is down and `0.0` if not.
+---@param key Key
+---@return number
+---@nodiscard
+function Keyboard.value(key) end
+
+---All keys that are currently down.
+---@param fill Key[]?
+---@return Key[]
+---@nodiscard
+function Keyboard.currently_down(fill) end
+
+---Every key, in order.
+---@param fill Key[]?
+---@return Key[]
+---@nodiscard
+function Keyboard.keys(fill) end
+
 ---Text that was typed by the keyboard this frame.
 ---@return string?
 ---@nodiscard
 function Keyboard.text_input() end
 
+---Text currently being composed in the IME, not yet committed.
+---@return string
+---@nodiscard
+function Keyboard.composition() end
+
+---If text is currently being composed in the IME.
+---@return boolean
+---@nodiscard
+function Keyboard.is_composing() end
+
 ---If left or right control is down.
 ---@return boolean
 ---@nodiscard
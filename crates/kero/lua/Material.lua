@@ -0,0 +1,57 @@
+---@meta
+
+---@class (exact) Material: MaterialMethods
+
+---@class MaterialModule: MaterialMethods
+local module = {}
+
+---@class MaterialMethods
+local methods = {}
+
+---Create a new material for the shader, with no parameters set. Parameters are validated when
+---they are set, and parameters the material doesn't set keep their current values when the
+---material is applied with `Draw.set_material()`.
+---@param shader Shader
+---@return Material
+---@nodiscard
+function module.new(shader) end
+
+---The material's shader.
+---@param self Material
+---@return Shader
+---@nodiscard
+function methods.shader(self) end
+
+---Create a copy of the material, which can have its own parameter overrides.
+---@param self Material
+---@return Material
+---@nodiscard
+function methods.clone(self) end
+
+---Set a parameter's value. Errors if the shader has no parameter with the name, or if the
+---value doesn't match the parameter's type. Struct and array parameters take a list of floats.
+---@param self Material
+---@param name string
+---@param value Texture|Sampler|number|Vec2|Vec3|Vec4|Mat2|Mat3|Mat4|number[]
+function methods.set(self, name, value) end
+
+---The value of a parameter, if the material sets it.
+---@param self Material
+---@param name string
+---@return Texture|Sampler|number|Vec2|Vec3|Vec4|Mat2|Mat3|Mat4|number[]|nil
+---@nodiscard
+function methods.get(self, name) end
+
+---Draw the shader's parameters and the material's values for them as a text overlay.
+---@param self Material
+---@param font Font
+---@param pos Vec2
+---@param color Color?
+function methods.draw_overlay(self, font, pos, color) end
+
+---Unset a parameter, so it keeps whatever value it had when the material is applied.
+---@param self Material
+---@param name string
+function methods.unset(self, name) end
+
+return module
@@ -18,11 +18,49 @@ function Mouse.x() end
 ---@nodiscard
 function Mouse.y() end
 
----Vertical scroll of the mouse.
+---Vertical scrolling this frame in lines, with pixel-precise scrolling converted to lines.
+---Positive values scroll up.
 ---@return number
 ---@nodiscard
 function Mouse.scroll() end
 
+---Horizontal scrolling this frame in lines. Positive values scroll left.
+---@return number
+---@nodiscard
+function Mouse.scroll_x() end
+
+---How far the mouse moved this frame, straight from the device, so it keeps reporting movement
+---while captured. The units depend on the platform and mouse sensitivity.
+---@return Vec2
+---@nodiscard
+function Mouse.motion() end
+
+---If the mouse is captured.
+---@return boolean
+---@nodiscard
+function Mouse.captured() end
+
+---Capture the mouse, hiding the cursor and locking it inside the window, for games that aim or
+---move the camera with the mouse. Returns `false` if the cursor couldn't be locked or confined.
+---@param captured boolean
+---@return boolean
+function Mouse.set_captured(captured) end
+
+---All scrolling since the game started in lines, for easing smoothly towards (eg. zoom levels).
+---@return Vec2
+---@nodiscard
+function Mouse.scroll_total() end
+
+---How many lines were scrolled this frame, by mouse wheels that scroll by line.
+---@return Vec2
+---@nodiscard
+function Mouse.scroll_lines() end
+
+---How far was scrolled this frame, by touchpads and mouse wheels that scroll by pixel.
+---@return Vec2
+---@nodiscard
+function Mouse.scroll_delta() end
+
 ---If the button is held down this frame.
 ---@param btn MouseButton
 ---@return boolean
@@ -0,0 +1,89 @@
+---@meta
+
+---A library of named particle effects loaded from JSON files, and every emitter spawned from
+---them. Emitters are removed once they have stopped and all their particles have died.
+---@class (exact) Particles: ParticlesMethods
+
+---@class ParticlesModule: ParticlesMethods
+local module = {}
+
+---@class ParticlesMethods
+local methods = {}
+
+---Create an empty particle system, optionally seeding its randomness.
+---@param seed integer?
+---@return Particles
+---@nodiscard
+function module.new(seed) end
+
+---Load an effect from a JSON emitter definition, replacing any effect that had the same name.
+---The definition's textures are looked up by name in the `textures` table.
+---@param self Particles
+---@param name string
+---@param path string
+---@param textures table<string, SubTexture>?
+function methods.load(self, name, path, textures) end
+
+---If an effect with the name has been loaded.
+---@param self Particles
+---@param name string
+---@return boolean
+---@nodiscard
+function methods.has_effect(self, name) end
+
+---Spawn an emitter from an effect at the position, returning its id.
+---@param self Particles
+---@param name string
+---@param pos Vec2
+---@return integer
+function methods.spawn(self, name, pos) end
+
+---If the emitter hasn't finished yet.
+---@param self Particles
+---@param id integer
+---@return boolean
+---@nodiscard
+function methods.is_alive(self, id) end
+
+---Move where an emitter spawns new particles.
+---@param self Particles
+---@param id integer
+---@param pos Vec2
+function methods.set_pos(self, id, pos) end
+
+---Immediately spawn particles from an emitter.
+---@param self Particles
+---@param id integer
+---@param count integer
+function methods.burst(self, id, count) end
+
+---Stop an emitter. It is removed once its particles have died.
+---@param self Particles
+---@param id integer
+function methods.stop(self, id) end
+
+---Immediately remove an emitter and its particles.
+---@param self Particles
+---@param id integer
+function methods.kill(self, id) end
+
+---Remove every emitter.
+---@param self Particles
+function methods.clear(self) end
+
+---How many particles are alive across every emitter.
+---@param self Particles
+---@return integer
+---@nodiscard
+function methods.count(self) end
+
+---Update every emitter, removing those that have finished.
+---@param self Particles
+---@param delta_time number
+function methods.update(self, delta_time) end
+
+---Draw every emitter.
+---@param self Particles
+function methods.draw(self) end
+
+return module
@@ -0,0 +1,31 @@
+---@meta
+
+---@class Platform
+local Platform = {}
+
+---@class PresenceTimestamps
+---@field start integer? When the activity started, in seconds since the Unix epoch.
+---@field end integer? When the activity ends, in seconds since the Unix epoch.
+
+---@class PresenceAssets
+---@field large_image string?
+---@field large_text string?
+---@field small_image string?
+---@field small_text string?
+
+---Show what the player is doing on their profile, such as "In Level 3".
+---@param state string
+---@param details string?
+---@param timestamps PresenceTimestamps?
+---@param assets PresenceAssets?
+function Platform.set_presence(state, details, timestamps, assets) end
+
+---Stop showing what the player is doing.
+function Platform.clear_presence() end
+
+---If a platform to show the player's presence on is connected.
+---@return boolean
+---@nodiscard
+function Platform.is_presence_connected() end
+
+return Platform
\ No newline at end of file
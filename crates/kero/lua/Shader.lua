@@ -12,6 +12,12 @@
 ---     |"mat2"
 ---     |"mat3"
 ---     |"mat4"
+---     |"block"
+
+---A parameter defined in a shader. Uniforms also have their `size` in bytes, how many
+---`components` make up their value (`nil` for blocks), and the `default` value they have
+---if nothing sets them.
+---@alias ParamDef { name: string, type: ParamType, binding: integer, size: integer?, components: integer?, default: any }
 
 ---@class (exact) Shader: ShaderMethods
 
@@ -38,6 +44,20 @@ function module.new(source) end
 ---@nodiscard
 function methods.params(self) end
 
+---Return a list of all the shader's parameters, in binding order.
+---@param self Shader
+---@return ParamDef[]
+---@nodiscard
+function methods.param_defs(self) end
+
+---Return the value a uniform param has if nothing sets it (or `nil` if the param doesn't exist
+---or isn't a uniform).
+---@param self Shader
+---@param name string
+---@return number|Vec2|Vec3|Vec4|Mat2|Mat3|Mat4|number[]|nil
+---@nodiscard
+function methods.param_default(self, name) end
+
 ---Return the type of the param (or `nil` if the param doesn't exist).
 ---@param self Shader
 ---@param name string
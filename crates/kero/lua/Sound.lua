@@ -0,0 +1,43 @@
+---@meta
+
+---A sound loaded into memory, ready to be played.
+---@class (exact) Sound: SoundMethods
+
+---@class SoundModule: SoundMethods
+local module = {}
+
+---@class SoundMethods
+local methods = {}
+
+---Load a sound from a file, detecting the format from its header. WAV and OGG Vorbis are
+---supported.
+---@param path string
+---@return Sound
+---@nodiscard
+function module.from_file(path) end
+
+---How many channels the sound has, `1` for mono or `2` for stereo.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.channels(self) end
+
+---The sample rate in hertz.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.sample_rate(self) end
+
+---How many frames the sound has, where each frame has one sample per channel.
+---@param self Sound
+---@return integer
+---@nodiscard
+function methods.frames(self) end
+
+---Length of the sound in seconds.
+---@param self Sound
+---@return number
+---@nodiscard
+function methods.duration(self) end
+
+return module
@@ -0,0 +1,64 @@
+---@meta
+
+---@alias SoundSelect "random"|"no_repeat"|"round_robin"|"shuffle"
+
+---Named sound events that each play one of several variations of a sound, with random volume
+---and pitch, so repeated sounds like footsteps don't sound identical.
+---@class (exact) SoundBank: SoundBankMethods
+
+---@class SoundBankModule: SoundBankMethods
+local module = {}
+
+---@class SoundBankMethods
+local methods = {}
+
+---Create an empty sound bank, optionally seeding how it picks sounds.
+---@param seed integer?
+---@return SoundBank
+---@nodiscard
+function module.new(seed) end
+
+---Add an event, replacing any event that had the same name. Each time the event plays, one of
+---its sounds is picked by `select` (default `"no_repeat"`), and its volume and pitch are picked
+---randomly from their ranges (default `1.0`).
+---@param self SoundBank
+---@param name string
+---@param sounds Sound[]
+---@param min_volume number?
+---@param max_volume number?
+---@param min_pitch number?
+---@param max_pitch number?
+---@param select SoundSelect?
+---@param channel AudioChannel?
+function methods.add(self, name, sounds, min_volume, max_volume, min_pitch, max_pitch, select, channel) end
+
+---Remove an event, returning `true` if it existed.
+---@param self SoundBank
+---@param name string
+---@return boolean
+function methods.remove(self, name) end
+
+---If the bank has an event with the name.
+---@param self SoundBank
+---@param name string
+---@return boolean
+---@nodiscard
+function methods.contains(self, name) end
+
+---Play the next sound of an event, with its volume multiplied by `volume`. Returns `nil` if there
+---is no event with the name, or it has no sounds.
+---@param self SoundBank
+---@param name string
+---@param volume number?
+---@return Playback?
+function methods.play(self, name, volume) end
+
+---Play the next sound of an event, emitted from a position in the world.
+---@param self SoundBank
+---@param name string
+---@param pos Vec2
+---@param volume number?
+---@return Playback?
+function methods.play_at(self, name, pos, volume) end
+
+return module
@@ -0,0 +1,33 @@
+---@meta
+
+---@class Telemetry
+local Telemetry = {}
+
+---If the player has opted in to telemetry.
+---@return boolean
+---@nodiscard
+function Telemetry.enabled() end
+
+---Opt in or out of telemetry, remembering the choice between runs.
+---@param enabled boolean
+function Telemetry.set_enabled(enabled) end
+
+---If events are being recorded, which requires both a sink and the player opting in.
+---@return boolean
+---@nodiscard
+function Telemetry.is_recording() end
+
+---A random ID for this run of the game, attached to every event.
+---@return string
+---@nodiscard
+function Telemetry.session() end
+
+---Record an event, with optional details.
+---@param name string
+---@param props table<string, boolean|number|string>?
+function Telemetry.track(name, props) end
+
+---Hand all queued events to the background thread to be sent now.
+function Telemetry.flush() end
+
+return Telemetry
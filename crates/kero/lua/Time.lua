@@ -8,11 +8,25 @@ local Time = {}
 ---@nodiscard
 function Time.fps() end
 
----Delta time since the last frame.
+---Delta time since the last frame, scaled by the time scale and stopped during hit-stop.
 ---@return number
 ---@nodiscard
 function Time.delta() end
 
+---Delta time since the last frame, ignoring the time scale and hit-stop.
+---@return number
+---@nodiscard
+function Time.unscaled_delta() end
+
+---How fast time passes in the game, where 1 is normal speed.
+---@return number
+---@nodiscard
+function Time.time_scale() end
+
+---Set how fast time passes in the game. Defaults to 1.
+---@param scale number
+function Time.set_time_scale(scale) end
+
 ---Total time passed since the app started.
 ---@return number
 ---@nodiscard
@@ -23,6 +37,47 @@ function Time.since_startup() end
 ---@nodiscard
 function Time.frame() end
 
+---How many times per second `Main:fixed_update()` is called.
+---@return number
+---@nodiscard
+function Time.fixed_rate() end
+
+---Set how many times per second `Main:fixed_update()` is called. Defaults to 60.
+---@param rate number
+function Time.set_fixed_rate(rate) end
+
+---Duration of each fixed update, in seconds.
+---@return number
+---@nodiscard
+function Time.fixed_delta() end
+
+---How many fixed updates have run.
+---@return integer
+---@nodiscard
+function Time.fixed_frame() end
+
+---How far between the last fixed update and the next one the game is, from 0 to 1. Draw anything
+---moved in `Main:fixed_update()` between its previous and current positions by this.
+---@return number
+---@nodiscard
+function Time.blend_factor() end
+
+---If updates are paused. While paused, the game keeps rendering but time stands still.
+---@return boolean
+---@nodiscard
+function Time.paused() end
+
+---Pause or resume updates.
+---@param paused boolean
+function Time.set_paused(paused) end
+
+---Resume updates if paused, otherwise pause them.
+function Time.toggle_paused() end
+
+---Run a single update while paused, to step through the game frame by frame. Does nothing if
+---not paused.
+function Time.step() end
+
 ---Flicker between true and false.
 ---@param on_time number
 ---@param off_time number?
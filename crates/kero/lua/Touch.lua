@@ -0,0 +1,73 @@
+---@meta
+
+---@class Touch
+local Touch = {}
+
+---@class TouchPoint
+---@field id integer Identifies the finger for as long as it is on the screen.
+---@field pos Vec2 The position in window coordinates.
+---@field start_pos Vec2 Where the finger first touched the screen.
+---@field force number? How hard the finger is pressing from 0 to 1, on screens that can tell.
+---@field held number How long the finger has been on the screen, in seconds.
+
+---How many fingers are on the screen.
+---@return integer
+---@nodiscard
+function Touch.count() end
+
+---If any fingers are on the screen.
+---@return boolean
+---@nodiscard
+function Touch.any_down() end
+
+---Every finger on the screen, in the order they touched it.
+---@return TouchPoint[]
+---@nodiscard
+function Touch.points() end
+
+---Fingers that touched the screen this frame.
+---@return TouchPoint[]
+---@nodiscard
+function Touch.started() end
+
+---Fingers that left the screen this frame.
+---@return TouchPoint[]
+---@nodiscard
+function Touch.ended() end
+
+---Where the screen was tapped this frame, if it was.
+---@return Vec2?
+---@nodiscard
+function Touch.tap() end
+
+---Where a single finger was held still long enough to count as a long-press this frame.
+---@return Vec2?
+---@nodiscard
+function Touch.long_press() end
+
+---If one or more fingers are dragging across the screen this frame.
+---@return boolean
+---@nodiscard
+function Touch.is_panning() end
+
+---How far the fingers dragged this frame.
+---@return Vec2
+---@nodiscard
+function Touch.pan_delta() end
+
+---If two or more fingers are pinching this frame.
+---@return boolean
+---@nodiscard
+function Touch.is_pinching() end
+
+---How much the first two fingers spread apart this frame, as a scale factor.
+---@return number
+---@nodiscard
+function Touch.pinch_scale() end
+
+---The point between the first two fingers, which a pinch zoom should zoom around.
+---@return Vec2?
+---@nodiscard
+function Touch.pinch_center() end
+
+return Touch
\ No newline at end of file
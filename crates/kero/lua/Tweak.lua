@@ -0,0 +1,55 @@
+---@meta
+
+---A global registry of variables that can be adjusted while the game runs. While the overlay
+---is enabled, variables are selected with the up and down arrow keys, adjusted with left and
+---right (hold `Shift` for bigger steps), and reset with `Backspace`.
+---@class TweakModule
+local Tweak = {}
+
+---Get the live value of a variable, registering it with the default the first time. The type
+---of the variable is taken from the default.
+---@generic T: boolean|integer|number|Vec2
+---@param name string
+---@param default T
+---@return T
+---@nodiscard
+function Tweak.get(name, default) end
+
+---Get the live value of a color variable, registering it with the default the first time.
+---@param name string
+---@param default Color
+---@return Color
+---@nodiscard
+function Tweak.get_color(name, default) end
+
+---Set the value of a registered variable. Returns false if it doesn't exist or has a
+---different type.
+---@param name string
+---@param value boolean|integer|number|Vec2|Color
+---@return boolean
+function Tweak.set(name, value) end
+
+---Reset a variable to its default value.
+---@param name string
+function Tweak.reset(name) end
+
+---Reset all variables to their default values.
+function Tweak.reset_all() end
+
+---If the overlay is drawn and listening to input.
+---@return boolean
+---@nodiscard
+function Tweak.overlay_enabled() end
+
+---Set if the overlay is drawn and listening to input.
+---@param enabled boolean
+function Tweak.set_overlay_enabled(enabled) end
+
+---Show the overlay if hidden, otherwise hide it.
+function Tweak.toggle_overlay() end
+
+---Set the font the overlay is drawn with. The overlay isn't drawn without one.
+---@param font Font?
+function Tweak.set_font(font) end
+
+return Tweak
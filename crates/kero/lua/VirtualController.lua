@@ -0,0 +1,368 @@
+---@meta
+
+---A virtual gamepad controller, which maps keyboard keys and gamepad inputs into virtual
+---inputs so both can be listened to at the same time.
+---@class (exact) VirtualController: VirtualControllerMethods
+
+---A virtual button that listens to a key and a gamepad button.
+---@class (exact) VirtualButton: VirtualButtonMethods
+
+---A virtual axis that listens to a gamepad axis and a negative and positive button.
+---@class (exact) VirtualAxis: VirtualAxisMethods
+
+---A virtual stick made of an x and y axis.
+---@class (exact) VirtualStick: VirtualStickMethods
+
+---@class VirtualControllerModule
+local module = {}
+
+---@class VirtualControllerMethods
+local methods = {}
+
+---@class VirtualButtonMethods
+local button = {}
+
+---@class VirtualAxisMethods
+local axis = {}
+
+---@class VirtualStickMethods
+local stick = {}
+
+---Create a controller with the arrow keys mapped to the left stick and d-pad, the face
+---buttons mapped to `Z` (south), `X` (east), `A` (west), and `S` (north), start and select
+---mapped to `Enter` and `Space`, the bumpers mapped to `Q` and `W`, and the triggers mapped to
+---left and right `Shift`.
+---@return VirtualController
+---@nodiscard
+function module.basic() end
+
+---Create a controller with no mappings that always listens to the last active gamepad.
+---@return VirtualController
+---@nodiscard
+function module.last_active() end
+
+---Create a controller with no mappings that listens to a specific gamepad.
+---@param gamepad Gamepad?
+---@return VirtualController
+---@nodiscard
+function module.specific(gamepad) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.left_bumper(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.right_bumper(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.left_trigger(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.right_trigger(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.dpad_left(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.dpad_right(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.dpad_up(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.dpad_down(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.east(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.south(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.west(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.north(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.start(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.select(self) end
+
+---@param self VirtualController
+---@return VirtualButton
+---@nodiscard
+function methods.menu(self) end
+
+---The left stick combined with the d-pad.
+---@param self VirtualController
+---@return VirtualStick
+---@nodiscard
+function methods.direction(self) end
+
+---@param self VirtualController
+---@return VirtualStick
+---@nodiscard
+function methods.left_stick(self) end
+
+---@param self VirtualController
+---@return VirtualStick
+---@nodiscard
+function methods.right_stick(self) end
+
+---Listen to a specific gamepad.
+---@param self VirtualController
+---@param gamepad Gamepad?
+function methods.set_specific(self, gamepad) end
+
+---Always listen to the last active gamepad.
+---@param self VirtualController
+function methods.set_last_active(self) end
+
+---Create a button that listens to the same gamepad as the controller.
+---@param self VirtualController
+---@param key Key?
+---@param btn GamepadButton?
+---@return VirtualButton
+---@nodiscard
+function methods.button(self, key, btn) end
+
+---Create an axis that listens to the same gamepad as the controller.
+---@param self VirtualController
+---@param axis GamepadAxis?
+---@param neg VirtualButton?
+---@param pos VirtualButton?
+---@return VirtualAxis
+---@nodiscard
+function methods.axis(self, axis, neg, pos) end
+
+---Create a stick that listens to the same gamepad as the controller.
+---@param self VirtualController
+---@param x_axis VirtualAxis?
+---@param y_axis VirtualAxis?
+---@return VirtualStick
+---@nodiscard
+function methods.stick(self, x_axis, y_axis) end
+
+---Map the bumpers to the left and right `Shift` keys.
+---@param self VirtualController
+function methods.set_bumpers_shift(self) end
+
+---Map the bumpers to `Q` and `W`.
+---@param self VirtualController
+function methods.set_bumpers_qw(self) end
+
+---Map the triggers to the left and right `Shift` keys.
+---@param self VirtualController
+function methods.set_triggers_shift(self) end
+
+---Map the triggers to `Q` and `W`.
+---@param self VirtualController
+function methods.set_triggers_qw(self) end
+
+---Map the face buttons to `Z` (south), `X` (east), `A` (west), and `S` (north).
+---@param self VirtualController
+function methods.set_face_buttons_zxas(self) end
+
+---Map the left stick to the arrow keys.
+---@param self VirtualController
+function methods.set_left_stick_arrows(self) end
+
+---Map the d-pad to the arrow keys.
+---@param self VirtualController
+function methods.set_dpad_arrows(self) end
+
+---If any of the controller's buttons are listening for a new binding.
+---@param self VirtualController
+---@return boolean
+---@nodiscard
+function methods.is_listening(self) end
+
+---Save all of the controller's bindings to a JSON file.
+---@param self VirtualController
+---@param path string
+function methods.save_bindings(self, path) end
+
+---Replace all of the controller's bindings with ones saved to a JSON file.
+---@param self VirtualController
+---@param path string
+function methods.load_bindings(self, path) end
+
+---The key this button listens to.
+---@param self VirtualButton
+---@return Key?
+---@nodiscard
+function button.key(self) end
+
+---The gamepad button this button listens to.
+---@param self VirtualButton
+---@return GamepadButton?
+---@nodiscard
+function button.button(self) end
+
+---Set the key to listen to.
+---@param self VirtualButton
+---@param key Key?
+function button.set_key(self, key) end
+
+---Set the gamepad button to listen to.
+---@param self VirtualButton
+---@param btn GamepadButton?
+function button.set_button(self, btn) end
+
+---Start or stop listening for a new binding. While listening, call `poll_rebind` every frame
+---to capture the next key or gamepad button the player presses.
+---@param self VirtualButton
+---@param listening boolean
+function button.rebind(self, listening) end
+
+---If the button is listening for a new binding.
+---@param self VirtualButton
+---@return boolean
+---@nodiscard
+function button.is_listening(self) end
+
+---If listening, check if a key or gamepad button was pressed this frame and bind it, returning
+---`true` once the button has been rebound.
+---@param self VirtualButton
+---@return boolean
+function button.poll_rebind(self) end
+
+---If the key or gamepad button is down.
+---@param self VirtualButton
+---@return boolean
+---@nodiscard
+function button.down(self) end
+
+---If the key or gamepad button was pressed this frame.
+---@param self VirtualButton
+---@return boolean
+---@nodiscard
+function button.pressed(self) end
+
+---If the key or gamepad button was released this frame.
+---@param self VirtualButton
+---@return boolean
+---@nodiscard
+function button.released(self) end
+
+---If the button state changed this frame.
+---@param self VirtualButton
+---@return boolean
+---@nodiscard
+function button.changed(self) end
+
+---The button value from `0.0` (fully up) to `1.0` (fully down).
+---@param self VirtualButton
+---@return number
+---@nodiscard
+function button.value(self) end
+
+---The gamepad axis this axis listens to.
+---@param self VirtualAxis
+---@return GamepadAxis?
+---@nodiscard
+function axis.axis(self) end
+
+---The button on the negative side of the axis.
+---@param self VirtualAxis
+---@return VirtualButton
+---@nodiscard
+function axis.neg(self) end
+
+---The button on the positive side of the axis.
+---@param self VirtualAxis
+---@return VirtualButton
+---@nodiscard
+function axis.pos(self) end
+
+---Set the gamepad axis to listen to.
+---@param self VirtualAxis
+---@param gamepad_axis GamepadAxis?
+function axis.set_axis(self, gamepad_axis) end
+
+---Set the negative and positive buttons.
+---@param self VirtualAxis
+---@param neg VirtualButton
+---@param pos VirtualButton
+function axis.set_buttons(self, neg, pos) end
+
+---If the axis value changed this frame.
+---@param self VirtualAxis
+---@return boolean
+---@nodiscard
+function axis.changed(self) end
+
+---The axis value from `-1.0` to `1.0`.
+---@param self VirtualAxis
+---@return number
+---@nodiscard
+function axis.value(self) end
+
+---@param self VirtualStick
+---@return VirtualAxis
+---@nodiscard
+function stick.x_axis(self) end
+
+---@param self VirtualStick
+---@return VirtualAxis
+---@nodiscard
+function stick.y_axis(self) end
+
+---Set the x and y axes.
+---@param self VirtualStick
+---@param x_axis VirtualAxis
+---@param y_axis VirtualAxis
+function stick.set_axes(self, x_axis, y_axis) end
+
+---If the stick value changed this frame.
+---@param self VirtualStick
+---@return boolean
+---@nodiscard
+function stick.changed(self) end
+
+---@param self VirtualStick
+---@return number
+---@nodiscard
+function stick.x(self) end
+
+---@param self VirtualStick
+---@return number
+---@nodiscard
+function stick.y(self) end
+
+---The stick value, with each axis from `-1.0` to `1.0`.
+---@param self VirtualStick
+---@return Vec2
+---@nodiscard
+function stick.value(self) end
+
+return module
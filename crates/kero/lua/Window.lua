@@ -162,6 +162,16 @@ function Window.resizable() end
 ---@param resizable boolean
 function Window.set_resizable(resizable) end
 
+---Set whether the window is kept above all other windows.
+---@param always_on_top boolean
+function Window.set_always_on_top(always_on_top) end
+
+---Set whether mouse input passes through the window to whatever is behind it. Returns `false`
+---if the platform doesn't support this.
+---@param click_through boolean
+---@return boolean
+function Window.set_click_through(click_through) end
+
 ---Returns `true` if the window is maximized.
 ---@return boolean
 ---@nodiscard
@@ -180,8 +190,24 @@ function Window.minimized() end
 ---@param minimized boolean
 function Window.set_minimized(minimized) end
 
----Set the window's cursor icon.
+---Set the window's cursor icon to one of the platform's standard cursors.
 ---@param cursor CursorIcon
 function Window.set_cursor(cursor) end
 
+---Set the window's cursor to an image, with the hotspot being the pixel that points at things.
+---The platform draws it, so it never lags behind the mouse. Set it once rather than every frame.
+---Returns `false` if the image is too large or the hotspot is outside of it.
+---@param img Image
+---@param hotspot Vec2
+---@return boolean
+function Window.set_cursor_image(img, hotspot) end
+
+---Allow the platform's input method editor (IME) to be used while a text field is focused.
+---@param allowed boolean
+function Window.set_ime_allowed(allowed) end
+
+---Tell the IME where the text cursor is, so its candidate window appears next to it.
+---@param area Rect
+function Window.set_ime_cursor_area(area) end
+
 return Window
\ No newline at end of file
@@ -0,0 +1,356 @@
+use fnv::FnvHashMap;
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+
+/// Result of ticking a [`Behavior`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Status {
+    Success,
+    Failure,
+
+    /// The behavior hasn't finished yet, and will continue from where it left off next tick.
+    Running,
+}
+
+impl From<bool> for Status {
+    #[inline]
+    fn from(value: bool) -> Self {
+        match value {
+            true => Self::Success,
+            false => Self::Failure,
+        }
+    }
+}
+
+/// Per-tree storage that behaviors can use to share values, such as a target or a path.
+#[derive(Default)]
+pub struct Blackboard {
+    values: FnvHashMap<String, Box<dyn Any>>,
+}
+
+impl Debug for Blackboard {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.values.keys()).finish()
+    }
+}
+
+impl Blackboard {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value with the key, if it exists and is of type `T`.
+    #[inline]
+    pub fn get<T: 'static>(&self, key: &str) -> Option<&T> {
+        self.values.get(key).and_then(|v| v.downcast_ref())
+    }
+
+    /// Get the value with the key, if it exists and is of type `T`.
+    #[inline]
+    pub fn get_mut<T: 'static>(&mut self, key: &str) -> Option<&mut T> {
+        self.values.get_mut(key).and_then(|v| v.downcast_mut())
+    }
+
+    /// Set the value with the key, replacing any existing value.
+    #[inline]
+    pub fn set<T: 'static>(&mut self, key: impl Into<String>, value: T) {
+        self.values.insert(key.into(), Box::new(value));
+    }
+
+    /// If a value with the key exists.
+    #[inline]
+    pub fn contains(&self, key: &str) -> bool {
+        self.values.contains_key(key)
+    }
+
+    /// Remove the value with the key, returning true if it existed.
+    #[inline]
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    /// Remove all values.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.values.clear();
+    }
+}
+
+type ActionFn<C> = Box<dyn FnMut(&mut C, &mut Blackboard) -> Status>;
+
+/// A node in a [`BehaviorTree`].
+///
+/// Sequences and selectors remember which child was running, so a running child is resumed on
+/// the next tick rather than starting over from the first child.
+pub struct Behavior<C> {
+    kind: Kind<C>,
+    index: usize,
+    count: u32,
+}
+
+enum Kind<C> {
+    Sequence(Vec<Behavior<C>>),
+    Selector(Vec<Behavior<C>>),
+    Invert(Box<Behavior<C>>),
+    Succeed(Box<Behavior<C>>),
+    Fail(Box<Behavior<C>>),
+    Repeat(Box<Behavior<C>>, Option<u32>),
+    UntilSuccess(Box<Behavior<C>>),
+    UntilFailure(Box<Behavior<C>>),
+    Action(ActionFn<C>),
+}
+
+impl<C> Debug for Behavior<C> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.kind {
+            Kind::Sequence(children) => f.debug_tuple("Sequence").field(children).finish(),
+            Kind::Selector(children) => f.debug_tuple("Selector").field(children).finish(),
+            Kind::Invert(child) => f.debug_tuple("Invert").field(child).finish(),
+            Kind::Succeed(child) => f.debug_tuple("Succeed").field(child).finish(),
+            Kind::Fail(child) => f.debug_tuple("Fail").field(child).finish(),
+            Kind::Repeat(child, n) => f.debug_tuple("Repeat").field(child).field(n).finish(),
+            Kind::UntilSuccess(child) => f.debug_tuple("UntilSuccess").field(child).finish(),
+            Kind::UntilFailure(child) => f.debug_tuple("UntilFailure").field(child).finish(),
+            Kind::Action(_) => f.write_str("Action"),
+        }
+    }
+}
+
+impl<C> Behavior<C> {
+    #[inline]
+    fn new(kind: Kind<C>) -> Self {
+        Self {
+            kind,
+            index: 0,
+            count: 0,
+        }
+    }
+
+    /// Run children in order until one fails or is running. Succeeds if all children succeed.
+    #[inline]
+    pub fn sequence(children: impl IntoIterator<Item = Self>) -> Self {
+        Self::new(Kind::Sequence(children.into_iter().collect()))
+    }
+
+    /// Run children in order until one succeeds or is running. Fails if all children fail.
+    #[inline]
+    pub fn selector(children: impl IntoIterator<Item = Self>) -> Self {
+        Self::new(Kind::Selector(children.into_iter().collect()))
+    }
+
+    /// Swap the child's success and failure.
+    #[inline]
+    pub fn invert(child: Self) -> Self {
+        Self::new(Kind::Invert(Box::new(child)))
+    }
+
+    /// Succeed when the child finishes, even if it failed.
+    #[inline]
+    pub fn succeed(child: Self) -> Self {
+        Self::new(Kind::Succeed(Box::new(child)))
+    }
+
+    /// Fail when the child finishes, even if it succeeded.
+    #[inline]
+    pub fn fail(child: Self) -> Self {
+        Self::new(Kind::Fail(Box::new(child)))
+    }
+
+    /// Run the child again each time it succeeds, succeeding after `count` runs, or never if
+    /// `count` is `None`. Fails if the child fails. The child runs at most once per tick.
+    #[inline]
+    pub fn repeat(child: Self, count: impl Into<Option<u32>>) -> Self {
+        Self::new(Kind::Repeat(Box::new(child), count.into()))
+    }
+
+    /// Run the child again each time it fails, succeeding once it succeeds.
+    #[inline]
+    pub fn until_success(child: Self) -> Self {
+        Self::new(Kind::UntilSuccess(Box::new(child)))
+    }
+
+    /// Run the child again each time it succeeds, succeeding once it fails.
+    #[inline]
+    pub fn until_failure(child: Self) -> Self {
+        Self::new(Kind::UntilFailure(Box::new(child)))
+    }
+
+    /// A leaf task that runs the closure.
+    #[inline]
+    pub fn action(f: impl FnMut(&mut C, &mut Blackboard) -> Status + 'static) -> Self {
+        Self::new(Kind::Action(Box::new(f)))
+    }
+
+    /// A leaf task that succeeds if the closure returns `true` and fails otherwise.
+    #[inline]
+    pub fn condition(mut f: impl FnMut(&C, &Blackboard) -> bool + 'static) -> Self {
+        Self::action(move |ctx, bb| f(ctx, bb).into())
+    }
+
+    /// Clear any running state, so the behavior starts over on its next tick.
+    pub fn reset(&mut self) {
+        self.index = 0;
+        self.count = 0;
+        match &mut self.kind {
+            Kind::Sequence(children) | Kind::Selector(children) => {
+                children.iter_mut().for_each(Self::reset);
+            }
+            Kind::Invert(child)
+            | Kind::Succeed(child)
+            | Kind::Fail(child)
+            | Kind::Repeat(child, _)
+            | Kind::UntilSuccess(child)
+            | Kind::UntilFailure(child) => child.reset(),
+            Kind::Action(_) => {}
+        }
+    }
+
+    /// Run the behavior for one tick.
+    pub fn tick(&mut self, ctx: &mut C, bb: &mut Blackboard) -> Status {
+        let status = match &mut self.kind {
+            Kind::Sequence(children) => {
+                Self::tick_children(children, &mut self.index, Status::Success, ctx, bb)
+            }
+            Kind::Selector(children) => {
+                Self::tick_children(children, &mut self.index, Status::Failure, ctx, bb)
+            }
+            Kind::Invert(child) => match child.tick(ctx, bb) {
+                Status::Success => Status::Failure,
+                Status::Failure => Status::Success,
+                Status::Running => Status::Running,
+            },
+            Kind::Succeed(child) => match child.tick(ctx, bb) {
+                Status::Running => Status::Running,
+                _ => Status::Success,
+            },
+            Kind::Fail(child) => match child.tick(ctx, bb) {
+                Status::Running => Status::Running,
+                _ => Status::Failure,
+            },
+            Kind::Repeat(child, limit) => match child.tick(ctx, bb) {
+                Status::Success => {
+                    child.reset();
+                    self.count += 1;
+                    match *limit {
+                        Some(limit) if self.count >= limit => Status::Success,
+                        _ => Status::Running,
+                    }
+                }
+                status => status,
+            },
+            Kind::UntilSuccess(child) => match child.tick(ctx, bb) {
+                Status::Failure => {
+                    child.reset();
+                    Status::Running
+                }
+                status => status,
+            },
+            Kind::UntilFailure(child) => match child.tick(ctx, bb) {
+                Status::Failure => Status::Success,
+                Status::Success => {
+                    child.reset();
+                    Status::Running
+                }
+                Status::Running => Status::Running,
+            },
+            Kind::Action(f) => f(ctx, bb),
+        };
+        if status != Status::Running {
+            self.reset();
+        }
+        status
+    }
+
+    /// Tick children starting at the running one, continuing while they return `pass`.
+    #[inline]
+    fn tick_children(
+        children: &mut [Self],
+        index: &mut usize,
+        pass: Status,
+        ctx: &mut C,
+        bb: &mut Blackboard,
+    ) -> Status {
+        while let Some(child) = children.get_mut(*index) {
+            let status = child.tick(ctx, bb);
+            if status != pass {
+                return status;
+            }
+            *index += 1;
+        }
+        pass
+    }
+}
+
+#[cfg(feature = "lua")]
+impl<C: mlua::IntoLua + Clone + 'static> Behavior<C> {
+    /// A leaf task that calls the Lua function with the context, which should return a status
+    /// (`"success"`, `"failure"`, `"running"`, or a boolean). If the function errors, the task
+    /// fails and the error is stored in the blackboard under [`LUA_ERROR_KEY`].
+    #[inline]
+    pub fn lua(func: mlua::Function) -> Self {
+        Self::action(move |ctx, bb| match func.call::<Status>((ctx.clone(),)) {
+            Ok(status) => status,
+            Err(err) => {
+                bb.set(LUA_ERROR_KEY, err);
+                Status::Failure
+            }
+        })
+    }
+}
+
+/// Blackboard key that Lua tasks store their last error in.
+#[cfg(feature = "lua")]
+pub const LUA_ERROR_KEY: &str = "lua_error";
+
+/// A tree of behaviors plus its blackboard, usually one per entity.
+///
+/// ```ignore
+/// let mut tree = BehaviorTree::new(Behavior::selector([
+///     Behavior::sequence([
+///         Behavior::condition(|enemy: &Enemy, _| enemy.can_see_player),
+///         Behavior::action(|enemy, _| enemy.chase()),
+///     ]),
+///     Behavior::action(|enemy, _| enemy.patrol()),
+/// ]));
+///
+/// // each frame
+/// tree.tick(&mut enemy);
+/// ```
+#[derive(Debug)]
+pub struct BehaviorTree<C> {
+    root: Behavior<C>,
+
+    /// Storage shared by all of the tree's behaviors.
+    pub blackboard: Blackboard,
+}
+
+impl<C> BehaviorTree<C> {
+    /// Create a new tree with an empty blackboard.
+    #[inline]
+    pub fn new(root: Behavior<C>) -> Self {
+        Self {
+            root,
+            blackboard: Blackboard::new(),
+        }
+    }
+
+    /// The root behavior.
+    #[inline]
+    pub fn root(&self) -> &Behavior<C> {
+        &self.root
+    }
+
+    /// Tick the root behavior. Once it finishes, the next tick starts the tree over.
+    #[inline]
+    pub fn tick(&mut self, ctx: &mut C) -> Status {
+        self.root.tick(ctx, &mut self.blackboard)
+    }
+
+    /// Clear any running state so the tree starts over. The blackboard is not cleared.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.root.reset();
+    }
+}
@@ -0,0 +1,7 @@
+//! Building blocks for game AI.
+
+mod behavior_tree;
+mod steering;
+
+pub use behavior_tree::*;
+pub use steering::*;
@@ -0,0 +1,270 @@
+use crate::math::{Vec2F, vec2};
+use crate::rand::Rand;
+use serde::{Deserialize, Serialize};
+
+/// How many seconds [`Kinematic::arrive`] aims to reach its desired velocity in.
+const ARRIVE_TIME: f32 = 0.1;
+
+/// A simple moving agent for steering behaviors.
+///
+/// Each behavior returns a steering force, which can be blended with others using a
+/// [`SteeringBlend`] and then applied with [`integrate`](Self::integrate).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Kinematic {
+    pub pos: Vec2F,
+    pub vel: Vec2F,
+
+    /// The fastest the agent can move, in units per second.
+    pub max_speed: f32,
+
+    /// The largest steering force that can be applied, in units per second squared.
+    pub max_force: f32,
+}
+
+impl Kinematic {
+    /// Create a new stationary agent.
+    #[inline]
+    pub const fn new(pos: Vec2F, max_speed: f32, max_force: f32) -> Self {
+        Self {
+            pos,
+            vel: Vec2F::ZERO,
+            max_speed,
+            max_force,
+        }
+    }
+
+    /// Apply the steering force for `delta_time` seconds, limiting the force and resulting speed,
+    /// and then move the agent by its velocity.
+    #[inline]
+    pub fn integrate(&mut self, force: Vec2F, delta_time: f32) {
+        let force = limit(force, self.max_force);
+        self.vel = limit(self.vel + force * delta_time, self.max_speed);
+        self.pos += self.vel * delta_time;
+    }
+
+    /// The steering force that changes the velocity towards `desired`.
+    #[inline]
+    pub fn steer_towards(&self, desired: Vec2F) -> Vec2F {
+        desired - self.vel
+    }
+
+    /// Steer towards the target at full speed.
+    #[inline]
+    pub fn seek(&self, target: Vec2F) -> Vec2F {
+        self.steer_towards((target - self.pos).norm_safe() * self.max_speed)
+    }
+
+    /// Steer away from the threat at full speed.
+    #[inline]
+    pub fn flee(&self, threat: Vec2F) -> Vec2F {
+        self.steer_towards((self.pos - threat).norm_safe() * self.max_speed)
+    }
+
+    /// Steer towards the target, slowing down once within `slow_radius` so the agent comes to a
+    /// stop on top of it.
+    #[inline]
+    pub fn arrive(&self, target: Vec2F, slow_radius: f32) -> Vec2F {
+        let to_target = target - self.pos;
+        let dist = to_target.len();
+        if dist <= f32::EPSILON {
+            return self.steer_towards(Vec2F::ZERO) / ARRIVE_TIME;
+        }
+        let speed = match slow_radius > 0.0 {
+            true => self.max_speed * (dist / slow_radius).min(1.0),
+            false => self.max_speed,
+        };
+        // reach the desired velocity quickly so the agent doesn't overshoot and oscillate
+        self.steer_towards(to_target / dist * speed) / ARRIVE_TIME
+    }
+
+    /// Steer towards where the target will be, predicting its position from its velocity.
+    #[inline]
+    pub fn pursuit(&self, target: &Kinematic) -> Vec2F {
+        self.seek(self.predict(target))
+    }
+
+    /// Steer away from where the threat will be, predicting its position from its velocity.
+    #[inline]
+    pub fn evade(&self, threat: &Kinematic) -> Vec2F {
+        self.flee(self.predict(threat))
+    }
+
+    #[inline]
+    fn predict(&self, other: &Kinematic) -> Vec2F {
+        let time = match self.max_speed > 0.0 {
+            true => self.pos.dist(other.pos) / self.max_speed,
+            false => 0.0,
+        };
+        other.pos + other.vel * time
+    }
+
+    /// Wander around randomly, smoothly changing direction.
+    #[inline]
+    pub fn wander(&self, wander: &mut Wander, rand: &mut Rand, delta_time: f32) -> Vec2F {
+        wander.angle += rand.range(-1.0..=1.0) * wander.jitter * delta_time;
+        let heading = match self.vel.sqr_len() > 0.0 {
+            true => self.vel.norm(),
+            false => Vec2F::X_AXIS,
+        };
+        let center = self.pos + heading * wander.distance;
+        let offset = vec2(wander.angle.cos(), wander.angle.sin()) * wander.radius;
+        self.seek(center + offset)
+    }
+
+    /// Steer away from neighbors closer than `radius`, pushing harder the closer they are.
+    /// Neighbors at the exact same position as the agent are ignored.
+    pub fn separation<'a>(
+        &self,
+        neighbors: impl IntoIterator<Item = &'a Kinematic>,
+        radius: f32,
+    ) -> Vec2F {
+        let mut push = Vec2F::ZERO;
+        let mut count = 0;
+        for other in neighbors {
+            let away = self.pos - other.pos;
+            let dist = away.len();
+            if dist > 0.0 && dist < radius {
+                push += away / (dist * dist);
+                count += 1;
+            }
+        }
+        match count {
+            0 => Vec2F::ZERO,
+            _ => self.steer_towards(push.norm_safe() * self.max_speed),
+        }
+    }
+
+    /// Steer to match the average heading of neighbors within `radius`.
+    pub fn alignment<'a>(
+        &self,
+        neighbors: impl IntoIterator<Item = &'a Kinematic>,
+        radius: f32,
+    ) -> Vec2F {
+        let (sum, count) = self.sum_neighbors(neighbors, radius, |other| other.vel);
+        match count {
+            0 => Vec2F::ZERO,
+            _ => self.steer_towards((sum / count as f32).norm_safe() * self.max_speed),
+        }
+    }
+
+    /// Steer towards the average position of neighbors within `radius`.
+    pub fn cohesion<'a>(
+        &self,
+        neighbors: impl IntoIterator<Item = &'a Kinematic>,
+        radius: f32,
+    ) -> Vec2F {
+        let (sum, count) = self.sum_neighbors(neighbors, radius, |other| other.pos);
+        match count {
+            0 => Vec2F::ZERO,
+            _ => self.seek(sum / count as f32),
+        }
+    }
+
+    #[inline]
+    fn sum_neighbors<'a>(
+        &self,
+        neighbors: impl IntoIterator<Item = &'a Kinematic>,
+        radius: f32,
+        value: impl Fn(&Kinematic) -> Vec2F,
+    ) -> (Vec2F, usize) {
+        let radius_sq = radius * radius;
+        let mut sum = Vec2F::ZERO;
+        let mut count = 0;
+        for other in neighbors {
+            let dist_sq = self.pos.sqr_dist(other.pos);
+            if dist_sq > 0.0 && dist_sq < radius_sq {
+                sum += value(other);
+                count += 1;
+            }
+        }
+        (sum, count)
+    }
+}
+
+/// State for [`Kinematic::wander`].
+///
+/// A target point moves randomly around a circle projected in front of the agent, which the agent
+/// then seeks towards.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wander {
+    /// Radius of the wander circle.
+    pub radius: f32,
+
+    /// How far in front of the agent the circle is.
+    pub distance: f32,
+
+    /// How fast the target can move around the circle, in radians per second.
+    pub jitter: f32,
+
+    /// Current angle of the target on the circle, in radians.
+    pub angle: f32,
+}
+
+impl Wander {
+    #[inline]
+    pub const fn new(radius: f32, distance: f32, jitter: f32) -> Self {
+        Self {
+            radius,
+            distance,
+            jitter,
+            angle: 0.0,
+        }
+    }
+}
+
+/// Blends several steering forces together by weight.
+///
+/// ```ignore
+/// let force = SteeringBlend::new()
+///     .add(agent.separation(&flock, 20.0), 1.5)
+///     .add(agent.alignment(&flock, 50.0), 1.0)
+///     .add(agent.cohesion(&flock, 50.0), 1.0)
+///     .force();
+/// agent.integrate(force, dt);
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct SteeringBlend {
+    force: Vec2F,
+}
+
+impl SteeringBlend {
+    #[inline]
+    pub const fn new() -> Self {
+        Self { force: Vec2F::ZERO }
+    }
+
+    /// Add a steering force, scaled by the weight.
+    #[inline]
+    pub fn add(mut self, force: Vec2F, weight: f32) -> Self {
+        self.force += force * weight;
+        self
+    }
+
+    /// Add a steering force only while there is force left under `max_force`, so that
+    /// higher-priority behaviors added first aren't drowned out by later ones.
+    #[inline]
+    pub fn add_prioritized(mut self, force: Vec2F, weight: f32, max_force: f32) -> Self {
+        let remaining = max_force - self.force.len();
+        if remaining > 0.0 {
+            self.force += limit(force * weight, remaining);
+        }
+        self
+    }
+
+    /// The blended steering force.
+    #[inline]
+    pub fn force(self) -> Vec2F {
+        self.force
+    }
+}
+
+/// Limit the length of the vector.
+#[inline]
+fn limit(v: Vec2F, max: f32) -> Vec2F {
+    let sqr_len = v.sqr_len();
+    if sqr_len > max * max {
+        v * (max / sqr_len.sqrt())
+    } else {
+        v
+    }
+}
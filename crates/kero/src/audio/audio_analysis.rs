@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::f32::consts::TAU;
+
+/// Levels of the mixed audio, measured once per frame by the
+/// [`AudioContext`](super::AudioContext), so visuals can react to what is playing.
+///
+/// ```ignore
+/// let levels = ctx.audio.analysis();
+/// let scale = 1.0 + levels.bands[0] * 0.5;
+/// if levels.beat {
+///     flash_lights();
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AudioAnalysis {
+    /// Root mean square level of the most recent samples, from `0` to about `1`.
+    pub rms: f32,
+
+    /// Loudest absolute sample among the most recent samples.
+    pub peak: f32,
+
+    /// Amplitude of the loudest frequency in each band, from the lowest band to the highest,
+    /// where a full-volume tone is about `1`. The bands are spaced logarithmically from 20 Hz
+    /// up to 16 kHz (or half the sample rate, if that is lower).
+    pub bands: Vec<f32>,
+
+    /// If a beat started this frame.
+    pub beat: bool,
+
+    /// How many beats have been detected since the game started.
+    pub beat_count: u64,
+
+    /// Estimated tempo in beats per minute, or `0` until a few beats have been detected.
+    pub tempo: f32,
+}
+
+/// Ring buffer of the most recent mixed samples, downmixed to mono.
+pub(crate) struct AudioTap {
+    samples: Vec<f32>,
+    write: usize,
+}
+
+impl AudioTap {
+    pub const SIZE: usize = 4096;
+
+    pub fn new() -> Self {
+        Self {
+            samples: vec![0.0; Self::SIZE],
+            write: 0,
+        }
+    }
+
+    /// Record interleaved stereo samples.
+    pub fn push_stereo(&mut self, samples: &[f32]) {
+        for frame in samples.chunks_exact(2) {
+            self.samples[self.write] = (frame[0] + frame[1]) * 0.5;
+            self.write = (self.write + 1) % Self::SIZE;
+        }
+    }
+
+    /// Copy the most recent samples into `out`, oldest first.
+    pub fn recent(&self, out: &mut [f32]) {
+        let len = out.len().min(Self::SIZE);
+        let start = (self.write + Self::SIZE - len) % Self::SIZE;
+        for (i, sample) in out[..len].iter_mut().enumerate() {
+            *sample = self.samples[(start + i) % Self::SIZE];
+        }
+    }
+}
+
+/// Measures levels, frequency bands, and beats from the tap each frame.
+pub(crate) struct AudioAnalyzer {
+    pub sensitivity: f32,
+    pub band_count: usize,
+    window: Vec<f32>,
+    samples: Vec<f32>,
+    re: Vec<f32>,
+    im: Vec<f32>,
+    bass_avg: f32,
+    since_beat: f32,
+    intervals: VecDeque<f32>,
+    result: AudioAnalysis,
+}
+
+impl AudioAnalyzer {
+    /// Number of samples analyzed each frame, which must be a power of two.
+    pub const FFT_SIZE: usize = 1024;
+
+    /// Shortest time between two beats, so one loud hit isn't detected as several.
+    const MIN_BEAT_INTERVAL: f32 = 0.25;
+
+    /// How many beat intervals are averaged to estimate the tempo.
+    const TEMPO_BEATS: usize = 8;
+
+    pub fn new() -> Self {
+        let n = Self::FFT_SIZE;
+        Self {
+            sensitivity: 1.5,
+            band_count: 8,
+            // hann window, so the edges of the analyzed samples don't smear across bands
+            window: (0..n)
+                .map(|i| 0.5 - 0.5 * (TAU * i as f32 / (n - 1) as f32).cos())
+                .collect(),
+            samples: vec![0.0; n],
+            re: vec![0.0; n],
+            im: vec![0.0; n],
+            bass_avg: 0.0,
+            since_beat: f32::INFINITY,
+            intervals: VecDeque::new(),
+            result: AudioAnalysis::default(),
+        }
+    }
+
+    #[inline]
+    pub fn result(&self) -> &AudioAnalysis {
+        &self.result
+    }
+
+    /// Analyze the most recent samples of the tap.
+    pub fn update(&mut self, tap: &AudioTap, sample_rate: u32, dt: f32) {
+        let n = Self::FFT_SIZE;
+        tap.recent(&mut self.samples);
+
+        // levels
+        let sum_sq: f32 = self.samples.iter().map(|s| s * s).sum();
+        self.result.rms = (sum_sq / n as f32).sqrt();
+        self.result.peak = self.samples.iter().fold(0.0f32, |p, s| p.max(s.abs()));
+
+        // spectrum, scaled so a full-scale sine has an amplitude of about 1
+        for i in 0..n {
+            self.re[i] = self.samples[i] * self.window[i];
+            self.im[i] = 0.0;
+        }
+        fft(&mut self.re, &mut self.im);
+        let scale = 4.0 / n as f32;
+        let bins = n / 2;
+        let mags: Vec<f32> = (0..bins)
+            .map(|k| (self.re[k] * self.re[k] + self.im[k] * self.im[k]).sqrt() * scale)
+            .collect();
+        let bin_hz = sample_rate as f32 / n as f32;
+
+        // logarithmically spaced bands, so each octave gets a similar share
+        let (lo, hi) = (20.0f32, (sample_rate as f32 * 0.5).min(16000.0));
+        let count = self.band_count.max(1);
+        self.result.bands.clear();
+        self.result.bands.extend((0..count).map(|b| {
+            let edge = |i: usize| lo * (hi / lo).powf(i as f32 / count as f32);
+            let start = ((edge(b) / bin_hz).floor() as usize).clamp(1, bins - 1);
+            let end = ((edge(b + 1) / bin_hz).ceil() as usize).clamp(start + 1, bins);
+            mags[start..end].iter().fold(0.0f32, |a, &m| a.max(m))
+        }));
+
+        // beats are sudden jumps in bass energy above its recent average
+        let bass_end = ((150.0 / bin_hz).ceil() as usize).clamp(2, bins);
+        let bass = mags[1..bass_end].iter().map(|m| m * m).sum::<f32>() / (bass_end - 1) as f32;
+        self.since_beat += dt;
+        self.result.beat = bass > self.bass_avg * self.sensitivity
+            && bass > 1e-5
+            && self.since_beat >= Self::MIN_BEAT_INTERVAL;
+        if self.result.beat {
+            self.result.beat_count += 1;
+            if self.since_beat < 2.0 {
+                self.intervals.push_back(self.since_beat);
+                if self.intervals.len() > Self::TEMPO_BEATS {
+                    self.intervals.pop_front();
+                }
+            }
+            self.since_beat = 0.0;
+        }
+        if self.intervals.len() >= 3 {
+            let avg = self.intervals.iter().sum::<f32>() / self.intervals.len() as f32;
+            self.result.tempo = 60.0 / avg;
+        }
+
+        // average the bass energy over about a second
+        let k = 1.0 - (-dt.max(0.0)).exp();
+        self.bass_avg += (bass - self.bass_avg) * k;
+    }
+}
+
+/// In-place radix-2 fast fourier transform. The length must be a power of two.
+fn fft(re: &mut [f32], im: &mut [f32]) {
+    let n = re.len();
+    debug_assert!(n.is_power_of_two());
+
+    // bit-reversal permutation
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            re.swap(i, j);
+            im.swap(i, j);
+        }
+    }
+
+    // butterflies
+    let mut len = 2;
+    while len <= n {
+        let angle = -TAU / len as f32;
+        let (wr, wi) = (angle.cos(), angle.sin());
+        for start in (0..n).step_by(len) {
+            let (mut cr, mut ci) = (1.0f32, 0.0f32);
+            for k in 0..len / 2 {
+                let (a, b) = (start + k, start + k + len / 2);
+                let tr = re[b] * cr - im[b] * ci;
+                let ti = re[b] * ci + im[b] * cr;
+                re[b] = re[a] - tr;
+                im[b] = im[a] - ti;
+                re[a] += tr;
+                im[a] += ti;
+                (cr, ci) = (cr * wr - ci * wi, cr * wi + ci * wr);
+            }
+        }
+        len <<= 1;
+    }
+}
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use strum::{EnumCount, FromRepr, VariantArray};
+
+/// A mixer channel that sounds play on, each with its own volume.
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    FromRepr,
+    EnumCount,
+    VariantArray,
+)]
+pub enum AudioChannel {
+    #[default]
+    Sfx = 0,
+    Music,
+    Ambience,
+    Voice,
+    Ui,
+}
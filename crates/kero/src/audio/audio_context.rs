@@ -0,0 +1,553 @@
+use crate::audio::playback::AtomicF32;
+use crate::audio::{AudioAnalysis, AudioAnalyzer, AudioChannel, AudioTap, Playback, Sound};
+use crate::math::{Vec2F, vec2};
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use strum::EnumCount;
+
+/// Handle to the game's audio mixer.
+///
+/// Every sound that is played is mixed together on its [`AudioChannel`], and each channel
+/// has its own volume on top of the master volume. Sounds are mixed by the
+/// [`AudioOutput`](super::AudioOutput) the game was built with, which is the system's default
+/// device unless the `cpal` feature is turned off. Without one, sounds are still mixed silently
+/// in step with the game, so one-shots still finish on time and can still be analyzed.
+///
+/// Sounds played with [`play_at`](Self::play_at) are positional: they get quieter the further
+/// they are from the [listener](Self::set_listener), and pan towards the side they are on.
+///
+/// The mixed audio is also [analyzed](Self::analysis) every frame, measuring its level,
+/// frequency bands and beats, so visuals can pulse with the music.
+///
+/// This handle can be cloned and passed around freely, even to other threads.
+///
+/// Obtained from [`Context`](crate::core::Context).
+#[derive(Clone)]
+pub struct AudioContext(Arc<AudioInner>);
+
+struct AudioInner {
+    sample_rate: u32,
+    has_output: AtomicBool,
+    master_volume: AtomicF32,
+    volumes: [AtomicF32; AudioChannel::COUNT],
+    listener_x: AtomicF32,
+    listener_y: AtomicF32,
+    min_distance: AtomicF32,
+    max_distance: AtomicF32,
+    voices: Mutex<Vec<Playback>>,
+    music: Mutex<Vec<Playback>>,
+    tap: Mutex<AudioTap>,
+    analyzer: Mutex<AudioAnalyzer>,
+    silent_mix: Mutex<Vec<f32>>,
+}
+
+impl Debug for AudioContext {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AudioContext").finish_non_exhaustive()
+    }
+}
+
+impl AudioContext {
+    /// Default distance within which positional sounds play at full volume.
+    pub const DEFAULT_MIN_DISTANCE: f32 = 64.0;
+
+    /// Default distance at which positional sounds become silent.
+    pub const DEFAULT_MAX_DISTANCE: f32 = 640.0;
+
+    /// Create a mixer that mixes at the sample rate.
+    pub fn new(sample_rate: u32) -> Self {
+        Self(Arc::new(AudioInner {
+            sample_rate: sample_rate.max(1),
+            has_output: AtomicBool::new(false),
+            master_volume: AtomicF32::new(1.0),
+            volumes: std::array::from_fn(|_| AtomicF32::new(1.0)),
+            listener_x: AtomicF32::new(0.0),
+            listener_y: AtomicF32::new(0.0),
+            min_distance: AtomicF32::new(Self::DEFAULT_MIN_DISTANCE),
+            max_distance: AtomicF32::new(Self::DEFAULT_MAX_DISTANCE),
+            voices: Mutex::new(Vec::new()),
+            music: Mutex::new(Vec::new()),
+            tap: Mutex::new(AudioTap::new()),
+            analyzer: Mutex::new(AudioAnalyzer::new()),
+            silent_mix: Mutex::new(Vec::new()),
+        }))
+    }
+
+    #[inline]
+    fn voices(&self) -> MutexGuard<'_, Vec<Playback>> {
+        self.0.voices.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[inline]
+    fn music_lock(&self) -> MutexGuard<'_, Vec<Playback>> {
+        self.0.music.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    #[inline]
+    fn analyzer(&self) -> MutexGuard<'_, AudioAnalyzer> {
+        self.0
+            .analyzer
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// The sample rate audio is mixed at, in hertz.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    /// The volume all channels are multiplied by.
+    #[inline]
+    pub fn master_volume(&self) -> f32 {
+        self.0.master_volume.get()
+    }
+
+    /// Set the volume all channels are multiplied by.
+    #[inline]
+    pub fn set_master_volume(&self, volume: f32) {
+        self.0.master_volume.set(volume.max(0.0));
+    }
+
+    /// Volume of the channel.
+    #[inline]
+    pub fn volume(&self, channel: AudioChannel) -> f32 {
+        self.0.volumes[channel as usize].get()
+    }
+
+    /// Set the volume of the channel.
+    #[inline]
+    pub fn set_volume(&self, channel: AudioChannel, volume: f32) {
+        self.0.volumes[channel as usize].set(volume.max(0.0));
+    }
+
+    /// Where positional sounds are heard from, usually the center of the camera.
+    #[inline]
+    pub fn listener(&self) -> Vec2F {
+        vec2(self.0.listener_x.get(), self.0.listener_y.get())
+    }
+
+    /// Move where positional sounds are heard from. Call this every frame with the center of
+    /// the camera so sounds pan and fade as it moves.
+    #[inline]
+    pub fn set_listener(&self, pos: impl Into<Vec2F>) {
+        let pos = pos.into();
+        self.0.listener_x.set(pos.x);
+        self.0.listener_y.set(pos.y);
+    }
+
+    /// Distance from the listener within which positional sounds play at full volume.
+    #[inline]
+    pub fn min_distance(&self) -> f32 {
+        self.0.min_distance.get()
+    }
+
+    /// Set the distance from the listener within which positional sounds play at full volume.
+    #[inline]
+    pub fn set_min_distance(&self, distance: f32) {
+        self.0.min_distance.set(distance.max(0.0));
+    }
+
+    /// Distance from the listener at which positional sounds become silent. This is also the
+    /// horizontal distance at which they are panned fully to one side.
+    #[inline]
+    pub fn max_distance(&self) -> f32 {
+        self.0.max_distance.get()
+    }
+
+    /// Set the distance from the listener at which positional sounds become silent.
+    #[inline]
+    pub fn set_max_distance(&self, distance: f32) {
+        self.0.max_distance.set(distance.max(0.0));
+    }
+
+    /// Play a sound once on the [`Sfx`](AudioChannel::Sfx) channel.
+    #[inline]
+    pub fn play(&self, sound: &Sound) -> Playback {
+        self.play_ext(sound, AudioChannel::Sfx, 1.0, 0.0, false)
+    }
+
+    /// Play a sound on a channel with a volume, a stereo pan from `-1.0` (left) to `1.0`
+    /// (right), and optionally looping.
+    pub fn play_ext(
+        &self,
+        sound: &Sound,
+        channel: AudioChannel,
+        volume: f32,
+        pan: f32,
+        looping: bool,
+    ) -> Playback {
+        let playback = Playback::new(sound.clone(), channel, volume, pan, looping, None);
+        self.start(playback.clone());
+        playback
+    }
+
+    /// Start mixing a playback.
+    #[inline]
+    pub(crate) fn start(&self, playback: Playback) {
+        self.voices().push(playback);
+    }
+
+    /// Play a sound once on the [`Sfx`](AudioChannel::Sfx) channel, emitted from a position
+    /// in the world.
+    #[inline]
+    pub fn play_at(&self, sound: &Sound, pos: impl Into<Vec2F>) -> Playback {
+        self.play_at_ext(sound, AudioChannel::Sfx, 1.0, pos, false)
+    }
+
+    /// Play a sound on a channel with a volume, emitted from a position in the world, and
+    /// optionally looping.
+    pub fn play_at_ext(
+        &self,
+        sound: &Sound,
+        channel: AudioChannel,
+        volume: f32,
+        pos: impl Into<Vec2F>,
+        looping: bool,
+    ) -> Playback {
+        let emitter = Some(pos.into());
+        let playback = Playback::new(sound.clone(), channel, volume, 0.0, looping, emitter);
+        self.start(playback.clone());
+        playback
+    }
+
+    /// The volume and pan a positional sound at `pos` is heard with, from the listener.
+    pub fn spatialize(&self, pos: impl Into<Vec2F>) -> (f32, f32) {
+        let offset = pos.into() - self.listener();
+        let min = self.min_distance();
+        let max = self.max_distance().max(min);
+        let dist = offset.len();
+        let volume = match dist {
+            d if d <= min => 1.0,
+            d if d >= max => 0.0,
+            d => 1.0 - (d - min) / (max - min),
+        };
+        let pan = match max > 0.0 {
+            true => (offset.x / max).clamp(-1.0, 1.0),
+            false => 0.0,
+        };
+        (volume, pan)
+    }
+
+    /// Loop a sound on the [`Music`](AudioChannel::Music) channel, stopping any music that
+    /// was already playing.
+    pub fn play_music(&self, sound: &Sound, volume: f32) -> Playback {
+        let playback = self.play_ext(sound, AudioChannel::Music, volume, 0.0, true);
+        let mut music = self.music_lock();
+        for prev in music.drain(..) {
+            prev.stop();
+        }
+        music.push(playback.clone());
+        playback
+    }
+
+    /// Loop a sound on the [`Music`](AudioChannel::Music) channel, fading it in over
+    /// `duration` seconds while any music that was already playing fades out.
+    pub fn crossfade_music(&self, sound: &Sound, volume: f32, duration: f32) -> Playback {
+        let playback = self.play_ext(sound, AudioChannel::Music, 0.0, 0.0, true);
+        playback.fade_to(volume, duration);
+        let mut music = self.music_lock();
+        for prev in music.drain(..) {
+            prev.fade_out(duration);
+        }
+        music.push(playback.clone());
+        playback
+    }
+
+    /// Loop several layers (stems) of a track together on the [`Music`](AudioChannel::Music)
+    /// channel, stopping any music that was already playing. Each stem gets its own
+    /// playback, so their volumes can be mixed while they play, such as bringing in drums
+    /// during combat. The stems should have the same length and sample rate.
+    pub fn play_music_stems(&self, stems: &[Sound], volume: f32) -> Vec<Playback> {
+        let playbacks: Vec<Playback> = stems
+            .iter()
+            .map(|sound| Playback::new(sound.clone(), AudioChannel::Music, volume, 0.0, true, None))
+            .collect();
+
+        // start them in the same mix, so they stay in sync
+        self.voices().extend(playbacks.iter().cloned());
+        let mut music = self.music_lock();
+        for prev in music.drain(..) {
+            prev.stop();
+        }
+        music.extend(playbacks.iter().cloned());
+        playbacks
+    }
+
+    /// The music that is playing. If the music has several stems, this is the first one.
+    #[inline]
+    pub fn music(&self) -> Option<Playback> {
+        self.music_lock().iter().find(|m| m.is_playing()).cloned()
+    }
+
+    /// Every stem of the music that is playing.
+    #[inline]
+    pub fn music_stems(&self) -> Vec<Playback> {
+        self.music_lock()
+            .iter()
+            .filter(|m| m.is_playing())
+            .cloned()
+            .collect()
+    }
+
+    /// Stop the music that is playing.
+    #[inline]
+    pub fn stop_music(&self) {
+        for music in self.music_lock().drain(..) {
+            music.stop();
+        }
+    }
+
+    /// Fade the music that is playing to silence over `duration` seconds, then stop it.
+    pub fn fade_out_music(&self, duration: f32) {
+        for music in self.music_lock().drain(..) {
+            music.fade_out(duration);
+        }
+    }
+
+    /// Stop every sound on the channel.
+    pub fn stop_channel(&self, channel: AudioChannel) {
+        for voice in self.voices().iter().filter(|v| v.channel() == channel) {
+            voice.stop();
+        }
+    }
+
+    /// Stop every sound.
+    pub fn stop_all(&self) {
+        for voice in self.voices().iter() {
+            voice.stop();
+        }
+        self.music_lock().clear();
+    }
+
+    /// How many sounds are playing.
+    #[inline]
+    pub fn playing_count(&self) -> usize {
+        self.voices().iter().filter(|v| v.is_playing()).count()
+    }
+
+    /// Mix every playing sound into `out`, which holds interleaved stereo samples at the
+    /// [`sample_rate`](Self::sample_rate). Called by the [`AudioOutput`](super::AudioOutput)
+    /// whenever it needs more samples.
+    pub fn mix(&self, out: &mut [f32]) {
+        out.fill(0.0);
+        let master = self.master_volume();
+        let frames = out.len() / 2;
+        self.voices().retain(|voice| {
+            let mut gain = master * self.volume(voice.channel());
+            let mut pan = voice.pan();
+            if let Some(pos) = voice.emitter_pos() {
+                let (volume, spatial_pan) = self.spatialize(pos);
+                gain *= volume;
+                pan = (pan + spatial_pan).clamp(-1.0, 1.0);
+            }
+            mix_voice(voice, self.0.sample_rate, gain, pan, out, frames)
+        });
+        self.0
+            .tap
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .push_stereo(out);
+    }
+
+    /// Levels, frequency bands and beats of the mixed audio, measured this frame.
+    ///
+    /// Sounds are mixed even without an [`AudioOutput`](super::AudioOutput), so this measures
+    /// what would be heard either way.
+    #[inline]
+    pub fn analysis(&self) -> AudioAnalysis {
+        self.analyzer().result().clone()
+    }
+
+    /// Root mean square level of the mixed audio this frame.
+    #[inline]
+    pub fn rms(&self) -> f32 {
+        self.analyzer().result().rms
+    }
+
+    /// Loudest sample of the mixed audio this frame.
+    #[inline]
+    pub fn peak(&self) -> f32 {
+        self.analyzer().result().peak
+    }
+
+    /// Amplitude of each frequency band of the mixed audio this frame, lowest first.
+    #[inline]
+    pub fn bands(&self) -> Vec<f32> {
+        self.analyzer().result().bands.clone()
+    }
+
+    /// If a beat started this frame.
+    #[inline]
+    pub fn beat(&self) -> bool {
+        self.analyzer().result().beat
+    }
+
+    /// Estimated tempo of the music in beats per minute, or `0` if it isn't known yet.
+    #[inline]
+    pub fn tempo(&self) -> f32 {
+        self.analyzer().result().tempo
+    }
+
+    /// How many frequency bands the audio is split into. Defaults to `8`.
+    #[inline]
+    pub fn band_count(&self) -> usize {
+        self.analyzer().band_count
+    }
+
+    /// Set how many frequency bands the audio is split into.
+    #[inline]
+    pub fn set_band_count(&self, count: usize) {
+        self.analyzer().band_count = count.clamp(1, AudioAnalyzer::FFT_SIZE / 2);
+    }
+
+    /// How far the bass must rise above its recent average to count as a beat. Defaults to
+    /// `1.5`, and lower values detect more beats.
+    #[inline]
+    pub fn beat_sensitivity(&self) -> f32 {
+        self.analyzer().sensitivity
+    }
+
+    /// Set how far the bass must rise above its recent average to count as a beat.
+    #[inline]
+    pub fn set_beat_sensitivity(&self, sensitivity: f32) {
+        self.analyzer().sensitivity = sensitivity.max(1.0);
+    }
+
+    /// Copy the most recently mixed samples into `out`, downmixed to mono and oldest first,
+    /// such as for drawing a waveform. At most 4096 samples are kept.
+    pub fn recent_samples(&self, out: &mut [f32]) {
+        self.0
+            .tap
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .recent(out);
+    }
+
+    /// Called once per frame. Without an output, the sounds for this frame are mixed here and
+    /// thrown away, so they advance in step with the game and still reach the analysis.
+    pub(crate) fn update(&self, dt: f32) {
+        if !self.0.has_output.load(Ordering::Relaxed) {
+            let frames = (dt.max(0.0) * self.0.sample_rate as f32).round() as usize;
+            let mut buffer = self
+                .0
+                .silent_mix
+                .lock()
+                .unwrap_or_else(|err| err.into_inner());
+            buffer.resize(frames * 2, 0.0);
+            self.mix(&mut buffer);
+        }
+        let tap = self.0.tap.lock().unwrap_or_else(|err| err.into_inner());
+        self.analyzer().update(&tap, self.0.sample_rate, dt);
+    }
+
+    /// Set whether an output is mixing this context. Without one, sounds are advanced silently
+    /// every frame instead.
+    pub(crate) fn set_has_output(&self, has_output: bool) {
+        self.0.has_output.store(has_output, Ordering::Relaxed);
+    }
+}
+
+/// Mix `frames` frames of a voice into `out`. Returns false once the voice has finished.
+fn mix_voice(
+    voice: &Playback,
+    sample_rate: u32,
+    gain: f32,
+    pan: f32,
+    out: &mut [f32],
+    frames: usize,
+) -> bool {
+    let state = &voice.0;
+    if state.stopped.load(Ordering::Relaxed) {
+        return false;
+    }
+    if state.paused.load(Ordering::Relaxed) {
+        return true;
+    }
+
+    let sound = &state.sound;
+    let len = sound.frames() as f64;
+    if len == 0.0 {
+        voice.stop();
+        return false;
+    }
+    let step = sound.sample_rate() as f64 / sample_rate as f64 * state.speed.get() as f64;
+    let looping = state.looping.load(Ordering::Relaxed);
+    let mut pos = f64::from_bits(state.position.load(Ordering::Relaxed));
+
+    // loop within the region, after playing whatever comes before it once
+    let region = match voice.loop_region() {
+        r if r.is_empty() => 0..sound.frames(),
+        r => r,
+    };
+    let (loop_start, loop_end) = (region.start as f64, region.end as f64);
+    let end = if looping { loop_end } else { len };
+    let wrap = |pos: f64| loop_start + (pos - loop_start) % (loop_end - loop_start);
+
+    // fade the volume a little every frame, so it changes smoothly
+    let mut volume = state.volume.get();
+    let fade_speed = state.fade_speed.get();
+    let fade_target = state.fade_target.get();
+    let fade_step = fade_speed / sample_rate as f32;
+
+    // balance panning, so centered sounds play at full volume in both ears
+    let (left, right) = (gain * (1.0 - pan).min(1.0), gain * (1.0 + pan).min(1.0));
+    for frame in out.chunks_exact_mut(2).take(frames) {
+        if pos >= end {
+            if !looping {
+                break;
+            }
+            pos = wrap(pos);
+        }
+
+        // linearly resample between neighboring frames
+        let i = pos as usize;
+        let t = (pos - i as f64) as f32;
+        let next = match i + 1 < end as usize {
+            true => i + 1,
+            false if looping => region.start,
+            false => i,
+        };
+        if fade_speed > 0.0 {
+            volume = approach(volume, fade_target, fade_step);
+        }
+        let (l0, r0) = sound.frame(i);
+        let (l1, r1) = sound.frame(next);
+        frame[0] += (l0 + (l1 - l0) * t) * left * volume;
+        frame[1] += (r0 + (r1 - r0) * t) * right * volume;
+        pos += step;
+    }
+
+    if fade_speed > 0.0 {
+        state.volume.set(volume);
+        if volume == fade_target {
+            state.fade_speed.set(0.0);
+            if state.fade_stop.load(Ordering::Relaxed) {
+                voice.stop();
+                return false;
+            }
+        }
+    }
+
+    if pos >= end {
+        if looping {
+            pos = wrap(pos);
+        } else {
+            voice.stop();
+            return false;
+        }
+    }
+    state.position.store(pos.to_bits(), Ordering::Relaxed);
+    true
+}
+
+/// Move `value` towards `target` by at most `step`.
+#[inline]
+fn approach(value: f32, target: f32, step: f32) -> f32 {
+    match value < target {
+        true => (value + step).min(target),
+        false => (value - step).max(target),
+    }
+}
@@ -0,0 +1,21 @@
+/// An error loading a [`Sound`](super::Sound) or opening an [`AudioOutput`](super::AudioOutput).
+#[derive(Debug, thiserror::Error)]
+pub enum AudioError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("invalid WAV: {0}")]
+    InvalidWav(&'static str),
+
+    #[error("unsupported WAV format: {0}")]
+    UnsupportedWav(String),
+
+    #[error("invalid OGG Vorbis: {0}")]
+    InvalidVorbis(String),
+
+    #[error("unsupported audio format: {0}")]
+    UnsupportedFormat(String),
+
+    #[error("audio output failed: {0}")]
+    Output(String),
+}
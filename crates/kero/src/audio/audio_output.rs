@@ -0,0 +1,14 @@
+use crate::audio::AudioContext;
+
+/// A device that plays the game's mixed audio.
+///
+/// Implementations open the device and call [`AudioContext::mix`] from its audio callback
+/// whenever it needs more samples. Set the output with
+/// [`GameBuilder::with_audio_output`](crate::core::GameBuilder::with_audio_output).
+pub trait AudioOutput: Send + 'static {
+    /// The sample rate the device plays at, in hertz.
+    fn sample_rate(&self) -> u32;
+
+    /// Start playing audio from the context.
+    fn start(self: Box<Self>, audio: AudioContext);
+}
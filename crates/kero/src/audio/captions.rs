@@ -0,0 +1,306 @@
+use crate::audio::{AudioContext, Playback, Sound};
+use crate::color::Rgba8;
+use crate::gfx::{Draw, DrawError, Font, TextAlign, TextLayout};
+use crate::math::{RectF, vec2};
+use crate::misc::Tweaks;
+use fnv::FnvHashMap;
+use serde::{Deserialize, Serialize};
+
+/// A line of text shown while a sound plays, such as dialogue or a description of a sound
+/// effect like `[door creaks]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CaptionLine {
+    /// Seconds into the sound when the line appears.
+    pub start: f32,
+
+    /// Seconds into the sound when the line disappears, or `None` to show it until the sound
+    /// stops.
+    pub end: Option<f32>,
+
+    /// The text to show.
+    pub text: String,
+
+    /// Who is speaking the line. Lines without a speaker are treated as sound effects.
+    pub speaker: Option<String>,
+}
+
+impl CaptionLine {
+    /// Create a line shown for as long as the sound plays.
+    #[inline]
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            start: 0.0,
+            end: None,
+            text: text.into(),
+            speaker: None,
+        }
+    }
+
+    /// Return the line shown only between `start` and `end` seconds into the sound.
+    #[inline]
+    pub fn with_time(mut self, start: f32, end: impl Into<Option<f32>>) -> Self {
+        self.start = start;
+        self.end = end.into();
+        self
+    }
+
+    /// Return the line spoken by the speaker.
+    #[inline]
+    pub fn with_speaker(mut self, speaker: impl Into<String>) -> Self {
+        self.speaker = Some(speaker.into());
+        self
+    }
+
+    /// If the line is visible `time` seconds into its sound.
+    #[inline]
+    pub fn is_visible_at(&self, time: f32) -> bool {
+        time >= self.start && self.end.is_none_or(|end| time < end)
+    }
+}
+
+/// Where captions are drawn within their area.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum CaptionPosition {
+    /// Along the bottom, with the newest line at the bottom.
+    #[default]
+    Bottom,
+
+    /// Along the top, with the newest line at the top.
+    Top,
+}
+
+/// Player-facing options for how [`Captions`] are shown.
+///
+/// Settings can be saved with the rest of a game's options, or registered as tweakable
+/// variables with [`sync_tweaks`](Self::sync_tweaks).
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CaptionSettings {
+    /// If captions are shown at all.
+    pub enabled: bool,
+
+    /// If captions for sound effects are shown, not just speech.
+    pub sound_effects: bool,
+
+    /// If lines are prefixed with the name of their speaker.
+    pub speaker_names: bool,
+
+    /// If lines are drawn in their speaker's color, instead of white.
+    pub speaker_colors: bool,
+
+    /// How much the text is scaled from the font's size.
+    pub scale: f32,
+
+    /// Opacity of the box behind each line, from `0` to `1`.
+    pub background: f32,
+
+    /// Where captions are drawn.
+    pub position: CaptionPosition,
+
+    /// Space between the captions and the edge of their area, in pixels.
+    pub margin: f32,
+
+    /// How wide lines can be before they wrap, as a fraction of the area's width.
+    pub max_width: f32,
+}
+
+impl Default for CaptionSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            sound_effects: true,
+            speaker_names: true,
+            speaker_colors: true,
+            scale: 1.0,
+            background: 0.6,
+            position: CaptionPosition::Bottom,
+            margin: 24.0,
+            max_width: 0.8,
+        }
+    }
+}
+
+impl CaptionSettings {
+    /// Register the toggles as tweakable variables named `captions.*`, using the current
+    /// settings as their defaults, then update the settings with their values. Call this every
+    /// frame to let the tweak overlay change captions, and save them with the tweaks file.
+    pub fn sync_tweaks(&mut self, tweaks: &mut Tweaks) {
+        self.enabled = tweaks.get("captions.enabled", self.enabled);
+        self.sound_effects = tweaks.get("captions.sound_effects", self.sound_effects);
+        self.speaker_names = tweaks.get("captions.speaker_names", self.speaker_names);
+        self.speaker_colors = tweaks.get("captions.speaker_colors", self.speaker_colors);
+        self.scale = tweaks.get("captions.scale", self.scale);
+        self.background = tweaks.get("captions.background", self.background);
+    }
+}
+
+/// Subtitles and captions shown while sounds play.
+///
+/// Lines are registered for a sound, then shown whenever a [tracked](Self::track) playback of
+/// that sound reaches them. Music started on the [`AudioContext`] is tracked automatically.
+///
+/// ```ignore
+/// captions.register(&greeting, [
+///     CaptionLine::new("Well met, traveler.").with_time(0.0, 1.8).with_speaker("Guard"),
+///     CaptionLine::new("[gate rattles]").with_time(1.8, None),
+/// ]);
+/// captions.set_speaker_color("Guard", Rgba8::new(255, 220, 120, 255));
+/// captions.track(ctx.audio.play(&greeting));
+///
+/// // every frame
+/// captions.update(&ctx.audio);
+/// captions.draw(draw, &font, screen_rect)?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Captions {
+    /// How captions are shown.
+    pub settings: CaptionSettings,
+
+    cues: Vec<(Sound, Vec<CaptionLine>)>,
+    speakers: FnvHashMap<String, Rgba8>,
+    tracked: Vec<Playback>,
+}
+
+impl Captions {
+    /// Create captions with the default settings.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the captions with different settings.
+    #[inline]
+    pub fn with_settings(mut self, settings: CaptionSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set the lines shown while the sound plays, replacing any it already had.
+    pub fn register(&mut self, sound: &Sound, lines: impl IntoIterator<Item = CaptionLine>) {
+        let lines = lines.into_iter().collect();
+        match self.cues.iter_mut().find(|(s, _)| s == sound) {
+            Some((_, cue)) => *cue = lines,
+            None => self.cues.push((sound.clone(), lines)),
+        }
+    }
+
+    /// Remove the lines registered for the sound.
+    #[inline]
+    pub fn unregister(&mut self, sound: &Sound) {
+        self.cues.retain(|(s, _)| s != sound);
+    }
+
+    /// The lines registered for the sound.
+    #[inline]
+    pub fn lines(&self, sound: &Sound) -> Option<&[CaptionLine]> {
+        self.cues
+            .iter()
+            .find(|(s, _)| s == sound)
+            .map(|(_, lines)| lines.as_slice())
+    }
+
+    /// The color of a speaker's lines.
+    #[inline]
+    pub fn speaker_color(&self, speaker: &str) -> Option<Rgba8> {
+        self.speakers.get(speaker).copied()
+    }
+
+    /// Set the color of a speaker's lines.
+    #[inline]
+    pub fn set_speaker_color(&mut self, speaker: impl Into<String>, color: Rgba8) {
+        self.speakers.insert(speaker.into(), color);
+    }
+
+    /// Show the lines registered for the playback's sound as it plays. Playbacks of sounds
+    /// without lines are ignored.
+    #[inline]
+    pub fn track(&mut self, playback: Playback) {
+        if self.lines(playback.sound()).is_some() && !self.tracked.contains(&playback) {
+            self.tracked.push(playback);
+        }
+    }
+
+    /// Stop showing lines for every playback.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tracked.clear();
+    }
+
+    /// Track any music that has started playing, and stop tracking playbacks that have
+    /// finished.
+    pub fn update(&mut self, audio: &AudioContext) {
+        self.tracked.retain(Playback::is_playing);
+        for stem in audio.music_stems() {
+            self.track(stem);
+        }
+    }
+
+    /// Every line that is currently visible, ignoring the settings, from oldest to newest.
+    pub fn visible(&self) -> impl Iterator<Item = &CaptionLine> {
+        self.tracked.iter().flat_map(|playback| {
+            let time = playback.position();
+            self.lines(playback.sound())
+                .unwrap_or_default()
+                .iter()
+                .filter(move |line| line.is_visible_at(time))
+        })
+    }
+
+    /// Draw the visible lines within the area, usually the whole screen, as allowed by the
+    /// settings.
+    pub fn draw(&self, draw: &mut Draw, font: &Font, area: RectF) -> Result<(), DrawError> {
+        let s = &self.settings;
+        if !s.enabled {
+            return Ok(());
+        }
+        let scale = s.scale.max(0.01);
+        let pad = font.size() * 0.25;
+        let max_width = area.w * s.max_width / scale - pad * 2.0;
+        let back = Rgba8::new(0, 0, 0, (s.background.clamp(0.0, 1.0) * 255.0) as u8);
+
+        let lines: Vec<_> = self
+            .visible()
+            .filter(|line| s.sound_effects || line.speaker.is_some())
+            .collect();
+        let mut y = match s.position {
+            CaptionPosition::Bottom => area.bottom() - s.margin,
+            CaptionPosition::Top => area.y + s.margin,
+        };
+
+        // the newest line is closest to the edge, so older lines move away from it
+        for line in lines.into_iter().rev() {
+            let (text, color) = match &line.speaker {
+                Some(speaker) => {
+                    let color = match s.speaker_colors {
+                        true => self.speaker_color(speaker).unwrap_or(Rgba8::WHITE),
+                        false => Rgba8::WHITE,
+                    };
+                    match s.speaker_names {
+                        true => (format!("{speaker}: {}", line.text), color),
+                        false => (line.text.clone(), color),
+                    }
+                }
+                None => (line.text.clone(), Rgba8::WHITE),
+            };
+            let layout = TextLayout::new(&text, font, max_width, TextAlign::Center, 1.2);
+            let size = (layout.size() + vec2(pad, pad) * 2.0) * scale;
+            let top = match s.position {
+                CaptionPosition::Bottom => y - size.y,
+                CaptionPosition::Top => y,
+            };
+            draw.push_translation(vec2(area.center().x - size.x * 0.5, top));
+            draw.push_scale_of(scale);
+            if back.a > 0 {
+                draw.rect(RectF::pos_size(vec2(0.0, 0.0), size / scale), back);
+            }
+            draw.text_layout(&layout, vec2(pad, pad), font, color);
+            draw.pop_transforms(2)?;
+            y = match s.position {
+                CaptionPosition::Bottom => top - pad * scale,
+                CaptionPosition::Top => top + size.y + pad * scale,
+            };
+        }
+        Ok(())
+    }
+}
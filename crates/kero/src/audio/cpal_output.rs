@@ -0,0 +1,156 @@
+use crate::audio::{AudioContext, AudioError, AudioOutput};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::{Device, FromSample, SampleFormat, SizedSample, Stream, StreamConfig, StreamError};
+use std::fmt::{Debug, Formatter};
+
+/// Plays audio through one of the system's output devices.
+///
+/// Unless the game is given another output with
+/// [`GameBuilder::with_audio_output`](crate::core::GameBuilder::with_audio_output), it plays
+/// through the system's default device. Requires the `cpal` feature, which is on by default.
+pub struct CpalOutput {
+    device: Device,
+    config: StreamConfig,
+    format: SampleFormat,
+}
+
+impl Debug for CpalOutput {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CpalOutput")
+            .field("sample_rate", &self.config.sample_rate.0)
+            .field("channels", &self.config.channels)
+            .field("format", &self.format)
+            .finish_non_exhaustive()
+    }
+}
+
+impl CpalOutput {
+    /// Open the system's default output device.
+    pub fn new() -> Result<Self, AudioError> {
+        let device = cpal::default_host()
+            .default_output_device()
+            .ok_or_else(|| AudioError::Output("no output device".to_string()))?;
+        Self::from_device(device)
+    }
+
+    /// Open an output device, playing at its default sample rate.
+    pub fn from_device(device: Device) -> Result<Self, AudioError> {
+        let config = device
+            .default_output_config()
+            .map_err(|err| AudioError::Output(err.to_string()))?;
+        let format = config.sample_format();
+        if !matches!(
+            format,
+            SampleFormat::I8
+                | SampleFormat::I16
+                | SampleFormat::I32
+                | SampleFormat::U8
+                | SampleFormat::U16
+                | SampleFormat::U32
+                | SampleFormat::F32
+                | SampleFormat::F64
+        ) {
+            return Err(AudioError::Output(format!(
+                "unsupported sample format {format}"
+            )));
+        }
+        Ok(Self {
+            device,
+            config: config.config(),
+            format,
+        })
+    }
+
+    /// Open a stream in the device's sample format that plays the context's mix.
+    fn build_stream(&self, audio: &AudioContext) -> Result<Stream, cpal::BuildStreamError> {
+        match self.format {
+            SampleFormat::I8 => self.build::<i8>(audio),
+            SampleFormat::I16 => self.build::<i16>(audio),
+            SampleFormat::I32 => self.build::<i32>(audio),
+            SampleFormat::U8 => self.build::<u8>(audio),
+            SampleFormat::U16 => self.build::<u16>(audio),
+            SampleFormat::U32 => self.build::<u32>(audio),
+            SampleFormat::F64 => self.build::<f64>(audio),
+            _ => self.build::<f32>(audio),
+        }
+    }
+
+    fn build<T: SizedSample + FromSample<f32>>(
+        &self,
+        audio: &AudioContext,
+    ) -> Result<Stream, cpal::BuildStreamError> {
+        let channels = self.config.channels.max(1) as usize;
+        let mut mixed = Vec::new();
+        let mix_audio = audio.clone();
+        let error_audio = audio.clone();
+        self.device.build_output_stream(
+            &self.config,
+            move |out: &mut [T], _| {
+                // the mix is always stereo, so it is spread across the device's channels
+                mixed.resize(out.len() / channels * 2, 0.0);
+                mix_audio.mix(&mut mixed);
+                for (frame, mix) in out.chunks_exact_mut(channels).zip(mixed.chunks_exact(2)) {
+                    let (left, right) = (mix[0].clamp(-1.0, 1.0), mix[1].clamp(-1.0, 1.0));
+                    match frame {
+                        [mono] => *mono = T::from_sample((left + right) * 0.5),
+                        [l, r, rest @ ..] => {
+                            *l = T::from_sample(left);
+                            *r = T::from_sample(right);
+                            rest.fill(T::EQUILIBRIUM);
+                        }
+                        [] => {}
+                    }
+                }
+            },
+            move |err| {
+                log::error!("audio output error: {err}");
+
+                // keep sounds advancing silently if the device goes away
+                if matches!(err, StreamError::DeviceNotAvailable) {
+                    error_audio.set_has_output(false);
+                }
+            },
+            None,
+        )
+    }
+}
+
+impl AudioOutput for CpalOutput {
+    #[inline]
+    fn sample_rate(&self) -> u32 {
+        self.config.sample_rate.0
+    }
+
+    fn start(self: Box<Self>, audio: AudioContext) {
+        // streams can't be sent between threads on every platform, so the stream is opened on
+        // its own thread, which keeps it alive until the game exits
+        let spawned = std::thread::Builder::new()
+            .name("audio output".to_string())
+            .spawn({
+                let audio = audio.clone();
+                move || {
+                    let stream = self
+                        .build_stream(&audio)
+                        .map_err(|err| err.to_string())
+                        .and_then(|stream| {
+                            stream.play().map_err(|err| err.to_string())?;
+                            Ok(stream)
+                        });
+                    match stream {
+                        Ok(_stream) => loop {
+                            std::thread::park();
+                        },
+                        Err(err) => {
+                            log::error!("failed to start audio output: {err}");
+                            audio.set_has_output(false);
+                        }
+                    }
+                }
+            });
+        if let Err(err) = spawned {
+            log::error!("failed to start audio output: {err}");
+            audio.set_has_output(false);
+        }
+    }
+}
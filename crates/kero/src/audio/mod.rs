@@ -0,0 +1,27 @@
+//! Sound loading, playback, and mixing.
+
+mod audio_analysis;
+mod audio_channel;
+mod audio_context;
+mod audio_error;
+mod audio_output;
+mod captions;
+#[cfg(feature = "cpal")]
+mod cpal_output;
+mod playback;
+mod sound;
+mod sound_bank;
+mod vorbis;
+mod wav;
+
+pub use audio_analysis::*;
+pub use audio_channel::*;
+pub use audio_context::*;
+pub use audio_error::*;
+pub use audio_output::*;
+pub use captions::*;
+#[cfg(feature = "cpal")]
+pub use cpal_output::*;
+pub use playback::*;
+pub use sound::*;
+pub use sound_bank::*;
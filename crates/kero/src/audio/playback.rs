@@ -0,0 +1,271 @@
+use crate::audio::{AudioChannel, Sound};
+use crate::math::{Vec2F, vec2};
+use std::fmt::{Debug, Formatter};
+use std::ops::Range;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+
+/// Handle to a sound that was played on an [`AudioContext`](super::AudioContext), which
+/// can be used to control it while it plays.
+///
+/// Dropping the handle does not stop the sound.
+#[derive(Clone)]
+pub struct Playback(pub(crate) Arc<PlaybackState>);
+
+/// State shared between a playback handle and the mixer.
+pub(crate) struct PlaybackState {
+    pub sound: Sound,
+    pub channel: AudioChannel,
+    pub volume: AtomicF32,
+    pub pan: AtomicF32,
+    pub speed: AtomicF32,
+    pub looping: AtomicBool,
+    pub paused: AtomicBool,
+    pub stopped: AtomicBool,
+    pub position: AtomicU64,
+    pub loop_start: AtomicU64,
+    pub loop_end: AtomicU64,
+    pub fade_target: AtomicF32,
+    pub fade_speed: AtomicF32,
+    pub fade_stop: AtomicBool,
+    pub spatial: AtomicBool,
+    pub emitter_x: AtomicF32,
+    pub emitter_y: AtomicF32,
+}
+
+impl Debug for Playback {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Playback")
+            .field("channel", &self.channel())
+            .field("playing", &self.is_playing())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for Playback {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Playback {
+    pub(crate) fn new(
+        sound: Sound,
+        channel: AudioChannel,
+        volume: f32,
+        pan: f32,
+        looping: bool,
+        emitter: Option<Vec2F>,
+    ) -> Self {
+        let pos = emitter.unwrap_or_default();
+        Self(Arc::new(PlaybackState {
+            sound,
+            channel,
+            volume: AtomicF32::new(volume.max(0.0)),
+            pan: AtomicF32::new(pan.clamp(-1.0, 1.0)),
+            speed: AtomicF32::new(1.0),
+            looping: AtomicBool::new(looping),
+            paused: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+            position: AtomicU64::new(0),
+            loop_start: AtomicU64::new(0),
+            loop_end: AtomicU64::new(u64::MAX),
+            fade_target: AtomicF32::new(0.0),
+            fade_speed: AtomicF32::new(0.0),
+            fade_stop: AtomicBool::new(false),
+            spatial: AtomicBool::new(emitter.is_some()),
+            emitter_x: AtomicF32::new(pos.x),
+            emitter_y: AtomicF32::new(pos.y),
+        }))
+    }
+
+    /// The sound being played.
+    #[inline]
+    pub fn sound(&self) -> &Sound {
+        &self.0.sound
+    }
+
+    /// The channel the sound is playing on.
+    #[inline]
+    pub fn channel(&self) -> AudioChannel {
+        self.0.channel
+    }
+
+    /// If the sound hasn't finished or been stopped. Paused sounds are still playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        !self.0.stopped.load(Ordering::Relaxed)
+    }
+
+    /// Stop the sound. It can't be resumed afterwards.
+    #[inline]
+    pub fn stop(&self) {
+        self.0.stopped.store(true, Ordering::Relaxed);
+    }
+
+    /// If the sound is paused.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.0.paused.load(Ordering::Relaxed)
+    }
+
+    /// Pause or resume the sound.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        self.0.paused.store(paused, Ordering::Relaxed);
+    }
+
+    /// Volume of the sound, where `1.0` is its original volume.
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        self.0.volume.get()
+    }
+
+    /// Set the volume of the sound, where `1.0` is its original volume. This cancels any fade
+    /// in progress.
+    #[inline]
+    pub fn set_volume(&self, volume: f32) {
+        self.0.fade_speed.set(0.0);
+        self.0.fade_stop.store(false, Ordering::Relaxed);
+        self.0.volume.set(volume.max(0.0));
+    }
+
+    /// Smoothly change the volume over `duration` seconds.
+    pub fn fade_to(&self, volume: f32, duration: f32) {
+        let volume = volume.max(0.0);
+        if duration <= 0.0 {
+            self.set_volume(volume);
+            return;
+        }
+        self.0.fade_stop.store(false, Ordering::Relaxed);
+        self.0.fade_target.set(volume);
+        self.0
+            .fade_speed
+            .set((volume - self.volume()).abs().max(f32::EPSILON) / duration);
+    }
+
+    /// Fade the sound to silence over `duration` seconds, then stop it.
+    pub fn fade_out(&self, duration: f32) {
+        if duration <= 0.0 {
+            self.stop();
+            return;
+        }
+        self.fade_to(0.0, duration);
+        self.0.fade_stop.store(true, Ordering::Relaxed);
+    }
+
+    /// If the volume is fading.
+    #[inline]
+    pub fn is_fading(&self) -> bool {
+        self.0.fade_speed.get() > 0.0
+    }
+
+    /// Stereo panning, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn pan(&self) -> f32 {
+        self.0.pan.get()
+    }
+
+    /// Set the stereo panning, from `-1.0` (left) to `1.0` (right).
+    #[inline]
+    pub fn set_pan(&self, pan: f32) {
+        self.0.pan.set(pan.clamp(-1.0, 1.0));
+    }
+
+    /// Playback speed, which also changes the pitch. `1.0` is the original speed.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.0.speed.get()
+    }
+
+    /// Set the playback speed, which also changes the pitch. `1.0` is the original speed.
+    #[inline]
+    pub fn set_speed(&self, speed: f32) {
+        self.0.speed.set(speed.max(0.0));
+    }
+
+    /// If the sound starts over when it reaches the end.
+    #[inline]
+    pub fn looping(&self) -> bool {
+        self.0.looping.load(Ordering::Relaxed)
+    }
+
+    /// Set if the sound starts over when it reaches the end.
+    #[inline]
+    pub fn set_looping(&self, looping: bool) {
+        self.0.looping.store(looping, Ordering::Relaxed);
+    }
+
+    /// The range of frames that repeats when the sound is looping. Frames before the region
+    /// play once as an intro, and frames after it are never reached. By default, this is the
+    /// whole sound.
+    #[inline]
+    pub fn loop_region(&self) -> Range<usize> {
+        let frames = self.0.sound.frames();
+        let end = (self.0.loop_end.load(Ordering::Relaxed) as usize).min(frames);
+        let start = (self.0.loop_start.load(Ordering::Relaxed) as usize).min(end);
+        start..end
+    }
+
+    /// Set the range of frames that repeats when the sound is looping, such as to play an
+    /// intro once before looping the rest of a track. Frames are counted at the sound's
+    /// [sample rate](Sound::sample_rate), so loop points are sample-accurate. Empty regions
+    /// loop the whole sound.
+    #[inline]
+    pub fn set_loop_region(&self, region: Range<usize>) {
+        let (start, end) = match region.start < region.end {
+            true => (region.start as u64, region.end as u64),
+            false => (0, u64::MAX),
+        };
+        self.0.loop_start.store(start, Ordering::Relaxed);
+        self.0.loop_end.store(end, Ordering::Relaxed);
+    }
+
+    /// Where the sound is being emitted from in the world, if it is positional.
+    #[inline]
+    pub fn emitter_pos(&self) -> Option<Vec2F> {
+        self.0
+            .spatial
+            .load(Ordering::Relaxed)
+            .then(|| vec2(self.0.emitter_x.get(), self.0.emitter_y.get()))
+    }
+
+    /// Move where the sound is being emitted from, making it positional if it wasn't already.
+    /// Call this every frame for sounds attached to moving objects.
+    #[inline]
+    pub fn set_emitter_pos(&self, pos: impl Into<Vec2F>) {
+        let pos = pos.into();
+        self.0.emitter_x.set(pos.x);
+        self.0.emitter_y.set(pos.y);
+        self.0.spatial.store(true, Ordering::Relaxed);
+    }
+
+    /// Current playback position in seconds.
+    #[inline]
+    pub fn position(&self) -> f32 {
+        let frame = f64::from_bits(self.0.position.load(Ordering::Relaxed));
+        (frame / self.0.sound.sample_rate() as f64) as f32
+    }
+}
+
+/// An `f32` that can be shared between threads.
+pub(crate) struct AtomicF32(AtomicU32);
+
+impl AtomicF32 {
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        Self(AtomicU32::new(value.to_bits()))
+    }
+
+    #[inline]
+    pub fn get(&self) -> f32 {
+        f32::from_bits(self.0.load(Ordering::Relaxed))
+    }
+
+    #[inline]
+    pub fn set(&self, value: f32) {
+        self.0.store(value.to_bits(), Ordering::Relaxed);
+    }
+}
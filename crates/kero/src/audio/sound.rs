@@ -0,0 +1,131 @@
+use crate::audio::AudioError;
+use crate::audio::vorbis::decode_vorbis;
+use crate::audio::wav::decode_wav;
+use std::fmt::{Debug, Formatter};
+use std::path::Path;
+use std::sync::Arc;
+
+/// A sound loaded into memory, ready to be played.
+///
+/// This handle can be cloned cheaply, and every clone shares the same samples.
+#[derive(Clone)]
+pub struct Sound(pub(crate) Arc<SoundData>);
+
+pub(crate) struct SoundData {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+impl Debug for Sound {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sound")
+            .field("channels", &self.channels())
+            .field("sample_rate", &self.sample_rate())
+            .field("frames", &self.frames())
+            .finish()
+    }
+}
+
+impl PartialEq for Sound {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Sound {
+    /// Create a sound from interleaved samples from `-1.0` to `1.0`, with 1 (mono) or 2
+    /// (stereo) channels.
+    pub fn from_samples(channels: u16, sample_rate: u32, samples: Vec<f32>) -> Self {
+        assert!(
+            channels == 1 || channels == 2,
+            "sounds must have 1 or 2 channels"
+        );
+        assert!(sample_rate > 0, "sample rate must be greater than zero");
+        Self(Arc::new(SoundData {
+            channels,
+            sample_rate,
+            samples,
+        }))
+    }
+
+    /// Load a sound from WAV bytes.
+    pub fn from_wav_bytes(bytes: &[u8]) -> Result<Self, AudioError> {
+        let wav = decode_wav(bytes)?;
+        Ok(Self::from_samples(
+            wav.channels,
+            wav.sample_rate,
+            wav.samples,
+        ))
+    }
+
+    /// Load a sound from OGG Vorbis bytes.
+    pub fn from_ogg_bytes(bytes: &[u8]) -> Result<Self, AudioError> {
+        let ogg = decode_vorbis(bytes)?;
+        Ok(Self::from_samples(
+            ogg.channels,
+            ogg.sample_rate,
+            ogg.samples,
+        ))
+    }
+
+    /// Load a sound from bytes, detecting the format from its header. WAV and OGG Vorbis are
+    /// supported.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, AudioError> {
+        match bytes.get(0..4) {
+            Some(b"RIFF") => Self::from_wav_bytes(bytes),
+            Some(b"OggS") => Self::from_ogg_bytes(bytes),
+            _ => Err(AudioError::UnsupportedFormat("unknown".to_string())),
+        }
+    }
+
+    /// Load a sound from a file, detecting the format from its header.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, AudioError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// How many channels the sound has, `1` for mono or `2` for stereo.
+    #[inline]
+    pub fn channels(&self) -> u16 {
+        self.0.channels
+    }
+
+    /// The sample rate in hertz.
+    #[inline]
+    pub fn sample_rate(&self) -> u32 {
+        self.0.sample_rate
+    }
+
+    /// The interleaved samples.
+    #[inline]
+    pub fn samples(&self) -> &[f32] {
+        &self.0.samples
+    }
+
+    /// How many frames the sound has, where each frame has one sample per channel.
+    #[inline]
+    pub fn frames(&self) -> usize {
+        self.0.samples.len() / self.0.channels as usize
+    }
+
+    /// Length of the sound in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.frames() as f32 / self.0.sample_rate as f32
+    }
+
+    /// The left and right samples of a frame. Mono sounds return the same sample for both.
+    #[inline]
+    pub fn frame(&self, frame: usize) -> (f32, f32) {
+        match self.0.channels {
+            1 => {
+                let s = self.0.samples[frame];
+                (s, s)
+            }
+            _ => (self.0.samples[frame * 2], self.0.samples[frame * 2 + 1]),
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::audio::{AudioChannel, AudioContext, Playback, Sound};
+use crate::math::Vec2F;
+use crate::rand::Rand;
+use fnv::FnvHashMap;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "lua")]
+pub type SoundBankObj = fey_lua::UserDataOf<SoundBank>;
+#[cfg(feature = "lua")]
+pub type SoundBankRef = mlua::UserDataRef<SoundBank>;
+#[cfg(feature = "lua")]
+pub type SoundBankMut = mlua::UserDataRefMut<SoundBank>;
+
+/// How a [`SoundEvent`] picks which of its sounds to play next.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SoundSelect {
+    /// Any sound, even the one that just played.
+    Random,
+
+    /// Any sound except the one that just played.
+    #[default]
+    NoRepeat,
+
+    /// Every sound in order, then starting over.
+    RoundRobin,
+
+    /// Every sound in a random order, then reshuffling, so each sound plays once per round.
+    Shuffle,
+}
+
+/// A named event in a [`SoundBank`], which plays one of several variations of a sound with a
+/// random volume and pitch.
+#[derive(Debug, Clone)]
+pub struct SoundEvent {
+    /// The variations of the sound.
+    pub sounds: Vec<Sound>,
+
+    /// Range the volume is randomly picked from.
+    pub volume: RangeInclusive<f32>,
+
+    /// Range the pitch is randomly picked from, where `1.0` is the original pitch.
+    pub pitch: RangeInclusive<f32>,
+
+    /// How the next variation is picked.
+    pub select: SoundSelect,
+
+    /// The channel the sounds play on.
+    pub channel: AudioChannel,
+
+    order: Vec<usize>,
+    next: usize,
+    last: Option<usize>,
+}
+
+impl SoundEvent {
+    /// Create an event that plays one of the sounds at full volume and its original pitch,
+    /// never playing the same sound twice in a row.
+    pub fn new(sounds: impl IntoIterator<Item = Sound>) -> Self {
+        Self {
+            sounds: sounds.into_iter().collect(),
+            volume: 1.0..=1.0,
+            pitch: 1.0..=1.0,
+            select: SoundSelect::NoRepeat,
+            channel: AudioChannel::Sfx,
+            order: Vec::new(),
+            next: 0,
+            last: None,
+        }
+    }
+
+    /// Return the event with its volume range changed.
+    #[inline]
+    pub fn with_volume(mut self, volume: RangeInclusive<f32>) -> Self {
+        self.volume = volume;
+        self
+    }
+
+    /// Return the event with its pitch range changed, such as `0.9..=1.1`.
+    #[inline]
+    pub fn with_pitch(mut self, pitch: RangeInclusive<f32>) -> Self {
+        self.pitch = pitch;
+        self
+    }
+
+    /// Return the event with its selection changed.
+    #[inline]
+    pub fn with_select(mut self, select: SoundSelect) -> Self {
+        self.select = select;
+        self
+    }
+
+    /// Return the event with its channel changed.
+    #[inline]
+    pub fn with_channel(mut self, channel: AudioChannel) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Pick the next sound to play, along with its volume and pitch.
+    pub fn pick(&mut self, rand: &mut Rand) -> Option<(&Sound, f32, f32)> {
+        let count = self.sounds.len();
+        if count == 0 {
+            return None;
+        }
+        let index = match self.select {
+            SoundSelect::Random => rand.range(0..count),
+            SoundSelect::NoRepeat => match self.last {
+                Some(last) if count > 1 && last < count => {
+                    // pick from every other sound, then skip past the last one
+                    let i = rand.range(0..count - 1);
+                    if i >= last { i + 1 } else { i }
+                }
+                _ => rand.range(0..count),
+            },
+            SoundSelect::RoundRobin => self.last.map_or(0, |last| (last + 1) % count),
+            SoundSelect::Shuffle => {
+                if self.next >= self.order.len() || self.order.len() != count {
+                    self.order = (0..count).collect();
+                    rand.shuffle(&mut self.order);
+                    // don't let a new round start with the sound that ended the last one
+                    if count > 1 && self.last == Some(self.order[0]) {
+                        self.order.swap(0, count - 1);
+                    }
+                    self.next = 0;
+                }
+                self.next += 1;
+                self.order[self.next - 1]
+            }
+        };
+        self.last = Some(index);
+        let volume = random_in(rand, &self.volume);
+        let pitch = random_in(rand, &self.pitch);
+        Some((&self.sounds[index], volume, pitch))
+    }
+}
+
+/// Randomly pick a value in the range, which may be reversed or empty.
+#[inline]
+fn random_in(rand: &mut Rand, range: &RangeInclusive<f32>) -> f32 {
+    let (a, b) = (*range.start(), *range.end());
+    match a < b {
+        true => rand.range(a..=b),
+        false => a,
+    }
+}
+
+/// Named sound events that each play one of several variations of a sound, with random
+/// volume and pitch, to avoid the "machine gun" effect of hearing the exact same footstep or
+/// hit over and over.
+///
+/// ```ignore
+/// let mut sfx = SoundBank::new().with_event(
+///     "step",
+///     SoundEvent::new([step1, step2, step3])
+///         .with_volume(0.8..=1.0)
+///         .with_pitch(0.9..=1.1),
+/// );
+/// sfx.play(&ctx.audio, "step");
+/// ```
+#[derive(Debug, Clone)]
+pub struct SoundBank {
+    events: FnvHashMap<String, SoundEvent>,
+    rand: Rand,
+}
+
+impl Default for SoundBank {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SoundBank {
+    /// Create an empty sound bank with a random seed.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_rand(Rand::new())
+    }
+
+    /// Create an empty sound bank that picks sounds using the random number generator.
+    #[inline]
+    pub fn from_rand(rand: Rand) -> Self {
+        Self {
+            events: FnvHashMap::default(),
+            rand,
+        }
+    }
+
+    /// Return the sound bank with an event added.
+    #[inline]
+    pub fn with_event(mut self, name: impl Into<String>, event: SoundEvent) -> Self {
+        self.insert(name, event);
+        self
+    }
+
+    /// Add an event, replacing any event that had the same name.
+    #[inline]
+    pub fn insert(&mut self, name: impl Into<String>, event: SoundEvent) -> Option<SoundEvent> {
+        self.events.insert(name.into(), event)
+    }
+
+    /// Remove an event.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<SoundEvent> {
+        self.events.remove(name)
+    }
+
+    /// If the bank has an event with the name.
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.events.contains_key(name)
+    }
+
+    /// Get an event.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SoundEvent> {
+        self.events.get(name)
+    }
+
+    /// Get a mutable reference to an event.
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut SoundEvent> {
+        self.events.get_mut(name)
+    }
+
+    /// The random number generator used to pick sounds.
+    #[inline]
+    pub fn rand(&mut self) -> &mut Rand {
+        &mut self.rand
+    }
+
+    /// Play the next sound of an event. Returns `None` if there is no event with the name, or
+    /// it has no sounds.
+    #[inline]
+    pub fn play(&mut self, audio: &AudioContext, name: &str) -> Option<Playback> {
+        self.play_ext(audio, name, 1.0, None)
+    }
+
+    /// Play the next sound of an event, emitted from a position in the world.
+    #[inline]
+    pub fn play_at(
+        &mut self,
+        audio: &AudioContext,
+        name: &str,
+        pos: impl Into<Vec2F>,
+    ) -> Option<Playback> {
+        self.play_ext(audio, name, 1.0, Some(pos.into()))
+    }
+
+    /// Play the next sound of an event with its volume multiplied by `volume`, and optionally
+    /// emitted from a position in the world.
+    pub fn play_ext(
+        &mut self,
+        audio: &AudioContext,
+        name: &str,
+        volume: f32,
+        pos: Option<Vec2F>,
+    ) -> Option<Playback> {
+        let event = self.events.get_mut(name)?;
+        let channel = event.channel;
+        let (sound, vol, pitch) = event.pick(&mut self.rand)?;
+        let playback = Playback::new(sound.clone(), channel, vol * volume, 0.0, false, pos);
+        playback.set_speed(pitch);
+        audio.start(playback.clone());
+        Some(playback)
+    }
+}
@@ -0,0 +1,39 @@
+use crate::audio::AudioError;
+use lewton::inside_ogg::OggStreamReader;
+use std::io::Cursor;
+
+/// Decoded OGG Vorbis audio, with up to 2 interleaved channels.
+pub(crate) struct Vorbis {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+/// Decode an OGG Vorbis file. Files with more than 2 channels only keep the first 2.
+pub(crate) fn decode_vorbis(bytes: &[u8]) -> Result<Vorbis, AudioError> {
+    let invalid = |err: lewton::VorbisError| AudioError::InvalidVorbis(err.to_string());
+    let mut reader = OggStreamReader::new(Cursor::new(bytes)).map_err(invalid)?;
+    let channels = reader.ident_hdr.audio_channels as usize;
+    let sample_rate = reader.ident_hdr.audio_sample_rate;
+    if channels == 0 || sample_rate == 0 {
+        return Err(AudioError::InvalidVorbis(
+            "no channels or sample rate".to_string(),
+        ));
+    }
+
+    let kept = channels.min(2);
+    let mut samples = Vec::new();
+    while let Some(packet) = reader.read_dec_packet_itl().map_err(invalid)? {
+        samples.extend(
+            packet
+                .chunks_exact(channels)
+                .flat_map(|frame| &frame[..kept])
+                .map(|&s| s as f32 / 32768.0),
+        );
+    }
+    Ok(Vorbis {
+        channels: kept as u16,
+        sample_rate,
+        samples,
+    })
+}
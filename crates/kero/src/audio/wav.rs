@@ -0,0 +1,86 @@
+use crate::audio::AudioError;
+
+/// Decoded WAV audio, with up to 2 interleaved channels.
+pub(crate) struct Wav {
+    pub channels: u16,
+    pub sample_rate: u32,
+    pub samples: Vec<f32>,
+}
+
+const FORMAT_PCM: u16 = 1;
+const FORMAT_FLOAT: u16 = 3;
+const FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Decode a WAV file. Files with more than 2 channels only keep the first 2.
+pub(crate) fn decode_wav(bytes: &[u8]) -> Result<Wav, AudioError> {
+    if bytes.len() < 12 || &bytes[0..4] != b"RIFF" || &bytes[8..12] != b"WAVE" {
+        return Err(AudioError::InvalidWav("missing RIFF header"));
+    }
+
+    // find the format and data chunks
+    let mut fmt = None;
+    let mut data = None;
+    let mut rest = &bytes[12..];
+    while rest.len() >= 8 {
+        let id = &rest[0..4];
+        let len = u32::from_le_bytes(rest[4..8].try_into().unwrap()) as usize;
+        let body = &rest[8..];
+        let chunk = &body[..len.min(body.len())];
+        match id {
+            b"fmt " => fmt = Some(chunk),
+            b"data" => data = Some(chunk),
+            _ => {}
+        }
+        // chunks are padded to an even length
+        rest = body.get(len + (len & 1)..).unwrap_or(&[]);
+    }
+    let fmt = fmt.ok_or(AudioError::InvalidWav("missing format chunk"))?;
+    let data = data.ok_or(AudioError::InvalidWav("missing data chunk"))?;
+    if fmt.len() < 16 {
+        return Err(AudioError::InvalidWav("format chunk too short"));
+    }
+
+    let u16_at = |i: usize| u16::from_le_bytes([fmt[i], fmt[i + 1]]);
+    let mut format = u16_at(0);
+    let channels = u16_at(2);
+    let sample_rate = u32::from_le_bytes(fmt[4..8].try_into().unwrap());
+    let bits = u16_at(14);
+    if format == FORMAT_EXTENSIBLE && fmt.len() >= 26 {
+        format = u16_at(24);
+    }
+    if channels == 0 || sample_rate == 0 {
+        return Err(AudioError::InvalidWav("no channels or sample rate"));
+    }
+
+    let decode: fn(&[u8]) -> f32 = match (format, bits) {
+        (FORMAT_PCM, 8) => |b| (b[0] as f32 - 128.0) / 128.0,
+        (FORMAT_PCM, 16) => |b| i16::from_le_bytes([b[0], b[1]]) as f32 / 32768.0,
+        (FORMAT_PCM, 24) => |b| i32::from_le_bytes([0, b[0], b[1], b[2]]) as f32 / 2147483648.0,
+        (FORMAT_PCM, 32) => |b| i32::from_le_bytes([b[0], b[1], b[2], b[3]]) as f32 / 2147483648.0,
+        (FORMAT_FLOAT, 32) => |b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        (FORMAT_FLOAT, 64) => |b| f64::from_le_bytes(b[..8].try_into().unwrap()) as f32,
+        _ => {
+            return Err(AudioError::UnsupportedWav(format!(
+                "format {format} with {bits} bits per sample"
+            )));
+        }
+    };
+
+    let sample_size = bits as usize / 8;
+    let frame_size = sample_size * channels as usize;
+    let kept = channels.min(2);
+    let samples = data
+        .chunks_exact(frame_size)
+        .flat_map(|frame| {
+            frame
+                .chunks_exact(sample_size)
+                .take(kept as usize)
+                .map(decode)
+        })
+        .collect();
+    Ok(Wav {
+        channels: kept,
+        sample_rate,
+        samples,
+    })
+}
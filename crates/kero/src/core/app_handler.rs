@@ -1,19 +1,29 @@
 use super::Game;
+use crate::audio::{AudioContext, AudioOutput};
+use crate::color::Rgba8;
 use crate::core::frame_timer::FrameTimer;
-use crate::core::{Context, GameBuilder, Time, Window};
+use crate::core::{Context, DebugTools, GameBuilder, Platform, ScreenEffects, Time, Window};
 use crate::gfx::{Draw, Graphics};
-use crate::input::{Gamepads, Keyboard, Mouse};
+use crate::input::{Gamepads, InputRecorder, Key, Keyboard, Mouse, Touch};
+use crate::math::{Affine2F, Numeric, vec2};
+use crate::misc::Tweaks;
 use crate::prelude::ContextData;
+use crate::telemetry::Telemetry;
 use directories::ProjectDirs;
 use dpi::LogicalSize;
 use std::cell::Cell;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::sync::Arc;
 use winit::application::ApplicationHandler;
-use winit::event::WindowEvent;
+use winit::event::{DeviceEvent, DeviceId, WindowEvent};
 use winit::event_loop::ActiveEventLoop;
-use winit::window::{WindowAttributes, WindowId};
+use winit::window::{WindowAttributes, WindowId, WindowLevel};
 
+/// Sample rate of the audio mixer when there is no output.
+const DEFAULT_SAMPLE_RATE: u32 = 48000;
+
+// there is only ever one of these, so the size difference doesn't matter
+#[allow(clippy::large_enum_variant)]
 enum AppState<G: Game> {
     Startup {
         opts: GameBuilder,
@@ -26,6 +36,8 @@ enum AppState<G: Game> {
         size: LogicalSize<f64>,
         game: G,
         has_updated: bool,
+        debug_keys: bool,
+        input_recording: Option<PathBuf>,
 
         #[cfg(feature = "lua")]
         lua_app: crate::core::LuaApp,
@@ -57,21 +69,42 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
         let size = LogicalSize::new(opts.size.x as f64, opts.size.y as f64);
         let attrs = WindowAttributes::default()
             .with_title(&opts.title)
-            .with_inner_size(size);
-        let window = Window(Arc::new(
+            .with_inner_size(size)
+            .with_transparent(opts.transparent)
+            .with_window_level(match opts.always_on_top {
+                true => WindowLevel::AlwaysOnTop,
+                false => WindowLevel::Normal,
+            });
+        let window = Window::new(
             event_loop
                 .create_window(attrs)
                 .expect("failed to create window"),
-        ));
+        );
+        if opts.click_through {
+            window.set_click_through(true);
+        }
 
         // initialize the graphics
         let graphics = Graphics::new(window.clone(), opts);
 
+        // start the audio mixer, playing through the output if there is one
+        let audio = match opts.audio_output.take().or_else(default_audio_output) {
+            Some(output) => {
+                let audio = AudioContext::new(output.sample_rate());
+                audio.set_has_output(true);
+                output.start(audio.clone());
+                audio
+            }
+            None => AudioContext::new(DEFAULT_SAMPLE_RATE),
+        };
+
         // create the drawing context
         let draw = Draw::new(
             graphics.device().clone(),
             graphics.queue().clone(),
+            graphics.memory().clone(),
             graphics.default_shader().clone(),
+            graphics.sdf_shader().clone(),
             graphics.default_texture().clone(),
         );
 
@@ -84,14 +117,46 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
         let dirs = ProjectDirs::from("", &opts.app_organization, app_name)
             .expect("failed to locate system directories");
 
+        // start telemetry, remembering whether the player opted in with the other settings
+        let telemetry = Telemetry::new(
+            opts.telemetry_sink.take(),
+            dirs.config_dir().join("telemetry.json"),
+        );
+
+        // bug reports are saved with the player's data, where testers can find them
+        let debug = DebugTools::new(dirs.data_dir().join("bug_reports"));
+
+        // platform integrations connect in the background once the game is running
+        #[cfg(feature = "discord")]
+        let discord_client_id = opts.discord_client_id.take();
+        #[cfg(not(feature = "discord"))]
+        let discord_client_id = None;
+        #[cfg(feature = "steam")]
+        let steam_app_id = opts.steam_app_id;
+        #[cfg(not(feature = "steam"))]
+        let steam_app_id = None;
+        let platform = Platform::new(discord_client_id, steam_app_id);
+
         // create the game context
+        let refresh_rate = window.refresh_rate_mhz();
+        let time = Time::new();
+        let recorder = InputRecorder::new(time.clone());
+        let mouse = Mouse::new(window.clone());
         let ctx = Context(Rc::new(ContextData {
             window,
-            time: Time::new(),
-            mouse: Mouse::new(),
+            effects: ScreenEffects::new(time.clone()),
+            time,
+            mouse,
+            touch: Touch::new(),
             keyboard: Keyboard::new(),
             gamepads: Gamepads::new(),
+            recorder,
             graphics,
+            audio,
+            telemetry,
+            debug,
+            platform,
+            build: std::mem::take(&mut opts.build_info),
 
             #[cfg(feature = "lua")]
             lua: opts.lua.weak(),
@@ -100,10 +165,24 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             reload_lua: Cell::new(false),
 
             quit_requested: Cell::new(false),
+            refresh_rate: Cell::new(refresh_rate),
+            refresh_rate_changed: Cell::new(false),
 
             dirs,
         }));
 
+        ctx.time.set_fixed_rate(opts.fixed_rate);
+        Tweaks::lock().set_build_label(ctx.build.to_string());
+        log::info!("starting {}", ctx.build);
+
+        // start recording or playing back input from the first update
+        if let Some(recording) = opts.input_playback.take() {
+            ctx.recorder.set_quit_when_finished(true);
+            ctx.recorder.play(recording);
+        } else if opts.input_recording.is_some() {
+            ctx.recorder.start_recording();
+        }
+
         // create the frame timer
         let timer = FrameTimer::new(ctx.time.0.clone());
 
@@ -122,6 +201,8 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             size,
             game,
             has_updated: false,
+            debug_keys: opts.debug_keys,
+            input_recording: opts.input_recording.take(),
 
             #[cfg(feature = "lua")]
             lua_app,
@@ -141,6 +222,8 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             size,
             game,
             has_updated,
+            debug_keys,
+            input_recording: _,
 
             #[cfg(feature = "lua")]
             lua_app,
@@ -154,6 +237,9 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             return;
         };
 
+        // custom cursors can only be created while handling events
+        ctx.window.apply_cursor_image(event_loop);
+
         match event {
             WindowEvent::ActivationTokenDone { .. } => {}
             WindowEvent::Resized(new_size) => {
@@ -168,23 +254,40 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             WindowEvent::DroppedFile(_) => {}
             WindowEvent::HoveredFile(_) => {}
             WindowEvent::HoveredFileCancelled => {}
-            WindowEvent::Focused(_) => {}
+            WindowEvent::Focused(focused) => {
+                if focused && ctx.mouse.captured() {
+                    ctx.mouse.apply_capture();
+                }
+            }
+            // real keyboard and mouse input is ignored while a recording plays back
             WindowEvent::KeyboardInput { event, .. } => {
-                ctx.keyboard.handle_event(event);
+                if !ctx.recorder.is_playing() {
+                    ctx.keyboard.handle_event(event);
+                }
             }
             WindowEvent::ModifiersChanged(_) => {}
-            WindowEvent::Ime(_) => {}
+            WindowEvent::Ime(ime) => {
+                if !ctx.recorder.is_playing() {
+                    ctx.keyboard.handle_ime(ime);
+                }
+            }
             WindowEvent::CursorMoved { position, .. } => {
-                let position = position.to_logical::<f32>(ctx.window.0.scale_factor());
-                ctx.mouse.handle_move(position);
+                if !ctx.recorder.is_playing() {
+                    let position = position.to_logical::<f32>(ctx.window.0.scale_factor());
+                    ctx.mouse.handle_move(position);
+                }
             }
             WindowEvent::CursorEntered { .. } => {}
             WindowEvent::CursorLeft { .. } => {}
             WindowEvent::MouseWheel { delta, .. } => {
-                ctx.mouse.handle_scroll(delta);
+                if !ctx.recorder.is_playing() {
+                    ctx.mouse.handle_scroll(delta);
+                }
             }
             WindowEvent::MouseInput { state, button, .. } => {
-                ctx.mouse.handle_input(button, state);
+                if !ctx.recorder.is_playing() {
+                    ctx.mouse.handle_input(button, state);
+                }
             }
             WindowEvent::PinchGesture { .. } => {}
             WindowEvent::PanGesture { .. } => {}
@@ -192,7 +295,9 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             WindowEvent::RotationGesture { .. } => {}
             WindowEvent::TouchpadPressure { .. } => {}
             WindowEvent::AxisMotion { .. } => {}
-            WindowEvent::Touch(_) => {}
+            WindowEvent::Touch(touch) => {
+                ctx.touch.handle_event(touch, ctx.window.0.scale_factor());
+            }
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 mut inner_size_writer,
@@ -206,14 +311,32 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             WindowEvent::ThemeChanged(_) => {}
             WindowEvent::Occluded(_) => {}
             WindowEvent::RedrawRequested => {
-                let monitor = ctx.window.monitor();
+                ctx.update_refresh_rate();
 
-                timer.tick(monitor, || {
+                // recognize touch gestures that depend on time passing
+                ctx.touch.update();
+
+                timer.tick(ctx.refresh_rate.get(), || {
                     *has_updated = true;
 
+                    // advance screen shake and hit-stop, which can freeze game time
+                    ctx.effects.update();
+
                     // update gamepad input
                     ctx.gamepads.update(ctx);
 
+                    // record input, or replace it with a recording being played back
+                    ctx.recorder.update(ctx);
+
+                    // catch fixed updates up with the time that passed
+                    for _ in 0..ctx.time.0.fixed_steps() {
+                        #[cfg(feature = "lua")]
+                        lua_app.fixed_update(ctx);
+
+                        // TODO: propagate this error somewhere
+                        game.fixed_update(ctx).unwrap();
+                    }
+
                     // update the lua app
                     #[cfg(feature = "lua")]
                     lua_app.update(ctx);
@@ -224,15 +347,33 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
 
                     // clear input on-frame events (eg. pressed, released)
                     ctx.mouse.clear_phase();
+                    ctx.touch.clear_phase();
                     ctx.keyboard.clear_phase();
                     ctx.gamepads.clear_phase();
                 });
 
+                // advance sounds that aren't being mixed by an output
+                ctx.audio.update(ctx.time.unfixed_delta());
+
                 // switch to the render phase for input
                 ctx.mouse.set_render_phase();
+                ctx.touch.set_render_phase();
                 ctx.keyboard.set_render_phase();
                 ctx.gamepads.set_render_phase();
 
+                // pause and step through updates with the debug keys
+                if *debug_keys {
+                    if ctx.keyboard.pressed(Key::F10) {
+                        ctx.time.toggle_paused();
+                    }
+                    if ctx.keyboard.pressed_or_repeated(Key::F11) {
+                        ctx.time.step();
+                    }
+                }
+
+                // upload some of any textures being loaded in the background
+                ctx.graphics.process_texture_loads();
+
                 // begin rendering a frame
                 draw.begin_frame(ctx.window.size());
 
@@ -247,19 +388,64 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
                     game.render(ctx, draw).unwrap();
                 }
 
+                // adjust and draw the tweaks overlay
+                {
+                    let mut tweaks = Tweaks::lock();
+                    tweaks.update_overlay(&ctx.keyboard);
+                    if tweaks.overlay_enabled() {
+                        draw.set_surface(None, None);
+                        draw.set_clip_rect(None);
+                        draw.push_new_transform(Affine2F::IDENTITY);
+                        tweaks.draw_overlay(draw, vec2(8.0, 8.0), Rgba8::WHITE);
+                        _ = draw.pop_transform();
+                    }
+                    // TODO: propagate this error somewhere
+                    _ = tweaks.save_changes();
+                }
+
+                // draw the atlas debug overlay on top of everything
+                {
+                    let atlas_debug = ctx.graphics.atlas_debug();
+                    if atlas_debug.enabled() {
+                        draw.set_surface(None, None);
+                        draw.set_clip_rect(None);
+                        draw.push_new_transform(Affine2F::IDENTITY);
+                        let size = ctx.window.size().to_f32();
+                        atlas_debug.draw(draw, size, Some(ctx.mouse.pos()));
+                        _ = draw.pop_transform();
+                    }
+                }
+
+                // capture the window if a bug report is being saved
+                if ctx.debug.is_capturing() {
+                    draw.request_capture();
+                }
+
                 // finish rendering a frame
-                draw.end_frame(timer.time.frame.get(), ctx.graphics.surface(), &ctx.window);
+                let stats =
+                    draw.end_frame(timer.time.frame.get(), ctx.graphics.surface(), &ctx.window);
+                ctx.debug.finish_reports(ctx, draw.take_capture());
+                ctx.graphics.set_frame_stats(stats);
+                ctx.graphics.memory().next_frame();
 
                 // clear input on-frame events (eg. pressed, released)
                 ctx.mouse.clear_phase();
+                ctx.touch.clear_phase();
                 ctx.keyboard.clear_phase();
                 ctx.gamepads.clear_phase();
 
                 // switch back to the update phase for input
                 ctx.mouse.set_update_phase();
+                ctx.touch.set_update_phase();
                 ctx.keyboard.set_update_phase();
                 ctx.gamepads.set_update_phase();
 
+                // send telemetry events once a batch is ready
+                ctx.telemetry.update(ctx.time.unscaled_delta());
+
+                // keep platform integrations connected and up to date
+                ctx.platform.update();
+
                 // quit if the user requested it
                 if ctx.quit_requested() {
                     event_loop.exit();
@@ -267,4 +453,57 @@ impl<G: Game> ApplicationHandler for AppHandler<G> {
             }
         }
     }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        let AppState::Running { ctx, .. } = &self.state else {
+            return;
+        };
+
+        // raw mouse motion keeps coming when the cursor is captured or against the screen edge
+        if let DeviceEvent::MouseMotion { delta } = event
+            && !ctx.recorder.is_playing()
+        {
+            ctx.mouse.handle_motion(delta);
+        }
+    }
+
+    fn exiting(&mut self, _event_loop: &ActiveEventLoop) {
+        if let AppState::Running {
+            ctx,
+            input_recording,
+            ..
+        } = &self.state
+        {
+            // send any telemetry events that are still queued
+            ctx.telemetry.finish();
+
+            // save the input recorded since the game started
+            if let Some(path) = input_recording
+                && let Some(recording) = ctx.recorder.stop_recording()
+            {
+                match recording.save(path) {
+                    Ok(()) => log::info!("saved input recording to {path:?}"),
+                    Err(err) => log::error!("failed to save input recording to {path:?}: {err}"),
+                }
+            }
+
+            // shut down platforms on the main thread, while the game still exists
+            ctx.platform.shutdown();
+        }
+    }
+}
+
+/// Open the system's default audio device, if the `cpal` feature is on and there is one.
+fn default_audio_output() -> Option<Box<dyn AudioOutput>> {
+    #[cfg(feature = "cpal")]
+    match crate::audio::CpalOutput::new() {
+        Ok(output) => return Some(Box::new(output)),
+        Err(err) => log::warn!("{err}, sounds will play silently"),
+    }
+    None
 }
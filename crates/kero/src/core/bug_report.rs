@@ -0,0 +1,71 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// A bug report being put together by
+/// [`DebugTools::capture_report`](crate::core::DebugTools::capture_report).
+///
+/// Passed to [`DebugTools::on_report`](crate::core::DebugTools::on_report) callbacks, so the
+/// game can attach its own files, such as a replay of the last few seconds or its settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BugReport {
+    description: String,
+    info: Map<String, Value>,
+    files: Vec<(String, Vec<u8>)>,
+}
+
+impl BugReport {
+    pub(crate) fn new(description: String) -> Self {
+        Self {
+            description,
+            info: Map::new(),
+            files: Vec::new(),
+        }
+    }
+
+    /// What the player wrote about the bug.
+    #[inline]
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    /// Details about the game and system, saved in `info.json`.
+    #[inline]
+    pub fn info(&self) -> &Map<String, Value> {
+        &self.info
+    }
+
+    /// Add a detail about the game or system, saved in `info.json`.
+    #[inline]
+    pub fn set_info(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.info.insert(key.into(), value.into());
+    }
+
+    /// The files attached to the report.
+    #[inline]
+    pub fn files(&self) -> impl ExactSizeIterator<Item = (&str, &[u8])> {
+        self.files
+            .iter()
+            .map(|(name, bytes)| (name.as_str(), bytes.as_slice()))
+    }
+
+    /// Attach a file to the report, replacing any with the same name.
+    pub fn attach(&mut self, name: impl Into<String>, bytes: impl Into<Vec<u8>>) {
+        let name = name.into();
+        let bytes = bytes.into();
+        match self.files.iter_mut().find(|(n, _)| *n == name) {
+            Some(file) => file.1 = bytes,
+            None => self.files.push((name, bytes)),
+        }
+    }
+
+    /// Attach a value to the report as a JSON file.
+    #[inline]
+    pub fn attach_json(
+        &mut self,
+        name: impl Into<String>,
+        value: &impl Serialize,
+    ) -> Result<(), serde_json::Error> {
+        self.attach(name, serde_json::to_vec_pretty(value)?);
+        Ok(())
+    }
+}
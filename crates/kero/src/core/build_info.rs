@@ -0,0 +1,158 @@
+use crate::storage::{StorageError, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// Create a [`BuildInfo`] for the crate this is called from, filled in at compile time.
+///
+/// The name and version come from the crate's `Cargo.toml`. The git hash and the date of that
+/// commit are detected by kero's build script, from the git repository the build's `target`
+/// folder is in, and update whenever a commit is made or checked out. If the repository was
+/// created after kero was first built, run `cargo clean -p kero` to detect it.
+///
+/// If the target folder is outside the game's repository, or git isn't available when
+/// building, they can be provided with the `KERO_GIT_HASH` and `KERO_BUILD_DATE` environment
+/// variables instead, which take priority:
+///
+/// ```text
+/// KERO_GIT_HASH=$(git rev-parse --short HEAD) KERO_BUILD_DATE=$(date +%F) cargo build --release
+/// ```
+///
+/// ```ignore
+/// kero::new_game()
+///     .with_build_info(kero::build_info!())
+///     .run::<MyGame>(())
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::core::BuildInfo {
+            git_hash: option_env!("KERO_GIT_HASH")
+                .or($crate::core::BuildInfo::DETECTED_GIT_HASH)
+                .map(Into::into),
+            build_date: option_env!("KERO_BUILD_DATE")
+                .or($crate::core::BuildInfo::DETECTED_BUILD_DATE)
+                .map(Into::into),
+            profile: match cfg!(debug_assertions) {
+                true => "debug",
+                false => "release",
+            }
+            .into(),
+            ..$crate::core::BuildInfo::new(env!("CARGO_PKG_NAME"), env!("CARGO_PKG_VERSION"))
+        }
+    };
+}
+
+/// Information about how the game was built, shown in bug reports and the tweaks overlay so
+/// testers can say exactly which build they were playing. Create one with [`build_info!`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BuildInfo {
+    /// The game's name.
+    pub name: String,
+
+    /// The game's version.
+    pub version: String,
+
+    /// The git commit the game was built from, if known.
+    pub git_hash: Option<String>,
+
+    /// The date the game was built, if known.
+    pub build_date: Option<String>,
+
+    /// The build profile, `debug` or `release`.
+    pub profile: String,
+
+    /// The platform the game was built for, such as `windows-x86_64`.
+    pub target: String,
+
+    /// The version of kero the game was built with.
+    pub kero_version: String,
+}
+
+impl Default for BuildInfo {
+    #[inline]
+    fn default() -> Self {
+        Self::new("", "")
+    }
+}
+
+impl BuildInfo {
+    /// The name of the manifest file written by [`write_manifest`](Self::write_manifest).
+    pub const MANIFEST_FILE: &'static str = "build.json";
+
+    /// The name of the version file written by [`write_manifest`](Self::write_manifest).
+    pub const VERSION_FILE: &'static str = "version.txt";
+
+    /// The git hash detected by kero's build script, used by [`build_info!`].
+    #[doc(hidden)]
+    pub const DETECTED_GIT_HASH: Option<&'static str> = option_env!("KERO_DETECTED_GIT_HASH");
+
+    /// The commit date detected by kero's build script, used by [`build_info!`].
+    #[doc(hidden)]
+    pub const DETECTED_BUILD_DATE: Option<&'static str> = option_env!("KERO_DETECTED_BUILD_DATE");
+
+    /// Create build information for a game with no git hash or build date.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            git_hash: None,
+            build_date: None,
+            profile: match cfg!(debug_assertions) {
+                true => "debug",
+                false => "release",
+            }
+            .to_string(),
+            target: format!("{}-{}", std::env::consts::OS, std::env::consts::ARCH),
+            kero_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+
+    /// If this is a debug build.
+    #[inline]
+    pub fn is_debug(&self) -> bool {
+        self.profile == "debug"
+    }
+
+    /// Write the build information next to the game's packed assets, as a `build.json`
+    /// manifest and a `version.txt` holding only the version.
+    ///
+    /// The version file can be passed to itch.io's butler so uploads are labelled with the
+    /// game's version:
+    ///
+    /// ```text
+    /// butler push build/ studio/game:windows --userversion-file build/version.txt
+    /// ```
+    pub fn write_manifest(&self, dir: impl AsRef<Path>) -> Result<(), StorageError> {
+        let dir = dir.as_ref();
+        std::fs::create_dir_all(dir)?;
+        let json = serde_json::to_vec_pretty(self)?;
+        write_atomic(dir.join(Self::MANIFEST_FILE), &json)?;
+        write_atomic(dir.join(Self::VERSION_FILE), self.version.as_bytes())?;
+        Ok(())
+    }
+
+    /// Read the manifest written by [`write_manifest`](Self::write_manifest) in the folder.
+    pub fn read_manifest(dir: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let bytes = std::fs::read(dir.as_ref().join(Self::MANIFEST_FILE))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+}
+
+impl Display for BuildInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.name.is_empty() {
+            true => write!(f, "kero {}", self.kero_version)?,
+            false => write!(f, "{} {}", self.name, self.version)?,
+        }
+        write!(f, " ({}", self.profile)?;
+        if let Some(hash) = &self.git_hash {
+            write!(f, ", {hash}")?;
+        }
+        if let Some(date) = &self.build_date {
+            write!(f, ", {date}")?;
+        }
+        write!(f, ")")
+    }
+}
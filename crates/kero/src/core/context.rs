@@ -1,7 +1,9 @@
 use super::Time;
-use crate::core::Window;
+use crate::audio::AudioContext;
+use crate::core::{BuildInfo, DebugTools, Platform, ScreenEffects, VideoMode, Window};
 use crate::gfx::Graphics;
-use crate::input::{Gamepads, Keyboard, Mouse};
+use crate::input::{Gamepads, InputRecorder, Keyboard, Mouse, Touch};
+use crate::telemetry::Telemetry;
 use directories::ProjectDirs;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -20,10 +22,18 @@ pub struct Context(pub(crate) Rc<ContextData>);
 pub struct ContextData {
     pub window: Window,
     pub time: Time,
+    pub effects: ScreenEffects,
     pub mouse: Mouse,
+    pub touch: Touch,
     pub keyboard: Keyboard,
     pub gamepads: Gamepads,
+    pub recorder: InputRecorder,
     pub graphics: Graphics,
+    pub audio: AudioContext,
+    pub telemetry: Telemetry,
+    pub debug: DebugTools,
+    pub platform: Platform,
+    pub build: BuildInfo,
 
     #[cfg(feature = "lua")]
     pub lua: mlua::WeakLua,
@@ -32,6 +42,8 @@ pub struct ContextData {
     pub reload_lua: Cell<bool>,
 
     pub quit_requested: Cell<bool>,
+    pub refresh_rate: Cell<Option<u32>>,
+    pub refresh_rate_changed: Cell<bool>,
     pub dirs: ProjectDirs,
 }
 
@@ -72,6 +84,33 @@ impl Context {
         self.quit_requested.get()
     }
 
+    /// The refresh rate of the display the window is on in hertz, if it is known.
+    #[inline]
+    pub fn refresh_rate(&self) -> Option<f64> {
+        self.refresh_rate.get().map(|mhz| mhz as f64 / 1000.0)
+    }
+
+    /// If the refresh rate changed this frame, such as from the window moving to another
+    /// monitor or switching video modes. Fixed timesteps and frame pacing can be reconfigured
+    /// when this happens.
+    #[inline]
+    pub fn refresh_rate_changed(&self) -> bool {
+        self.refresh_rate_changed.get()
+    }
+
+    /// The video mode the window is displayed with.
+    #[inline]
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        self.window.video_mode()
+    }
+
+    /// Check the window's refresh rate, flagging if it changed since the last frame.
+    pub(crate) fn update_refresh_rate(&self) {
+        let refresh_rate = self.window.refresh_rate_mhz();
+        let prev = self.refresh_rate.replace(refresh_rate);
+        self.refresh_rate_changed.set(prev != refresh_rate);
+    }
+
     #[cfg(feature = "lua")]
     pub fn reload_lua(&self) {
         self.reload_lua.set(true);
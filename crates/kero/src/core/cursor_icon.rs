@@ -1,41 +1,111 @@
 use strum::FromRepr;
 
+/// One of the platform's standard cursors, set with
+/// [`Window::set_cursor`](crate::core::Window::set_cursor). For a custom one, use
+/// [`Window::set_cursor_image`](crate::core::Window::set_cursor_image).
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, FromRepr)]
 pub enum CursorIcon {
+    /// The platform's default cursor, usually an arrow.
     #[default]
     Default,
+
+    /// A context menu is available.
     ContextMenu,
+
+    /// Help is available.
     Help,
+
+    /// A link or clickable button, usually a pointing hand.
     Pointer,
+
+    /// The game is busy, but can still be interacted with.
     Progress,
+
+    /// The game is busy and can't be interacted with.
     Wait,
+
+    /// A table cell or group of cells can be selected.
     Cell,
+
+    /// A crosshair, for precise selection.
     Crosshair,
+
+    /// Text can be selected.
     Text,
+
+    /// Vertical text can be selected.
     VerticalText,
+
+    /// An alias or shortcut will be created.
     Alias,
+
+    /// Something will be copied.
     Copy,
+
+    /// Something will be moved.
     Move,
+
+    /// The item can't be dropped here.
     NoDrop,
+
+    /// The action is not allowed.
     NotAllowed,
+
+    /// Something can be grabbed, usually an open hand.
     Grab,
+
+    /// Something is being grabbed, usually a closed hand.
     Grabbing,
+
+    /// The right edge can be resized.
     EResize,
+
+    /// The top edge can be resized.
     NResize,
+
+    /// The top-right corner can be resized.
     NeResize,
+
+    /// The top-left corner can be resized.
     NwResize,
+
+    /// The bottom edge can be resized.
     SResize,
+
+    /// The bottom-right corner can be resized.
     SeResize,
+
+    /// The bottom-left corner can be resized.
     SwResize,
+
+    /// The left edge can be resized.
     WResize,
+
+    /// Something can be resized horizontally.
     EwResize,
+
+    /// Something can be resized vertically.
     NsResize,
+
+    /// Something can be resized diagonally, from top-right to bottom-left.
     NeswResize,
+
+    /// Something can be resized diagonally, from top-left to bottom-right.
     NwseResize,
+
+    /// A column can be resized horizontally.
     ColResize,
+
+    /// A row can be resized vertically.
     RowResize,
+
+    /// Something can be scrolled in any direction.
     AllScroll,
+
+    /// Something can be zoomed in.
     ZoomIn,
+
+    /// Something can be zoomed out.
     ZoomOut,
 }
 
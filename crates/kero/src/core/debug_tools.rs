@@ -0,0 +1,230 @@
+use crate::core::{BugReport, Context, recent_logs};
+use crate::img::ImageRgba8;
+use crate::misc::Tweaks;
+use crate::storage::{ZipWriter, write_atomic};
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Handle to the game's debugging tools.
+///
+/// This handle can be cloned and passed around freely to give objects access to the tools.
+#[derive(Clone)]
+pub struct DebugTools(Rc<DebugToolsState>);
+
+struct DebugToolsState {
+    report_dir: RefCell<PathBuf>,
+    pending: RefCell<Vec<(String, PathBuf)>>,
+    callbacks: RefCell<Vec<ReportFn>>,
+}
+
+type ReportFn = Box<dyn FnMut(&mut BugReport)>;
+
+impl Debug for DebugTools {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DebugTools").finish_non_exhaustive()
+    }
+}
+
+impl DebugTools {
+    pub(crate) fn new(report_dir: PathBuf) -> Self {
+        Self(Rc::new(DebugToolsState {
+            report_dir: RefCell::new(report_dir),
+            pending: RefCell::new(Vec::new()),
+            callbacks: RefCell::new(Vec::new()),
+        }))
+    }
+
+    /// The folder bug reports are saved in. Defaults to `bug_reports` in the
+    /// [data directory](Context::data_dir).
+    #[inline]
+    pub fn report_dir(&self) -> PathBuf {
+        self.0.report_dir.borrow().clone()
+    }
+
+    /// Set the folder bug reports are saved in.
+    #[inline]
+    pub fn set_report_dir(&self, dir: impl Into<PathBuf>) {
+        *self.0.report_dir.borrow_mut() = dir.into();
+    }
+
+    /// Add a function called for every bug report, to attach the game's own files to it, such
+    /// as a replay of the last few seconds or its settings.
+    ///
+    /// ```ignore
+    /// ctx.debug.on_report(move |report| {
+    ///     _ = report.attach_json("replay.json", &replay.borrow().recent(600));
+    ///     report.set_info("level", level_name.clone());
+    /// });
+    /// ```
+    pub fn on_report(&self, f: impl FnMut(&mut BugReport) + 'static) {
+        self.0.callbacks.borrow_mut().push(Box::new(f));
+    }
+
+    /// Capture a bug report with the player's description of the problem, returning the path
+    /// it will be saved to.
+    ///
+    /// At the end of the frame, a screenshot, the recent [log lines](crate::core::LogCapture),
+    /// system information, the [recent input](crate::input::InputRecorder::history) as a
+    /// replay, the current [tweaks](Tweaks), the settings files in the
+    /// [config folder](Context::config_dir), and any files attached by
+    /// [`on_report`](Self::on_report) callbacks are zipped up together and saved to the
+    /// [report folder](Self::report_dir), so testers can send in a single file.
+    pub fn capture_report(&self, description: impl Into<String>) -> PathBuf {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let dir = self.report_dir();
+        let mut pending = self.0.pending.borrow_mut();
+        let path = (0..)
+            .map(|n| match n {
+                0 => dir.join(format!("bug_report_{secs}.zip")),
+                n => dir.join(format!("bug_report_{secs}_{n}.zip")),
+            })
+            .find(|path| !path.exists() && pending.iter().all(|(_, p)| p != path))
+            .unwrap();
+        pending.push((description.into(), path.clone()));
+        path
+    }
+
+    /// If a bug report will be saved at the end of this frame.
+    #[inline]
+    pub fn is_capturing(&self) -> bool {
+        !self.0.pending.borrow().is_empty()
+    }
+
+    /// Save all bug reports captured this frame.
+    pub(crate) fn finish_reports(&self, ctx: &Context, screenshot: Option<ImageRgba8>) {
+        let pending = std::mem::take(&mut *self.0.pending.borrow_mut());
+        if pending.is_empty() {
+            return;
+        }
+
+        // encode the screenshot once for all the reports
+        let screenshot = screenshot.and_then(|img| {
+            let mut png = Vec::new();
+            img.save_png(&mut png).ok().map(|_| png)
+        });
+
+        for (description, path) in pending {
+            let mut report = BugReport::new(description);
+            self.gather(ctx, &mut report);
+            if let Some(png) = &screenshot {
+                report.attach("screenshot.png", png.clone());
+            }
+            for f in self.0.callbacks.borrow_mut().iter_mut() {
+                f(&mut report);
+            }
+            match save_report(&report, &path) {
+                Ok(()) => log::info!("saved bug report to {path:?}"),
+                Err(err) => log::error!("failed to save bug report to {path:?}: {err}"),
+            }
+        }
+    }
+
+    /// Fill a report with system information, logs, and settings.
+    fn gather(&self, ctx: &Context, report: &mut BugReport) {
+        let adapter = ctx.graphics.adapter_info();
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        report.set_info("created_at", created_at);
+        report.set_info("build", ctx.build.to_string());
+        report.set_info("version", ctx.build.version.as_str());
+        report.set_info("git_hash", ctx.build.git_hash.as_deref());
+        report.set_info("build_date", ctx.build.build_date.as_deref());
+        report.set_info("profile", ctx.build.profile.as_str());
+        report.set_info("kero_version", ctx.build.kero_version.as_str());
+        report.set_info("os", std::env::consts::OS);
+        report.set_info("arch", std::env::consts::ARCH);
+        report.set_info("gpu", adapter.name);
+        report.set_info("gpu_backend", adapter.backend.to_string());
+        report.set_info("gpu_driver", adapter.driver);
+        report.set_info("gpu_driver_info", adapter.driver_info);
+        report.set_info("window_size", format!("{:?}", ctx.window.size()));
+        report.set_info(
+            "window_pixel_size",
+            format!("{:?}", ctx.window.pixel_size()),
+        );
+        report.set_info("scale_factor", ctx.window.scale_factor());
+        report.set_info("display_mode", format!("{:?}", ctx.window.display_mode()));
+        report.set_info("refresh_rate", ctx.refresh_rate.get());
+        report.set_info("monitor", ctx.window.monitor().and_then(|m| m.name()));
+        report.set_info("fps", ctx.time.fps());
+        report.set_info("frame", ctx.time.frame());
+        report.set_info("since_startup", ctx.time.since_startup());
+        report.set_info("time_scale", ctx.time.time_scale());
+
+        let mut logs = recent_logs().join("\n");
+        logs.push('\n');
+        report.attach("log.txt", logs);
+
+        let recent = ctx.recorder.history();
+        if !recent.is_empty() {
+            report.attach("replay.kinp", recent.to_bytes());
+        }
+
+        let tweaks: serde_json::Map<_, _> = Tweaks::lock()
+            .iter()
+            .filter_map(|(name, tweak)| {
+                Some((name.to_string(), serde_json::to_value(tweak.value).ok()?))
+            })
+            .collect();
+        _ = report.attach_json("settings/tweaks.json", &tweaks);
+        _ = report.attach_json(
+            "settings/telemetry.json",
+            &serde_json::json!({ "enabled": ctx.telemetry.enabled() }),
+        );
+        attach_dir(
+            report,
+            ctx.config_dir(),
+            "settings/config",
+            &self.report_dir(),
+        );
+    }
+}
+
+/// Files larger than this are left out of reports, since settings files are small.
+const MAX_SETTINGS_FILE: u64 = 1 << 20;
+
+/// Attach every file in the folder and its subfolders under the name prefix, skipping the
+/// report folder in case it's inside, as it can be on macOS.
+fn attach_dir(report: &mut BugReport, dir: &Path, prefix: &str, report_dir: &Path) {
+    if dir == report_dir {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let Ok(kind) = entry.file_type() else {
+            continue;
+        };
+        let name = format!("{prefix}/{}", entry.file_name().to_string_lossy());
+        if kind.is_dir() {
+            attach_dir(report, &entry.path(), &name, report_dir);
+        } else if kind.is_file()
+            && entry.metadata().is_ok_and(|m| m.len() <= MAX_SETTINGS_FILE)
+            && let Ok(bytes) = std::fs::read(entry.path())
+        {
+            report.attach(name, bytes);
+        }
+    }
+}
+
+/// Zip up a report and save it.
+fn save_report(report: &BugReport, path: &Path) -> std::io::Result<()> {
+    let mut zip = ZipWriter::new();
+    zip.add_file("description.txt", report.description().as_bytes())?;
+    zip.add_file("info.json", &serde_json::to_vec_pretty(report.info())?)?;
+    for (name, bytes) in report.files() {
+        zip.add_file(name, bytes)?;
+    }
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    write_atomic(path, &zip.finish())
+}
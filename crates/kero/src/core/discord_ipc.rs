@@ -0,0 +1,231 @@
+use serde_json::{Value, json};
+use std::io::{self, Read, Write};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, RecvTimeoutError, Sender, channel};
+use std::time::{Duration, Instant};
+
+#[cfg(unix)]
+type Stream = std::os::unix::net::UnixStream;
+
+#[cfg(not(unix))]
+type Stream = std::fs::File;
+
+/// How long to wait between attempts to find a running Discord client.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How long to wait between activity updates. Discord only accepts a few updates every 20
+/// seconds and drops the rest.
+const SEND_INTERVAL: Duration = Duration::from_secs(4);
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+const OP_CLOSE: u32 = 2;
+const OP_PING: u32 = 3;
+const OP_PONG: u32 = 4;
+
+/// A connection to the local Discord client over its IPC socket.
+///
+/// The socket is only used from a background thread, since connecting, writing, and waiting on
+/// replies can all block while the client is busy or hung. The game sends it the latest
+/// activity over a channel, and it sends that on when it can.
+pub(crate) struct DiscordIpc {
+    activities: Sender<Option<Value>>,
+    connected: Arc<AtomicBool>,
+}
+
+impl DiscordIpc {
+    pub fn new(client_id: String) -> Self {
+        let (activities, received) = channel();
+        let connected = Arc::new(AtomicBool::new(false));
+        let worker = Worker {
+            client_id,
+            stream: None,
+            connected: connected.clone(),
+            nonce: 0,
+        };
+        std::thread::spawn(move || worker.run(received));
+        Self {
+            activities,
+            connected,
+        }
+    }
+
+    #[inline]
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Set or clear the player's activity, which is sent once connected and the previous
+    /// update is far enough in the past.
+    #[inline]
+    pub fn set_activity(&self, activity: Option<Value>) {
+        _ = self.activities.send(activity);
+    }
+}
+
+/// The background thread that owns the socket.
+struct Worker {
+    client_id: String,
+    stream: Option<Stream>,
+    connected: Arc<AtomicBool>,
+    nonce: u64,
+}
+
+impl Worker {
+    /// Keep connected and send activities until the game drops its [`DiscordIpc`].
+    fn run(mut self, activities: Receiver<Option<Value>>) {
+        let mut activity = None;
+        let mut changed = false;
+        let mut next_connect = Instant::now();
+        let mut next_send = Instant::now();
+        loop {
+            // sleep until a new activity arrives, or it is time to connect or send
+            let wake = match self.stream {
+                None => Some(next_connect),
+                Some(_) if changed => Some(next_send),
+                Some(_) => None,
+            };
+            let received = match wake {
+                Some(wake) => {
+                    activities.recv_timeout(wake.saturating_duration_since(Instant::now()))
+                }
+                None => activities
+                    .recv()
+                    .map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(latest) => {
+                    // only the newest activity matters
+                    activity = activities.try_iter().last().unwrap_or(latest);
+                    changed = true;
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+
+            let now = Instant::now();
+            if self.stream.is_none() && now >= next_connect {
+                next_connect = now + RECONNECT_INTERVAL;
+                if let Ok(stream) = self.connect() {
+                    log::info!("connected to discord");
+                    self.stream = Some(stream);
+                    self.connected.store(true, Ordering::Relaxed);
+
+                    // a fresh connection has no activity yet, so resend ours
+                    changed = activity.is_some();
+                }
+            }
+            if self.stream.is_some() && changed && now >= next_send {
+                next_send = now + SEND_INTERVAL;
+                match self.send_activity(activity.as_ref()) {
+                    Ok(()) => changed = false,
+                    Err(err) => {
+                        log::info!("disconnected from discord: {err}");
+                        self.stream = None;
+                        self.connected.store(false, Ordering::Relaxed);
+                        next_connect = now + RECONNECT_INTERVAL;
+                    }
+                }
+            }
+        }
+
+        if let Some(stream) = self.stream.as_mut() {
+            _ = write_frame(stream, OP_CLOSE, &json!({}));
+        }
+    }
+
+    /// Set or clear the player's activity.
+    fn send_activity(&mut self, activity: Option<&Value>) -> io::Result<()> {
+        self.nonce += 1;
+        let payload = json!({
+            "cmd": "SET_ACTIVITY",
+            "args": {
+                "pid": std::process::id(),
+                "activity": activity,
+            },
+            "nonce": self.nonce.to_string(),
+        });
+        let Some(stream) = self.stream.as_mut() else {
+            return Err(io::ErrorKind::NotConnected.into());
+        };
+        write_frame(stream, OP_FRAME, &payload)?;
+        let reply = read_reply(stream)?;
+        if reply.get("evt").and_then(Value::as_str) == Some("ERROR") {
+            log::warn!("discord rejected the activity: {}", reply["data"]);
+        }
+        Ok(())
+    }
+
+    /// Find the running client's socket and perform the handshake.
+    fn connect(&self) -> io::Result<Stream> {
+        let mut last_err = io::Error::from(io::ErrorKind::NotFound);
+        for n in 0..10 {
+            match open(n) {
+                Ok(mut stream) => {
+                    let handshake = json!({ "v": 1, "client_id": self.client_id });
+                    write_frame(&mut stream, OP_HANDSHAKE, &handshake)?;
+                    read_reply(&mut stream)?;
+                    return Ok(stream);
+                }
+                Err(err) => last_err = err,
+            }
+        }
+        Err(last_err)
+    }
+}
+
+#[cfg(unix)]
+fn open(n: u32) -> io::Result<Stream> {
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .unwrap_or_else(|| "/tmp".into());
+    let stream = Stream::connect(std::path::Path::new(&dir).join(format!("discord-ipc-{n}")))?;
+    stream.set_read_timeout(Some(Duration::from_secs(2)))?;
+    stream.set_write_timeout(Some(Duration::from_secs(2)))?;
+    Ok(stream)
+}
+
+#[cfg(not(unix))]
+fn open(n: u32) -> io::Result<Stream> {
+    std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .open(format!(r"\\.\pipe\discord-ipc-{n}"))
+}
+
+/// Write a frame: the opcode and payload length as little-endian `u32`s, then the JSON.
+fn write_frame(stream: &mut Stream, op: u32, payload: &Value) -> io::Result<()> {
+    let json = serde_json::to_vec(payload)?;
+    let mut frame = Vec::with_capacity(8 + json.len());
+    frame.extend_from_slice(&op.to_le_bytes());
+    frame.extend_from_slice(&(json.len() as u32).to_le_bytes());
+    frame.extend_from_slice(&json);
+    stream.write_all(&frame)?;
+    stream.flush()
+}
+
+/// Read frames until a reply arrives, answering pings along the way.
+fn read_reply(stream: &mut Stream) -> io::Result<Value> {
+    loop {
+        let mut header = [0u8; 8];
+        stream.read_exact(&mut header)?;
+        let op = u32::from_le_bytes(header[..4].try_into().unwrap());
+        let len = u32::from_le_bytes(header[4..].try_into().unwrap()) as usize;
+        let mut json = vec![0u8; len];
+        stream.read_exact(&mut json)?;
+        let payload: Value = serde_json::from_slice(&json)?;
+        match op {
+            OP_FRAME => return Ok(payload),
+            OP_PING => write_frame(stream, OP_PONG, &payload)?,
+            OP_CLOSE => {
+                return Err(io::Error::new(
+                    io::ErrorKind::ConnectionAborted,
+                    payload["message"].as_str().unwrap_or("closed").to_string(),
+                ));
+            }
+            _ => {}
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::core::{Monitor, TimeState};
+use crate::core::TimeState;
 use std::mem::replace;
 use std::rc::Rc;
 use std::time::{Duration, Instant};
@@ -29,12 +29,10 @@ impl FrameTimer {
         }
     }
 
-    pub fn tick<F: FnMut()>(&mut self, display: Option<Monitor>, mut update_fn: F) {
-        let refresh_rate = display
-            .and_then(|monitor| monitor.refresh_rate_mhz())
-            .unwrap_or(60000);
+    pub fn tick<F: FnMut()>(&mut self, refresh_rate: Option<u32>, mut update_fn: F) {
+        let refresh_rate = refresh_rate.unwrap_or(60000);
 
-        // get the target FPS, or the monitor refresh rate if none was provided
+        // get the target FPS, or the display refresh rate if none was provided
         let target_fps = self
             .time
             .target_fps
@@ -69,6 +67,14 @@ impl FrameTimer {
                 if max_frames > 0 {
                     max_frames -= 1;
 
+                    // while paused, only update if a step was requested
+                    if self.time.paused.get() {
+                        match self.time.steps.get() {
+                            0 => continue,
+                            steps => self.time.steps.set(steps - 1),
+                        }
+                    }
+
                     // how long since our last update?
                     let now = Instant::now();
                     if let Some(last_unfixed) = replace(&mut self.last_unfixed, Some(now)) {
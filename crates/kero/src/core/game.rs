@@ -14,6 +14,18 @@ pub trait Game: 'static {
     /// Called every update in order to perform game logic.
     fn update(&mut self, ctx: &Context) -> Result<(), GameError>;
 
+    /// Called at the fixed rate set by [`Time::set_fixed_rate`](crate::core::Time::set_fixed_rate),
+    /// before [`update`](Game::update), for logic like physics that should step by the same
+    /// amount regardless of frame rate. It may run zero or several times per update, so
+    /// input events like key presses should be read in `update` instead.
+    ///
+    /// Step by [`Time::fixed_delta`](crate::core::Time::fixed_delta) here, and draw with
+    /// [`Time::blend_factor`](crate::core::Time::blend_factor) to smooth out the difference.
+    #[inline]
+    fn fixed_update(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
     /// Called every frame refresh in order to perform game rendering.
     fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError>;
 }
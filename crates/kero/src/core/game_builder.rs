@@ -1,16 +1,39 @@
+use crate::audio::AudioOutput;
 use crate::core::app_handler::AppHandler;
-use crate::core::{Game, GameError};
+use crate::core::{BuildInfo, Game, GameError, LogCapture};
+use crate::input::InputRecording;
 use crate::math::Vec2U;
+use crate::misc::Tweaks;
+use crate::telemetry::TelemetrySink;
+use std::path::{Path, PathBuf};
 use winit::event_loop::EventLoop;
 
 /// A builder for a game.
 pub struct GameBuilder {
     pub title: String,
     pub size: Vec2U,
+    pub transparent: bool,
+    pub always_on_top: bool,
+    pub click_through: bool,
 
     pub app_organization: String,
     pub app_name: String,
 
+    pub gpu_timing: bool,
+    pub debug_keys: bool,
+    pub fixed_rate: f64,
+    pub audio_output: Option<Box<dyn AudioOutput>>,
+    pub telemetry_sink: Option<Box<dyn TelemetrySink>>,
+    pub input_recording: Option<PathBuf>,
+    pub input_playback: Option<InputRecording>,
+    pub build_info: BuildInfo,
+
+    #[cfg(feature = "discord")]
+    pub discord_client_id: Option<String>,
+
+    #[cfg(feature = "steam")]
+    pub steam_app_id: Option<u32>,
+
     #[cfg(feature = "lua")]
     pub lua: mlua::Lua,
 }
@@ -20,10 +43,28 @@ impl GameBuilder {
         let this = Self {
             title: "New Game".to_string(),
             size: (1280, 720).into(),
+            transparent: false,
+            always_on_top: false,
+            click_through: false,
 
             app_organization: String::new(),
             app_name: String::new(),
 
+            gpu_timing: false,
+            debug_keys: cfg!(debug_assertions),
+            fixed_rate: 60.0,
+            audio_output: None,
+            telemetry_sink: None,
+            input_recording: None,
+            input_playback: None,
+            build_info: BuildInfo::default(),
+
+            #[cfg(feature = "discord")]
+            discord_client_id: None,
+
+            #[cfg(feature = "steam")]
+            steam_app_id: None,
+
             #[cfg(feature = "lua")]
             lua: {
                 let lua = mlua::Lua::new();
@@ -43,6 +84,10 @@ impl GameBuilder {
                 .with_modules::<fey_math::MathModules>()?
                 .with_module::<fey_rand::RandModule>()?
                 .with_module::<AppModule>()?
+                .with_module::<AtlasDebugModule>()?
+                .with_module::<AudioModule>()?
+                .with_module::<AudioChannelModule>()?
+                .with_module::<Camera2DModule>()?
                 .with_module::<ColorModeModule>()?
                 .with_module::<DrawModule>()?
                 .with_module::<FontModule>()?
@@ -52,18 +97,27 @@ impl GameBuilder {
                 .with_module::<GamepadAxisModule>()?
                 .with_module::<KeyModule>()?
                 .with_module::<KeyboardModule>()?
+                .with_module::<MaterialModule>()?
                 .with_module::<MonitorModule>()?
                 .with_module::<MouseModule>()?
+                .with_module::<ParticlesModule>()?
+                .with_module::<PlatformModule>()?
                 .with_module::<SamplerModule>()?
                 .with_module::<ScreenModule>()?
                 .with_module::<ShaderModule>()?
+                .with_module::<SoundModule>()?
+                .with_module::<SoundBankModule>()?
                 .with_module::<SubTextureModule>()?
                 .with_module::<SurfaceModule>()?
+                .with_module::<TelemetryModule>()?
                 .with_module::<TextureModule>()?
                 .with_module::<TimeModule>()?
+                .with_module::<TouchModule>()?
+                .with_module::<TweakModule>()?
                 .with_module::<VertexBufferModule>()?
                 .with_module::<VertexModule>()?
                 .with_module::<VideoModeModule>()?
+                .with_module::<VirtualControllerModule>()?
                 .with_module::<WindowModule>()?
         };
 
@@ -72,9 +126,14 @@ impl GameBuilder {
 
     /// Run the game with a default [log](https://crates.io/crates/log) implementation initialized.
     /// If you want to bring your own logger, you can omit this call and initialize it before
-    /// calling `run_game()`.
+    /// calling `run_game()`. Wrap it in a [`LogCapture`] to include its output in bug reports.
     pub fn with_default_logger(self) -> Self {
-        env_logger::init();
+        let logger = env_logger::Builder::from_default_env().build();
+        let level = logger.filter();
+        LogCapture::new(logger)
+            .with_inner_level(level)
+            .install()
+            .expect("failed to install logger");
         self
     }
 
@@ -94,6 +153,33 @@ impl GameBuilder {
         }
     }
 
+    /// Make the window transparent, so it is composited with what is behind it using the alpha
+    /// of each pixel. Clear the screen with a transparent color to see through it. Whether this
+    /// worked can be checked with [`Graphics::transparent`](crate::gfx::Graphics::transparent).
+    pub fn with_transparent(self, transparent: bool) -> Self {
+        Self {
+            transparent,
+            ..self
+        }
+    }
+
+    /// Keep the window above all other windows.
+    pub fn with_always_on_top(self, always_on_top: bool) -> Self {
+        Self {
+            always_on_top,
+            ..self
+        }
+    }
+
+    /// Let mouse input pass through the window to whatever is behind it, where the platform
+    /// supports it. Useful for overlays when combined with transparency and always-on-top.
+    pub fn with_click_through(self, click_through: bool) -> Self {
+        Self {
+            click_through,
+            ..self
+        }
+    }
+
     /// Set the app information used to determine system directories.
     pub fn with_app_info(self, organization: &str, name: &str) -> Self {
         Self {
@@ -103,6 +189,109 @@ impl GameBuilder {
         }
     }
 
+    /// Persist [`Tweaks`](crate::misc::Tweaks) to a JSON file, loading any values it already has.
+    /// Changed values are saved to it at the end of each frame.
+    pub fn with_tweaks_file(self, path: impl Into<PathBuf>) -> Result<Self, GameError> {
+        Tweaks::lock().set_file(path)?;
+        Ok(self)
+    }
+
+    /// Time each render pass on the GPU, if the device supports timestamp queries. The timings
+    /// are available from [`Graphics::frame_stats`](crate::gfx::Graphics::frame_stats).
+    pub fn with_gpu_timing(self, enabled: bool) -> Self {
+        Self {
+            gpu_timing: enabled,
+            ..self
+        }
+    }
+
+    /// Play audio through the output. Without one, audio plays through the system's default
+    /// device with the `cpal` feature, or silently if it's turned off or there is no device.
+    pub fn with_audio_output(self, output: impl AudioOutput) -> Self {
+        Self {
+            audio_output: Some(Box::new(output)),
+            ..self
+        }
+    }
+
+    /// Send [telemetry](crate::telemetry::Telemetry) events to the sink, once the player has
+    /// opted in. Without one, no events are recorded.
+    pub fn with_telemetry(self, sink: impl TelemetrySink) -> Self {
+        Self {
+            telemetry_sink: Some(Box::new(sink)),
+            ..self
+        }
+    }
+
+    /// Set the game's build information, shown in bug reports and the tweaks overlay. Pass it
+    /// [`build_info!`](crate::build_info) to fill it in at compile time.
+    pub fn with_build_info(self, build_info: BuildInfo) -> Self {
+        Self { build_info, ..self }
+    }
+
+    /// Record all input from when the game starts, saving it to the file when the game exits.
+    /// See [`InputRecorder`](crate::input::InputRecorder).
+    pub fn with_input_recording(self, path: impl Into<PathBuf>) -> Self {
+        Self {
+            input_recording: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Play back an input recording from when the game starts, quitting once it finishes. This
+    /// is useful for automated regression tests. See
+    /// [`InputRecorder`](crate::input::InputRecorder).
+    pub fn with_input_playback(self, path: impl AsRef<Path>) -> Result<Self, GameError> {
+        Ok(Self {
+            input_playback: Some(InputRecording::load(path)?),
+            ..self
+        })
+    }
+
+    /// Show the player's [presence](crate::core::Platform::set_presence) on their Discord
+    /// profile, using the application ID from the Discord developer portal.
+    #[cfg(feature = "discord")]
+    pub fn with_discord(self, client_id: impl Into<String>) -> Self {
+        Self {
+            discord_client_id: Some(client_id.into()),
+            ..self
+        }
+    }
+
+    /// Start [Steam](crate::steam::Steam) when the game starts, if it is running. During
+    /// development, put a `steam_appid.txt` file holding the app ID next to the executable.
+    ///
+    /// Release builds should call
+    /// [`Steam::restart_app_if_necessary`](crate::steam::Steam::restart_app_if_necessary)
+    /// first, so the game is relaunched through Steam if the player started it directly.
+    #[cfg(feature = "steam")]
+    pub fn with_steam(self, app_id: u32) -> Self {
+        Self {
+            steam_app_id: Some(app_id),
+            ..self
+        }
+    }
+
+    /// Enable the debug keys: `F10` pauses and resumes updates, and `F11` steps a single update
+    /// while paused. Enabled by default in debug builds.
+    ///
+    /// See [`Time::paused`](crate::core::Time::paused).
+    pub fn with_debug_keys(self, enabled: bool) -> Self {
+        Self {
+            debug_keys: enabled,
+            ..self
+        }
+    }
+
+    /// Call [`Game::fixed_update`](crate::core::Game::fixed_update) this many times per
+    /// second. Defaults to `60`.
+    pub fn with_fixed_rate(self, rate: f64) -> Self {
+        Self {
+            fixed_rate: rate,
+            ..self
+        }
+    }
+
     #[cfg(feature = "lua")]
     pub fn with_module<M: crate::lua::LuaModule>(self) -> Result<Self, GameError> {
         let module = M::load(&self.lua)?;
@@ -1,6 +1,9 @@
+use crate::audio::AudioError;
 use crate::gfx::{DrawError, IndexBufferUploadError, VertexBufferUploadError};
 use crate::guid::GuidParseError;
 use crate::img::ImageError;
+use crate::input::InputRecordingError;
+use crate::misc::TweakError;
 use std::error::Error;
 use winit::error::EventLoopError;
 
@@ -37,6 +40,15 @@ pub enum GameError {
     #[error("{0}")]
     Font(#[from] fey_font::FontError),
 
+    #[error("{0}")]
+    Tweak(#[from] TweakError),
+
+    #[error("{0}")]
+    Audio(#[from] AudioError),
+
+    #[error("{0}")]
+    InputRecording(#[from] InputRecordingError),
+
     #[cfg(feature = "lua")]
     #[error("{0}")]
     Lua(#[from] mlua::prelude::LuaError),
@@ -0,0 +1,92 @@
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// How many recent log lines are kept for bug reports.
+const MAX_RECENT_LOGS: usize = 1000;
+
+static RECENT_LOGS: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+/// A logger that keeps the most recent log lines in memory, so they can be included in
+/// [bug reports](crate::core::DebugTools::capture_report), and passes every record on to
+/// another logger.
+///
+/// [`GameBuilder::with_default_logger`](crate::core::GameBuilder::with_default_logger) installs
+/// one of these automatically. To bring your own logger, wrap it in one before installing it.
+///
+/// ```ignore
+/// LogCapture::new(my_logger).install()?;
+/// ```
+#[derive(Debug)]
+pub struct LogCapture<L> {
+    inner: L,
+    inner_level: LevelFilter,
+    level: LevelFilter,
+}
+
+impl<L: Log + 'static> LogCapture<L> {
+    /// Capture log lines at [`Info`](LevelFilter::Info) and above, passing every record on to
+    /// the logger.
+    #[inline]
+    pub fn new(inner: L) -> Self {
+        Self {
+            inner,
+            inner_level: LevelFilter::Trace,
+            level: LevelFilter::Info,
+        }
+    }
+
+    /// Return the capture keeping log lines at a different level and above, whether or not the
+    /// inner logger shows them.
+    #[inline]
+    pub fn with_level(mut self, level: LevelFilter) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// Return the capture only passing records at the level and above on to the inner logger.
+    #[inline]
+    pub fn with_inner_level(mut self, level: LevelFilter) -> Self {
+        self.inner_level = level;
+        self
+    }
+
+    /// Install the capture as the global logger.
+    pub fn install(self) -> Result<(), SetLoggerError> {
+        log::set_max_level(self.level.max(self.inner_level));
+        log::set_boxed_logger(Box::new(self))
+    }
+}
+
+impl<L: Log> Log for LogCapture<L> {
+    #[inline]
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+            || (metadata.level() <= self.inner_level && self.inner.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record) {
+        if record.level() <= self.level {
+            let line = format!("[{} {}] {}", record.level(), record.target(), record.args());
+            let mut logs = RECENT_LOGS.lock().unwrap_or_else(|err| err.into_inner());
+            if logs.len() >= MAX_RECENT_LOGS {
+                logs.pop_front();
+            }
+            logs.push_back(line);
+        }
+        if record.level() <= self.inner_level {
+            self.inner.log(record);
+        }
+    }
+
+    #[inline]
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// The most recent log lines captured by [`LogCapture`], oldest first.
+pub fn recent_logs() -> Vec<String> {
+    let logs = RECENT_LOGS.lock().unwrap_or_else(|err| err.into_inner());
+    logs.iter().cloned().collect()
+}
@@ -128,6 +128,14 @@ impl LuaApp {
         }
     }
 
+    pub fn fixed_update(&mut self, _ctx: &Context) {
+        // call Main:fixed_update(), if there is one
+        if let Ok(Err(err)) = self.main.as_ref().map(|main| main.fixed_update()) {
+            println!("{err}");
+            self.main = Err(err);
+        }
+    }
+
     pub fn render(&mut self, _ctx: &Context, draw: &mut Draw) {
         // call Main:render()
         if let Ok(Err(err)) = self.main.as_ref().map(|main| main.render(&self.lua, draw)) {
@@ -144,6 +152,7 @@ pub struct LuaMain {
     module: Table,
     init_fn: Function,
     update_fn: Function,
+    fixed_update_fn: Option<Function>,
     render_fn: Function,
 }
 
@@ -183,6 +192,7 @@ impl LuaMain {
             .call::<Table>("Main")?;
         let init_fn = module.get("init")?;
         let update_fn = module.get("update")?;
+        let fixed_update_fn = module.get("fixed_update")?;
         let render_fn = module.get("render")?;
 
         // run the garbage collector
@@ -193,6 +203,7 @@ impl LuaMain {
             module,
             init_fn,
             update_fn,
+            fixed_update_fn,
             render_fn,
         })
     }
@@ -207,6 +218,14 @@ impl LuaMain {
         self.update_fn.call(self.module.clone())
     }
 
+    #[inline]
+    fn fixed_update(&self) -> LuaResult<()> {
+        match &self.fixed_update_fn {
+            Some(f) => f.call(self.module.clone()),
+            None => Ok(()),
+        }
+    }
+
     #[inline]
     fn render(&self, lua: &Lua, draw: &mut Draw) -> LuaResult<()> {
         let draw: *mut Draw = draw;
@@ -1,28 +1,53 @@
 //! The framework's core systems.
 
 mod app_handler;
+mod bug_report;
+mod build_info;
 mod context;
 mod cursor_icon;
+mod debug_tools;
 mod display_mode;
 mod frame_timer;
 mod game;
 mod game_builder;
 mod game_error;
+mod log_capture;
 mod monitor;
+mod platform;
+mod presence;
+mod scene;
+mod scene_stack;
+mod scene_transition;
+mod scheduler;
+mod screen_effects;
 mod time;
 mod video_mode;
 mod window;
 
+#[cfg(feature = "discord")]
+mod discord_ipc;
+
 #[cfg(feature = "lua")]
 mod lua_app;
 
+pub use bug_report::*;
+pub use build_info::*;
 pub use context::*;
 pub use cursor_icon::*;
+pub use debug_tools::*;
 pub use display_mode::*;
 pub use game::*;
 pub use game_builder::*;
 pub use game_error::*;
+pub use log_capture::*;
 pub use monitor::*;
+pub use platform::*;
+pub use presence::*;
+pub use scene::*;
+pub use scene_stack::*;
+pub use scene_transition::*;
+pub use scheduler::*;
+pub use screen_effects::*;
 pub use time::*;
 pub use video_mode::*;
 pub use window::*;
@@ -0,0 +1,157 @@
+use crate::core::{Presence, PresenceAssets, PresenceTimestamps};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+#[cfg(feature = "discord")]
+use crate::core::discord_ipc::DiscordIpc;
+
+#[cfg(feature = "steam")]
+use crate::steam::Steam;
+
+/// Handle to the game's platform integrations, such as Steam or showing what the player is
+/// doing on their Discord profile.
+///
+/// Every integration is optional: when one is not enabled, or the platform is not running on
+/// the player's computer, its functions do nothing, so they can be called unconditionally.
+///
+/// This handle can be cloned and passed around freely to give objects access to the platform.
+#[derive(Clone)]
+pub struct Platform(Rc<PlatformState>);
+
+struct PlatformState {
+    presence: RefCell<Option<Presence>>,
+    presence_changed: Cell<bool>,
+
+    #[cfg(feature = "discord")]
+    discord: Option<DiscordIpc>,
+
+    #[cfg(feature = "steam")]
+    steam: Option<Steam>,
+}
+
+impl Debug for Platform {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Platform").finish_non_exhaustive()
+    }
+}
+
+impl Platform {
+    pub(crate) fn new(
+        #[allow(unused_variables)] discord_client_id: Option<String>,
+        #[allow(unused_variables)] steam_app_id: Option<u32>,
+    ) -> Self {
+        Self(Rc::new(PlatformState {
+            presence: RefCell::new(None),
+            presence_changed: Cell::new(false),
+
+            #[cfg(feature = "discord")]
+            discord: discord_client_id.map(DiscordIpc::new),
+
+            #[cfg(feature = "steam")]
+            steam: steam_app_id.and_then(|app_id| match Steam::init() {
+                Ok(steam) if steam.app_id() != app_id => {
+                    log::warn!("steam is running app {}, not {app_id}", steam.app_id());
+                    Some(steam)
+                }
+                Ok(steam) => Some(steam),
+                Err(err) => {
+                    log::warn!("{err}");
+                    None
+                }
+            }),
+        }))
+    }
+
+    /// Steam, if the game was started with
+    /// [`GameBuilder::with_steam`](crate::core::GameBuilder::with_steam) and Steam is running.
+    #[cfg(feature = "steam")]
+    #[inline]
+    pub fn steam(&self) -> Option<&Steam> {
+        self.0.steam.as_ref()
+    }
+
+    /// Show what the player is doing on their profile, such as `"In Level 3"`.
+    ///
+    /// Discord shows `details` on the first line and `state` on the second. Updates are sent
+    /// at most every few seconds from a background thread, so calling this every frame only
+    /// sends the latest one.
+    ///
+    /// Requires the `discord` feature and a client ID given to
+    /// [`GameBuilder::with_discord`](crate::core::GameBuilder::with_discord).
+    ///
+    /// ```ignore
+    /// ctx.platform.set_presence(
+    ///     "In Level 3",
+    ///     "Speedrun mode",
+    ///     PresenceTimestamps::elapsed(),
+    ///     PresenceAssets::none().with_large("forest", "The Forest"),
+    /// );
+    /// ```
+    pub fn set_presence(
+        &self,
+        state: impl Into<String>,
+        details: impl Into<String>,
+        timestamps: PresenceTimestamps,
+        assets: PresenceAssets,
+    ) {
+        let presence = Some(Presence {
+            state: state.into(),
+            details: details.into(),
+            timestamps,
+            assets,
+        });
+        let mut current = self.0.presence.borrow_mut();
+        if *current != presence {
+            *current = presence;
+            self.0.presence_changed.set(true);
+        }
+    }
+
+    /// Stop showing what the player is doing.
+    pub fn clear_presence(&self) {
+        if self.0.presence.borrow_mut().take().is_some() {
+            self.0.presence_changed.set(true);
+        }
+    }
+
+    /// If a platform to show the player's presence on is connected.
+    #[inline]
+    pub fn is_presence_connected(&self) -> bool {
+        #[cfg(feature = "discord")]
+        if let Some(discord) = &self.0.discord {
+            return discord.is_connected();
+        }
+        false
+    }
+
+    /// Update platforms and send changes to them.
+    pub(crate) fn update(&self) {
+        #[cfg(feature = "steam")]
+        if let Some(steam) = &self.0.steam {
+            steam.update();
+        }
+
+        #[cfg(feature = "discord")]
+        if let Some(discord) = self.0.discord.as_ref()
+            && self.0.presence_changed.replace(false)
+        {
+            let activity = self
+                .0
+                .presence
+                .borrow()
+                .as_ref()
+                .and_then(|presence| serde_json::to_value(presence).ok());
+            discord.set_activity(activity);
+        }
+    }
+
+    /// Shut down platforms that need it. Called when the game exits.
+    pub(crate) fn shutdown(&self) {
+        #[cfg(feature = "steam")]
+        if let Some(steam) = &self.0.steam {
+            steam.shutdown();
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// The times shown with a player's [presence](crate::core::Platform::set_presence), as a
+/// timer counting up from the start or down to the end.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize)]
+pub struct PresenceTimestamps {
+    /// When the activity started, in seconds since the Unix epoch. Shown as time elapsed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+
+    /// When the activity ends, in seconds since the Unix epoch. Shown as time remaining.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<u64>,
+}
+
+impl PresenceTimestamps {
+    /// No timer.
+    #[inline]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// A timer counting up from now.
+    #[inline]
+    pub fn elapsed() -> Self {
+        Self {
+            start: Some(unix_secs(SystemTime::now())),
+            end: None,
+        }
+    }
+
+    /// A timer counting up from a time, such as when the level started.
+    #[inline]
+    pub fn elapsed_since(start: SystemTime) -> Self {
+        Self {
+            start: Some(unix_secs(start)),
+            end: None,
+        }
+    }
+
+    /// A timer counting down to `secs` seconds from now, such as for a timed round.
+    #[inline]
+    pub fn remaining(secs: f32) -> Self {
+        let end = SystemTime::now() + Duration::from_secs_f32(secs.max(0.0));
+        Self {
+            start: None,
+            end: Some(unix_secs(end)),
+        }
+    }
+
+    #[inline]
+    fn is_none(&self) -> bool {
+        self.start.is_none() && self.end.is_none()
+    }
+}
+
+/// The images shown with a player's [presence](crate::core::Platform::set_presence). Images
+/// are referred to by the names they were uploaded with to the platform, such as in the
+/// Discord developer portal.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct PresenceAssets {
+    /// The name of the large image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large_image: Option<String>,
+
+    /// The text shown when hovering the large image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub large_text: Option<String>,
+
+    /// The name of the small image, shown in the corner of the large one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small_image: Option<String>,
+
+    /// The text shown when hovering the small image.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub small_text: Option<String>,
+}
+
+impl PresenceAssets {
+    /// No images.
+    #[inline]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Return the assets with a large image and its hover text.
+    #[inline]
+    pub fn with_large(mut self, image: impl Into<String>, text: impl Into<String>) -> Self {
+        self.large_image = Some(image.into());
+        self.large_text = Some(text.into());
+        self
+    }
+
+    /// Return the assets with a small image and its hover text.
+    #[inline]
+    pub fn with_small(mut self, image: impl Into<String>, text: impl Into<String>) -> Self {
+        self.small_image = Some(image.into());
+        self.small_text = Some(text.into());
+        self
+    }
+
+    #[inline]
+    fn is_none(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// A player's status, in the layout platforms expect.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub(crate) struct Presence {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub state: String,
+
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub details: String,
+
+    #[serde(skip_serializing_if = "PresenceTimestamps::is_none")]
+    pub timestamps: PresenceTimestamps,
+
+    #[serde(skip_serializing_if = "PresenceAssets::is_none")]
+    pub assets: PresenceAssets,
+}
+
+#[inline]
+fn unix_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs())
+}
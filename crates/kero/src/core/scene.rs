@@ -0,0 +1,138 @@
+use crate::core::{Context, GameError, SceneTransition};
+use crate::gfx::Draw;
+use std::fmt::{Debug, Formatter};
+
+/// A screen of the game, such as the title menu, gameplay, or a pause menu, managed by a
+/// [`SceneStack`](crate::core::SceneStack).
+///
+/// Only the scene on top of the stack is updated. Scenes change the stack by returning a
+/// [`SceneAction`] from [`update`](Scene::update).
+pub trait Scene: 'static {
+    /// Called when the scene is added to the stack.
+    #[inline]
+    fn on_enter(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when the scene is removed from the stack.
+    #[inline]
+    fn on_exit(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when another scene is pushed on top of this one.
+    #[inline]
+    fn on_pause(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// Called when this scene is on top again after the scene above it was popped.
+    #[inline]
+    fn on_resume(&mut self, _ctx: &Context) -> Result<(), GameError> {
+        Ok(())
+    }
+
+    /// If the scenes below this one should be drawn before it, such as for a pause menu
+    /// shown over gameplay.
+    #[inline]
+    fn is_overlay(&self) -> bool {
+        false
+    }
+
+    /// Called every update while the scene is on top of the stack.
+    fn update(&mut self, ctx: &Context) -> Result<SceneAction, GameError>;
+
+    /// Called every frame while the scene is visible.
+    fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError>;
+}
+
+/// A change to make to a [`SceneStack`](crate::core::SceneStack).
+pub(crate) enum SceneChange {
+    Push(Box<dyn Scene>),
+    Pop,
+    Replace(Box<dyn Scene>),
+    Set(Box<dyn Scene>),
+}
+
+/// What a [`Scene`] wants to happen to the stack after it updates.
+///
+/// ```ignore
+/// if ctx.keyboard.pressed(Key::Escape) {
+///     return Ok(SceneAction::push(PauseMenu::new()).with_transition(SceneTransition::CUT));
+/// }
+/// Ok(SceneAction::none())
+/// ```
+#[derive(Default)]
+pub struct SceneAction {
+    pub(crate) change: Option<SceneChange>,
+    pub(crate) transition: Option<SceneTransition>,
+}
+
+impl Debug for SceneAction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let change = match &self.change {
+            None => "None",
+            Some(SceneChange::Push(_)) => "Push",
+            Some(SceneChange::Pop) => "Pop",
+            Some(SceneChange::Replace(_)) => "Replace",
+            Some(SceneChange::Set(_)) => "Set",
+        };
+        f.debug_struct("SceneAction")
+            .field("change", &change)
+            .field("transition", &self.transition)
+            .finish()
+    }
+}
+
+impl SceneAction {
+    /// Leave the stack as it is.
+    #[inline]
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Push a scene on top of the stack.
+    #[inline]
+    pub fn push(scene: impl Scene) -> Self {
+        Self::change(SceneChange::Push(Box::new(scene)))
+    }
+
+    /// Pop the scene on top of the stack.
+    #[inline]
+    pub fn pop() -> Self {
+        Self::change(SceneChange::Pop)
+    }
+
+    /// Replace the scene on top of the stack.
+    #[inline]
+    pub fn replace(scene: impl Scene) -> Self {
+        Self::change(SceneChange::Replace(Box::new(scene)))
+    }
+
+    /// Remove every scene and push this one, such as when quitting to the title screen.
+    #[inline]
+    pub fn set(scene: impl Scene) -> Self {
+        Self::change(SceneChange::Set(Box::new(scene)))
+    }
+
+    #[inline]
+    fn change(change: SceneChange) -> Self {
+        Self {
+            change: Some(change),
+            transition: None,
+        }
+    }
+
+    /// Return the action with a transition other than the stack's default.
+    #[inline]
+    pub fn with_transition(mut self, transition: SceneTransition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+
+    /// If the action leaves the stack as it is.
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        self.change.is_none()
+    }
+}
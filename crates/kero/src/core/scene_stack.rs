@@ -0,0 +1,250 @@
+use crate::core::{Context, GameError, Scene, SceneAction, SceneChange, SceneTransition};
+use crate::gfx::Draw;
+use crate::math::{Numeric, RectF};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+
+/// A change in progress, waiting for the screen to be covered before it's made.
+struct ActiveTransition {
+    transition: SceneTransition,
+    change: Option<SceneChange>,
+    time: f32,
+}
+
+/// A stack of [scenes](Scene), so menus, gameplay, and pause screens can be pushed on top of
+/// each other and popped off again, with a transition between them.
+///
+/// Keep one in your game and forward its update and render to the stack.
+///
+/// ```ignore
+/// fn new(ctx: &Context, _: ()) -> Result<Self, GameError> {
+///     let mut scenes = SceneStack::new().with_transition(SceneTransition::fade(0.5));
+///     scenes.push(TitleMenu::new(ctx)?);
+///     Ok(Self { scenes })
+/// }
+///
+/// fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+///     self.scenes.update(ctx)
+/// }
+///
+/// fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+///     self.scenes.render(ctx, draw)
+/// }
+/// ```
+#[derive(Default)]
+pub struct SceneStack {
+    scenes: Vec<Box<dyn Scene>>,
+    default_transition: SceneTransition,
+    queue: VecDeque<SceneAction>,
+    active: Option<ActiveTransition>,
+}
+
+impl Debug for SceneStack {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SceneStack")
+            .field("len", &self.scenes.len())
+            .field("default_transition", &self.default_transition)
+            .field("queue", &self.queue)
+            .field("transitioning", &self.active.is_some())
+            .finish()
+    }
+}
+
+impl SceneStack {
+    /// Create an empty stack.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the stack with a transition used by changes that don't specify their own.
+    #[inline]
+    pub fn with_transition(mut self, transition: SceneTransition) -> Self {
+        self.default_transition = transition;
+        self
+    }
+
+    /// The transition used by changes that don't specify their own.
+    #[inline]
+    pub fn default_transition(&self) -> SceneTransition {
+        self.default_transition
+    }
+
+    /// Set the transition used by changes that don't specify their own.
+    #[inline]
+    pub fn set_default_transition(&mut self, transition: SceneTransition) {
+        self.default_transition = transition;
+    }
+
+    /// How many scenes are on the stack.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    /// If there are no scenes on the stack.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    /// If a transition is playing. Scenes aren't updated while one is.
+    #[inline]
+    pub fn is_transitioning(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// The scene on top of the stack.
+    #[inline]
+    pub fn top(&self) -> Option<&dyn Scene> {
+        self.scenes.last().map(|s| s.as_ref())
+    }
+
+    /// The scene on top of the stack.
+    #[inline]
+    pub fn top_mut(&mut self) -> Option<&mut dyn Scene> {
+        self.scenes.last_mut().map(|s| s.as_mut())
+    }
+
+    /// Queue an action, made at the start of the next update, after any before it.
+    #[inline]
+    pub fn queue(&mut self, action: SceneAction) {
+        if !action.is_none() {
+            self.queue.push_back(action);
+        }
+    }
+
+    /// Push a scene on top of the stack at the start of the next update.
+    #[inline]
+    pub fn push(&mut self, scene: impl Scene) {
+        self.queue(SceneAction::push(scene));
+    }
+
+    /// Pop the scene on top of the stack at the start of the next update.
+    #[inline]
+    pub fn pop(&mut self) {
+        self.queue(SceneAction::pop());
+    }
+
+    /// Replace the scene on top of the stack at the start of the next update.
+    #[inline]
+    pub fn replace(&mut self, scene: impl Scene) {
+        self.queue(SceneAction::replace(scene));
+    }
+
+    /// Remove every scene and push this one at the start of the next update.
+    #[inline]
+    pub fn set(&mut self, scene: impl Scene) {
+        self.queue(SceneAction::set(scene));
+    }
+
+    /// Play any transition, make queued changes, and update the scene on top of the stack.
+    pub fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+        if !self.advance(ctx)? {
+            return Ok(());
+        }
+        if let Some(top) = self.scenes.last_mut() {
+            let action = top.update(ctx)?;
+            self.queue(action);
+            self.advance(ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Move the transition along and start the next queued change when there's none playing.
+    /// Returns `false` if a transition is still playing.
+    fn advance(&mut self, ctx: &Context) -> Result<bool, GameError> {
+        if let Some(active) = &mut self.active {
+            active.time += ctx.time.unscaled_delta();
+            let change = match active.time >= active.transition.duration * 0.5 {
+                true => active.change.take(),
+                false => None,
+            };
+            let done = active.time >= active.transition.duration;
+            if let Some(change) = change {
+                self.apply(ctx, change)?;
+            }
+            if !done {
+                return Ok(false);
+            }
+            self.active = None;
+        }
+
+        while let Some(action) = self.queue.pop_front() {
+            let Some(change) = action.change else {
+                continue;
+            };
+            let transition = action.transition.unwrap_or(self.default_transition);
+            if transition.is_cut() {
+                self.apply(ctx, change)?;
+            } else {
+                self.active = Some(ActiveTransition {
+                    transition,
+                    change: Some(change),
+                    time: 0.0,
+                });
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    fn apply(&mut self, ctx: &Context, change: SceneChange) -> Result<(), GameError> {
+        match change {
+            SceneChange::Push(mut scene) => {
+                if let Some(top) = self.scenes.last_mut() {
+                    top.on_pause(ctx)?;
+                }
+                scene.on_enter(ctx)?;
+                self.scenes.push(scene);
+            }
+            SceneChange::Pop => {
+                if let Some(mut scene) = self.scenes.pop() {
+                    scene.on_exit(ctx)?;
+                }
+                if let Some(top) = self.scenes.last_mut() {
+                    top.on_resume(ctx)?;
+                }
+            }
+            SceneChange::Replace(mut scene) => {
+                if let Some(mut old) = self.scenes.pop() {
+                    old.on_exit(ctx)?;
+                }
+                scene.on_enter(ctx)?;
+                self.scenes.push(scene);
+            }
+            SceneChange::Set(mut scene) => {
+                while let Some(mut old) = self.scenes.pop() {
+                    old.on_exit(ctx)?;
+                }
+                scene.on_enter(ctx)?;
+                self.scenes.push(scene);
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the visible scenes, from the highest one that isn't an
+    /// [overlay](Scene::is_overlay) up, and then any transition over them.
+    pub fn render(&mut self, ctx: &Context, draw: &mut Draw) -> Result<(), GameError> {
+        let start = self
+            .scenes
+            .iter()
+            .rposition(|s| !s.is_overlay())
+            .unwrap_or(0);
+        for scene in &mut self.scenes[start..] {
+            scene.render(ctx, draw)?;
+        }
+
+        if let Some(active) = &self.active {
+            let size = match draw.surface() {
+                Some(surface) => surface.size(),
+                None => ctx.window.size(),
+            };
+            let t = active.time / active.transition.duration;
+            let area = RectF::pos_size(Default::default(), size.to_f32());
+            active.transition.draw(draw, area, t);
+        }
+        Ok(())
+    }
+}
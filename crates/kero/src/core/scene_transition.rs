@@ -0,0 +1,111 @@
+use crate::color::Rgba8;
+use crate::gfx::Draw;
+use crate::math::{Affine2F, Cardinal, RectF};
+
+/// How a [`SceneTransition`] covers the screen.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TransitionEffect {
+    /// Switch scenes instantly.
+    Cut,
+
+    /// Fade to the color and back.
+    Fade(Rgba8),
+
+    /// Sweep the color across the screen in the direction, switching scenes once it's
+    /// covered.
+    Wipe(Cardinal, Rgba8),
+}
+
+/// How a [`SceneStack`](crate::core::SceneStack) changes between scenes. The screen is covered
+/// over the first half of the transition, the scenes are switched, and then it is uncovered.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct SceneTransition {
+    /// How the screen is covered.
+    pub effect: TransitionEffect,
+
+    /// How long the whole transition takes, in seconds.
+    pub duration: f32,
+}
+
+impl Default for SceneTransition {
+    #[inline]
+    fn default() -> Self {
+        Self::CUT
+    }
+}
+
+impl SceneTransition {
+    /// Switch scenes instantly.
+    pub const CUT: Self = Self {
+        effect: TransitionEffect::Cut,
+        duration: 0.0,
+    };
+
+    /// Fade to black and back.
+    #[inline]
+    pub const fn fade(duration: f32) -> Self {
+        Self {
+            effect: TransitionEffect::Fade(Rgba8::BLACK),
+            duration,
+        }
+    }
+
+    /// Sweep black across the screen in the direction.
+    #[inline]
+    pub const fn wipe(direction: Cardinal, duration: f32) -> Self {
+        Self {
+            effect: TransitionEffect::Wipe(direction, Rgba8::BLACK),
+            duration,
+        }
+    }
+
+    /// Return the transition with a color other than black.
+    #[inline]
+    pub const fn with_color(mut self, color: Rgba8) -> Self {
+        self.effect = match self.effect {
+            TransitionEffect::Cut => TransitionEffect::Cut,
+            TransitionEffect::Fade(_) => TransitionEffect::Fade(color),
+            TransitionEffect::Wipe(dir, _) => TransitionEffect::Wipe(dir, color),
+        };
+        self
+    }
+
+    /// If the transition switches scenes instantly.
+    #[inline]
+    pub fn is_cut(&self) -> bool {
+        self.effect == TransitionEffect::Cut || self.duration <= 0.0
+    }
+
+    /// Draw the transition covering the area, `t` of the way through, from `0` to `1`.
+    pub fn draw(&self, draw: &mut Draw, area: RectF, t: f32) {
+        let t = t.clamp(0.0, 1.0);
+        draw.push_new_transform(Affine2F::IDENTITY);
+        match self.effect {
+            TransitionEffect::Cut => {}
+            TransitionEffect::Fade(color) => {
+                let alpha = 1.0 - (t * 2.0 - 1.0).abs();
+                draw.rect(area, color.un_mul((alpha * 255.0).round() as u8));
+            }
+            TransitionEffect::Wipe(dir, color) => {
+                // the leading edge crosses the screen in the first half, then the trailing
+                // edge follows it in the second
+                let lead = (t * 2.0).min(1.0);
+                let trail = (t * 2.0 - 1.0).max(0.0);
+                let (from, to) = match dir {
+                    Cardinal::East | Cardinal::South => (trail, lead),
+                    Cardinal::West | Cardinal::North => (1.0 - lead, 1.0 - trail),
+                };
+                let rect = match dir {
+                    Cardinal::East | Cardinal::West => {
+                        RectF::new(area.x + area.w * from, area.y, area.w * (to - from), area.h)
+                    }
+                    Cardinal::South | Cardinal::North => {
+                        RectF::new(area.x, area.y + area.h * from, area.w, area.h * (to - from))
+                    }
+                };
+                draw.rect(rect, color);
+            }
+        }
+        draw.pop_transform().unwrap();
+    }
+}
@@ -0,0 +1,278 @@
+use std::fmt::{Debug, Formatter};
+
+/// Identifies a task started by a [`Scheduler`], so it can be cancelled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TaskId(u64);
+
+/// A task that runs over several updates, pausing between steps, such as a cutscene.
+///
+/// Each time it is resumed it does some work and returns how many seconds to wait before it
+/// is resumed again, or `None` when it is finished. Closures of the form
+/// `FnMut(&mut T) -> Option<f32>` are coroutines, and [`Sequence`] builds one from steps.
+pub trait Coroutine<T>: 'static {
+    /// Run the next step, returning how long to wait before the one after it.
+    fn resume(&mut self, state: &mut T) -> Option<f32>;
+}
+
+impl<T, F> Coroutine<T> for F
+where
+    F: FnMut(&mut T) -> Option<f32> + 'static,
+{
+    #[inline]
+    fn resume(&mut self, state: &mut T) -> Option<f32> {
+        self(state)
+    }
+}
+
+enum Step<T> {
+    Wait(f32),
+    Run(Box<dyn FnMut(&mut T)>),
+    Until(Box<dyn FnMut(&mut T) -> bool>),
+}
+
+/// A [`Coroutine`] made from a list of steps, run in order.
+///
+/// ```ignore
+/// let intro = Sequence::new()
+///     .then(|game: &mut Game| game.dialog.show("Who goes there?"))
+///     .wait_until(|game| game.dialog.is_closed())
+///     .wait(0.5)
+///     .then(|game| game.door.open());
+/// game.scheduler.start(intro);
+/// ```
+pub struct Sequence<T> {
+    steps: Vec<Step<T>>,
+    next: usize,
+}
+
+impl<T> Default for Sequence<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            steps: Vec::new(),
+            next: 0,
+        }
+    }
+}
+
+impl<T> Debug for Sequence<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Sequence")
+            .field("steps", &self.steps.len())
+            .field("next", &self.next)
+            .finish()
+    }
+}
+
+impl<T> Sequence<T> {
+    /// Create an empty sequence.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Return the sequence with a step that waits for the number of seconds.
+    #[inline]
+    pub fn wait(mut self, seconds: f32) -> Self {
+        self.steps.push(Step::Wait(seconds));
+        self
+    }
+
+    /// Return the sequence with a step that runs the function.
+    #[inline]
+    pub fn then(mut self, f: impl FnMut(&mut T) + 'static) -> Self {
+        self.steps.push(Step::Run(Box::new(f)));
+        self
+    }
+
+    /// Return the sequence with a step that checks the condition every update, continuing once
+    /// it is true.
+    #[inline]
+    pub fn wait_until(mut self, f: impl FnMut(&mut T) -> bool + 'static) -> Self {
+        self.steps.push(Step::Until(Box::new(f)));
+        self
+    }
+
+    /// How many steps are in the sequence.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// If the sequence has no steps.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+impl<T: 'static> Coroutine<T> for Sequence<T> {
+    fn resume(&mut self, state: &mut T) -> Option<f32> {
+        while let Some(step) = self.steps.get_mut(self.next) {
+            match step {
+                Step::Wait(seconds) => {
+                    self.next += 1;
+                    return Some(*seconds);
+                }
+                Step::Run(f) => {
+                    f(state);
+                    self.next += 1;
+                }
+                Step::Until(f) => match f(state) {
+                    true => self.next += 1,
+                    false => return Some(0.0),
+                },
+            }
+        }
+        None
+    }
+}
+
+type OnceFn<T> = Box<dyn FnOnce(&mut T)>;
+
+enum TaskKind<T> {
+    Once(Option<OnceFn<T>>),
+    Every(f32, Box<dyn FnMut(&mut T)>),
+    Coroutine(Box<dyn Coroutine<T>>),
+}
+
+struct Task<T> {
+    id: TaskId,
+    wait: f32,
+    kind: TaskKind<T>,
+}
+
+/// Runs functions after a delay, on an interval, or as [coroutines](Coroutine) that wait
+/// between steps. Useful for cutscenes, delayed effects, and anything else that happens over
+/// time.
+///
+/// Tasks are given mutable access to a state of type `T`, such as the game's world, which is
+/// passed in when the scheduler is updated. Keep the scheduler outside of that state so both
+/// can be borrowed at once.
+///
+/// ```ignore
+/// self.scheduler.after(2.0, |world: &mut World| world.spawn_boss());
+/// self.scheduler.every(0.5, |world| world.spawn_coin());
+///
+/// // every update
+/// self.scheduler.update(&mut self.world, ctx.dt());
+/// ```
+pub struct Scheduler<T> {
+    tasks: Vec<Task<T>>,
+    next_id: u64,
+}
+
+impl<T> Default for Scheduler<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            tasks: Vec::new(),
+            next_id: 0,
+        }
+    }
+}
+
+impl<T> Debug for Scheduler<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("tasks", &self.tasks.len())
+            .finish()
+    }
+}
+
+impl<T: 'static> Scheduler<T> {
+    /// Create an empty scheduler.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn add(&mut self, wait: f32, kind: TaskKind<T>) -> TaskId {
+        let id = TaskId(self.next_id);
+        self.next_id += 1;
+        self.tasks.push(Task { id, wait, kind });
+        id
+    }
+
+    /// Run the function once, after the number of seconds.
+    #[inline]
+    pub fn after(&mut self, seconds: f32, f: impl FnOnce(&mut T) + 'static) -> TaskId {
+        self.add(seconds, TaskKind::Once(Some(Box::new(f))))
+    }
+
+    /// Run the function every time the number of seconds passes, until it is cancelled. It is
+    /// run at most once per update, even if the interval is shorter than the update.
+    #[inline]
+    pub fn every(&mut self, seconds: f32, f: impl FnMut(&mut T) + 'static) -> TaskId {
+        self.add(seconds, TaskKind::Every(seconds, Box::new(f)))
+    }
+
+    /// Start the coroutine, resuming it for the first time on the next update.
+    #[inline]
+    pub fn start(&mut self, coroutine: impl Coroutine<T>) -> TaskId {
+        self.add(0.0, TaskKind::Coroutine(Box::new(coroutine)))
+    }
+
+    /// Stop the task, returning `true` if it was still running.
+    pub fn cancel(&mut self, id: TaskId) -> bool {
+        let len = self.tasks.len();
+        self.tasks.retain(|task| task.id != id);
+        self.tasks.len() != len
+    }
+
+    /// If the task is still running.
+    #[inline]
+    pub fn is_running(&self, id: TaskId) -> bool {
+        self.tasks.iter().any(|task| task.id == id)
+    }
+
+    /// Stop all tasks.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.tasks.clear();
+    }
+
+    /// How many tasks are running.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// If no tasks are running.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Advance time by `dt` seconds, running any tasks that are due, in the order they were
+    /// started. Time left over when a task is due carries into its next wait, so intervals
+    /// don't drift with the frame rate.
+    pub fn update(&mut self, state: &mut T, dt: f32) {
+        self.tasks.retain_mut(|task| {
+            task.wait -= dt;
+            if task.wait > 0.0 {
+                return true;
+            }
+            match &mut task.kind {
+                TaskKind::Once(f) => {
+                    if let Some(f) = f.take() {
+                        f(state);
+                    }
+                    false
+                }
+                TaskKind::Every(interval, f) => {
+                    f(state);
+                    task.wait = (task.wait + *interval).max(0.0);
+                    true
+                }
+                TaskKind::Coroutine(co) => match co.resume(state) {
+                    Some(wait) => {
+                        task.wait = (task.wait + wait).max(0.0);
+                        true
+                    }
+                    None => false,
+                },
+            }
+        });
+    }
+}
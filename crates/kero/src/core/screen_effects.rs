@@ -0,0 +1,198 @@
+use crate::core::Time;
+use crate::gfx::shake_noise;
+use crate::math::{Vec2F, vec2};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// How a [hit-stop](ScreenEffects::hitstop) combines with one that's already happening.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum HitStopStacking {
+    /// Keep whichever has longer left, so a flurry of small hits doesn't freeze the game for
+    /// long.
+    #[default]
+    Longest,
+
+    /// Add the durations together, up to the [max](ScreenEffects::max_hitstop).
+    Add,
+
+    /// Replace the current one.
+    Replace,
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Shake {
+    amplitude: f32,
+    duration: f32,
+    frequency: f32,
+    time: f32,
+    seed: u32,
+}
+
+#[derive(Debug)]
+pub(crate) struct ScreenEffectsState {
+    time: Time,
+    shakes: RefCell<Vec<Shake>>,
+    next_seed: Cell<u32>,
+    hitstop: Cell<f32>,
+    stacking: Cell<HitStopStacking>,
+    max_hitstop: Cell<f32>,
+}
+
+/// Handle to game-feel effects that apply to the whole game: screen shake, which a
+/// [`Camera2D`](crate::gfx::Camera2D) picks up with
+/// [`sync_effects`](crate::gfx::Camera2D::sync_effects), and hit-stop, which briefly freezes
+/// [game time](crate::core::Time::delta).
+///
+/// Effects play out in real time, so a shake keeps moving during a hit-stop.
+///
+/// Obtained from [`Context`](super::Context).
+///
+/// ```ignore
+/// if hit_enemy {
+///     ctx.effects.shake(6.0, 0.25, 30.0);
+///     ctx.effects.hitstop(0.08);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ScreenEffects(pub(crate) Rc<ScreenEffectsState>);
+
+impl Debug for ScreenEffects {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("ScreenEffects").finish_non_exhaustive()
+    }
+}
+
+impl ScreenEffects {
+    pub(crate) fn new(time: Time) -> Self {
+        Self(Rc::new(ScreenEffectsState {
+            time,
+            shakes: RefCell::new(Vec::new()),
+            next_seed: Cell::new(0),
+            hitstop: Cell::new(0.0),
+            stacking: Cell::new(HitStopStacking::Longest),
+            max_hitstop: Cell::new(0.5),
+        }))
+    }
+
+    /// Shake the screen up to `amplitude` pixels, fading out over `duration` seconds, changing
+    /// direction `frequency` times per second. Shakes that overlap add together.
+    pub fn shake(&self, amplitude: f32, duration: f32, frequency: f32) {
+        if duration <= 0.0 {
+            return;
+        }
+        let seed = self.0.next_seed.get();
+        self.0.next_seed.set(seed.wrapping_add(2));
+        self.0.shakes.borrow_mut().push(Shake {
+            amplitude,
+            duration,
+            frequency,
+            time: 0.0,
+            seed,
+        });
+    }
+
+    /// Stop all screen shakes.
+    #[inline]
+    pub fn stop_shaking(&self) {
+        self.0.shakes.borrow_mut().clear();
+    }
+
+    /// If the screen is shaking.
+    #[inline]
+    pub fn is_shaking(&self) -> bool {
+        !self.0.shakes.borrow().is_empty()
+    }
+
+    /// How far all the current shakes offset the screen, in pixels.
+    pub fn shake_offset(&self) -> Vec2F {
+        self.0
+            .shakes
+            .borrow()
+            .iter()
+            .map(|shake| {
+                let t = shake.time * shake.frequency;
+                let fade = 1.0 - shake.time / shake.duration;
+                vec2(shake_noise(t, shake.seed), shake_noise(t, shake.seed + 1))
+                    * (shake.amplitude * fade)
+            })
+            .fold(Vec2F::ZERO, |a, b| a + b)
+    }
+
+    /// Freeze game time for `duration` seconds, to give hits more impact. If a hit-stop is
+    /// already happening, the two are combined by the [stacking](Self::hitstop_stacking) rule.
+    pub fn hitstop(&self, duration: f32) {
+        let current = self.0.hitstop.get();
+        let max = self.0.max_hitstop.get();
+        let duration = duration.clamp(0.0, max);
+        let remaining = match self.0.stacking.get() {
+            HitStopStacking::Longest => current.max(duration),
+            HitStopStacking::Add => (current + duration).min(max),
+            HitStopStacking::Replace => duration,
+        };
+        self.0.hitstop.set(remaining);
+        self.apply_hitstop();
+    }
+
+    /// How long is left of the current hit-stop, in seconds.
+    #[inline]
+    pub fn hitstop_remaining(&self) -> f32 {
+        self.0.hitstop.get()
+    }
+
+    /// If game time is frozen by a hit-stop.
+    #[inline]
+    pub fn is_hitstopped(&self) -> bool {
+        self.0.hitstop.get() > 0.0
+    }
+
+    /// End the current hit-stop early.
+    #[inline]
+    pub fn cancel_hitstop(&self) {
+        self.0.hitstop.set(0.0);
+        self.apply_hitstop();
+    }
+
+    /// How a hit-stop combines with one that's already happening.
+    #[inline]
+    pub fn hitstop_stacking(&self) -> HitStopStacking {
+        self.0.stacking.get()
+    }
+
+    /// Set how a hit-stop combines with one that's already happening. Defaults to
+    /// [`Longest`](HitStopStacking::Longest).
+    #[inline]
+    pub fn set_hitstop_stacking(&self, stacking: HitStopStacking) {
+        self.0.stacking.set(stacking);
+    }
+
+    /// The longest a hit-stop can last, in seconds.
+    #[inline]
+    pub fn max_hitstop(&self) -> f32 {
+        self.0.max_hitstop.get()
+    }
+
+    /// Set the longest a hit-stop can last, in seconds. Defaults to `0.5`.
+    #[inline]
+    pub fn set_max_hitstop(&self, max: f32) {
+        self.0.max_hitstop.set(max.max(0.0));
+    }
+
+    #[inline]
+    fn apply_hitstop(&self) {
+        let scale = if self.is_hitstopped() { 0.0 } else { 1.0 };
+        self.0.time.0.effect_scale.set(scale);
+    }
+
+    /// Advance the effects by the real time since the last update.
+    pub(crate) fn update(&self) {
+        let dt = self.0.time.unscaled_delta();
+        self.0.shakes.borrow_mut().retain_mut(|shake| {
+            shake.time += dt;
+            shake.time < shake.duration
+        });
+        self.0.hitstop.update(|t| (t - dt).max(0.0));
+        self.apply_hitstop();
+    }
+}
@@ -27,6 +27,15 @@ pub(crate) struct TimeState {
     pub unfixed_delta: Cell<f32>,
     pub since_startup: Cell<f32>,
     pub frame: Cell<u64>,
+    pub paused: Cell<bool>,
+    pub steps: Cell<u32>,
+    pub fixed_rate: Cell<f64>,
+    pub max_fixed_steps: Cell<u32>,
+    pub fixed_accum: Cell<f64>,
+    pub fixed_frame: Cell<u64>,
+    pub blend_factor: Cell<f32>,
+    pub scale: Cell<f32>,
+    pub effect_scale: Cell<f32>,
 }
 
 impl Default for TimeState {
@@ -40,10 +49,52 @@ impl Default for TimeState {
             unfixed_delta: Cell::new(0.0),
             since_startup: Cell::new(0.0),
             frame: Cell::new(0),
+            paused: Cell::new(false),
+            steps: Cell::new(0),
+            fixed_rate: Cell::new(60.0),
+            max_fixed_steps: Cell::new(8),
+            fixed_accum: Cell::new(0.0),
+            fixed_frame: Cell::new(0),
+            blend_factor: Cell::new(0.0),
+            scale: Cell::new(1.0),
+            effect_scale: Cell::new(1.0),
         }
     }
 }
 
+impl TimeState {
+    /// The update's delta, scaled by the time scale and any effects such as hit-stop.
+    #[inline]
+    pub fn scaled_delta(&self) -> f32 {
+        self.delta.get() * self.scale.get() * self.effect_scale.get()
+    }
+
+    /// Add the update's delta to the fixed timestep accumulator and return how many fixed
+    /// updates should run. Time beyond the step limit is dropped, so a slow frame doesn't
+    /// snowball into more and more fixed updates.
+    pub fn fixed_steps(&self) -> u32 {
+        // tolerate rounding error so a fixed rate equal to the update rate steps every update
+        const EPSILON: f64 = 1e-7;
+
+        let step = 1.0 / self.fixed_rate.get();
+        let mut accum = self.fixed_accum.get() + self.scaled_delta() as f64;
+        let mut steps = 0;
+        while accum >= step - EPSILON {
+            accum -= step;
+            steps += 1;
+            if steps >= self.max_fixed_steps.get().max(1) {
+                accum = accum.min(step - EPSILON);
+                break;
+            }
+        }
+        let accum = accum.max(0.0);
+        self.fixed_accum.set(accum);
+        self.fixed_frame.update(|f| f + steps as u64);
+        self.blend_factor.set((accum / step) as f32);
+        steps
+    }
+}
+
 impl Time {
     pub(crate) fn new() -> Self {
         Self(Rc::new(TimeState::default()))
@@ -75,12 +126,32 @@ impl Time {
         self.0.fps.get()
     }
 
-    /// Duration since the last frame, in seconds.
+    /// Duration since the last frame, in seconds, scaled by the [time scale](Self::time_scale)
+    /// and stopped during [hit-stop](crate::core::ScreenEffects::hitstop).
     #[inline]
     pub fn delta(&self) -> f32 {
+        self.0.scaled_delta()
+    }
+
+    /// Duration since the last frame, in seconds, ignoring the time scale and hit-stop. Use
+    /// this for anything that should keep moving when the game is slowed down, such as menus.
+    #[inline]
+    pub fn unscaled_delta(&self) -> f32 {
         self.0.delta.get()
     }
 
+    /// How fast time passes in the game, where `1` is normal speed and `0.5` is half speed.
+    #[inline]
+    pub fn time_scale(&self) -> f32 {
+        self.0.scale.get()
+    }
+
+    /// Set how fast time passes in the game. Defaults to `1`.
+    #[inline]
+    pub fn set_time_scale(&self, scale: f32) {
+        self.0.scale.set(scale.max(0.0));
+    }
+
     /// Unfixed duration since last frame, in seconds.
     #[inline]
     pub fn unfixed_delta(&self) -> f32 {
@@ -99,6 +170,85 @@ impl Time {
         self.0.frame.get()
     }
 
+    /// How many times per second [`fixed_update`](crate::core::Game::fixed_update) is called.
+    #[inline]
+    pub fn fixed_rate(&self) -> f64 {
+        self.0.fixed_rate.get()
+    }
+
+    /// Set how many times per second [`fixed_update`](crate::core::Game::fixed_update) is
+    /// called. Defaults to `60`.
+    #[inline]
+    pub fn set_fixed_rate(&self, rate: f64) {
+        self.0.fixed_rate.set(rate.max(1.0));
+    }
+
+    /// The most fixed updates that can run in a single update. If the game falls further
+    /// behind than this, the extra time is skipped instead of caught up on.
+    #[inline]
+    pub fn max_fixed_steps(&self) -> u32 {
+        self.0.max_fixed_steps.get()
+    }
+
+    /// Set the most fixed updates that can run in a single update. Defaults to `8`.
+    #[inline]
+    pub fn set_max_fixed_steps(&self, max: u32) {
+        self.0.max_fixed_steps.set(max.max(1));
+    }
+
+    /// Duration of each fixed update, in seconds.
+    #[inline]
+    pub fn fixed_delta(&self) -> f32 {
+        (1.0 / self.0.fixed_rate.get()) as f32
+    }
+
+    /// How many fixed updates have run.
+    #[inline]
+    pub fn fixed_frame(&self) -> u64 {
+        self.0.fixed_frame.get()
+    }
+
+    /// How far between the last fixed update and the next one the game is, from `0` to `1`.
+    /// Draw anything moved in [`fixed_update`](crate::core::Game::fixed_update) between its
+    /// previous and current positions by this, so it moves smoothly when the fixed rate and
+    /// frame rate differ.
+    ///
+    /// ```ignore
+    /// let pos = self.prev_pos.lerp(self.pos, ctx.time.blend_factor());
+    /// ```
+    #[inline]
+    pub fn blend_factor(&self) -> f32 {
+        self.0.blend_factor.get()
+    }
+
+    /// If updates are paused. While paused, the game keeps rendering but time stands still.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.0.paused.get()
+    }
+
+    /// Pause or resume updates.
+    #[inline]
+    pub fn set_paused(&self, paused: bool) {
+        self.0.paused.set(paused);
+        self.0.steps.set(0);
+    }
+
+    /// Resume updates if paused, otherwise pause them.
+    #[inline]
+    pub fn toggle_paused(&self) {
+        self.set_paused(!self.paused());
+    }
+
+    /// Run a single update while paused, to step through the game frame by frame. Does
+    /// nothing if not paused.
+    #[inline]
+    pub fn step(&self) {
+        if self.paused() {
+            self.0.steps.update(|n| n + 1);
+        }
+    }
+
     #[inline]
     pub fn flicker(&self, on_time: f32, off_time: f32) -> bool {
         (self.since_startup() % (on_time + off_time)) < on_time
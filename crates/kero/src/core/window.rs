@@ -1,9 +1,14 @@
 use dpi::{LogicalPosition, LogicalSize, PhysicalPosition, PhysicalSize};
 use std::fmt::{Debug, Formatter};
-use std::sync::Arc;
-use winit::window::{Cursor, Fullscreen, Window as WinitWindow};
+use std::sync::{Arc, Mutex, MutexGuard};
+use winit::event_loop::ActiveEventLoop;
+use winit::window::{
+    Cursor, CustomCursor, CustomCursorSource, Fullscreen, Window as WinitWindow, WindowLevel,
+};
 
-use crate::math::{Vec2I, Vec2U};
+use crate::grid::Grid;
+use crate::img::ImageRgba8;
+use crate::math::{Numeric, RectF, Vec2I, Vec2U};
 
 use super::{CursorIcon, DisplayMode, Monitor, VideoMode};
 
@@ -13,7 +18,10 @@ use super::{CursorIcon, DisplayMode, Monitor, VideoMode};
 ///
 /// Obtained from [`Context`](super::Context).
 #[derive(Clone)]
-pub struct Window(pub(crate) Arc<WinitWindow>);
+pub struct Window(
+    pub(crate) Arc<WinitWindow>,
+    Arc<Mutex<Option<CustomCursorSource>>>,
+);
 
 impl Debug for Window {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -22,6 +30,10 @@ impl Debug for Window {
 }
 
 impl Window {
+    pub(crate) fn new(window: WinitWindow) -> Self {
+        Self(Arc::new(window), Arc::new(Mutex::new(None)))
+    }
+
     /// The window title.
     #[inline]
     pub fn title(&self) -> String {
@@ -108,6 +120,32 @@ impl Window {
         }
     }
 
+    /// The video mode the window is displayed with. This is the exclusive fullscreen mode if
+    /// there is one, otherwise the monitor's mode matching its current size and refresh rate.
+    pub fn video_mode(&self) -> Option<VideoMode> {
+        if let Some(Fullscreen::Exclusive(mode)) = self.0.fullscreen() {
+            return Some(VideoMode(mode));
+        }
+        let monitor = self.monitor()?;
+        let refresh_rate = monitor.refresh_rate_mhz();
+        monitor
+            .video_modes()
+            .filter(|mode| {
+                mode.pixel_size() == monitor.pixel_size()
+                    && refresh_rate.is_none_or(|rate| rate == mode.refresh_rate())
+            })
+            .max_by_key(|mode| mode.bit_depth())
+    }
+
+    /// The refresh rate of the display the window is on in millihertz, from the exclusive
+    /// fullscreen mode if there is one, otherwise from the window's monitor.
+    pub fn refresh_rate_mhz(&self) -> Option<u32> {
+        match self.0.fullscreen() {
+            Some(Fullscreen::Exclusive(mode)) => Some(mode.refresh_rate_millihertz()),
+            _ => self.monitor()?.refresh_rate_mhz(),
+        }
+    }
+
     /// If the window is in a fullscreen mode.
     #[inline]
     pub fn is_fullscreen(&self) -> bool {
@@ -308,9 +346,85 @@ impl Window {
             .set_max_inner_size(size.into().map(|s| LogicalSize::new(s.x, s.y)));
     }
 
-    /// Set the cursor to display when the mouse is over the window.
+    /// Set if the window is kept above all other windows.
+    #[inline]
+    pub fn set_always_on_top(&self, always_on_top: bool) {
+        self.0.set_window_level(match always_on_top {
+            true => WindowLevel::AlwaysOnTop,
+            false => WindowLevel::Normal,
+        });
+    }
+
+    /// Set if mouse input passes through the window to whatever is behind it. Returns `false`
+    /// if the platform doesn't support this.
+    #[inline]
+    pub fn set_click_through(&self, click_through: bool) -> bool {
+        self.0.set_cursor_hittest(!click_through).is_ok()
+    }
+
+    /// Allow the platform's input method editor (IME) to be used, so players can type
+    /// languages like Japanese, Chinese, and Korean. Allow it while a text field is focused,
+    /// and disallow it otherwise so it doesn't capture gameplay keys.
+    ///
+    /// See [`Keyboard::ime_events`](crate::input::Keyboard::ime_events).
+    #[inline]
+    pub fn set_ime_allowed(&self, allowed: bool) {
+        self.0.set_ime_allowed(allowed);
+    }
+
+    /// Tell the IME where the text cursor is, in DPI-independent coordinates, so its candidate
+    /// window can be shown next to it without covering the text.
+    #[inline]
+    pub fn set_ime_cursor_area(&self, area: impl Into<RectF>) {
+        let area = area.into();
+        self.0.set_ime_cursor_area(
+            LogicalPosition::new(area.x, area.y),
+            LogicalSize::new(area.w, area.h),
+        );
+    }
+
+    /// Set the cursor to display when the mouse is over the window to one of the platform's
+    /// standard cursors.
     #[inline]
     pub fn set_cursor(&self, icon: CursorIcon) {
+        *self.cursor_image() = None;
         self.0.set_cursor(Cursor::Icon(icon.into()));
     }
+
+    /// Set the cursor to display when the mouse is over the window to an image, with the
+    /// hotspot being the pixel that points at things. Unlike drawing a sprite at the mouse
+    /// position, the platform draws this itself, so it never lags behind the real mouse.
+    ///
+    /// The image is sent to the platform when events are next handled, so set it once rather
+    /// than every frame. Returns `false` if the image is larger than `65535` pixels in either
+    /// direction or the hotspot is outside of it. Some platforms limit cursors to smaller sizes,
+    /// such as `32x32`, and will fall back to the default cursor if it is too big.
+    pub fn set_cursor_image(&self, image: &ImageRgba8, hotspot: impl Into<Vec2U>) -> bool {
+        let hotspot = hotspot.into();
+        let (size16, hotspot16) = (image.size().to_u16(), hotspot.to_u16());
+        if size16.to_u32() != image.size() || hotspot16.to_u32() != hotspot {
+            return false;
+        }
+        let (w, h) = (size16.x, size16.y);
+        match CustomCursor::from_rgba(image.bytes(), w, h, hotspot16.x, hotspot16.y) {
+            Ok(source) => {
+                *self.cursor_image() = Some(source);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    #[inline]
+    fn cursor_image(&self) -> MutexGuard<'_, Option<CustomCursorSource>> {
+        self.1.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Create and display the most recently set cursor image.
+    pub(crate) fn apply_cursor_image(&self, event_loop: &ActiveEventLoop) {
+        if let Some(source) = self.cursor_image().take() {
+            let cursor = event_loop.create_custom_cursor(source);
+            self.0.set_cursor(Cursor::Custom(cursor));
+        }
+    }
 }
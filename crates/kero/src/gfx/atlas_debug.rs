@@ -0,0 +1,226 @@
+use crate::color::Rgba8;
+use crate::gfx::{Draw, Font, Texture, TextureAtlas};
+use crate::math::{Numeric, RectF, Vec2F, vec2};
+use std::fmt::Display;
+use std::hash::Hash;
+
+/// A debug overlay that shows atlas pages with every packed rectangle outlined and labeled,
+/// to diagnose packing and bleeding issues.
+///
+/// Register pages with [`add_page`](Self::add_page) or [`add_atlas`](Self::add_atlas), then
+/// toggle the overlay at runtime. While enabled, the current page is drawn over everything
+/// else at the end of each frame, and the rectangle under the mouse is highlighted.
+///
+/// Get the overlay from [`Graphics::atlas_debug`](super::Graphics::atlas_debug).
+#[derive(Debug, Default)]
+pub struct AtlasDebug {
+    enabled: bool,
+    page: usize,
+    pages: Vec<AtlasDebugPage>,
+    font: Option<Font>,
+}
+
+/// A page of an [`AtlasDebug`] overlay.
+#[derive(Debug, Clone)]
+pub struct AtlasDebugPage {
+    pub name: String,
+    pub texture: Texture,
+
+    /// Names and rectangles of everything packed into the texture, in pixels.
+    pub rects: Vec<(String, RectF)>,
+}
+
+const BACKGROUND: Rgba8 = Rgba8::new(0, 0, 0, 200);
+const PAGE_BACKGROUND: Rgba8 = Rgba8::new(0x47, 0x6c, 0x6c, 255);
+const OUTLINE: Rgba8 = Rgba8::new(255, 255, 255, 160);
+const HOVERED: Rgba8 = Rgba8::new(255, 220, 0, 255);
+const PADDING: f32 = 16.0;
+
+impl AtlasDebug {
+    /// If the overlay is drawn.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set if the overlay is drawn.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Show the overlay if hidden, otherwise hide it.
+    #[inline]
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+
+    /// Set the font used to label pages and rectangles. Without one, only outlines are drawn.
+    #[inline]
+    pub fn set_font(&mut self, font: Option<Font>) {
+        self.font = font;
+    }
+
+    /// All registered pages.
+    #[inline]
+    pub fn pages(&self) -> &[AtlasDebugPage] {
+        &self.pages
+    }
+
+    /// Register a page, replacing any existing page with the same name.
+    pub fn add_page(
+        &mut self,
+        name: impl Into<String>,
+        texture: Texture,
+        rects: impl IntoIterator<Item = (String, RectF)>,
+    ) {
+        let page = AtlasDebugPage {
+            name: name.into(),
+            texture,
+            rects: rects.into_iter().collect(),
+        };
+        match self.pages.iter_mut().find(|p| p.name == page.name) {
+            Some(existing) => *existing = page,
+            None => self.pages.push(page),
+        }
+    }
+
+    /// Register the texture of an atlas as a page, labeling each subtexture with its key.
+    pub fn add_atlas<K: Clone + Eq + Hash + Display>(
+        &mut self,
+        name: impl Into<String>,
+        atlas: &TextureAtlas<K>,
+    ) {
+        let rects = atlas.iter().map(|(key, sub)| (key.to_string(), sub.rect));
+        self.add_page(name, atlas.texture().clone(), rects);
+    }
+
+    /// Remove the page with the name.
+    pub fn remove_page(&mut self, name: &str) {
+        self.pages.retain(|p| p.name != name);
+        self.page = self.page.min(self.pages.len().saturating_sub(1));
+    }
+
+    /// Remove all pages.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.pages.clear();
+        self.page = 0;
+    }
+
+    /// Index of the page being shown.
+    #[inline]
+    pub fn page(&self) -> usize {
+        self.page
+    }
+
+    /// Set which page is shown.
+    #[inline]
+    pub fn set_page(&mut self, page: usize) {
+        self.page = page.min(self.pages.len().saturating_sub(1));
+    }
+
+    /// Show the next page, wrapping around to the first.
+    #[inline]
+    pub fn next_page(&mut self) {
+        self.page = (self.page + 1) % self.pages.len().max(1);
+    }
+
+    /// Show the previous page, wrapping around to the last.
+    #[inline]
+    pub fn prev_page(&mut self) {
+        let len = self.pages.len().max(1);
+        self.page = (self.page + len - 1) % len;
+    }
+
+    /// Draw the current page, scaled to fit an area of `size` with its top-left at the origin.
+    /// If `cursor` is over a rectangle, it is highlighted and its name is shown.
+    pub fn draw(&self, draw: &mut Draw, size: Vec2F, cursor: Option<Vec2F>) {
+        draw.rect(RectF::sized(size), BACKGROUND);
+        let Some(page) = self.pages.get(self.page) else {
+            if let Some(font) = &self.font {
+                draw_label(draw, font, "no atlas pages", vec2(PADDING, PADDING));
+            }
+            return;
+        };
+
+        // fit the page below the header, only scaling pixel-perfect when enlarging
+        let header = self.font.as_ref().map_or(0.0, |f| f.size() * 1.5);
+        let tex_size = page.texture.size().to_f32();
+        let area = size - vec2(PADDING * 2.0, PADDING * 2.0 + header);
+        let fit = area / tex_size;
+        let mut scale = fit.x.min(fit.y).max(0.01);
+        if scale >= 1.0 {
+            scale = scale.floor();
+        }
+        let origin = vec2(PADDING, PADDING + header);
+        let to_screen = |r: RectF| RectF::pos_size(origin + r.top_left() * scale, r.size() * scale);
+
+        draw.rect(to_screen(RectF::sized(tex_size)), PAGE_BACKGROUND);
+        draw.textured_quad(&page.texture, to_screen(RectF::sized(tex_size)));
+
+        let mut hovered = None;
+        for (name, rect) in &page.rects {
+            let rect = to_screen(*rect);
+            if cursor.is_some_and(|p| rect.contains(p)) {
+                hovered = Some((name, rect));
+            }
+            draw.rect_outline(rect, OUTLINE);
+        }
+
+        let Some(font) = &self.font else {
+            if let Some((_, rect)) = hovered {
+                draw.rect_outline(rect, HOVERED);
+            }
+            return;
+        };
+
+        // label rects that are big enough to fit their name
+        for (name, rect) in &page.rects {
+            let rect = to_screen(*rect);
+            if font.text_width(name) + 4.0 <= rect.w && font.size() + 4.0 <= rect.h {
+                draw.text(
+                    name,
+                    rect.top_left() + vec2(2.0, 2.0 + font.size()),
+                    font,
+                    OUTLINE,
+                    None,
+                );
+            }
+        }
+
+        let title = format!(
+            "{} ({}/{}) {}x{}, {} rects",
+            page.name,
+            self.page + 1,
+            self.pages.len(),
+            page.texture.width(),
+            page.texture.height(),
+            page.rects.len(),
+        );
+        draw_label(draw, font, &title, vec2(PADDING, PADDING));
+
+        if let Some((name, rect)) = hovered {
+            draw.rect_outline(rect, HOVERED);
+            let pixels = (rect.top_left() - origin) / scale;
+            let text = format!(
+                "{name} ({}, {}, {}x{})",
+                pixels.x.round(),
+                pixels.y.round(),
+                (rect.w / scale).round(),
+                (rect.h / scale).round(),
+            );
+            draw_label(draw, font, &text, rect.bottom_left() + vec2(0.0, 2.0));
+        }
+    }
+}
+
+/// Draw text on a dark background, with its top-left at `pos`.
+fn draw_label(draw: &mut Draw, font: &Font, text: &str, pos: Vec2F) {
+    let size = font.size();
+    draw.rect(
+        RectF::pos_size(pos, vec2(font.text_width(text) + 4.0, size * 1.25 + 4.0)),
+        BACKGROUND,
+    );
+    draw.text(text, pos + vec2(2.0, 2.0 + size), font, Rgba8::WHITE, None);
+}
@@ -1,7 +1,7 @@
 use crate::gfx::{ParamType, Sampler, Shader, Texture, UniformType};
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
 use arrayvec::ArrayVec;
-use bytemuck::bytes_of;
+use bytemuck::{Pod, bytes_of, cast_slice};
 use serde::{Deserialize, Serialize};
 use std::hash::{DefaultHasher, Hash, Hasher};
 use std::sync::Arc;
@@ -75,14 +75,16 @@ impl Bindings {
     }
 }
 
+/// The value assigned to a shader parameter.
 #[derive(Debug, Clone, PartialEq)]
-pub(crate) enum BindingValue {
+pub enum BindingValue {
     Texture(Texture),
     Sampler(Sampler),
     Uniform(UniformValue),
 }
 
 impl BindingValue {
+    /// The type of parameter this value can be assigned to.
     #[inline]
     pub fn param_ty(&self) -> ParamType {
         match self {
@@ -105,9 +107,36 @@ pub enum UniformValue {
     Mat2(Mat2<f32>),
     Mat3(Mat3<f32>),
     Mat4(Mat4<f32>),
+    /// Raw bytes for a struct or array uniform block. See [`UniformValue::block`].
+    Block(Vec<u8>),
 }
 
 impl UniformValue {
+    /// Create a uniform block from a value, which must match the memory layout of the
+    /// WGSL struct it is assigned to. Keep in mind that WGSL uniforms align `vec3` and
+    /// array elements to 16 bytes, so the Rust struct may need explicit padding fields.
+    ///
+    /// ```ignore
+    /// #[derive(Copy, Clone, Pod, Zeroable)]
+    /// #[repr(C)]
+    /// struct Light {
+    ///     pos: Vec2F,
+    ///     radius: f32,
+    ///     _pad: f32,
+    ///     color: Vec4F,
+    /// }
+    /// ```
+    #[inline]
+    pub fn block<T: Pod>(value: &T) -> Self {
+        Self::Block(bytes_of(value).to_vec())
+    }
+
+    /// Create a uniform block from a slice of values, such as an array of `Vec4F`.
+    #[inline]
+    pub fn block_slice<T: Pod>(values: &[T]) -> Self {
+        Self::Block(cast_slice(values).to_vec())
+    }
+
     #[inline]
     pub fn bytes(&self) -> &[u8] {
         match self {
@@ -120,6 +149,7 @@ impl UniformValue {
             Self::Mat2(val) => bytes_of(val),
             Self::Mat3(val) => bytes_of(val),
             Self::Mat4(val) => bytes_of(val),
+            Self::Block(bytes) => bytes,
         }
     }
 
@@ -135,6 +165,62 @@ impl UniformValue {
             Self::Mat2(_) => UniformType::Mat2,
             Self::Mat3(_) => UniformType::Mat3,
             Self::Mat4(_) => UniformType::Mat4,
+            Self::Block(bytes) => UniformType::Block {
+                size: bytes.len() as u32,
+            },
         }
     }
 }
+
+impl From<Texture> for BindingValue {
+    #[inline]
+    fn from(value: Texture) -> Self {
+        Self::Texture(value)
+    }
+}
+
+impl From<Sampler> for BindingValue {
+    #[inline]
+    fn from(value: Sampler) -> Self {
+        Self::Sampler(value)
+    }
+}
+
+impl<T: Into<UniformValue>> From<T> for BindingValue {
+    #[inline]
+    fn from(value: T) -> Self {
+        Self::Uniform(value.into())
+    }
+}
+
+macro_rules! impl_uniform_from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+        impl From<$ty> for UniformValue {
+            #[inline]
+            fn from(value: $ty) -> Self {
+                Self::$variant(value)
+            }
+        }
+        )*
+    };
+}
+
+impl_uniform_from!(
+    i32 => Int,
+    u32 => Uint,
+    f32 => Float,
+    Vec2<f32> => Vec2,
+    Vec3<f32> => Vec3,
+    Vec4<f32> => Vec4,
+    Mat2<f32> => Mat2,
+    Mat3<f32> => Mat3,
+    Mat4<f32> => Mat4
+);
+
+impl<const N: usize> From<[Vec4<f32>; N]> for UniformValue {
+    #[inline]
+    fn from(value: [Vec4<f32>; N]) -> Self {
+        Self::block_slice(&value)
+    }
+}
@@ -32,6 +32,9 @@ pub enum BlendMode {
 
     /// Pixels are multiplicatively blended into the backdrop.
     Multiply,
+
+    /// Pixels overwrite the backdrop, including its alpha.
+    Replace,
 }
 
 impl Into<BlendState> for BlendMode {
@@ -85,6 +88,7 @@ impl Into<BlendState> for BlendMode {
                     operation: BlendOperation::Add,
                 },
             },
+            Self::Replace => BlendState::REPLACE,
         }
     }
 }
@@ -1,4 +1,4 @@
-use crate::gfx::{IndexBuffer, Vertex, VertexBuffer};
+use crate::gfx::{GpuMemory, IndexBuffer, Vertex, VertexBuffer};
 use std::collections::HashMap;
 use wgpu::{Device, Queue};
 
@@ -24,6 +24,7 @@ impl BufferCache {
         &mut self,
         device: &Device,
         queue: &Queue,
+        memory: &GpuMemory,
         vertices: &[Vertex],
         indices: &[u32],
     ) -> (VertexBuffer, IndexBuffer) {
@@ -32,7 +33,7 @@ impl BufferCache {
             let cache = self.vertices.entry(size).or_insert_with(Vec::new);
             let buffer = cache
                 .pop()
-                .unwrap_or_else(|| VertexBuffer::new(device, queue.clone(), size));
+                .unwrap_or_else(|| VertexBuffer::new(device, queue.clone(), memory, size));
             buffer
                 .upload(vertices)
                 .expect("cache gave invalid vertex buffer size");
@@ -44,7 +45,7 @@ impl BufferCache {
             let cache = self.indices.entry(size).or_insert_with(Vec::new);
             let buffer = cache
                 .pop()
-                .unwrap_or_else(|| IndexBuffer::new(device, queue.clone(), size));
+                .unwrap_or_else(|| IndexBuffer::new(device, queue.clone(), memory, size));
             buffer
                 .upload(indices)
                 .expect("cache gave invalid index buffer size");
@@ -0,0 +1,225 @@
+use crate::core::ScreenEffects;
+use crate::gfx::{CameraShake, Draw, DrawError};
+use crate::math::{Affine2F, Angle, Radians, RadiansF, RectF, Vec2F, vec2};
+
+#[cfg(feature = "lua")]
+pub type Camera2DObj = fey_lua::UserDataOf<Camera2D>;
+#[cfg(feature = "lua")]
+pub type Camera2DRef = mlua::UserDataRef<Camera2D>;
+#[cfg(feature = "lua")]
+pub type Camera2DMut = mlua::UserDataRefMut<Camera2D>;
+
+/// A 2D camera that looks at a point in the world, drawing it to a viewport on the screen.
+///
+/// The camera's [`pos`](Self::pos) is drawn at the center of its viewport, scaled by its
+/// [`zoom`](Self::zoom) and rotated by its [`rotation`](Self::rotation). Drawing between
+/// [`apply`](Self::apply) and [`pop`](Self::pop) happens in world space, and positions can be
+/// converted between the two spaces, such as to find where the mouse is in the world.
+///
+/// ```ignore
+/// camera.follow(player.pos, 8.0, time.delta());
+/// let cursor = camera.screen_to_world(screen.mouse_pos());
+///
+/// camera.apply(draw);
+/// world.draw(draw);
+/// camera.pop(draw)?;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Camera2D {
+    /// The point in the world at the center of the viewport.
+    pub pos: Vec2F,
+
+    /// How much the world is scaled, where `2.0` draws everything twice as big.
+    pub zoom: f32,
+
+    /// Rotation of the camera. The world is drawn rotated the opposite way.
+    pub rotation: RadiansF,
+
+    /// Where the camera draws to, in screen space.
+    pub viewport: RectF,
+
+    /// If set, the camera is kept from showing anything outside of these bounds whenever it
+    /// [follows](Self::follow) a target.
+    pub bounds: Option<RectF>,
+
+    /// Screen shake applied to the view, advanced by [`update`](Self::update).
+    pub shake: CameraShake,
+
+    effects_offset: Vec2F,
+}
+
+impl Camera2D {
+    /// Create a camera looking at the origin of the world, drawing to the viewport.
+    #[inline]
+    pub fn new(viewport: impl Into<RectF>) -> Self {
+        Self {
+            pos: Vec2F::ZERO,
+            zoom: 1.0,
+            rotation: RadiansF::ZERO,
+            viewport: viewport.into(),
+            bounds: None,
+            shake: CameraShake::new(),
+            effects_offset: Vec2F::ZERO,
+        }
+    }
+
+    /// Return the camera looking at a different position.
+    #[inline]
+    pub fn with_pos(mut self, pos: impl Into<Vec2F>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    /// Return the camera with a different zoom.
+    #[inline]
+    pub fn with_zoom(mut self, zoom: f32) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Return the camera with a different rotation.
+    #[inline]
+    pub fn with_rotation(mut self, rotation: impl Angle<f32>) -> Self {
+        self.rotation = rotation.to_radians();
+        self
+    }
+
+    /// Return the camera kept within the bounds.
+    #[inline]
+    pub fn with_bounds(mut self, bounds: impl Into<Option<RectF>>) -> Self {
+        self.bounds = bounds.into();
+        self
+    }
+
+    /// Return the camera with its shake changed.
+    #[inline]
+    pub fn with_shake(mut self, shake: CameraShake) -> Self {
+        self.shake = shake;
+        self
+    }
+
+    /// Add trauma to the camera's [shake](CameraShake), such as `0.3` for a hit or `0.8` for an
+    /// explosion.
+    #[inline]
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.shake.add_trauma(amount);
+    }
+
+    /// Advance the camera's shake. Call this once per frame.
+    #[inline]
+    pub fn update(&mut self, delta_time: f32) {
+        self.shake.update(delta_time);
+    }
+
+    /// Pick up the game-wide [screen shake](ScreenEffects::shake), on top of the camera's own.
+    /// Call this once per frame.
+    #[inline]
+    pub fn sync_effects(&mut self, effects: &ScreenEffects) {
+        self.effects_offset = effects.shake_offset();
+    }
+
+    /// The transform from world space to screen space, including shake.
+    #[inline]
+    pub fn view_matrix(&self) -> Affine2F {
+        let rotation = self.rotation.0 + self.shake.rotation().0;
+        Affine2F::translation(self.viewport.center() + self.shake.offset() + self.effects_offset)
+            * Affine2F::rotation(Radians(-rotation))
+            * Affine2F::scale_of(self.zoom)
+            * Affine2F::translation(-self.pos)
+    }
+
+    /// The transform from screen space to world space.
+    #[inline]
+    pub fn inv_view_matrix(&self) -> Affine2F {
+        self.view_matrix().inverse().unwrap_or(Affine2F::IDENTITY)
+    }
+
+    /// Convert a position in the world to a position on the screen.
+    #[inline]
+    pub fn world_to_screen(&self, pos: impl Into<Vec2F>) -> Vec2F {
+        self.view_matrix().transform_pos2(pos.into())
+    }
+
+    /// Convert a position on the screen to a position in the world, such as to find which
+    /// tile the mouse is over.
+    #[inline]
+    pub fn screen_to_world(&self, pos: impl Into<Vec2F>) -> Vec2F {
+        self.inv_view_matrix().transform_pos2(pos.into())
+    }
+
+    /// The area of the world that is visible in the viewport. If the camera is rotated, this
+    /// is the bounding box of the visible area.
+    pub fn visible_rect(&self) -> RectF {
+        let inv = self.inv_view_matrix();
+        let vp = self.viewport;
+        let corners = [
+            vp.top_left(),
+            vp.top_left() + vec2(vp.w, 0.0),
+            vp.bottom_right(),
+            vp.top_left() + vec2(0.0, vp.h),
+        ]
+        .map(|p| inv.transform_pos2(p));
+        let min = corners.iter().fold(corners[0], |a, &b| a.min(b));
+        let max = corners.iter().fold(corners[0], |a, &b| a.max(b));
+        RectF::pos_size(min, max - min)
+    }
+
+    /// Start drawing in world space. Drawing is clipped to the viewport. Must be followed by
+    /// [`pop`](Self::pop).
+    #[inline]
+    pub fn apply(&self, draw: &mut Draw) {
+        draw.push_clip_rect(self.viewport);
+        draw.push_transform(self.view_matrix());
+    }
+
+    /// Finish drawing in world space, started with [`apply`](Self::apply).
+    #[inline]
+    pub fn pop(&self, draw: &mut Draw) -> Result<(), DrawError> {
+        draw.pop_transform()?;
+        draw.pop_clip_rect()
+    }
+
+    /// Smoothly move towards the target. Higher `speed` catches up faster, closing about 63%
+    /// of the distance every `1 / speed` seconds, regardless of the frame rate.
+    pub fn follow(&mut self, target: impl Into<Vec2F>, speed: f32, delta_time: f32) {
+        let t = 1.0 - (-speed.max(0.0) * delta_time.max(0.0)).exp();
+        self.pos += (target.into() - self.pos) * t;
+        self.clamp_to_bounds();
+    }
+
+    /// Smoothly move towards the target only once it leaves a dead zone of `size` world units
+    /// around the center of the view, so small movements don't move the camera.
+    pub fn follow_deadzone(
+        &mut self,
+        target: impl Into<Vec2F>,
+        size: impl Into<Vec2F>,
+        speed: f32,
+        delta_time: f32,
+    ) {
+        let target = target.into();
+        let half = size.into() * 0.5;
+        let offset = target - self.pos;
+        let excess = offset - offset.max(-half).min(half);
+        self.follow(self.pos + excess, speed, delta_time);
+    }
+
+    /// Move the camera so it shows nothing outside of its [`bounds`](Self::bounds), if it has
+    /// any. If the bounds are smaller than the visible area, they are centered instead.
+    pub fn clamp_to_bounds(&mut self) {
+        let Some(bounds) = self.bounds else {
+            return;
+        };
+        let half = self.visible_rect().size() * 0.5;
+        let min = bounds.top_left() + half;
+        let max = bounds.bottom_right() - half;
+        let center = bounds.center();
+        let clamp = |pos: f32, min: f32, max: f32, center: f32| match min <= max {
+            true => pos.clamp(min, max),
+            false => center,
+        };
+        self.pos = vec2(
+            clamp(self.pos.x, min.x, max.x, center.x),
+            clamp(self.pos.y, min.y, max.y, center.y),
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use crate::math::{Radians, RadiansF, Vec2F, vec2};
+
+/// Screen shake driven by trauma, which a [`Camera2D`](crate::gfx::Camera2D) applies to its
+/// view.
+///
+/// Events like explosions [add trauma](Self::add_trauma), which decays over time. The camera
+/// shakes by smooth noise scaled by the square of the trauma, so small hits barely shake it
+/// while big ones shake it hard, and several hits in a row build on each other.
+///
+/// ```ignore
+/// camera.add_trauma(0.5);
+/// // every frame
+/// camera.update(ctx.time.delta());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CameraShake {
+    trauma: f32,
+    time: f32,
+
+    /// Furthest the view is offset at full trauma, in screen pixels.
+    pub max_offset: Vec2F,
+
+    /// Furthest the view is rotated at full trauma.
+    pub max_rotation: RadiansF,
+
+    /// How many times per second the shake changes direction.
+    pub frequency: f32,
+
+    /// How much trauma is removed every second.
+    pub decay: f32,
+
+    /// Seed for the shake's noise, so cameras with different seeds shake differently.
+    pub seed: u32,
+}
+
+impl Default for CameraShake {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CameraShake {
+    /// Create a shake with no trauma, offsetting up to 16 pixels and rotating up to about 5
+    /// degrees at full trauma.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            trauma: 0.0,
+            time: 0.0,
+            max_offset: vec2(16.0, 16.0),
+            max_rotation: Radians(0.1),
+            frequency: 15.0,
+            decay: 1.0,
+            seed: 0,
+        }
+    }
+
+    /// Return the shake with the furthest offset changed.
+    #[inline]
+    pub fn with_max_offset(mut self, max_offset: impl Into<Vec2F>) -> Self {
+        self.max_offset = max_offset.into();
+        self
+    }
+
+    /// Return the shake with the furthest rotation changed.
+    #[inline]
+    pub fn with_max_rotation(mut self, max_rotation: RadiansF) -> Self {
+        self.max_rotation = max_rotation;
+        self
+    }
+
+    /// Return the shake with the frequency changed.
+    #[inline]
+    pub fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Return the shake with the decay changed.
+    #[inline]
+    pub fn with_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+
+    /// Return the shake with the seed changed.
+    #[inline]
+    pub fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// How much trauma there is, from `0` to `1`.
+    #[inline]
+    pub fn trauma(&self) -> f32 {
+        self.trauma
+    }
+
+    /// Set the trauma, clamped from `0` to `1`.
+    #[inline]
+    pub fn set_trauma(&mut self, trauma: f32) {
+        self.trauma = trauma.clamp(0.0, 1.0);
+    }
+
+    /// Add trauma, such as `0.3` for a hit or `0.8` for an explosion, up to a maximum of `1`.
+    #[inline]
+    pub fn add_trauma(&mut self, amount: f32) {
+        self.set_trauma(self.trauma + amount);
+    }
+
+    /// How strongly the camera is shaking, which is the trauma squared.
+    #[inline]
+    pub fn intensity(&self) -> f32 {
+        self.trauma * self.trauma
+    }
+
+    /// Advance the shake and decay the trauma.
+    #[inline]
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time.max(0.0);
+        self.set_trauma(self.trauma - self.decay * delta_time.max(0.0));
+    }
+
+    /// How far the view is offset, in screen pixels.
+    #[inline]
+    pub fn offset(&self) -> Vec2F {
+        let s = self.intensity();
+        vec2(self.noise(0) * s, self.noise(1) * s) * self.max_offset
+    }
+
+    /// How far the view is rotated.
+    #[inline]
+    pub fn rotation(&self) -> RadiansF {
+        Radians(self.noise(2) * self.intensity() * self.max_rotation.0)
+    }
+
+    /// Smooth noise from `-1` to `1` over time, with a different pattern for each channel.
+    fn noise(&self, channel: u32) -> f32 {
+        let seed = self.seed.wrapping_mul(3).wrapping_add(channel);
+        shake_noise(self.time * self.frequency, seed)
+    }
+}
+
+/// Smooth noise from `-1` to `1`, changing direction once per whole number of `t`.
+pub(crate) fn shake_noise(t: f32, seed: u32) -> f32 {
+    let i = t.floor();
+    let f = t - i;
+    let a = hash(i as i32 as u32, seed);
+    let b = hash((i as i32 as u32).wrapping_add(1), seed);
+    a + (b - a) * (f * f * (3.0 - 2.0 * f))
+}
+
+/// A random value from `-1` to `1` for each integer and seed.
+#[inline]
+fn hash(x: u32, seed: u32) -> f32 {
+    let mut h = x.wrapping_mul(0x9e37_79b9) ^ seed.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x7feb_352d);
+    h ^= h >> 15;
+    h = h.wrapping_mul(0x846c_a68b);
+    h ^= h >> 16;
+    h as f32 / u32::MAX as f32 * 2.0 - 1.0
+}
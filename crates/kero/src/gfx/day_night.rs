@@ -0,0 +1,234 @@
+use crate::color::Rgba8;
+
+/// A part of the day, which gameplay can react to (eg. spawning monsters at night).
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DayPhase {
+    Dawn,
+    #[default]
+    Day,
+    Dusk,
+    Night,
+}
+
+/// The ambient light at an hour of the day. The light between keys is interpolated.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct AmbientKey {
+    /// Hour of the day, from `0` to `24`.
+    pub hour: f32,
+
+    /// The phase of the day that starts at this key.
+    pub phase: DayPhase,
+
+    /// Color of the ambient light.
+    pub color: Rgba8,
+
+    /// Brightness of the ambient light, where `1` is full brightness.
+    pub intensity: f32,
+}
+
+impl AmbientKey {
+    #[inline]
+    pub fn new(hour: f32, phase: DayPhase, color: Rgba8, intensity: f32) -> Self {
+        Self {
+            hour,
+            phase,
+            color,
+            intensity,
+        }
+    }
+}
+
+/// Drives a global ambient light over a 24-hour day.
+///
+/// The light is keyed at hours of the day, such as dawn, noon, dusk and night, and blended
+/// between them as time passes. Each frame, call [`update`](Self::update) to advance the time,
+/// which also reports when the [phase](DayPhase) of the day changes, then use
+/// [`ambient`](Self::ambient) as the color the scene is drawn with or as the ambient light of
+/// the scene's lighting.
+///
+/// ```ignore
+/// let mut sky = DayNight::new(8.0, 0.1);
+/// // every frame
+/// if sky.update(time.delta()) == Some(DayPhase::Night) {
+///     spawn_monsters();
+/// }
+/// draw.texture_at_ext(&world, Vec2F::ZERO, sky.ambient(), ColorMode::MULT);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DayNight {
+    hour: f32,
+    day: u32,
+    phase: DayPhase,
+    keys: Vec<AmbientKey>,
+
+    /// How many hours pass every second, such as `0.1` for a 4-minute day.
+    pub hours_per_second: f32,
+}
+
+impl DayNight {
+    /// Number of hours in a day.
+    pub const HOURS: f32 = 24.0;
+
+    /// Create a cycle starting at `hour`, using the [default keys](Self::default_keys).
+    pub fn new(hour: f32, hours_per_second: f32) -> Self {
+        let mut cycle = Self {
+            hour: 0.0,
+            day: 0,
+            phase: DayPhase::Day,
+            keys: Self::default_keys(),
+            hours_per_second,
+        };
+        cycle.set_hour(hour);
+        cycle
+    }
+
+    /// Warm light at dawn (6:00) and dusk (18:00), white light at noon (12:00), and dim blue
+    /// light at night (21:00 to 4:00).
+    pub fn default_keys() -> Vec<AmbientKey> {
+        let night = Rgba8::new(74, 90, 154, 255);
+        vec![
+            AmbientKey::new(4.0, DayPhase::Night, night, 0.35),
+            AmbientKey::new(6.0, DayPhase::Dawn, Rgba8::from_temperature(3000.0), 0.75),
+            AmbientKey::new(12.0, DayPhase::Day, Rgba8::WHITE, 1.0),
+            AmbientKey::new(18.0, DayPhase::Dusk, Rgba8::from_temperature(2500.0), 0.7),
+            AmbientKey::new(21.0, DayPhase::Night, night, 0.35),
+        ]
+    }
+
+    /// Replace the ambient keys. They do not need to be sorted. Panics if `keys` is empty.
+    pub fn with_keys(mut self, keys: Vec<AmbientKey>) -> Self {
+        self.set_keys(keys);
+        self
+    }
+
+    /// The ambient keys, sorted by hour.
+    #[inline]
+    pub fn keys(&self) -> &[AmbientKey] {
+        &self.keys
+    }
+
+    /// Replace the ambient keys. They do not need to be sorted. Panics if `keys` is empty.
+    pub fn set_keys(&mut self, mut keys: Vec<AmbientKey>) {
+        assert!(!keys.is_empty(), "day/night cycle needs at least one key");
+        for key in &mut keys {
+            key.hour = key.hour.rem_euclid(Self::HOURS);
+        }
+        keys.sort_by(|a, b| a.hour.total_cmp(&b.hour));
+        self.keys = keys;
+        self.phase = self.phase_at(self.hour);
+    }
+
+    /// The current hour of the day, from `0` up to `24`.
+    #[inline]
+    pub fn hour(&self) -> f32 {
+        self.hour
+    }
+
+    /// Jump to an hour of the day, wrapped to `0..24`. This does not change the day count.
+    #[inline]
+    pub fn set_hour(&mut self, hour: f32) {
+        self.hour = hour.rem_euclid(Self::HOURS);
+        self.phase = self.phase_at(self.hour);
+    }
+
+    /// How many days have passed.
+    #[inline]
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Set the day count, such as when loading a saved game.
+    #[inline]
+    pub fn set_day(&mut self, day: u32) {
+        self.day = day;
+    }
+
+    /// The current phase of the day.
+    #[inline]
+    pub fn phase(&self) -> DayPhase {
+        self.phase
+    }
+
+    /// The phase of the day at `hour`, which is the phase of the last key at or before it.
+    pub fn phase_at(&self, hour: f32) -> DayPhase {
+        self.keys[self.key_before(hour.rem_euclid(Self::HOURS))].phase
+    }
+
+    /// Advance time, returning the new phase if the phase of the day changed.
+    pub fn update(&mut self, delta_time: f32) -> Option<DayPhase> {
+        let hour = self.hour + delta_time * self.hours_per_second;
+        let days = hour.div_euclid(Self::HOURS);
+        self.day = (self.day as i64 + days as i64).max(0) as u32;
+        self.hour = hour.rem_euclid(Self::HOURS);
+
+        let phase = self.phase_at(self.hour);
+        (phase != self.phase).then(|| {
+            self.phase = phase;
+            phase
+        })
+    }
+
+    /// Color of the ambient light, without its intensity applied.
+    #[inline]
+    pub fn ambient_color(&self) -> Rgba8 {
+        self.sample(self.hour).0
+    }
+
+    /// Brightness of the ambient light, where `1` is full brightness.
+    #[inline]
+    pub fn intensity(&self) -> f32 {
+        self.sample(self.hour).1
+    }
+
+    /// The opaque ambient light, which is its color scaled by its intensity.
+    #[inline]
+    pub fn ambient(&self) -> Rgba8 {
+        self.ambient_at(self.hour)
+    }
+
+    /// The opaque ambient light at `hour`.
+    pub fn ambient_at(&self, hour: f32) -> Rgba8 {
+        let (color, intensity) = self.sample(hour.rem_euclid(Self::HOURS));
+        let scale = |c: u8| (c as f32 * intensity).round().clamp(0.0, 255.0) as u8;
+        Rgba8::new(scale(color.r), scale(color.g), scale(color.b), 255)
+    }
+
+    /// Tint a color by the ambient light, leaving its alpha unchanged.
+    #[inline]
+    pub fn tint(&self, color: Rgba8) -> Rgba8 {
+        color.mul_color(self.ambient())
+    }
+
+    /// Index of the last key at or before the hour, wrapping around to the last key.
+    #[inline]
+    fn key_before(&self, hour: f32) -> usize {
+        match self.keys.iter().rposition(|k| k.hour <= hour) {
+            Some(i) => i,
+            None => self.keys.len() - 1,
+        }
+    }
+
+    /// Blend the color and intensity of the keys around the hour.
+    fn sample(&self, hour: f32) -> (Rgba8, f32) {
+        let i = self.key_before(hour);
+        let prev = &self.keys[i];
+        let next = &self.keys[(i + 1) % self.keys.len()];
+        let span = (next.hour - prev.hour).rem_euclid(Self::HOURS);
+        let t = match span > 0.0 {
+            true => (hour - prev.hour).rem_euclid(Self::HOURS) / span,
+            false => 0.0,
+        };
+        let (a, b) = (prev.color, next.color);
+        let lerp = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        let color = Rgba8::new(
+            lerp(a.r, b.r),
+            lerp(a.g, b.g),
+            lerp(a.b, b.b),
+            lerp(a.a, b.a),
+        );
+        (
+            color,
+            prev.intensity + (next.intensity - prev.intensity) * t,
+        )
+    }
+}
@@ -1,21 +1,34 @@
 use crate::color::{Rgba8, Rgba64F, ToRgba};
 use crate::core::Window;
 use crate::gfx::buffer_cache::BufferCache;
+use crate::gfx::gpu_timer::GpuTimer;
+use crate::gfx::graphics::default_resources;
 use crate::gfx::{
-    BindingValue, BlendMode, ColorMode, DrawCall, FilterMode, Font, IndexBuffer, RenderData,
-    RenderLayer, RenderPass, Sampler, Shader, SubTexture, Surface, Texture, Topology, UniformValue,
+    AddressMode, BindingValue, BlendMode, ColorMode, DrawCall, FilterMode, Font, FrameStats, Glyph,
+    GpuMemory, IndexBuffer, Material, Mesh2D, RenderData, RenderLayer, RenderPass, Sampler, Shader,
+    SubTexture, SubpixelMode, Surface, TextLayout, Texture, TextureFormat, Topology, UniformValue,
     Vertex, VertexBuffer,
 };
+use crate::img::ImageRgba8;
 use crate::math::{
-    Affine2F, Angle, CircleF, LineF, Mat2F, Mat3F, Mat4F, Numeric, PolygonF, QuadF, RadiansF,
-    RectF, RectU, TriangleF, Vec2, Vec2F, Vec2U, Vec3F, Vec4F, vec2,
+    Affine2F, Angle, CircleF, Direction, FillRule, LineF, Mat2F, Mat3F, Mat4F, Numeric, PathF,
+    PolygonF, PolylineF, QuadF, RadiansF, RectF, RectU, TriangleF, Vec2, Vec2F, Vec2U, Vec3F,
+    Vec4F, triangulate, vec2,
 };
+#[cfg(feature = "svg")]
+use crate::svg::Svg;
+use bytemuck::Pod;
+use pollster::FutureExt;
 use std::collections::HashMap;
+use std::f32::consts::TAU;
 use std::fmt::{Debug, Formatter};
 use std::mem::{replace, swap};
 use wgpu::{
-    Color, CommandEncoderDescriptor, Device, IndexFormat, LoadOp, Operations, Queue,
-    RenderPassColorAttachment, RenderPassDescriptor, StoreOp, TextureViewDescriptor,
+    Buffer, BufferDescriptor, BufferUsages, COPY_BYTES_PER_ROW_ALIGNMENT, Color, CommandEncoder,
+    CommandEncoderDescriptor, Device, DeviceDescriptor, IndexFormat, Instance, InstanceDescriptor,
+    LoadOp, MapMode, Operations, PollType, PowerPreference, Queue, RenderPassColorAttachment,
+    RenderPassDescriptor, RequestAdapterOptions, StoreOp, TexelCopyBufferInfo,
+    TexelCopyBufferLayout, TextureUsages, TextureViewDescriptor,
 };
 
 /// Rendering API.
@@ -27,6 +40,12 @@ pub struct Draw {
     matrix: Affine2F,
     matrix_stack: Vec<Affine2F>,
     clip_rect: Option<RectU>,
+    clip_stack: Vec<Option<RectU>>,
+    gpu_timer: Option<GpuTimer>,
+    capture: bool,
+    captured: Option<ImageRgba8>,
+    headless: Option<Texture>,
+    headless_frame: u64,
 }
 
 impl Debug for Draw {
@@ -40,14 +59,19 @@ impl Draw {
     pub(crate) fn new(
         device: Device,
         queue: Queue,
+        memory: GpuMemory,
         default_shader: Shader,
+        sdf_shader: Shader,
         default_texture: Texture,
     ) -> Self {
         Self {
+            gpu_timer: GpuTimer::new(&device, &queue),
             cache: DrawCache {
                 device,
                 queue,
+                memory,
                 default_shader,
+                sdf_shader,
                 default_texture,
                 samplers: HashMap::new(),
                 buffer_cache: BufferCache::default(),
@@ -56,6 +80,8 @@ impl Draw {
                 vertices_vecs: Vec::new(),
                 indices_vecs: Vec::new(),
                 window_size: Vec2U::ZERO,
+                post_textures: Vec::new(),
+                post_textures_used: Vec::new(),
             },
             data: RenderData::new(),
             pass: RenderPass::new(None, None, Vec::new()),
@@ -63,15 +89,104 @@ impl Draw {
             matrix: Affine2F::IDENTITY,
             matrix_stack: Vec::new(),
             clip_rect: None,
+            clip_stack: Vec::new(),
+            capture: false,
+            captured: None,
+            headless: None,
+            headless_frame: 0,
         }
     }
 
+    /// Create a renderer with no window, which draws to an offscreen texture instead. Returns
+    /// `None` if no graphics device is available.
+    ///
+    /// This is for benchmarks and automated tests, which can draw frames between
+    /// [`begin_headless_frame`](Self::begin_headless_frame) and
+    /// [`end_headless_frame`](Self::end_headless_frame) without running a game.
+    pub fn new_headless() -> Option<Self> {
+        let instance = Instance::new(&InstanceDescriptor::default());
+        let adapter = instance
+            .request_adapter(&RequestAdapterOptions {
+                power_preference: PowerPreference::HighPerformance,
+                force_fallback_adapter: false,
+                compatible_surface: None,
+            })
+            .block_on()
+            .ok()?;
+        let (device, queue) = adapter
+            .request_device(&DeviceDescriptor::default())
+            .block_on()
+            .ok()?;
+        let (default_shader, sdf_shader, memory, default_texture) =
+            default_resources(&device, &queue);
+        Some(Self::new(
+            device,
+            queue,
+            memory,
+            default_shader,
+            sdf_shader,
+            default_texture,
+        ))
+    }
+
+    /// Begin drawing a frame of a [headless](Self::new_headless) renderer, with the window
+    /// replaced by an offscreen texture of the size.
+    pub fn begin_headless_frame(&mut self, size: Vec2U) {
+        if self.headless.as_ref().is_none_or(|tex| tex.size() != size) {
+            let (device, queue) = (&self.cache.device, self.cache.queue.clone());
+            let texture = Texture::new(
+                device,
+                queue,
+                &self.cache.memory,
+                size,
+                TextureFormat::Rgba8,
+                true,
+            );
+            self.headless = Some(texture);
+        }
+        self.begin_frame(size);
+    }
+
+    /// Finish drawing a frame of a [headless](Self::new_headless) renderer, waiting for the
+    /// GPU to render it and returning its stats.
+    pub fn end_headless_frame(&mut self) -> FrameStats {
+        let pass = replace(&mut self.pass, RenderPass::new(None, None, Vec::new()));
+        pass.finish(&mut self.cache, &mut self.data.passes);
+
+        let target = self.headless.clone().expect("headless frame was not begun");
+        let mut encoder = self
+            .cache
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor { label: None });
+        self.headless_frame += 1;
+        let (stats, _) = self.encode_passes(&mut encoder, &target.0.texture, self.headless_frame);
+        self.cache.queue.submit([encoder.finish()]);
+        _ = self.cache.device.poll(PollType::wait_indefinitely());
+        stats
+    }
+
+    /// Capture the window's contents at the end of this frame, if the platform supports it.
+    #[inline]
+    pub(crate) fn request_capture(&mut self) {
+        self.capture = true;
+    }
+
+    /// Take the window contents captured at the end of the last frame, if any.
+    #[inline]
+    pub(crate) fn take_capture(&mut self) -> Option<ImageRgba8> {
+        self.captured.take()
+    }
+
     pub(crate) fn begin_frame(&mut self, window_size: Vec2U) {
         self.cache.window_size = window_size;
 
         // reset the buffer cache so the buffers can be reused
         self.cache.buffer_cache.reset();
 
+        // free the post textures from the previous frame, dropping any that went unused
+        let used = std::mem::take(&mut self.cache.post_textures_used);
+        self.cache.post_textures = used;
+
         // reclaim vectors from the render data so they can be reused
         for mut pass in self.data.passes.drain(..) {
             for mut layer in pass.layers.drain(..) {
@@ -101,6 +216,7 @@ impl Draw {
         self.matrix = Affine2F::IDENTITY;
         self.matrix_stack.clear();
         self.clip_rect = None;
+        self.clip_stack.clear();
     }
 
     pub(crate) fn end_frame(
@@ -108,12 +224,10 @@ impl Draw {
         frame: u64,
         surface: &wgpu::Surface<'static>,
         window: &Window,
-    ) {
+    ) -> FrameStats {
         // if the current render pass has anything in it, finish and submit it
-        let mut pass = replace(&mut self.pass, RenderPass::new(None, None, Vec::new()));
-        if pass.finish(&mut self.cache) {
-            self.data.passes.push(pass);
-        }
+        let pass = replace(&mut self.pass, RenderPass::new(None, None, Vec::new()));
+        pass.finish(&mut self.cache, &mut self.data.passes);
 
         // get the window surface
         let window_surface = surface
@@ -126,14 +240,46 @@ impl Draw {
             .device
             .create_command_encoder(&CommandEncoderDescriptor { label: None });
 
+        let (stats, timed) = self.encode_passes(&mut encoder, &window_surface.texture, frame);
+
+        // copy the window into a buffer if a capture was requested
+        let capture = match std::mem::take(&mut self.capture)
+            && window_surface
+                .texture
+                .usage()
+                .contains(TextureUsages::COPY_SRC)
+        {
+            true => Some(self.copy_to_buffer(&mut encoder, &window_surface.texture)),
+            false => None,
+        };
+
+        self.cache.queue.submit([encoder.finish()]);
+        if let (true, Some(timer)) = (timed, self.gpu_timer.as_mut()) {
+            timer.map();
+        }
+        if let Some((buffer, padded_row)) = capture {
+            self.captured = Some(self.read_capture(&window_surface.texture, &buffer, padded_row));
+        }
+        window.0.pre_present_notify();
+        window_surface.present();
+        window.0.request_redraw();
+        stats
+    }
+
+    /// Record every render pass of the frame, with passes that have no target drawing to the
+    /// window's texture. Returns the frame's stats and if the passes are being timed.
+    fn encode_passes(
+        &mut self,
+        encoder: &mut CommandEncoder,
+        window_tex: &wgpu::Texture,
+        frame: u64,
+    ) -> (FrameStats, bool) {
         // if there are no user-submitted render passes, clear the window black
         if self.data.passes.is_empty() {
             _ = encoder.begin_render_pass(&RenderPassDescriptor {
                 label: None,
                 color_attachments: &[Some(RenderPassColorAttachment {
-                    view: &window_surface
-                        .texture
-                        .create_view(&TextureViewDescriptor::default()),
+                    view: &window_tex.create_view(&TextureViewDescriptor::default()),
                     depth_slice: None,
                     resolve_target: None,
                     ops: Operations {
@@ -147,12 +293,25 @@ impl Draw {
             });
         }
 
+        // time the passes on the GPU if timestamp queries are enabled
+        let pass_count = self.data.passes.len().to_u32();
+        let timed = match self.gpu_timer.as_mut() {
+            Some(timer) => timer.begin(&self.cache.device, pass_count),
+            None => false,
+        };
+
+        let mut stats = FrameStats {
+            passes: self.data.passes.len(),
+            ..FrameStats::default()
+        };
+
         // perform the rest of our render passes
-        for pass in &self.data.passes {
-            let surface_tex = if let Some(surface) = pass.surface.as_ref() {
-                surface.texture().0.texture.clone()
+        for (pass_index, pass) in self.data.passes.iter().enumerate() {
+            let surface_tex = if let Some(target) = pass.target.as_ref() {
+                target.touch();
+                target.0.texture.clone()
             } else {
-                window_surface.texture.clone()
+                window_tex.clone()
             };
             let surface_format = surface_tex.format();
             let load = if let Some(clear_color) = pass.clear_color {
@@ -173,7 +332,10 @@ impl Draw {
                     },
                 })],
                 depth_stencil_attachment: None,
-                timestamp_writes: None,
+                timestamp_writes: match (timed, self.gpu_timer.as_ref()) {
+                    (true, Some(timer)) => Some(timer.writes(pass_index.to_u32())),
+                    _ => None,
+                },
                 occlusion_query_set: None,
             });
 
@@ -189,10 +351,15 @@ impl Draw {
                         call.blend_mode,
                     ));
 
+                    let size = surface_tex.size();
                     if let Some(RectU { x, y, w, h }) = call.clip_rect {
+                        // keep the rect inside the target, which wgpu requires
+                        let x = x.min(size.width);
+                        let y = y.min(size.height);
+                        let w = w.min(size.width - x);
+                        let h = h.min(size.height - y);
                         wgpu_pass.set_scissor_rect(x, y, w, h);
                     } else {
-                        let size = surface_tex.size();
                         wgpu_pass.set_scissor_rect(0, 0, size.width, size.height);
                     };
 
@@ -225,14 +392,97 @@ impl Draw {
 
                     // perform the draw call
                     wgpu_pass.draw_indexed(0..call.indices.count().to_u32(), 0, 0..1);
+
+                    // mark the resources as used for leak detection
+                    call.vertices.touch();
+                    call.indices.touch();
+                    for value in &call.bindings.values {
+                        if let BindingValue::Texture(texture) = value {
+                            texture.touch();
+                        }
+                    }
+
+                    stats.draw_calls += 1;
+                    stats.vertices += call.vertices.count();
+                    stats.indices += call.indices.count();
                 }
             }
         }
 
-        self.cache.queue.submit([encoder.finish()]);
-        window.0.pre_present_notify();
-        window_surface.present();
-        window.0.request_redraw();
+        if let Some(timer) = self.gpu_timer.as_mut() {
+            if timed {
+                timer.resolve(encoder, pass_count);
+            }
+            stats.pass_times.clone_from(&timer.times);
+        }
+
+        (stats, timed)
+    }
+
+    /// Copy a texture into a buffer that can be read back, returning it and its padded row
+    /// size.
+    fn copy_to_buffer(
+        &self,
+        encoder: &mut CommandEncoder,
+        texture: &wgpu::Texture,
+    ) -> (Buffer, u32) {
+        let size = texture.size();
+        let padded_row = (size.width * 4).next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = self.cache.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (padded_row * size.height) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        encoder.copy_texture_to_buffer(
+            texture.as_image_copy(),
+            TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(size.height),
+                },
+            },
+            size,
+        );
+        (buffer, padded_row)
+    }
+
+    /// Wait for a copied texture and read it back as an opaque image.
+    fn read_capture(
+        &self,
+        texture: &wgpu::Texture,
+        buffer: &Buffer,
+        padded_row: u32,
+    ) -> ImageRgba8 {
+        buffer.map_async(MapMode::Read, .., |_| {});
+        self.cache
+            .device
+            .poll(PollType::wait_indefinitely())
+            .expect("failed to capture window");
+
+        // strip the row padding, swizzle to rgba, and drop the alpha the compositor may use
+        let size = texture.size();
+        let bgra = matches!(
+            texture.format(),
+            wgpu::TextureFormat::Bgra8Unorm | wgpu::TextureFormat::Bgra8UnormSrgb
+        );
+        let row_len = (size.width * 4) as usize;
+        let mut bytes = Vec::with_capacity(row_len * size.height as usize);
+        for row in buffer
+            .get_mapped_range(..)
+            .chunks_exact(padded_row as usize)
+        {
+            for px in row[..row_len].chunks_exact(4) {
+                match bgra {
+                    true => bytes.extend_from_slice(&[px[2], px[1], px[0], 255]),
+                    false => bytes.extend_from_slice(&[px[0], px[1], px[2], 255]),
+                }
+            }
+        }
+        buffer.unmap();
+        ImageRgba8::from_raw(Vec2U::new(size.width, size.height), bytes)
     }
 
     /// Set the target surface and optionally clear it with a single color. If `None` is passed
@@ -246,7 +496,7 @@ impl Draw {
     ) {
         let surface = surface.into();
         let clear_color = clear_color.into();
-        let mut prev = replace(
+        let prev = replace(
             &mut self.pass,
             RenderPass::new(
                 surface,
@@ -255,9 +505,13 @@ impl Draw {
             ),
         );
         self.pass.ensure_layer(self.layer, &mut self.cache);
-        if prev.finish(&mut self.cache) {
-            self.data.passes.push(prev);
-        }
+        prev.finish(&mut self.cache, &mut self.data.passes);
+    }
+
+    /// The target surface, or `None` if drawing to the window.
+    #[inline]
+    pub fn surface(&self) -> Option<&Surface> {
+        self.pass.surface.as_ref()
     }
 
     /// Set the target layer. For the most part you will be rendering to the default layer `0`,
@@ -271,6 +525,24 @@ impl Draw {
         self.pass.ensure_layer(layer, &mut self.cache);
     }
 
+    /// The post material of the current layer.
+    #[inline]
+    pub fn layer_post(&mut self) -> Option<&Material> {
+        self.pass.layer(self.layer).post.as_ref()
+    }
+
+    /// Set a post material for the current layer. When the current surface is finished,
+    /// everything drawn to the layer is rendered to an offscreen texture, which is then drawn
+    /// over the surface as a single quad through the material's shader (as its `main_texture`)
+    /// at the layer's depth. This makes it easy to run one layer through an effect, such as a
+    /// wavy underwater distortion.
+    ///
+    /// Like the rest of the layer's state, this is reset when the surface changes.
+    #[inline]
+    pub fn set_layer_post(&mut self, material: impl Into<Option<Material>>) {
+        self.pass.layer(self.layer).post = material.into();
+    }
+
     /// Set the shader future drawing methods will use. If the shader is already in use, nothing
     /// will happen. If not, the shader will switch and all the new shader's parameters will be
     /// initialized with their default values.
@@ -284,6 +556,22 @@ impl Draw {
             .set_shader(&shader, &mut self.cache);
     }
 
+    /// Set the material future drawing methods will use. This switches to the material's shader
+    /// (see [`set_shader`](Self::set_shader)) and then assigns all the parameters the material
+    /// has set. If they already have those values, the current batch is not interrupted.
+    #[inline]
+    pub fn set_material(&mut self, material: &Material) {
+        self.pass
+            .layer(self.layer)
+            .set_material(material, &mut self.cache);
+    }
+
+    /// The shader currently in use.
+    #[inline]
+    pub fn shader(&mut self) -> &Shader {
+        &self.pass.layer(self.layer).shader
+    }
+
     /// Set an `i32` parameter.
     #[inline]
     pub fn set_param_i32(&mut self, name: &str, value: i32) {
@@ -374,6 +662,28 @@ impl Draw {
         );
     }
 
+    /// Set a struct or array parameter declared as `var<uniform>`. The value's bytes must
+    /// match the WGSL type's layout and size. See [`UniformValue::block`].
+    #[inline]
+    pub fn set_param_block<T: Pod>(&mut self, name: &str, value: &T) {
+        self.pass.layer(self.layer).set_param(
+            name,
+            BindingValue::Uniform(UniformValue::block(value)),
+            &mut self.cache,
+        );
+    }
+
+    /// Set an array parameter declared as `var<uniform>` from a slice, such as `&[Vec4F]`.
+    /// The slice's bytes must match the WGSL array's size.
+    #[inline]
+    pub fn set_param_block_slice<T: Pod>(&mut self, name: &str, values: &[T]) {
+        self.pass.layer(self.layer).set_param(
+            name,
+            BindingValue::Uniform(UniformValue::block_slice(values)),
+            &mut self.cache,
+        );
+    }
+
     /// Set a `texture_2d<f32>` parameter.
     #[inline]
     pub fn set_param_texture(&mut self, name: &str, value: Texture) {
@@ -447,6 +757,39 @@ impl Draw {
             .set_scissor_rect(self.clip_rect, &mut self.cache);
     }
 
+    /// Size of the clipping rectangle stack.
+    #[inline]
+    pub fn clip_rect_count(&self) -> usize {
+        self.clip_stack.len()
+    }
+
+    /// Push a clipping rectangle to the top of the stack. The rectangle is transformed by the
+    /// current transform into surface space (using its bounding box if rotated), and then
+    /// intersected with the current clipping rectangle, so nested clip regions never draw
+    /// outside their parents.
+    pub fn push_clip_rect(&mut self, rect: impl Into<RectF>) {
+        let [a, b, c, d] = self.matrix.transform_rect(rect.into()).0;
+        let min = a.min(b).min(c.min(d)).floor().max(Vec2F::ZERO);
+        let max = a.max(b).max(c.max(d)).ceil().max(min);
+        let size = max - min;
+        let mut clip = RectU::new(min.x as u32, min.y as u32, size.x as u32, size.y as u32);
+        if let Some(curr) = self.clip_rect {
+            clip = curr
+                .overlap(&clip)
+                .unwrap_or(RectU::new(clip.x, clip.y, 0, 0));
+        }
+        self.clip_stack.push(self.clip_rect);
+        self.set_clip_rect(clip);
+    }
+
+    /// Pop a clipping rectangle off the top of the stack, restoring the previous one.
+    #[inline]
+    pub fn pop_clip_rect(&mut self) -> Result<(), DrawError> {
+        let prev = self.clip_stack.pop().ok_or(DrawError::NoClipRectToPop)?;
+        self.set_clip_rect(prev);
+        Ok(())
+    }
+
     /// Size of the transform stack.
     #[inline]
     pub fn transform_count(&self) -> usize {
@@ -612,6 +955,80 @@ impl Draw {
         self.textured_quad_ext(texture, quad, Rgba8::WHITE, ColorMode::MULT);
     }
 
+    /// Draw a quad filled with the region of a texture specified by `uv_rect`, in normalized
+    /// texture coordinates. Coordinates outside of `0..1` will wrap or clamp depending on the
+    /// address modes of the [main sampler](Self::main_sampler).
+    #[inline]
+    pub fn textured_quad_uv(
+        &mut self,
+        texture: impl AsRef<Texture>,
+        quad: impl Into<QuadF>,
+        uv_rect: impl Into<RectF>,
+        color: Rgba8,
+        mode: ColorMode,
+    ) {
+        let (verts, inds, mat) = self.tex_mode(texture.as_ref());
+        let [a, b, c, d] = quad.into().0.map(|p| mat.transform_pos2(p));
+        let [aa, bb, cc, dd] = uv_rect.into().corners();
+        let i = verts.len() as u32;
+        verts.extend_from_slice(&[
+            Vertex::new(a, aa, color, mode),
+            Vertex::new(b, bb, color, mode),
+            Vertex::new(c, cc, color, mode),
+            Vertex::new(d, dd, color, mode),
+        ]);
+        inds.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+    }
+
+    /// Fill a rectangle by repeating a texture at its native size. The `uv_scale` multiplies
+    /// the texture coordinates (so `2.0` tiles twice as densely), and `uv_offset` scrolls them
+    /// in units of the texture's size, making it easy to draw scrolling backgrounds.
+    #[inline]
+    pub fn texture_tiled(
+        &mut self,
+        texture: impl AsRef<Texture>,
+        dst: impl Into<RectF>,
+        uv_scale: impl Into<Vec2F>,
+        uv_offset: impl Into<Vec2F>,
+    ) {
+        self.texture_tiled_ext(
+            texture,
+            dst,
+            uv_scale,
+            uv_offset,
+            Rgba8::WHITE,
+            ColorMode::MULT,
+        );
+    }
+
+    /// Fill a rectangle by repeating a texture at its native size. The `uv_scale` multiplies
+    /// the texture coordinates (so `2.0` tiles twice as densely), and `uv_offset` scrolls them
+    /// in units of the texture's size, making it easy to draw scrolling backgrounds.
+    pub fn texture_tiled_ext(
+        &mut self,
+        texture: impl AsRef<Texture>,
+        dst: impl Into<RectF>,
+        uv_scale: impl Into<Vec2F>,
+        uv_offset: impl Into<Vec2F>,
+        color: Rgba8,
+        mode: ColorMode,
+    ) {
+        let texture = texture.as_ref();
+        let dst = dst.into();
+        let tiles = dst.size() / texture.size().to_f32() * uv_scale.into();
+        let uv_rect = RectF::pos_size(uv_offset.into(), tiles);
+
+        // tiling requires the sampler to repeat, so temporarily switch to it
+        let prev_sampler = self.main_sampler();
+        self.set_main_sampler(Sampler {
+            address_x: AddressMode::Repeat,
+            address_y: AddressMode::Repeat,
+            ..prev_sampler
+        });
+        self.textured_quad_uv(texture, dst, uv_rect, color, mode);
+        self.set_main_sampler(prev_sampler);
+    }
+
     /// Draw a texture with the top-left at the provided position.
     #[inline]
     pub fn texture_at_flipped(
@@ -701,6 +1118,46 @@ impl Draw {
         }
     }
 
+    /// Draw a dashed line. The `phase` offsets where the dash pattern starts, and can be
+    /// animated to make the dashes crawl along the line. If `dash_len` is zero, the line
+    /// will be dotted instead.
+    #[inline]
+    pub fn line_dashed(
+        &mut self,
+        line: impl Into<LineF>,
+        dash_len: f32,
+        gap_len: f32,
+        color: Rgba8,
+        phase: f32,
+    ) {
+        let line = line.into();
+        self.lines_dashed(line.points(), dash_len, gap_len, color, phase, false);
+    }
+
+    /// Draw dashed lines connecting the series of points into a chain, optionally looping to
+    /// the start. The dash pattern continues around corners.
+    pub fn lines_dashed(
+        &mut self,
+        points: impl IntoIterator<Item = Vec2F>,
+        dash_len: f32,
+        gap_len: f32,
+        color: Rgba8,
+        phase: f32,
+        loops: bool,
+    ) {
+        let mut chain: PolylineF = points.into_iter().collect();
+        if loops && let Some(&first) = chain.points().first() {
+            chain.push(first);
+        }
+        for dash in chain.dashed(&[dash_len.max(0.0), gap_len.max(0.0)], phase) {
+            if dash.length() > 0.0 {
+                self.lines(dash, color, false);
+            } else if let Some(&p) = dash.points().first() {
+                self.point(p, color);
+            }
+        }
+    }
+
     /// Draw a filled triangle.
     #[inline]
     pub fn triangle(&mut self, tri: impl Into<TriangleF>, color: Rgba8) {
@@ -773,6 +1230,20 @@ impl Draw {
         self.quad_outline(rect.into(), color);
     }
 
+    /// Draw a dashed rectangle outline. Animating the `phase` creates "marching ants".
+    #[inline]
+    pub fn rect_outline_dashed(
+        &mut self,
+        rect: impl Into<RectF>,
+        dash_len: f32,
+        gap_len: f32,
+        color: Rgba8,
+        phase: f32,
+    ) {
+        let quad: QuadF = rect.into().into();
+        self.lines_dashed(quad.0, dash_len, gap_len, color, phase, true);
+    }
+
     /// Draw a filled polygon.
     #[inline]
     pub fn polygon(&mut self, poly: &PolygonF, color: Rgba8) {
@@ -789,12 +1260,62 @@ impl Draw {
         }
     }
 
+    /// Draw a textured polygon, with a texture coordinate for each of its points. The polygon
+    /// can be concave, and will be triangulated internally.
+    #[inline]
+    pub fn textured_polygon(
+        &mut self,
+        texture: impl AsRef<Texture>,
+        points: &[Vec2F],
+        uvs: &[Vec2F],
+        color: Rgba8,
+    ) {
+        self.textured_polygon_ext(texture, points, uvs, color, ColorMode::MULT);
+    }
+
+    /// Draw a textured polygon, with a texture coordinate for each of its points. The polygon
+    /// can be concave, and will be triangulated internally.
+    pub fn textured_polygon_ext(
+        &mut self,
+        texture: impl AsRef<Texture>,
+        points: &[Vec2F],
+        uvs: &[Vec2F],
+        color: Rgba8,
+        mode: ColorMode,
+    ) {
+        let len = points.len().min(uvs.len());
+        let tris = triangulate(&points[..len]);
+        let (verts, inds, mat) = self.tex_mode(texture.as_ref());
+        let start = verts.len() as u32;
+        verts.extend(
+            points
+                .iter()
+                .zip(uvs)
+                .map(|(p, uv)| Vertex::new(mat.transform_pos2(*p), *uv, color, mode)),
+        );
+        inds.extend(tris.iter().flatten().map(|&i| start + i as u32));
+    }
+
     /// Draw a polygon outline.
     #[inline]
     pub fn polygon_outline(&mut self, poly: &PolygonF, color: Rgba8) {
         self.lines(poly.points().iter().copied(), color, true);
     }
 
+    /// Draw a dashed polygon outline.
+    #[inline]
+    pub fn polygon_outline_dashed(
+        &mut self,
+        poly: &PolygonF,
+        dash_len: f32,
+        gap_len: f32,
+        color: Rgba8,
+        phase: f32,
+    ) {
+        let points = poly.points().iter().copied();
+        self.lines_dashed(points, dash_len, gap_len, color, phase, true);
+    }
+
     #[inline]
     fn fan(&mut self, points: impl IntoIterator<Item = Vec2F>, color: Rgba8, loops: bool) {
         let (verts, inds, mat) = self.tri_mode();
@@ -858,6 +1379,165 @@ impl Draw {
         );
     }
 
+    /// Draw a dashed circle outline using the provided number of segments. If `None`, then
+    /// [`suggest_seg_count_f`](crate::math::Circle::suggest_seg_count) will be used.
+    #[inline]
+    pub fn circle_outline_dashed(
+        &mut self,
+        circ: impl Into<CircleF>,
+        dash_len: f32,
+        gap_len: f32,
+        color: Rgba8,
+        phase: f32,
+        seg_count: Option<u32>,
+    ) {
+        let circ = circ.into();
+        let seg_count = seg_count
+            .map(u32::to_f32)
+            .unwrap_or_else(|| circ.suggest_seg_count_f(|p| self.matrix.transform_pos2(p)));
+        let points = circ.iter_hull_points_n(seg_count, RadiansF::ZERO);
+        self.lines_dashed(points, dash_len, gap_len, color, phase, true);
+    }
+
+    /// How many segments to use for an arc sweeping `t` of the way around a circle.
+    #[inline]
+    fn arc_seg_count(&self, circ: &CircleF, t: f32, seg_count: Option<u32>) -> f32 {
+        let full = seg_count
+            .map(u32::to_f32)
+            .unwrap_or_else(|| circ.suggest_seg_count_f(|p| self.matrix.transform_pos2(p)));
+        (full * t).ceil().max(1.0)
+    }
+
+    /// Draw a pie-shaped fill of the circle, sweeping clockwise from `angle` and filled `t`
+    /// of the way around, from `0` to `1`. Useful for cooldown indicators. The number of
+    /// segments a full circle would use can be provided, or if `None`, then
+    /// [`suggest_seg_count_f`](crate::math::Circle::suggest_seg_count) will be used.
+    pub fn radial_fill(
+        &mut self,
+        circ: impl Into<CircleF>,
+        angle: impl Angle<f32>,
+        t: f32,
+        color: Rgba8,
+        seg_count: Option<u32>,
+    ) {
+        let circ = circ.into();
+        let t = t.clamp(0.0, 1.0);
+        if t <= 0.0 {
+            return;
+        }
+        let seg_count = self.arc_seg_count(&circ, t, seg_count);
+        self.fan(
+            Some(circ.center)
+                .into_iter()
+                .chain(circ.iter_arc_points_n(seg_count, angle, t * TAU)),
+            color,
+            false,
+        );
+    }
+
+    /// Draw a ring `thickness` units thick inside the edge of the circle, sweeping clockwise
+    /// from `angle` and filled `t` of the way around, from `0` to `1`. The number of segments
+    /// a full circle would use can be provided, or if `None`, then
+    /// [`suggest_seg_count_f`](crate::math::Circle::suggest_seg_count) will be used.
+    pub fn radial_ring(
+        &mut self,
+        circ: impl Into<CircleF>,
+        thickness: f32,
+        angle: impl Angle<f32>,
+        t: f32,
+        color: Rgba8,
+        seg_count: Option<u32>,
+    ) {
+        let circ = circ.into();
+        let t = t.clamp(0.0, 1.0);
+        if t <= 0.0 {
+            return;
+        }
+        let angle = angle.to_radians();
+        let seg_count = self.arc_seg_count(&circ, t, seg_count);
+        let inner = CircleF::new(circ.center, (circ.radius - thickness).max(0.0));
+        let outer = circ.iter_arc_points_n(seg_count, angle, t * TAU);
+        let inner = inner.iter_arc_points_n(seg_count, angle, t * TAU);
+
+        let (verts, inds, mat) = self.tri_mode();
+        let start = verts.len() as u32;
+        for (a, b) in outer.zip(inner) {
+            verts.push(Vertex::veto(mat.transform_pos2(a), color));
+            verts.push(Vertex::veto(mat.transform_pos2(b), color));
+        }
+        let end = verts.len() as u32;
+        for i in (start..end.saturating_sub(2)).step_by(2) {
+            inds.extend_from_slice(&[i, i + 2, i + 3, i, i + 3, i + 1]);
+        }
+    }
+
+    #[inline]
+    fn quads(&mut self, quads: &[QuadF], color: Rgba8) {
+        let (verts, inds, mat) = self.tri_mode();
+        for quad in quads {
+            let i = verts.len() as u32;
+            verts.extend(quad.0.map(|p| Vertex::veto(mat.transform_pos2(p), color)));
+            inds.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+        }
+    }
+
+    /// The flattening tolerance for paths, so curves are accurate to a quarter pixel
+    /// regardless of the current transform's scale.
+    #[inline]
+    fn path_tolerance(&self) -> f32 {
+        let scale = self
+            .matrix
+            .transform_vec2(Vec2F::X_AXIS)
+            .len()
+            .max(self.matrix.transform_vec2(Vec2F::Y_AXIS).len());
+        if scale > 0.0 { 0.25 / scale } else { 0.25 }
+    }
+
+    /// Draw a filled vector path, using the fill rule to determine which of its areas are inside.
+    pub fn path(&mut self, path: &PathF, rule: FillRule, color: Rgba8) {
+        let quads = path.fill(rule, self.path_tolerance());
+        self.quads(&quads, color);
+    }
+
+    /// Draw the outline of a vector path, `width` units thick.
+    pub fn path_outline(&mut self, path: &PathF, width: f32, color: Rgba8) {
+        self.path_outline_ext(path, width, color, &[], 0.0);
+    }
+
+    /// Draw the outline of a vector path, `width` units thick, split into dashes. The `dashes`
+    /// pattern alternates between the lengths of dashes and the gaps between them, and
+    /// `dash_offset` is how far into the pattern the outline starts.
+    pub fn path_outline_ext(
+        &mut self,
+        path: &PathF,
+        width: f32,
+        color: Rgba8,
+        dashes: &[f32],
+        dash_offset: f32,
+    ) {
+        let quads = path.stroke(width, self.path_tolerance(), dashes, dash_offset);
+        self.quads(&quads, color);
+    }
+
+    /// Draw all the shapes of an SVG document as vector paths.
+    #[cfg(feature = "svg")]
+    pub fn svg(&mut self, svg: &Svg) {
+        for shape in &svg.shapes {
+            if let Some(fill) = &shape.fill {
+                self.path(&shape.path, fill.rule, fill.color);
+            }
+            if let Some(stroke) = &shape.stroke {
+                self.path_outline_ext(
+                    &shape.path,
+                    stroke.width,
+                    stroke.color,
+                    &stroke.dashes,
+                    stroke.dash_offset,
+                );
+            }
+        }
+    }
+
     /// Draw a subtexture.
     #[inline]
     pub fn subtextured_quad_flipped(
@@ -917,7 +1597,7 @@ impl Draw {
         self.subtextured_quad_ext(sub, dst, Rgba8::WHITE, ColorMode::MULT);
     }
 
-    /// Draw a subtexture at the provided position.
+    /// Draw a subtexture at the provided position, flipped within its untrimmed frame.
     #[inline]
     pub fn subtexture_at_flipped(
         &mut self,
@@ -927,7 +1607,18 @@ impl Draw {
         mode: ColorMode,
         flip: impl Into<Vec2<bool>>,
     ) {
-        let dst = RectF::pos_size(pos.into(), sub.as_ref().rect.size());
+        // mirror the trimmed offset within the untrimmed frame so flipping stays in place
+        let sub = sub.as_ref();
+        let flip = flip.into();
+        let size = sub.rect.size();
+        let mut off = sub.offset;
+        if flip.x {
+            off.x = sub.size.x - off.x - size.x;
+        }
+        if flip.y {
+            off.y = sub.size.y - off.y - size.y;
+        }
+        let dst = RectF::pos_size(pos.into() + off, size);
         self.subtextured_quad_flipped(sub, dst, color, mode, flip);
     }
 
@@ -950,6 +1641,48 @@ impl Draw {
         self.subtexture_at_ext(sub, pos, Rgba8::WHITE, ColorMode::MULT);
     }
 
+    /// Draw a subtexture rotated and scaled around a pivot, which is placed at `pos`. The pivot is
+    /// relative to the top-left of the subtexture's untrimmed frame, so trimmed sprites rotate
+    /// around the same point as their original image.
+    #[inline]
+    pub fn subtexture_rotated(
+        &mut self,
+        sub: impl AsRef<SubTexture>,
+        pos: impl Into<Vec2F>,
+        angle: impl Angle<f32>,
+        pivot: impl Into<Vec2F>,
+        scale: impl Into<Vec2F>,
+        color: Rgba8,
+    ) {
+        self.subtexture_rotated_ext(sub, pos, angle, pivot, scale, color, ColorMode::MULT);
+    }
+
+    /// Draw a subtexture rotated and scaled around a pivot, which is placed at `pos`. The pivot is
+    /// relative to the top-left of the subtexture's untrimmed frame.
+    #[allow(clippy::too_many_arguments)]
+    pub fn subtexture_rotated_ext(
+        &mut self,
+        sub: impl AsRef<SubTexture>,
+        pos: impl Into<Vec2F>,
+        angle: impl Angle<f32>,
+        pivot: impl Into<Vec2F>,
+        scale: impl Into<Vec2F>,
+        color: Rgba8,
+        mode: ColorMode,
+    ) {
+        let sub = sub.as_ref();
+        let local = Affine2F::trs(pos.into(), angle, scale.into())
+            * Affine2F::translation(sub.offset - pivot.into());
+        let (verts, inds, mat) = self.tex_mode(&sub.texture);
+        let mat = *mat * local;
+        let corners = RectF::pos_size(Vec2F::ZERO, sub.rect.size()).corners();
+        let i = verts.len() as u32;
+        verts.extend_from_slice(&std::array::from_fn::<_, 4, _>(|i| {
+            Vertex::new(mat.transform_pos2(corners[i]), sub.coords[i], color, mode)
+        }));
+        inds.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+    }
+
     /// Draw text with the provided font and size.
     #[inline]
     pub fn text(
@@ -961,35 +1694,152 @@ impl Draw {
         size: impl Into<Option<f32>>,
     ) {
         let size = size.into().unwrap_or(font.size());
-        let prev_sampler = self.main_sampler();
-        let mag_filter = match font.pixelated() {
-            true => FilterMode::Nearest,
-            false => FilterMode::Linear,
-        };
-        if prev_sampler.mag_filter != mag_filter {
-            self.set_main_sampler(Sampler {
-                mag_filter,
-                ..prev_sampler
-            });
-        }
+        let prev = self.begin_font(font);
 
         self.push_translation(pos);
         self.push_scale_of(size / font.size());
 
+        // subpixel placement happens in screen-space, so we need to be able to map back
+        let subpixel = font.subpixel();
+        let inverse = match subpixel {
+            SubpixelMode::Off => None,
+            _ => self.matrix.inverse(),
+        };
+
         let mut cursor = Vec2F::ZERO;
         for chr in text.chars() {
             if let Some(g) = font.glyph(chr) {
-                if let Some(sub) = g.sub.as_ref() {
-                    self.subtexture_at_ext(sub, cursor, color, ColorMode::MULT);
-                }
+                self.glyph_at(g, cursor, subpixel, inverse, color);
                 cursor.x += g.adv;
             }
         }
 
         self.pop_transforms(2).unwrap();
+        self.end_font(prev);
+    }
+
+    /// Draw text that was laid out with the font, with the layout's top-left at `pos`.
+    pub fn text_layout(
+        &mut self,
+        layout: &TextLayout,
+        pos: impl Into<Vec2F>,
+        font: &Font,
+        color: Rgba8,
+    ) {
+        let prev = self.begin_font(font);
+        self.push_translation(pos.into());
+
+        let subpixel = font.subpixel();
+        let inverse = match subpixel {
+            SubpixelMode::Off => None,
+            _ => self.matrix.inverse(),
+        };
+        for lg in layout.glyphs() {
+            if let Some(g) = font.glyph(lg.chr) {
+                self.glyph_at(g, lg.pos, subpixel, inverse, color);
+            }
+        }
+
+        self.pop_transform().unwrap();
+        self.end_font(prev);
+    }
 
-        if prev_sampler.mag_filter != mag_filter {
-            self.set_main_sampler(prev_sampler);
+    /// Draw a glyph at the pen position, placing it in screen-space if `inverse` is provided.
+    #[inline]
+    fn glyph_at(
+        &mut self,
+        g: &Glyph,
+        cursor: Vec2F,
+        subpixel: SubpixelMode,
+        inverse: Option<Affine2F>,
+        color: Rgba8,
+    ) {
+        let (at, phase) = match inverse {
+            Some(inv) => {
+                let (at, phase) = subpixel.place(self.matrix.transform_pos2(cursor));
+                (inv.transform_pos2(at), phase)
+            }
+            None => (cursor, 0),
+        };
+        if let Some(sub) = g.phase_sub(phase) {
+            self.subtexture_at_ext(sub, at, color, ColorMode::MULT);
+        }
+    }
+
+    /// Draw text along a path, with each glyph's baseline resting on the path and rotated to
+    /// follow it. The text starts `offset` units along the path, `spacing` is added between each
+    /// glyph, and any glyphs that don't fit on the path are not drawn.
+    #[allow(clippy::too_many_arguments)]
+    pub fn text_on_path(
+        &mut self,
+        text: &str,
+        path: &PolylineF,
+        font: &Font,
+        color: Rgba8,
+        size: impl Into<Option<f32>>,
+        offset: f32,
+        spacing: f32,
+    ) {
+        let size = size.into().unwrap_or(font.size());
+        let scale = size / font.size();
+        let prev = self.begin_font(font);
+
+        let mut cursor = offset;
+        for chr in text.chars() {
+            let Some(g) = font.glyph(chr) else {
+                continue;
+            };
+            let adv = g.adv * scale;
+
+            // place the glyph by its center so it tilts evenly around curves
+            let center = cursor + adv * 0.5;
+            cursor += adv + spacing;
+            let Some((pos, dir)) = path.sample(center) else {
+                if center < 0.0 {
+                    continue;
+                }
+                break;
+            };
+            if let Some(sub) = g.sub.as_ref() {
+                self.push_trs(pos, RadiansF::from_vec2(dir), Vec2F::splat(scale));
+                self.subtexture_at_ext(sub, vec2(-g.adv * 0.5, 0.0), color, ColorMode::MULT);
+                self.pop_transform().unwrap();
+            }
+        }
+
+        self.end_font(prev);
+    }
+
+    /// Switch to the font's sampler filtering, and to the SDF shader if it's an SDF font,
+    /// returning the previous sampler and shader.
+    #[inline]
+    fn begin_font(&mut self, font: &Font) -> (Sampler, Option<Shader>) {
+        let prev_sampler = self.main_sampler();
+        let mag_filter = match font.pixelated() {
+            true => FilterMode::Nearest,
+            false => FilterMode::Linear,
+        };
+        self.set_main_sampler(Sampler {
+            mag_filter,
+            ..prev_sampler
+        });
+        let prev_shader = match font.sdf() {
+            true => {
+                let prev_shader = self.shader().clone();
+                self.set_shader(self.cache.sdf_shader.clone());
+                Some(prev_shader)
+            }
+            false => None,
+        };
+        (prev_sampler, prev_shader)
+    }
+
+    /// Restore the sampler and shader from before [`begin_font`](Self::begin_font).
+    #[inline]
+    fn end_font(&mut self, (prev_sampler, prev_shader): (Sampler, Option<Shader>)) {
+        self.set_main_sampler(prev_sampler);
+        if let Some(shader) = prev_shader {
+            self.set_shader(shader);
         }
     }
 
@@ -1018,6 +1868,41 @@ impl Draw {
         inds.extend(indices.into_iter().map(|i| len + i));
     }
 
+    /// Draw a retained mesh. The mesh's vertices are kept in GPU buffers, and are only
+    /// re-uploaded when the mesh is deformed or the current transform changes. Drawing the same
+    /// mesh multiple times in a frame with different transforms will upload it each time, and
+    /// only the last upload will be used, so draw separate meshes instead.
+    pub fn mesh(&mut self, mesh: &mut Mesh2D, texture: Option<&Texture>) {
+        if mesh.is_empty() {
+            return;
+        }
+        if mesh.buffers.is_none() {
+            let (vbuf, ibuf) = self.create_buffers(mesh.len(), mesh.indices().len());
+            ibuf.upload(mesh.indices()).unwrap();
+            mesh.buffers = Some((vbuf, ibuf));
+            mesh.uploaded = None;
+        }
+        let (vbuf, ibuf) = mesh.buffers.as_ref().unwrap();
+        if mesh.uploaded != Some(self.matrix) {
+            vbuf.upload(&mesh.vertices(&self.matrix)).unwrap();
+            mesh.uploaded = Some(self.matrix);
+        }
+        self.buffers(texture.cloned(), Topology::Triangles, vbuf, ibuf);
+    }
+
+    /// Create empty vertex and index buffers with room for the number of vertices and indices.
+    pub(crate) fn create_buffers(
+        &self,
+        vertices: usize,
+        indices: usize,
+    ) -> (VertexBuffer, IndexBuffer) {
+        let (device, queue, memory) = (&self.cache.device, &self.cache.queue, &self.cache.memory);
+        (
+            VertexBuffer::new(device, queue.clone(), memory, vertices),
+            IndexBuffer::new(device, queue.clone(), memory, indices),
+        )
+    }
+
     /// Draw the provided vertex/index buffers.
     #[inline]
     pub fn buffers(
@@ -1042,7 +1927,9 @@ impl Draw {
 pub(crate) struct DrawCache {
     pub device: Device,
     pub queue: Queue,
+    pub memory: GpuMemory,
     pub default_shader: Shader,
+    pub sdf_shader: Shader,
     pub default_texture: Texture,
     pub samplers: HashMap<Sampler, wgpu::Sampler>,
     pub buffer_cache: BufferCache,
@@ -1051,6 +1938,28 @@ pub(crate) struct DrawCache {
     pub vertices_vecs: Vec<Vec<Vertex>>,
     pub indices_vecs: Vec<Vec<u32>>,
     pub window_size: Vec2U,
+    pub post_textures: Vec<Texture>,
+    pub post_textures_used: Vec<Texture>,
+}
+
+impl DrawCache {
+    /// Get an offscreen texture to render a layer with a post material to, which stays in use
+    /// until the next frame.
+    pub fn post_texture(&mut self, size: Vec2U) -> Texture {
+        let texture = match self.post_textures.iter().position(|t| t.size() == size) {
+            Some(idx) => self.post_textures.swap_remove(idx),
+            None => Texture::new(
+                &self.device,
+                self.queue.clone(),
+                &self.memory,
+                size,
+                TextureFormat::Rgba8,
+                true,
+            ),
+        };
+        self.post_textures_used.push(texture.clone());
+        texture
+    }
 }
 
 /// A drawing error.
@@ -1058,4 +1967,7 @@ pub(crate) struct DrawCache {
 pub enum DrawError {
     #[error("no transform to pop")]
     NoTransformToPop,
+
+    #[error("no clip rect to pop")]
+    NoClipRectToPop,
 }
@@ -0,0 +1,127 @@
+use crate::color::{GreyAlpha, Rgba8};
+use crate::gfx::{AddressMode, ColorMode, Draw, Graphics, Sampler, Shader, Texture};
+use crate::math::{Numeric, RectF, Vec2F, vec2};
+
+/// Stock post-processing effects.
+///
+/// These are applied by drawing a source texture (usually a [`Surface`](super::Surface) the
+/// scene was rendered to) back out through an effect shader.
+#[derive(Debug, Clone)]
+pub struct Effects {
+    distortion: Shader,
+    noise: Texture,
+}
+
+impl Effects {
+    /// Size of the default noise texture.
+    pub const NOISE_SIZE: u32 = 128;
+
+    /// Compile the effect shaders and generate their default textures.
+    pub fn new(gfx: &Graphics) -> Self {
+        let size = Self::NOISE_SIZE;
+        Self {
+            distortion: gfx.create_shader(include_str!("shader_distortion.wgsl")),
+            noise: gfx.create_texture(vec2(size, size), &distortion_noise(size, 16, 0x5EED)),
+        }
+    }
+
+    /// The default seamless noise texture used for displacement.
+    #[inline]
+    pub fn noise(&self) -> &Texture {
+        &self.noise
+    }
+
+    /// Draw the region of the source texture with a water/heat-haze distortion. The pixels are
+    /// displaced by up to `strength` pixels, and `scroll` is the offset of the noise pattern,
+    /// measured in pattern repeats, which should be animated over time (eg. `time * 0.1`).
+    #[inline]
+    pub fn distortion(
+        &self,
+        draw: &mut Draw,
+        source: &Texture,
+        region: impl Into<RectF>,
+        strength: f32,
+        scroll: Vec2F,
+    ) {
+        let size = source.size().to_f32();
+        self.distortion_ext(
+            draw,
+            source,
+            region,
+            &self.noise,
+            size / Self::NOISE_SIZE as f32,
+            Vec2F::splat(strength),
+            scroll,
+        );
+    }
+
+    /// Draw the region of the source texture with a distortion, using a custom noise texture
+    /// whose red and green channels displace pixels horizontally and vertically, where `0.5` is
+    /// no displacement. The noise repeats `noise_scale` times across the source texture.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distortion_ext(
+        &self,
+        draw: &mut Draw,
+        source: &Texture,
+        region: impl Into<RectF>,
+        noise: &Texture,
+        noise_scale: Vec2F,
+        strength: Vec2F,
+        scroll: Vec2F,
+    ) {
+        let region = region.into();
+        let size = source.size().to_f32();
+        let prev = draw.shader().clone();
+        draw.set_shader(self.distortion.clone());
+        draw.set_param_texture("noise_texture", noise.clone());
+        draw.set_param_sampler("noise_sampler", Sampler::linear(AddressMode::Repeat));
+        draw.set_param_vec2("noise_scroll", scroll);
+        draw.set_param_vec2("noise_scale", noise_scale);
+        draw.set_param_vec2("strength", strength / size);
+        draw.textured_quad_uv(
+            source,
+            region,
+            RectF::new(
+                region.x / size.x,
+                region.y / size.y,
+                region.w / size.x,
+                region.h / size.y,
+            ),
+            Rgba8::WHITE,
+            ColorMode::MULT,
+        );
+        draw.set_shader(prev);
+    }
+}
+
+/// Generate seamless value noise, with independent noise in the grey and alpha channels.
+fn distortion_noise(size: u32, cells: u32, seed: u32) -> Vec<GreyAlpha<u8>> {
+    let hash = |x: u32, y: u32, ch: u32| {
+        let mut h = (x % cells)
+            .wrapping_mul(0x27d4_eb2d)
+            .wrapping_add((y % cells).wrapping_mul(0x1656_67b1))
+            .wrapping_add(ch.wrapping_mul(0x9e37_79b9))
+            ^ seed;
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 13;
+        (h & 0xffff) as f32 / 65535.0
+    };
+    let smooth = |t: f32| t * t * (3.0 - 2.0 * t);
+    let sample = |p: Vec2F, ch: u32| {
+        let (x, y) = (p.x.floor() as u32, p.y.floor() as u32);
+        let (tx, ty) = (smooth(p.x.fract()), smooth(p.y.fract()));
+        let top = hash(x, y, ch) * (1.0 - tx) + hash(x + 1, y, ch) * tx;
+        let bot = hash(x, y + 1, ch) * (1.0 - tx) + hash(x + 1, y + 1, ch) * tx;
+        top * (1.0 - ty) + bot * ty
+    };
+    let scale = cells as f32 / size as f32;
+    (0..size * size)
+        .map(|i| {
+            let p = vec2((i % size) as f32, (i / size) as f32) * scale;
+            let r = (sample(p, 0) * 255.0).round() as u8;
+            let g = (sample(p, 1) * 255.0).round() as u8;
+            GreyAlpha::new(r, g)
+        })
+        .collect()
+}
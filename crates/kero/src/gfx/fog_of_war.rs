@@ -0,0 +1,242 @@
+use crate::color::Rgba8;
+use crate::gfx::{Draw, Topology, Vertex};
+use crate::grid::{Grid, GridMut, VecGrid, field_of_view};
+use crate::math::{RectU, Vec2F, Vec2U, vec2};
+
+/// Visibility state of a cell in a [`FogOfWar`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum FogState {
+    /// The cell has never been seen.
+    #[default]
+    Unexplored,
+
+    /// The cell has been seen before, but isn't currently visible.
+    Explored,
+
+    /// The cell is currently visible.
+    Visible,
+}
+
+impl FogState {
+    #[inline]
+    fn opacity(self, explored_opacity: f32) -> f32 {
+        match self {
+            Self::Unexplored => 1.0,
+            Self::Explored => explored_opacity,
+            Self::Visible => 0.0,
+        }
+    }
+}
+
+/// Per-cell fog of war over a grid-based map.
+///
+/// Each frame, call [`update_fov`](Self::update_fov) (or [`clear_visible`](Self::clear_visible)
+/// and then [`reveal_fov`](Self::reveal_fov) for each viewer), then [`update`](Self::update) to
+/// fade the fog towards its new state, and finally [`draw`](Self::draw) it over the map.
+#[derive(Debug, Clone)]
+pub struct FogOfWar {
+    states: VecGrid<FogState>,
+    opacity: VecGrid<f32>,
+
+    /// Fog opacity over explored cells that aren't currently visible.
+    pub explored_opacity: f32,
+
+    /// How fast the fog fades in or out, in opacity per second.
+    pub fade_speed: f32,
+}
+
+impl FogOfWar {
+    /// Create a new fog of war where all cells are unexplored.
+    pub fn new(size: impl Into<Vec2U>) -> Self {
+        let size = size.into();
+        Self {
+            states: VecGrid::new_with(size, || FogState::Unexplored),
+            opacity: VecGrid::new_with(size, || 1.0),
+            explored_opacity: 0.5,
+            fade_speed: 4.0,
+        }
+    }
+
+    /// Size of the map, in cells.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.states.size()
+    }
+
+    /// State of the cell, or `None` if it is out of bounds.
+    #[inline]
+    pub fn state(&self, x: u32, y: u32) -> Option<FogState> {
+        self.states.get(x, y).copied()
+    }
+
+    /// If the cell is currently visible.
+    #[inline]
+    pub fn is_visible(&self, x: u32, y: u32) -> bool {
+        self.state(x, y) == Some(FogState::Visible)
+    }
+
+    /// If the cell has ever been seen.
+    #[inline]
+    pub fn is_explored(&self, x: u32, y: u32) -> bool {
+        self.state(x, y)
+            .is_some_and(|state| state != FogState::Unexplored)
+    }
+
+    /// Set the state of the cell.
+    #[inline]
+    pub fn set_state(&mut self, x: u32, y: u32, state: FogState) {
+        if let Some(s) = self.states.get_mut(x, y) {
+            *s = state;
+        }
+    }
+
+    /// Mark the cell as explored without making it visible, eg. when the player finds a map.
+    #[inline]
+    pub fn explore(&mut self, x: u32, y: u32) {
+        if let Some(s @ FogState::Unexplored) = self.states.get_mut(x, y) {
+            *s = FogState::Explored;
+        }
+    }
+
+    /// Make the cell visible.
+    #[inline]
+    pub fn reveal(&mut self, x: u32, y: u32) {
+        self.set_state(x, y, FogState::Visible);
+    }
+
+    /// Change all visible cells to explored.
+    pub fn clear_visible(&mut self) {
+        for state in self.states.as_mut_slice() {
+            if *state == FogState::Visible {
+                *state = FogState::Explored;
+            }
+        }
+    }
+
+    /// Reveal all cells visible from `origin` within `radius` cells, where `is_opaque(x, y)`
+    /// returns `true` for cells that block sight.
+    #[inline]
+    pub fn reveal_fov(
+        &mut self,
+        origin: impl Into<Vec2U>,
+        radius: u32,
+        is_opaque: impl FnMut(u32, u32) -> bool,
+    ) {
+        let size = self.size();
+        field_of_view(size, origin.into(), radius, is_opaque, |x, y| {
+            self.reveal(x, y)
+        });
+    }
+
+    /// Clear all visible cells, and then reveal the cells visible from `origin`.
+    #[inline]
+    pub fn update_fov(
+        &mut self,
+        origin: impl Into<Vec2U>,
+        radius: u32,
+        is_opaque: impl FnMut(u32, u32) -> bool,
+    ) {
+        self.clear_visible();
+        self.reveal_fov(origin, radius, is_opaque);
+    }
+
+    /// Target fog opacity for the state.
+    #[inline]
+    pub fn target_opacity(&self, state: FogState) -> f32 {
+        state.opacity(self.explored_opacity)
+    }
+
+    /// Current fog opacity of the cell.
+    #[inline]
+    pub fn opacity(&self, x: u32, y: u32) -> Option<f32> {
+        self.opacity.get(x, y).copied()
+    }
+
+    /// Fade each cell's fog towards the opacity of its state.
+    pub fn update(&mut self, delta_time: f32) {
+        let step = self.fade_speed * delta_time;
+        let states = self.states.as_slice().iter();
+        for (opacity, &state) in self.opacity.as_mut_slice().iter_mut().zip(states) {
+            let target = state.opacity(self.explored_opacity);
+            *opacity = if *opacity < target {
+                (*opacity + step).min(target)
+            } else {
+                (*opacity - step).max(target)
+            };
+        }
+    }
+
+    /// Immediately set each cell's fog to the opacity of its state, skipping the fade.
+    pub fn snap(&mut self) {
+        let states = self.states.as_slice().iter();
+        for (opacity, &state) in self.opacity.as_mut_slice().iter_mut().zip(states) {
+            *opacity = state.opacity(self.explored_opacity);
+        }
+    }
+
+    /// Draw the fog over the whole map, with each cell being `cell_size` large.
+    #[inline]
+    pub fn draw(&self, draw: &mut Draw, cell_size: impl Into<Vec2F>, color: Rgba8) {
+        let size = self.size();
+        self.draw_area(draw, cell_size, color, RectU::new(0, 0, size.x, size.y));
+    }
+
+    /// Draw the fog over an area of the map, such as the cells visible on screen. Opacity is
+    /// blended between neighboring cells, giving the fog soft edges.
+    pub fn draw_area(
+        &self,
+        draw: &mut Draw,
+        cell_size: impl Into<Vec2F>,
+        color: Rgba8,
+        area: RectU,
+    ) {
+        let cell_size = cell_size.into();
+        let size = self.size();
+        let (x0, y0) = (area.x.min(size.x), area.y.min(size.y));
+        let (x1, y1) = ((area.x + area.w).min(size.x), (area.y + area.h).min(size.y));
+
+        // the opacity at a cell corner is the average of the cells touching it
+        let corner = |x: u32, y: u32| {
+            let (mut sum, mut count) = (0.0, 0.0);
+            let (px, py) = (x.wrapping_sub(1), y.wrapping_sub(1));
+            for (cx, cy) in [(x, y), (px, y), (x, py), (px, py)] {
+                if let Some(&o) = self.opacity.get(cx, cy) {
+                    sum += o;
+                    count += 1.0;
+                }
+            }
+            sum / count
+        };
+        let vert = |x: u32, y: u32, opacity: f32| {
+            let pos = vec2(x as f32, y as f32) * cell_size;
+            let fade = |c: u8| (c as f32 * opacity).round() as u8;
+            let col = Rgba8::new(fade(color.r), fade(color.g), fade(color.b), fade(color.a));
+            Vertex::veto(pos, col)
+        };
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        for y in y0..y1 {
+            for x in x0..x1 {
+                let c = [
+                    corner(x, y),
+                    corner(x + 1, y),
+                    corner(x + 1, y + 1),
+                    corner(x, y + 1),
+                ];
+                if c.iter().all(|&o| o <= 0.0) {
+                    continue;
+                }
+                let i = vertices.len() as u32;
+                vertices.extend([
+                    vert(x, y, c[0]),
+                    vert(x + 1, y, c[1]),
+                    vert(x + 1, y + 1, c[2]),
+                    vert(x, y + 1, c[3]),
+                ]);
+                indices.extend([i, i + 1, i + 2, i, i + 2, i + 3]);
+            }
+        }
+        draw.custom(None, Topology::Triangles, vertices, indices);
+    }
+}
@@ -1,7 +1,8 @@
 use crate::gfx::{Graphics, Texture, TexturePacker};
+use crate::misc::Charset;
 use crate::prelude::SubTexture;
 use fey_font::Font as FeyFont;
-use fey_math::Vec2F;
+use fey_math::{Vec2F, vec2};
 use fnv::FnvHashMap;
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
@@ -13,10 +14,62 @@ pub type FontRef = mlua::UserDataRef<Font>;
 #[cfg(feature = "lua")]
 pub type FontMut = mlua::UserDataRefMut<Font>;
 
+/// How smooth glyphs are positioned when text is drawn at fractional pixel coordinates.
+///
+/// Placement happens in screen-space, so this works best when text is drawn unrotated and at
+/// the size the font was loaded with.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum SubpixelMode {
+    /// Glyphs are drawn exactly where they land, which can look blurry between pixels.
+    #[default]
+    Off,
+
+    /// Each glyph is snapped to the nearest whole pixel.
+    Snap,
+
+    /// Each glyph is pre-rasterized at this many horizontal offsets (eg. `4` gives offsets of
+    /// `0`, `0.25`, `0.5`, and `0.75`) and the closest one is picked when drawn. Vertical
+    /// positions are snapped to the nearest whole pixel.
+    Phases(u8),
+}
+
+impl SubpixelMode {
+    /// How many horizontal variants are rasterized per glyph.
+    #[inline]
+    pub fn phase_count(self) -> u8 {
+        match self {
+            Self::Phases(n) => n.max(1),
+            _ => 1,
+        }
+    }
+
+    /// Given a pen position in screen-space, returns the pixel-aligned position the glyph
+    /// should be drawn at and which phase variant to use.
+    #[inline]
+    pub fn place(self, pos: Vec2F) -> (Vec2F, u8) {
+        match self {
+            Self::Off => (pos, 0),
+            Self::Snap => (pos.round(), 0),
+            Self::Phases(_) => {
+                let n = self.phase_count();
+                let x = pos.x.floor();
+                let phase = ((pos.x - x) * n as f32).round() as u8;
+                match phase >= n {
+                    true => (vec2(x + 1.0, pos.y.round()), 0),
+                    false => (vec2(x, pos.y.round()), phase),
+                }
+            }
+        }
+    }
+}
+
 /// A drawable font.
+#[derive(Clone)]
 pub struct Font {
     size: f32,
     pixelated: bool,
+    subpixel: SubpixelMode,
+    sdf: bool,
     glyphs: FnvHashMap<char, Glyph>,
     kerning: FnvHashMap<(char, char), f32>,
 }
@@ -33,13 +86,33 @@ impl Debug for Font {
 pub(crate) struct Glyph {
     pub sub: Option<SubTexture>,
     pub adv: f32,
+    pub phases: Vec<Option<SubTexture>>,
+}
+
+impl Glyph {
+    /// The subtexture for the phase, falling back on the main one if it wasn't rasterized.
+    #[inline]
+    pub fn phase_sub(&self, phase: u8) -> Option<&SubTexture> {
+        match phase {
+            0 => self.sub.as_ref(),
+            n => match self.phases.get(n as usize - 1) {
+                Some(sub) => sub.as_ref(),
+                None => self.sub.as_ref(),
+            },
+        }
+    }
 }
 
 impl Font {
+    /// How many pixels the distance fields of SDF fonts extend past each glyph's edge.
+    pub const SDF_SPREAD: f32 = 4.0;
+
     pub fn new(size: f32, pixelated: bool) -> Self {
         Self {
             size,
             pixelated,
+            subpixel: SubpixelMode::Off,
+            sdf: false,
             glyphs: FnvHashMap::default(),
             kerning: FnvHashMap::default(),
         }
@@ -51,9 +124,35 @@ impl Font {
         size: f32,
         pixelated: bool,
         chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        Self::from_ttf_bytes_ext(gfx, font, size, pixelated, SubpixelMode::Off, chars)
+    }
+
+    /// Load a font from TTF bytes, choosing how smooth glyphs get positioned when drawn.
+    /// This is ignored for pixelated fonts, which are always drawn as-is.
+    pub fn from_ttf_bytes_ext(
+        gfx: &Graphics,
+        font: &[u8],
+        size: f32,
+        pixelated: bool,
+        subpixel: SubpixelMode,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        let font = FeyFont::from_slice(font, size)?;
+        Ok(Self::pack(gfx, font, pixelated, subpixel, false, chars))
+    }
+
+    /// Load a font from TTF bytes, rasterizing its glyphs as signed distance fields. These
+    /// are drawn with the [SDF shader](Graphics::sdf_shader), so text stays sharp when it's
+    /// scaled up or rotated. Glyphs are padded by [`SDF_SPREAD`](Self::SDF_SPREAD) pixels.
+    pub fn from_ttf_bytes_sdf(
+        gfx: &Graphics,
+        font: &[u8],
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
     ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
         let font = FeyFont::from_slice(font, size)?;
-        Ok(Self::pack(gfx, font, pixelated, chars))
+        Ok(Self::pack(gfx, font, false, SubpixelMode::Off, true, chars))
     }
 
     pub fn from_ttf_file(
@@ -62,37 +161,81 @@ impl Font {
         size: f32,
         pixelated: bool,
         chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        Self::from_ttf_file_ext(gfx, path, size, pixelated, SubpixelMode::Off, chars)
+    }
+
+    /// Load a font from a TTF file, choosing how smooth glyphs get positioned when drawn.
+    /// This is ignored for pixelated fonts, which are always drawn as-is.
+    pub fn from_ttf_file_ext(
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        size: f32,
+        pixelated: bool,
+        subpixel: SubpixelMode,
+        chars: impl IntoIterator<Item = char>,
     ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
         let font = FeyFont::from_file(path, size)?;
-        Ok(Self::pack(gfx, font, pixelated, chars))
+        Ok(Self::pack(gfx, font, pixelated, subpixel, false, chars))
+    }
+
+    /// Load a font from a TTF file, rasterizing its glyphs as signed distance fields. See
+    /// [`from_ttf_bytes_sdf`](Self::from_ttf_bytes_sdf).
+    pub fn from_ttf_file_sdf(
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        size: f32,
+        chars: impl IntoIterator<Item = char>,
+    ) -> Result<Option<(Self, Texture)>, fey_font::FontError> {
+        let font = FeyFont::from_file(path, size)?;
+        Ok(Self::pack(gfx, font, false, SubpixelMode::Off, true, chars))
     }
 
     fn pack(
         gfx: &Graphics,
         font: FeyFont<'_>,
         pixelated: bool,
+        subpixel: SubpixelMode,
+        sdf: bool,
         chars: impl IntoIterator<Item = char>,
     ) -> Option<(Self, Texture)> {
         let mut packer = TexturePacker::new();
 
-        // rasterize and pack all glyphs, collect their char/advance/offset
-        let chars: Vec<(char, f32, Vec2F)> = chars
+        // pixelated fonts never get subpixel variants
+        let subpixel = match pixelated {
+            true => SubpixelMode::Off,
+            false => subpixel,
+        };
+        let phases = subpixel.phase_count();
+
+        // rasterize and pack all glyphs (and their phase variants), collect their
+        // char/advance and the offset of each variant
+        let chars: Vec<(char, f32, Vec<Vec2F>)> = chars
             .into_iter()
             .enumerate()
             .map(|(i, chr)| {
-                let g = font.char_glyph(chr);
-                let raster = match pixelated {
-                    true => g.rasterize_pixelated(),
-                    false => g.rasterize_smooth(),
-                };
-                let off = match raster {
-                    Some(raster) => {
-                        packer.add_image(i, raster.image, None, None);
-                        raster.offset
-                    }
-                    None => Vec2F::ZERO,
-                };
-                (chr, g.advance(), off)
+                let offsets = (0..phases)
+                    .map(|phase| {
+                        let g = font.char_glyph(chr);
+                        let g = match phase {
+                            0 => g,
+                            _ => g.with_subpixel_offset(vec2(phase as f32 / phases as f32, 0.0)),
+                        };
+                        let raster = match (sdf, pixelated) {
+                            (true, _) => g.rasterize_sdf(Self::SDF_SPREAD),
+                            (false, true) => g.rasterize_pixelated(),
+                            (false, false) => g.rasterize_smooth(),
+                        };
+                        match raster {
+                            Some(raster) => {
+                                packer.add_image((i, phase), raster.image, None, None);
+                                raster.offset
+                            }
+                            None => Vec2F::ZERO,
+                        }
+                    })
+                    .collect();
+                (chr, font.char_glyph(chr).advance(), offsets)
             })
             .collect();
 
@@ -108,19 +251,24 @@ impl Font {
         }
 
         // pack the atlas
-        let (tex, mut subs) = packer.pack(gfx)?;
+        let atlas = packer.pack(gfx)?;
 
         // build the glyph list and apply offset to the subtextures
         let glyphs = chars
             .into_iter()
             .enumerate()
-            .map(|(i, (chr, adv, off))| {
-                let mut sub = subs.remove(&i);
-                if let Some(sub) = sub.as_mut() {
-                    sub.offset.x += off.x;
-                    sub.offset.y -= off.y;
-                };
-                (chr, Glyph { sub, adv })
+            .map(|(i, (chr, adv, offsets))| {
+                let mut variants = offsets.into_iter().enumerate().map(|(phase, off)| {
+                    let mut sub = atlas.get(&(i, phase as u8)).cloned();
+                    if let Some(sub) = sub.as_mut() {
+                        sub.offset.x += off.x;
+                        sub.offset.y -= off.y;
+                    };
+                    sub
+                });
+                let sub = variants.next().flatten();
+                let phases = variants.collect();
+                (chr, Glyph { sub, adv, phases })
             })
             .collect();
 
@@ -129,13 +277,22 @@ impl Font {
             Self {
                 size: font.size(),
                 pixelated,
+                subpixel,
+                sdf,
                 glyphs,
                 kerning,
             },
-            tex,
+            atlas.texture().clone(),
         ))
     }
 
+    /// The set of characters the font has glyphs for. Subtract this from the characters some
+    /// text needs to find the ones that are missing.
+    #[inline]
+    pub fn charset(&self) -> Charset {
+        self.glyphs.keys().copied().collect()
+    }
+
     #[inline]
     pub fn size(&self) -> f32 {
         self.size
@@ -146,9 +303,42 @@ impl Font {
         self.pixelated
     }
 
+    /// If the font's glyphs are signed distance fields, which are drawn with the
+    /// [SDF shader](Graphics::sdf_shader).
+    #[inline]
+    pub fn sdf(&self) -> bool {
+        self.sdf
+    }
+
+    /// Set if the font's glyphs are signed distance fields, such as when building a font
+    /// from glyphs rasterized with [`rasterize_sdf`](fey_font::Glyph::rasterize_sdf).
+    #[inline]
+    pub fn set_sdf(&mut self, sdf: bool) {
+        self.sdf = sdf;
+    }
+
+    #[inline]
+    pub fn subpixel(&self) -> SubpixelMode {
+        self.subpixel
+    }
+
+    /// Set how glyphs are positioned when drawn. If the font was not packed with phase
+    /// variants, [`Phases`](SubpixelMode::Phases) will fall back on the unshifted glyphs.
+    #[inline]
+    pub fn set_subpixel(&mut self, subpixel: SubpixelMode) {
+        self.subpixel = subpixel;
+    }
+
     #[inline]
     pub fn set_glyph(&mut self, chr: char, sub: Option<SubTexture>, adv: f32) {
-        self.glyphs.insert(chr, Glyph { sub, adv });
+        self.glyphs.insert(
+            chr,
+            Glyph {
+                sub,
+                adv,
+                phases: Vec::new(),
+            },
+        );
     }
 
     #[inline]
@@ -165,4 +355,13 @@ impl Font {
     pub fn kerning(&self, left: char, right: char) -> Option<f32> {
         self.kerning.get(&(left, right)).copied()
     }
+
+    /// Width of the text when drawn at the font's size, summing the advance of each glyph.
+    #[inline]
+    pub fn text_width(&self, text: &str) -> f32 {
+        text.chars()
+            .filter_map(|chr| self.glyph(chr))
+            .map(|g| g.adv)
+            .sum()
+    }
 }
@@ -0,0 +1,79 @@
+use crate::color::Rgba8;
+use crate::gfx::{Draw, Font};
+use crate::math::{RectF, Vec2F, vec2};
+use std::time::Duration;
+
+/// Rendering statistics for a frame, useful for finding where batching can be improved.
+///
+/// Get the stats of the most recently rendered frame from
+/// [`Graphics::frame_stats`](super::Graphics::frame_stats).
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct FrameStats {
+    /// How many render passes were submitted. Each change of target surface starts a new pass.
+    pub passes: usize,
+
+    /// How many draw calls were submitted. Changing the shader, texture, blend mode, etc. will
+    /// flush the current batch into a new draw call.
+    pub draw_calls: usize,
+
+    /// How many vertices were submitted.
+    pub vertices: usize,
+
+    /// How many indices were submitted.
+    pub indices: usize,
+
+    /// How long each render pass took on the GPU. Only available if GPU timing was enabled with
+    /// [`GameBuilder::with_gpu_timing`](crate::core::GameBuilder::with_gpu_timing) and the
+    /// device supports it. These timings arrive asynchronously, so are from a slightly
+    /// earlier frame than the other stats.
+    pub pass_times: Vec<Duration>,
+}
+
+impl FrameStats {
+    /// Total GPU time of all render passes, if GPU timings are available.
+    #[inline]
+    pub fn gpu_time(&self) -> Option<Duration> {
+        (!self.pass_times.is_empty()).then(|| self.pass_times.iter().sum())
+    }
+
+    /// Draw the stats as a text overlay, with its top-left at `pos`.
+    pub fn draw_overlay(&self, draw: &mut Draw, font: &Font, pos: impl Into<Vec2F>, color: Rgba8) {
+        let pos = pos.into();
+        let mut lines = vec![
+            format!("passes: {}", self.passes),
+            format!("calls: {}", self.draw_calls),
+            format!("vertices: {}", self.vertices),
+            format!("indices: {}", self.indices),
+        ];
+        if let Some(time) = self.gpu_time() {
+            lines.push(format!("gpu: {:.3}ms", time.as_secs_f64() * 1000.0));
+            lines.extend(
+                self.pass_times
+                    .iter()
+                    .enumerate()
+                    .map(|(i, time)| format!("  pass {i}: {:.3}ms", time.as_secs_f64() * 1000.0)),
+            );
+        }
+
+        let size = font.size();
+        let line_height = size * 1.25;
+        let width = lines
+            .iter()
+            .map(|line| font.text_width(line))
+            .fold(0.0, f32::max);
+        let pad = line_height * 0.25;
+        draw.rect(
+            RectF::new(
+                pos.x,
+                pos.y,
+                width + pad * 2.0,
+                line_height * lines.len() as f32 + pad * 2.0,
+            ),
+            Rgba8::new(0, 0, 0, 160),
+        );
+        for (i, line) in lines.iter().enumerate() {
+            let at = pos + vec2(pad, pad + size + line_height * i as f32);
+            draw.text(line, at, font, color, None);
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use fnv::FnvHashMap;
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// The kind of a GPU resource tracked by [`GpuMemory`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum GpuResourceKind {
+    Texture,
+    Surface,
+    VertexBuffer,
+    IndexBuffer,
+}
+
+/// Information about a GPU resource that is currently allocated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GpuAllocation {
+    pub kind: GpuResourceKind,
+
+    /// The label given to the resource, if any.
+    pub label: Option<String>,
+
+    /// How many bytes the resource occupies.
+    pub bytes: usize,
+
+    /// The frame the resource was created on.
+    pub created_frame: u64,
+
+    /// The last frame the resource was drawn with, or `None` if it hasn't been used yet.
+    pub last_used_frame: Option<u64>,
+}
+
+/// Tracks how much memory is allocated for textures, surfaces and buffers.
+///
+/// Resources can be given labels (eg. [`Texture::set_label`](super::Texture::set_label)) so
+/// memory usage can be attributed to them, and resources that haven't been drawn with for a
+/// number of frames can be found with [`unused_for`](Self::unused_for) to detect leaks.
+///
+/// Accessed via [`Graphics::memory`](super::Graphics::memory).
+#[derive(Clone)]
+pub struct GpuMemory(Arc<Inner>);
+
+struct Inner {
+    frame: AtomicU64,
+    next_id: AtomicUsize,
+    allocations: Mutex<FnvHashMap<usize, Arc<Info>>>,
+}
+
+struct Info {
+    kind: GpuResourceKind,
+    bytes: usize,
+    created_frame: u64,
+    last_used_frame: AtomicU64,
+    label: Mutex<Option<String>>,
+}
+
+impl Debug for GpuMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GpuMemory")
+            .field("total_bytes", &self.total_bytes())
+            .finish_non_exhaustive()
+    }
+}
+
+impl GpuMemory {
+    pub(crate) fn new() -> Self {
+        Self(Arc::new(Inner {
+            frame: AtomicU64::new(0),
+            next_id: AtomicUsize::new(0),
+            allocations: Mutex::new(FnvHashMap::default()),
+        }))
+    }
+
+    pub(crate) fn allocate(&self, kind: GpuResourceKind, bytes: usize) -> Allocation {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed);
+        let info = Arc::new(Info {
+            kind,
+            bytes,
+            created_frame: self.frame(),
+            last_used_frame: AtomicU64::new(NEVER_USED),
+            label: Mutex::new(None),
+        });
+        self.0.allocations.lock().unwrap().insert(id, info.clone());
+        Allocation {
+            id,
+            info,
+            memory: self.clone(),
+        }
+    }
+
+    pub(crate) fn next_frame(&self) {
+        self.0.frame.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// The current frame, used for [`GpuAllocation::last_used_frame`].
+    #[inline]
+    pub fn frame(&self) -> u64 {
+        self.0.frame.load(Ordering::Relaxed)
+    }
+
+    /// Total bytes allocated by all tracked resources.
+    #[inline]
+    pub fn total_bytes(&self) -> usize {
+        self.0
+            .allocations
+            .lock()
+            .unwrap()
+            .values()
+            .map(|i| i.bytes)
+            .sum()
+    }
+
+    /// Total bytes allocated by resources of the kind.
+    #[inline]
+    pub fn kind_bytes(&self, kind: GpuResourceKind) -> usize {
+        self.0
+            .allocations
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|i| i.kind == kind)
+            .map(|i| i.bytes)
+            .sum()
+    }
+
+    /// How many resources are allocated.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.allocations.lock().unwrap().len()
+    }
+
+    /// Information about every allocated resource.
+    pub fn allocations(&self) -> Vec<GpuAllocation> {
+        self.0
+            .allocations
+            .lock()
+            .unwrap()
+            .values()
+            .map(|info| info.snapshot())
+            .collect()
+    }
+
+    /// Total bytes allocated per label, sorted from largest to smallest. Unlabeled resources are
+    /// grouped by their kind, such as `"<Texture>"`.
+    pub fn bytes_by_label(&self) -> Vec<(String, usize)> {
+        let mut totals: FnvHashMap<String, usize> = FnvHashMap::default();
+        for info in self.0.allocations.lock().unwrap().values() {
+            let label = match info.label.lock().unwrap().as_ref() {
+                Some(label) => label.clone(),
+                None => format!("<{:?}>", info.kind),
+            };
+            *totals.entry(label).or_default() += info.bytes;
+        }
+        let mut totals: Vec<_> = totals.into_iter().collect();
+        totals.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        totals
+    }
+
+    /// Resources that haven't been drawn with in the last `frames` frames, or ever if they were
+    /// created at least that long ago. These are likely leaks if they stay alive.
+    pub fn unused_for(&self, frames: u64) -> Vec<GpuAllocation> {
+        let frame = self.frame();
+        self.0
+            .allocations
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|info| {
+                let last = match info.last_used_frame.load(Ordering::Relaxed) {
+                    NEVER_USED => info.created_frame,
+                    last => last,
+                };
+                frame.saturating_sub(last) >= frames
+            })
+            .map(|info| info.snapshot())
+            .collect()
+    }
+}
+
+const NEVER_USED: u64 = u64::MAX;
+
+impl Info {
+    fn snapshot(&self) -> GpuAllocation {
+        GpuAllocation {
+            kind: self.kind,
+            label: self.label.lock().unwrap().clone(),
+            bytes: self.bytes,
+            created_frame: self.created_frame,
+            last_used_frame: match self.last_used_frame.load(Ordering::Relaxed) {
+                NEVER_USED => None,
+                frame => Some(frame),
+            },
+        }
+    }
+}
+
+/// A tracked resource's entry in [`GpuMemory`], removed when dropped.
+pub(crate) struct Allocation {
+    id: usize,
+    info: Arc<Info>,
+    memory: GpuMemory,
+}
+
+impl Debug for Allocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Allocation")
+            .field("kind", &self.info.kind)
+            .field("bytes", &self.info.bytes)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Allocation {
+    /// Mark the resource as used this frame.
+    #[inline]
+    pub fn touch(&self) {
+        self.info
+            .last_used_frame
+            .store(self.memory.frame(), Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn label(&self) -> Option<String> {
+        self.info.label.lock().unwrap().clone()
+    }
+
+    #[inline]
+    pub fn set_label(&self, label: Option<String>) {
+        *self.info.label.lock().unwrap() = label;
+    }
+}
+
+impl Drop for Allocation {
+    fn drop(&mut self) {
+        self.memory.0.allocations.lock().unwrap().remove(&self.id);
+    }
+}
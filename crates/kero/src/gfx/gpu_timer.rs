@@ -0,0 +1,140 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::Duration;
+use wgpu::{
+    Buffer, BufferDescriptor, BufferUsages, CommandEncoder, Device, Features, MapMode, PollType,
+    QUERY_SIZE, QuerySet, QuerySetDescriptor, QueryType, Queue, RenderPassTimestampWrites,
+};
+
+const MAP_WAITING: u8 = 0;
+const MAP_OK: u8 = 1;
+const MAP_FAILED: u8 = 2;
+
+/// Measures how long each render pass takes on the GPU using timestamp queries.
+///
+/// Results are read back asynchronously, so they arrive a frame or more after the passes were
+/// recorded. Frames submitted while the previous results are still being read back are not timed.
+#[derive(Debug)]
+pub(crate) struct GpuTimer {
+    query_set: QuerySet,
+    resolve: Buffer,
+    readback: Buffer,
+    capacity: u32,
+    period: f32,
+    map_state: Arc<AtomicU8>,
+    pending: Option<u32>,
+    pub times: Vec<Duration>,
+}
+
+impl GpuTimer {
+    /// Create a timer if the device has timestamp queries enabled.
+    pub fn new(device: &Device, queue: &Queue) -> Option<Self> {
+        if !device.features().contains(Features::TIMESTAMP_QUERY) {
+            return None;
+        }
+        let capacity = 8;
+        let (query_set, resolve, readback) = Self::create_buffers(device, capacity);
+        Some(Self {
+            query_set,
+            resolve,
+            readback,
+            capacity,
+            period: queue.get_timestamp_period(),
+            map_state: Arc::new(AtomicU8::new(MAP_WAITING)),
+            pending: None,
+            times: Vec::new(),
+        })
+    }
+
+    fn create_buffers(device: &Device, capacity: u32) -> (QuerySet, Buffer, Buffer) {
+        let size = (capacity * 2 * QUERY_SIZE) as u64;
+        let query_set = device.create_query_set(&QuerySetDescriptor {
+            label: None,
+            ty: QueryType::Timestamp,
+            count: capacity * 2,
+        });
+        let resolve = device.create_buffer(&BufferDescriptor {
+            label: None,
+            size,
+            usage: BufferUsages::QUERY_RESOLVE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = device.create_buffer(&BufferDescriptor {
+            label: None,
+            size,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        (query_set, resolve, readback)
+    }
+
+    /// Collect any finished results and prepare to time `passes` render passes. Returns false if
+    /// this frame can't be timed because the previous results are still being read back.
+    pub fn begin(&mut self, device: &Device, passes: u32) -> bool {
+        if let Some(count) = self.pending {
+            _ = device.poll(PollType::Poll);
+            match self.map_state.load(Ordering::Acquire) {
+                MAP_WAITING => return false,
+                MAP_OK => self.read(count),
+                _ => self.times.clear(),
+            }
+            self.map_state.store(MAP_WAITING, Ordering::Release);
+            self.pending = None;
+        }
+        if passes == 0 {
+            return false;
+        }
+        if passes > self.capacity {
+            self.capacity = passes.next_power_of_two();
+            (self.query_set, self.resolve, self.readback) =
+                Self::create_buffers(device, self.capacity);
+        }
+        true
+    }
+
+    /// Timestamp writes for the render pass at the index.
+    #[inline]
+    pub fn writes(&self, pass: u32) -> RenderPassTimestampWrites<'_> {
+        RenderPassTimestampWrites {
+            query_set: &self.query_set,
+            beginning_of_pass_write_index: Some(pass * 2),
+            end_of_pass_write_index: Some(pass * 2 + 1),
+        }
+    }
+
+    /// Resolve the timestamps of `passes` render passes into the readback buffer.
+    pub fn resolve(&mut self, encoder: &mut CommandEncoder, passes: u32) {
+        let size = (passes * 2 * QUERY_SIZE) as u64;
+        encoder.resolve_query_set(&self.query_set, 0..passes * 2, &self.resolve, 0);
+        encoder.copy_buffer_to_buffer(&self.resolve, 0, &self.readback, 0, size);
+        self.pending = Some(passes);
+    }
+
+    /// Start reading back the results, once the resolved frame has been submitted.
+    pub fn map(&mut self) {
+        let Some(passes) = self.pending else {
+            return;
+        };
+        let map_state = self.map_state.clone();
+        let size = (passes * 2 * QUERY_SIZE) as u64;
+        self.readback
+            .map_async(MapMode::Read, ..size, move |result| {
+                let state = if result.is_ok() { MAP_OK } else { MAP_FAILED };
+                map_state.store(state, Ordering::Release);
+            });
+    }
+
+    fn read(&mut self, passes: u32) {
+        let size = (passes * 2 * QUERY_SIZE) as u64;
+        {
+            let data = self.readback.slice(..size).get_mapped_range();
+            let stamps: &[u64] = bytemuck::cast_slice(&data);
+            self.times.clear();
+            self.times.extend(stamps.chunks_exact(2).map(|pair| {
+                let ticks = pair[1].saturating_sub(pair[0]);
+                Duration::from_nanos((ticks as f64 * self.period as f64) as u64)
+            }));
+        }
+        self.readback.unmap();
+    }
+}
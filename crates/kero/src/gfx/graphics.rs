@@ -1,21 +1,28 @@
 use crate::color::{FromRgb, Rgba8, Rgba16, Rgba32F};
 use crate::core::{GameBuilder, Window};
 use crate::gfx::{
-    IndexBuffer, Shader, Surface, Texture, TextureFormat, TexturePixel, Vertex, VertexBuffer,
+    AtlasDebug, FrameStats, GpuMemory, IndexBuffer, Shader, Surface, Texture, TextureFormat,
+    TextureLoad, TexturePixel, UPLOAD_BYTES_PER_FRAME, Vertex, VertexBuffer,
 };
 use crate::grid::Grid;
-use crate::img::{DynImage, Image, ImageError, ImageRgba8};
-use crate::math::Vec2U;
+use crate::img::{
+    DynImage, Image, ImageError, ImageGrey8, ImageGrey16, ImageGrey32F, ImageGreyAlpha8,
+    ImageGreyAlpha16, ImageGreyAlpha32F, ImageRgba8, ImageRgba16, ImageRgba32F,
+};
+use crate::math::{Numeric, Vec2U};
+use bytemuck::pod_collect_to_vec;
 use dpi::PhysicalSize;
 use pollster::FutureExt;
 use std::fmt::{Debug, Formatter};
 use std::path::Path;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
 use wgpu::{
-    Adapter, BackendOptions, Backends, Device, DeviceDescriptor, ExperimentalFeatures, Features,
-    Instance, InstanceDescriptor, InstanceFlags, Limits, MemoryBudgetThresholds, MemoryHints,
-    PowerPreference, PresentMode, Queue, RequestAdapterOptions, SurfaceCapabilities,
-    SurfaceConfiguration, TextureUsages, Trace,
+    Adapter, AdapterInfo, BackendOptions, Backends, BufferDescriptor, BufferUsages,
+    COPY_BYTES_PER_ROW_ALIGNMENT, CommandEncoderDescriptor, CompositeAlphaMode, Device,
+    DeviceDescriptor, ExperimentalFeatures, Extent3d, Features, Instance, InstanceDescriptor,
+    InstanceFlags, Limits, MapMode, MemoryBudgetThresholds, MemoryHints, PollType, PowerPreference,
+    PresentMode, Queue, RequestAdapterOptions, SurfaceConfiguration, TexelCopyBufferInfo,
+    TexelCopyBufferLayout, TextureUsages, Trace,
 };
 
 /// Handle to the graphics state, used to create surfaces, textures, shaders, etc.
@@ -34,14 +41,20 @@ impl Debug for Graphics {
 struct GraphicsInner {
     window: Window,
     _instance: Instance,
-    surface_caps: SurfaceCapabilities,
+    alpha_mode: CompositeAlphaMode,
+    surface_usage: TextureUsages,
     pub(crate) surface: wgpu::Surface<'static>,
-    _adapter: Adapter,
+    adapter: Adapter,
     device: Device,
     queue: Queue,
     limits: Limits,
     default_texture: Texture,
     default_shader: Shader,
+    sdf_shader: Shader,
+    memory: GpuMemory,
+    texture_loads: Mutex<Vec<TextureLoad>>,
+    frame_stats: Mutex<FrameStats>,
+    atlas_debug: Mutex<AtlasDebug>,
 
     #[cfg(feature = "lua")]
     default_texture_userdata: mlua::AnyUserData,
@@ -53,19 +66,43 @@ struct GraphicsInner {
     lua: mlua::WeakLua,
 }
 
-fn config(size: PhysicalSize<u32>, caps: &SurfaceCapabilities) -> SurfaceConfiguration {
+fn config(
+    size: PhysicalSize<u32>,
+    alpha_mode: CompositeAlphaMode,
+    usage: TextureUsages,
+) -> SurfaceConfiguration {
     SurfaceConfiguration {
-        usage: TextureUsages::RENDER_ATTACHMENT,
+        usage,
         format: wgpu::TextureFormat::Bgra8Unorm,
         width: size.width,
         height: size.height,
         present_mode: PresentMode::AutoVsync,
         desired_maximum_frame_latency: 2,
-        alpha_mode: caps.alpha_modes[0],
+        alpha_mode,
         view_formats: Vec::new(),
     }
 }
 
+/// Create the default shaders, memory tracker, and default texture for a device.
+pub(crate) fn default_resources(
+    device: &Device,
+    queue: &Queue,
+) -> (Shader, Shader, GpuMemory, Texture) {
+    let default_shader = Shader::new(device, include_str!("shader_default.wgsl"));
+    let sdf_shader = Shader::new(device, include_str!("shader_sdf.wgsl"));
+    let memory = GpuMemory::new();
+    let default_texture = Texture::new(
+        device,
+        queue.clone(),
+        &memory,
+        Vec2U::ONE,
+        TextureFormat::Rgba8,
+        false,
+    );
+    default_texture.upload_bytes(bytemuck::cast_slice(&[Rgba8::FUCHSIA]));
+    (default_shader, sdf_shader, memory, default_texture)
+}
+
 impl Graphics {
     #[allow(unused_variables)]
     pub(crate) fn new(window: Window, opts: &GameBuilder) -> Self {
@@ -103,11 +140,17 @@ impl Graphics {
             .block_on()
             .expect("failed to find a suitable graphics device");
 
+        // only enable timestamp queries if requested, since they have some overhead
+        let mut required_features = Features::default();
+        if opts.gpu_timing && adapter.features().contains(Features::TIMESTAMP_QUERY) {
+            required_features |= Features::TIMESTAMP_QUERY;
+        }
+
         // request a graphics device and queue for it
         let (device, queue) = adapter
             .request_device(&DeviceDescriptor {
                 label: None,
-                required_features: Features::default(),
+                required_features,
                 required_limits: Limits::default(),
                 experimental_features: ExperimentalFeatures::default(),
                 memory_hints: MemoryHints::Performance,
@@ -121,28 +164,42 @@ impl Graphics {
         let limits = device.limits();
 
         // create the surface configuration and configure the surface
+        // transparent windows need an alpha mode that composites with what is behind them
         let surface_caps = surface.get_capabilities(&adapter);
-        surface.configure(&device, &config(window.0.inner_size(), &surface_caps));
-
-        // create the default shader
-        let default_shader = Shader::new(&device, include_str!("shader_default.wgsl"));
+        let alpha_mode = surface_caps
+            .alpha_modes
+            .iter()
+            .copied()
+            .find(|mode| {
+                opts.transparent
+                    && matches!(
+                        mode,
+                        CompositeAlphaMode::PreMultiplied | CompositeAlphaMode::PostMultiplied
+                    )
+            })
+            .unwrap_or(surface_caps.alpha_modes[0]);
 
-        // create the default texture
-        let default_texture = Texture::new(
+        // allow copying from the window surface for screenshots, if the platform supports it
+        let mut surface_usage = TextureUsages::RENDER_ATTACHMENT;
+        if surface_caps.usages.contains(TextureUsages::COPY_SRC) {
+            surface_usage |= TextureUsages::COPY_SRC;
+        }
+        surface.configure(
             &device,
-            queue.clone(),
-            Vec2U::ONE,
-            TextureFormat::Rgba8,
-            false,
+            &config(window.0.inner_size(), alpha_mode, surface_usage),
         );
-        default_texture.upload_bytes(bytemuck::cast_slice(&[Rgba8::FUCHSIA]));
+
+        // create the default shaders, memory tracker, and default texture
+        let (default_shader, sdf_shader, memory, default_texture) =
+            default_resources(&device, &queue);
 
         Self(Arc::new(GraphicsInner {
             window,
             _instance: instance,
-            surface_caps,
+            alpha_mode,
+            surface_usage,
             surface,
-            _adapter: adapter,
+            adapter,
             device,
             queue,
             limits,
@@ -157,7 +214,12 @@ impl Graphics {
             lua: opts.lua.weak(),
 
             default_shader,
+            sdf_shader,
             default_texture,
+            memory,
+            texture_loads: Mutex::new(Vec::new()),
+            frame_stats: Mutex::new(FrameStats::default()),
+            atlas_debug: Mutex::new(AtlasDebug::default()),
         }))
     }
 
@@ -210,6 +272,13 @@ impl Graphics {
         &self.0.default_shader
     }
 
+    /// Shader used to draw [SDF fonts](crate::gfx::Font::from_ttf_bytes_sdf), which thresholds the
+    /// distance stored in each texel's alpha so glyphs stay sharp at any scale.
+    #[inline]
+    pub fn sdf_shader(&self) -> &Shader {
+        &self.0.sdf_shader
+    }
+
     /// Texture that is used by default (a single `Rgba8::FUCHSIA` pixel).
     #[inline]
     pub fn default_texture(&self) -> &Texture {
@@ -228,6 +297,36 @@ impl Graphics {
         &self.0.default_texture_userdata
     }
 
+    /// Tracks memory allocated for textures, surfaces and buffers.
+    #[inline]
+    pub fn memory(&self) -> &GpuMemory {
+        &self.0.memory
+    }
+
+    /// Rendering statistics of the most recently rendered frame.
+    #[inline]
+    pub fn frame_stats(&self) -> FrameStats {
+        self.0.frame_stats.lock().unwrap().clone()
+    }
+
+    #[inline]
+    pub(crate) fn set_frame_stats(&self, stats: FrameStats) {
+        *self.0.frame_stats.lock().unwrap() = stats;
+    }
+
+    /// The atlas debug overlay, which is drawn over each frame while enabled.
+    #[inline]
+    pub fn atlas_debug(&self) -> MutexGuard<'_, AtlasDebug> {
+        self.0.atlas_debug.lock().unwrap()
+    }
+
+    /// If render passes are being timed on the GPU. See
+    /// [`GameBuilder::with_gpu_timing`](crate::core::GameBuilder::with_gpu_timing).
+    #[inline]
+    pub fn gpu_timing(&self) -> bool {
+        self.0.device.features().contains(Features::TIMESTAMP_QUERY)
+    }
+
     /// Create a new shader from the provided [WGSL](https://www.w3.org/TR/WGSL/) source code.
     ///
     /// See [`default_shader`](Self::default_shader) for a starting point.
@@ -250,6 +349,7 @@ impl Graphics {
             Texture::new(
                 &self.0.device,
                 self.0.queue.clone(),
+                &self.0.memory,
                 size.into(),
                 format,
                 true,
@@ -267,6 +367,7 @@ impl Graphics {
         let texture = Texture::new(
             &self.0.device,
             self.0.queue.clone(),
+            &self.0.memory,
             size,
             P::TEXTURE_FORMAT,
             false,
@@ -289,6 +390,59 @@ impl Graphics {
         Ok(self.create_texture_from_dyn_img(&img))
     }
 
+    /// Start loading a texture from a PNG file in the background, so large images don't cause the
+    /// game to hitch. The image is decoded on a worker thread and uploaded across several frames,
+    /// and the returned handle provides the [default texture](Self::default_texture) until then.
+    #[inline]
+    pub fn load_texture_async(&self, path: impl AsRef<Path>, premultiply: bool) -> TextureLoad {
+        self.load_texture_async_ext(path, premultiply, self.0.default_texture.clone())
+    }
+
+    /// Start loading a texture from a PNG file in the background, with a placeholder texture for
+    /// the handle to provide until it is ready. See [`Self::load_texture_async`].
+    pub fn load_texture_async_ext(
+        &self,
+        path: impl AsRef<Path>,
+        premultiply: bool,
+        placeholder: Texture,
+    ) -> TextureLoad {
+        let load = TextureLoad::start(path.as_ref().to_path_buf(), premultiply, placeholder);
+        self.0.texture_loads.lock().unwrap().push(load.clone());
+        load
+    }
+
+    /// How many textures are still being loaded in the background.
+    #[inline]
+    pub fn pending_texture_loads(&self) -> usize {
+        self.0.texture_loads.lock().unwrap().len()
+    }
+
+    /// Upload a frame's worth of data for textures being loaded in the background.
+    pub(crate) fn process_texture_loads(&self) {
+        let mut loads = self.0.texture_loads.lock().unwrap();
+        let mut budget = UPLOAD_BYTES_PER_FRAME;
+        for load in loads.iter() {
+            if budget == 0 {
+                break;
+            }
+            let uploaded = load.upload_chunk(
+                |size, format| {
+                    Texture::new(
+                        &self.0.device,
+                        self.0.queue.clone(),
+                        &self.0.memory,
+                        size,
+                        format,
+                        false,
+                    )
+                },
+                budget,
+            );
+            budget = budget.saturating_sub(uploaded);
+        }
+        loads.retain(|load| !load.is_done());
+    }
+
     /// Create a new texture from the bytes of a PNG file. The texture's format will be determined
     /// by the image's pixel format.
     pub fn load_png_from_memory(
@@ -317,6 +471,13 @@ impl Graphics {
         Ok(self.create_texture_from_dyn_img(&img))
     }
 
+    /// Create a new [`Rgba32F`](TextureFormat::Rgba32F) texture from the bytes of a Radiance
+    /// HDR file, keeping color values above `1.0` for HDR rendering.
+    pub fn load_hdr_from_memory(&self, bytes: &[u8]) -> Result<Texture, ImageError> {
+        let img = DynImage::load_hdr_from_memory(bytes)?;
+        Ok(self.create_texture_from_dyn_img(&img))
+    }
+
     /// Create a new texture from an [`Image`].
     pub fn create_texture_from_img<P: TexturePixel, S: AsRef<[P::Channel]>>(
         &self,
@@ -341,6 +502,7 @@ impl Graphics {
             DynImage::Rgba8(img) => self.create_texture_from_img(img),
             DynImage::Rgba16(img) => self.create_texture_from_img(img),
             DynImage::Rgba32F(img) => self.create_texture_from_img(img),
+            DynImage::Indexed(img) => self.create_texture_from_img(&img.to_rgba8()),
         }
     }
 
@@ -349,24 +511,128 @@ impl Graphics {
         self.create_texture_from_img(image)
     }
 
+    /// Read a texture's pixels back from the GPU. This blocks until the GPU has finished all
+    /// submitted work, so it is meant for tools and exporting, not for every frame.
+    pub fn download_texture(&self, texture: &Texture) -> DynImage {
+        let size = texture.size();
+        let bytes_per_row = texture.format().bytes_per_pixel().to_u32() * size.x;
+        let padded_row = bytes_per_row.next_multiple_of(COPY_BYTES_PER_ROW_ALIGNMENT);
+        let buffer = self.0.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: (padded_row * size.y) as u64,
+            usage: BufferUsages::COPY_DST | BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        // copy the texture into the buffer and wait for it to be mapped
+        let mut encoder = self
+            .0
+            .device
+            .create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            texture.0.texture.as_image_copy(),
+            TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(padded_row),
+                    rows_per_image: Some(size.y),
+                },
+            },
+            Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.0.queue.submit([encoder.finish()]);
+        buffer.map_async(MapMode::Read, .., |_| {});
+        self.0
+            .device
+            .poll(PollType::wait_indefinitely())
+            .expect("failed to download texture");
+
+        // strip the row padding
+        let mut bytes = Vec::with_capacity(texture.size_in_bytes());
+        for row in buffer
+            .get_mapped_range(..)
+            .chunks_exact(padded_row as usize)
+        {
+            bytes.extend_from_slice(&row[..bytes_per_row as usize]);
+        }
+        buffer.unmap();
+
+        match texture.format() {
+            TextureFormat::R8 => ImageGrey8::from_raw(size, bytes).into(),
+            TextureFormat::R16 => ImageGrey16::from_raw(size, pod_collect_to_vec(&bytes)).into(),
+            TextureFormat::R32F => ImageGrey32F::from_raw(size, pod_collect_to_vec(&bytes)).into(),
+            TextureFormat::Rg8 => ImageGreyAlpha8::from_raw(size, bytes).into(),
+            TextureFormat::Rg16 => {
+                ImageGreyAlpha16::from_raw(size, pod_collect_to_vec(&bytes)).into()
+            }
+            TextureFormat::Rg32F => {
+                ImageGreyAlpha32F::from_raw(size, pod_collect_to_vec(&bytes)).into()
+            }
+            TextureFormat::Rgba8 => ImageRgba8::from_raw(size, bytes).into(),
+            TextureFormat::Rgba16 => ImageRgba16::from_raw(size, pod_collect_to_vec(&bytes)).into(),
+            TextureFormat::Rgba32F => {
+                ImageRgba32F::from_raw(size, pod_collect_to_vec(&bytes)).into()
+            }
+        }
+    }
+
     /// Create a new index buffer from the provided indices.
     pub fn create_index_buffer(&self, indices: &[u32]) -> IndexBuffer {
-        let buffer = IndexBuffer::new(&self.0.device, self.0.queue.clone(), indices.len());
+        let buffer = IndexBuffer::new(
+            &self.0.device,
+            self.0.queue.clone(),
+            &self.0.memory,
+            indices.len(),
+        );
         buffer.upload(indices).unwrap();
         buffer
     }
 
     /// Create a new vertex buffer from the provided vertices.
     pub fn create_vertex_buffer(&self, vertices: &[Vertex]) -> VertexBuffer {
-        let buffer = VertexBuffer::new(&self.0.device, self.0.queue.clone(), vertices.len());
+        let buffer = VertexBuffer::new(
+            &self.0.device,
+            self.0.queue.clone(),
+            &self.0.memory,
+            vertices.len(),
+        );
         buffer.upload(vertices).unwrap();
         buffer
     }
 
+    /// If the window is composited with what is behind it using the alpha of each pixel. This
+    /// is only the case if [`GameBuilder::with_transparent`] was used and the platform
+    /// supports it.
+    #[inline]
+    pub fn transparent(&self) -> bool {
+        matches!(
+            self.0.alpha_mode,
+            CompositeAlphaMode::PreMultiplied | CompositeAlphaMode::PostMultiplied
+        )
+    }
+
+    /// If the window's contents can be captured, such as for
+    /// [bug reports](crate::core::DebugTools::capture_report).
+    #[inline]
+    pub fn can_capture_window(&self) -> bool {
+        self.0.surface_usage.contains(TextureUsages::COPY_SRC)
+    }
+
+    /// Information about the graphics device and driver.
+    #[inline]
+    pub fn adapter_info(&self) -> AdapterInfo {
+        self.0.adapter.get_info()
+    }
+
     pub(crate) fn resized(&self, new_size: PhysicalSize<u32>) {
         // only configure surface if the window has an actual size
         if new_size.width > 0 && new_size.height > 0 {
-            let config = config(new_size, &self.0.surface_caps);
+            let config = config(new_size, self.0.alpha_mode, self.0.surface_usage);
             self.0.surface.configure(&self.0.device, &config);
         }
     }
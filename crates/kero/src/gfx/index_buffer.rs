@@ -1,3 +1,5 @@
+use crate::gfx::gpu_memory::Allocation;
+use crate::gfx::{GpuMemory, GpuResourceKind};
 use bytemuck::cast_slice;
 use std::fmt::{Debug, Formatter};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -42,20 +44,23 @@ struct Inner {
     buffer: wgpu::Buffer,
     queue: Queue,
     count: AtomicUsize,
+    allocation: Allocation,
 }
 
 impl IndexBuffer {
-    pub(crate) fn new(device: &Device, queue: Queue, capacity: usize) -> Self {
+    pub(crate) fn new(device: &Device, queue: Queue, memory: &GpuMemory, capacity: usize) -> Self {
         let buffer = device.create_buffer(&BufferDescriptor {
             label: None,
             size: (capacity * size_of::<u32>()) as BufferAddress,
             usage: BufferUsages::INDEX | BufferUsages::COPY_DST,
             mapped_at_creation: false,
         });
+        let allocation = memory.allocate(GpuResourceKind::IndexBuffer, buffer.size() as usize);
         Self(Arc::new(Inner {
             buffer,
             queue,
             count: AtomicUsize::new(0),
+            allocation,
         }))
     }
 
@@ -82,6 +87,24 @@ impl IndexBuffer {
         &self.0.buffer
     }
 
+    /// Mark the buffer as used this frame, for [`GpuMemory`] leak detection.
+    #[inline]
+    pub(crate) fn touch(&self) {
+        self.0.allocation.touch();
+    }
+
+    /// The buffer's label in [`GpuMemory`], if it has one.
+    #[inline]
+    pub fn label(&self) -> Option<String> {
+        self.0.allocation.label()
+    }
+
+    /// Label the buffer, so its memory usage can be attributed in [`GpuMemory`].
+    #[inline]
+    pub fn set_label(&self, label: impl Into<String>) {
+        self.0.allocation.set_label(Some(label.into()));
+    }
+
     /// Maximum amount of indices the buffer can hold.
     #[inline]
     pub fn capacity(&self) -> usize {
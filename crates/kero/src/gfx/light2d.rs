@@ -0,0 +1,131 @@
+use crate::color::Rgba8;
+use crate::math::{Angle, CircleF, RadiansF, RectF, Vec2F, vec2};
+
+/// The shape of the area lit by a [`Light2D`].
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum LightShape {
+    /// Light spreads out in every direction.
+    #[default]
+    Point,
+
+    /// Light spreads out in a cone, like a flashlight.
+    Cone {
+        /// The direction the cone points in.
+        direction: RadiansF,
+
+        /// How wide the cone is, from one edge to the other.
+        width: RadiansF,
+    },
+}
+
+/// A light drawn into a [`LightMap`](super::LightMap), which fades out from its position to
+/// the edge of its radius.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Light2D {
+    /// Where the light is in the world.
+    pub pos: Vec2F,
+
+    /// How far the light reaches.
+    pub radius: f32,
+
+    /// Color of the light at its center, premultiplied.
+    pub color: Rgba8,
+
+    /// Multiplies the light's color, so values above `1` can overexpose it.
+    pub intensity: f32,
+
+    /// The shape of the lit area.
+    pub shape: LightShape,
+
+    /// If occluders block the light.
+    pub shadows: bool,
+}
+
+impl Light2D {
+    /// Create a light that shines in every direction.
+    #[inline]
+    pub fn point(pos: impl Into<Vec2F>, radius: f32, color: Rgba8) -> Self {
+        Self {
+            pos: pos.into(),
+            radius,
+            color,
+            intensity: 1.0,
+            shape: LightShape::Point,
+            shadows: true,
+        }
+    }
+
+    /// Create a light that shines in a cone `width` wide, pointing in `direction`.
+    #[inline]
+    pub fn cone(
+        pos: impl Into<Vec2F>,
+        radius: f32,
+        color: Rgba8,
+        direction: impl Angle<f32>,
+        width: impl Angle<f32>,
+    ) -> Self {
+        Self {
+            shape: LightShape::Cone {
+                direction: direction.to_radians(),
+                width: width.to_radians(),
+            },
+            ..Self::point(pos, radius, color)
+        }
+    }
+
+    /// Return the light with a different intensity.
+    #[inline]
+    pub fn with_intensity(mut self, intensity: f32) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Return the light with shadows enabled or disabled.
+    #[inline]
+    pub fn with_shadows(mut self, shadows: bool) -> Self {
+        self.shadows = shadows;
+        self
+    }
+
+    /// The circle the light reaches.
+    #[inline]
+    pub fn circle(&self) -> CircleF {
+        CircleF::new(self.pos, self.radius)
+    }
+
+    /// The area the light can reach.
+    #[inline]
+    pub fn bounds(&self) -> RectF {
+        let r = vec2(self.radius, self.radius);
+        RectF::pos_size(self.pos - r, r * 2.0)
+    }
+
+    /// The light's color multiplied by its intensity.
+    #[inline]
+    pub fn center_color(&self) -> Rgba8 {
+        let f = |c: u8| (c as f32 * self.intensity).round().clamp(0.0, 255.0) as u8;
+        Rgba8::new(
+            f(self.color.r),
+            f(self.color.g),
+            f(self.color.b),
+            f(self.color.a),
+        )
+    }
+
+    /// Points around the edge of the lit area, from one side to the other. Cones start and end
+    /// at their edges, while point lights go all the way around.
+    pub fn edge_points(&self, segments: u32) -> impl Iterator<Item = Vec2F> + '_ {
+        let segments = segments.max(3);
+        let (start, sweep) = match self.shape {
+            LightShape::Point => (0.0, std::f32::consts::TAU),
+            LightShape::Cone { direction, width } => {
+                let width = width.0.abs().min(std::f32::consts::TAU);
+                (direction.0 - width * 0.5, width)
+            }
+        };
+        (0..=segments).map(move |i| {
+            let angle = start + sweep * i as f32 / segments as f32;
+            self.pos + vec2(angle.cos(), angle.sin()) * self.radius
+        })
+    }
+}
@@ -0,0 +1,196 @@
+use crate::color::Rgba8;
+use crate::gfx::{
+    BlendMode, Draw, DrawError, Graphics, Light2D, Surface, TextureFormat, Topology, Vertex,
+};
+use crate::math::{
+    Affine2F, DynShapeF, Numeric, RadiansF, RectF, Shape, TriangleF, Vec2F, Vec2U, vec2,
+};
+
+/// Add the outline of an occluder to `out`, with circles approximated by 16 points.
+pub fn occluder_outline(shape: &DynShapeF, out: &mut Vec<Vec2F>) {
+    match shape {
+        DynShapeF::Circle(circ) => {
+            out.extend(circ.iter_hull_points_n(16.0, RadiansF::ZERO));
+        }
+        DynShapeF::Triangle(tri) => out.extend_from_slice(&tri.0),
+        DynShapeF::Rect(rect) => out.extend_from_slice(&rect.corners()),
+        DynShapeF::Quad(quad) => out.extend_from_slice(&quad.0),
+        DynShapeF::Polygon(poly) => out.extend_from_slice(poly.points()),
+    }
+}
+
+/// Add triangles covering the shadow the outline casts from a light at `light`, reaching at
+/// least `reach` away from it. Only edges facing away from the light cast shadows, so the
+/// occluder itself stays lit.
+pub fn shadow_geometry(light: Vec2F, reach: f32, outline: &[Vec2F], out: &mut Vec<TriangleF>) {
+    if outline.len() < 3 {
+        return;
+    }
+
+    // the winding decides which side of each edge is outside
+    let area: f32 = (0..outline.len())
+        .map(|i| outline[i].cross(outline[(i + 1) % outline.len()]))
+        .sum();
+    let sign = if area < 0.0 { -1.0 } else { 1.0 };
+
+    for i in 0..outline.len() {
+        let a = outline[i];
+        let b = outline[(i + 1) % outline.len()];
+        let edge = b - a;
+        let normal = vec2(edge.y, -edge.x) * sign;
+        if normal.dot(a - light) <= 0.0 {
+            continue;
+        }
+
+        // project the edge away from the light, with a middle point so the far side of the
+        // shadow stays outside the light's reach even when the edge is very close to it
+        let far = |p: Vec2F| light + (p - light).norm_safe() * (reach * 2.0 + p.dist(light));
+        let (fa, fb) = (far(a), far(b));
+        let mid = far((fa + fb) * 0.5);
+        out.push(TriangleF::new(a, b, fb));
+        out.push(TriangleF::new(a, fb, mid));
+        out.push(TriangleF::new(a, mid, fa));
+    }
+}
+
+/// A surface that lights are drawn into, which darkens the scene wherever it isn't lit when
+/// drawn over it.
+///
+/// Every frame, [`render`](Self::render) the lights for the visible part of the world, then
+/// draw the scene and [`draw`](Self::draw) the light map over it. Lights add together, and
+/// anywhere no light reaches is the [`ambient`](Self::ambient) color.
+///
+/// ```ignore
+/// let view = camera.visible_rect();
+/// light_map.render(draw, view, &lights, &walls)?;
+///
+/// camera.apply(draw);
+/// world.draw(draw);
+/// light_map.draw(draw, view);
+/// camera.pop(draw)?;
+/// ```
+#[derive(Debug)]
+pub struct LightMap {
+    map: Surface,
+    scratch: Surface,
+    outline: Vec<Vec2F>,
+    shadows: Vec<TriangleF>,
+
+    /// The light color where no light reaches, premultiplied.
+    pub ambient: Rgba8,
+
+    /// How many segments lights are drawn with.
+    pub segments: u32,
+}
+
+impl LightMap {
+    /// Create a light map with its own surfaces. Smaller sizes are faster, and the light map is
+    /// stretched over the area it covers, so it can be a fraction of the screen's size.
+    pub fn new(gfx: &Graphics, size: impl Into<Vec2U>) -> Self {
+        let size = size.into();
+        Self {
+            map: gfx.create_surface(size, TextureFormat::Rgba8),
+            scratch: gfx.create_surface(size, TextureFormat::Rgba8),
+            outline: Vec::new(),
+            shadows: Vec::new(),
+            ambient: Rgba8::new(40, 40, 60, 255),
+            segments: 48,
+        }
+    }
+
+    /// Return the light map with a different ambient color.
+    #[inline]
+    pub fn with_ambient(mut self, ambient: Rgba8) -> Self {
+        self.ambient = ambient;
+        self
+    }
+
+    /// The surface holding the light map.
+    #[inline]
+    pub fn surface(&self) -> &Surface {
+        &self.map
+    }
+
+    /// Draw the lights into the light map, covering the `view` area of the world. Lights
+    /// with shadows enabled are blocked by the occluders.
+    pub fn render(
+        &mut self,
+        draw: &mut Draw,
+        view: RectF,
+        lights: &[Light2D],
+        occluders: &[DynShapeF],
+    ) -> Result<(), DrawError> {
+        let prev_surface = draw.surface().cloned();
+        let prev_blend = draw.blend_mode();
+        let size = self.map.texture().size().to_f32();
+        let to_map = Affine2F::scale(size / view.size()) * Affine2F::translation(-view.top_left());
+
+        draw.set_surface(self.map.clone(), self.ambient);
+        for light in lights {
+            if !light.bounds().overlaps(&view) {
+                continue;
+            }
+
+            self.shadows.clear();
+            if light.shadows {
+                let reach = light.radius;
+                for shape in occluders {
+                    if !shape.overlaps_circ(&light.circle()) {
+                        continue;
+                    }
+                    self.outline.clear();
+                    occluder_outline(shape, &mut self.outline);
+                    shadow_geometry(light.pos, reach, &self.outline, &mut self.shadows);
+                }
+            }
+
+            // lights without shadows can be added to the map directly, but the rest are drawn
+            // alone so their shadows can be cut out before they are added
+            draw.push_new_transform(to_map);
+            if !self.shadows.is_empty() {
+                draw.set_surface(self.scratch.clone(), Rgba8::TRANSPARENT);
+                draw.set_blend_mode(BlendMode::Normal);
+                draw_light(draw, light, self.segments);
+                draw.set_blend_mode(BlendMode::Replace);
+                for &tri in &self.shadows {
+                    draw.triangle(tri, Rgba8::TRANSPARENT);
+                }
+                draw.set_surface(self.map.clone(), None);
+                draw.set_blend_mode(BlendMode::Add);
+                draw.push_new_transform(Affine2F::IDENTITY);
+                draw.texture_at(self.scratch.texture(), Vec2F::ZERO);
+                draw.pop_transform()?;
+            } else {
+                draw.set_blend_mode(BlendMode::Add);
+                draw_light(draw, light, self.segments);
+            }
+            draw.pop_transform()?;
+        }
+
+        draw.set_surface(prev_surface, None);
+        draw.set_blend_mode(prev_blend);
+        Ok(())
+    }
+
+    /// Draw the light map over the `view` area of the world it was rendered for, multiplying
+    /// the scene by it.
+    pub fn draw(&self, draw: &mut Draw, view: RectF) {
+        let prev_blend = draw.blend_mode();
+        draw.set_blend_mode(BlendMode::Multiply);
+        draw.textured_quad(self.map.texture(), view);
+        draw.set_blend_mode(prev_blend);
+    }
+}
+
+/// Draw a light as a fan that fades from its color at the center to nothing at the edge.
+fn draw_light(draw: &mut Draw, light: &Light2D, segments: u32) {
+    let center = Vertex::veto(light.pos, light.center_color());
+    let vertices = std::iter::once(center).chain(
+        light
+            .edge_points(segments)
+            .map(|p| Vertex::veto(p, Rgba8::TRANSPARENT)),
+    );
+    let count = segments.max(3);
+    let indices = (1..=count).flat_map(|i| [0, i, i + 1]);
+    draw.custom(None, Topology::Triangles, vertices, indices);
+}
@@ -0,0 +1,195 @@
+use crate::color::Rgba8;
+use crate::gfx::{BindingValue, Draw, Font, ParamType, Shader, UniformValue};
+use crate::math::{RectF, Vec2F, vec2};
+use std::sync::Arc;
+
+#[cfg(feature = "lua")]
+pub type MaterialObj = fey_lua::UserDataOf<Material>;
+#[cfg(feature = "lua")]
+pub type MaterialRef = mlua::UserDataRef<Material>;
+#[cfg(feature = "lua")]
+pub type MaterialMut = mlua::UserDataRefMut<Material>;
+
+/// A shader paired with a set of parameter values.
+///
+/// Parameter names and types are validated when they are set on the material, so applying
+/// it with [`Draw::set_material`](super::Draw::set_material) doesn't need to look anything up
+/// by name. Parameters the material doesn't set keep their current values.
+///
+/// Cloning a material is cheap, and setting a parameter on a clone only copies its values, so
+/// materials can be used as templates with per-object overrides:
+///
+/// ```ignore
+/// let red = outline.clone().with("color", Vec4F::new(1.0, 0.0, 0.0, 1.0))?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Material {
+    shader: Shader,
+    values: Arc<Vec<Option<BindingValue>>>,
+}
+
+impl Material {
+    /// Create a new material for the shader, with no parameters set.
+    #[inline]
+    pub fn new(shader: Shader) -> Self {
+        let len = shader.param_defs().defs.len();
+        Self {
+            shader,
+            values: Arc::new(vec![None; len]),
+        }
+    }
+
+    /// The material's shader.
+    #[inline]
+    pub fn shader(&self) -> &Shader {
+        &self.shader
+    }
+
+    /// Set a parameter's value.
+    pub fn set(
+        &mut self,
+        name: &str,
+        value: impl Into<BindingValue>,
+    ) -> Result<&mut Self, MaterialError> {
+        let value = value.into();
+        let (idx, def) = self
+            .shader
+            .param_defs()
+            .defs
+            .iter()
+            .enumerate()
+            .find(|(_, def)| def.name == name)
+            .ok_or_else(|| MaterialError::ParamNotFound(name.to_string()))?;
+        let got = value.param_ty();
+        if def.ty != got {
+            return Err(MaterialError::TypeMismatch {
+                name: name.to_string(),
+                expected: def.ty,
+                got,
+            });
+        }
+        Arc::make_mut(&mut self.values)[idx] = Some(value);
+        Ok(self)
+    }
+
+    /// Return the material with a parameter's value set.
+    #[inline]
+    pub fn with(
+        mut self,
+        name: &str,
+        value: impl Into<BindingValue>,
+    ) -> Result<Self, MaterialError> {
+        self.set(name, value)?;
+        Ok(self)
+    }
+
+    /// Unset a parameter, so it will keep whatever value it had when the material is applied.
+    pub fn unset(&mut self, name: &str) -> Result<&mut Self, MaterialError> {
+        let idx = self
+            .shader
+            .param_defs()
+            .defs
+            .iter()
+            .position(|def| def.name == name)
+            .ok_or_else(|| MaterialError::ParamNotFound(name.to_string()))?;
+        Arc::make_mut(&mut self.values)[idx] = None;
+        Ok(self)
+    }
+
+    /// The value of a parameter, if it has been set.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&BindingValue> {
+        let defs = &self.shader.param_defs().defs;
+        let idx = defs.iter().position(|def| def.name == name)?;
+        self.values[idx].as_ref()
+    }
+
+    /// Draw the shader's parameters and the material's values for them as a text overlay, with
+    /// its top-left at `pos`. Parameters the material doesn't set show their default.
+    pub fn draw_overlay(&self, draw: &mut Draw, font: &Font, pos: impl Into<Vec2F>, color: Rgba8) {
+        let pos = pos.into();
+        let lines: Vec<String> = self
+            .shader
+            .param_defs()
+            .iter()
+            .zip(self.values.iter())
+            .map(|(def, value)| {
+                let value = match (value, def.default_value()) {
+                    (Some(BindingValue::Uniform(value)), _) => uniform_text(value),
+                    (Some(BindingValue::Texture(tex)), _) => {
+                        format!("{}x{}", tex.width(), tex.height())
+                    }
+                    (Some(BindingValue::Sampler(_)), _) => "set".to_string(),
+                    (None, Some(value)) => format!("{} (default)", uniform_text(&value)),
+                    (None, None) => "default".to_string(),
+                };
+                format!("{}: {:?} = {value}", def.name, def.ty)
+            })
+            .collect();
+
+        let size = font.size();
+        let line_height = size * 1.25;
+        let width = lines
+            .iter()
+            .map(|line| font.text_width(line))
+            .fold(0.0, f32::max);
+        let pad = line_height * 0.25;
+        draw.rect(
+            RectF::new(
+                pos.x,
+                pos.y,
+                width + pad * 2.0,
+                line_height * lines.len() as f32 + pad * 2.0,
+            ),
+            Rgba8::new(0, 0, 0, 160),
+        );
+        for (i, line) in lines.iter().enumerate() {
+            let at = pos + vec2(pad, pad + size + line_height * i as f32);
+            draw.text(line, at, font, color, None);
+        }
+    }
+
+    /// Iterate over the indices and values of all set parameters.
+    #[inline]
+    pub(crate) fn values(&self) -> impl Iterator<Item = (usize, &BindingValue)> {
+        self.values
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, val)| val.as_ref().map(|val| (idx, val)))
+    }
+}
+
+/// A short description of a uniform value for overlays.
+fn uniform_text(value: &UniformValue) -> String {
+    match value {
+        UniformValue::Int(v) => v.to_string(),
+        UniformValue::Uint(v) => v.to_string(),
+        UniformValue::Float(v) => format!("{v:.3}"),
+        UniformValue::Vec2(v) => format!("({v:.3})"),
+        UniformValue::Vec3(v) => format!("({v:.3})"),
+        UniformValue::Vec4(v) => format!("({v:.3})"),
+        UniformValue::Mat2(_) | UniformValue::Mat3(_) | UniformValue::Mat4(_) => "matrix".into(),
+        UniformValue::Block(bytes) => format!("{} bytes", bytes.len()),
+    }
+}
+
+impl From<Shader> for Material {
+    #[inline]
+    fn from(value: Shader) -> Self {
+        Self::new(value)
+    }
+}
+
+/// An error setting a [`Material`] parameter.
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum MaterialError {
+    #[error("param {0:?} not found")]
+    ParamNotFound(String),
+
+    #[error("cannot set param {name:?} of type {expected:?} to a value of type {got:?}")]
+    TypeMismatch {
+        name: String,
+        expected: ParamType,
+        got: ParamType,
+    },
+}
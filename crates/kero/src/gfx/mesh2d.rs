@@ -0,0 +1,264 @@
+use crate::color::Rgba8;
+use crate::gfx::{ColorMode, IndexBuffer, Vertex, VertexBuffer};
+use crate::math::{Affine2F, RectF, Vec2F, vec2};
+
+/// A retained 2D triangle mesh that can be deformed on the CPU every frame.
+///
+/// The mesh keeps its original rest positions around, so deformations like
+/// [`squash_stretch`](Self::squash_stretch), [`sway`](Self::sway) and
+/// [`skin`](Self::skin) are always applied relative to its undeformed shape.
+///
+/// Draw it with [`Draw::mesh`](super::Draw::mesh), which keeps its geometry in
+/// GPU buffers and only re-uploads it when the mesh has changed.
+#[derive(Debug)]
+pub struct Mesh2D {
+    rest: Vec<Vec2F>,
+    positions: Vec<Vec2F>,
+    uvs: Vec<Vec2F>,
+    indices: Vec<u32>,
+    weights: Vec<BoneWeights>,
+    color: Rgba8,
+    mode: ColorMode,
+    pub(crate) buffers: Option<(VertexBuffer, IndexBuffer)>,
+    pub(crate) uploaded: Option<Affine2F>,
+}
+
+/// The bones influencing a vertex in a skinned [`Mesh2D`], and how strongly.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoneWeights {
+    /// Indices of the two bones.
+    pub bones: [u16; 2],
+
+    /// Influence of each bone. These should add up to `1.0`.
+    pub weights: [f32; 2],
+}
+
+impl BoneWeights {
+    /// A vertex fully attached to a single bone.
+    #[inline]
+    pub const fn single(bone: u16) -> Self {
+        Self {
+            bones: [bone, bone],
+            weights: [1.0, 0.0],
+        }
+    }
+
+    /// A vertex blended between two bones, where `t` is the influence of bone `b`.
+    #[inline]
+    pub const fn blend(a: u16, b: u16, t: f32) -> Self {
+        Self {
+            bones: [a, b],
+            weights: [1.0 - t, t],
+        }
+    }
+}
+
+impl Clone for Mesh2D {
+    /// Clones the mesh's geometry. The clone gets its own GPU buffers when it's first drawn.
+    fn clone(&self) -> Self {
+        Self {
+            rest: self.rest.clone(),
+            positions: self.positions.clone(),
+            uvs: self.uvs.clone(),
+            indices: self.indices.clone(),
+            weights: self.weights.clone(),
+            color: self.color,
+            mode: self.mode,
+            buffers: None,
+            uploaded: None,
+        }
+    }
+}
+
+impl Mesh2D {
+    /// Create a new mesh from vertex positions, texture coordinates and triangle indices.
+    pub fn new(positions: Vec<Vec2F>, uvs: Vec<Vec2F>, indices: Vec<u32>) -> Self {
+        assert_eq!(positions.len(), uvs.len());
+        assert!(indices.iter().all(|&i| (i as usize) < positions.len()));
+        Self {
+            rest: positions.clone(),
+            positions,
+            uvs,
+            indices,
+            weights: Vec::new(),
+            color: Rgba8::WHITE,
+            mode: ColorMode::MULT,
+            buffers: None,
+            uploaded: None,
+        }
+    }
+
+    /// Create a mesh by subdividing the rectangle into a grid of `cols` by `rows` cells,
+    /// mapping `uv_rect` across it. More cells make for smoother deformations.
+    pub fn grid(rect: impl Into<RectF>, uv_rect: impl Into<RectF>, cols: u32, rows: u32) -> Self {
+        let (rect, uv_rect) = (rect.into(), uv_rect.into());
+        let (cols, rows) = (cols.max(1), rows.max(1));
+        let mut positions = Vec::with_capacity(((cols + 1) * (rows + 1)) as usize);
+        let mut uvs = Vec::with_capacity(positions.capacity());
+        for y in 0..=rows {
+            for x in 0..=cols {
+                let t = vec2(x as f32 / cols as f32, y as f32 / rows as f32);
+                positions.push(rect.top_left() + rect.size() * t);
+                uvs.push(uv_rect.top_left() + uv_rect.size() * t);
+            }
+        }
+        let mut indices = Vec::with_capacity((cols * rows * 6) as usize);
+        for y in 0..rows {
+            for x in 0..cols {
+                let i = y * (cols + 1) + x;
+                let j = i + cols + 1;
+                indices.extend_from_slice(&[i, i + 1, j + 1, i, j + 1, j]);
+            }
+        }
+        Self::new(positions, uvs, indices)
+    }
+
+    /// How many vertices the mesh has.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// If the mesh has no vertices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The undeformed vertex positions.
+    #[inline]
+    pub fn rest_positions(&self) -> &[Vec2F] {
+        &self.rest
+    }
+
+    /// The current, deformed vertex positions.
+    #[inline]
+    pub fn positions(&self) -> &[Vec2F] {
+        &self.positions
+    }
+
+    /// Mutable access to the current vertex positions.
+    #[inline]
+    pub fn positions_mut(&mut self) -> &mut [Vec2F] {
+        self.uploaded = None;
+        &mut self.positions
+    }
+
+    /// The vertex texture coordinates.
+    #[inline]
+    pub fn uvs(&self) -> &[Vec2F] {
+        &self.uvs
+    }
+
+    /// The triangle indices.
+    #[inline]
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+
+    /// Color applied to all vertices.
+    #[inline]
+    pub fn color(&self) -> Rgba8 {
+        self.color
+    }
+
+    /// Set the color applied to all vertices.
+    #[inline]
+    pub fn set_color(&mut self, color: Rgba8) {
+        if color != self.color {
+            self.color = color;
+            self.uploaded = None;
+        }
+    }
+
+    /// How the vertex color is applied to the texture.
+    #[inline]
+    pub fn color_mode(&self) -> ColorMode {
+        self.mode
+    }
+
+    /// Set how the vertex color is applied to the texture.
+    #[inline]
+    pub fn set_color_mode(&mut self, mode: ColorMode) {
+        if mode != self.mode {
+            self.mode = mode;
+            self.uploaded = None;
+        }
+    }
+
+    /// Restore all vertices to their rest positions.
+    #[inline]
+    pub fn reset(&mut self) {
+        self.positions.copy_from_slice(&self.rest);
+        self.uploaded = None;
+    }
+
+    /// Move each vertex to the position returned by `f`, which is given the vertex's rest
+    /// position and texture coordinate.
+    #[inline]
+    pub fn deform(&mut self, mut f: impl FnMut(Vec2F, Vec2F) -> Vec2F) {
+        for ((pos, rest), uv) in self.positions.iter_mut().zip(&self.rest).zip(&self.uvs) {
+            *pos = f(*rest, *uv);
+        }
+        self.uploaded = None;
+    }
+
+    /// Squash or stretch the mesh vertically around `pivot` while preserving its area. An
+    /// `amount` of `0.5` stretches it to 150% height (and narrows it), `-0.5` squashes it to
+    /// 50% height (and widens it).
+    pub fn squash_stretch(&mut self, pivot: Vec2F, amount: f32) {
+        let sy = (1.0 + amount).max(0.01);
+        let scale = vec2(1.0 / sy, sy);
+        self.deform(|p, _| pivot + (p - pivot) * scale);
+    }
+
+    /// Bend the mesh horizontally like a plant in the wind. Vertices at `base_y` stay in
+    /// place, and vertices at `top_y` are pushed `offset` units, with a smooth curve between.
+    pub fn sway(&mut self, base_y: f32, top_y: f32, offset: f32) {
+        let height = base_y - top_y;
+        if height == 0.0 {
+            return;
+        }
+        self.deform(|p, _| {
+            let t = ((base_y - p.y) / height).clamp(0.0, 1.0);
+            vec2(p.x + offset * t * t, p.y)
+        });
+    }
+
+    /// The bone weights of each vertex, used for [skinning](Self::skin).
+    #[inline]
+    pub fn weights(&self) -> &[BoneWeights] {
+        &self.weights
+    }
+
+    /// Set the bone weights of each vertex, used for [skinning](Self::skin).
+    #[inline]
+    pub fn set_weights(&mut self, weights: Vec<BoneWeights>) {
+        assert_eq!(weights.len(), self.rest.len());
+        self.weights = weights;
+    }
+
+    /// Deform the mesh using its bone weights. Each bone's matrix should transform from the
+    /// mesh's rest pose to the bone's current pose (ie. the bone's current transform multiplied
+    /// by the inverse of its transform in the rest pose). Missing bones are treated as identity.
+    pub fn skin(&mut self, bones: &[Affine2F]) {
+        if self.weights.is_empty() {
+            return;
+        }
+        let bone = |i: u16| bones.get(i as usize).unwrap_or(&Affine2F::IDENTITY);
+        for ((pos, rest), w) in self.positions.iter_mut().zip(&self.rest).zip(&self.weights) {
+            *pos = bone(w.bones[0]).transform_pos2(*rest) * w.weights[0]
+                + bone(w.bones[1]).transform_pos2(*rest) * w.weights[1];
+        }
+        self.uploaded = None;
+    }
+
+    /// Build the mesh's vertices, transformed by the matrix.
+    pub(crate) fn vertices(&self, matrix: &Affine2F) -> Vec<Vertex> {
+        self.positions
+            .iter()
+            .zip(&self.uvs)
+            .map(|(p, uv)| Vertex::new(matrix.transform_pos2(*p), *uv, self.color, self.mode))
+            .collect()
+    }
+}
@@ -0,0 +1,227 @@
+use crate::color::Rgba8;
+use crate::gfx::{BlendMode, Draw, Graphics, Surface};
+use crate::grid::{Grid, GridMut, VecGrid};
+use crate::math::{Affine2F, Numeric, RectF, Vec2F, Vec2U, vec2};
+
+/// A small map of grid-based terrain, with entity markers and a viewport rectangle drawn over it.
+///
+/// Each cell of the terrain is rendered as a solid block of color to a [`Surface`]. Only the cells
+/// that change are redrawn, so the terrain can be updated every frame cheaply.
+#[derive(Debug)]
+pub struct Minimap {
+    surface: Surface,
+    cell_size: u32,
+    cells: VecGrid<Rgba8>,
+    dirty: VecGrid<bool>,
+    dirty_list: Vec<Vec2U>,
+    redraw_all: bool,
+    markers: Vec<MinimapMarker>,
+    viewport: Option<MinimapViewport>,
+}
+
+/// A marker drawn on a [`Minimap`], such as the player or an enemy.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MinimapMarker {
+    /// Position on the map, in cells.
+    pub pos: Vec2F,
+
+    /// Size of the marker when drawn, in pixels.
+    pub size: f32,
+    pub color: Rgba8,
+}
+
+/// A rectangle outlined on a [`Minimap`], usually showing the area the camera can see.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MinimapViewport {
+    /// Area on the map, in cells.
+    pub rect: RectF,
+    pub color: Rgba8,
+}
+
+impl Minimap {
+    /// Create a new minimap, where each of its cells is `cell_size` pixels on its surface.
+    /// All cells start transparent.
+    pub fn new(gfx: &Graphics, size: impl Into<Vec2U>, cell_size: u32) -> Self {
+        let size = size.into();
+        let cell_size = cell_size.max(1);
+        Self {
+            surface: gfx.create_rgba8_surface(size * cell_size),
+            cell_size,
+            cells: VecGrid::new_with(size, || Rgba8::TRANSPARENT),
+            dirty: VecGrid::new_with(size, || false),
+            dirty_list: Vec::new(),
+            redraw_all: true,
+            markers: Vec::new(),
+            viewport: None,
+        }
+    }
+
+    /// Size of the map, in cells.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.cells.size()
+    }
+
+    /// How many pixels each cell takes up on the surface.
+    #[inline]
+    pub fn cell_size(&self) -> u32 {
+        self.cell_size
+    }
+
+    /// The surface the terrain is rendered to. This is only up to date after calling
+    /// [`render`](Self::render) or [`draw`](Self::draw).
+    #[inline]
+    pub fn surface(&self) -> &Surface {
+        &self.surface
+    }
+
+    /// Color of the cell.
+    #[inline]
+    pub fn cell(&self, pos: impl Into<Vec2U>) -> Option<Rgba8> {
+        let pos = pos.into();
+        self.cells.get(pos.x, pos.y).copied()
+    }
+
+    /// Set the color of the cell. If the color changed, the cell will be redrawn.
+    pub fn set_cell(&mut self, pos: impl Into<Vec2U>, color: Rgba8) {
+        let pos = pos.into();
+        let Some(cell) = self.cells.get_mut(pos.x, pos.y) else {
+            return;
+        };
+        if *cell == color {
+            return;
+        }
+        *cell = color;
+        if !self.redraw_all {
+            let dirty = self.dirty.get_mut(pos.x, pos.y).unwrap();
+            if !*dirty {
+                *dirty = true;
+                self.dirty_list.push(pos);
+            }
+        }
+    }
+
+    /// Update every cell from the grid, mapping each of its items to a color. The grid should be
+    /// the same size as the map. Only cells whose color changed will be redrawn.
+    pub fn update_from<G: Grid>(&mut self, grid: &G, mut color: impl FnMut(&G::Item) -> Rgba8) {
+        let size = self.size();
+        for y in 0..size.y.min(grid.height()) {
+            for x in 0..size.x.min(grid.width()) {
+                let item = grid.get(x, y).unwrap();
+                self.set_cell(vec2(x, y), color(item));
+            }
+        }
+    }
+
+    /// Redraw every cell on the next render.
+    #[inline]
+    pub fn mark_all_dirty(&mut self) {
+        self.redraw_all = true;
+    }
+
+    /// Markers drawn over the map.
+    #[inline]
+    pub fn markers(&self) -> &[MinimapMarker] {
+        &self.markers
+    }
+
+    /// Remove all markers. Markers are usually cleared and re-added every frame.
+    #[inline]
+    pub fn clear_markers(&mut self) {
+        self.markers.clear();
+    }
+
+    /// Add a marker at the position, in cells.
+    #[inline]
+    pub fn add_marker(&mut self, pos: impl Into<Vec2F>, size: f32, color: Rgba8) {
+        self.markers.push(MinimapMarker {
+            pos: pos.into(),
+            size,
+            color,
+        });
+    }
+
+    /// The viewport rectangle drawn over the map.
+    #[inline]
+    pub fn viewport(&self) -> Option<&MinimapViewport> {
+        self.viewport.as_ref()
+    }
+
+    /// Set the viewport rectangle drawn over the map, in cells.
+    #[inline]
+    pub fn set_viewport(&mut self, viewport: impl Into<Option<MinimapViewport>>) {
+        self.viewport = viewport.into();
+    }
+
+    /// Redraw any changed cells to the minimap's surface. Drawing is restored to the surface that
+    /// was active before this call.
+    pub fn render(&mut self, draw: &mut Draw) {
+        if !self.redraw_all && self.dirty_list.is_empty() {
+            return;
+        }
+
+        let prev_surface = draw.surface().cloned();
+        let prev_blend = draw.blend_mode();
+        let clear = self.redraw_all.then_some(Rgba8::TRANSPARENT);
+        draw.set_surface(self.surface.clone(), clear);
+        draw.push_new_transform(Affine2F::IDENTITY);
+        draw.set_blend_mode(BlendMode::Replace);
+
+        let cell_size = self.cell_size as f32;
+        let mut draw_cell = |pos: Vec2U, color: Rgba8| {
+            let pos = pos.to_f32() * cell_size;
+            draw.rect(RectF::new(pos.x, pos.y, cell_size, cell_size), color);
+        };
+        if self.redraw_all {
+            let size = self.cells.size();
+            for y in 0..size.y {
+                for x in 0..size.x {
+                    let color = self.cells[vec2(x, y)];
+                    if color.a > 0 {
+                        draw_cell(vec2(x, y), color);
+                    }
+                }
+            }
+        } else {
+            for &pos in &self.dirty_list {
+                draw_cell(pos, self.cells[pos]);
+            }
+        }
+        for pos in self.dirty_list.drain(..) {
+            self.dirty[pos] = false;
+        }
+        self.redraw_all = false;
+
+        draw.set_blend_mode(prev_blend);
+        draw.pop_transform().unwrap();
+        draw.set_surface(prev_surface, None);
+    }
+
+    /// Render any changed cells, then draw the minimap into the destination rectangle along with
+    /// its markers and viewport.
+    pub fn draw(&mut self, draw: &mut Draw, dst: impl Into<RectF>) {
+        self.render(draw);
+
+        let dst = dst.into();
+        let scale = dst.size() / self.size().to_f32();
+        draw.textured_quad(self.surface.texture(), dst);
+        if let Some(viewport) = &self.viewport {
+            let rect = viewport.rect;
+            draw.rect_outline(
+                RectF::pos_size(
+                    dst.top_left() + rect.top_left() * scale,
+                    rect.size() * scale,
+                ),
+                viewport.color,
+            );
+        }
+        for marker in &self.markers {
+            let pos = dst.top_left() + marker.pos * scale;
+            let half = marker.size * 0.5;
+            draw.rect(
+                RectF::new(pos.x - half, pos.y - half, marker.size, marker.size),
+                marker.color,
+            );
+        }
+    }
+}
@@ -1,46 +1,83 @@
 //! Graphics and rendering.
 
+mod atlas_debug;
 mod bindings;
 mod blend_mode;
 mod buffer_cache;
+mod camera2d;
+mod camera_shake;
 mod color_mode;
+mod day_night;
 mod draw;
+mod effects;
+mod fog_of_war;
 mod font;
+mod frame_stats;
+mod gpu_memory;
+mod gpu_timer;
 mod graphics;
 mod index_buffer;
+mod light2d;
+mod light_map;
+mod material;
+mod mesh2d;
+mod minimap;
 mod params;
 mod render_data;
 mod sampler;
 mod screen;
 mod shader;
+mod split_screen;
 mod sub_texture;
 mod surface;
+mod text_layout;
 mod texture;
+mod texture_atlas;
 mod texture_format;
+mod texture_load;
 mod texture_packer;
 mod texture_pixel;
 mod topology;
 mod vertex;
 mod vertex_buffer;
+mod weather;
 
+pub use atlas_debug::*;
 pub use bindings::*;
 pub use blend_mode::*;
+pub use camera_shake::*;
+pub use camera2d::*;
 pub use color_mode::*;
+pub use day_night::*;
 pub use draw::*;
+pub use effects::*;
+pub use fog_of_war::*;
 pub use font::*;
+pub use frame_stats::*;
+pub use gpu_memory::*;
 pub use graphics::*;
 pub use index_buffer::*;
+pub use light_map::*;
+pub use light2d::*;
+pub use material::*;
+pub use mesh2d::*;
+pub use minimap::*;
 pub use params::*;
 pub(crate) use render_data::*;
 pub use sampler::*;
 pub use screen::*;
 pub use shader::*;
+pub use split_screen::*;
 pub use sub_texture::*;
 pub use surface::*;
+pub use text_layout::*;
 pub use texture::*;
+pub use texture_atlas::*;
 pub use texture_format::*;
+pub use texture_load::*;
 pub use texture_packer::*;
 pub use texture_pixel::*;
 pub use topology::*;
 pub use vertex::*;
 pub use vertex_buffer::*;
+pub use weather::*;
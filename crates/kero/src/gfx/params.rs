@@ -1,10 +1,23 @@
 use crate::gfx::{BindingValue, Sampler, Texture, UniformValue};
 use crate::math::{Mat2, Mat3, Mat4, Vec2, Vec3, Vec4};
-use naga::{ImageClass, ImageDimension, Module, Scalar, ScalarKind, TypeInner, VectorSize};
+use naga::{
+    AddressSpace, ArraySize, ImageClass, ImageDimension, Module, Scalar, ScalarKind, TypeInner,
+    VectorSize,
+};
 use serde::{Deserialize, Serialize};
 use std::hash::Hash;
 
-/// A shader's defined parameters.
+/// A shader's defined parameters, in binding order.
+///
+/// This can be used to inspect what a shader expects at runtime, such as to generate tweak
+/// sliders for every uniform of a shader:
+///
+/// ```ignore
+/// for def in shader.param_defs().uniforms() {
+///     let value = material.get(&def.name).cloned().or_else(|| def.default_value().map(Into::into));
+///     // ...
+/// }
+/// ```
 #[derive(Debug, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
 pub struct ParamDefs {
     pub defs: Vec<ParamDef>,
@@ -116,6 +129,17 @@ impl ParamDefs {
                         },
                 } => ParamType::Uniform(UniformType::Mat4),
 
+                // uniform blocks (structs and fixed-size arrays)
+                TypeInner::Struct { .. }
+                | TypeInner::Array {
+                    size: ArraySize::Constant(_),
+                    ..
+                } if global.space == AddressSpace::Uniform => {
+                    ParamType::Uniform(UniformType::Block {
+                        size: naga_ty.inner.size(module.to_ctx()),
+                    })
+                }
+
                 _ => {
                     let naga_name = naga_ty.name.clone().unwrap_or_else(|| "???".to_string());
                     panic!("global variable {name:?} has invalid type {naga_name:?}");
@@ -123,7 +147,11 @@ impl ParamDefs {
             };
 
             // the parameter is valid, add it to the list
-            defs.push(ParamDef { name, ty });
+            defs.push(ParamDef {
+                name,
+                ty,
+                binding: binding.binding,
+            });
         }
 
         Self { defs }
@@ -134,6 +162,38 @@ impl ParamDefs {
     pub fn find(&self, name: &str) -> Option<&ParamDef> {
         self.defs.iter().find(|def| def.name == name)
     }
+
+    /// Index of the parameter with the name, which is also its binding.
+    #[inline]
+    pub fn position(&self, name: &str) -> Option<usize> {
+        self.defs.iter().position(|def| def.name == name)
+    }
+
+    /// How many parameters are defined.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.defs.len()
+    }
+
+    /// If no parameters are defined.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.defs.is_empty()
+    }
+
+    /// Iterate over all parameters, in binding order.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &ParamDef> {
+        self.defs.iter()
+    }
+
+    /// Iterate over the uniform parameters, skipping textures and samplers.
+    #[inline]
+    pub fn uniforms(&self) -> impl Iterator<Item = &ParamDef> {
+        self.defs
+            .iter()
+            .filter(|def| matches!(def.ty, ParamType::Uniform(_)))
+    }
 }
 
 /// A parameter defined in the shader.
@@ -141,6 +201,22 @@ impl ParamDefs {
 pub struct ParamDef {
     pub name: String,
     pub ty: ParamType,
+
+    /// The parameter's `@binding` index in `@group(0)`.
+    #[serde(default)]
+    pub binding: u32,
+}
+
+impl ParamDef {
+    /// The value the parameter has if nothing sets it. Textures and samplers have no default
+    /// here, since they are bound to the default texture and sampler.
+    #[inline]
+    pub fn default_value(&self) -> Option<UniformValue> {
+        match self.ty {
+            ParamType::Uniform(ty) => Some(ty.default_value()),
+            _ => None,
+        }
+    }
 }
 
 /// A shader's parameter type.
@@ -173,6 +249,10 @@ pub enum UniformType {
     Mat2,
     Mat3,
     Mat4,
+    /// A struct or fixed-size array, uploaded as raw bytes. The size is in bytes.
+    Block {
+        size: u32,
+    },
 }
 
 impl UniformType {
@@ -189,9 +269,31 @@ impl UniformType {
             Self::Mat2 => UniformValue::Mat2(Mat2::IDENTITY),
             Self::Mat3 => UniformValue::Mat3(Mat3::IDENTITY),
             Self::Mat4 => UniformValue::Mat4(Mat4::IDENTITY),
+            Self::Block { size } => UniformValue::Block(vec![0; size as usize]),
         }
     }
 
+    /// How many numbers make up a value of this type, such as `3` for a `vec3`, or `None` for
+    /// uniform blocks, whose layout isn't known.
+    #[inline]
+    pub fn components(self) -> Option<usize> {
+        match self {
+            Self::Int | Self::Uint | Self::Float => Some(1),
+            Self::Vec2 => Some(2),
+            Self::Vec3 => Some(3),
+            Self::Vec4 | Self::Mat2 => Some(4),
+            Self::Mat3 => Some(9),
+            Self::Mat4 => Some(16),
+            Self::Block { .. } => None,
+        }
+    }
+
+    /// If values of this type are whole numbers.
+    #[inline]
+    pub fn is_integer(self) -> bool {
+        matches!(self, Self::Int | Self::Uint)
+    }
+
     /// The size (in bytes) of this uniform type.
     #[inline]
     pub fn size(self) -> usize {
@@ -202,6 +304,7 @@ impl UniformType {
             Self::Vec4 | Self::Mat2 => 16,
             Self::Mat3 => 36,
             Self::Mat4 => 64,
+            Self::Block { size } => size as usize,
         }
     }
 }
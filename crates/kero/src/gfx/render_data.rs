@@ -1,10 +1,11 @@
 use crate::color::Rgba8;
 use crate::gfx::draw::DrawCache;
 use crate::gfx::{
-    BindingValue, Bindings, BlendMode, IndexBuffer, Sampler, Shader, Surface, Texture, Topology,
-    UniformValue, Vertex, VertexBuffer,
+    BindingValue, Bindings, BlendMode, ColorMode, IndexBuffer, Material, Sampler, Shader, Surface,
+    Texture, Topology, UniformValue, Vertex, VertexBuffer,
 };
-use crate::math::{Mat4, Numeric, Rect, Vec2};
+use crate::math::{Mat4, Numeric, Rect, Vec2, Vec2U, vec2};
+use std::mem::swap;
 
 #[derive(Debug)]
 pub struct RenderData {
@@ -24,6 +25,9 @@ impl RenderData {
 #[derive(Debug)]
 pub struct RenderPass {
     pub surface: Option<Surface>,
+    /// The texture rendered to, or `None` for the window. This is the surface's texture, or
+    /// an offscreen texture that a layer with a post material is rendered to.
+    pub target: Option<Texture>,
     pub clear_color: Option<Rgba8>,
     pub layers: Vec<RenderLayer>,
 }
@@ -35,34 +39,59 @@ impl RenderPass {
         layers_vec: Vec<RenderLayer>,
     ) -> Self {
         Self {
+            target: surface.as_ref().map(|s| s.texture().clone()),
             surface,
             clear_color,
             layers: layers_vec,
         }
     }
 
+    fn target_size(&self, cache: &DrawCache) -> Vec2U {
+        self.target
+            .as_ref()
+            .map(|t| t.size())
+            .unwrap_or(cache.window_size)
+    }
+
     pub fn layer(&mut self, index: usize) -> &mut RenderLayer {
         self.layers.get_mut(index).unwrap()
     }
 
     pub fn ensure_layer(&mut self, layer: usize, cache: &mut DrawCache) {
-        let size = self
-            .surface
-            .as_ref()
-            .map(|s| s.size())
-            .unwrap_or(cache.window_size);
+        let size = self.target_size(cache);
         while self.layers.len() <= layer {
             self.layers.push(RenderLayer::new(cache, size.to_f32()));
         }
     }
 
-    pub fn finish(&mut self, cache: &mut DrawCache) -> bool {
+    /// Flush all the layers and add the pass to `passes` if it has anything to render. Layers
+    /// with a post material add a pass before it that renders them to an offscreen texture.
+    pub fn finish(mut self, cache: &mut DrawCache, passes: &mut Vec<RenderPass>) {
         let mut should_submit = self.clear_color.is_some();
+        let size = self.target_size(cache);
         for layer in self.layers.iter_mut() {
             layer.flush(cache);
+            if let Some(post) = layer.post.take()
+                && !layer.calls.is_empty()
+            {
+                let texture = cache.post_texture(size);
+                let mut inner = RenderLayer::new(cache, size.to_f32());
+                swap(&mut inner.calls, &mut layer.calls);
+                let mut layers = cache.render_layer_vecs.pop().unwrap_or_default();
+                layers.push(inner);
+                passes.push(Self {
+                    surface: None,
+                    target: Some(texture.clone()),
+                    clear_color: Some(Rgba8::TRANSPARENT),
+                    layers,
+                });
+                layer.composite(&post, &texture, size.to_f32(), cache);
+            }
             should_submit |= layer.calls.len() > 0;
         }
-        should_submit
+        if should_submit {
+            passes.push(self);
+        }
     }
 }
 
@@ -80,6 +109,7 @@ pub struct RenderLayer {
     pub main_texture: Texture,
     pub main_sampler: Sampler,
     pub ortho: Mat4<f32>,
+    pub post: Option<Material>,
 }
 
 impl RenderLayer {
@@ -97,6 +127,7 @@ impl RenderLayer {
             main_texture: cache.default_texture.clone(),
             main_sampler: Sampler::default(),
             ortho: Mat4::ortho(0.0, size.x, size.y, 0.0, 0.0, 1.0),
+            post: None,
         }
     }
 
@@ -106,10 +137,13 @@ impl RenderLayer {
         }
 
         // update the vertex/index buffers
-        let (vertices, indices) =
-            cache
-                .buffer_cache
-                .request(&cache.device, &cache.queue, &self.vertices, &self.indices);
+        let (vertices, indices) = cache.buffer_cache.request(
+            &cache.device,
+            &cache.queue,
+            &cache.memory,
+            &self.vertices,
+            &self.indices,
+        );
         self.vertices.clear();
         self.indices.clear();
 
@@ -151,6 +185,19 @@ impl RenderLayer {
         self.bindings.reset(&self.shader, &cache.default_texture);
     }
 
+    pub fn set_material(&mut self, material: &Material, cache: &mut DrawCache) {
+        self.set_shader(material.shader(), cache);
+        if material
+            .values()
+            .any(|(idx, val)| &self.bindings.values[idx] != val)
+        {
+            self.flush(cache);
+            for (idx, val) in material.values() {
+                self.bindings.values[idx] = val.clone();
+            }
+        }
+    }
+
     pub fn set_param(&mut self, name: &str, value: BindingValue, cache: &mut DrawCache) {
         self.flush(cache);
         self.bindings.set(&self.shader, name, value);
@@ -206,6 +253,32 @@ impl RenderLayer {
         }
     }
 
+    /// Draw the texture over the whole layer through the post material.
+    fn composite(
+        &mut self,
+        post: &Material,
+        texture: &Texture,
+        size: Vec2<f32>,
+        cache: &mut DrawCache,
+    ) {
+        self.set_material(post, cache);
+        self.blend_mode = BlendMode::Normal;
+        self.scissor_rect = None;
+        self.topology = Topology::Triangles;
+        self.view_matrix = Mat4::IDENTITY;
+        self.main_texture = texture.clone();
+        self.main_sampler = Sampler::default();
+        let (color, mode) = (Rgba8::WHITE, ColorMode::MULT);
+        self.vertices.extend_from_slice(&[
+            Vertex::new(vec2(0.0, 0.0), vec2(0.0, 0.0), color, mode),
+            Vertex::new(vec2(size.x, 0.0), vec2(1.0, 0.0), color, mode),
+            Vertex::new(size, vec2(1.0, 1.0), color, mode),
+            Vertex::new(vec2(0.0, size.y), vec2(0.0, 1.0), color, mode),
+        ]);
+        self.indices.extend_from_slice(&[0, 1, 2, 0, 2, 3]);
+        self.flush(cache);
+    }
+
     pub fn submit_buffers(
         &mut self,
         texture: Texture,
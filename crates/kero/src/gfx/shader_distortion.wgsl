@@ -0,0 +1,31 @@
+@group(0) @binding(0)
+var noise_texture: texture_2d<f32>;
+
+@group(0) @binding(1)
+var noise_sampler: sampler;
+
+// offset of the noise texture, in noise texture repeats
+@group(0) @binding(2)
+var<uniform> noise_scroll: vec2f;
+
+// how many times the noise texture repeats across the source texture
+@group(0) @binding(3)
+var<uniform> noise_scale: vec2f;
+
+// maximum displacement, in source texture coordinates
+@group(0) @binding(4)
+var<uniform> strength: vec2f;
+
+@vertex
+fn vert_main(vert: Vertex) -> Fragment {
+    return vert_default(vert);
+}
+
+@fragment
+fn frag_main(frag: Fragment) -> @location(0) vec4f {
+    // the noise's red/green channels map to a -1 to 1 displacement on each axis
+    let noise = textureSample(noise_texture, noise_sampler, frag.tex * noise_scale + noise_scroll);
+    var displaced = frag;
+    displaced.tex = frag.tex + (noise.rg * 2.0 - 1.0) * strength;
+    return frag_default(displaced);
+}
@@ -0,0 +1,14 @@
+@vertex
+fn vert_main(vert: Vertex) -> Fragment {
+    return vert_default(vert);
+}
+
+@fragment
+fn frag_main(frag: Fragment) -> @location(0) vec4f {
+    // the glyph's edge is where the distance is 0.5, smoothed across about one screen pixel
+    // so it stays crisp however much the glyph is scaled
+    let dist = textureSample(main_texture, main_sampler, frag.tex).a;
+    let width = max(fwidth(dist) * 0.5, 0.0001);
+    let alpha = smoothstep(0.5 - width, 0.5 + width, dist);
+    return apply_mode(vec4f(alpha), frag.col, frag.mode);
+}
@@ -0,0 +1,219 @@
+use crate::gfx::Draw;
+use crate::math::{Affine2F, RectF, Vec2F, rect, vec2};
+
+/// How a [`SplitScreen`] divides the screen between players.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum SplitLayout {
+    /// Side by side for 2 players, one wide view on top of two for 3 players, and a
+    /// grid for more.
+    #[default]
+    Auto,
+
+    /// Views stacked on top of each other.
+    Rows,
+
+    /// Views placed side by side.
+    Columns,
+
+    /// Views placed in the smallest grid that fits them, filled row by row.
+    Grid,
+}
+
+/// Divides the screen into a viewport for each player, and renders the shared world once per
+/// viewport with each player's view transform.
+///
+/// ```ignore
+/// split.render(
+///     draw,
+///     |i, size| SplitScreen::look_at(size, players[i].pos, 2.0),
+///     |draw, i| world.draw(draw),
+///     |draw, i, size| hud.draw(draw, &players[i], size),
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct SplitScreen {
+    size: Vec2F,
+    players: usize,
+    layout: SplitLayout,
+    gap: f32,
+    rects: Vec<RectF>,
+}
+
+impl SplitScreen {
+    /// Create a split screen of the provided size for a number of players.
+    pub fn new(size: impl Into<Vec2F>, players: usize) -> Self {
+        let mut split = Self {
+            size: size.into(),
+            players,
+            layout: SplitLayout::Auto,
+            gap: 0.0,
+            rects: Vec::new(),
+        };
+        split.update_rects();
+        split
+    }
+
+    /// Return the split screen with the layout set.
+    #[inline]
+    pub fn with_layout(mut self, layout: SplitLayout) -> Self {
+        self.set_layout(layout);
+        self
+    }
+
+    /// Return the split screen with the gap set.
+    #[inline]
+    pub fn with_gap(mut self, gap: f32) -> Self {
+        self.set_gap(gap);
+        self
+    }
+
+    /// Total size of the screen being split.
+    #[inline]
+    pub fn size(&self) -> Vec2F {
+        self.size
+    }
+
+    /// Set the total size of the screen being split, such as when the window is resized.
+    #[inline]
+    pub fn set_size(&mut self, size: impl Into<Vec2F>) {
+        self.size = size.into();
+        self.update_rects();
+    }
+
+    /// How many players the screen is split between.
+    #[inline]
+    pub fn players(&self) -> usize {
+        self.players
+    }
+
+    /// Set how many players the screen is split between.
+    #[inline]
+    pub fn set_players(&mut self, players: usize) {
+        self.players = players;
+        self.update_rects();
+    }
+
+    /// How the screen is divided.
+    #[inline]
+    pub fn layout(&self) -> SplitLayout {
+        self.layout
+    }
+
+    /// Set how the screen is divided.
+    #[inline]
+    pub fn set_layout(&mut self, layout: SplitLayout) {
+        self.layout = layout;
+        self.update_rects();
+    }
+
+    /// Space between viewports, in pixels.
+    #[inline]
+    pub fn gap(&self) -> f32 {
+        self.gap
+    }
+
+    /// Set the space between viewports, in pixels.
+    #[inline]
+    pub fn set_gap(&mut self, gap: f32) {
+        self.gap = gap.max(0.0);
+        self.update_rects();
+    }
+
+    /// The viewport rectangle of every player, in screen space.
+    #[inline]
+    pub fn rects(&self) -> &[RectF] {
+        &self.rects
+    }
+
+    /// The viewport rectangle of a player, in screen space.
+    #[inline]
+    pub fn rect(&self, player: usize) -> Option<RectF> {
+        self.rects.get(player).copied()
+    }
+
+    /// Find the player whose viewport contains the screen position.
+    #[inline]
+    pub fn player_at(&self, pos: impl Into<Vec2F>) -> Option<usize> {
+        let pos = pos.into();
+        self.rects.iter().position(|r| r.contains(pos))
+    }
+
+    /// A view transform that centers `focus` in a viewport of `size`, scaled by `zoom`.
+    #[inline]
+    pub fn look_at(size: impl Into<Vec2F>, focus: impl Into<Vec2F>, zoom: f32) -> Affine2F {
+        Affine2F::translation(size.into() / 2.0)
+            * Affine2F::scale_of(zoom)
+            * Affine2F::translation(-focus.into())
+    }
+
+    /// Start drawing to a player's viewport. The viewport's origin becomes `(0, 0)` and
+    /// drawing is clipped to it. Must be followed by [`pop_view`](Self::pop_view).
+    pub fn push_view(&self, draw: &mut Draw, player: usize) {
+        let rect = self.rects[player];
+        draw.push_new_transform(Affine2F::translation(rect.top_left()));
+        draw.push_clip_rect(RectF::sized(rect.size()));
+    }
+
+    /// Finish drawing to the viewport started with [`push_view`](Self::push_view).
+    pub fn pop_view(&self, draw: &mut Draw) {
+        draw.pop_clip_rect().unwrap();
+        draw.pop_transform().unwrap();
+    }
+
+    /// Render every player's viewport. For each player, `view` returns their view transform
+    /// given the viewport size, `world` draws the shared world with that transform applied,
+    /// and then `ui` draws the player's UI in viewport space on top of it.
+    pub fn render(
+        &self,
+        draw: &mut Draw,
+        mut view: impl FnMut(usize, Vec2F) -> Affine2F,
+        mut world: impl FnMut(&mut Draw, usize),
+        mut ui: impl FnMut(&mut Draw, usize, Vec2F),
+    ) {
+        for (player, rect) in self.rects.iter().enumerate() {
+            self.push_view(draw, player);
+            draw.push_transform(view(player, rect.size()));
+            world(draw, player);
+            draw.pop_transform().unwrap();
+            ui(draw, player, rect.size());
+            self.pop_view(draw);
+        }
+    }
+
+    fn update_rects(&mut self) {
+        self.rects.clear();
+        let n = self.players;
+        if n == 0 {
+            return;
+        }
+        let (cols, rows) = match self.layout {
+            SplitLayout::Auto if n == 3 => {
+                // one wide view on top, two below
+                let h = (self.size.y - self.gap) / 2.0;
+                let w = (self.size.x - self.gap) / 2.0;
+                let y = h + self.gap;
+                self.rects.extend([
+                    rect(0.0, 0.0, self.size.x, h),
+                    rect(0.0, y, w, h),
+                    rect(w + self.gap, y, w, h),
+                ]);
+                return;
+            }
+            SplitLayout::Auto if n <= 2 => (n, 1),
+            SplitLayout::Rows => (1, n),
+            SplitLayout::Columns => (n, 1),
+            SplitLayout::Auto | SplitLayout::Grid => {
+                let cols = (n as f32).sqrt().ceil() as usize;
+                (cols, n.div_ceil(cols))
+            }
+        };
+        let cell = vec2(
+            (self.size.x - self.gap * (cols - 1) as f32) / cols as f32,
+            (self.size.y - self.gap * (rows - 1) as f32) / rows as f32,
+        );
+        self.rects.extend((0..n).map(|i| {
+            let (x, y) = ((i % cols) as f32, (i / cols) as f32);
+            RectF::pos_size(cell * vec2(x, y) + vec2(x, y) * self.gap, cell)
+        }));
+    }
+}
@@ -0,0 +1,321 @@
+use crate::gfx::Font;
+use fey_math::{Vec2F, vec2};
+use std::ops::Range;
+
+/// How lines of a [`TextLayout`] are aligned.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum TextAlign {
+    #[default]
+    Left,
+    Center,
+    Right,
+
+    /// Wrapped lines are stretched to the layout's width by widening their spaces. The last
+    /// line of each paragraph is left-aligned.
+    Justify,
+}
+
+/// A glyph positioned by a [`TextLayout`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct LayoutGlyph {
+    /// The character, which may not have a glyph in the font.
+    pub chr: char,
+
+    /// Byte index of the character in the source text.
+    pub index: usize,
+
+    /// Which line the glyph is on.
+    pub line: usize,
+
+    /// Pen position of the glyph, relative to the top-left of the layout.
+    pub pos: Vec2F,
+
+    /// How far the pen advances after the glyph, including kerning.
+    pub adv: f32,
+}
+
+/// A line of a [`TextLayout`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutLine {
+    /// Range of the line's glyphs in [`TextLayout::glyphs`].
+    pub glyphs: Range<usize>,
+
+    /// Byte range of the line in the source text, not including its line break.
+    pub bytes: Range<usize>,
+
+    /// Left edge of the line after alignment.
+    pub x: f32,
+
+    /// Top of the line.
+    pub y: f32,
+
+    /// Width of the line, not including trailing whitespace.
+    pub width: f32,
+}
+
+/// Text broken into lines and positioned glyphs, ready to be measured, hit-tested, or drawn
+/// with [`Draw::text_layout`](crate::gfx::Draw::text_layout).
+///
+/// Text is wrapped at spaces to fit the max width, and words too long for a line on their own
+/// are broken between characters. Line breaks (`\n`) always start a new line. Lines are
+/// aligned within the width of the widest line, which is also the layout's width.
+///
+/// ```ignore
+/// let layout = TextLayout::new("Hello, world!", &font, 120.0, TextAlign::Center, 1.2);
+/// let pos = panel.center() - layout.size() * 0.5;
+/// draw.text_layout(&layout, pos, &font, Rgba8::WHITE);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextLayout {
+    glyphs: Vec<LayoutGlyph>,
+    lines: Vec<LayoutLine>,
+    line_height: f32,
+    size: Vec2F,
+}
+
+impl TextLayout {
+    /// Lay out the text at the font's size. Lines are wrapped to `max_width` if provided, and
+    /// `line_spacing` is multiplied by the font's size to get the distance between lines.
+    pub fn new(
+        text: &str,
+        font: &Font,
+        max_width: impl Into<Option<f32>>,
+        align: TextAlign,
+        line_spacing: f32,
+    ) -> Self {
+        let max_width = max_width.into().unwrap_or(f32::INFINITY);
+        let mut layout = Self {
+            glyphs: Vec::new(),
+            lines: Vec::new(),
+            line_height: font.size() * line_spacing,
+            size: Vec2F::ZERO,
+        };
+
+        // lines that were wrapped, as opposed to ending a paragraph, can be justified
+        let mut wrapped = Vec::new();
+        let mut start = 0;
+        for para in text.split('\n') {
+            layout.wrap_paragraph(para, start, font, max_width, &mut wrapped);
+            start += para.len() + 1;
+        }
+
+        let width = layout.lines.iter().map(|l| l.width).fold(0.0, f32::max);
+        for (line, wrapped) in layout.lines.iter_mut().zip(wrapped) {
+            let extra = width - line.width;
+            let glyphs = &mut layout.glyphs[line.glyphs.clone()];
+            match align {
+                TextAlign::Left => {}
+                TextAlign::Center => line.x = extra * 0.5,
+                TextAlign::Right => line.x = extra,
+                TextAlign::Justify => {
+                    let content = glyphs
+                        .iter()
+                        .rposition(|g| !g.chr.is_whitespace())
+                        .map_or(0, |i| i + 1);
+                    let gaps = glyphs[..content]
+                        .iter()
+                        .filter(|g| g.chr.is_whitespace())
+                        .count();
+                    if wrapped && gaps > 0 {
+                        let gap = extra / gaps as f32;
+                        let mut shift = 0.0;
+                        for (i, g) in glyphs.iter_mut().enumerate() {
+                            g.pos.x += shift;
+                            if g.chr.is_whitespace() && i < content {
+                                g.adv += gap;
+                                shift += gap;
+                            }
+                        }
+                        line.width = width;
+                    }
+                }
+            }
+            for g in glyphs {
+                g.pos.x += line.x;
+            }
+        }
+
+        layout.size = vec2(width, layout.lines.len() as f32 * layout.line_height);
+        layout
+    }
+
+    /// Break a paragraph into lines that fit within `max_width`.
+    fn wrap_paragraph(
+        &mut self,
+        para: &str,
+        offset: usize,
+        font: &Font,
+        max_width: f32,
+        wrapped: &mut Vec<bool>,
+    ) {
+        let chars: Vec<(usize, char)> = para.char_indices().collect();
+        let advs: Vec<f32> = chars
+            .iter()
+            .enumerate()
+            .map(|(i, &(_, chr))| {
+                let adv = font.glyph(chr).map_or(0.0, |g| g.adv);
+                let kern = chars
+                    .get(i + 1)
+                    .and_then(|&(_, next)| font.kerning(chr, next));
+                adv + kern.unwrap_or(0.0)
+            })
+            .collect();
+        let width = |range: Range<usize>| advs[range].iter().sum::<f32>();
+
+        let mut line_start = 0;
+        let mut x = 0.0;
+        let mut i = 0;
+        while i < chars.len() {
+            // each segment is a word followed by the whitespace after it
+            let word_end = (i..chars.len())
+                .find(|&j| chars[j].1.is_whitespace())
+                .unwrap_or(chars.len());
+            let seg_end = (word_end..chars.len())
+                .find(|&j| !chars[j].1.is_whitespace())
+                .unwrap_or(chars.len());
+
+            let word = width(i..word_end);
+            if x + word > max_width && i > line_start {
+                self.push_line(&chars, &advs, offset, line_start..i);
+                wrapped.push(true);
+                line_start = i;
+                x = 0.0;
+            }
+            if word > max_width {
+                for j in i..word_end {
+                    if x + advs[j] > max_width && j > line_start {
+                        self.push_line(&chars, &advs, offset, line_start..j);
+                        wrapped.push(true);
+                        line_start = j;
+                        x = 0.0;
+                    }
+                    x += advs[j];
+                }
+            } else {
+                x += word;
+            }
+            x += width(word_end..seg_end);
+            i = seg_end;
+        }
+        self.push_line(&chars, &advs, offset, line_start..chars.len());
+        wrapped.push(false);
+    }
+
+    /// Add a line of characters, positioned from the left.
+    fn push_line(
+        &mut self,
+        chars: &[(usize, char)],
+        advs: &[f32],
+        offset: usize,
+        range: Range<usize>,
+    ) {
+        let line = self.lines.len();
+        let y = line as f32 * self.line_height;
+        let first = self.glyphs.len();
+        let mut x = 0.0;
+        let mut width = 0.0;
+        for i in range.clone() {
+            let (index, chr) = chars[i];
+            self.glyphs.push(LayoutGlyph {
+                chr,
+                index: offset + index,
+                line,
+                pos: vec2(x, y),
+                adv: advs[i],
+            });
+            x += advs[i];
+            if !chr.is_whitespace() {
+                width = x;
+            }
+        }
+        let byte = |i: usize| offset + chars.get(i).map_or_else(|| byte_len(chars), |c| c.0);
+        self.lines.push(LayoutLine {
+            glyphs: first..self.glyphs.len(),
+            bytes: byte(range.start)..byte(range.end),
+            x: 0.0,
+            y,
+            width,
+        });
+    }
+
+    /// Every character of the text except line breaks, in order.
+    #[inline]
+    pub fn glyphs(&self) -> &[LayoutGlyph] {
+        &self.glyphs
+    }
+
+    /// The lines of the layout, in order. There is always at least one line.
+    #[inline]
+    pub fn lines(&self) -> &[LayoutLine] {
+        &self.lines
+    }
+
+    /// Distance between the tops of consecutive lines.
+    #[inline]
+    pub fn line_height(&self) -> f32 {
+        self.line_height
+    }
+
+    /// Size of the layout, which is the width of its widest line by the height of its lines.
+    #[inline]
+    pub fn size(&self) -> Vec2F {
+        self.size
+    }
+
+    /// The byte index in the text where a cursor should be placed when clicking `pos`,
+    /// relative to the top-left of the layout. Positions outside the layout snap to the
+    /// closest line and character.
+    pub fn hit_test(&self, pos: impl Into<Vec2F>) -> usize {
+        let pos = pos.into();
+        let line = match self.line_height > 0.0 {
+            true => (pos.y / self.line_height).floor().max(0.0) as usize,
+            false => 0,
+        };
+        let line = line.min(self.lines.len() - 1);
+        let glyphs = &self.glyphs[self.lines[line].glyphs.clone()];
+        if let Some(g) = glyphs.iter().find(|g| pos.x < g.pos.x + g.adv * 0.5) {
+            return g.index;
+        }
+
+        // a wrapped line's end is the start of the next, so stay before its trailing space
+        let end = self.lines[line].bytes.end;
+        match self.lines.get(line + 1) {
+            Some(next) if next.bytes.start == end => glyphs.last().map_or(end, |g| g.index),
+            _ => end,
+        }
+    }
+
+    /// Position of a cursor placed before the character at byte `index`, relative to the
+    /// top-left of the layout. The cursor extends [`line_height`](Self::line_height) downward
+    /// from this point. Indices past the end of the text are placed at the end.
+    pub fn cursor_pos(&self, index: usize) -> Vec2F {
+        for (i, line) in self.lines.iter().enumerate() {
+            if let Some(g) = self.glyphs[line.glyphs.clone()]
+                .iter()
+                .find(|g| g.index == index)
+            {
+                return g.pos;
+            }
+            // a wrapped line's end is the start of the next, so it belongs to that one
+            let next_start = self.lines.get(i + 1).map(|l| l.bytes.start);
+            if index == line.bytes.end && next_start != Some(index) {
+                return vec2(self.line_end(line), line.y);
+            }
+        }
+        let last = self.lines.last().unwrap();
+        vec2(self.line_end(last), last.y)
+    }
+
+    /// Right edge of the line, including trailing whitespace.
+    #[inline]
+    fn line_end(&self, line: &LayoutLine) -> f32 {
+        self.glyphs[line.glyphs.clone()]
+            .last()
+            .map_or(line.x, |g| g.pos.x + g.adv)
+    }
+}
+
+#[inline]
+fn byte_len(chars: &[(usize, char)]) -> usize {
+    chars.last().map_or(0, |&(i, chr)| i + chr.len_utf8())
+}
@@ -1,7 +1,9 @@
-use crate::gfx::{SubTexture, TextureFormat};
-use crate::grid::VecGrid;
-use crate::math::{Numeric, RectU, Vec2U};
+use crate::gfx::gpu_memory::Allocation;
+use crate::gfx::{GpuMemory, GpuResourceKind, SubTexture, TextureFormat, TexturePixel};
+use crate::grid::{DirtyGrid, Grid, VecGrid};
+use crate::math::{Numeric, RectU, Vec2U, rect, vec2};
 use std::cmp::Ordering;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 use wgpu::{
@@ -48,17 +50,20 @@ pub(crate) struct Inner {
     queue: Queue,
     size: Vec2U,
     format: TextureFormat,
+    allocation: Allocation,
 }
 
 impl Texture {
     pub(crate) fn new(
         device: &Device,
         queue: Queue,
+        memory: &GpuMemory,
         size: Vec2U,
         format: TextureFormat,
         surface: bool,
     ) -> Self {
-        let mut usage = TextureUsages::COPY_DST | TextureUsages::TEXTURE_BINDING;
+        let mut usage =
+            TextureUsages::COPY_DST | TextureUsages::COPY_SRC | TextureUsages::TEXTURE_BINDING;
         if surface {
             usage |= TextureUsages::RENDER_ATTACHMENT;
         }
@@ -76,40 +81,106 @@ impl Texture {
             usage,
             view_formats: &[],
         });
+        let kind = match surface {
+            true => GpuResourceKind::Surface,
+            false => GpuResourceKind::Texture,
+        };
+        let bytes = size.x as usize * size.y as usize * format.bytes_per_pixel();
         Self(Arc::new(Inner {
             texture,
             queue,
             size,
             format,
+            allocation: memory.allocate(kind, bytes),
         }))
     }
 
     pub(crate) fn upload_bytes(&self, data: &[u8]) {
         assert_eq!(data.len(), self.size_in_bytes());
-        let (width, height) = self.0.size.into();
-        let bytes_per_row = Some(self.0.format.bytes_per_pixel().to_u32() * width);
-        let rows_per_image = Some(height);
+        self.upload_rows(0, self.0.size.y, data);
+    }
+
+    /// Upload `height` rows of pixel data, starting at row `y`.
+    #[inline]
+    pub(crate) fn upload_rows(&self, y: u32, height: u32, data: &[u8]) {
+        self.write_region(rect(0, y, self.0.size.x, height), data);
+    }
+
+    /// Overwrite a region of the texture with tightly packed pixel data in the texture's
+    /// [`format`](Self::format). Panics if the region is out of bounds or the data is the
+    /// wrong length.
+    pub fn write_region(&self, region: impl Into<RectU>, data: &[u8]) {
+        let RectU { x, y, w, h } = region.into();
+        assert!(
+            x.checked_add(w).is_some_and(|r| r <= self.0.size.x)
+                && y.checked_add(h).is_some_and(|b| b <= self.0.size.y),
+            "region is out of the texture's bounds"
+        );
+        let bytes_per_row = self.0.format.bytes_per_pixel().to_u32() * w;
+        assert_eq!(data.len(), bytes_per_row as usize * h as usize);
+        if w == 0 || h == 0 {
+            return;
+        }
         self.0.queue.write_texture(
             TexelCopyTextureInfo {
                 texture: &self.0.texture,
                 mip_level: 0,
-                origin: Origin3d::ZERO,
+                origin: Origin3d { x, y, z: 0 },
                 aspect: TextureAspect::All,
             },
             data,
             TexelCopyBufferLayout {
                 offset: 0,
-                bytes_per_row,
-                rows_per_image,
+                bytes_per_row: Some(bytes_per_row),
+                rows_per_image: Some(h),
             },
             Extent3d {
-                width,
-                height,
+                width: w,
+                height: h,
                 depth_or_array_layers: 1,
             },
         );
     }
 
+    /// Overwrite a region of the texture with the same region of a grid of pixels. Panics if
+    /// the pixel format doesn't match the texture or the region is out of either's bounds.
+    pub fn write_grid_region<G, P>(&self, grid: &G, region: impl Into<RectU>)
+    where
+        G: Grid<Item = P>,
+        P: TexturePixel,
+    {
+        assert_eq!(P::TEXTURE_FORMAT, self.format(), "pixel format mismatch");
+        let region = region.into();
+        let (left, right) = (region.x as usize, region.right() as usize);
+        let mut pixels = Vec::with_capacity(region.w as usize * region.h as usize);
+        for y in region.y..region.bottom() {
+            match grid.row_slice(y).and_then(|row| row.get(left..right)) {
+                Some(row) => pixels.extend_from_slice(row),
+                None => pixels.extend(
+                    (region.x..region.right())
+                        .map(|x| *grid.get(x, y).expect("region is out of the grid's bounds")),
+                ),
+            }
+        }
+        self.write_region(region, bytemuck::cast_slice(&pixels));
+    }
+
+    /// Upload the dirty region of a grid of pixels and clear it. Returns `true` if anything
+    /// was uploaded.
+    pub fn write_dirty<G, P>(&self, grid: &mut DirtyGrid<G>) -> bool
+    where
+        G: Grid<Item = P>,
+        P: TexturePixel,
+    {
+        match grid.take_dirty() {
+            Some(region) => {
+                self.write_grid_region(grid, region);
+                true
+            }
+            None => false,
+        }
+    }
+
     // pub fn upload_pixels<P: TexturePixel>(&self, pixels: &[P]) -> Result<(), TextureUploadError> {
     //     if P::TEXTURE_FORMAT != self.format() {
     //         return Err(TextureUploadError::FormatMismatch {
@@ -141,6 +212,24 @@ impl Texture {
     //     }
     // }
 
+    /// Mark the texture as used this frame, for [`GpuMemory`] leak detection.
+    #[inline]
+    pub(crate) fn touch(&self) {
+        self.0.allocation.touch();
+    }
+
+    /// The texture's label in [`GpuMemory`], if it has one.
+    #[inline]
+    pub fn label(&self) -> Option<String> {
+        self.0.allocation.label()
+    }
+
+    /// Label the texture, so its memory usage can be attributed in [`GpuMemory`].
+    #[inline]
+    pub fn set_label(&self, label: impl Into<String>) {
+        self.0.allocation.set_label(Some(label.into()));
+    }
+
     /// Size of the texture in pixels.
     #[inline]
     pub fn size(&self) -> Vec2U {
@@ -187,12 +276,48 @@ impl Texture {
     /// Split the texture into a grid of tiles.
     #[inline]
     pub fn split_into_tiles(&self, tile_size: impl Into<Vec2U>) -> VecGrid<SubTexture> {
+        self.split_into_tiles_ext(tile_size, Vec2U::ZERO, Vec2U::ZERO)
+    }
+
+    /// Split the texture into a grid of tiles, where the grid is inset from the texture's edges
+    /// by `margin` and there is `spacing` between each tile. Partial tiles are not included.
+    pub fn split_into_tiles_ext(
+        &self,
+        tile_size: impl Into<Vec2U>,
+        margin: impl Into<Vec2U>,
+        spacing: impl Into<Vec2U>,
+    ) -> VecGrid<SubTexture> {
         let tile_size = tile_size.into();
-        let grid_size = self.size() / tile_size;
+        let margin = margin.into();
+        let spacing = spacing.into();
+        let step = tile_size + spacing;
+        let count = |size: u32, margin: u32, tile: u32, step: u32| match size
+            .checked_sub(margin * 2)
+            .and_then(|inner| inner.checked_sub(tile))
+        {
+            Some(rest) if step > 0 => rest / step + 1,
+            _ => 0,
+        };
+        let grid_size = vec2(
+            count(self.width(), margin.x, tile_size.x, step.x),
+            count(self.height(), margin.y, tile_size.y, step.y),
+        );
         VecGrid::new_from(grid_size, |tile| {
-            self.sub(RectU::pos_size(tile * tile_size, tile_size))
+            self.sub(RectU::pos_size(margin + tile * step, tile_size))
         })
     }
+
+    /// Split named regions of the texture into sub-textures, for sheets with irregularly sized
+    /// sprites. If a name is repeated, the last region with that name is used.
+    pub fn split_regions<K: Into<String> + Clone>(
+        &self,
+        regions: &[(K, RectU)],
+    ) -> HashMap<String, SubTexture> {
+        regions
+            .iter()
+            .map(|(name, rect)| (name.clone().into(), self.sub(*rect)))
+            .collect()
+    }
 }
 
 // /// An error uploading data to a texture.
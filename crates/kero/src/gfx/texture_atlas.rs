@@ -0,0 +1,116 @@
+use crate::gfx::{SubTexture, Texture};
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// The result of [`TexturePacker::pack`](super::TexturePacker::pack): a texture and the
+/// subtextures packed into it.
+///
+/// Entries are stored in key order, so iterating an atlas is deterministic, and they can also
+/// be looked up by key or by index.
+#[derive(Debug, Clone)]
+pub struct TextureAtlas<K> {
+    texture: Texture,
+    entries: Vec<(K, SubTexture)>,
+    index: HashMap<K, usize>,
+}
+
+impl<K: Clone + Eq + Hash> TextureAtlas<K> {
+    /// Create an atlas from entries, which should already be in order.
+    pub(crate) fn new(texture: Texture, entries: Vec<(K, SubTexture)>) -> Self {
+        let index = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (key, _))| (key.clone(), i))
+            .collect();
+        Self {
+            texture,
+            entries,
+            index,
+        }
+    }
+
+    /// The packed texture.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// How many subtextures are in the atlas.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// If the atlas has no subtextures.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// The subtexture with the key.
+    #[inline]
+    pub fn get<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<&SubTexture>
+    where
+        K: Borrow<Q>,
+    {
+        self.index.get(key).map(|&i| &self.entries[i].1)
+    }
+
+    /// If the atlas has a subtexture with the key.
+    #[inline]
+    pub fn contains_key<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+    {
+        self.index.contains_key(key)
+    }
+
+    /// The index of the subtexture with the key.
+    #[inline]
+    pub fn index_of<Q: Hash + Eq + ?Sized>(&self, key: &Q) -> Option<usize>
+    where
+        K: Borrow<Q>,
+    {
+        self.index.get(key).copied()
+    }
+
+    /// The key and subtexture at the index.
+    #[inline]
+    pub fn get_index(&self, index: usize) -> Option<(&K, &SubTexture)> {
+        self.entries.get(index).map(|(k, s)| (k, s))
+    }
+
+    /// Iterate over all keys and subtextures, in key order.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&K, &SubTexture)> {
+        self.entries.iter().map(|(k, s)| (k, s))
+    }
+
+    /// Iterate over all keys, in order.
+    #[inline]
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterate over all subtextures, in key order.
+    #[inline]
+    pub fn subs(&self) -> impl ExactSizeIterator<Item = &SubTexture> {
+        self.entries.iter().map(|(_, s)| s)
+    }
+
+    /// Convert into the texture and a map of the subtextures.
+    pub fn into_map(self) -> (Texture, HashMap<K, SubTexture>) {
+        (self.texture, self.entries.into_iter().collect())
+    }
+}
+
+impl<K> IntoIterator for TextureAtlas<K> {
+    type Item = (K, SubTexture);
+    type IntoIter = std::vec::IntoIter<(K, SubTexture)>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
+}
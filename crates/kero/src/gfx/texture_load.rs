@@ -0,0 +1,227 @@
+use crate::color::{FromRgb, Rgba16, Rgba32F};
+use crate::gfx::{Texture, TextureFormat, TexturePixel};
+use crate::grid::Grid;
+use crate::img::{DynImage, Image, ImageError};
+use crate::math::Vec2U;
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// How many bytes of texture data are uploaded per frame across all pending loads.
+pub(crate) const UPLOAD_BYTES_PER_FRAME: usize = 4 * 1024 * 1024;
+
+/// Handle to a texture being loaded in the background, created by
+/// [`Graphics::load_texture_async`](super::Graphics::load_texture_async).
+///
+/// The image is decoded on a worker thread and then uploaded a few rows at a time across
+/// frames. Until it is ready, [`texture`](Self::texture) returns a placeholder texture.
+///
+/// This handle can be cloned and passed around freely.
+#[derive(Clone)]
+pub struct TextureLoad(Arc<Inner>);
+
+struct Inner {
+    path: PathBuf,
+    placeholder: Texture,
+    state: Mutex<State>,
+}
+
+pub(crate) enum State {
+    Decoding,
+    Decoded(Decoded),
+    Uploading {
+        texture: Texture,
+        bytes: Vec<u8>,
+        row: u32,
+    },
+    Ready(Texture),
+    Failed(Arc<ImageError>),
+}
+
+pub(crate) struct Decoded {
+    size: Vec2U,
+    format: TextureFormat,
+    bytes: Vec<u8>,
+}
+
+impl Debug for TextureLoad {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TextureLoad")
+            .field("path", &self.0.path)
+            .field("ready", &self.is_ready())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for TextureLoad {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl TextureLoad {
+    /// Start decoding the image on a worker thread.
+    pub(crate) fn start(path: PathBuf, premultiply: bool, placeholder: Texture) -> Self {
+        let load = Self(Arc::new(Inner {
+            path,
+            placeholder,
+            state: Mutex::new(State::Decoding),
+        }));
+        let worker = load.clone();
+        std::thread::spawn(move || {
+            let state = match DynImage::load_png_from_file(&worker.0.path) {
+                Ok(mut img) => {
+                    if premultiply {
+                        img.premultiply();
+                    }
+                    State::Decoded(Decoded::from_dyn_img(&img))
+                }
+                Err(err) => State::Failed(Arc::new(err)),
+            };
+            *worker.state() = state;
+        });
+        load
+    }
+
+    #[inline]
+    pub(crate) fn state(&self) -> MutexGuard<'_, State> {
+        self.0.state.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Path of the image being loaded.
+    #[inline]
+    pub fn path(&self) -> &PathBuf {
+        &self.0.path
+    }
+
+    /// The loaded texture if it is ready, otherwise the placeholder.
+    #[inline]
+    pub fn texture(&self) -> Texture {
+        self.try_texture()
+            .unwrap_or_else(|| self.0.placeholder.clone())
+    }
+
+    /// The loaded texture, if it is ready.
+    #[inline]
+    pub fn try_texture(&self) -> Option<Texture> {
+        match &*self.state() {
+            State::Ready(texture) => Some(texture.clone()),
+            _ => None,
+        }
+    }
+
+    /// The texture shown until loading has finished.
+    #[inline]
+    pub fn placeholder(&self) -> &Texture {
+        &self.0.placeholder
+    }
+
+    /// If the texture has finished loading and uploading.
+    #[inline]
+    pub fn is_ready(&self) -> bool {
+        matches!(&*self.state(), State::Ready(_))
+    }
+
+    /// If loading has finished, either successfully or with an error.
+    #[inline]
+    pub fn is_done(&self) -> bool {
+        matches!(&*self.state(), State::Ready(_) | State::Failed(_))
+    }
+
+    /// The error that loading failed with, if it failed.
+    #[inline]
+    pub fn error(&self) -> Option<Arc<ImageError>> {
+        match &*self.state() {
+            State::Failed(err) => Some(err.clone()),
+            _ => None,
+        }
+    }
+
+    /// How much of the load has finished, from `0` to `1`.
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        match &*self.state() {
+            State::Decoding | State::Decoded(_) => 0.0,
+            State::Uploading { texture, row, .. } => *row as f32 / texture.height().max(1) as f32,
+            State::Ready(_) | State::Failed(_) => 1.0,
+        }
+    }
+
+    /// Block the current thread until the image has been decoded.
+    pub fn wait_decoded(&self) {
+        while matches!(&*self.state(), State::Decoding) {
+            std::thread::yield_now();
+        }
+    }
+
+    /// Upload up to `budget` bytes of the texture, creating it first if it was just decoded.
+    /// Returns how many bytes were uploaded.
+    pub(crate) fn upload_chunk(
+        &self,
+        create: impl FnOnce(Vec2U, TextureFormat) -> Texture,
+        budget: usize,
+    ) -> usize {
+        let mut state = self.state();
+        if matches!(&*state, State::Decoded(_)) {
+            let State::Decoded(decoded) = std::mem::replace(&mut *state, State::Decoding) else {
+                unreachable!()
+            };
+            *state = State::Uploading {
+                texture: create(decoded.size, decoded.format),
+                bytes: decoded.bytes,
+                row: 0,
+            };
+        }
+        let State::Uploading {
+            texture,
+            bytes,
+            row,
+        } = &mut *state
+        else {
+            return 0;
+        };
+
+        // always upload at least one row so large textures make progress
+        let row_bytes = texture.width() as usize * texture.format().bytes_per_pixel();
+        let rows = (budget / row_bytes.max(1)).max(1) as u32;
+        let rows = rows.min(texture.height() - *row);
+        let start = *row as usize * row_bytes;
+        let end = start + rows as usize * row_bytes;
+        texture.upload_rows(*row, rows, &bytes[start..end]);
+        *row += rows;
+
+        if *row >= texture.height() {
+            *state = State::Ready(texture.clone());
+        }
+        end - start
+    }
+}
+
+impl Decoded {
+    fn from_img<P: TexturePixel, S: AsRef<[P::Channel]>>(img: &Image<P, S>) -> Self {
+        Self {
+            size: img.size(),
+            format: P::TEXTURE_FORMAT,
+            bytes: bytemuck::cast_slice(img.pixels()).to_vec(),
+        }
+    }
+
+    fn from_dyn_img(img: &DynImage) -> Self {
+        match img {
+            DynImage::Grey8(img) => Self::from_img(img),
+            DynImage::Grey16(img) => Self::from_img(img),
+            DynImage::Grey32F(img) => Self::from_img(img),
+            DynImage::GreyAlpha8(img) => Self::from_img(img),
+            DynImage::GreyAlpha16(img) => Self::from_img(img),
+            DynImage::GreyAlpha32F(img) => Self::from_img(img),
+            DynImage::Rgb8(img) => Self::from_img(&img.to_rgba8()),
+            DynImage::Rgb16(img) => Self::from_img(&img.map(Rgba16::from_rgb)),
+            DynImage::Rgb32F(img) => Self::from_img(&img.map(Rgba32F::from_rgb)),
+            DynImage::Rgba8(img) => Self::from_img(img),
+            DynImage::Rgba16(img) => Self::from_img(img),
+            DynImage::Rgba32F(img) => Self::from_img(img),
+            DynImage::Indexed(img) => Self::from_img(&img.to_rgba8()),
+        }
+    }
+}
@@ -1,11 +1,10 @@
-use crate::gfx::{Graphics, SubTexture, Texture};
+use crate::gfx::{Graphics, SubTexture, TextureAtlas};
 use crate::grid::{Grid, GridMut};
 use crate::math::{Numeric, RectF, RectU, Vec2F, Vec2U};
 use crate::prelude::TexturePixel;
 use fey_color::{Grey8, GreyAlpha8, Rgb8, Rgba8};
 use fey_img::Image;
 use fey_packer::{Item, Packed, RectPacker};
-use std::collections::HashMap;
 use std::hash::Hash;
 use std::ops::Deref;
 use std::rc::Rc;
@@ -26,7 +25,7 @@ struct ToPack<'a, K, P: TexturePixel> {
     trim_rect: RectU,
 }
 
-impl<'a, K: Clone + Eq + Hash, P: TexturePixel> TexturePacker<'a, K, P> {
+impl<'a, K: Clone + Eq + Hash + Ord, P: TexturePixel> TexturePacker<'a, K, P> {
     pub fn new() -> Self {
         Self {
             to_pack: Vec::new(),
@@ -60,19 +59,24 @@ impl<'a, K: Clone + Eq + Hash, P: TexturePixel> TexturePacker<'a, K, P> {
         self.to_pack.len()
     }
 
-    pub fn pack(self, gfx: &Graphics) -> Option<(Texture, HashMap<K, SubTexture>)> {
+    /// Pack all the images into a texture. Images are packed in key order, so identical
+    /// inputs always produce the same atlas, regardless of the order they were added in.
+    pub fn pack(self, gfx: &Graphics) -> Option<TextureAtlas<K>> {
         self.pack_ext(gfx, gfx.max_texture_size(), 1, 2)
     }
 
     pub fn pack_ext(
-        self,
+        mut self,
         gfx: &Graphics,
         max_size: u32,
         spacing: u32,
         padding: u32,
-    ) -> Option<(Texture, HashMap<K, SubTexture>)> {
+    ) -> Option<TextureAtlas<K>> {
         let padding = Vec2U::splat(padding);
 
+        // sort so the packing doesn't depend on the order images were added in
+        self.to_pack.sort_by(|a, b| a.key.cmp(&b.key));
+
         let items: Vec<Item<usize>> = self
             .to_pack
             .iter()
@@ -91,7 +95,7 @@ impl<'a, K: Clone + Eq + Hash, P: TexturePixel> TexturePacker<'a, K, P> {
         let padding = padding.to_f32();
         let sub_info: Vec<(K, RectF, Vec2F, Vec2F)> = packed
             .into_iter()
-            .map(|Packed { data: i, pos }| {
+            .map(|Packed { data: i, pos, .. }| {
                 let ToPack {
                     key,
                     img,
@@ -121,7 +125,7 @@ impl<'a, K: Clone + Eq + Hash, P: TexturePixel> TexturePacker<'a, K, P> {
             })
             .collect();
 
-        Some((tex, subs))
+        Some(TextureAtlas::new(tex, subs))
     }
 }
 
@@ -0,0 +1,577 @@
+use crate::color::Rgba8;
+use crate::gfx::{
+    BlendMode, ColorMode, Draw, Graphics, Material, MaterialError, Surface, TextureFormat,
+};
+use crate::math::{CircleF, LineF, RectF, Vec2F, Vec2U, Vec4F, vec2, vec4};
+use crate::rand::Rand;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::TAU;
+
+/// Wind that pushes weather particles around, which can also be passed to shaders so things
+/// like grass and trees sway along with it.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Wind {
+    /// The steady wind velocity, in pixels per second.
+    pub velocity: Vec2F,
+
+    /// How much gusts add to the velocity at their peak, as a fraction of it.
+    pub gust_strength: f32,
+
+    /// Roughly how many gusts happen per second.
+    pub gust_frequency: f32,
+}
+
+impl Wind {
+    /// No wind at all.
+    pub const CALM: Self = Self {
+        velocity: Vec2F::ZERO,
+        gust_strength: 0.0,
+        gust_frequency: 0.0,
+    };
+
+    /// A steady wind with light gusts.
+    #[inline]
+    pub fn new(velocity: impl Into<Vec2F>) -> Self {
+        Self {
+            velocity: velocity.into(),
+            gust_strength: 0.5,
+            gust_frequency: 0.2,
+        }
+    }
+
+    /// How strong the gust is at `time` seconds, from `0` (no gust) to `1` (peak gust).
+    #[inline]
+    pub fn gust(&self, time: f32) -> f32 {
+        // a few out-of-phase waves, so gusts don't repeat too obviously
+        let t = time * self.gust_frequency * TAU;
+        let wave = t.sin() * 0.5 + (t * 2.3 + 1.7).sin() * 0.3 + (t * 0.37 + 4.1).sin() * 0.2;
+        (wave * 0.5 + 0.5).clamp(0.0, 1.0)
+    }
+
+    /// The wind velocity at `time` seconds, including gusts.
+    #[inline]
+    pub fn velocity_at(&self, time: f32) -> Vec2F {
+        self.velocity * (1.0 + self.gust_strength * self.gust(time))
+    }
+
+    /// The wind packed for shaders as `vec4f(velocity.x, velocity.y, gust, time)`, where the
+    /// velocity includes gusts.
+    #[inline]
+    pub fn params(&self, time: f32) -> Vec4F {
+        let vel = self.velocity_at(time);
+        vec4(vel.x, vel.y, self.gust(time), time)
+    }
+
+    /// Set a `vec4f` parameter of the current shader to the wind's [`params`](Self::params).
+    /// Panics if the shader doesn't have the parameter.
+    #[inline]
+    pub fn set_param(&self, draw: &mut Draw, name: &str, time: f32) {
+        draw.set_param_vec4(name, self.params(time));
+    }
+
+    /// Set a `vec4f` parameter of the material to the wind's [`params`](Self::params).
+    #[inline]
+    pub fn apply_to(
+        &self,
+        material: &mut Material,
+        name: &str,
+        time: f32,
+    ) -> Result<(), MaterialError> {
+        material.set(name, self.params(time)).map(|_| ())
+    }
+}
+
+/// What falls from the sky.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Precipitation {
+    #[default]
+    None,
+    Rain,
+    Snow,
+}
+
+/// Settings for a type of [`Weather`]. Start from one of the presets and adjust from there.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeatherPreset {
+    /// What falls from the sky.
+    pub precipitation: Precipitation,
+
+    /// How many particles are spawned per second, for every 100 pixels of width.
+    pub density: f32,
+
+    /// How fast particles fall, in pixels per second.
+    pub fall_speed: f32,
+
+    /// How much each particle's fall speed randomly varies, as a fraction of it.
+    pub speed_variance: f32,
+
+    /// Length of rain streaks, or radius of snowflakes.
+    pub size: f32,
+
+    /// Color of the particles, premultiplied.
+    pub color: Rgba8,
+
+    /// How many splash particles a raindrop makes when it lands.
+    pub splashes: u32,
+
+    /// How much the wind pushes particles, where `1` moves them at the wind's speed.
+    pub wind_response: f32,
+
+    /// How far snowflakes drift side to side, in pixels per second.
+    pub sway: f32,
+
+    /// How many droplets run down the screen per second, as if rain was hitting the camera.
+    pub droplets: f32,
+}
+
+impl Default for WeatherPreset {
+    #[inline]
+    fn default() -> Self {
+        Self::clear()
+    }
+}
+
+impl WeatherPreset {
+    /// Nothing falls.
+    pub fn clear() -> Self {
+        Self {
+            precipitation: Precipitation::None,
+            density: 0.0,
+            fall_speed: 0.0,
+            speed_variance: 0.0,
+            size: 0.0,
+            color: Rgba8::TRANSPARENT,
+            splashes: 0,
+            wind_response: 0.0,
+            sway: 0.0,
+            droplets: 0.0,
+        }
+    }
+
+    /// Light, sparse rain.
+    pub fn drizzle() -> Self {
+        Self {
+            precipitation: Precipitation::Rain,
+            density: 6.0,
+            fall_speed: 500.0,
+            speed_variance: 0.15,
+            size: 8.0,
+            color: Rgba8::new(56, 61, 71, 120),
+            splashes: 1,
+            wind_response: 0.6,
+            sway: 0.0,
+            droplets: 0.0,
+        }
+    }
+
+    /// Steady rain.
+    pub fn rain() -> Self {
+        Self {
+            density: 25.0,
+            fall_speed: 800.0,
+            size: 14.0,
+            color: Rgba8::new(82, 88, 100, 150),
+            splashes: 3,
+            droplets: 0.5,
+            ..Self::drizzle()
+        }
+    }
+
+    /// Heavy, wind-driven rain.
+    pub fn storm() -> Self {
+        Self {
+            density: 60.0,
+            fall_speed: 1100.0,
+            size: 20.0,
+            splashes: 4,
+            wind_response: 1.0,
+            droplets: 2.0,
+            ..Self::rain()
+        }
+    }
+
+    /// Gently falling snow.
+    pub fn snow() -> Self {
+        Self {
+            precipitation: Precipitation::Snow,
+            density: 8.0,
+            fall_speed: 60.0,
+            speed_variance: 0.4,
+            size: 2.0,
+            color: Rgba8::new(216, 221, 230, 230),
+            splashes: 0,
+            wind_response: 0.8,
+            sway: 20.0,
+            droplets: 0.0,
+        }
+    }
+
+    /// Thick snow driven by the wind.
+    pub fn blizzard() -> Self {
+        Self {
+            density: 40.0,
+            fall_speed: 140.0,
+            size: 2.5,
+            wind_response: 1.2,
+            sway: 35.0,
+            ..Self::snow()
+        }
+    }
+}
+
+/// What a [`WeatherParticle`] is.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WeatherParticleKind {
+    Drop,
+    Splash,
+    Flake,
+}
+
+/// A particle simulated by [`Weather`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct WeatherParticle {
+    pub kind: WeatherParticleKind,
+    pub pos: Vec2F,
+    pub vel: Vec2F,
+
+    /// Seconds until a splash disappears.
+    pub life: f32,
+
+    /// Random offset so snowflakes don't sway in unison.
+    pub phase: f32,
+}
+
+/// A droplet running down the screen, in normalized screen coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScreenDroplet {
+    /// Position on the screen, where `(0, 0)` is the top-left and `(1, 1)` the bottom-right.
+    pub pos: Vec2F,
+
+    /// Radius of the droplet, in pixels.
+    pub radius: f32,
+
+    /// How long the droplet has existed.
+    pub age: f32,
+
+    /// How long the droplet lasts.
+    pub life: f32,
+}
+
+/// Simulates rain, snow, and wind over an area of the world.
+///
+/// Each frame, call [`update`](Self::update) with the visible area, then [`draw`](Self::draw)
+/// the particles over the scene and [`draw_droplets`](Self::draw_droplets) over the screen.
+/// Snowflakes that land can be stamped into a [`SnowCover`] so snow builds up over time.
+///
+/// ```ignore
+/// let mut weather = Weather::new(WeatherPreset::rain(), Wind::new(vec2(-120.0, 0.0)));
+/// // every frame
+/// weather.update(time.delta(), view, |p| ground.is_solid(p));
+/// weather.draw(draw);
+/// weather.wind.set_param(draw, "wind", weather.time());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Weather {
+    /// The type of weather.
+    pub preset: WeatherPreset,
+
+    /// The wind blowing particles around.
+    pub wind: Wind,
+
+    rand: Rand,
+    time: f32,
+    spawn: f32,
+    droplet_spawn: f32,
+    particles: Vec<WeatherParticle>,
+    droplets: Vec<ScreenDroplet>,
+    landed: Vec<Vec2F>,
+}
+
+impl Weather {
+    /// Most particles that will be alive at once.
+    pub const MAX_PARTICLES: usize = 20_000;
+
+    /// Gravity applied to splash particles, in pixels per second squared.
+    pub const SPLASH_GRAVITY: f32 = 900.0;
+
+    pub fn new(preset: WeatherPreset, wind: Wind) -> Self {
+        Self {
+            preset,
+            wind,
+            rand: Rand::new(),
+            time: 0.0,
+            spawn: 0.0,
+            droplet_spawn: 0.0,
+            particles: Vec::new(),
+            droplets: Vec::new(),
+            landed: Vec::new(),
+        }
+    }
+
+    /// Seconds the weather has been updated for, which drives wind gusts.
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Every live particle.
+    #[inline]
+    pub fn particles(&self) -> &[WeatherParticle] {
+        &self.particles
+    }
+
+    /// Every droplet on the screen.
+    #[inline]
+    pub fn droplets(&self) -> &[ScreenDroplet] {
+        &self.droplets
+    }
+
+    /// Where snowflakes landed during the last update.
+    #[inline]
+    pub fn landed(&self) -> &[Vec2F] {
+        &self.landed
+    }
+
+    /// Remove every particle and droplet.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.particles.clear();
+        self.droplets.clear();
+        self.landed.clear();
+    }
+
+    /// Spawn and move particles over the area, which is usually the part of the world that is
+    /// on screen. Particles land once `is_ground` returns true for their position, or when
+    /// they fall past the bottom of the area.
+    pub fn update(
+        &mut self,
+        delta_time: f32,
+        area: impl Into<RectF>,
+        mut is_ground: impl FnMut(Vec2F) -> bool,
+    ) {
+        let area = area.into();
+        let p = self.preset;
+        self.time += delta_time;
+        self.landed.clear();
+        let wind = self.wind.velocity_at(self.time) * p.wind_response;
+
+        // spawn above the area, shifted upwind so particles blown sideways still cover it
+        if p.precipitation != Precipitation::None && p.fall_speed > 0.0 {
+            let drift = wind.x * (area.h / p.fall_speed);
+            let min_x = area.x.min(area.x - drift);
+            let max_x = area.right().max(area.right() - drift);
+            self.spawn += p.density * (max_x - min_x) / 100.0 * delta_time;
+            while self.spawn >= 1.0 {
+                self.spawn -= 1.0;
+                if self.particles.len() >= Self::MAX_PARTICLES {
+                    continue;
+                }
+                let speed = p.fall_speed * (1.0 + self.rand.range(-1.0..=1.0) * p.speed_variance);
+                let kind = match p.precipitation {
+                    Precipitation::Rain => WeatherParticleKind::Drop,
+                    _ => WeatherParticleKind::Flake,
+                };
+                self.particles.push(WeatherParticle {
+                    kind,
+                    pos: vec2(self.rand.range(min_x..=max_x), area.y - p.size),
+                    vel: vec2(wind.x, speed.max(1.0)),
+                    life: 0.0,
+                    phase: self.rand.range(0.0..TAU),
+                });
+            }
+        }
+
+        // move the particles, splashing or landing them when they hit the ground
+        let bottom = area.bottom();
+        let mut splashes = Vec::new();
+        let time = self.time;
+        let landed = &mut self.landed;
+        self.particles.retain_mut(|part| match part.kind {
+            WeatherParticleKind::Drop => {
+                part.vel.x = wind.x;
+                part.pos += part.vel * delta_time;
+                let hit = part.pos.y >= bottom || is_ground(part.pos);
+                if hit {
+                    splashes.push(part.pos);
+                }
+                !hit
+            }
+            WeatherParticleKind::Flake => {
+                part.vel.x = wind.x + (time * 1.3 + part.phase).sin() * p.sway;
+                part.pos += part.vel * delta_time;
+                let hit = part.pos.y >= bottom || is_ground(part.pos);
+                if hit {
+                    landed.push(part.pos);
+                }
+                !hit
+            }
+            WeatherParticleKind::Splash => {
+                part.vel.y += Self::SPLASH_GRAVITY * delta_time;
+                part.pos += part.vel * delta_time;
+                part.life -= delta_time;
+                part.life > 0.0
+            }
+        });
+        for pos in splashes {
+            for _ in 0..p.splashes {
+                if self.particles.len() >= Self::MAX_PARTICLES {
+                    break;
+                }
+                let vel = vec2(
+                    self.rand.range(-60.0..=60.0),
+                    -self.rand.range(80.0..=200.0),
+                );
+                self.particles.push(WeatherParticle {
+                    kind: WeatherParticleKind::Splash,
+                    pos: pos - vec2(0.0, 1.0),
+                    vel: vel + wind * 0.2,
+                    life: self.rand.range(0.15..=0.35),
+                    phase: 0.0,
+                });
+            }
+        }
+
+        // droplets appear on the screen and slowly run down it
+        self.droplet_spawn += p.droplets * delta_time;
+        while self.droplet_spawn >= 1.0 {
+            self.droplet_spawn -= 1.0;
+            self.droplets.push(ScreenDroplet {
+                pos: vec2(self.rand.range(0.0..1.0), self.rand.range(0.0..1.0)),
+                radius: self.rand.range(4.0..=12.0),
+                age: 0.0,
+                life: self.rand.range(1.5..=4.0),
+            });
+        }
+        self.droplets.retain_mut(|drop| {
+            drop.age += delta_time;
+            drop.pos.y += drop.radius * 0.002 * delta_time * drop.age;
+            drop.age < drop.life
+        });
+    }
+
+    /// Draw the particles, in the same space as the area they were updated with.
+    pub fn draw(&self, draw: &mut Draw) {
+        let p = &self.preset;
+        for part in &self.particles {
+            match part.kind {
+                WeatherParticleKind::Drop => {
+                    let tail = part.vel.norm_safe() * p.size;
+                    draw.line(LineF::new(part.pos - tail, part.pos), p.color);
+                }
+                WeatherParticleKind::Splash => {
+                    let rect = RectF::new(part.pos.x - 0.5, part.pos.y - 0.5, 1.0, 1.0);
+                    draw.rect(rect, p.color);
+                }
+                WeatherParticleKind::Flake => {
+                    draw.circle(CircleF::new(part.pos, p.size), p.color, Some(6));
+                }
+            }
+        }
+    }
+
+    /// Draw the screen droplets over the screen region with a premultiplied color, fading
+    /// them out as they age.
+    pub fn draw_droplets(&self, draw: &mut Draw, screen: impl Into<RectF>, color: Rgba8) {
+        let screen = screen.into();
+        for drop in &self.droplets {
+            let fade = 1.0 - drop.age / drop.life;
+            let pos = screen.top_left() + drop.pos * screen.size();
+            let f = |c: u8| (c as f32 * fade).round() as u8;
+            let body = Rgba8::new(f(color.r), f(color.g), f(color.b), f(color.a));
+            draw.circle(CircleF::new(pos, drop.radius), body, None);
+
+            // a highlight near the top gives the droplet some shape
+            let glint = Rgba8::new(f(96), f(96), f(96), f(96));
+            let offset = vec2(-0.3, -0.4) * drop.radius;
+            draw.circle(CircleF::new(pos + offset, drop.radius * 0.3), glint, None);
+        }
+    }
+}
+
+/// A mask that snow builds up in as it lands, so it can be drawn over the ground.
+///
+/// The mask covers a region of the world with its top-left at [`origin`](Self::origin), one
+/// pixel per world unit. Snow is stored as premultiplied white, so the mask can be drawn
+/// directly over the scene, or sampled by a shader as an accumulation mask.
+#[derive(Debug)]
+pub struct SnowCover {
+    front: Surface,
+    back: Surface,
+
+    /// World position of the mask's top-left.
+    pub origin: Vec2F,
+
+    /// Radius that each landed snowflake stamps into the mask.
+    pub flake_radius: f32,
+
+    /// How opaque each stamp is, so the mask fills in over many flakes.
+    pub flake_opacity: f32,
+}
+
+impl SnowCover {
+    /// Create an empty mask covering `size` pixels of the world, starting at `origin`.
+    pub fn new(gfx: &Graphics, size: impl Into<Vec2U>, origin: impl Into<Vec2F>) -> Self {
+        let size = size.into();
+        Self {
+            front: gfx.create_surface(size, TextureFormat::Rgba8),
+            back: gfx.create_surface(size, TextureFormat::Rgba8),
+            origin: origin.into(),
+            flake_radius: 2.0,
+            flake_opacity: 0.25,
+        }
+    }
+
+    /// The surface holding the mask.
+    #[inline]
+    pub fn surface(&self) -> &Surface {
+        &self.front
+    }
+
+    /// Stamp every snowflake that landed during the weather's last update into the mask.
+    pub fn accumulate(&self, draw: &mut Draw, weather: &Weather) {
+        if weather.landed().is_empty() {
+            return;
+        }
+        let prev = draw.surface().cloned();
+        draw.set_surface(self.front.clone(), None);
+        let a = (self.flake_opacity.clamp(0.0, 1.0) * 255.0) as u8;
+        let color = Rgba8::new(a, a, a, a);
+        for &pos in weather.landed() {
+            let circ = CircleF::new(pos - self.origin, self.flake_radius);
+            draw.circle(circ, color, Some(8));
+        }
+        draw.set_surface(prev, None);
+    }
+
+    /// Fade the snow by a fraction of its current amount, such as `0.01` every frame to
+    /// slowly melt it.
+    pub fn melt(&mut self, draw: &mut Draw, amount: f32) {
+        let prev = draw.surface().cloned();
+        let k = ((1.0 - amount.clamp(0.0, 1.0)) * 255.0) as u8;
+        draw.set_surface(self.back.clone(), Rgba8::TRANSPARENT);
+        let prev_blend = draw.blend_mode();
+        draw.set_blend_mode(BlendMode::Replace);
+        draw.texture_at_ext(
+            self.front.texture(),
+            Vec2F::ZERO,
+            Rgba8::new(k, k, k, k),
+            ColorMode::MULT,
+        );
+        draw.set_blend_mode(prev_blend);
+        draw.set_surface(prev, None);
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Remove all the snow.
+    pub fn clear(&self, draw: &mut Draw) {
+        let prev = draw.surface().cloned();
+        draw.set_surface(self.front.clone(), Rgba8::TRANSPARENT);
+        draw.set_surface(prev, None);
+    }
+
+    /// Draw the snow over the world it covers.
+    #[inline]
+    pub fn draw(&self, draw: &mut Draw) {
+        draw.texture_at(self.front.texture(), self.origin);
+    }
+}
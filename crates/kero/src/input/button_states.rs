@@ -0,0 +1,44 @@
+use std::cell::Cell;
+
+/// The state of a set of buttons for one frame, as stored in an [`InputFrame`](crate::input::InputFrame).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonStates<const N: usize> {
+    /// Buttons held down.
+    pub down: [bool; N],
+
+    /// Buttons pressed this frame.
+    pub pressed: [bool; N],
+
+    /// Buttons released this frame.
+    pub released: [bool; N],
+
+    /// Buttons repeated this frame by being held down.
+    pub repeated: [bool; N],
+}
+
+impl<const N: usize> Default for ButtonStates<N> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            down: [false; N],
+            pressed: [false; N],
+            released: [false; N],
+            repeated: [false; N],
+        }
+    }
+}
+
+/// Write recorded flags into an input phase, replacing the update phase's flags and adding to
+/// the render phase's, which collects every update in the frame.
+#[inline]
+pub(crate) fn play_flags<const N: usize>(phase: usize, cell: &Cell<[bool; N]>, flags: &[bool; N]) {
+    match phase {
+        0 => cell.set(*flags),
+        _ => cell.update(|mut cur| {
+            for (cur, &flag) in cur.iter_mut().zip(flags) {
+                *cur |= flag;
+            }
+            cur
+        }),
+    }
+}
@@ -1,8 +1,12 @@
-use crate::input::{GamepadButton, GamepadStatus};
+use crate::input::{
+    ButtonStates, GamepadButton, GamepadFrame, GamepadStatus, VibrationPattern, VibrationPlayback,
+    play_flags,
+};
 use gilrs::{GamepadId, Gilrs};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
+use std::time::Duration;
 use std::{rc::Rc, time::SystemTime};
 use strum::EnumCount;
 
@@ -36,7 +40,7 @@ impl PartialOrd for Gamepad {
 }
 
 struct State {
-    id: GamepadId,
+    id: Option<GamepadId>,
     name: String,
     status: Cell<GamepadStatus>,
     down: Cell<[bool; GamepadButton::COUNT]>,
@@ -47,6 +51,19 @@ struct State {
     last_update: Cell<SystemTime>,
     connect_time: SystemTime,
     connected: Cell<bool>,
+    rumble_supported: bool,
+    rumble: Cell<Option<Rumble>>,
+    vibrations: RefCell<Vec<VibrationPlayback>>,
+    vibration_sent: Cell<(f32, f32)>,
+    vibration_age: Cell<f32>,
+}
+
+/// A rumble requested since the gamepads were last updated.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub(crate) struct Rumble {
+    pub strong: f32,
+    pub weak: f32,
+    pub duration: Duration,
 }
 
 #[derive(Clone)]
@@ -74,10 +91,11 @@ impl Default for Phase {
 
 impl Gamepad {
     pub(crate) fn new(
-        id: GamepadId,
+        id: Option<GamepadId>,
         name: String,
         status: GamepadStatus,
         connect_time: SystemTime,
+        rumble_supported: bool,
     ) -> Self {
         Self(Rc::new(State {
             id,
@@ -91,6 +109,11 @@ impl Gamepad {
             last_update: Cell::new(connect_time),
             connect_time,
             connected: Cell::new(true),
+            rumble_supported,
+            rumble: Cell::new(None),
+            vibrations: RefCell::new(Vec::new()),
+            vibration_sent: Cell::new((0.0, 0.0)),
+            vibration_age: Cell::new(0.0),
         }))
     }
 
@@ -193,10 +216,113 @@ impl Gamepad {
         Cell::as_array_of_cells(&self.phase().axis_changed)[axis as usize].get()
     }
 
+    /// If the gamepad supports rumble.
+    #[inline]
+    pub fn rumble_supported(&self) -> bool {
+        self.0.rumble_supported
+    }
+
+    /// Rumble the gamepad's strong (low frequency) and weak (high frequency) motors for the
+    /// duration, with strengths from `0.0` to `1.0`. This replaces any rumble already playing,
+    /// and does nothing if the gamepad doesn't support rumble.
+    #[inline]
+    pub fn rumble(&self, strong: f32, weak: f32, duration: Duration) {
+        self.0.rumble.set(Some(Rumble {
+            strong: strong.clamp(0.0, 1.0),
+            weak: weak.clamp(0.0, 1.0),
+            duration,
+        }));
+    }
+
+    /// Stop any rumble that is playing.
+    #[inline]
+    pub fn stop_rumble(&self) {
+        self.rumble(0.0, 0.0, Duration::ZERO);
+    }
+
+    /// Play a vibration pattern, like a sound effect. Patterns play on top of each other,
+    /// with the strongest winning where they overlap, and replace any [rumble](Self::rumble)
+    /// while they play.
+    #[inline]
+    pub fn vibrate(&self, pattern: &VibrationPattern) -> VibrationPlayback {
+        self.vibrate_ext(pattern, 1.0)
+    }
+
+    /// Play a vibration pattern with an intensity from `0.0` to `1.0`.
+    pub fn vibrate_ext(&self, pattern: &VibrationPattern, intensity: f32) -> VibrationPlayback {
+        let playback = VibrationPlayback::new(pattern.clone(), intensity);
+        self.0.vibrations.borrow_mut().push(playback.clone());
+        playback
+    }
+
+    /// Stop all vibration patterns playing on the gamepad.
+    pub fn stop_vibrations(&self) {
+        for playback in self.0.vibrations.borrow_mut().drain(..) {
+            playback.stop();
+        }
+    }
+
+    /// If any vibration patterns are playing on the gamepad.
+    #[inline]
+    pub fn is_vibrating(&self) -> bool {
+        !self.0.vibrations.borrow().is_empty()
+    }
+
+    /// Advance the vibration patterns and request a rumble of their combined strength when it
+    /// changes, or before the last one runs out.
+    pub(crate) fn update_vibrations(&self, dt: f32) {
+        // how long each rumble is requested for, and how often it's requested again
+        const HOLD: Duration = Duration::from_millis(200);
+        const REFRESH: f32 = 0.1;
+
+        let mut vibrations = self.0.vibrations.borrow_mut();
+        let sent = self.0.vibration_sent.get();
+        if vibrations.is_empty() && sent == (0.0, 0.0) {
+            return;
+        }
+
+        let (mut strong, mut weak) = (0.0f32, 0.0f32);
+        vibrations.retain(|playback| {
+            let (s, w) = playback.advance(dt);
+            strong = strong.max(s);
+            weak = weak.max(w);
+            playback.is_playing()
+        });
+
+        // round the strengths so small changes don't restart the rumble every update
+        let round = |v: f32| (v * 32.0).round() / 32.0;
+        let level = (round(strong), round(weak));
+        let age = self.0.vibration_age.get() + dt;
+        if level != sent || (level != (0.0, 0.0) && age >= REFRESH) {
+            let hold = if level == (0.0, 0.0) {
+                Duration::ZERO
+            } else {
+                HOLD
+            };
+            self.rumble(level.0, level.1, hold);
+            self.0.vibration_sent.set(level);
+            self.0.vibration_age.set(0.0);
+        } else {
+            self.0.vibration_age.set(age);
+        }
+    }
+
+    #[inline]
+    pub(crate) fn take_rumble(&self) -> Option<Rumble> {
+        self.0.rumble.take()
+    }
+
+    /// The gilrs ID of the gamepad, or `None` if it is being played back from a recording.
+    #[inline]
+    pub(crate) fn gilrs_id(&self) -> Option<GamepadId> {
+        self.0.id
+    }
+
     #[inline]
     pub(crate) fn update_status(&self, gilrs: &Gilrs, time: SystemTime) {
-        let power_info = gilrs.connected_gamepad(self.0.id).unwrap().power_info();
-        self.0.status.set(GamepadStatus::from(power_info));
+        if let Some(pad) = self.0.id.and_then(|id| gilrs.connected_gamepad(id)) {
+            self.0.status.set(GamepadStatus::from(pad.power_info()));
+        }
         self.0.last_update.set(time);
     }
 
@@ -231,6 +357,45 @@ impl Gamepad {
         Cell::as_array_of_cells(&self.0.axis_value)[axis as usize].set(val);
     }
 
+    /// The buttons and axes of the update phase, for recording.
+    pub(crate) fn record(&self) -> GamepadFrame {
+        let phase = &self.0.phases[0];
+        GamepadFrame {
+            name: self.0.name.clone(),
+            buttons: ButtonStates {
+                down: self.0.down.get(),
+                pressed: phase.pressed.get(),
+                released: phase.released.get(),
+                repeated: phase.repeated.get(),
+            },
+            values: self.0.btn_value.get(),
+            axes: self.0.axis_value.get(),
+        }
+    }
+
+    /// Replace the update phase with recorded buttons and axes.
+    pub(crate) fn play(&self, frame: &GamepadFrame) {
+        let buttons = &frame.buttons;
+        let (values, axes) = (self.0.btn_value.get(), self.0.axis_value.get());
+        let btn_changed = std::array::from_fn(|i| {
+            buttons.pressed[i] || buttons.released[i] || frame.values[i] != values[i]
+        });
+        let axis_changed = std::array::from_fn(|i| frame.axes[i] != axes[i]);
+        if btn_changed.contains(&true) || axis_changed.contains(&true) {
+            self.0.last_update.set(SystemTime::now());
+        }
+        self.0.down.set(buttons.down);
+        self.0.btn_value.set(frame.values);
+        self.0.axis_value.set(frame.axes);
+        for (i, phase) in self.0.phases.iter().enumerate() {
+            play_flags(i, &phase.pressed, &buttons.pressed);
+            play_flags(i, &phase.released, &buttons.released);
+            play_flags(i, &phase.repeated, &buttons.repeated);
+            play_flags(i, &phase.btn_changed, &btn_changed);
+            play_flags(i, &phase.axis_changed, &axis_changed);
+        }
+    }
+
     pub(crate) fn set_update_phase(&self) {
         self.0.phase.set(0);
     }
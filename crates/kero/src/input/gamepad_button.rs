@@ -1,9 +1,22 @@
 use gilrs::Button;
+use serde::{Deserialize, Serialize};
 use strum::{EnumCount, FromRepr, VariantArray};
 
 /// A gamepad button.
 #[derive(
-    Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, EnumCount, FromRepr, VariantArray,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Serialize,
+    Deserialize,
+    EnumCount,
+    FromRepr,
+    VariantArray,
 )]
 pub enum GamepadButton {
     South,
@@ -0,0 +1,30 @@
+use crate::input::{ButtonStates, GamepadAxis, GamepadButton};
+use strum::EnumCount;
+
+/// The state of one gamepad for one frame, as stored in an [`InputFrame`](crate::input::InputFrame).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GamepadFrame {
+    /// The gamepad's name.
+    pub name: String,
+
+    /// The gamepad's buttons.
+    pub buttons: ButtonStates<{ GamepadButton::COUNT }>,
+
+    /// How far each button is pressed, for analog buttons such as triggers.
+    pub values: [f32; GamepadButton::COUNT],
+
+    /// The value of each axis.
+    pub axes: [f32; GamepadAxis::COUNT],
+}
+
+impl Default for GamepadFrame {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            buttons: ButtonStates::default(),
+            values: [0.0; _],
+            axes: [0.0; _],
+        }
+    }
+}
@@ -1,7 +1,8 @@
-use super::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use super::{Gamepad, GamepadAxis, GamepadButton, GamepadFrame, GamepadStatus};
 use crate::core::Context;
 use fnv::FnvHashMap;
-use gilrs::{Event, EventType, GamepadId, Gilrs};
+use gilrs::ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks};
+use gilrs::{Event, EventType, Gilrs};
 use smallvec::SmallVec;
 use std::cell::Cell;
 use std::fmt::{Debug, Formatter};
@@ -23,12 +24,15 @@ impl Debug for Gamepads {
 
 struct State {
     gilrs: Option<RefCell<Gilrs>>,
-    gamepads: RefCell<FnvHashMap<GamepadId, Pad>>,
+    gamepads: RefCell<FnvHashMap<usize, Pad>>,
+    suspended: RefCell<FnvHashMap<usize, Pad>>,
+    playing: Cell<bool>,
     last_active: Cell<SystemTime>,
 }
 
 struct Pad {
     pad: Gamepad,
+    rumble: Option<Effect>,
 
     #[cfg(feature = "lua")]
     userdata: mlua::AnyUserData,
@@ -39,6 +43,8 @@ impl Gamepads {
         Self(Rc::new(State {
             gilrs: Gilrs::new().ok().map(RefCell::new),
             gamepads: RefCell::new(FnvHashMap::default()),
+            suspended: RefCell::new(FnvHashMap::default()),
+            playing: Cell::new(false),
             last_active: Cell::new(SystemTime::UNIX_EPOCH),
         }))
     }
@@ -48,11 +54,20 @@ impl Gamepads {
         let Some(mut gilrs) = self.0.gilrs.as_ref().map(|g| g.borrow_mut()) else {
             return;
         };
-        let mut gamepads = self.0.gamepads.borrow_mut();
+        let mut live = self.0.gamepads.borrow_mut();
+        let mut suspended = self.0.suspended.borrow_mut();
+
+        // real gamepads keep tracking their state while set aside for playback
+        let gamepads = match self.0.playing.get() {
+            true => &mut *suspended,
+            false => &mut *live,
+        };
         while let Some(Event {
             id, event, time, ..
         }) = gilrs.next_event()
         {
+            let gilrs_id = id;
+            let id = usize::from(id);
             self.0.last_active.set(time);
 
             match event {
@@ -96,11 +111,13 @@ impl Gamepads {
                     }
                 }
                 EventType::Connected => {
-                    let pad = gilrs.connected_gamepad(id).unwrap();
+                    let pad = gilrs.connected_gamepad(gilrs_id).unwrap();
                     let name = pad.name().to_string();
                     let status = GamepadStatus::from(pad.power_info());
-                    let pad = Gamepad::new(id, name, status, time);
+                    let ff = pad.is_ff_supported();
+                    let pad = Gamepad::new(Some(gilrs_id), name, status, time, ff);
                     let pad = Pad {
+                        rumble: None,
                         #[cfg(feature = "lua")]
                         userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
                         pad,
@@ -116,6 +133,118 @@ impl Gamepads {
                 _ => {}
             }
         }
+
+        // start any rumble that was requested, dropping the previous effect stops it
+        let dt = ctx.time.unscaled_delta();
+        for pad in live.values_mut() {
+            pad.pad.update_vibrations(dt);
+            let Some(rumble) = pad.pad.take_rumble() else {
+                continue;
+            };
+            pad.rumble = None;
+            let ms = rumble.duration.as_millis().min(u32::MAX as u128) as u32;
+            let Some(id) = pad.pad.gilrs_id() else {
+                continue;
+            };
+            if !pad.pad.rumble_supported() || ms == 0 {
+                continue;
+            }
+            let scheduling = Replay {
+                play_for: Ticks::from_ms(ms),
+                ..Replay::default()
+            };
+            let effect = EffectBuilder::new()
+                .add_effect(BaseEffect {
+                    kind: BaseEffectType::Strong {
+                        magnitude: (rumble.strong * u16::MAX as f32) as u16,
+                    },
+                    scheduling,
+                    ..BaseEffect::default()
+                })
+                .add_effect(BaseEffect {
+                    kind: BaseEffectType::Weak {
+                        magnitude: (rumble.weak * u16::MAX as f32) as u16,
+                    },
+                    scheduling,
+                    ..BaseEffect::default()
+                })
+                .repeat(Repeat::For(Ticks::from_ms(ms)))
+                .gamepads(&[id])
+                .finish(&mut gilrs);
+            if let Ok(effect) = effect
+                && effect.play().is_ok()
+            {
+                pad.rumble = Some(effect);
+            }
+        }
+    }
+
+    /// The state of every gamepad for the update phase, in the order they connected.
+    pub(crate) fn record(&self) -> Vec<GamepadFrame> {
+        let gamepads = self.0.gamepads.borrow();
+        let mut pads: Vec<_> = gamepads.iter().collect();
+        pads.sort_by_key(|(id, pad)| (pad.pad.connect_time(), **id));
+        pads.into_iter().map(|(_, pad)| pad.pad.record()).collect()
+    }
+
+    /// Set the real gamepads aside so recorded ones can be played back in their place.
+    pub(crate) fn begin_playback(&self) {
+        if self.0.playing.replace(true) {
+            return;
+        }
+        let real = std::mem::take(&mut *self.0.gamepads.borrow_mut());
+        *self.0.suspended.borrow_mut() = real;
+    }
+
+    /// Replace the update phase with recorded gamepads, connecting and disconnecting virtual
+    /// gamepads to match them.
+    #[allow(unused_variables)]
+    pub(crate) fn play(&self, ctx: &Context, frames: &[GamepadFrame]) {
+        // virtual gamepads count down from the top so they never collide with real IDs
+        let mut gamepads = self.0.gamepads.borrow_mut();
+        gamepads.retain(|&id, pad| {
+            let keep = frames
+                .get(usize::MAX - id)
+                .is_some_and(|frame| frame.name == pad.pad.name());
+            if !keep {
+                pad.pad.disconnect();
+            }
+            keep
+        });
+        for (i, frame) in frames.iter().enumerate() {
+            let pad = gamepads.entry(usize::MAX - i).or_insert_with(|| {
+                let now = SystemTime::now();
+                let pad =
+                    Gamepad::new(None, frame.name.clone(), GamepadStatus::Unknown, now, false);
+                Pad {
+                    rumble: None,
+                    #[cfg(feature = "lua")]
+                    userdata: ctx.lua.upgrade().create_userdata(pad.clone()).unwrap(),
+                    pad,
+                }
+            });
+            pad.pad.play(frame);
+        }
+    }
+
+    /// Disconnect the recorded gamepads and bring back the real ones.
+    pub(crate) fn end_playback(&self) {
+        if !self.0.playing.replace(false) {
+            return;
+        }
+        let mut gamepads = self.0.gamepads.borrow_mut();
+        for (_, pad) in gamepads.drain() {
+            pad.pad.disconnect();
+        }
+        *gamepads = std::mem::take(&mut *self.0.suspended.borrow_mut());
+
+        // drop presses that happened while they were set aside
+        for pad in gamepads.values() {
+            pad.pad.set_render_phase();
+            pad.pad.clear_phase();
+            pad.pad.set_update_phase();
+            pad.pad.clear_phase();
+        }
     }
 
     #[inline]
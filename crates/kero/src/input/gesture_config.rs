@@ -0,0 +1,23 @@
+/// Thresholds used by [`Touch`](crate::input::Touch) to recognize gestures.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// The longest a finger can be held, in seconds, and still count as a tap.
+    pub tap_time: f32,
+
+    /// How far a finger can move before it is dragging rather than tapping or long-pressing.
+    pub slop: f32,
+
+    /// How long a finger must be held still, in seconds, to count as a long-press.
+    pub long_press_time: f32,
+}
+
+impl Default for GestureConfig {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            tap_time: 0.3,
+            slop: 10.0,
+            long_press_time: 0.5,
+        }
+    }
+}
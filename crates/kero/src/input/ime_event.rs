@@ -0,0 +1,32 @@
+use compact_str::CompactString;
+
+/// An event from the platform's input method editor (IME), used to type languages like
+/// Japanese, Chinese, and Korean by composing characters before committing them.
+///
+/// To receive these, allow the IME with
+/// [`Window::set_ime_allowed`](crate::core::Window::set_ime_allowed) while a text field is
+/// focused.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ImeEvent {
+    /// The IME was enabled, and composition events may follow.
+    Enabled,
+
+    /// The text being composed changed. It should be drawn at the text cursor, usually
+    /// underlined, but not yet inserted into the text field. Empty if composition was cancelled
+    /// or is about to be committed.
+    Preedit {
+        /// The text being composed.
+        text: CompactString,
+
+        /// The byte range of the IME's cursor or selection within the text, if it should be
+        /// shown.
+        cursor: Option<(usize, usize)>,
+    },
+
+    /// Composed text was committed and should be inserted into the text field. It is also
+    /// added to [`Keyboard::text_input`](crate::input::Keyboard::text_input).
+    Commit(CompactString),
+
+    /// The IME was disabled.
+    Disabled,
+}
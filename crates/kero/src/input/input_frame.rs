@@ -0,0 +1,33 @@
+use crate::input::{ButtonStates, GamepadFrame, Key, MouseButton};
+use crate::math::Vec2F;
+use compact_str::CompactString;
+use strum::EnumCount;
+
+/// The keyboard, mouse, and gamepad state for one update, as stored in an
+/// [`InputRecording`](crate::input::InputRecording).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct InputFrame {
+    /// The keyboard's keys.
+    pub keys: ButtonStates<{ Key::COUNT }>,
+
+    /// Text typed this frame.
+    pub text: CompactString,
+
+    /// The mouse position in window coordinates.
+    pub mouse_pos: Vec2F,
+
+    /// How far the mouse moved this frame, straight from the device.
+    pub mouse_motion: Vec2F,
+
+    /// The mouse's buttons.
+    pub mouse_buttons: ButtonStates<{ MouseButton::COUNT }>,
+
+    /// Lines scrolled this frame by mouse wheels that scroll by line.
+    pub scroll_lines: Vec2F,
+
+    /// Pixels scrolled this frame by trackpads and mouse wheels that scroll by pixel.
+    pub scroll_delta: Vec2F,
+
+    /// Every connected gamepad, in the order they connected.
+    pub gamepads: Vec<GamepadFrame>,
+}
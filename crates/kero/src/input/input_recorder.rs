@@ -0,0 +1,287 @@
+use crate::core::{Context, Time};
+use crate::input::{InputCursor, InputFrame, InputRecording};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Handle to the input recorder, which records the keyboard, mouse, and gamepads every update
+/// and plays recordings back in their place.
+///
+/// While a recording plays, real keyboard and mouse input is ignored and the real gamepads are
+/// replaced by recorded ones. Because every update sees exactly the input it saw when recorded,
+/// a game that only depends on its input and [`Time::delta`] replays the same way every time,
+/// which is useful for replays, demos, and regression tests.
+///
+/// ```ignore
+/// // start recording from the title screen
+/// ctx.recorder.start_recording();
+///
+/// // later, save it
+/// if let Some(recording) = ctx.recorder.stop_recording() {
+///     recording.save("replay.kinp")?;
+/// }
+///
+/// // and play it back
+/// ctx.recorder.play(InputRecording::load("replay.kinp")?);
+/// ```
+///
+/// The last few seconds of input are also kept as a [history](Self::history) even when not
+/// recording, which is attached to [bug reports](crate::core::DebugTools::capture_report).
+#[derive(Clone)]
+pub struct InputRecorder(Rc<State>);
+
+struct State {
+    time: Time,
+    mode: RefCell<Mode>,
+    devices_playing: Cell<bool>,
+    finished: Cell<bool>,
+    quit_when_finished: Cell<bool>,
+    history_len: Cell<f32>,
+    history: RefCell<VecDeque<(InputRecording, f32)>>,
+}
+
+enum Mode {
+    Idle,
+    Recording(Box<InputRecording>),
+    Playing(Box<(InputRecording, InputCursor)>),
+}
+
+impl Debug for InputRecorder {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("InputRecorder").finish_non_exhaustive()
+    }
+}
+
+impl InputRecorder {
+    pub(crate) fn new(time: Time) -> Self {
+        Self(Rc::new(State {
+            time,
+            mode: RefCell::new(Mode::Idle),
+            devices_playing: Cell::new(false),
+            finished: Cell::new(false),
+            quit_when_finished: Cell::new(false),
+            history_len: Cell::new(10.0),
+            history: RefCell::new(VecDeque::new()),
+        }))
+    }
+
+    /// Start recording input, starting from the next update. This stops any recording or
+    /// playback already in progress.
+    ///
+    /// Recordings only replay exactly if the game has a
+    /// [target frame rate](Time::set_target_fps), so every update has the same delta time.
+    pub fn start_recording(&self) {
+        let fps = self.0.time.target_fps().unwrap_or(0.0);
+        *self.0.mode.borrow_mut() = Mode::Recording(Box::new(InputRecording::new(fps)));
+    }
+
+    /// Stop recording input, returning the recording.
+    pub fn stop_recording(&self) -> Option<InputRecording> {
+        let mut mode = self.0.mode.borrow_mut();
+        match std::mem::replace(&mut *mode, Mode::Idle) {
+            Mode::Recording(recording) => Some(*recording),
+            other => {
+                *mode = other;
+                None
+            }
+        }
+    }
+
+    /// If input is being recorded.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        matches!(*self.0.mode.borrow(), Mode::Recording(_))
+    }
+
+    /// How many frames have been recorded so far.
+    #[inline]
+    pub fn recorded_frames(&self) -> usize {
+        match &*self.0.mode.borrow() {
+            Mode::Recording(recording) => recording.len(),
+            _ => 0,
+        }
+    }
+
+    /// Play a recording back, starting from the next update. This stops any recording or
+    /// playback already in progress, and sets the [target frame rate](Time::set_target_fps)
+    /// to the one it was recorded at.
+    pub fn play(&self, recording: InputRecording) {
+        if recording.target_fps() > 0.0 {
+            self.0.time.set_target_fps(Some(recording.target_fps()));
+        }
+        self.0.finished.set(false);
+        *self.0.mode.borrow_mut() = Mode::Playing(Box::new((recording, InputCursor::default())));
+    }
+
+    /// Stop playing back a recording, giving control back to the real input devices.
+    pub fn stop_playback(&self) {
+        let mut mode = self.0.mode.borrow_mut();
+        if matches!(*mode, Mode::Playing(_)) {
+            *mode = Mode::Idle;
+        }
+    }
+
+    /// If a recording is being played back.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        matches!(*self.0.mode.borrow(), Mode::Playing(_))
+    }
+
+    /// How many frames of the recording have been played back.
+    #[inline]
+    pub fn playback_frame(&self) -> usize {
+        match &*self.0.mode.borrow() {
+            Mode::Playing(playing) => playing.1.index,
+            _ => 0,
+        }
+    }
+
+    /// How many frames the recording being played back has.
+    #[inline]
+    pub fn playback_len(&self) -> usize {
+        match &*self.0.mode.borrow() {
+            Mode::Playing(playing) => playing.0.len(),
+            _ => 0,
+        }
+    }
+
+    /// If the last recording played back reached its end.
+    #[inline]
+    pub fn playback_finished(&self) -> bool {
+        self.0.finished.get()
+    }
+
+    /// If the game quits when a recording finishes playing back, which is useful for
+    /// automated tests. Defaults to `false`.
+    #[inline]
+    pub fn quit_when_finished(&self) -> bool {
+        self.0.quit_when_finished.get()
+    }
+
+    /// Set if the game quits when a recording finishes playing back.
+    #[inline]
+    pub fn set_quit_when_finished(&self, quit: bool) {
+        self.0.quit_when_finished.set(quit);
+    }
+
+    /// How many seconds of recent input are kept in the [history](Self::history). Defaults
+    /// to `10`.
+    #[inline]
+    pub fn history_len(&self) -> f32 {
+        self.0.history_len.get()
+    }
+
+    /// Set how many seconds of recent input are kept in the history. Setting this to `0`
+    /// stops keeping it.
+    pub fn set_history_len(&self, seconds: f32) {
+        self.0.history_len.set(seconds.max(0.0));
+        if seconds <= 0.0 {
+            self.0.history.borrow_mut().clear();
+        }
+    }
+
+    /// The recent input, covering at least the last [`history_len`](Self::history_len)
+    /// seconds once the game has run that long. Input is not kept while a recording plays.
+    pub fn history(&self) -> InputRecording {
+        let chunks = self.0.history.borrow();
+        let fps = chunks.front().map_or(0.0, |(chunk, _)| chunk.target_fps());
+        let mut history = InputRecording::new(fps);
+        for frame in chunks.iter().flat_map(|(chunk, _)| chunk.frames()) {
+            history.push(&frame);
+        }
+        history
+    }
+
+    /// Add the frame to the history, dropping the oldest input once there is enough.
+    fn push_history(&self, frame: &InputFrame) {
+        let len = self.history_len();
+        if len <= 0.0 {
+            return;
+        }
+
+        // input is kept in chunks, so the oldest can be dropped a chunk at a time
+        let mut chunks = self.0.history.borrow_mut();
+        if chunks
+            .back()
+            .is_none_or(|(_, duration)| *duration >= len / 4.0)
+        {
+            let fps = self.0.time.target_fps().unwrap_or(0.0);
+            chunks.push_back((InputRecording::new(fps), 0.0));
+        }
+        let (chunk, duration) = chunks.back_mut().unwrap();
+        chunk.push(frame);
+        *duration += self.0.time.unscaled_delta();
+
+        let mut total: f32 = chunks.iter().map(|(_, duration)| duration).sum();
+        while let Some((_, oldest)) = chunks.front()
+            && total - oldest >= len
+        {
+            total -= oldest;
+            chunks.pop_front();
+        }
+    }
+
+    /// Record the input for this update, or replace it with the next recorded frame.
+    pub(crate) fn update(&self, ctx: &Context) {
+        let mut mode = self.0.mode.borrow_mut();
+        match &mut *mode {
+            Mode::Idle => {
+                if self.0.devices_playing.get() {
+                    self.release_devices(ctx);
+                }
+                self.push_history(&capture(ctx));
+            }
+            Mode::Recording(recording) => {
+                let frame = capture(ctx);
+                recording.push(&frame);
+                self.push_history(&frame);
+            }
+            Mode::Playing(playing) => {
+                let (recording, cursor) = &mut **playing;
+                if !self.0.devices_playing.replace(true) {
+                    ctx.gamepads.begin_playback();
+                }
+                if recording.next_frame(cursor) {
+                    apply(ctx, &cursor.frame);
+                } else {
+                    *mode = Mode::Idle;
+                    self.release_devices(ctx);
+                    self.0.finished.set(true);
+                    if self.quit_when_finished() {
+                        ctx.quit();
+                    }
+                }
+            }
+        }
+    }
+
+    /// Give control back to the real input devices.
+    fn release_devices(&self, ctx: &Context) {
+        self.0.devices_playing.set(false);
+        ctx.keyboard.reset();
+        ctx.mouse.reset();
+        ctx.gamepads.end_playback();
+    }
+}
+
+/// Capture the input of the current update.
+fn capture(ctx: &Context) -> InputFrame {
+    let (keys, text) = ctx.keyboard.record();
+    let mut frame = InputFrame {
+        keys,
+        text,
+        gamepads: ctx.gamepads.record(),
+        ..Default::default()
+    };
+    ctx.mouse.record(&mut frame);
+    frame
+}
+
+/// Replace the input of the current update with a recorded frame.
+fn apply(ctx: &Context, frame: &InputFrame) {
+    ctx.keyboard.play(&frame.keys, &frame.text);
+    ctx.mouse.play(frame);
+    ctx.gamepads.play(ctx, &frame.gamepads);
+}
@@ -0,0 +1,441 @@
+use crate::input::{
+    GamepadAxis, GamepadButton, GamepadFrame, InputFrame, InputRecordingError, Key,
+};
+use crate::math::{Vec2F, vec2};
+use crate::storage::write_atomic;
+use std::path::Path;
+use strum::EnumCount;
+
+const MAGIC: &[u8; 4] = b"KINP";
+const VERSION: u8 = 2;
+const HEADER_LEN: usize = 17;
+
+const KEYS: u8 = 1 << 0;
+const TEXT: u8 = 1 << 1;
+const MOUSE_POS: u8 = 1 << 2;
+const MOUSE_BUTTONS: u8 = 1 << 3;
+const SCROLL: u8 = 1 << 4;
+const GAMEPADS: u8 = 1 << 5;
+const MOUSE_MOTION: u8 = 1 << 6;
+const ALL: u8 = KEYS | TEXT | MOUSE_POS | MOUSE_BUTTONS | SCROLL | GAMEPADS | MOUSE_MOTION;
+
+// key indices are stored as bytes and gamepad buttons and axes as bitmasks
+const _: () = assert!(Key::COUNT <= 256);
+const _: () = assert!(GamepadButton::COUNT <= 32);
+const _: () = assert!(GamepadAxis::COUNT <= 8);
+
+/// A recording of the keyboard, mouse, and gamepad state for every update, which can be played
+/// back through the [`InputRecorder`](crate::input::InputRecorder) to reproduce a play session.
+///
+/// Frames are stored in a compact binary format as they are recorded, only storing what
+/// changed since the previous frame, so a frame with no input changes takes a single byte.
+#[derive(Debug, Clone)]
+pub struct InputRecording {
+    target_fps: f64,
+    len: usize,
+    bytes: Vec<u8>,
+    last: Box<InputFrame>,
+}
+
+impl InputRecording {
+    /// Create an empty recording of a game updating at the frame rate.
+    #[inline]
+    pub fn new(target_fps: f64) -> Self {
+        Self {
+            target_fps,
+            len: 0,
+            bytes: Vec::new(),
+            last: Box::default(),
+        }
+    }
+
+    /// The frame rate the game was updating at when recorded. Playing the recording back at
+    /// the same rate keeps [`Time::delta`](crate::core::Time::delta) the same.
+    #[inline]
+    pub fn target_fps(&self) -> f64 {
+        self.target_fps
+    }
+
+    /// How many frames were recorded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// If no frames were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// How long the recording is, in seconds.
+    #[inline]
+    pub fn duration(&self) -> f64 {
+        match self.target_fps > 0.0 {
+            true => self.len as f64 / self.target_fps,
+            false => 0.0,
+        }
+    }
+
+    /// Add a frame to the end of the recording.
+    pub fn push(&mut self, frame: &InputFrame) {
+        encode_frame(&mut self.bytes, &self.last, frame);
+        self.last.as_mut().clone_from(frame);
+        self.len += 1;
+    }
+
+    /// Iterate over every frame in order.
+    #[inline]
+    pub fn frames(&self) -> InputFrames<'_> {
+        InputFrames {
+            recording: self,
+            cursor: InputCursor::default(),
+        }
+    }
+
+    /// Decode the frame after the cursor into it, returning `false` at the end.
+    pub(crate) fn next_frame(&self, cursor: &mut InputCursor) -> bool {
+        if cursor.index >= self.len {
+            return false;
+        }
+        let mut reader = Reader {
+            bytes: &self.bytes,
+            pos: cursor.pos,
+        };
+        // frames were checked when they were pushed or loaded
+        if decode_frame(&mut reader, &mut cursor.frame).is_none() {
+            return false;
+        }
+        cursor.pos = reader.pos;
+        cursor.index += 1;
+        true
+    }
+
+    /// Encode the recording as bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.bytes.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&self.target_fps.to_le_bytes());
+        bytes.extend_from_slice(&(self.len as u32).to_le_bytes());
+        bytes.extend_from_slice(&self.bytes);
+        bytes
+    }
+
+    /// Decode a recording encoded with [`to_bytes`](Self::to_bytes), checking every frame.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, InputRecordingError> {
+        let (header, body) = bytes
+            .split_at_checked(HEADER_LEN)
+            .ok_or(InputRecordingError::InvalidHeader)?;
+        if &header[..4] != MAGIC {
+            return Err(InputRecordingError::InvalidHeader);
+        }
+        // version 1 is the same, but never has mouse motion
+        if !(1..=VERSION).contains(&header[4]) {
+            return Err(InputRecordingError::UnsupportedVersion(header[4]));
+        }
+        let target_fps = f64::from_le_bytes(header[5..13].try_into().unwrap());
+        let len = u32::from_le_bytes(header[13..17].try_into().unwrap()) as usize;
+
+        // decode every frame up front, so playback never runs into a corrupt one
+        let mut reader = Reader {
+            bytes: body,
+            pos: 0,
+        };
+        let mut last = InputFrame::default();
+        for i in 0..len {
+            decode_frame(&mut reader, &mut last).ok_or(InputRecordingError::Corrupt(i))?;
+        }
+        if reader.pos != body.len() {
+            return Err(InputRecordingError::Corrupt(len));
+        }
+        Ok(Self {
+            target_fps,
+            len,
+            bytes: body.to_vec(),
+            last: Box::new(last),
+        })
+    }
+
+    /// Save the recording to a file.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), InputRecordingError> {
+        Ok(write_atomic(path, &self.to_bytes())?)
+    }
+
+    /// Load a recording from a file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, InputRecordingError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// A position in an [`InputRecording`], holding the last frame decoded.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InputCursor {
+    pub index: usize,
+    pub frame: InputFrame,
+    pos: usize,
+}
+
+/// An iterator over the frames of an [`InputRecording`].
+#[derive(Debug, Clone)]
+pub struct InputFrames<'a> {
+    recording: &'a InputRecording,
+    cursor: InputCursor,
+}
+
+impl Iterator for InputFrames<'_> {
+    type Item = InputFrame;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.recording
+            .next_frame(&mut self.cursor)
+            .then(|| self.cursor.frame.clone())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.recording.len - self.cursor.index;
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for InputFrames<'_> {}
+
+fn encode_frame(out: &mut Vec<u8>, prev: &InputFrame, frame: &InputFrame) {
+    let mut mask = 0;
+    if frame.keys != prev.keys {
+        mask |= KEYS;
+    }
+    if frame.text != prev.text {
+        mask |= TEXT;
+    }
+    if frame.mouse_pos != prev.mouse_pos {
+        mask |= MOUSE_POS;
+    }
+    if frame.mouse_buttons != prev.mouse_buttons {
+        mask |= MOUSE_BUTTONS;
+    }
+    if frame.scroll_lines != prev.scroll_lines || frame.scroll_delta != prev.scroll_delta {
+        mask |= SCROLL;
+    }
+    if frame.gamepads != prev.gamepads {
+        mask |= GAMEPADS;
+    }
+    if frame.mouse_motion != prev.mouse_motion {
+        mask |= MOUSE_MOTION;
+    }
+    out.push(mask);
+
+    if mask & KEYS != 0 {
+        let keys = &frame.keys;
+        for flags in [&keys.down, &keys.pressed, &keys.released, &keys.repeated] {
+            let count = flags.iter().filter(|&&f| f).count();
+            write_varint(out, count as u64);
+            out.extend((0..Key::COUNT).filter(|&i| flags[i]).map(|i| i as u8));
+        }
+    }
+    if mask & TEXT != 0 {
+        write_varint(out, frame.text.len() as u64);
+        out.extend_from_slice(frame.text.as_bytes());
+    }
+    if mask & MOUSE_POS != 0 {
+        write_vec2(out, frame.mouse_pos);
+    }
+    if mask & MOUSE_BUTTONS != 0 {
+        let b = &frame.mouse_buttons;
+        let bits = to_bits(&b.down)
+            | (to_bits(&b.pressed) << 4)
+            | (to_bits(&b.released) << 8)
+            | (to_bits(&b.repeated) << 12);
+        out.extend_from_slice(&(bits as u16).to_le_bytes());
+    }
+    if mask & SCROLL != 0 {
+        write_vec2(out, frame.scroll_lines);
+        write_vec2(out, frame.scroll_delta);
+    }
+    if mask & GAMEPADS != 0 {
+        write_varint(out, frame.gamepads.len() as u64);
+        for pad in &frame.gamepads {
+            write_varint(out, pad.name.len() as u64);
+            out.extend_from_slice(pad.name.as_bytes());
+            let b = &pad.buttons;
+            for flags in [&b.down, &b.pressed, &b.released, &b.repeated] {
+                out.extend_from_slice(&to_bits(flags).to_le_bytes());
+            }
+            out.extend_from_slice(&nonzero_bits(&pad.values).to_le_bytes());
+            out.extend(
+                pad.values
+                    .iter()
+                    .filter(|&&v| v != 0.0)
+                    .flat_map(|v| v.to_le_bytes()),
+            );
+            out.push(nonzero_bits(&pad.axes) as u8);
+            out.extend(
+                pad.axes
+                    .iter()
+                    .filter(|&&v| v != 0.0)
+                    .flat_map(|v| v.to_le_bytes()),
+            );
+        }
+    }
+    if mask & MOUSE_MOTION != 0 {
+        write_vec2(out, frame.mouse_motion);
+    }
+}
+
+/// Decode the next frame on top of the previous one, or `None` if the data is corrupt.
+fn decode_frame(r: &mut Reader, frame: &mut InputFrame) -> Option<()> {
+    let mask = r.u8()?;
+    if mask & !ALL != 0 {
+        return None;
+    }
+
+    // these only last for the frame they happened on, so are not stored when cleared
+    if mask & TEXT == 0 {
+        frame.text.clear();
+    }
+
+    if mask & KEYS != 0 {
+        let keys = &mut frame.keys;
+        for flags in [
+            &mut keys.down,
+            &mut keys.pressed,
+            &mut keys.released,
+            &mut keys.repeated,
+        ] {
+            *flags = [false; _];
+            for _ in 0..r.varint()? {
+                *flags.get_mut(r.u8()? as usize)? = true;
+            }
+        }
+    }
+    if mask & TEXT != 0 {
+        let len = r.varint()? as usize;
+        frame.text = std::str::from_utf8(r.take(len)?).ok()?.into();
+    }
+    if mask & MOUSE_POS != 0 {
+        frame.mouse_pos = r.vec2()?;
+    }
+    if mask & MOUSE_BUTTONS != 0 {
+        let bits = u16::from_le_bytes(r.array()?) as u32;
+        let b = &mut frame.mouse_buttons;
+        b.down = from_bits(bits);
+        b.pressed = from_bits(bits >> 4);
+        b.released = from_bits(bits >> 8);
+        b.repeated = from_bits(bits >> 12);
+    }
+    if mask & SCROLL != 0 {
+        frame.scroll_lines = r.vec2()?;
+        frame.scroll_delta = r.vec2()?;
+    }
+    if mask & GAMEPADS != 0 {
+        let count = r.varint()? as usize;
+        frame.gamepads.clear();
+        for _ in 0..count {
+            let mut pad = GamepadFrame::default();
+            let len = r.varint()? as usize;
+            pad.name = std::str::from_utf8(r.take(len)?).ok()?.to_string();
+            let b = &mut pad.buttons;
+            for flags in [
+                &mut b.down,
+                &mut b.pressed,
+                &mut b.released,
+                &mut b.repeated,
+            ] {
+                *flags = from_bits(u32::from_le_bytes(r.array()?));
+            }
+            let bits = u32::from_le_bytes(r.array()?);
+            for (i, value) in pad.values.iter_mut().enumerate() {
+                if bits & (1 << i) != 0 {
+                    *value = f32::from_le_bytes(r.array()?);
+                }
+            }
+            let bits = r.u8()?;
+            for (i, axis) in pad.axes.iter_mut().enumerate() {
+                if bits & (1 << i) != 0 {
+                    *axis = f32::from_le_bytes(r.array()?);
+                }
+            }
+            frame.gamepads.push(pad);
+        }
+    }
+    if mask & MOUSE_MOTION != 0 {
+        frame.mouse_motion = r.vec2()?;
+    }
+    Some(())
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    #[inline]
+    fn take(&mut self, len: usize) -> Option<&'a [u8]> {
+        let bytes = self.bytes.get(self.pos..self.pos.checked_add(len)?)?;
+        self.pos += len;
+        Some(bytes)
+    }
+
+    #[inline]
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    #[inline]
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    #[inline]
+    fn vec2(&mut self) -> Option<Vec2F> {
+        let x = f32::from_le_bytes(self.array()?);
+        let y = f32::from_le_bytes(self.array()?);
+        Some(vec2(x, y))
+    }
+
+    fn varint(&mut self) -> Option<u64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+#[inline]
+fn write_vec2(out: &mut Vec<u8>, v: Vec2F) {
+    out.extend_from_slice(&v.x.to_le_bytes());
+    out.extend_from_slice(&v.y.to_le_bytes());
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8 & 0x7f) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+#[inline]
+fn to_bits<const N: usize>(flags: &[bool; N]) -> u32 {
+    flags
+        .iter()
+        .enumerate()
+        .fold(0, |bits, (i, &f)| bits | ((f as u32) << i))
+}
+
+#[inline]
+fn from_bits<const N: usize>(bits: u32) -> [bool; N] {
+    std::array::from_fn(|i| bits & (1 << i) != 0)
+}
+
+#[inline]
+fn nonzero_bits<const N: usize>(values: &[f32; N]) -> u32 {
+    to_bits(&values.map(|v| v != 0.0))
+}
@@ -0,0 +1,15 @@
+/// An error reading an [`InputRecording`](crate::input::InputRecording).
+#[derive(Debug, thiserror::Error)]
+pub enum InputRecordingError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("not an input recording")]
+    InvalidHeader,
+
+    #[error("unsupported input recording version [{0}]")]
+    UnsupportedVersion(u8),
+
+    #[error("input recording is truncated or corrupt at frame [{0}]")]
+    Corrupt(usize),
+}
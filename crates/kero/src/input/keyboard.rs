@@ -1,11 +1,11 @@
-use crate::input::Key;
+use crate::input::{ButtonStates, ImeEvent, Key, play_flags};
 use compact_str::CompactString;
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::time::SystemTime;
 use strum::{EnumCount, VariantArray};
-use winit::event::{ElementState, KeyEvent};
+use winit::event::{ElementState, Ime, KeyEvent};
 use winit::keyboard::PhysicalKey;
 
 /// Handle to the keyboard state.
@@ -27,6 +27,9 @@ struct State {
     phases: [Phase; 2],
     phase: Cell<usize>,
     last_active: Cell<SystemTime>,
+    ime_enabled: Cell<bool>,
+    composition: RefCell<CompactString>,
+    composition_cursor: Cell<Option<(usize, usize)>>,
 }
 
 impl Default for State {
@@ -38,9 +41,13 @@ impl Default for State {
                 released: Cell::new([false; _]),
                 repeated: Cell::new([false; _]),
                 text_input: Cell::new(CompactString::default()),
+                ime_events: Cell::new(Vec::new()),
             }),
             phase: Cell::new(0),
             last_active: Cell::new(SystemTime::now()),
+            ime_enabled: Cell::new(false),
+            composition: RefCell::new(CompactString::default()),
+            composition_cursor: Cell::new(None),
         }
     }
 }
@@ -50,6 +57,7 @@ struct Phase {
     pub released: Cell<[bool; Key::COUNT]>,
     pub repeated: Cell<[bool; Key::COUNT]>,
     pub text_input: Cell<CompactString>,
+    pub ime_events: Cell<Vec<ImeEvent>>,
 }
 
 impl Clone for Phase {
@@ -64,6 +72,11 @@ impl Clone for Phase {
                 self.text_input.set(text.clone());
                 Cell::new(text)
             },
+            ime_events: {
+                let events = self.ime_events.take();
+                self.ime_events.set(events.clone());
+                Cell::new(events)
+            },
         }
     }
 }
@@ -129,6 +142,51 @@ impl Keyboard {
         text
     }
 
+    /// Characters typed this frame, including text committed by the IME, but not control
+    /// characters like backspace or enter. Use this for chat boxes and name entry, and check
+    /// keys like [`Key::Backspace`] separately for editing.
+    pub fn typed_chars(&self) -> impl Iterator<Item = char> {
+        let text = self.text_input();
+        let chars: Vec<char> = text.chars().filter(|c| !c.is_control()).collect();
+        chars.into_iter()
+    }
+
+    /// IME events that occurred this frame.
+    #[inline]
+    pub fn ime_events(&self) -> Vec<ImeEvent> {
+        let phase = self.phase();
+        let events = phase.ime_events.take();
+        phase.ime_events.set(events.clone());
+        events
+    }
+
+    /// If the IME is enabled.
+    #[inline]
+    pub fn ime_enabled(&self) -> bool {
+        self.0.ime_enabled.get()
+    }
+
+    /// The text currently being composed in the IME, which should be drawn at the text cursor
+    /// but not yet inserted. Empty if nothing is being composed.
+    #[inline]
+    pub fn composition(&self) -> CompactString {
+        self.0.composition.borrow().clone()
+    }
+
+    /// The byte range of the IME's cursor or selection within the
+    /// [composition](Self::composition), if it should be shown.
+    #[inline]
+    pub fn composition_cursor(&self) -> Option<(usize, usize)> {
+        self.0.composition_cursor.get()
+    }
+
+    /// If text is currently being composed in the IME. While it is, keys like enter and
+    /// backspace are being used by the IME and should not edit the text field.
+    #[inline]
+    pub fn is_composing(&self) -> bool {
+        !self.0.composition.borrow().is_empty()
+    }
+
     /// All keys that are currently down.
     #[inline]
     pub fn currently_down(&self) -> impl Iterator<Item = Key> {
@@ -220,6 +278,87 @@ impl Keyboard {
         }
     }
 
+    pub(crate) fn handle_ime(&self, ime: Ime) {
+        self.0.last_active.set(SystemTime::now());
+
+        let event = match ime {
+            Ime::Enabled => {
+                self.0.ime_enabled.set(true);
+                ImeEvent::Enabled
+            }
+            Ime::Preedit(text, cursor) => {
+                let text = CompactString::from(text);
+                *self.0.composition.borrow_mut() = text.clone();
+                self.0.composition_cursor.set(cursor);
+                ImeEvent::Preedit { text, cursor }
+            }
+            Ime::Commit(text) => {
+                for phase in &self.0.phases {
+                    let mut dst = phase.text_input.take();
+                    dst.push_str(&text);
+                    phase.text_input.set(dst);
+                }
+                self.0.composition.borrow_mut().clear();
+                self.0.composition_cursor.set(None);
+                ImeEvent::Commit(text.into())
+            }
+            Ime::Disabled => {
+                self.0.ime_enabled.set(false);
+                self.0.composition.borrow_mut().clear();
+                self.0.composition_cursor.set(None);
+                ImeEvent::Disabled
+            }
+        };
+        for phase in &self.0.phases {
+            let mut events = phase.ime_events.take();
+            events.push(event.clone());
+            phase.ime_events.set(events);
+        }
+    }
+
+    /// The keys and text of the update phase, for recording.
+    pub(crate) fn record(&self) -> (ButtonStates<{ Key::COUNT }>, CompactString) {
+        let phase = &self.0.phases[0];
+        let keys = ButtonStates {
+            down: self.0.down.get(),
+            pressed: phase.pressed.get(),
+            released: phase.released.get(),
+            repeated: phase.repeated.get(),
+        };
+        let text = phase.text_input.take();
+        phase.text_input.set(text.clone());
+        (keys, text)
+    }
+
+    /// Replace the update phase with recorded keys and text, as if they had been typed.
+    pub(crate) fn play(&self, keys: &ButtonStates<{ Key::COUNT }>, text: &str) {
+        if *keys != ButtonStates::default() || !text.is_empty() {
+            self.0.last_active.set(SystemTime::now());
+        }
+        self.0.down.set(keys.down);
+        for (i, phase) in self.0.phases.iter().enumerate() {
+            play_flags(i, &phase.pressed, &keys.pressed);
+            play_flags(i, &phase.released, &keys.released);
+            play_flags(i, &phase.repeated, &keys.repeated);
+            let mut dst = phase.text_input.take();
+            if i == 0 {
+                dst.clear();
+            }
+            dst.push_str(text);
+            phase.text_input.set(dst);
+        }
+    }
+
+    /// Release every key and clear both phases, so nothing is left held after playback.
+    pub(crate) fn reset(&self) {
+        self.0.down.set([false; _]);
+        for i in 0..self.0.phases.len() {
+            let phase = self.0.phase.replace(i);
+            self.clear_phase();
+            self.0.phase.set(phase);
+        }
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
         let phase = self.phase();
@@ -227,5 +366,8 @@ impl Keyboard {
         phase.released.set([false; _]);
         phase.repeated.set([false; _]);
         phase.text_input.set(CompactString::default());
+        let mut events = phase.ime_events.take();
+        events.clear();
+        phase.ime_events.set(events);
     }
 }
@@ -1,30 +1,58 @@
-//! Mouse, keyboard, and gamepad input handling.
+//! Mouse, keyboard, touch, and gamepad input handling.
 
+mod button_states;
 mod gamepad;
 mod gamepad_axis;
 mod gamepad_button;
+mod gamepad_frame;
 mod gamepad_status;
 mod gamepads;
+mod gesture_config;
+mod ime_event;
+mod input_frame;
+mod input_recorder;
+mod input_recording;
+mod input_recording_error;
 mod key;
 mod keyboard;
 mod mouse;
 mod mouse_button;
+mod touch;
+mod touch_phase;
+mod touch_point;
+mod vibration;
+mod vibration_playback;
 mod virtual_axis;
+mod virtual_bindings;
 mod virtual_button;
 mod virtual_controller;
 mod virtual_source;
 mod virtual_stick;
 
+pub use button_states::*;
 pub use gamepad::*;
 pub use gamepad_axis::*;
 pub use gamepad_button::*;
+pub use gamepad_frame::*;
 pub use gamepad_status::*;
 pub use gamepads::*;
+pub use gesture_config::*;
+pub use ime_event::*;
+pub use input_frame::*;
+pub use input_recorder::*;
+pub use input_recording::*;
+pub use input_recording_error::*;
 pub use key::*;
 pub use keyboard::*;
 pub use mouse::*;
 pub use mouse_button::*;
+pub use touch::*;
+pub use touch_phase::*;
+pub use touch_point::*;
+pub use vibration::*;
+pub use vibration_playback::*;
 pub use virtual_axis::*;
+pub use virtual_bindings::*;
 pub use virtual_button::*;
 pub use virtual_controller::*;
 pub use virtual_source::*;
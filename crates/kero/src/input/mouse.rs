@@ -1,4 +1,5 @@
-use crate::input::MouseButton;
+use crate::core::Window;
+use crate::input::{ButtonStates, InputFrame, MouseButton, play_flags};
 use crate::math::{Numeric, Vec2F, vec2};
 use dpi::{LogicalPosition, PhysicalPosition};
 use std::cell::Cell;
@@ -6,6 +7,7 @@ use std::fmt::{Debug, Formatter};
 use std::rc::Rc;
 use std::time::SystemTime;
 use winit::event::{ElementState, MouseScrollDelta};
+use winit::window::CursorGrabMode;
 
 /// Handle to the mouse state.
 ///
@@ -21,7 +23,10 @@ impl Debug for Mouse {
 
 #[derive(Debug)]
 struct State {
+    window: Window,
+    captured: Cell<bool>,
     pos: Cell<Vec2F>,
+    scroll_total: Cell<Vec2F>,
     down: Cell<[bool; 3]>,
     phases: [Phase; 2],
     phase: Cell<usize>,
@@ -30,6 +35,7 @@ struct State {
 
 #[derive(Debug, Default)]
 struct Phase {
+    motion: Cell<Vec2F>,
     scroll_lines: Cell<Vec2F>,
     scroll_delta: Cell<Vec2F>,
     pressed: Cell<[bool; 3]>,
@@ -37,9 +43,15 @@ struct Phase {
 }
 
 impl Mouse {
-    pub(crate) fn new() -> Self {
+    /// How many pixels of pixel-precise scrolling (eg. from a trackpad) count as one line.
+    pub const PIXELS_PER_LINE: f32 = 20.0;
+
+    pub(crate) fn new(window: Window) -> Self {
         Self(Rc::new(State {
+            window,
+            captured: Cell::new(false),
             pos: Cell::new(Vec2F::ZERO),
+            scroll_total: Cell::new(Vec2F::ZERO),
             down: Cell::new([false; _]),
             phases: std::array::from_fn(|_| Phase::default()),
             phase: Cell::new(0),
@@ -64,18 +76,93 @@ impl Mouse {
         self.0.pos.get()
     }
 
-    /// How many lines were scrolled this frame.
+    /// How far the mouse moved this frame, straight from the device rather than the cursor, so
+    /// it keeps reporting movement while [captured](Self::set_captured) or when the cursor is
+    /// against the edge of the screen. The units depend on the platform and mouse sensitivity,
+    /// so scale it by a sensitivity setting rather than treating it as pixels.
+    #[inline]
+    pub fn motion(&self) -> Vec2F {
+        self.phase().motion.get()
+    }
+
+    /// If the mouse is captured.
+    #[inline]
+    pub fn captured(&self) -> bool {
+        self.0.captured.get()
+    }
+
+    /// Capture the mouse, hiding the cursor and locking it inside the window so it can't leave
+    /// or click outside of it, for games that aim or move the camera with the mouse. Read its
+    /// movement with [`motion`](Self::motion), since its [position](Self::pos) stops changing
+    /// on platforms that lock it in place.
+    ///
+    /// Returns `false` if the platform couldn't lock or confine the cursor, in which case it is
+    /// still hidden. The capture is restored when the window regains focus.
+    pub fn set_captured(&self, captured: bool) -> bool {
+        self.0.captured.set(captured);
+        self.apply_capture()
+    }
+
+    /// Apply the capture to the window, again after it regains focus since platforms release
+    /// the cursor when switching away.
+    pub(crate) fn apply_capture(&self) -> bool {
+        let window = &self.0.window.0;
+        let captured = self.0.captured.get();
+        window.set_cursor_visible(!captured);
+        if !captured {
+            return window.set_cursor_grab(CursorGrabMode::None).is_ok();
+        }
+
+        // not every platform can lock the cursor in place, but they can at least keep it inside
+        window.set_cursor_grab(CursorGrabMode::Locked).is_ok()
+            || window.set_cursor_grab(CursorGrabMode::Confined).is_ok()
+    }
+
+    /// How many lines were scrolled this frame by mouse wheels that scroll by line. Positive
+    /// `y` scrolls up and positive `x` scrolls left.
     #[inline]
     pub fn scroll_lines(&self) -> Vec2F {
         self.phase().scroll_lines.get()
     }
 
-    /// Delta vector of the mouse scroll this frame.
+    /// How many pixels were scrolled this frame by trackpads and mouse wheels that scroll by
+    /// pixel. Positive `y` scrolls up and positive `x` scrolls left.
     #[inline]
     pub fn scroll_delta(&self) -> Vec2F {
         self.phase().scroll_delta.get()
     }
 
+    /// All scrolling this frame in lines, with pixel-precise scrolling converted using
+    /// [`PIXELS_PER_LINE`](Self::PIXELS_PER_LINE).
+    #[inline]
+    pub fn scroll(&self) -> Vec2F {
+        self.scroll_lines() + self.scroll_delta() / Self::PIXELS_PER_LINE
+    }
+
+    /// Horizontal scrolling this frame in lines.
+    #[inline]
+    pub fn scroll_x(&self) -> f32 {
+        self.scroll().x
+    }
+
+    /// Vertical scrolling this frame in lines.
+    #[inline]
+    pub fn scroll_y(&self) -> f32 {
+        self.scroll().y
+    }
+
+    /// All scrolling since the game started in lines. Easing towards a value based on this
+    /// gives smooth zooming that doesn't lose any wheel clicks:
+    ///
+    /// ```ignore
+    /// let target = 1.1_f32.powf(ctx.mouse.scroll_total().y);
+    /// self.zoom += (target - self.zoom) * (1.0 - (-15.0 * ctx.dt()).exp());
+    /// ```
+    #[inline]
+    pub fn scroll_total(&self) -> Vec2F {
+        self.0.scroll_total.get()
+    }
+
     /// If the button is down.
     #[inline]
     pub fn down(&self, button: MouseButton) -> bool {
@@ -165,19 +252,36 @@ impl Mouse {
         self.0.pos.set(vec2(pos.x, pos.y));
     }
 
+    #[inline]
+    pub(crate) fn handle_motion(&self, (x, y): (f64, f64)) {
+        self.0.last_active.set(SystemTime::now());
+
+        let delta = vec2(x, y).to_f32();
+        for phase in &self.0.phases {
+            phase.motion.update(|m| m + delta);
+        }
+    }
+
     #[inline]
     pub(crate) fn handle_scroll(&self, delta: MouseScrollDelta) {
         self.0.last_active.set(SystemTime::now());
 
-        let phase = &self.0.phases[self.0.phase.get()];
-        match delta {
+        let lines = match delta {
             MouseScrollDelta::LineDelta(x, y) => {
-                phase.scroll_lines.update(|s| s + vec2(x, y));
+                for phase in &self.0.phases {
+                    phase.scroll_lines.update(|s| s + vec2(x, y));
+                }
+                vec2(x, y)
             }
             MouseScrollDelta::PixelDelta(PhysicalPosition { x, y }) => {
-                phase.scroll_delta.update(|s| s + vec2(x, y).to_f32());
+                let delta = vec2(x, y).to_f32();
+                for phase in &self.0.phases {
+                    phase.scroll_delta.update(|s| s + delta);
+                }
+                delta / Self::PIXELS_PER_LINE
             }
-        }
+        };
+        self.0.scroll_total.update(|s| s + lines);
     }
 
     #[inline]
@@ -204,9 +308,71 @@ impl Mouse {
         }
     }
 
+    /// Record the position, motion, buttons, and scrolling of the update phase.
+    pub(crate) fn record(&self, frame: &mut InputFrame) {
+        let phase = &self.0.phases[0];
+        frame.mouse_pos = self.pos();
+        frame.mouse_motion = phase.motion.get();
+        frame.mouse_buttons = ButtonStates {
+            down: self.0.down.get(),
+            pressed: phase.pressed.get(),
+            released: phase.released.get(),
+            repeated: [false; _],
+        };
+        frame.scroll_lines = phase.scroll_lines.get();
+        frame.scroll_delta = phase.scroll_delta.get();
+    }
+
+    /// Replace the update phase with a recorded position, motion, buttons, and scrolling.
+    pub(crate) fn play(&self, frame: &InputFrame) {
+        let lines = frame.scroll_lines;
+        let delta = frame.scroll_delta;
+        let motion = frame.mouse_motion;
+        if frame.mouse_pos != self.pos()
+            || frame.mouse_buttons != ButtonStates::default()
+            || lines != Vec2F::ZERO
+            || delta != Vec2F::ZERO
+            || motion != Vec2F::ZERO
+        {
+            self.0.last_active.set(SystemTime::now());
+        }
+        self.0.pos.set(frame.mouse_pos);
+        self.0.down.set(frame.mouse_buttons.down);
+        for (i, phase) in self.0.phases.iter().enumerate() {
+            play_flags(i, &phase.pressed, &frame.mouse_buttons.pressed);
+            play_flags(i, &phase.released, &frame.mouse_buttons.released);
+            match i {
+                0 => {
+                    phase.motion.set(motion);
+                    phase.scroll_lines.set(lines);
+                    phase.scroll_delta.set(delta);
+                }
+                _ => {
+                    phase.motion.update(|m| m + motion);
+                    phase.scroll_lines.update(|s| s + lines);
+                    phase.scroll_delta.update(|s| s + delta);
+                }
+            }
+        }
+        self.0
+            .scroll_total
+            .update(|s| s + lines + delta / Self::PIXELS_PER_LINE);
+    }
+
+    /// Release every button and clear both phases, so nothing is left held after playback.
+    pub(crate) fn reset(&self) {
+        self.0.down.set([false; _]);
+        for i in 0..self.0.phases.len() {
+            let phase = self.0.phase.replace(i);
+            self.clear_phase();
+            self.0.phase.set(phase);
+        }
+    }
+
     #[inline]
     pub(crate) fn clear_phase(&self) {
         let phase = self.phase();
+        phase.motion.set(Vec2F::ZERO);
         phase.scroll_lines.set(Vec2F::ZERO);
         phase.scroll_delta.set(Vec2F::ZERO);
         phase.pressed.set([false; 3]);
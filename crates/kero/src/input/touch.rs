@@ -0,0 +1,336 @@
+use crate::input::{GestureConfig, TouchPhase, TouchPoint};
+use crate::math::{Vec2F, vec2};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+use std::time::{Instant, SystemTime};
+
+/// Handle to the touch screen state.
+///
+/// Tracks every finger on the screen as a [`TouchPoint`], and recognizes taps, long-presses,
+/// panning, and pinch zooming from them.
+///
+/// ```ignore
+/// if let Some(pos) = ctx.touch.tap() {
+///     self.select(camera.screen_to_world(pos));
+/// }
+/// camera.pos -= ctx.touch.pan_delta() / camera.zoom;
+/// camera.zoom *= ctx.touch.pinch_scale();
+/// ```
+///
+/// This handle can be cloned and passed around freely to give objects access to touch input.
+#[derive(Clone)]
+pub struct Touch(Rc<State>);
+
+impl Debug for Touch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Touch").finish_non_exhaustive()
+    }
+}
+
+struct State {
+    points: RefCell<Vec<Tracked>>,
+    config: Cell<GestureConfig>,
+    phases: [Phase; 2],
+    phase: Cell<usize>,
+    last_active: Cell<SystemTime>,
+}
+
+/// A touch point along with what gestures it can still be part of.
+struct Tracked {
+    point: TouchPoint,
+    moved: bool,
+    multi: bool,
+    long_pressed: bool,
+}
+
+#[derive(Default)]
+struct Phase {
+    started: RefCell<Vec<TouchPoint>>,
+    ended: RefCell<Vec<TouchPoint>>,
+    taps: RefCell<Vec<Vec2F>>,
+    long_presses: RefCell<Vec<Vec2F>>,
+    pan_delta: Cell<Vec2F>,
+    panning: Cell<bool>,
+    pinch_scale: Cell<Option<f32>>,
+    pinch_center: Cell<Vec2F>,
+}
+
+impl Touch {
+    pub(crate) fn new() -> Self {
+        Self(Rc::new(State {
+            points: RefCell::new(Vec::new()),
+            config: Cell::new(GestureConfig::default()),
+            phases: std::array::from_fn(|_| Phase::default()),
+            phase: Cell::new(0),
+            last_active: Cell::new(SystemTime::UNIX_EPOCH),
+        }))
+    }
+
+    /// Time the touch state last changed.
+    #[inline]
+    pub fn last_active(&self) -> SystemTime {
+        self.0.last_active.get()
+    }
+
+    #[inline]
+    fn phase(&self) -> &Phase {
+        &self.0.phases[self.0.phase.get()]
+    }
+
+    /// The thresholds used to recognize gestures.
+    #[inline]
+    pub fn gesture_config(&self) -> GestureConfig {
+        self.0.config.get()
+    }
+
+    /// Set the thresholds used to recognize gestures.
+    #[inline]
+    pub fn set_gesture_config(&self, config: GestureConfig) {
+        self.0.config.set(config);
+    }
+
+    /// How many fingers are on the screen.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.0.points.borrow().len()
+    }
+
+    /// If any fingers are on the screen.
+    #[inline]
+    pub fn any_down(&self) -> bool {
+        self.count() > 0
+    }
+
+    /// Every finger on the screen, in the order they touched it.
+    #[inline]
+    pub fn points(&self) -> Vec<TouchPoint> {
+        self.0.points.borrow().iter().map(|t| t.point).collect()
+    }
+
+    /// The finger with the ID, if it is on the screen.
+    #[inline]
+    pub fn point(&self, id: u64) -> Option<TouchPoint> {
+        self.0
+            .points
+            .borrow()
+            .iter()
+            .find(|t| t.point.id == id)
+            .map(|t| t.point)
+    }
+
+    /// Fingers that touched the screen this frame.
+    #[inline]
+    pub fn started(&self) -> Vec<TouchPoint> {
+        self.phase().started.borrow().clone()
+    }
+
+    /// Fingers that left the screen this frame, either [ended](TouchPhase::Ended) or
+    /// [cancelled](TouchPhase::Cancelled).
+    #[inline]
+    pub fn ended(&self) -> Vec<TouchPoint> {
+        self.phase().ended.borrow().clone()
+    }
+
+    /// Where the screen was tapped this frame: touched and released quickly by a single
+    /// finger without moving.
+    #[inline]
+    pub fn taps(&self) -> Vec<Vec2F> {
+        self.phase().taps.borrow().clone()
+    }
+
+    /// Where the screen was first tapped this frame, if it was.
+    #[inline]
+    pub fn tap(&self) -> Option<Vec2F> {
+        self.phase().taps.borrow().first().copied()
+    }
+
+    /// Where a single finger was held still long enough to count as a long-press this frame.
+    /// Each hold only counts once.
+    #[inline]
+    pub fn long_press(&self) -> Option<Vec2F> {
+        self.phase().long_presses.borrow().first().copied()
+    }
+
+    /// If one or more fingers are dragging across the screen this frame.
+    #[inline]
+    pub fn is_panning(&self) -> bool {
+        self.phase().panning.get()
+    }
+
+    /// How far the fingers dragged this frame. With two or more fingers down, this is how far
+    /// the point between the first two moved.
+    #[inline]
+    pub fn pan_delta(&self) -> Vec2F {
+        self.phase().pan_delta.get()
+    }
+
+    /// If two or more fingers are pinching this frame.
+    #[inline]
+    pub fn is_pinching(&self) -> bool {
+        self.phase().pinch_scale.get().is_some()
+    }
+
+    /// How much the first two fingers spread apart this frame, as a scale factor: above `1`
+    /// when zooming in and below `1` when zooming out.
+    #[inline]
+    pub fn pinch_scale(&self) -> f32 {
+        self.phase().pinch_scale.get().unwrap_or(1.0)
+    }
+
+    /// The point between the first two fingers, which a pinch zoom should zoom around.
+    #[inline]
+    pub fn pinch_center(&self) -> Option<Vec2F> {
+        self.is_pinching().then(|| self.phase().pinch_center.get())
+    }
+
+    #[inline]
+    pub(crate) fn set_update_phase(&self) {
+        self.0.phase.set(0);
+    }
+
+    #[inline]
+    pub(crate) fn set_render_phase(&self) {
+        self.0.phase.set(1);
+    }
+
+    pub(crate) fn handle_event(&self, touch: winit::event::Touch, scale_factor: f64) {
+        self.0.last_active.set(SystemTime::now());
+
+        let pos = touch.location.to_logical::<f32>(scale_factor);
+        let pos = vec2(pos.x, pos.y);
+        let force = touch.force.map(|force| force.normalized() as f32);
+        let config = self.gesture_config();
+        let mut points = self.0.points.borrow_mut();
+
+        match TouchPhase::from(touch.phase) {
+            TouchPhase::Started => {
+                let point = TouchPoint {
+                    id: touch.id,
+                    pos,
+                    start_pos: pos,
+                    phase: TouchPhase::Started,
+                    force,
+                    started_at: Instant::now(),
+                };
+                let multi = !points.is_empty();
+                for t in points.iter_mut() {
+                    t.multi = true;
+                }
+                points.push(Tracked {
+                    point,
+                    moved: false,
+                    multi,
+                    long_pressed: false,
+                });
+                for phase in &self.0.phases {
+                    phase.started.borrow_mut().push(point);
+                }
+            }
+            TouchPhase::Moved => {
+                let Some(idx) = points.iter().position(|t| t.point.id == touch.id) else {
+                    return;
+                };
+                let pair_before = pair(&points);
+                let prev = points[idx].point.pos;
+                let t = &mut points[idx];
+                t.point.pos = pos;
+                t.point.phase = TouchPhase::Moved;
+                t.point.force = force;
+                if t.point.offset().len() > config.slop {
+                    t.moved = true;
+                }
+
+                // one finger drags, and two fingers drag and pinch around their center
+                if points.len() == 1 {
+                    if points[0].moved {
+                        for phase in &self.0.phases {
+                            phase.pan_delta.update(|d| d + (pos - prev));
+                            phase.panning.set(true);
+                        }
+                    }
+                } else if idx < 2 {
+                    let (center_before, dist_before) = pair_before;
+                    let (center, dist) = pair(&points);
+                    for phase in &self.0.phases {
+                        phase.pan_delta.update(|d| d + (center - center_before));
+                        phase.panning.set(true);
+                        if dist_before > 0.0 {
+                            let scale = dist / dist_before;
+                            phase.pinch_scale.update(|s| Some(s.unwrap_or(1.0) * scale));
+                            phase.pinch_center.set(center);
+                        }
+                    }
+                }
+            }
+            phase @ (TouchPhase::Ended | TouchPhase::Cancelled) => {
+                let Some(idx) = points.iter().position(|t| t.point.id == touch.id) else {
+                    return;
+                };
+                let mut t = points.remove(idx);
+                t.point.pos = pos;
+                t.point.phase = phase;
+                let tapped = phase == TouchPhase::Ended
+                    && !t.moved
+                    && !t.multi
+                    && !t.long_pressed
+                    && t.point.held().as_secs_f32() <= config.tap_time;
+                for p in &self.0.phases {
+                    p.ended.borrow_mut().push(t.point);
+                    if tapped {
+                        p.taps.borrow_mut().push(pos);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Recognize long-presses, which happen without any touch events.
+    pub(crate) fn update(&self) {
+        let config = self.gesture_config();
+        for t in self.0.points.borrow_mut().iter_mut() {
+            if !t.moved
+                && !t.multi
+                && !t.long_pressed
+                && t.point.held().as_secs_f32() >= config.long_press_time
+            {
+                t.long_pressed = true;
+                for phase in &self.0.phases {
+                    phase.long_presses.borrow_mut().push(t.point.pos);
+                }
+            }
+        }
+    }
+
+    pub(crate) fn clear_phase(&self) {
+        let phase = self.phase();
+        phase.started.borrow_mut().clear();
+        phase.ended.borrow_mut().clear();
+        phase.taps.borrow_mut().clear();
+        phase.long_presses.borrow_mut().clear();
+        phase.pan_delta.set(Vec2F::ZERO);
+        phase.panning.set(false);
+        phase.pinch_scale.set(None);
+
+        // once the frame has been rendered, fingers that are still down are no longer new
+        if self.0.phase.get() == 1 {
+            for t in self.0.points.borrow_mut().iter_mut() {
+                if t.point.phase == TouchPhase::Started {
+                    t.point.phase = TouchPhase::Moved;
+                }
+            }
+        }
+    }
+}
+
+/// The center of the first two fingers and the distance between them.
+#[inline]
+fn pair(points: &[Tracked]) -> (Vec2F, f32) {
+    match points {
+        [a, b, ..] => (
+            (a.point.pos + b.point.pos) * 0.5,
+            (b.point.pos - a.point.pos).len(),
+        ),
+        _ => (Vec2F::ZERO, 0.0),
+    }
+}
@@ -0,0 +1,30 @@
+use winit::event::TouchPhase as Winit;
+
+/// The stage a [touch point](crate::input::TouchPoint) is in.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum TouchPhase {
+    /// The finger touched the screen this frame.
+    Started,
+
+    /// The finger is on the screen.
+    Moved,
+
+    /// The finger was lifted from the screen.
+    Ended,
+
+    /// The touch was interrupted by the system, such as by a system gesture or the window
+    /// losing focus, and should not count as a completed action.
+    Cancelled,
+}
+
+impl From<Winit> for TouchPhase {
+    #[inline]
+    fn from(value: Winit) -> Self {
+        match value {
+            Winit::Started => Self::Started,
+            Winit::Moved => Self::Moved,
+            Winit::Ended => Self::Ended,
+            Winit::Cancelled => Self::Cancelled,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::input::TouchPhase;
+use crate::math::Vec2F;
+use std::time::{Duration, Instant};
+
+/// A finger on the touch screen.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchPoint {
+    /// Identifies the finger for as long as it is on the screen.
+    pub id: u64,
+
+    /// The position in window coordinates.
+    pub pos: Vec2F,
+
+    /// Where the finger first touched the screen.
+    pub start_pos: Vec2F,
+
+    /// The touch's current stage.
+    pub phase: TouchPhase,
+
+    /// How hard the finger is pressing from `0` to `1`, on screens that can tell.
+    pub force: Option<f32>,
+
+    /// When the finger first touched the screen.
+    pub started_at: Instant,
+}
+
+impl TouchPoint {
+    /// How far the finger has moved since it first touched the screen.
+    #[inline]
+    pub fn offset(&self) -> Vec2F {
+        self.pos - self.start_pos
+    }
+
+    /// How long the finger has been on the screen.
+    #[inline]
+    pub fn held(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
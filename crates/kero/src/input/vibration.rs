@@ -0,0 +1,216 @@
+use crate::math::Ease;
+use serde::{Deserialize, Serialize};
+
+/// A single pulse of vibration that ramps up, holds, and fades out, as part of a
+/// [`VibrationPattern`].
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VibrationEnvelope {
+    /// How long after the pattern starts the pulse begins, in seconds.
+    pub delay: f32,
+
+    /// How long the pulse takes to ramp up to full strength, in seconds.
+    pub attack: f32,
+
+    /// How long the pulse holds at full strength, in seconds.
+    pub sustain: f32,
+
+    /// How long the pulse takes to fade out, in seconds.
+    pub release: f32,
+
+    /// Full strength of the strong (low frequency) motor, from `0` to `1`.
+    pub strong: f32,
+
+    /// Full strength of the weak (high frequency) motor, from `0` to `1`.
+    pub weak: f32,
+
+    /// The curve of the ramp up.
+    pub attack_ease: Ease,
+
+    /// The curve of the fade out.
+    pub release_ease: Ease,
+}
+
+impl Default for VibrationEnvelope {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            delay: 0.0,
+            attack: 0.0,
+            sustain: 0.1,
+            release: 0.0,
+            strong: 1.0,
+            weak: 1.0,
+            attack_ease: Ease::Linear,
+            release_ease: Ease::Linear,
+        }
+    }
+}
+
+impl VibrationEnvelope {
+    /// Create a pulse with the motor strengths, held for the duration.
+    #[inline]
+    pub fn new(strong: f32, weak: f32, sustain: f32) -> Self {
+        Self {
+            strong,
+            weak,
+            sustain,
+            ..Default::default()
+        }
+    }
+
+    /// Return the pulse starting later in the pattern.
+    #[inline]
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Return the pulse with a ramp up along the curve.
+    #[inline]
+    pub fn with_attack(mut self, attack: f32, ease: Ease) -> Self {
+        self.attack = attack;
+        self.attack_ease = ease;
+        self
+    }
+
+    /// Return the pulse with a fade out along the curve.
+    #[inline]
+    pub fn with_release(mut self, release: f32, ease: Ease) -> Self {
+        self.release = release;
+        self.release_ease = ease;
+        self
+    }
+
+    /// When the pulse ends, in seconds from the start of the pattern.
+    #[inline]
+    pub fn end(&self) -> f32 {
+        self.delay + self.attack.max(0.0) + self.sustain.max(0.0) + self.release.max(0.0)
+    }
+
+    /// How strong the pulse is `time` seconds into the pattern, from `0` to `1`.
+    pub fn level(&self, time: f32) -> f32 {
+        let t = time - self.delay;
+        if t < 0.0 || t >= self.end() - self.delay {
+            return 0.0;
+        }
+        if t < self.attack {
+            return self.attack_ease.apply(t / self.attack);
+        }
+        let t = t - self.attack.max(0.0) - self.sustain.max(0.0);
+        if t < 0.0 {
+            return 1.0;
+        }
+        1.0 - self.release_ease.apply(t / self.release)
+    }
+}
+
+/// A vibration made of pulses that play at set times, for rumble with more character than a
+/// flat buzz. Play one on a gamepad with [`Gamepad::vibrate`](crate::input::Gamepad::vibrate).
+///
+/// Patterns can be built in code, loaded from data, or taken from the [presets](Self::preset).
+///
+/// ```ignore
+/// pad.vibrate(&VibrationPattern::small_hit());
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct VibrationPattern {
+    /// The pulses in the pattern. Where they overlap, the strongest wins.
+    pub envelopes: Vec<VibrationEnvelope>,
+
+    /// If the pattern starts over once it ends, until it is stopped.
+    pub looping: bool,
+
+    /// How long the pattern is before it ends or loops, in seconds. If `0`, it lasts until the
+    /// last pulse ends.
+    pub length: f32,
+}
+
+impl VibrationPattern {
+    /// Create a pattern from pulses.
+    #[inline]
+    pub fn new(envelopes: impl IntoIterator<Item = VibrationEnvelope>) -> Self {
+        Self {
+            envelopes: envelopes.into_iter().collect(),
+            looping: false,
+            length: 0.0,
+        }
+    }
+
+    /// Return the pattern with a pulse added.
+    #[inline]
+    pub fn with_envelope(mut self, envelope: VibrationEnvelope) -> Self {
+        self.envelopes.push(envelope);
+        self
+    }
+
+    /// Return the pattern looping every `length` seconds, or when its last pulse ends if `0`.
+    #[inline]
+    pub fn with_looping(mut self, length: f32) -> Self {
+        self.looping = true;
+        self.length = length;
+        self
+    }
+
+    /// How long the pattern is before it ends or loops, in seconds.
+    pub fn duration(&self) -> f32 {
+        match self.length > 0.0 {
+            true => self.length,
+            false => self.envelopes.iter().map(|e| e.end()).fold(0.0, f32::max),
+        }
+    }
+
+    /// The strength of the strong and weak motors `time` seconds into the pattern.
+    pub fn sample(&self, time: f32) -> (f32, f32) {
+        self.envelopes.iter().fold((0.0, 0.0), |(strong, weak), e| {
+            let level = e.level(time);
+            (strong.max(e.strong * level), weak.max(e.weak * level))
+        })
+    }
+
+    /// A short, sharp tap, such as for taking a light hit.
+    pub fn small_hit() -> Self {
+        Self::new([VibrationEnvelope::new(0.3, 0.6, 0.05).with_release(0.08, Ease::QuadOut)])
+    }
+
+    /// A heavy jolt that rumbles on and fades away, such as for an explosion nearby.
+    pub fn big_explosion() -> Self {
+        Self::new([
+            VibrationEnvelope::new(1.0, 1.0, 0.15).with_release(0.2, Ease::QuadOut),
+            VibrationEnvelope::new(0.7, 0.2, 0.2)
+                .with_delay(0.1)
+                .with_release(0.8, Ease::CubicIn),
+        ])
+    }
+
+    /// A double thump that repeats about once a second until stopped, such as for low health.
+    pub fn heartbeat() -> Self {
+        Self::new([
+            VibrationEnvelope::new(0.6, 0.0, 0.06)
+                .with_attack(0.02, Ease::Linear)
+                .with_release(0.1, Ease::QuadOut),
+            VibrationEnvelope::new(0.4, 0.0, 0.05)
+                .with_delay(0.25)
+                .with_attack(0.02, Ease::Linear)
+                .with_release(0.1, Ease::QuadOut),
+        ])
+        .with_looping(1.0)
+    }
+
+    /// A gentle buzz, such as for selecting a menu item.
+    pub fn tick() -> Self {
+        Self::new([VibrationEnvelope::new(0.0, 0.25, 0.03)])
+    }
+
+    /// A preset by name: `"small_hit"`, `"big_explosion"`, `"heartbeat"`, or `"tick"`.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name {
+            "small_hit" => Some(Self::small_hit()),
+            "big_explosion" => Some(Self::big_explosion()),
+            "heartbeat" => Some(Self::heartbeat()),
+            "tick" => Some(Self::tick()),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::input::VibrationPattern;
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Handle to a [`VibrationPattern`] playing on a gamepad.
+///
+/// The pattern keeps playing if the handle is dropped. Keep it around to stop it early or to
+/// change its intensity, such as for a heartbeat that quickens as health drops.
+#[derive(Clone)]
+pub struct VibrationPlayback(pub(crate) Rc<VibrationPlaybackState>);
+
+pub(crate) struct VibrationPlaybackState {
+    pattern: VibrationPattern,
+    duration: f32,
+    time: Cell<f32>,
+    intensity: Cell<f32>,
+    speed: Cell<f32>,
+    playing: Cell<bool>,
+}
+
+impl Debug for VibrationPlayback {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("VibrationPlayback").finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for VibrationPlayback {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl VibrationPlayback {
+    pub(crate) fn new(pattern: VibrationPattern, intensity: f32) -> Self {
+        let duration = pattern.duration();
+        Self(Rc::new(VibrationPlaybackState {
+            pattern,
+            duration,
+            time: Cell::new(0.0),
+            intensity: Cell::new(intensity.clamp(0.0, 1.0)),
+            speed: Cell::new(1.0),
+            playing: Cell::new(true),
+        }))
+    }
+
+    /// The pattern being played.
+    #[inline]
+    pub fn pattern(&self) -> &VibrationPattern {
+        &self.0.pattern
+    }
+
+    /// If the pattern is still playing.
+    #[inline]
+    pub fn is_playing(&self) -> bool {
+        self.0.playing.get()
+    }
+
+    /// Stop the pattern.
+    #[inline]
+    pub fn stop(&self) {
+        self.0.playing.set(false);
+    }
+
+    /// How far into the pattern it is, in seconds.
+    #[inline]
+    pub fn position(&self) -> f32 {
+        self.0.time.get()
+    }
+
+    /// How strongly the pattern plays, from `0` to `1`.
+    #[inline]
+    pub fn intensity(&self) -> f32 {
+        self.0.intensity.get()
+    }
+
+    /// Set how strongly the pattern plays, from `0` to `1`.
+    #[inline]
+    pub fn set_intensity(&self, intensity: f32) {
+        self.0.intensity.set(intensity.clamp(0.0, 1.0));
+    }
+
+    /// How fast the pattern plays, where `1` is normal speed.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.0.speed.get()
+    }
+
+    /// Set how fast the pattern plays.
+    #[inline]
+    pub fn set_speed(&self, speed: f32) {
+        self.0.speed.set(speed.max(0.0));
+    }
+
+    /// Advance the pattern and return the strength of its strong and weak motors, stopping
+    /// it once it ends.
+    pub(crate) fn advance(&self, dt: f32) -> (f32, f32) {
+        if !self.is_playing() {
+            return (0.0, 0.0);
+        }
+        let s = &self.0;
+        let mut time = s.time.get() + dt * s.speed.get();
+        if time >= s.duration {
+            if !s.pattern.looping || s.duration <= 0.0 {
+                self.stop();
+                return (0.0, 0.0);
+            }
+            time %= s.duration;
+        }
+        s.time.set(time);
+        let (strong, weak) = s.pattern.sample(time);
+        let intensity = s.intensity.get();
+        (strong * intensity, weak * intensity)
+    }
+}
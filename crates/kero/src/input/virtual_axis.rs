@@ -1,6 +1,8 @@
 use super::VirtualButton;
-use crate::input::GamepadAxis;
 use crate::input::virtual_source::VirtualSource;
+use crate::input::{AxisBinding, GamepadAxis};
+#[cfg(feature = "steam")]
+use crate::steam::SteamAnalogAction;
 use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
@@ -8,8 +10,9 @@ use std::rc::Rc;
 /// Handle to a virtual axis.
 ///
 /// This can be used to simultaneously listen to the state of a gamepad axis, but also to
-/// treat a pair of buttons as negative and positive inputs for that axis. This handle can be
-/// cloned and passed around to give objects access to it.
+/// treat a pair of buttons as negative and positive inputs for that axis. With the `steam`
+/// feature, it can also listen to one direction of a [Steam Input action](Self::set_steam_x).
+/// This handle can be cloned and passed around to give objects access to it.
 #[derive(Clone)]
 pub struct VirtualAxis(Rc<Inner>);
 
@@ -32,6 +35,10 @@ struct Inner {
     axis: Cell<Option<GamepadAxis>>,
     neg: RefCell<VirtualButton>,
     pos: RefCell<VirtualButton>,
+    #[cfg(feature = "steam")]
+    steam: RefCell<Option<SteamAnalogAction>>,
+    #[cfg(feature = "steam")]
+    steam_y: Cell<bool>,
 }
 
 impl VirtualAxis {
@@ -58,6 +65,10 @@ impl VirtualAxis {
             axis: Cell::new(axis.into()),
             neg: RefCell::new(neg),
             pos: RefCell::new(pos),
+            #[cfg(feature = "steam")]
+            steam: RefCell::new(None),
+            #[cfg(feature = "steam")]
+            steam_y: Cell::new(false),
         }))
     }
 
@@ -90,6 +101,60 @@ impl VirtualAxis {
         *self.0.pos.borrow_mut() = pos.into();
     }
 
+    /// The Steam Input action this input listens to.
+    #[cfg(feature = "steam")]
+    #[inline]
+    pub fn steam_action(&self) -> Option<SteamAnalogAction> {
+        self.0.steam.borrow().clone()
+    }
+
+    /// Listen to the horizontal value of a Steam Input action, such as `move`, as well as the
+    /// gamepad axis and buttons.
+    #[cfg(feature = "steam")]
+    pub fn set_steam_x(&self, action: impl Into<Option<SteamAnalogAction>>) {
+        self.0.steam.replace(action.into());
+        self.0.steam_y.set(false);
+    }
+
+    /// Listen to the vertical value of a Steam Input action, such as `move`, as well as the
+    /// gamepad axis and buttons. Steam treats up as positive, so the value is flipped to match
+    /// gamepad axes, where down is positive.
+    #[cfg(feature = "steam")]
+    pub fn set_steam_y(&self, action: impl Into<Option<SteamAnalogAction>>) {
+        self.0.steam.replace(action.into());
+        self.0.steam_y.set(true);
+    }
+
+    /// The Steam Input action's value along this axis, and if it changed this frame.
+    #[inline]
+    fn steam_state(&self) -> Option<(f32, bool)> {
+        #[cfg(feature = "steam")]
+        if let Some(action) = self.0.steam.borrow().as_ref() {
+            let value = match self.0.steam_y.get() {
+                true => -action.y(),
+                false => action.x(),
+            };
+            return Some((value, action.changed()));
+        }
+        None
+    }
+
+    /// The gamepad axis and buttons this input listens to.
+    pub fn binding(&self) -> AxisBinding {
+        AxisBinding {
+            axis: self.axis(),
+            neg: self.0.neg.borrow().binding(),
+            pos: self.0.pos.borrow().binding(),
+        }
+    }
+
+    /// Set the gamepad axis and rebind the negative and positive buttons.
+    pub fn set_binding(&self, binding: AxisBinding) {
+        self.set_axis(binding.axis);
+        self.0.neg.borrow().set_binding(binding.neg);
+        self.0.pos.borrow().set_binding(binding.pos);
+    }
+
     /// If the axis state changed this frame.
     #[inline]
     pub fn changed(&self) -> bool {
@@ -100,6 +165,7 @@ impl VirtualAxis {
             .unwrap_or(false)
             || self.0.neg.borrow().changed()
             || self.0.pos.borrow().changed()
+            || self.steam_state().is_some_and(|(_, changed)| changed)
     }
 
     /// The axis value from `-1.0` to `1.0`.
@@ -111,6 +177,7 @@ impl VirtualAxis {
         }
         value -= self.0.neg.borrow().value();
         value += self.0.pos.borrow().value();
+        value += self.steam_state().map_or(0.0, |(steam, _)| steam);
         value.clamp(-1.0, 1.0)
     }
 }
@@ -0,0 +1,93 @@
+use crate::input::{GamepadAxis, GamepadButton, Key};
+use crate::storage::{StorageError, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// The key and gamepad button a [`VirtualButton`](crate::input::VirtualButton) listens to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ButtonBinding {
+    pub key: Option<Key>,
+    pub button: Option<GamepadButton>,
+}
+
+impl ButtonBinding {
+    /// Create a binding to the key and gamepad button.
+    #[inline]
+    pub fn new(key: impl Into<Option<Key>>, button: impl Into<Option<GamepadButton>>) -> Self {
+        Self {
+            key: key.into(),
+            button: button.into(),
+        }
+    }
+}
+
+/// The gamepad axis and buttons a [`VirtualAxis`](crate::input::VirtualAxis) listens to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AxisBinding {
+    pub axis: Option<GamepadAxis>,
+    pub neg: ButtonBinding,
+    pub pos: ButtonBinding,
+}
+
+/// The axes a [`VirtualStick`](crate::input::VirtualStick) listens to.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct StickBinding {
+    pub x_axis: AxisBinding,
+    pub y_axis: AxisBinding,
+}
+
+/// Every binding of a [`VirtualController`](crate::input::VirtualController), which can be
+/// saved to disk so players' remapped controls are remembered between runs.
+///
+/// ```ignore
+/// // after the player has remapped their controls
+/// controller.bindings().save(ctx.config_dir().join("controls.json"))?;
+///
+/// // at startup
+/// if let Ok(bindings) = ControllerBindings::load(ctx.config_dir().join("controls.json")) {
+///     controller.set_bindings(&bindings);
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControllerBindings {
+    pub direction: StickBinding,
+    pub left_stick: StickBinding,
+    pub right_stick: StickBinding,
+    pub left_bumper: ButtonBinding,
+    pub right_bumper: ButtonBinding,
+    pub left_trigger: ButtonBinding,
+    pub right_trigger: ButtonBinding,
+    pub dpad_left: ButtonBinding,
+    pub dpad_right: ButtonBinding,
+    pub dpad_up: ButtonBinding,
+    pub dpad_down: ButtonBinding,
+    pub east: ButtonBinding,
+    pub south: ButtonBinding,
+    pub west: ButtonBinding,
+    pub north: ButtonBinding,
+    pub start: ButtonBinding,
+    pub select: ButtonBinding,
+    pub menu: ButtonBinding,
+}
+
+impl ControllerBindings {
+    /// Load bindings from a JSON file.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let bytes = std::fs::read(path)?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    /// Save the bindings to a JSON file, creating its folder if needed.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), StorageError> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let bytes = serde_json::to_vec_pretty(self)?;
+        Ok(write_atomic(path, &bytes)?)
+    }
+}
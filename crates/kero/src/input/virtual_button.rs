@@ -1,13 +1,17 @@
 use crate::input::virtual_source::VirtualSource;
-use crate::input::{GamepadButton, Key, Keyboard};
-use std::cell::Cell;
+use crate::input::{ButtonBinding, GamepadButton, Key, Keyboard};
+#[cfg(feature = "steam")]
+use crate::steam::SteamDigitalAction;
+use std::cell::{Cell, RefCell};
 use std::cmp::Ordering;
 use std::rc::Rc;
+use strum::VariantArray;
 
 /// Handle to a virtual button.
 ///
 /// This can be used to simultaneously listen to the state of a key and gamepad button, and
-/// can be cloned and passed around to give objects access to it.
+/// can be cloned and passed around to give objects access to it. With the `steam` feature, it
+/// can also listen to a [Steam Input action](Self::set_steam_action).
 #[derive(Clone)]
 pub struct VirtualButton(Rc<Inner>);
 
@@ -29,6 +33,17 @@ struct Inner {
     source: VirtualSource,
     btn: Cell<Option<GamepadButton>>,
     key: Cell<Option<Key>>,
+    rebind: RefCell<Option<Rebind>>,
+    #[cfg(feature = "steam")]
+    steam: RefCell<Option<SteamDigitalAction>>,
+}
+
+/// Inputs that were already down when listening for a rebind started, which are ignored until
+/// they are released so the press that opened the rebind prompt is not captured.
+#[derive(Default)]
+struct Rebind {
+    keys: Vec<Key>,
+    buttons: Vec<GamepadButton>,
 }
 
 impl VirtualButton {
@@ -47,6 +62,9 @@ impl VirtualButton {
             source: source.clone(),
             btn: Cell::new(btn.into()),
             key: Cell::new(key.into()),
+            rebind: RefCell::new(None),
+            #[cfg(feature = "steam")]
+            steam: RefCell::new(None),
         }))
     }
 
@@ -55,6 +73,18 @@ impl VirtualButton {
         self.0.source.keyboard()
     }
 
+    /// The gamepad button this input listens to.
+    #[inline]
+    pub fn button(&self) -> Option<GamepadButton> {
+        self.0.btn.get()
+    }
+
+    /// The key this input listens to.
+    #[inline]
+    pub fn key(&self) -> Option<Key> {
+        self.0.key.get()
+    }
+
     /// Set the gamepad button to listen to.
     #[inline]
     pub fn set_button(&self, btn: impl Into<Option<GamepadButton>>) {
@@ -67,6 +97,122 @@ impl VirtualButton {
         self.0.key.set(key.into());
     }
 
+    /// The Steam Input action this input listens to.
+    #[cfg(feature = "steam")]
+    #[inline]
+    pub fn steam_action(&self) -> Option<SteamDigitalAction> {
+        self.0.steam.borrow().clone()
+    }
+
+    /// Listen to a Steam Input action as well as the key and gamepad button, so players can
+    /// rebind it through Steam.
+    ///
+    /// ```ignore
+    /// controller.south.set_steam_action(steam.input().digital_action("jump")?);
+    /// ```
+    #[cfg(feature = "steam")]
+    #[inline]
+    pub fn set_steam_action(&self, action: impl Into<Option<SteamDigitalAction>>) {
+        self.0.steam.replace(action.into());
+    }
+
+    /// If the Steam Input action was pressed and released this frame, and if it is down.
+    #[inline]
+    fn steam_state(&self) -> Option<(bool, bool, bool)> {
+        #[cfg(feature = "steam")]
+        if let Some(action) = self.0.steam.borrow().as_ref() {
+            return Some((action.pressed(), action.released(), action.down()));
+        }
+        None
+    }
+
+    /// The key and gamepad button this input listens to.
+    #[inline]
+    pub fn binding(&self) -> ButtonBinding {
+        ButtonBinding::new(self.key(), self.button())
+    }
+
+    /// Set the key and gamepad button to listen to.
+    #[inline]
+    pub fn set_binding(&self, binding: ButtonBinding) {
+        self.set_key(binding.key);
+        self.set_button(binding.button);
+    }
+
+    /// Start or stop listening for a new binding. While listening, call
+    /// [`poll_rebind`](Self::poll_rebind) every frame to capture the next key or gamepad button
+    /// the player presses.
+    ///
+    /// ```ignore
+    /// if menu.confirm.pressed() {
+    ///     jump.rebind(true);
+    /// }
+    /// if jump.is_listening() {
+    ///     if ctx.keyboard.pressed(Key::Escape) {
+    ///         jump.rebind(false);
+    ///     } else if jump.poll_rebind() {
+    ///         save_controls();
+    ///     }
+    /// }
+    /// ```
+    pub fn rebind(&self, listening: bool) {
+        let rebind = listening.then(|| Rebind {
+            keys: self.keyboard().currently_down().collect(),
+            buttons: self
+                .0
+                .source
+                .read(|pad| {
+                    GamepadButton::VARIANTS
+                        .iter()
+                        .copied()
+                        .filter(|&btn| pad.down(btn))
+                        .collect()
+                })
+                .unwrap_or_default(),
+        });
+        self.0.rebind.replace(rebind);
+    }
+
+    /// If the button is listening for a new binding.
+    #[inline]
+    pub fn is_listening(&self) -> bool {
+        self.0.rebind.borrow().is_some()
+    }
+
+    /// If listening for a new binding, check if a key or gamepad button was pressed this frame.
+    /// A key replaces the button's key and a gamepad button replaces its gamepad button, after
+    /// which it stops listening and this returns `true`.
+    pub fn poll_rebind(&self) -> bool {
+        let mut rebind = self.0.rebind.borrow_mut();
+        let Some(held) = rebind.as_mut() else {
+            return false;
+        };
+        let keyboard = self.keyboard();
+        held.keys.retain(|&key| keyboard.down(key));
+        let key = Key::VARIANTS
+            .iter()
+            .copied()
+            .find(|&key| keyboard.pressed(key) && !held.keys.contains(&key));
+        if let Some(key) = key {
+            self.0.key.set(Some(key));
+            *rebind = None;
+            return true;
+        }
+        let btn = self.0.source.read(|pad| {
+            held.buttons.retain(|&btn| pad.down(btn));
+            GamepadButton::VARIANTS
+                .iter()
+                .copied()
+                .find(|&btn| pad.pressed(btn) && !held.buttons.contains(&btn))
+        });
+        if let Some(btn) = btn.flatten() {
+            self.0.btn.set(Some(btn));
+            *rebind = None;
+            return true;
+        }
+        false
+    }
+
     /// If the button's key or gamepad button is down.
     #[inline]
     pub fn down(&self) -> bool {
@@ -79,6 +225,7 @@ impl VirtualButton {
                 .key
                 .get()
                 .is_some_and(|key| self.keyboard().down(key))
+            || self.steam_state().is_some_and(|(_, _, down)| down)
     }
 
     /// If the button's key or gamepad button was pressed this frame.
@@ -106,10 +253,17 @@ impl VirtualButton {
                 return false;
             }
         }
+        if let Some((press, _, down)) = self.steam_state() {
+            if press {
+                pressed = true;
+            } else if down {
+                return false;
+            }
+        }
         pressed
     }
 
-    /// If the button's key or gamepad button was pressed this frame.
+    /// If the button's key or gamepad button was released this frame.
     ///
     /// This treats the mappings as if they were one button, meaning if the gamepad button is
     /// released but the key is still held down, this will not return true until the key is also
@@ -123,7 +277,7 @@ impl VirtualButton {
             {
                 if release {
                     released = true;
-                } else if !down {
+                } else if down {
                     return false;
                 }
             }
@@ -131,7 +285,14 @@ impl VirtualButton {
         if let Some(key) = self.0.key.get() {
             if self.keyboard().released(key) {
                 released = true;
-            } else if !self.keyboard().down(key) {
+            } else if self.keyboard().down(key) {
+                return false;
+            }
+        }
+        if let Some((_, release, down)) = self.steam_state() {
+            if release {
+                released = true;
+            } else if down {
                 return false;
             }
         }
@@ -151,6 +312,9 @@ impl VirtualButton {
             .key
             .get()
             .is_some_and(|key| self.keyboard().pressed(key) || self.keyboard().released(key))
+            || self
+                .steam_state()
+                .is_some_and(|(press, release, _)| press || release)
     }
 
     /// Value of the button.
@@ -161,6 +325,7 @@ impl VirtualButton {
             .key
             .get()
             .is_some_and(|key| self.keyboard().down(key))
+            || self.steam_state().is_some_and(|(_, _, down)| down)
         {
             return 1.0;
         }
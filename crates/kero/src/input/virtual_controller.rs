@@ -1,7 +1,8 @@
 use crate::core::Context;
 use crate::input::virtual_source::VirtualSource;
 use crate::input::{
-    Gamepad, GamepadAxis, GamepadButton, Key, VirtualAxis, VirtualButton, VirtualStick,
+    ControllerBindings, Gamepad, GamepadAxis, GamepadButton, Key, VirtualAxis, VirtualButton,
+    VirtualStick,
 };
 use std::fmt::{Debug, Formatter};
 
@@ -121,6 +122,83 @@ impl VirtualController {
         }
     }
 
+    /// The controller's buttons paired with their names, such as `"south"`, for listing them
+    /// on a remapping screen.
+    pub fn buttons(&self) -> [(&'static str, &VirtualButton); 15] {
+        [
+            ("left_bumper", &self.left_bumper),
+            ("right_bumper", &self.right_bumper),
+            ("left_trigger", &self.left_trigger),
+            ("right_trigger", &self.right_trigger),
+            ("dpad_left", &self.dpad_left),
+            ("dpad_right", &self.dpad_right),
+            ("dpad_up", &self.dpad_up),
+            ("dpad_down", &self.dpad_down),
+            ("east", &self.east),
+            ("south", &self.south),
+            ("west", &self.west),
+            ("north", &self.north),
+            ("start", &self.start),
+            ("select", &self.select),
+            ("menu", &self.menu),
+        ]
+    }
+
+    /// If any of the controller's buttons are listening for a new binding.
+    pub fn is_listening(&self) -> bool {
+        self.buttons().iter().any(|(_, btn)| btn.is_listening())
+    }
+
+    /// All of the controller's bindings, which can be [saved](ControllerBindings::save).
+    pub fn bindings(&self) -> ControllerBindings {
+        ControllerBindings {
+            direction: self.direction.binding(),
+            left_stick: self.left_stick.binding(),
+            right_stick: self.right_stick.binding(),
+            left_bumper: self.left_bumper.binding(),
+            right_bumper: self.right_bumper.binding(),
+            left_trigger: self.left_trigger.binding(),
+            right_trigger: self.right_trigger.binding(),
+            dpad_left: self.dpad_left.binding(),
+            dpad_right: self.dpad_right.binding(),
+            dpad_up: self.dpad_up.binding(),
+            dpad_down: self.dpad_down.binding(),
+            east: self.east.binding(),
+            south: self.south.binding(),
+            west: self.west.binding(),
+            north: self.north.binding(),
+            start: self.start.binding(),
+            select: self.select.binding(),
+            menu: self.menu.binding(),
+        }
+    }
+
+    /// Replace all of the controller's bindings, such as with ones
+    /// [loaded](ControllerBindings::load) from disk.
+    ///
+    /// The d-pad buttons are shared with the [direction](Self::direction) stick, so they are set
+    /// after it and take priority if the two disagree.
+    pub fn set_bindings(&self, bindings: &ControllerBindings) {
+        self.direction.set_binding(bindings.direction);
+        self.left_stick.set_binding(bindings.left_stick);
+        self.right_stick.set_binding(bindings.right_stick);
+        self.left_bumper.set_binding(bindings.left_bumper);
+        self.right_bumper.set_binding(bindings.right_bumper);
+        self.left_trigger.set_binding(bindings.left_trigger);
+        self.right_trigger.set_binding(bindings.right_trigger);
+        self.dpad_left.set_binding(bindings.dpad_left);
+        self.dpad_right.set_binding(bindings.dpad_right);
+        self.dpad_up.set_binding(bindings.dpad_up);
+        self.dpad_down.set_binding(bindings.dpad_down);
+        self.east.set_binding(bindings.east);
+        self.south.set_binding(bindings.south);
+        self.west.set_binding(bindings.west);
+        self.north.set_binding(bindings.north);
+        self.start.set_binding(bindings.start);
+        self.select.set_binding(bindings.select);
+        self.menu.set_binding(bindings.menu);
+    }
+
     /// Map the bumpers to the left and right <kbd>Shift</kbd> buttons.
     pub fn set_bumpers_shift(&self) {
         self.left_bumper.set_key(Key::ShiftLeft);
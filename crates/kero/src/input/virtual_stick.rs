@@ -1,6 +1,8 @@
-use super::VirtualAxis;
+use super::{StickBinding, VirtualAxis};
 use crate::input::virtual_source::VirtualSource;
 use crate::math::{Vec2, vec2};
+#[cfg(feature = "steam")]
+use crate::steam::SteamAnalogAction;
 use std::cell::RefCell;
 use std::cmp::Ordering;
 use std::fmt::{Debug, Formatter};
@@ -77,6 +79,33 @@ impl VirtualStick {
         *self.0.y_axis.borrow_mut() = y_axis;
     }
 
+    /// Listen to a Steam Input action, such as `move`, as well as the axes' gamepad axes and
+    /// buttons.
+    ///
+    /// ```ignore
+    /// controller.left_stick.set_steam_action(steam.input().analog_action("move")?);
+    /// ```
+    #[cfg(feature = "steam")]
+    pub fn set_steam_action(&self, action: impl Into<Option<SteamAnalogAction>>) {
+        let action = action.into();
+        self.0.x_axis.borrow().set_steam_x(action.clone());
+        self.0.y_axis.borrow().set_steam_y(action);
+    }
+
+    /// The axes this input listens to.
+    pub fn binding(&self) -> StickBinding {
+        StickBinding {
+            x_axis: self.0.x_axis.borrow().binding(),
+            y_axis: self.0.y_axis.borrow().binding(),
+        }
+    }
+
+    /// Rebind the stick's axes.
+    pub fn set_binding(&self, binding: StickBinding) {
+        self.0.x_axis.borrow().set_binding(binding.x_axis);
+        self.0.y_axis.borrow().set_binding(binding.y_axis);
+    }
+
     /// If either axes changed this frame.
     #[inline]
     pub fn changed(&self) -> bool {
@@ -15,6 +15,7 @@
 //!
 //! - 🖥️ a window, game loop, and rendering context out of the box and ready to go
 //! - 🎮 mouse, keyboard, and gamepad input as well as virtual input mapping
+//! - 🔊 sound loading, playback, and mixing
 //! - 🖼️ shaders, surfaces, textures, and other graphics resources
 //! - 🖌️ a straightforward but powerful canvas-style drawing API
 //! - 🧮 various math types for vectors, matrices, rotations, etc.
@@ -93,11 +94,19 @@
 //! in having more contributors. It would be great if this could be polished up, stabilized, and turned
 //! into a reliable game development tool for the Rust ecosystem.
 
+pub mod ai;
+pub mod audio;
 pub mod core;
 pub mod gfx;
 pub mod input;
 pub mod misc;
 mod new_game;
+pub mod particles;
+pub mod storage;
+pub mod telemetry;
+pub mod tiles;
+pub mod tween;
+pub mod ui;
 
 #[cfg(feature = "lua")]
 pub use fey_lua as lua;
@@ -105,6 +114,9 @@ pub use fey_lua as lua;
 #[cfg(feature = "lua")]
 pub mod lua_modules;
 
+#[cfg(feature = "steam")]
+pub mod steam;
+
 #[doc(inline)]
 pub use fey_color as color;
 
@@ -123,10 +135,16 @@ pub use fey_math as math;
 #[doc(inline)]
 pub use fey_rand as rand;
 
+#[cfg(feature = "svg")]
+#[doc(inline)]
+pub use fey_svg as svg;
+
 pub use new_game::new_game;
 
 ///! Include all types and traits.
 pub mod prelude {
+    pub use crate::ai::*;
+    pub use crate::audio::*;
     pub use crate::color::*;
     pub use crate::core::*;
     pub use crate::gfx::*;
@@ -136,8 +154,19 @@ pub mod prelude {
     pub use crate::input::*;
     pub use crate::math::*;
     pub use crate::misc::*;
+    pub use crate::particles::*;
     pub use crate::rand::*;
+    pub use crate::storage::*;
+    #[cfg(feature = "svg")]
+    pub use crate::svg::*;
+    pub use crate::telemetry::*;
+    pub use crate::tiles::*;
+    pub use crate::tween::*;
+    pub use crate::ui::*;
 
     #[cfg(feature = "lua")]
     pub use crate::lua::*;
+
+    #[cfg(feature = "steam")]
+    pub use crate::steam::*;
 }
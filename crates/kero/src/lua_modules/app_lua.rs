@@ -29,6 +29,15 @@ impl UserData for AppModule {
         methods.add_function("restart_requested", |lua, _: ()| {
             Ok(Context::from_lua(lua).reload_lua_requested())
         });
+        methods.add_function("refresh_rate", |lua, _: ()| {
+            Ok(Context::from_lua(lua).refresh_rate())
+        });
+        methods.add_function("refresh_rate_changed", |lua, _: ()| {
+            Ok(Context::from_lua(lua).refresh_rate_changed())
+        });
+        methods.add_function("video_mode", |lua, _: ()| {
+            Ok(Context::from_lua(lua).video_mode())
+        });
         methods.add_function("cache_dir", |lua, _: ()| {
             Context::from_lua(lua).cache_dir().into_lua(lua)
         });
@@ -47,5 +56,23 @@ impl UserData for AppModule {
         methods.add_function("preferences_dir", |lua, _: ()| {
             Context::from_lua(lua).preferences_dir().into_lua(lua)
         });
+        methods.add_function("build_info", |lua, _: ()| {
+            let ctx = Context::from_lua(lua);
+            let info = &ctx.build;
+            let t = lua.create_table()?;
+            t.set("name", info.name.as_str())?;
+            t.set("version", info.version.as_str())?;
+            t.set("git_hash", info.git_hash.as_deref())?;
+            t.set("build_date", info.build_date.as_deref())?;
+            t.set("profile", info.profile.as_str())?;
+            t.set("target", info.target.as_str())?;
+            t.set("kero_version", info.kero_version.as_str())?;
+            t.set("label", info.to_string())?;
+            Ok(t)
+        });
+        methods.add_function("capture_report", |lua, description: String| {
+            let path = Context::from_lua(lua).debug.capture_report(description);
+            path.as_path().into_lua(lua)
+        });
     }
 }
@@ -0,0 +1,85 @@
+use crate::core::Context;
+use crate::gfx::{FontRef, TextureRef};
+use crate::lua::LuaModule;
+use crate::math::RectF;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct AtlasDebugModule;
+
+impl LuaModule for AtlasDebugModule {
+    const PATH: &'static str = "AtlasDebug";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for AtlasDebugModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("enabled", |lua, _: ()| {
+            Ok(Context::from_lua(lua).graphics.atlas_debug().enabled())
+        });
+        methods.add_function("set_enabled", |lua, enabled: bool| {
+            Context::from_lua(lua)
+                .graphics
+                .atlas_debug()
+                .set_enabled(enabled);
+            Ok(())
+        });
+        methods.add_function("toggle", |lua, _: ()| {
+            Context::from_lua(lua).graphics.atlas_debug().toggle();
+            Ok(())
+        });
+        methods.add_function("set_font", |lua, font: Option<FontRef>| {
+            Context::from_lua(lua)
+                .graphics
+                .atlas_debug()
+                .set_font(font.map(|font| font.clone()));
+            Ok(())
+        });
+        methods.add_function(
+            "add_page",
+            |lua, (name, texture, rects): (BorrowedStr, TextureRef, Table)| {
+                let rects = rects
+                    .pairs::<String, RectF>()
+                    .collect::<LuaResult<Vec<_>>>()?;
+                Context::from_lua(lua).graphics.atlas_debug().add_page(
+                    name.as_ref(),
+                    texture.clone(),
+                    rects,
+                );
+                Ok(())
+            },
+        );
+        methods.add_function("remove_page", |lua, name: BorrowedStr| {
+            Context::from_lua(lua)
+                .graphics
+                .atlas_debug()
+                .remove_page(name.as_ref());
+            Ok(())
+        });
+        methods.add_function("clear", |lua, _: ()| {
+            Context::from_lua(lua).graphics.atlas_debug().clear();
+            Ok(())
+        });
+        methods.add_function("num_pages", |lua, _: ()| {
+            Ok(Context::from_lua(lua).graphics.atlas_debug().pages().len())
+        });
+        methods.add_function("page", |lua, _: ()| {
+            Ok(Context::from_lua(lua).graphics.atlas_debug().page())
+        });
+        methods.add_function("set_page", |lua, page: usize| {
+            Context::from_lua(lua).graphics.atlas_debug().set_page(page);
+            Ok(())
+        });
+        methods.add_function("next_page", |lua, _: ()| {
+            Context::from_lua(lua).graphics.atlas_debug().next_page();
+            Ok(())
+        });
+        methods.add_function("prev_page", |lua, _: ()| {
+            Context::from_lua(lua).graphics.atlas_debug().prev_page();
+            Ok(())
+        });
+    }
+}
@@ -0,0 +1,40 @@
+use crate::audio::AudioChannel;
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, Integer, IntoLua, Lua, Value};
+
+pub struct AudioChannelModule;
+
+impl LuaModule for AudioChannelModule {
+    const PATH: &'static str = "AudioChannel";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+        m.set("SFX", AudioChannel::Sfx)?;
+        m.set("MUSIC", AudioChannel::Music)?;
+        m.set("AMBIENCE", AudioChannel::Ambience)?;
+        m.set("VOICE", AudioChannel::Voice)?;
+        m.set("UI", AudioChannel::Ui)?;
+        Ok(Value::Table(m))
+    }
+}
+
+impl FromLua for AudioChannel {
+    #[inline]
+    fn from_lua(value: Value, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::Integer(channel) => AudioChannel::from_repr(channel as usize)
+                .ok_or_else(|| LuaError::runtime(format!("invalid audio channel [{channel}]"))),
+            value => Err(LuaError::runtime(format!(
+                "invalid audio channel [{value:?}]"
+            ))),
+        }
+    }
+}
+
+impl IntoLua for AudioChannel {
+    #[inline]
+    fn into_lua(self, _lua: &Lua) -> LuaResult<Value> {
+        Ok(Value::Integer(self as Integer))
+    }
+}
@@ -0,0 +1,246 @@
+use crate::audio::{AudioChannel, Playback, Sound};
+use crate::core::Context;
+use crate::lua::LuaModule;
+use crate::lua_modules::SoundRef;
+use crate::math::Vec2F;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub type PlaybackRef = UserDataRef<Playback>;
+
+pub struct AudioModule;
+
+impl LuaModule for AudioModule {
+    const PATH: &'static str = "Audio";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for AudioModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("sample_rate", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.sample_rate())
+        });
+        methods.add_function("master_volume", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.master_volume())
+        });
+        methods.add_function("set_master_volume", |lua, volume: f32| {
+            Context::from_lua(lua).audio.set_master_volume(volume);
+            Ok(())
+        });
+        methods.add_function("volume", |lua, channel: AudioChannel| {
+            Ok(Context::from_lua(lua).audio.volume(channel))
+        });
+        methods.add_function(
+            "set_volume",
+            |lua, (channel, volume): (AudioChannel, f32)| {
+                Context::from_lua(lua).audio.set_volume(channel, volume);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "play",
+            |lua,
+             (sound, channel, volume, pan, looping): (
+                SoundRef,
+                Option<AudioChannel>,
+                Option<f32>,
+                Option<f32>,
+                Option<bool>,
+            )| {
+                Ok(Context::from_lua(lua).audio.play_ext(
+                    &sound,
+                    channel.unwrap_or_default(),
+                    volume.unwrap_or(1.0),
+                    pan.unwrap_or(0.0),
+                    looping.unwrap_or(false),
+                ))
+            },
+        );
+        methods.add_function(
+            "play_at",
+            |lua,
+             (sound, pos, channel, volume, looping): (
+                SoundRef,
+                Vec2F,
+                Option<AudioChannel>,
+                Option<f32>,
+                Option<bool>,
+            )| {
+                Ok(Context::from_lua(lua).audio.play_at_ext(
+                    &sound,
+                    channel.unwrap_or_default(),
+                    volume.unwrap_or(1.0),
+                    pos,
+                    looping.unwrap_or(false),
+                ))
+            },
+        );
+        methods.add_function("listener", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.listener())
+        });
+        methods.add_function("set_listener", |lua, pos: Vec2F| {
+            Context::from_lua(lua).audio.set_listener(pos);
+            Ok(())
+        });
+        methods.add_function("min_distance", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.min_distance())
+        });
+        methods.add_function("set_min_distance", |lua, distance: f32| {
+            Context::from_lua(lua).audio.set_min_distance(distance);
+            Ok(())
+        });
+        methods.add_function("max_distance", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.max_distance())
+        });
+        methods.add_function("set_max_distance", |lua, distance: f32| {
+            Context::from_lua(lua).audio.set_max_distance(distance);
+            Ok(())
+        });
+        methods.add_function(
+            "play_music",
+            |lua, (sound, volume): (SoundRef, Option<f32>)| {
+                Ok(Context::from_lua(lua)
+                    .audio
+                    .play_music(&sound, volume.unwrap_or(1.0)))
+            },
+        );
+        methods.add_function(
+            "crossfade_music",
+            |lua, (sound, duration, volume): (SoundRef, f32, Option<f32>)| {
+                Ok(Context::from_lua(lua).audio.crossfade_music(
+                    &sound,
+                    volume.unwrap_or(1.0),
+                    duration,
+                ))
+            },
+        );
+        methods.add_function(
+            "play_music_stems",
+            |lua, (stems, volume): (Vec<SoundRef>, Option<f32>)| {
+                let stems: Vec<Sound> = stems.iter().map(|s| (**s).clone()).collect();
+                Ok(Context::from_lua(lua)
+                    .audio
+                    .play_music_stems(&stems, volume.unwrap_or(1.0)))
+            },
+        );
+        methods.add_function("music", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.music())
+        });
+        methods.add_function("music_stems", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.music_stems())
+        });
+        methods.add_function("stop_music", |lua, _: ()| {
+            Context::from_lua(lua).audio.stop_music();
+            Ok(())
+        });
+        methods.add_function("fade_out_music", |lua, duration: f32| {
+            Context::from_lua(lua).audio.fade_out_music(duration);
+            Ok(())
+        });
+        methods.add_function("stop_channel", |lua, channel: AudioChannel| {
+            Context::from_lua(lua).audio.stop_channel(channel);
+            Ok(())
+        });
+        methods.add_function("stop_all", |lua, _: ()| {
+            Context::from_lua(lua).audio.stop_all();
+            Ok(())
+        });
+        methods.add_function("playing_count", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.playing_count())
+        });
+        methods.add_function("rms", |lua, _: ()| Ok(Context::from_lua(lua).audio.rms()));
+        methods.add_function("peak", |lua, _: ()| Ok(Context::from_lua(lua).audio.peak()));
+        methods.add_function("bands", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.bands())
+        });
+        methods.add_function("beat", |lua, _: ()| Ok(Context::from_lua(lua).audio.beat()));
+        methods.add_function("tempo", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.tempo())
+        });
+        methods.add_function("band_count", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.band_count())
+        });
+        methods.add_function("set_band_count", |lua, count: usize| {
+            Context::from_lua(lua).audio.set_band_count(count);
+            Ok(())
+        });
+        methods.add_function("beat_sensitivity", |lua, _: ()| {
+            Ok(Context::from_lua(lua).audio.beat_sensitivity())
+        });
+        methods.add_function("set_beat_sensitivity", |lua, sensitivity: f32| {
+            Context::from_lua(lua)
+                .audio
+                .set_beat_sensitivity(sensitivity);
+            Ok(())
+        });
+    }
+}
+
+impl UserData for Playback {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("sound", |_, this: PlaybackRef| Ok(this.sound().clone()));
+        methods.add_function("channel", |_, this: PlaybackRef| Ok(this.channel()));
+        methods.add_function("is_playing", |_, this: PlaybackRef| Ok(this.is_playing()));
+        methods.add_function("stop", |_, this: PlaybackRef| {
+            this.stop();
+            Ok(())
+        });
+        methods.add_function("paused", |_, this: PlaybackRef| Ok(this.paused()));
+        methods.add_function("set_paused", |_, (this, paused): (PlaybackRef, bool)| {
+            this.set_paused(paused);
+            Ok(())
+        });
+        methods.add_function("volume", |_, this: PlaybackRef| Ok(this.volume()));
+        methods.add_function("set_volume", |_, (this, volume): (PlaybackRef, f32)| {
+            this.set_volume(volume);
+            Ok(())
+        });
+        methods.add_function(
+            "fade_to",
+            |_, (this, volume, duration): (PlaybackRef, f32, f32)| {
+                this.fade_to(volume, duration);
+                Ok(())
+            },
+        );
+        methods.add_function("fade_out", |_, (this, duration): (PlaybackRef, f32)| {
+            this.fade_out(duration);
+            Ok(())
+        });
+        methods.add_function("is_fading", |_, this: PlaybackRef| Ok(this.is_fading()));
+        methods.add_function("pan", |_, this: PlaybackRef| Ok(this.pan()));
+        methods.add_function("set_pan", |_, (this, pan): (PlaybackRef, f32)| {
+            this.set_pan(pan);
+            Ok(())
+        });
+        methods.add_function("speed", |_, this: PlaybackRef| Ok(this.speed()));
+        methods.add_function("set_speed", |_, (this, speed): (PlaybackRef, f32)| {
+            this.set_speed(speed);
+            Ok(())
+        });
+        methods.add_function("looping", |_, this: PlaybackRef| Ok(this.looping()));
+        methods.add_function("set_looping", |_, (this, looping): (PlaybackRef, bool)| {
+            this.set_looping(looping);
+            Ok(())
+        });
+        methods.add_function("loop_region", |_, this: PlaybackRef| {
+            let region = this.loop_region();
+            Ok((region.start, region.end))
+        });
+        methods.add_function(
+            "set_loop_region",
+            |_, (this, start, end): (PlaybackRef, usize, usize)| {
+                this.set_loop_region(start..end);
+                Ok(())
+            },
+        );
+        methods.add_function("emitter_pos", |_, this: PlaybackRef| Ok(this.emitter_pos()));
+        methods.add_function("set_emitter_pos", |_, (this, pos): (PlaybackRef, Vec2F)| {
+            this.set_emitter_pos(pos);
+            Ok(())
+        });
+        methods.add_function("position", |_, this: PlaybackRef| Ok(this.position()));
+    }
+}
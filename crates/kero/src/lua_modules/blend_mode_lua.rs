@@ -11,6 +11,7 @@ impl FromLua for BlendMode {
             "add" => BlendMode::Add,
             "subtract" => BlendMode::Subtract,
             "multiply" => BlendMode::Multiply,
+            "replace" => BlendMode::Replace,
             s => return Err(LuaError::runtime(format!("invalid blend mode {s:?}"))),
         })
     }
@@ -24,6 +25,7 @@ impl BlendMode {
             Self::Add => "add",
             Self::Subtract => "subtract",
             Self::Multiply => "multiply",
+            Self::Replace => "replace",
         }
     }
 }
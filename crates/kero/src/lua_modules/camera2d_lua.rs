@@ -0,0 +1,135 @@
+use crate::gfx::{Camera2D, Camera2DMut, Camera2DRef, Draw};
+use crate::lua::LuaModule;
+use crate::math::{RadiansF, RectF, Vec2F};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, UserData, UserDataMethods, Value};
+
+pub struct Camera2DModule;
+
+impl LuaModule for Camera2DModule {
+    const PATH: &'static str = "Camera2D";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for Camera2DModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |_, viewport: RectF| Ok(Camera2D::new(viewport)));
+        add_methods(methods);
+    }
+}
+
+impl UserData for Camera2D {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("pos", |_, this: Camera2DRef| Ok(this.pos));
+    methods.add_function("set_pos", |_, (mut this, pos): (Camera2DMut, Vec2F)| {
+        this.pos = pos;
+        Ok(())
+    });
+    methods.add_function("zoom", |_, this: Camera2DRef| Ok(this.zoom));
+    methods.add_function("set_zoom", |_, (mut this, zoom): (Camera2DMut, f32)| {
+        this.zoom = zoom;
+        Ok(())
+    });
+    methods.add_function("rotation", |_, this: Camera2DRef| Ok(this.rotation));
+    methods.add_function(
+        "set_rotation",
+        |_, (mut this, rotation): (Camera2DMut, RadiansF)| {
+            this.rotation = rotation;
+            Ok(())
+        },
+    );
+    methods.add_function("viewport", |_, this: Camera2DRef| Ok(this.viewport));
+    methods.add_function(
+        "set_viewport",
+        |_, (mut this, viewport): (Camera2DMut, RectF)| {
+            this.viewport = viewport;
+            Ok(())
+        },
+    );
+    methods.add_function("bounds", |_, this: Camera2DRef| Ok(this.bounds));
+    methods.add_function(
+        "set_bounds",
+        |_, (mut this, bounds): (Camera2DMut, Option<RectF>)| {
+            this.bounds = bounds;
+            Ok(())
+        },
+    );
+    methods.add_function("trauma", |_, this: Camera2DRef| Ok(this.shake.trauma()));
+    methods.add_function("set_trauma", |_, (mut this, trauma): (Camera2DMut, f32)| {
+        this.shake.set_trauma(trauma);
+        Ok(())
+    });
+    methods.add_function("add_trauma", |_, (mut this, amount): (Camera2DMut, f32)| {
+        this.add_trauma(amount);
+        Ok(())
+    });
+    methods.add_function(
+        "set_shake",
+        |_,
+         (mut this, max_offset, max_rotation, frequency, decay): (
+            Camera2DMut,
+            Vec2F,
+            RadiansF,
+            Option<f32>,
+            Option<f32>,
+        )| {
+            this.shake.max_offset = max_offset;
+            this.shake.max_rotation = max_rotation;
+            if let Some(frequency) = frequency {
+                this.shake.frequency = frequency;
+            }
+            if let Some(decay) = decay {
+                this.shake.decay = decay;
+            }
+            Ok(())
+        },
+    );
+    methods.add_function("update", |_, (mut this, dt): (Camera2DMut, f32)| {
+        this.update(dt);
+        Ok(())
+    });
+    methods.add_function("world_to_screen", |_, (this, pos): (Camera2DRef, Vec2F)| {
+        Ok(this.world_to_screen(pos))
+    });
+    methods.add_function("screen_to_world", |_, (this, pos): (Camera2DRef, Vec2F)| {
+        Ok(this.screen_to_world(pos))
+    });
+    methods.add_function("visible_rect", |_, this: Camera2DRef| {
+        Ok(this.visible_rect())
+    });
+    methods.add_function("apply", |lua, this: Camera2DRef| {
+        let draw = Draw::from_lua(lua)?;
+        this.apply(draw);
+        Ok(())
+    });
+    methods.add_function("pop", |lua, this: Camera2DRef| {
+        let draw = Draw::from_lua(lua)?;
+        this.pop(draw).map_err(LuaError::external)
+    });
+    methods.add_function(
+        "follow",
+        |_, (mut this, target, speed, dt): (Camera2DMut, Vec2F, f32, f32)| {
+            this.follow(target, speed, dt);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "follow_deadzone",
+        |_, (mut this, target, size, speed, dt): (Camera2DMut, Vec2F, Vec2F, f32, f32)| {
+            this.follow_deadzone(target, size, speed, dt);
+            Ok(())
+        },
+    );
+    methods.add_function("clamp_to_bounds", |_, mut this: Camera2DMut| {
+        this.clamp_to_bounds();
+        Ok(())
+    });
+}
@@ -1,12 +1,13 @@
 use crate::gfx::{
-    BlendMode, ColorMode, Draw, FontRef, IndexBufferRef, Sampler, ShaderRef, SubTextureRef,
-    SurfaceRef, Texture, TextureRef, Topology, Vertex, VertexBufferRef,
+    BlendMode, ColorMode, Draw, FontRef, IndexBufferRef, MaterialRef, Sampler, ShaderRef,
+    SubTextureRef, SurfaceRef, TextAlign, TextLayout, Texture, TextureRef, Topology, Vertex,
+    VertexBufferRef,
 };
 use fey_color::{Rgba8, rgba};
 use fey_lua::LuaModule;
 use fey_math::{
-    Affine2F, CircleF, LineF, Mat2F, Mat3F, Mat4F, Mat4Ref, PolygonRef, QuadF, RadiansF, RectF,
-    RectU, TriangleF, Vec2F, Vec3F, Vec4F, circle, line, vec2,
+    Affine2F, CircleF, LineF, Mat2F, Mat3F, Mat4F, Mat4Ref, PolygonRef, PolylineF, QuadF, RadiansF,
+    RectF, RectU, TriangleF, Vec2F, Vec3F, Vec4F, circle, line, vec2,
 };
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{BorrowedStr, Either, IntoLua, Lua, Number, Table, UserData, UserDataMethods, Value};
@@ -62,6 +63,17 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.set_shader(shader.map(|s| s.clone()));
         Ok(())
     });
+    methods.add_function("set_material", |lua, material: MaterialRef| {
+        Draw::from_lua(lua)?.set_material(&material);
+        Ok(())
+    });
+    methods.add_function("layer_post", |lua, _: ()| {
+        Ok(Draw::from_lua(lua)?.layer_post().cloned())
+    });
+    methods.add_function("set_layer_post", |lua, material: Option<MaterialRef>| {
+        Draw::from_lua(lua)?.set_layer_post(material.map(|m| m.clone()));
+        Ok(())
+    });
     methods.add_function("set_param_i32", |lua, (name, value): (BorrowedStr, i32)| {
         Draw::from_lua(lua)?.set_param_i32(&name, value);
         Ok(())
@@ -116,6 +128,13 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "set_param_block",
+        |lua, (name, value): (BorrowedStr, Vec<f32>)| {
+            Draw::from_lua(lua)?.set_param_block_slice(&name, &value);
+            Ok(())
+        },
+    );
     methods.add_function(
         "set_param_texture",
         |lua, (name, value): (BorrowedStr, TextureRef)| {
@@ -155,6 +174,15 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.set_clip_rect(value);
         Ok(())
     });
+    methods.add_function("push_clip_rect", |lua, value: RectF| {
+        Draw::from_lua(lua)?.push_clip_rect(value);
+        Ok(())
+    });
+    methods.add_function("pop_clip_rect", |lua, _: ()| {
+        Draw::from_lua(lua)?
+            .pop_clip_rect()
+            .map_err(LuaError::external)
+    });
     methods.add_function("transform", |lua, _: ()| {
         Ok(*Draw::from_lua(lua)?.transform())
     });
@@ -263,6 +291,66 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "textured_quad_uv",
+        |lua,
+         (tex, quad, uv_rect, col, mode): (
+            TextureRef,
+            QuadF,
+            RectF,
+            Option<Rgba8>,
+            Option<ColorMode>,
+        )| {
+            let col = col.unwrap_or(Rgba8::WHITE);
+            let mode = mode.unwrap_or(ColorMode::MULT);
+            Draw::from_lua(lua)?.textured_quad_uv(tex.deref(), quad, uv_rect, col, mode);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "texture_tiled",
+        |lua,
+         (tex, dst, uv_scale, uv_offset, col, mode): (
+            TextureRef,
+            RectF,
+            Option<Vec2F>,
+            Option<Vec2F>,
+            Option<Rgba8>,
+            Option<ColorMode>,
+        )| {
+            Draw::from_lua(lua)?.texture_tiled_ext(
+                tex.deref(),
+                dst,
+                uv_scale.unwrap_or(Vec2F::ONE),
+                uv_offset.unwrap_or(Vec2F::ZERO),
+                col.unwrap_or(Rgba8::WHITE),
+                mode.unwrap_or(ColorMode::MULT),
+            );
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "textured_polygon",
+        |lua,
+         (tex, points, uvs, col, mode): (
+            TextureRef,
+            Table,
+            Table,
+            Option<Rgba8>,
+            Option<ColorMode>,
+        )| {
+            let points: Vec<Vec2F> = points.sequence_values().filter_map(Result::ok).collect();
+            let uvs: Vec<Vec2F> = uvs.sequence_values().filter_map(Result::ok).collect();
+            Draw::from_lua(lua)?.textured_polygon_ext(
+                tex.deref(),
+                &points,
+                &uvs,
+                col.unwrap_or(Rgba8::WHITE),
+                mode.unwrap_or(ColorMode::MULT),
+            );
+            Ok(())
+        },
+    );
     methods.add_function("point", |lua, (pos, col): (Vec2F, Rgba8)| {
         Draw::from_lua(lua)?.point(pos, col);
         Ok(())
@@ -299,6 +387,35 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.line(line, col.unwrap_or(Rgba8::WHITE));
         Ok(())
     });
+    methods.add_function(
+        "line_dashed",
+        |lua, (from, to, dash, gap, col, phase): (Vec2F, Vec2F, f32, f32, Rgba8, Option<f32>)| {
+            Draw::from_lua(lua)?.line_dashed((from, to), dash, gap, col, phase.unwrap_or(0.0));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "lines_dashed",
+        |lua,
+         (points, dash, gap, col, loops, phase): (
+            Table,
+            f32,
+            f32,
+            Rgba8,
+            bool,
+            Option<f32>,
+        )| {
+            Draw::from_lua(lua)?.lines_dashed(
+                points.sequence_values::<Vec2F>().filter_map(Result::ok),
+                dash,
+                gap,
+                col,
+                phase.unwrap_or(0.0),
+                loops,
+            );
+            Ok(())
+        },
+    );
     methods.add_function(
         "lines",
         |lua, (points, col, loops): (Table, Rgba8, bool)| {
@@ -379,6 +496,13 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         Draw::from_lua(lua)?.rect_outline(rect, col);
         Ok(())
     });
+    methods.add_function(
+        "rect_outline_dashed",
+        |lua, (rect, dash, gap, col, phase): (RectF, f32, f32, Rgba8, Option<f32>)| {
+            Draw::from_lua(lua)?.rect_outline_dashed(rect, dash, gap, col, phase.unwrap_or(0.0));
+            Ok(())
+        },
+    );
     methods.add_function("polygon", |lua, (poly, col): (PolygonRef, Rgba8)| {
         Draw::from_lua(lua)?.polygon(&poly, col);
         Ok(())
@@ -390,6 +514,19 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "polygon_outline_dashed",
+        |lua, (poly, dash, gap, col, phase): (PolygonRef, f32, f32, Rgba8, Option<f32>)| {
+            Draw::from_lua(lua)?.polygon_outline_dashed(
+                &poly,
+                dash,
+                gap,
+                col,
+                phase.unwrap_or(0.0),
+            );
+            Ok(())
+        },
+    );
     methods.add_function(
         "circle",
         |lua,
@@ -440,6 +577,50 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "circle_outline_dashed",
+        |lua,
+         (circ, dash, gap, col, phase, segs): (
+            CircleF,
+            f32,
+            f32,
+            Rgba8,
+            Option<f32>,
+            Option<u32>,
+        )| {
+            Draw::from_lua(lua)?.circle_outline_dashed(
+                circ,
+                dash,
+                gap,
+                col,
+                phase.unwrap_or(0.0),
+                segs,
+            );
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "radial_fill",
+        |lua, (circ, angle, t, col, segs): (CircleF, RadiansF, f32, Rgba8, Option<u32>)| {
+            Draw::from_lua(lua)?.radial_fill(circ, angle, t, col, segs);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "radial_ring",
+        |lua,
+         (circ, thickness, angle, t, col, segs): (
+            CircleF,
+            f32,
+            RadiansF,
+            f32,
+            Rgba8,
+            Option<u32>,
+        )| {
+            Draw::from_lua(lua)?.radial_ring(circ, thickness, angle, t, col, segs);
+            Ok(())
+        },
+    );
     methods.add_function(
         "subtextured_quad",
         |lua,
@@ -494,6 +675,35 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "subtexture_rotated",
+        |lua,
+         (sub, pos, rot, pivot, scale, col, mode): (
+            SubTextureRef,
+            Vec2F,
+            RadiansF,
+            Option<Vec2F>,
+            Option<Either<Vec2F, f32>>,
+            Option<Rgba8>,
+            Option<ColorMode>,
+        )| {
+            let scale = match scale {
+                Some(Either::Left(s)) => s,
+                Some(Either::Right(s)) => vec2(s, s),
+                None => Vec2F::ONE,
+            };
+            Draw::from_lua(lua)?.subtexture_rotated_ext(
+                sub.deref(),
+                pos,
+                rot,
+                pivot.unwrap_or(Vec2F::ZERO),
+                scale,
+                col.unwrap_or(Rgba8::WHITE),
+                mode.unwrap_or(ColorMode::MULT),
+            );
+            Ok(())
+        },
+    );
     methods.add_function(
         "text",
         |lua,
@@ -519,6 +729,62 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(())
         },
     );
+    methods.add_function(
+        "text_layout",
+        |lua,
+         (txt, pos, font, max_width, align, line_spacing, col): (
+            BorrowedStr,
+            Vec2F,
+            FontRef,
+            Option<f32>,
+            Option<TextAlign>,
+            Option<f32>,
+            Option<Rgba8>,
+        )| {
+            let layout = TextLayout::new(
+                txt.as_ref(),
+                font.deref(),
+                max_width,
+                align.unwrap_or_default(),
+                line_spacing.unwrap_or(1.0),
+            );
+            Draw::from_lua(lua)?.text_layout(
+                &layout,
+                pos,
+                font.deref(),
+                col.unwrap_or(Rgba8::WHITE),
+            );
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "text_on_path",
+        |lua,
+         (txt, points, font, col, size, offset, spacing): (
+            BorrowedStr,
+            Table,
+            FontRef,
+            Option<Rgba8>,
+            Option<f32>,
+            Option<f32>,
+            Option<f32>,
+        )| {
+            let path: PolylineF = points
+                .sequence_values::<Vec2F>()
+                .filter_map(Result::ok)
+                .collect();
+            Draw::from_lua(lua)?.text_on_path(
+                txt.as_ref(),
+                &path,
+                font.deref(),
+                col.unwrap_or(Rgba8::WHITE),
+                size,
+                offset.unwrap_or(0.0),
+                spacing.unwrap_or(0.0),
+            );
+            Ok(())
+        },
+    );
     methods.add_function(
         "custom",
         |lua, (tex, topo, verts, inds): (Option<Texture>, Topology, Table, Table)| {
@@ -1,7 +1,7 @@
 use crate::core::Context;
-use crate::gfx::{Font, FontMut, FontRef, SubTexture};
+use crate::gfx::{Font, FontMut, FontRef, SubTexture, SubpixelMode, TextAlign, TextLayout};
 use crate::lua::LuaModule;
-use crate::misc::BASIC_LATIN;
+use crate::misc::Charset;
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{BorrowedStr, Lua, UserData, UserDataMethods, Value};
 
@@ -22,12 +22,39 @@ impl UserData for FontModule {
         });
         methods.add_function(
             "from_ttf_file",
-            |lua, (path, size, pixelated, chars): (BorrowedStr, f32, bool, Option<BorrowedStr>)| {
+            |lua,
+             (path, size, pixelated, chars, subpixel): (
+                BorrowedStr,
+                f32,
+                bool,
+                Option<BorrowedStr>,
+                Option<SubpixelMode>,
+            )| {
                 let chars = chars
                     .map(|chrs| chrs.to_string())
-                    .unwrap_or_else(|| BASIC_LATIN.chars().collect());
+                    .unwrap_or_else(|| Charset::BASIC_LATIN.chars().collect());
                 let ctx = Context::from_lua(lua);
-                Font::from_ttf_file(&ctx.graphics, path.as_ref(), size, pixelated, chars.chars())
+                Font::from_ttf_file_ext(
+                    &ctx.graphics,
+                    path.as_ref(),
+                    size,
+                    pixelated,
+                    subpixel.unwrap_or_default(),
+                    chars.chars(),
+                )
+                .map_err(LuaError::external)?
+                .ok_or_else(|| LuaError::runtime("failed to pack font"))
+                .map(|(font, _)| font)
+            },
+        );
+        methods.add_function(
+            "from_ttf_file_sdf",
+            |lua, (path, size, chars): (BorrowedStr, f32, Option<BorrowedStr>)| {
+                let chars = chars
+                    .map(|chrs| chrs.to_string())
+                    .unwrap_or_else(|| Charset::BASIC_LATIN.chars().collect());
+                let ctx = Context::from_lua(lua);
+                Font::from_ttf_file_sdf(&ctx.graphics, path.as_ref(), size, chars.chars())
                     .map_err(LuaError::external)?
                     .ok_or_else(|| LuaError::runtime("failed to pack font"))
                     .map(|(font, _)| font)
@@ -52,6 +79,15 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
 
     methods.add_function("size", |_, this: FontRef| Ok(this.size()));
     methods.add_function("pixelated", |_, this: FontRef| Ok(this.pixelated()));
+    methods.add_function("sdf", |_, this: FontRef| Ok(this.sdf()));
+    methods.add_function("subpixel", |_, this: FontRef| Ok(this.subpixel()));
+    methods.add_function(
+        "set_subpixel",
+        |_, (mut this, subpixel): (FontMut, SubpixelMode)| {
+            this.set_subpixel(subpixel);
+            Ok(())
+        },
+    );
     methods.add_function(
         "set_glyph",
         |_, (mut this, chr, sub, adv): (FontMut, BorrowedStr, Option<SubTexture>, f32)| {
@@ -76,4 +112,24 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             Ok(this.kerning(left, right).unwrap_or(0.0))
         },
     );
+    methods.add_function(
+        "layout_size",
+        |_,
+         (this, text, max_width, align, line_spacing): (
+            FontRef,
+            BorrowedStr,
+            Option<f32>,
+            Option<TextAlign>,
+            Option<f32>,
+        )| {
+            let layout = TextLayout::new(
+                text.as_ref(),
+                &this,
+                max_width,
+                align.unwrap_or_default(),
+                line_spacing.unwrap_or(1.0),
+            );
+            Ok(layout.size())
+        },
+    );
 }
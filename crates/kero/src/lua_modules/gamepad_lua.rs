@@ -1,9 +1,10 @@
 use crate::core::Context;
-use crate::input::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus};
+use crate::input::{Gamepad, GamepadAxis, GamepadButton, GamepadStatus, VibrationPattern};
 use crate::lua::LuaModule;
 use fey_lua::{UserDataOf, create_fill};
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{FromLua, IntoLua, Lua, Table, UserData, UserDataMethods, UserDataRef, Value};
+use std::time::Duration;
 
 pub type GamepadObj = UserDataOf<Gamepad>;
 pub type GamepadRef = UserDataRef<Gamepad>;
@@ -103,6 +104,37 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         "axis_changed",
         |_, (this, axis): (GamepadRef, GamepadAxis)| Ok(this.axis_changed(axis)),
     );
+    methods.add_function("rumble_supported", |_, this: GamepadRef| {
+        Ok(this.rumble_supported())
+    });
+    methods.add_function(
+        "rumble",
+        |_, (this, strong, weak, duration): (GamepadRef, f32, f32, f32)| {
+            this.rumble(strong, weak, Duration::from_secs_f32(duration.max(0.0)));
+            Ok(())
+        },
+    );
+    methods.add_function("stop_rumble", |_, this: GamepadRef| {
+        this.stop_rumble();
+        Ok(())
+    });
+    methods.add_function(
+        "vibrate",
+        |_, (this, preset, intensity): (GamepadRef, String, Option<f32>)| {
+            let pattern = VibrationPattern::preset(&preset)
+                .ok_or_else(|| LuaError::runtime(format!("invalid vibration preset [{preset}]")))?;
+            this.vibrate_ext(&pattern, intensity.unwrap_or(1.0));
+            Ok(())
+        },
+    );
+    methods.add_function("stop_vibrations", |_, this: GamepadRef| {
+        this.stop_vibrations();
+        Ok(())
+    });
+    methods.add_function(
+        "is_vibrating",
+        |_, this: GamepadRef| Ok(this.is_vibrating()),
+    );
 }
 
 pub struct GamepadButtonModule;
@@ -21,6 +21,7 @@ impl UserData for IndexBufferModule {
             Ok(IndexBuffer::new(
                 ctx.graphics.device(),
                 ctx.graphics.queue().clone(),
+                ctx.graphics.memory(),
                 cap,
             ))
         });
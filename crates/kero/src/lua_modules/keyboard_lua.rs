@@ -1,8 +1,10 @@
 use crate::core::Context;
 use crate::input::Key;
 use crate::lua::LuaModule;
+use fey_lua::create_fill;
 use mlua::prelude::LuaResult;
-use mlua::{Lua, Value};
+use mlua::{Lua, Table, Value};
+use strum::VariantArray;
 
 pub struct KeyboardModule;
 
@@ -33,12 +35,46 @@ impl LuaModule for KeyboardModule {
                 Ok(Context::from_lua(lua).keyboard.pressed_or_repeated(key))
             })?,
         )?;
+        m.set(
+            "value",
+            lua.create_function(|lua, key: Key| Ok(Context::from_lua(lua).keyboard.value(key)))?,
+        )?;
+        m.set(
+            "currently_down",
+            lua.create_function(|lua, fill: Option<Table>| {
+                let fill = create_fill(lua, fill)?;
+                for key in Context::from_lua(lua).keyboard.currently_down() {
+                    fill.raw_push(key)?;
+                }
+                Ok(fill)
+            })?,
+        )?;
+        m.set(
+            "keys",
+            lua.create_function(|lua, fill: Option<Table>| {
+                let fill = create_fill(lua, fill)?;
+                for key in Key::VARIANTS {
+                    fill.raw_push(*key)?;
+                }
+                Ok(fill)
+            })?,
+        )?;
         m.set(
             "text_input",
             lua.create_function(|lua, _: ()| {
                 lua.create_string(Context::from_lua(lua).keyboard.text_input())
             })?,
         )?;
+        m.set(
+            "composition",
+            lua.create_function(|lua, _: ()| {
+                lua.create_string(Context::from_lua(lua).keyboard.composition())
+            })?,
+        )?;
+        m.set(
+            "is_composing",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.is_composing()))?,
+        )?;
         m.set(
             "ctrl",
             lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).keyboard.ctrl()))?,
@@ -0,0 +1,93 @@
+use crate::color::Rgba8;
+use crate::gfx::{
+    BindingValue, Draw, FontRef, Material, MaterialMut, MaterialRef, ParamType, Sampler, ShaderRef,
+    TextureRef, UniformType, UniformValue,
+};
+use crate::lua::LuaModule;
+use fey_math::{Mat2F, Mat3F, Mat4F, Vec2F, Vec3F, Vec4F};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, FromLua, Lua, UserData, UserDataMethods, Value};
+
+pub struct MaterialModule;
+
+impl LuaModule for MaterialModule {
+    const PATH: &'static str = "Material";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for MaterialModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |_, shader: ShaderRef| {
+            Ok(Material::new(shader.clone()))
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for Material {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("shader", |_, this: MaterialRef| Ok(this.shader().clone()));
+    methods.add_function("clone", |_, this: MaterialRef| Ok(this.clone()));
+    methods.add_function(
+        "set",
+        |lua, (mut this, name, value): (MaterialMut, BorrowedStr, Value)| {
+            let Some(def) = this.shader().param_defs().find(name.as_ref()) else {
+                return Err(LuaError::runtime(format!(
+                    "param {:?} not found",
+                    name.as_ref()
+                )));
+            };
+            let value = binding_value_from_lua(def.ty, value, lua)?;
+            this.set(name.as_ref(), value).map_err(LuaError::external)?;
+            Ok(())
+        },
+    );
+    methods.add_function("get", |_, (this, name): (MaterialRef, BorrowedStr)| {
+        Ok(this.get(name.as_ref()).cloned())
+    });
+    methods.add_function(
+        "draw_overlay",
+        |lua, (this, font, pos, color): (MaterialRef, FontRef, Vec2F, Option<Rgba8>)| {
+            let draw = Draw::from_lua(lua)?;
+            this.draw_overlay(draw, &font, pos, color.unwrap_or(Rgba8::WHITE));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "unset",
+        |_, (mut this, name): (MaterialMut, BorrowedStr)| {
+            this.unset(name.as_ref()).map_err(LuaError::external)?;
+            Ok(())
+        },
+    );
+}
+
+/// Convert a Lua value to a binding value of the parameter type.
+fn binding_value_from_lua(ty: ParamType, value: Value, lua: &Lua) -> LuaResult<BindingValue> {
+    Ok(match ty {
+        ParamType::Texture => TextureRef::from_lua(value, lua)?.clone().into(),
+        ParamType::Sampler => Sampler::from_lua(value, lua)?.into(),
+        ParamType::Uniform(ty) => match ty {
+            UniformType::Int => i32::from_lua(value, lua)?.into(),
+            UniformType::Uint => u32::from_lua(value, lua)?.into(),
+            UniformType::Float => f32::from_lua(value, lua)?.into(),
+            UniformType::Vec2 => Vec2F::from_lua(value, lua)?.into(),
+            UniformType::Vec3 => Vec3F::from_lua(value, lua)?.into(),
+            UniformType::Vec4 => Vec4F::from_lua(value, lua)?.into(),
+            UniformType::Mat2 => Mat2F::from_lua(value, lua)?.into(),
+            UniformType::Mat3 => Mat3F::from_lua(value, lua)?.into(),
+            UniformType::Mat4 => Mat4F::from_lua(value, lua)?.into(),
+            UniformType::Block { .. } => {
+                UniformValue::block_slice(&Vec::<f32>::from_lua(value, lua)?).into()
+            }
+        },
+    })
+}
@@ -1,5 +1,9 @@
 mod app_lua;
+mod atlas_debug_lua;
+mod audio_channel_lua;
+mod audio_lua;
 mod blend_mode_lua;
+mod camera2d_lua;
 mod color_mode_lua;
 mod draw_lua;
 mod font_lua;
@@ -7,24 +11,41 @@ mod gamepad_lua;
 mod index_buffer_lua;
 mod key_lua;
 mod keyboard_lua;
+mod material_lua;
 mod monitor_lua;
 mod mouse_button_lua;
 mod mouse_lua;
+mod particles_lua;
+mod platform_lua;
 mod sampler_lua;
 mod screen_lua;
 mod shader_lua;
+mod sound_bank_lua;
+mod sound_lua;
+mod sound_select_lua;
+mod status_lua;
 mod sub_texture_lua;
+mod subpixel_mode_lua;
 mod surface_lua;
+mod telemetry_lua;
+mod text_align_lua;
 mod texture_format_lua;
 mod texture_lua;
 mod time_lua;
 mod topology_lua;
+mod touch_lua;
+mod tweak_lua;
 mod vertex_buffer_lua;
 mod vertex_lua;
 mod video_mode_lua;
+mod virtual_controller_lua;
 mod window_lua;
 
 pub use app_lua::*;
+pub use atlas_debug_lua::*;
+pub use audio_channel_lua::*;
+pub use audio_lua::*;
+pub use camera2d_lua::*;
 pub use color_mode_lua::*;
 pub use draw_lua::*;
 pub use font_lua::*;
@@ -32,20 +53,29 @@ pub use gamepad_lua::*;
 pub use index_buffer_lua::*;
 pub use key_lua::*;
 pub use keyboard_lua::*;
+pub use material_lua::*;
 use mlua::prelude::LuaError;
 pub use monitor_lua::*;
 pub use mouse_button_lua::*;
 pub use mouse_lua::*;
+pub use particles_lua::*;
+pub use platform_lua::*;
 pub use sampler_lua::*;
 pub use screen_lua::*;
 pub use shader_lua::*;
+pub use sound_bank_lua::*;
+pub use sound_lua::*;
 pub use sub_texture_lua::*;
 pub use surface_lua::*;
+pub use telemetry_lua::*;
 pub use texture_lua::*;
 pub use time_lua::*;
+pub use touch_lua::*;
+pub use tweak_lua::*;
 pub use vertex_buffer_lua::*;
 pub use vertex_lua::*;
 pub use video_mode_lua::*;
+pub use virtual_controller_lua::*;
 pub use window_lua::*;
 
 impl Into<LuaError> for crate::core::GameError {
@@ -26,7 +26,37 @@ impl LuaModule for MouseModule {
         )?;
         m.set(
             "scroll",
-            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_delta().y))?,
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_y()))?,
+        )?;
+        m.set(
+            "scroll_x",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_x()))?,
+        )?;
+        m.set(
+            "motion",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.motion()))?,
+        )?;
+        m.set(
+            "captured",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.captured()))?,
+        )?;
+        m.set(
+            "set_captured",
+            lua.create_function(|lua, captured: bool| {
+                Ok(Context::from_lua(lua).mouse.set_captured(captured))
+            })?,
+        )?;
+        m.set(
+            "scroll_total",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_total()))?,
+        )?;
+        m.set(
+            "scroll_lines",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_lines()))?,
+        )?;
+        m.set(
+            "scroll_delta",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).mouse.scroll_delta()))?,
         )?;
         m.set(
             "down",
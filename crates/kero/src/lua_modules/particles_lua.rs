@@ -0,0 +1,105 @@
+use crate::gfx::{Draw, SubTextureRef};
+use crate::lua::LuaModule;
+use crate::math::Vec2F;
+use crate::particles::{EmitterDef, ParticleId, Particles, ParticlesMut, ParticlesRef};
+use crate::rand::Rand;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct ParticlesModule;
+
+impl LuaModule for ParticlesModule {
+    const PATH: &'static str = "Particles";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for ParticlesModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |_, seed: Option<u64>| {
+            Ok(match seed {
+                Some(seed) => Particles::from_rand(Rand::from_seed(seed)),
+                None => Particles::new(),
+            })
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for Particles {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function(
+        "load",
+        |_, (mut this, name, path, textures): (ParticlesMut, String, BorrowedStr, Option<Table>)| {
+            let def = EmitterDef::from_file(&*path).map_err(LuaError::external)?;
+            let textures = def
+                .resolve_textures(|name| {
+                    let sub = textures.as_ref()?.get::<Option<SubTextureRef>>(name);
+                    sub.ok().flatten().map(|sub| (*sub).clone())
+                })
+                .map_err(LuaError::external)?;
+            this.insert(name, def, textures);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "has_effect",
+        |_, (this, name): (ParticlesRef, BorrowedStr)| Ok(this.effect(&name).is_some()),
+    );
+    methods.add_function(
+        "spawn",
+        |_, (mut this, name, pos): (ParticlesMut, BorrowedStr, Vec2F)| {
+            let id = this.spawn(&name, pos).map_err(LuaError::external)?;
+            Ok(id.0)
+        },
+    );
+    methods.add_function("is_alive", |_, (this, id): (ParticlesRef, u64)| {
+        Ok(this.get(ParticleId(id)).is_some())
+    });
+    methods.add_function(
+        "set_pos",
+        |_, (mut this, id, pos): (ParticlesMut, u64, Vec2F)| {
+            if let Some(emitter) = this.get_mut(ParticleId(id)) {
+                emitter.pos = pos;
+            }
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "burst",
+        |_, (mut this, id, count): (ParticlesMut, u64, u32)| {
+            if let Some(emitter) = this.get_mut(ParticleId(id)) {
+                emitter.burst(count);
+            }
+            Ok(())
+        },
+    );
+    methods.add_function("stop", |_, (mut this, id): (ParticlesMut, u64)| {
+        this.stop(ParticleId(id));
+        Ok(())
+    });
+    methods.add_function("kill", |_, (mut this, id): (ParticlesMut, u64)| {
+        this.kill(ParticleId(id));
+        Ok(())
+    });
+    methods.add_function("clear", |_, mut this: ParticlesMut| {
+        this.clear();
+        Ok(())
+    });
+    methods.add_function("count", |_, this: ParticlesRef| Ok(this.count()));
+    methods.add_function("update", |_, (mut this, dt): (ParticlesMut, f32)| {
+        this.update(dt);
+        Ok(())
+    });
+    methods.add_function("draw", |lua, this: ParticlesRef| {
+        this.draw(Draw::from_lua(lua)?);
+        Ok(())
+    });
+}
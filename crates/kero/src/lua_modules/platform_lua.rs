@@ -0,0 +1,56 @@
+use crate::core::{Context, PresenceAssets, PresenceTimestamps};
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct PlatformModule;
+
+type PresenceArgs = (String, Option<String>, Option<Table>, Option<Table>);
+
+impl LuaModule for PlatformModule {
+    const PATH: &'static str = "Platform";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for PlatformModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function(
+            "set_presence",
+            |lua, (state, details, timestamps, assets): PresenceArgs| {
+                let timestamps = match timestamps {
+                    Some(t) => PresenceTimestamps {
+                        start: t.get("start")?,
+                        end: t.get("end")?,
+                    },
+                    None => PresenceTimestamps::none(),
+                };
+                let assets = match assets {
+                    Some(t) => PresenceAssets {
+                        large_image: t.get("large_image")?,
+                        large_text: t.get("large_text")?,
+                        small_image: t.get("small_image")?,
+                        small_text: t.get("small_text")?,
+                    },
+                    None => PresenceAssets::none(),
+                };
+                Context::from_lua(lua).platform.set_presence(
+                    state,
+                    details.unwrap_or_default(),
+                    timestamps,
+                    assets,
+                );
+                Ok(())
+            },
+        );
+        methods.add_function("clear_presence", |lua, _: ()| {
+            Context::from_lua(lua).platform.clear_presence();
+            Ok(())
+        });
+        methods.add_function("is_presence_connected", |lua, _: ()| {
+            Ok(Context::from_lua(lua).platform.is_presence_connected())
+        });
+    }
+}
@@ -1,5 +1,5 @@
 use crate::core::Context;
-use crate::gfx::{ParamType, Shader, ShaderRef, UniformType};
+use crate::gfx::{BindingValue, ParamType, Shader, ShaderRef, UniformType, UniformValue};
 use crate::lua::LuaModule;
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{BorrowedStr, FromLua, IntoLua, Lua, UserData, UserDataMethods, UserDataRef, Value};
@@ -42,6 +42,31 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         }
         Ok(t)
     });
+    methods.add_function("param_defs", |lua, this: ShaderRef| {
+        let t = lua.create_table()?;
+        for def in this.param_defs().iter() {
+            let d = lua.create_table()?;
+            d.raw_set("name", def.name.as_str())?;
+            d.raw_set("type", def.ty)?;
+            d.raw_set("binding", def.binding)?;
+            if let ParamType::Uniform(ty) = def.ty {
+                d.raw_set("size", ty.size())?;
+                d.raw_set("components", ty.components())?;
+            }
+            d.raw_set("default", def.default_value())?;
+            t.raw_push(d)?;
+        }
+        Ok(t)
+    });
+    methods.add_function(
+        "param_default",
+        |_, (this, name): (ShaderRef, BorrowedStr)| {
+            Ok(this
+                .param_defs()
+                .find(name.as_ref())
+                .and_then(|def| def.default_value()))
+        },
+    );
     methods.add_function("param_type", |_, (this, name): (ShaderRef, BorrowedStr)| {
         Ok(this
             .param_defs()
@@ -95,11 +120,40 @@ impl ParamType {
                 UniformType::Mat2 => "mat2",
                 UniformType::Mat3 => "mat3",
                 UniformType::Mat4 => "mat4",
+                UniformType::Block { .. } => "block",
             },
         }
     }
 }
 
+impl IntoLua for UniformValue {
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Int(v) => v.into_lua(lua),
+            Self::Uint(v) => v.into_lua(lua),
+            Self::Float(v) => v.into_lua(lua),
+            Self::Vec2(v) => v.into_lua(lua),
+            Self::Vec3(v) => v.into_lua(lua),
+            Self::Vec4(v) => v.into_lua(lua),
+            Self::Mat2(v) => v.into_lua(lua),
+            Self::Mat3(v) => v.into_lua(lua),
+            Self::Mat4(v) => v.into_lua(lua),
+            Self::Block(bytes) => bytemuck::pod_collect_to_vec::<u8, f32>(&bytes).into_lua(lua),
+        }
+    }
+}
+
+impl IntoLua for BindingValue {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Texture(tex) => tex.into_lua(lua),
+            Self::Sampler(sampler) => sampler.into_lua(lua),
+            Self::Uniform(value) => value.into_lua(lua),
+        }
+    }
+}
+
 impl IntoLua for ParamType {
     #[inline]
     fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
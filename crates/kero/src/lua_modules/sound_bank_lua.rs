@@ -0,0 +1,87 @@
+use crate::audio::{AudioChannel, SoundBank, SoundBankMut, SoundEvent, SoundSelect};
+use crate::core::Context;
+use crate::lua::LuaModule;
+use crate::lua_modules::SoundRef;
+use crate::math::Vec2F;
+use crate::rand::Rand;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, Lua, UserData, UserDataMethods, Value};
+
+pub struct SoundBankModule;
+
+impl LuaModule for SoundBankModule {
+    const PATH: &'static str = "SoundBank";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for SoundBankModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("new", |_, seed: Option<u64>| {
+            Ok(match seed {
+                Some(seed) => SoundBank::from_rand(Rand::from_seed(seed)),
+                None => SoundBank::new(),
+            })
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for SoundBank {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+type AddArgs = (
+    SoundBankMut,
+    String,
+    Vec<SoundRef>,
+    Option<f32>,
+    Option<f32>,
+    Option<f32>,
+    Option<f32>,
+    Option<SoundSelect>,
+    Option<AudioChannel>,
+);
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function(
+        "add",
+        |_, (mut this, name, sounds, min_vol, max_vol, min_pitch, max_pitch, select, channel): AddArgs| {
+            let min_vol = min_vol.unwrap_or(1.0);
+            let min_pitch = min_pitch.unwrap_or(1.0);
+            let event = SoundEvent::new(sounds.iter().map(|s| (**s).clone()))
+                .with_volume(min_vol..=max_vol.unwrap_or(min_vol))
+                .with_pitch(min_pitch..=max_pitch.unwrap_or(min_pitch))
+                .with_select(select.unwrap_or_default())
+                .with_channel(channel.unwrap_or(AudioChannel::Sfx));
+            this.insert(name, event);
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "remove",
+        |_, (mut this, name): (SoundBankMut, BorrowedStr)| Ok(this.remove(&name).is_some()),
+    );
+    methods.add_function(
+        "contains",
+        |_, (this, name): (SoundBankMut, BorrowedStr)| Ok(this.contains(&name)),
+    );
+    methods.add_function(
+        "play",
+        |lua, (mut this, name, volume): (SoundBankMut, BorrowedStr, Option<f32>)| {
+            let audio = &Context::from_lua(lua).audio;
+            Ok(this.play_ext(audio, &name, volume.unwrap_or(1.0), None))
+        },
+    );
+    methods.add_function(
+        "play_at",
+        |lua, (mut this, name, pos, volume): (SoundBankMut, BorrowedStr, Vec2F, Option<f32>)| {
+            let audio = &Context::from_lua(lua).audio;
+            Ok(this.play_ext(audio, &name, volume.unwrap_or(1.0), Some(pos)))
+        },
+    );
+}
@@ -0,0 +1,38 @@
+use crate::audio::Sound;
+use crate::lua::LuaModule;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{BorrowedStr, Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub type SoundRef = UserDataRef<Sound>;
+
+pub struct SoundModule;
+
+impl LuaModule for SoundModule {
+    const PATH: &'static str = "Sound";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for SoundModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("from_file", |_, path: BorrowedStr| {
+            Sound::from_file(path.as_ref()).map_err(LuaError::external)
+        });
+        add_methods(methods);
+    }
+}
+
+impl UserData for Sound {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        add_methods(methods);
+    }
+}
+
+fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function("channels", |_, sound: SoundRef| Ok(sound.channels()));
+    methods.add_function("sample_rate", |_, sound: SoundRef| Ok(sound.sample_rate()));
+    methods.add_function("frames", |_, sound: SoundRef| Ok(sound.frames()));
+    methods.add_function("duration", |_, sound: SoundRef| Ok(sound.duration()));
+}
@@ -0,0 +1,32 @@
+use crate::audio::SoundSelect;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+impl FromLua for SoundSelect {
+    #[inline]
+    fn from_lua(value: Value, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::String(s) => match s.to_str()?.as_ref() {
+                "random" => Ok(Self::Random),
+                "no_repeat" => Ok(Self::NoRepeat),
+                "round_robin" => Ok(Self::RoundRobin),
+                "shuffle" => Ok(Self::Shuffle),
+                s => Err(LuaError::runtime(format!("invalid sound select {s:?}"))),
+            },
+            value => Err(LuaError::runtime(format!("invalid sound select {value:?}"))),
+        }
+    }
+}
+
+impl IntoLua for SoundSelect {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Random => "random",
+            Self::NoRepeat => "no_repeat",
+            Self::RoundRobin => "round_robin",
+            Self::Shuffle => "shuffle",
+        }
+        .into_lua(lua)
+    }
+}
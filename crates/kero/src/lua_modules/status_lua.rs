@@ -0,0 +1,30 @@
+use crate::ai::Status;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+impl FromLua for Status {
+    #[inline]
+    fn from_lua(value: Value, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::Boolean(b) => Ok(b.into()),
+            Value::String(s) => match s.to_str()?.as_ref() {
+                "success" => Ok(Self::Success),
+                "failure" => Ok(Self::Failure),
+                "running" => Ok(Self::Running),
+                s => Err(LuaError::runtime(format!("invalid status {s:?}"))),
+            },
+            value => Err(LuaError::runtime(format!("invalid status {value:?}"))),
+        }
+    }
+}
+
+impl IntoLua for Status {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Success => "success".into_lua(lua),
+            Self::Failure => "failure".into_lua(lua),
+            Self::Running => "running".into_lua(lua),
+        }
+    }
+}
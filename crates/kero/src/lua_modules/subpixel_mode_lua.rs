@@ -0,0 +1,31 @@
+use crate::gfx::SubpixelMode;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+impl FromLua for SubpixelMode {
+    #[inline]
+    fn from_lua(value: Value, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::Integer(n) if (1..=u8::MAX as i64).contains(&n) => Ok(Self::Phases(n as u8)),
+            Value::String(s) => match s.to_str()?.as_ref() {
+                "off" => Ok(Self::Off),
+                "snap" => Ok(Self::Snap),
+                s => Err(LuaError::runtime(format!("invalid subpixel mode {s:?}"))),
+            },
+            value => Err(LuaError::runtime(format!(
+                "invalid subpixel mode {value:?}"
+            ))),
+        }
+    }
+}
+
+impl IntoLua for SubpixelMode {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Off => "off".into_lua(lua),
+            Self::Snap => "snap".into_lua(lua),
+            Self::Phases(n) => n.into_lua(lua),
+        }
+    }
+}
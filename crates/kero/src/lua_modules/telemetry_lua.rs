@@ -0,0 +1,61 @@
+use crate::core::Context;
+use crate::lua::LuaModule;
+use crate::telemetry::TelemetryEvent;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, Table, UserData, UserDataMethods, Value};
+
+pub struct TelemetryModule;
+
+impl LuaModule for TelemetryModule {
+    const PATH: &'static str = "Telemetry";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for TelemetryModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("enabled", |lua, _: ()| {
+            Ok(Context::from_lua(lua).telemetry.enabled())
+        });
+        methods.add_function("set_enabled", |lua, enabled: bool| {
+            Context::from_lua(lua)
+                .telemetry
+                .set_enabled(enabled)
+                .map_err(LuaError::external)
+        });
+        methods.add_function("is_recording", |lua, _: ()| {
+            Ok(Context::from_lua(lua).telemetry.is_recording())
+        });
+        methods.add_function("session", |lua, _: ()| {
+            Ok(Context::from_lua(lua).telemetry.session().to_string())
+        });
+        methods.add_function("track", |lua, (name, props): (String, Option<Table>)| {
+            let mut event = TelemetryEvent::new(name);
+            if let Some(props) = props {
+                for pair in props.pairs::<String, Value>() {
+                    let (key, value) = pair?;
+                    let value = match value {
+                        Value::Boolean(b) => serde_json::Value::from(b),
+                        Value::Integer(i) => serde_json::Value::from(i),
+                        Value::Number(n) => serde_json::Value::from(n),
+                        Value::String(s) => serde_json::Value::from(s.to_str()?.to_string()),
+                        value => {
+                            return Err(LuaError::runtime(format!(
+                                "invalid telemetry value [{value:?}]"
+                            )));
+                        }
+                    };
+                    event.props.insert(key, value);
+                }
+            }
+            Context::from_lua(lua).telemetry.track_event(event);
+            Ok(())
+        });
+        methods.add_function("flush", |lua, _: ()| {
+            Context::from_lua(lua).telemetry.flush();
+            Ok(())
+        });
+    }
+}
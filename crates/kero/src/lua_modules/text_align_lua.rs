@@ -0,0 +1,32 @@
+use crate::gfx::TextAlign;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{FromLua, IntoLua, Lua, Value};
+
+impl FromLua for TextAlign {
+    #[inline]
+    fn from_lua(value: Value, _lua: &Lua) -> LuaResult<Self> {
+        match value {
+            Value::String(s) => match s.to_str()?.as_ref() {
+                "left" => Ok(Self::Left),
+                "center" => Ok(Self::Center),
+                "right" => Ok(Self::Right),
+                "justify" => Ok(Self::Justify),
+                s => Err(LuaError::runtime(format!("invalid text align {s:?}"))),
+            },
+            value => Err(LuaError::runtime(format!("invalid text align {value:?}"))),
+        }
+    }
+}
+
+impl IntoLua for TextAlign {
+    #[inline]
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Left => "left",
+            Self::Center => "center",
+            Self::Right => "right",
+            Self::Justify => "justify",
+        }
+        .into_lua(lua)
+    }
+}
@@ -24,6 +24,28 @@ impl LuaModule for TimeModule {
                 Ok(ctx.time.delta())
             })?,
         )?;
+        m.set(
+            "unscaled_delta",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.unscaled_delta())
+            })?,
+        )?;
+        m.set(
+            "time_scale",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.time_scale())
+            })?,
+        )?;
+        m.set(
+            "set_time_scale",
+            lua.create_function(|lua, scale: f32| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.set_time_scale(scale);
+                Ok(())
+            })?,
+        )?;
         m.set(
             "since_startup",
             lua.create_function(|lua, _: ()| {
@@ -38,6 +60,73 @@ impl LuaModule for TimeModule {
                 Ok(ctx.time.frame())
             })?,
         )?;
+        m.set(
+            "fixed_rate",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.fixed_rate())
+            })?,
+        )?;
+        m.set(
+            "set_fixed_rate",
+            lua.create_function(|lua, rate: f64| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.set_fixed_rate(rate);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "fixed_delta",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.fixed_delta())
+            })?,
+        )?;
+        m.set(
+            "fixed_frame",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.fixed_frame())
+            })?,
+        )?;
+        m.set(
+            "blend_factor",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.blend_factor())
+            })?,
+        )?;
+        m.set(
+            "paused",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.time.paused())
+            })?,
+        )?;
+        m.set(
+            "set_paused",
+            lua.create_function(|lua, paused: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.set_paused(paused);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "toggle_paused",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.toggle_paused();
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "step",
+            lua.create_function(|lua, _: ()| {
+                let ctx = Context::from_lua(lua);
+                ctx.time.step();
+                Ok(())
+            })?,
+        )?;
         m.set(
             "flicker",
             lua.create_function(|lua, (on, off): (f32, Option<f32>)| {
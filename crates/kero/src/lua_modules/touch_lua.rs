@@ -0,0 +1,86 @@
+use crate::core::Context;
+use crate::input::TouchPoint;
+use crate::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{Lua, Table, Value};
+
+pub struct TouchModule;
+
+impl LuaModule for TouchModule {
+    const PATH: &'static str = "Touch";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let m = lua.create_table()?;
+
+        m.set(
+            "count",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.count()))?,
+        )?;
+        m.set(
+            "any_down",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.any_down()))?,
+        )?;
+        m.set(
+            "points",
+            lua.create_function(|lua, _: ()| {
+                points_table(lua, Context::from_lua(lua).touch.points())
+            })?,
+        )?;
+        m.set(
+            "started",
+            lua.create_function(|lua, _: ()| {
+                points_table(lua, Context::from_lua(lua).touch.started())
+            })?,
+        )?;
+        m.set(
+            "ended",
+            lua.create_function(|lua, _: ()| {
+                points_table(lua, Context::from_lua(lua).touch.ended())
+            })?,
+        )?;
+        m.set(
+            "tap",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.tap()))?,
+        )?;
+        m.set(
+            "long_press",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.long_press()))?,
+        )?;
+        m.set(
+            "is_panning",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.is_panning()))?,
+        )?;
+        m.set(
+            "pan_delta",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.pan_delta()))?,
+        )?;
+        m.set(
+            "is_pinching",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.is_pinching()))?,
+        )?;
+        m.set(
+            "pinch_scale",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.pinch_scale()))?,
+        )?;
+        m.set(
+            "pinch_center",
+            lua.create_function(|lua, _: ()| Ok(Context::from_lua(lua).touch.pinch_center()))?,
+        )?;
+
+        Ok(Value::Table(m))
+    }
+}
+
+fn points_table(lua: &Lua, points: Vec<TouchPoint>) -> LuaResult<Table> {
+    let list = lua.create_table()?;
+    for point in points {
+        let t = lua.create_table()?;
+        t.set("id", point.id)?;
+        t.set("pos", point.pos)?;
+        t.set("start_pos", point.start_pos)?;
+        t.set("force", point.force)?;
+        t.set("held", point.held().as_secs_f32())?;
+        list.push(t)?;
+    }
+    Ok(list)
+}
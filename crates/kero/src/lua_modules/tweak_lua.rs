@@ -0,0 +1,86 @@
+use crate::color::Rgba8;
+use crate::gfx::FontRef;
+use crate::lua::LuaModule;
+use crate::math::Vec2F;
+use crate::misc::{TweakValue, Tweaks};
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, FromLua, IntoLua, Lua, UserData, UserDataMethods, Value};
+
+pub struct TweakModule;
+
+impl LuaModule for TweakModule {
+    const PATH: &'static str = "Tweak";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for TweakModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("get", |_, (name, default): (BorrowedStr, TweakValue)| {
+            Ok(Tweaks::lock().register(name.as_ref(), default).value)
+        });
+        methods.add_function("get_color", |_, (name, default): (BorrowedStr, Rgba8)| {
+            Ok(Tweaks::lock().get(name.as_ref(), default))
+        });
+        methods.add_function("set", |lua, (name, value): (BorrowedStr, Value)| {
+            let mut tweaks = Tweaks::lock();
+            let name = name.as_ref();
+            Ok(match value {
+                Value::Boolean(value) => tweaks.set(name, value),
+                Value::Integer(value) => {
+                    tweaks.set(name, value as i32) || tweaks.set(name, Rgba8::unpack(value as u32))
+                }
+                Value::Number(value) => tweaks.set(name, value as f32),
+                value => tweaks.set(name, Vec2F::from_lua(value, lua)?),
+            })
+        });
+        methods.add_function("reset", |_, name: BorrowedStr| {
+            Tweaks::lock().reset(name.as_ref());
+            Ok(())
+        });
+        methods.add_function("reset_all", |_, _: ()| {
+            Tweaks::lock().reset_all();
+            Ok(())
+        });
+        methods.add_function("overlay_enabled", |_, _: ()| {
+            Ok(Tweaks::lock().overlay_enabled())
+        });
+        methods.add_function("set_overlay_enabled", |_, enabled: bool| {
+            Tweaks::lock().set_overlay_enabled(enabled);
+            Ok(())
+        });
+        methods.add_function("toggle_overlay", |_, _: ()| {
+            Tweaks::lock().toggle_overlay();
+            Ok(())
+        });
+        methods.add_function("set_font", |_, font: Option<FontRef>| {
+            Tweaks::lock().set_font(font.map(|font| font.clone()));
+            Ok(())
+        });
+    }
+}
+
+impl FromLua for TweakValue {
+    fn from_lua(value: Value, lua: &Lua) -> LuaResult<Self> {
+        Ok(match value {
+            Value::Boolean(value) => Self::Bool(value),
+            Value::Integer(value) => Self::Int(value as i32),
+            Value::Number(value) => Self::Float(value as f32),
+            value => Self::Vec2(Vec2F::from_lua(value, lua)?),
+        })
+    }
+}
+
+impl IntoLua for TweakValue {
+    fn into_lua(self, lua: &Lua) -> LuaResult<Value> {
+        match self {
+            Self::Bool(value) => value.into_lua(lua),
+            Self::Int(value) => value.into_lua(lua),
+            Self::Float(value) => value.into_lua(lua),
+            Self::Vec2(value) => value.into_lua(lua),
+            Self::Color(value) => value.into_lua(lua),
+        }
+    }
+}
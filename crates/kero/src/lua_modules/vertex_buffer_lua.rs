@@ -21,6 +21,7 @@ impl UserData for VertexBufferModule {
             Ok(VertexBuffer::new(
                 ctx.graphics.device(),
                 ctx.graphics.queue().clone(),
+                ctx.graphics.memory(),
                 cap,
             ))
         });
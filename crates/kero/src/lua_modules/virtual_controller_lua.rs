@@ -0,0 +1,256 @@
+use crate::core::Context;
+use crate::input::{
+    ControllerBindings, GamepadAxis, GamepadButton, Key, VirtualAxis, VirtualButton,
+    VirtualController, VirtualStick,
+};
+use crate::lua::LuaModule;
+use crate::lua_modules::GamepadRef;
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{Lua, UserData, UserDataMethods, UserDataRef, Value};
+
+pub type VirtualControllerRef = UserDataRef<VirtualController>;
+pub type VirtualButtonRef = UserDataRef<VirtualButton>;
+pub type VirtualAxisRef = UserDataRef<VirtualAxis>;
+pub type VirtualStickRef = UserDataRef<VirtualStick>;
+
+type ButtonField = fn(&VirtualController) -> &VirtualButton;
+type StickField = fn(&VirtualController) -> &VirtualStick;
+
+const BUTTONS: [(&str, ButtonField); 15] = [
+    ("left_bumper", |c| &c.left_bumper),
+    ("right_bumper", |c| &c.right_bumper),
+    ("left_trigger", |c| &c.left_trigger),
+    ("right_trigger", |c| &c.right_trigger),
+    ("dpad_left", |c| &c.dpad_left),
+    ("dpad_right", |c| &c.dpad_right),
+    ("dpad_up", |c| &c.dpad_up),
+    ("dpad_down", |c| &c.dpad_down),
+    ("east", |c| &c.east),
+    ("south", |c| &c.south),
+    ("west", |c| &c.west),
+    ("north", |c| &c.north),
+    ("start", |c| &c.start),
+    ("select", |c| &c.select),
+    ("menu", |c| &c.menu),
+];
+
+const STICKS: [(&str, StickField); 3] = [
+    ("direction", |c| &c.direction),
+    ("left_stick", |c| &c.left_stick),
+    ("right_stick", |c| &c.right_stick),
+];
+
+pub struct VirtualControllerModule;
+
+impl LuaModule for VirtualControllerModule {
+    const PATH: &'static str = "VirtualController";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        lua.create_userdata(Self).map(Value::UserData)
+    }
+}
+
+impl UserData for VirtualControllerModule {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("basic", |lua, _: ()| {
+            Ok(VirtualController::basic(&Context::from_lua(lua)))
+        });
+        methods.add_function("last_active", |lua, _: ()| {
+            Ok(VirtualController::last_active(&Context::from_lua(lua)))
+        });
+        methods.add_function("specific", |lua, pad: Option<GamepadRef>| {
+            let pad = pad.map(|pad| pad.clone());
+            Ok(VirtualController::specific(&Context::from_lua(lua), pad))
+        });
+    }
+}
+
+impl UserData for VirtualController {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        for (name, field) in BUTTONS {
+            methods.add_function(name, move |_, this: VirtualControllerRef| {
+                Ok(field(&this).clone())
+            });
+        }
+        for (name, field) in STICKS {
+            methods.add_function(name, move |_, this: VirtualControllerRef| {
+                Ok(field(&this).clone())
+            });
+        }
+        methods.add_function(
+            "set_specific",
+            |_, (this, pad): (VirtualControllerRef, Option<GamepadRef>)| {
+                this.source.set_specific(pad.map(|pad| pad.clone()));
+                Ok(())
+            },
+        );
+        methods.add_function("set_last_active", |_, this: VirtualControllerRef| {
+            this.source.set_last_active();
+            Ok(())
+        });
+        methods.add_function(
+            "button",
+            |_, (this, key, btn): (VirtualControllerRef, Option<Key>, Option<GamepadButton>)| {
+                Ok(VirtualButton::new(&this.source, key, btn))
+            },
+        );
+        methods.add_function(
+            "axis",
+            |_,
+             (this, axis, neg, pos): (
+                VirtualControllerRef,
+                Option<GamepadAxis>,
+                Option<VirtualButtonRef>,
+                Option<VirtualButtonRef>,
+            )| {
+                Ok(VirtualAxis::new(
+                    &this.source,
+                    axis,
+                    neg.map(|b| b.clone()),
+                    pos.map(|b| b.clone()),
+                ))
+            },
+        );
+        methods.add_function(
+            "stick",
+            |_,
+             (this, x_axis, y_axis): (
+                VirtualControllerRef,
+                Option<VirtualAxisRef>,
+                Option<VirtualAxisRef>,
+            )| {
+                Ok(VirtualStick::new(
+                    &this.source,
+                    x_axis.map(|a| a.clone()),
+                    y_axis.map(|a| a.clone()),
+                ))
+            },
+        );
+        methods.add_function("set_bumpers_shift", |_, this: VirtualControllerRef| {
+            this.set_bumpers_shift();
+            Ok(())
+        });
+        methods.add_function("set_bumpers_qw", |_, this: VirtualControllerRef| {
+            this.set_bumpers_qw();
+            Ok(())
+        });
+        methods.add_function("set_triggers_shift", |_, this: VirtualControllerRef| {
+            this.set_triggers_shift();
+            Ok(())
+        });
+        methods.add_function("set_triggers_qw", |_, this: VirtualControllerRef| {
+            this.set_triggers_qw();
+            Ok(())
+        });
+        methods.add_function("set_face_buttons_zxas", |_, this: VirtualControllerRef| {
+            this.set_face_buttons_zxas();
+            Ok(())
+        });
+        methods.add_function("set_left_stick_arrows", |_, this: VirtualControllerRef| {
+            this.set_left_stick_arrows();
+            Ok(())
+        });
+        methods.add_function("set_dpad_arrows", |_, this: VirtualControllerRef| {
+            this.set_dpad_arrows();
+            Ok(())
+        });
+        methods.add_function("is_listening", |_, this: VirtualControllerRef| {
+            Ok(this.is_listening())
+        });
+        methods.add_function(
+            "save_bindings",
+            |_, (this, path): (VirtualControllerRef, String)| {
+                this.bindings().save(path).map_err(LuaError::external)
+            },
+        );
+        methods.add_function(
+            "load_bindings",
+            |_, (this, path): (VirtualControllerRef, String)| {
+                let bindings = ControllerBindings::load(path).map_err(LuaError::external)?;
+                this.set_bindings(&bindings);
+                Ok(())
+            },
+        );
+    }
+}
+
+impl UserData for VirtualButton {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("key", |_, this: VirtualButtonRef| Ok(this.key()));
+        methods.add_function("button", |_, this: VirtualButtonRef| Ok(this.button()));
+        methods.add_function(
+            "set_key",
+            |_, (this, key): (VirtualButtonRef, Option<Key>)| {
+                this.set_key(key);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "set_button",
+            |_, (this, btn): (VirtualButtonRef, Option<GamepadButton>)| {
+                this.set_button(btn);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "rebind",
+            |_, (this, listening): (VirtualButtonRef, bool)| {
+                this.rebind(listening);
+                Ok(())
+            },
+        );
+        methods.add_function("is_listening", |_, this: VirtualButtonRef| {
+            Ok(this.is_listening())
+        });
+        methods.add_function("poll_rebind", |_, this: VirtualButtonRef| {
+            Ok(this.poll_rebind())
+        });
+        methods.add_function("down", |_, this: VirtualButtonRef| Ok(this.down()));
+        methods.add_function("pressed", |_, this: VirtualButtonRef| Ok(this.pressed()));
+        methods.add_function("released", |_, this: VirtualButtonRef| Ok(this.released()));
+        methods.add_function("changed", |_, this: VirtualButtonRef| Ok(this.changed()));
+        methods.add_function("value", |_, this: VirtualButtonRef| Ok(this.value()));
+    }
+}
+
+impl UserData for VirtualAxis {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("axis", |_, this: VirtualAxisRef| Ok(this.axis()));
+        methods.add_function("neg", |_, this: VirtualAxisRef| Ok(this.neg()));
+        methods.add_function("pos", |_, this: VirtualAxisRef| Ok(this.pos()));
+        methods.add_function(
+            "set_axis",
+            |_, (this, axis): (VirtualAxisRef, Option<GamepadAxis>)| {
+                this.set_axis(axis);
+                Ok(())
+            },
+        );
+        methods.add_function(
+            "set_buttons",
+            |_, (this, neg, pos): (VirtualAxisRef, VirtualButtonRef, VirtualButtonRef)| {
+                this.set_buttons(neg.clone(), pos.clone());
+                Ok(())
+            },
+        );
+        methods.add_function("changed", |_, this: VirtualAxisRef| Ok(this.changed()));
+        methods.add_function("value", |_, this: VirtualAxisRef| Ok(this.value()));
+    }
+}
+
+impl UserData for VirtualStick {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_function("x_axis", |_, this: VirtualStickRef| Ok(this.x_axis()));
+        methods.add_function("y_axis", |_, this: VirtualStickRef| Ok(this.y_axis()));
+        methods.add_function(
+            "set_axes",
+            |_, (this, x_axis, y_axis): (VirtualStickRef, VirtualAxisRef, VirtualAxisRef)| {
+                this.set_axes(x_axis.clone(), y_axis.clone());
+                Ok(())
+            },
+        );
+        methods.add_function("changed", |_, this: VirtualStickRef| Ok(this.changed()));
+        methods.add_function("x", |_, this: VirtualStickRef| Ok(this.x()));
+        methods.add_function("y", |_, this: VirtualStickRef| Ok(this.y()));
+        methods.add_function("value", |_, this: VirtualStickRef| Ok(this.value()));
+    }
+}
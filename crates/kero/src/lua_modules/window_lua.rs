@@ -1,7 +1,8 @@
 use super::VideoModeRef;
 use crate::core::{Context, CursorIcon, DisplayMode, MonitorRef};
+use crate::img::DynImageRef;
 use crate::lua::LuaModule;
-use crate::math::Numeric;
+use crate::math::{Numeric, RectF, Vec2F};
 use fey_math::Vec2;
 use mlua::prelude::LuaResult;
 use mlua::{BorrowedStr, Lua, Value};
@@ -209,6 +210,20 @@ impl LuaModule for WindowModule {
                 Ok(ctx.window.set_resizable(resizable))
             })?,
         )?;
+        m.set(
+            "set_always_on_top",
+            lua.create_function(|lua, always_on_top: bool| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.window.set_always_on_top(always_on_top))
+            })?,
+        )?;
+        m.set(
+            "set_click_through",
+            lua.create_function(|lua, click_through: bool| {
+                let ctx = Context::from_lua(lua);
+                Ok(ctx.window.set_click_through(click_through))
+            })?,
+        )?;
         m.set(
             "maximized",
             lua.create_function(|lua, _: ()| {
@@ -245,6 +260,30 @@ impl LuaModule for WindowModule {
                 Ok(())
             })?,
         )?;
+        m.set(
+            "set_cursor_image",
+            lua.create_function(|lua, (img, hotspot): (DynImageRef, Vec2F)| {
+                let ctx = Context::from_lua(lua);
+                let img = img.clone().to_rgba8();
+                Ok(ctx.window.set_cursor_image(&img, hotspot.to_u32()))
+            })?,
+        )?;
+        m.set(
+            "set_ime_allowed",
+            lua.create_function(|lua, allowed: bool| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_allowed(allowed);
+                Ok(())
+            })?,
+        )?;
+        m.set(
+            "set_ime_cursor_area",
+            lua.create_function(|lua, area: RectF| {
+                let ctx = Context::from_lua(lua);
+                ctx.window.set_ime_cursor_area(area);
+                Ok(())
+            })?,
+        )?;
         Ok(Value::Table(m))
     }
 }
@@ -0,0 +1,296 @@
+use crate::misc::{UnicodeRange, UnicodeRanges};
+use std::borrow::Cow;
+use std::ops::{BitAnd, BitOr, RangeInclusive, Sub};
+
+/// A set of characters, such as the characters to pack into a font.
+///
+/// Characters are stored as sorted ranges, so large blocks of Unicode are cheap. Sets can be
+/// combined with `|` (union), `-` (difference) and `&` (intersection), and iterate their
+/// characters in order:
+///
+/// ```ignore
+/// let chars = Charset::LATIN_1 | Charset::CYRILLIC | Charset::from("€…");
+/// let font = Font::from_ttf_file(gfx, "font.ttf", 16.0, false, &chars)?;
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Charset {
+    ranges: Cow<'static, [(char, char)]>,
+}
+
+impl Charset {
+    /// An empty set.
+    pub const EMPTY: Self = Self::from_static(&[]);
+
+    /// The digits `0-9`.
+    pub const ASCII_DIGITS: Self = Self::from_static(&[('0', '9')]);
+
+    /// Printable ASCII characters, from space to `~`.
+    pub const BASIC_LATIN: Self = Self::from_static(&[(' ', '~')]);
+
+    /// Printable ASCII and the Latin-1 Supplement, covering most western European languages.
+    pub const LATIN_1: Self = Self::from_static(&[(' ', '~'), ('\u{A0}', '\u{FF}')]);
+
+    /// Latin Extended-A and Extended-B, covering central and eastern European languages.
+    pub const LATIN_EXTENDED: Self = Self::from_static(&[('\u{100}', '\u{24F}')]);
+
+    /// The Greek alphabet, with tonos and dialytika.
+    pub const GREEK: Self = Self::from_static(&[
+        ('\u{384}', '\u{38A}'),
+        ('\u{38C}', '\u{38C}'),
+        ('\u{38E}', '\u{3A1}'),
+        ('\u{3A3}', '\u{3CE}'),
+    ]);
+
+    /// The Cyrillic block.
+    pub const CYRILLIC: Self = Self::from_static(&[('\u{400}', '\u{4FF}')]);
+
+    /// Hiragana, including small kana and iteration marks.
+    pub const HIRAGANA: Self =
+        Self::from_static(&[('\u{3041}', '\u{3096}'), ('\u{3099}', '\u{309F}')]);
+
+    /// Katakana, including small kana, the middle dot and the prolonged sound mark.
+    pub const KATAKANA: Self = Self::from_static(&[('\u{30A0}', '\u{30FF}')]);
+
+    /// Create a set from sorted, non-overlapping, non-adjacent inclusive ranges.
+    #[inline]
+    pub const fn from_static(ranges: &'static [(char, char)]) -> Self {
+        Self {
+            ranges: Cow::Borrowed(ranges),
+        }
+    }
+
+    /// Create a set from ranges of characters, which may be in any order and overlap.
+    pub fn from_ranges(ranges: impl IntoIterator<Item = RangeInclusive<char>>) -> Self {
+        let mut ranges: Vec<(char, char)> = ranges
+            .into_iter()
+            .filter(|r| !r.is_empty())
+            .map(|r| (*r.start(), *r.end()))
+            .collect();
+        ranges.sort_unstable();
+        Self::from_sorted(ranges)
+    }
+
+    /// Create the exact set of characters used by a collection of strings, such as the values
+    /// of every localized string table. Packing fonts with this (plus any characters that can
+    /// be typed or generated at runtime) includes every needed glyph without packing entire
+    /// Unicode blocks.
+    ///
+    /// ```ignore
+    /// let chars = Charset::from_text(tables.iter().flat_map(|t| t.values())) | Charset::BASIC_LATIN;
+    /// ```
+    pub fn from_text<S: AsRef<str>>(strings: impl IntoIterator<Item = S>) -> Self {
+        let mut chars: Vec<char> = strings
+            .into_iter()
+            .flat_map(|s| s.as_ref().chars().collect::<Vec<_>>())
+            .filter(|chr| !chr.is_control())
+            .collect();
+        chars.sort_unstable();
+        chars.dedup();
+        Self::from_sorted(chars.into_iter().map(|chr| (chr, chr)).collect())
+    }
+
+    /// Add all the characters used by the text, ignoring control characters such as newlines.
+    #[inline]
+    pub fn insert_text(&mut self, text: &str) {
+        *self = self.union(&Self::from_text([text]));
+    }
+
+    /// Merge sorted ranges that overlap or touch.
+    fn from_sorted(ranges: Vec<(char, char)>) -> Self {
+        let mut merged: Vec<(char, char)> = Vec::with_capacity(ranges.len());
+        for (start, end) in ranges {
+            match merged.last_mut() {
+                Some(last) if start as u32 <= last.1 as u32 + 1 => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+        Self {
+            ranges: Cow::Owned(merged),
+        }
+    }
+
+    /// The ranges of characters in the set, sorted.
+    #[inline]
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<char>> + '_ {
+        self.ranges.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// How many characters are in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|&(start, end)| {
+                let (start, end) = (start as u32, end as u32);
+                let surrogates = (end.min(0xDFFF) + 1).saturating_sub(start.max(0xD800));
+                (end - start + 1 - surrogates) as usize
+            })
+            .sum()
+    }
+
+    /// If the set has no characters.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// If the character is in the set.
+    #[inline]
+    pub fn contains(&self, chr: char) -> bool {
+        let i = self.ranges.partition_point(|&(_, end)| end < chr);
+        self.ranges.get(i).is_some_and(|&(start, _)| start <= chr)
+    }
+
+    /// Iterate over all the characters in the set, in order.
+    #[inline]
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        self.ranges().flatten()
+    }
+
+    /// The characters in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges: Vec<(char, char)> = self
+            .ranges
+            .iter()
+            .chain(other.ranges.iter())
+            .copied()
+            .collect();
+        ranges.sort_unstable();
+        Self::from_sorted(ranges)
+    }
+
+    /// The characters in this set that aren't in the other.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for &(start, end) in self.ranges.iter() {
+            let mut start = Some(start as u32);
+            let end = end as u32;
+            for &(o_start, o_end) in other.ranges.iter() {
+                let (o_start, o_end) = (o_start as u32, o_end as u32);
+                let Some(s) = start else {
+                    break;
+                };
+                if o_end < s || o_start > end {
+                    continue;
+                }
+                if o_start > s {
+                    ranges.extend(char_range(s, o_start - 1));
+                }
+                start = (o_end < end).then_some(o_end + 1);
+            }
+            if let Some(s) = start {
+                ranges.extend(char_range(s, end));
+            }
+        }
+        Self::from_sorted(ranges)
+    }
+
+    /// The characters in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        for &(start, end) in self.ranges.iter() {
+            for &(o_start, o_end) in other.ranges.iter() {
+                let (s, e) = (start.max(o_start), end.min(o_end));
+                if s <= e {
+                    ranges.push((s, e));
+                }
+            }
+        }
+        ranges.sort_unstable();
+        Self::from_sorted(ranges)
+    }
+}
+
+/// The range of characters between two codes, skipping if both are surrogates.
+fn char_range(start: u32, end: u32) -> Option<(char, char)> {
+    let start = char::from_u32(start).or_else(|| char::from_u32(0xE000))?;
+    let end = char::from_u32(end).or_else(|| char::from_u32(0xD7FF))?;
+    (start <= end).then_some((start, end))
+}
+
+impl FromIterator<char> for Charset {
+    fn from_iter<T: IntoIterator<Item = char>>(iter: T) -> Self {
+        Self::from_ranges(iter.into_iter().map(|chr| chr..=chr))
+    }
+}
+
+impl From<&str> for Charset {
+    #[inline]
+    fn from(value: &str) -> Self {
+        value.chars().collect()
+    }
+}
+
+impl From<RangeInclusive<char>> for Charset {
+    #[inline]
+    fn from(value: RangeInclusive<char>) -> Self {
+        Self::from_ranges([value])
+    }
+}
+
+impl From<UnicodeRange> for Charset {
+    #[inline]
+    fn from(value: UnicodeRange) -> Self {
+        let codes = value.codes();
+        Self::from_sorted(
+            char_range(*codes.start(), *codes.end())
+                .into_iter()
+                .collect(),
+        )
+    }
+}
+
+impl From<UnicodeRanges> for Charset {
+    #[inline]
+    fn from(value: UnicodeRanges) -> Self {
+        Self::from_ranges(value.ranges().filter_map(|range| {
+            let codes = range.codes();
+            char_range(*codes.start(), *codes.end()).map(|(start, end)| start..=end)
+        }))
+    }
+}
+
+impl IntoIterator for Charset {
+    type Item = char;
+    type IntoIter = std::vec::IntoIter<char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars().collect::<Vec<char>>().into_iter()
+    }
+}
+
+impl IntoIterator for &Charset {
+    type Item = char;
+    type IntoIter = std::vec::IntoIter<char>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.chars().collect::<Vec<char>>().into_iter()
+    }
+}
+
+impl BitOr for Charset {
+    type Output = Self;
+
+    #[inline]
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(&rhs)
+    }
+}
+
+impl Sub for Charset {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(&rhs)
+    }
+}
+
+impl BitAnd for Charset {
+    type Output = Self;
+
+    #[inline]
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(&rhs)
+    }
+}
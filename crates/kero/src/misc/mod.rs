@@ -1,3 +1,13 @@
+mod charset;
+mod replay;
+mod snapshot;
+mod state_hash;
+mod tweaks;
 mod unicode;
 
+pub use charset::*;
+pub use replay::*;
+pub use snapshot::*;
+pub use state_hash::*;
+pub use tweaks::*;
 pub use unicode::*;
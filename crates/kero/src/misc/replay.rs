@@ -0,0 +1,306 @@
+use crate::color::Rgba8;
+use crate::gfx::Draw;
+use crate::input::{Key, Keyboard, Mouse};
+use crate::math::{RectF, vec2};
+use crate::misc::{Snapshot, SnapshotError, SnapshotValue};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// A recording of a deterministic game, made of the input for every frame plus periodic
+/// [`Snapshot`]s of the game state.
+///
+/// The game defines its own per-frame input type `I` and state type `S`, and must produce the
+/// same state when given the same inputs. Any frame can then be reconstructed by restoring the
+/// nearest snapshot before it and fast-forwarding through the recorded inputs.
+///
+/// Replays are snapshots themselves, so they can be saved to a file and attached to bug reports.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound(serialize = "I: Serialize", deserialize = "I: Deserialize<'de>"))]
+pub struct Replay<I, S> {
+    inputs: Vec<I>,
+    snapshots: Vec<(usize, SnapshotValue)>,
+    snapshot_interval: usize,
+    #[serde(skip)]
+    marker: PhantomData<fn() -> S>,
+}
+
+impl<I, S: Snapshot> Replay<I, S> {
+    /// Create a new empty replay, which snapshots the game state every `snapshot_interval`
+    /// frames. Shorter intervals make seeking faster but the replay larger.
+    #[inline]
+    pub fn new(snapshot_interval: usize) -> Self {
+        Self {
+            inputs: Vec::new(),
+            snapshots: Vec::new(),
+            snapshot_interval: snapshot_interval.max(1),
+            marker: PhantomData,
+        }
+    }
+
+    /// How many frames were recorded.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.inputs.len()
+    }
+
+    /// If no frames were recorded.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.inputs.is_empty()
+    }
+
+    /// How many frames pass between snapshots.
+    #[inline]
+    pub fn snapshot_interval(&self) -> usize {
+        self.snapshot_interval
+    }
+
+    /// The recorded input for every frame.
+    #[inline]
+    pub fn inputs(&self) -> &[I] {
+        &self.inputs
+    }
+
+    /// The recorded input for the frame.
+    #[inline]
+    pub fn input(&self, frame: usize) -> Option<&I> {
+        self.inputs.get(frame)
+    }
+
+    /// Frames that have a snapshot, in order.
+    #[inline]
+    pub fn snapshot_frames(&self) -> impl Iterator<Item = usize> {
+        self.snapshots.iter().map(|(frame, _)| *frame)
+    }
+
+    /// Record the next frame, where `state` is the game state before `input` is applied to it.
+    /// The state is snapshotted if this frame lands on the snapshot interval.
+    pub fn record(&mut self, state: &S, input: I) -> Result<(), SnapshotError> {
+        let frame = self.inputs.len();
+        if frame.is_multiple_of(self.snapshot_interval) {
+            self.snapshots.push((frame, state.to_snapshot_value()?));
+        }
+        self.inputs.push(input);
+        Ok(())
+    }
+
+    /// Remove all frames from `frame` onwards, so recording can resume from there.
+    pub fn truncate(&mut self, frame: usize) {
+        self.inputs.truncate(frame);
+        self.snapshots.retain(|(f, _)| *f < frame);
+    }
+
+    /// Reconstruct the game state at the start of the frame, by restoring the nearest snapshot
+    /// before it and then calling `step` with each recorded input up to the frame. Returns
+    /// `None` if nothing has been recorded.
+    pub fn seek(
+        &self,
+        frame: usize,
+        mut step: impl FnMut(&mut S, &I),
+    ) -> Result<Option<S>, SnapshotError> {
+        let frame = frame.min(self.len());
+        let Some((start, data)) = self.snapshots.iter().rev().find(|(f, _)| *f <= frame) else {
+            return Ok(None);
+        };
+        let mut state = S::from_snapshot_value(data.clone())?;
+        for input in &self.inputs[*start..frame] {
+            step(&mut state, input);
+        }
+        Ok(Some(state))
+    }
+}
+
+impl<I: Serialize + DeserializeOwned, S: Snapshot> Snapshot for Replay<I, S> {}
+
+/// Plays back a [`Replay`], with controls for pausing, stepping, changing speed and seeking.
+///
+/// The game's `step` function, which applies one frame of input to the state, is passed into
+/// each method that needs to advance the state.
+#[derive(Debug, Clone)]
+pub struct ReplayPlayer<I, S> {
+    replay: Replay<I, S>,
+    state: Option<S>,
+    frame: usize,
+    accum: f32,
+
+    /// If playback is paused.
+    pub paused: bool,
+
+    /// How many frames are played per update. Can be fractional to play in slow motion.
+    pub speed: f32,
+}
+
+impl<I, S: Snapshot> ReplayPlayer<I, S> {
+    /// Create a player starting at the first frame of the replay.
+    pub fn new(replay: Replay<I, S>) -> Result<Self, SnapshotError> {
+        let state = replay.seek(0, |_, _| {})?;
+        Ok(Self {
+            replay,
+            state,
+            frame: 0,
+            accum: 0.0,
+            paused: false,
+            speed: 1.0,
+        })
+    }
+
+    /// The replay being played.
+    #[inline]
+    pub fn replay(&self) -> &Replay<I, S> {
+        &self.replay
+    }
+
+    /// Stop playback and return the replay.
+    #[inline]
+    pub fn into_replay(self) -> Replay<I, S> {
+        self.replay
+    }
+
+    /// The game state at the current frame, or `None` if the replay is empty.
+    #[inline]
+    pub fn state(&self) -> Option<&S> {
+        self.state.as_ref()
+    }
+
+    /// The current frame.
+    #[inline]
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// If playback has reached the last frame.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.replay.len()
+    }
+
+    /// Playback progress, from `0.0` to `1.0`.
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        match self.replay.len() {
+            0 => 1.0,
+            len => self.frame as f32 / len as f32,
+        }
+    }
+
+    /// Advance playback by [`speed`](Self::speed) frames, unless paused or finished.
+    pub fn update(&mut self, mut step: impl FnMut(&mut S, &I)) {
+        if self.paused {
+            return;
+        }
+        self.accum += self.speed.max(0.0);
+        while self.accum >= 1.0 && !self.is_finished() {
+            self.accum -= 1.0;
+            self.step_forward(&mut step);
+        }
+        if self.is_finished() {
+            self.accum = 0.0;
+        }
+    }
+
+    /// Advance by a single frame.
+    pub fn step_forward(&mut self, mut step: impl FnMut(&mut S, &I)) {
+        if let (Some(state), Some(input)) = (&mut self.state, self.replay.input(self.frame)) {
+            step(state, input);
+            self.frame += 1;
+        }
+    }
+
+    /// Go back a single frame.
+    #[inline]
+    pub fn step_back(&mut self, step: impl FnMut(&mut S, &I)) -> Result<(), SnapshotError> {
+        self.seek(self.frame.saturating_sub(1), step)
+    }
+
+    /// Jump to the frame.
+    pub fn seek(
+        &mut self,
+        frame: usize,
+        mut step: impl FnMut(&mut S, &I),
+    ) -> Result<(), SnapshotError> {
+        let frame = frame.min(self.replay.len());
+        if frame == self.frame {
+            return Ok(());
+        }
+
+        // close enough ahead that stepping forward is cheaper than restoring a snapshot
+        if frame > self.frame && frame - self.frame < self.replay.snapshot_interval {
+            while self.frame < frame && self.state.is_some() {
+                self.step_forward(&mut step);
+            }
+            self.accum = 0.0;
+            return Ok(());
+        }
+
+        self.state = self.replay.seek(frame, step)?;
+        self.frame = frame;
+        self.accum = 0.0;
+        Ok(())
+    }
+
+    /// Handle the default keyboard controls: `Space` toggles pause, `Left` and `Right` step
+    /// while paused, `Up` and `Down` double or halve the speed, and `Home` restarts.
+    pub fn handle_keys(
+        &mut self,
+        keyboard: &Keyboard,
+        mut step: impl FnMut(&mut S, &I),
+    ) -> Result<(), SnapshotError> {
+        if keyboard.pressed(Key::Space) {
+            self.paused = !self.paused;
+        }
+        if keyboard.pressed(Key::ArrowUp) {
+            self.speed = (self.speed * 2.0).min(64.0);
+        }
+        if keyboard.pressed(Key::ArrowDown) {
+            self.speed = (self.speed * 0.5).max(1.0 / 64.0);
+        }
+        if keyboard.pressed(Key::Home) {
+            self.seek(0, &mut step)?;
+        }
+        if self.paused {
+            if keyboard.pressed_or_repeated(Key::ArrowRight) {
+                self.step_forward(&mut step);
+            }
+            if keyboard.pressed_or_repeated(Key::ArrowLeft) {
+                self.step_back(&mut step)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Seek to the frame under the mouse while the left button is held over the timeline.
+    pub fn handle_timeline(
+        &mut self,
+        mouse: &Mouse,
+        rect: impl Into<RectF>,
+        step: impl FnMut(&mut S, &I),
+    ) -> Result<(), SnapshotError> {
+        let rect = rect.into();
+        let pos = mouse.pos();
+        if !mouse.left_down() || !rect.contains(pos) || rect.w <= 0.0 {
+            return Ok(());
+        }
+        let t = ((pos.x - rect.x) / rect.w).clamp(0.0, 1.0);
+        let frame = (t * self.replay.len() as f32).round() as usize;
+        self.seek(frame, step)
+    }
+
+    /// Draw a timeline bar showing playback progress, with a tick for each snapshot.
+    pub fn draw_timeline(&self, draw: &mut Draw, rect: impl Into<RectF>, color: Rgba8) {
+        let rect = rect.into();
+        let len = self.replay.len().max(1) as f32;
+        draw.rect(
+            RectF::new(rect.x, rect.y, rect.w * self.progress(), rect.h),
+            color,
+        );
+        for frame in self.replay.snapshot_frames() {
+            let x = rect.x + rect.w * (frame as f32 / len);
+            draw.line(
+                (vec2(x, rect.y + rect.h * 0.5), vec2(x, rect.bottom())),
+                color,
+            );
+        }
+        draw.rect_outline(rect, color);
+    }
+}
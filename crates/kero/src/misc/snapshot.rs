@@ -0,0 +1,123 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Untyped snapshot data, passed to [`Snapshot::migrate`] so old saves can be reshaped before
+/// they are deserialized.
+pub type SnapshotValue = serde_json::Value;
+
+/// An error saving or loading a [`Snapshot`].
+#[derive(Debug, thiserror::Error)]
+pub enum SnapshotError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("snapshot version {found} is newer than the supported version {current}")]
+    FutureVersion { found: u32, current: u32 },
+
+    #[error("no migration from snapshot version {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("snapshot migration from version {version} failed: {message}")]
+    Migration { version: u32, message: String },
+}
+
+/// Game state that can be saved to and loaded from a versioned snapshot.
+///
+/// Any type that derives `Serialize` and `Deserialize` can implement this with an empty `impl`.
+/// Snapshots can contain entity data, grids, [`Rand`](crate::rand::Rand) seeds, timers, or
+/// anything else serde can handle. When the layout of the type changes, increase its
+/// [`VERSION`](Self::VERSION) and upgrade older saves in [`migrate`](Self::migrate).
+///
+/// ```ignore
+/// #[derive(Serialize, Deserialize)]
+/// struct SaveData {
+///     level: VecGrid<Tile>,
+///     rng: Rand,
+///     play_time: f32,
+/// }
+///
+/// impl Snapshot for SaveData {
+///     const VERSION: u32 = 2;
+///
+///     fn migrate(version: u32, data: &mut SnapshotValue) -> Result<(), SnapshotError> {
+///         match version {
+///             // version 2 added the play timer
+///             1 => data["play_time"] = 0.0.into(),
+///             _ => return Err(SnapshotError::UnsupportedVersion(version)),
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait Snapshot: Serialize + DeserializeOwned {
+    /// The current version of the snapshot's layout.
+    const VERSION: u32 = 1;
+
+    /// Upgrade snapshot data from `version` to `version + 1`. When loading, this is called for
+    /// each version between the saved one and [`VERSION`](Self::VERSION), in order.
+    #[inline]
+    fn migrate(version: u32, data: &mut SnapshotValue) -> Result<(), SnapshotError> {
+        let _ = data;
+        Err(SnapshotError::UnsupportedVersion(version))
+    }
+
+    /// Serialize the snapshot, tagged with its current version.
+    #[inline]
+    fn to_snapshot_value(&self) -> Result<SnapshotValue, SnapshotError> {
+        Ok(serde_json::to_value(SnapshotFile {
+            version: Self::VERSION,
+            data: self,
+        })?)
+    }
+
+    /// Deserialize a snapshot, migrating it from older versions if required.
+    fn from_snapshot_value(value: SnapshotValue) -> Result<Self, SnapshotError> {
+        let SnapshotFile { version, mut data } =
+            serde_json::from_value::<SnapshotFile<SnapshotValue>>(value)?;
+        if version > Self::VERSION {
+            return Err(SnapshotError::FutureVersion {
+                found: version,
+                current: Self::VERSION,
+            });
+        }
+        for version in version..Self::VERSION {
+            Self::migrate(version, &mut data)?;
+        }
+        Ok(serde_json::from_value(data)?)
+    }
+
+    /// Serialize the snapshot to bytes.
+    #[inline]
+    fn to_snapshot_bytes(&self) -> Result<Vec<u8>, SnapshotError> {
+        Ok(serde_json::to_vec(&self.to_snapshot_value()?)?)
+    }
+
+    /// Deserialize a snapshot from bytes, migrating it from older versions if required.
+    #[inline]
+    fn from_snapshot_bytes(bytes: &[u8]) -> Result<Self, SnapshotError> {
+        Self::from_snapshot_value(serde_json::from_slice(bytes)?)
+    }
+
+    /// Save the snapshot to a file.
+    #[inline]
+    fn save_snapshot(&self, path: impl AsRef<Path>) -> Result<(), SnapshotError> {
+        Ok(std::fs::write(path, self.to_snapshot_bytes()?)?)
+    }
+
+    /// Load a snapshot from a file, migrating it from older versions if required.
+    #[inline]
+    fn load_snapshot(path: impl AsRef<Path>) -> Result<Self, SnapshotError> {
+        Self::from_snapshot_bytes(&std::fs::read(path)?)
+    }
+}
+
+/// The on-disk layout of a snapshot.
+#[derive(Serialize, Deserialize)]
+struct SnapshotFile<D> {
+    version: u32,
+    data: D,
+}
@@ -0,0 +1,461 @@
+use crate::color::Rgba8;
+use crate::math::{Rect, Vec2, Vec3, Vec4};
+use crate::misc::Snapshot;
+use crate::rand::Rand;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::hash::Hasher;
+
+/// Game state that can be hashed to check that two runs stayed in sync.
+///
+/// Unlike [`Hash`], floats are hashed by their exact bits, so any difference in simulation is
+/// caught. Implementations should hash everything that affects the simulation, in a fixed
+/// order, and skip anything purely visual.
+///
+/// ```ignore
+/// impl HashState for Player {
+///     fn hash_state(&self, hasher: &mut StateHasher) {
+///         self.pos.hash_state(hasher);
+///         self.vel.hash_state(hasher);
+///         self.health.hash_state(hasher);
+///     }
+/// }
+/// ```
+pub trait HashState {
+    fn hash_state(&self, hasher: &mut StateHasher);
+}
+
+/// A hasher that produces the same hashes on every platform, build, and run, using FNV-1a.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StateHasher(u64);
+
+impl StateHasher {
+    const OFFSET: u64 = 0xcbf29ce484222325;
+    const PRIME: u64 = 0x100000001b3;
+
+    /// Create a hasher that continues from a previous hash.
+    #[inline]
+    pub fn with_seed(seed: u64) -> Self {
+        Self(seed)
+    }
+
+    /// Hash a value.
+    #[inline]
+    pub fn hash<T: HashState + ?Sized>(&mut self, value: &T) {
+        value.hash_state(self);
+    }
+}
+
+impl Default for StateHasher {
+    #[inline]
+    fn default() -> Self {
+        Self(Self::OFFSET)
+    }
+}
+
+/// Implement [`Hasher`] methods that write an integer's little-endian bytes.
+macro_rules! write_le {
+    ($($write:ident: $ty:ty),* $(,)?) => {
+        $(
+            #[inline]
+            fn $write(&mut self, i: $ty) {
+                self.write(&i.to_le_bytes());
+            }
+        )*
+    };
+}
+
+impl Hasher for StateHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(Self::PRIME);
+        }
+    }
+
+    // the default methods write native-endian bytes, so integers are written little-endian to
+    // get the same hashes on big-endian platforms
+    write_le!(
+        write_u8: u8,
+        write_u16: u16,
+        write_u32: u32,
+        write_u64: u64,
+        write_u128: u128,
+        write_i8: i8,
+        write_i16: i16,
+        write_i32: i32,
+        write_i64: i64,
+        write_i128: i128,
+    );
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        // hash as 64 bits so 32 and 64 bit platforms agree
+        self.write_u64(i as u64);
+    }
+
+    #[inline]
+    fn write_isize(&mut self, i: isize) {
+        self.write_i64(i as i64);
+    }
+}
+
+macro_rules! impl_hash_state {
+    ($($ty:ty => $write:ident),* $(,)?) => {
+        $(
+            impl HashState for $ty {
+                #[inline]
+                fn hash_state(&self, hasher: &mut StateHasher) {
+                    hasher.$write((*self).into());
+                }
+            }
+        )*
+    };
+}
+
+impl_hash_state!(
+    bool => write_u8,
+    u8 => write_u8,
+    i8 => write_i8,
+    u16 => write_u16,
+    i16 => write_i16,
+    u32 => write_u32,
+    i32 => write_i32,
+    char => write_u32,
+    u64 => write_u64,
+    i64 => write_i64,
+    u128 => write_u128,
+    i128 => write_i128,
+);
+
+impl HashState for usize {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        // hash as 64 bits so 32 and 64 bit platforms agree
+        hasher.write_u64(*self as u64);
+    }
+}
+
+impl HashState for isize {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_i64(*self as i64);
+    }
+}
+
+impl HashState for f32 {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.to_bits());
+    }
+}
+
+impl HashState for f64 {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u64(self.to_bits());
+    }
+}
+
+impl HashState for str {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.len().hash_state(hasher);
+        hasher.write(self.as_bytes());
+    }
+}
+
+impl HashState for String {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.as_str().hash_state(hasher);
+    }
+}
+
+impl<T: HashState + ?Sized> HashState for &T {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        (**self).hash_state(hasher);
+    }
+}
+
+impl<T: HashState + ?Sized> HashState for Box<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        (**self).hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for [T] {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.len().hash_state(hasher);
+        for value in self {
+            value.hash_state(hasher);
+        }
+    }
+}
+
+impl<T: HashState, const N: usize> HashState for [T; N] {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.as_slice().hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Vec<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.as_slice().hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Option<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        match self {
+            Some(value) => {
+                hasher.write_u8(1);
+                value.hash_state(hasher);
+            }
+            None => hasher.write_u8(0),
+        }
+    }
+}
+
+impl<A: HashState, B: HashState> HashState for (A, B) {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.0.hash_state(hasher);
+        self.1.hash_state(hasher);
+    }
+}
+
+impl<A: HashState, B: HashState, C: HashState> HashState for (A, B, C) {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.0.hash_state(hasher);
+        self.1.hash_state(hasher);
+        self.2.hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Vec2<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.x.hash_state(hasher);
+        self.y.hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Vec3<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.x.hash_state(hasher);
+        self.y.hash_state(hasher);
+        self.z.hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Vec4<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.x.hash_state(hasher);
+        self.y.hash_state(hasher);
+        self.z.hash_state(hasher);
+        self.w.hash_state(hasher);
+    }
+}
+
+impl<T: HashState> HashState for Rect<T> {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        self.x.hash_state(hasher);
+        self.y.hash_state(hasher);
+        self.w.hash_state(hasher);
+        self.h.hash_state(hasher);
+    }
+}
+
+impl HashState for Rgba8 {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u32(self.pack());
+    }
+}
+
+impl HashState for Rand {
+    #[inline]
+    fn hash_state(&self, hasher: &mut StateHasher) {
+        hasher.write_u64(self.0);
+    }
+}
+
+/// The hashes of every frame of a run, which can be saved and compared against another run.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateHashes(pub Vec<u64>);
+
+impl Snapshot for StateHashes {}
+
+impl StateHashes {
+    /// Find the first frame where the hashes differ from another run. If one run is longer than
+    /// the other, only the frames they share are compared.
+    pub fn compare(&self, other: &StateHashes) -> Option<Divergence> {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .position(|(a, b)| a != b)
+            .map(|frame| Divergence {
+                frame,
+                expected: other.0[frame],
+                found: self.0[frame],
+            })
+    }
+}
+
+/// The first frame where two runs stopped producing the same state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub frame: usize,
+    pub expected: u64,
+    pub found: u64,
+}
+
+impl Display for Divergence {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "state diverged on frame {}: expected hash {:016x}, found {:016x}",
+            self.frame, self.expected, self.found
+        )
+    }
+}
+
+/// An opt-in audit that hashes game state every update, to find where two runs of a
+/// deterministic game, such as a live game and its [`Replay`](super::Replay), stop agreeing.
+///
+/// Call [`record`](Self::record) once per update with everything that affects the simulation.
+/// Each frame's hash includes the previous one, so a desync is caught on the frame it happens
+/// and stays caught. Save the [`hashes`](Self::hashes) of a known good run, then load them as
+/// the reference of another run, and the first frame that differs is logged.
+///
+/// ```ignore
+/// fn update(&mut self, ctx: &Context) -> Result<(), GameError> {
+///     self.world.step(self.input());
+///     self.audit.record(&(&self.world, &self.rng));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct DeterminismAudit {
+    enabled: bool,
+    hashes: StateHashes,
+    reference: Option<StateHashes>,
+    divergence: Option<Divergence>,
+}
+
+impl DeterminismAudit {
+    /// Create an audit, which does nothing unless enabled.
+    #[inline]
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// If state is being recorded.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Set if state is being recorded.
+    #[inline]
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// The hashes recorded so far, one per frame.
+    #[inline]
+    pub fn hashes(&self) -> &StateHashes {
+        &self.hashes
+    }
+
+    /// How many frames were recorded.
+    #[inline]
+    pub fn frame(&self) -> usize {
+        self.hashes.0.len()
+    }
+
+    /// The hash of the last recorded frame.
+    #[inline]
+    pub fn last_hash(&self) -> Option<u64> {
+        self.hashes.0.last().copied()
+    }
+
+    /// The hashes of another run that each frame is compared against.
+    #[inline]
+    pub fn reference(&self) -> Option<&StateHashes> {
+        self.reference.as_ref()
+    }
+
+    /// Compare each frame against the hashes of another run.
+    pub fn set_reference(&mut self, reference: Option<StateHashes>) {
+        self.divergence = None;
+        self.reference = reference;
+        if let Some(reference) = &self.reference {
+            self.divergence = self.hashes.compare(reference);
+        }
+    }
+
+    /// The first frame that didn't match the reference, if any.
+    #[inline]
+    pub fn divergence(&self) -> Option<Divergence> {
+        self.divergence
+    }
+
+    /// Hash the state for the next frame and return the hash, or `None` if not enabled. The
+    /// first frame that doesn't match the reference is logged.
+    pub fn record<T: HashState + ?Sized>(&mut self, state: &T) -> Option<u64> {
+        if !self.enabled {
+            return None;
+        }
+        let mut hasher = match self.last_hash() {
+            Some(prev) => StateHasher::with_seed(prev),
+            None => StateHasher::default(),
+        };
+        state.hash_state(&mut hasher);
+        let hash = hasher.finish();
+
+        let frame = self.frame();
+        self.hashes.0.push(hash);
+        let expected = self
+            .reference
+            .as_ref()
+            .and_then(|r| r.0.get(frame).copied());
+        if let (None, Some(expected)) = (self.divergence, expected)
+            && expected != hash
+        {
+            let divergence = Divergence {
+                frame,
+                expected,
+                found: hash,
+            };
+            log::warn!("{divergence}");
+            self.divergence = Some(divergence);
+        }
+        Some(hash)
+    }
+
+    /// Forget all recorded frames, keeping the reference.
+    pub fn clear(&mut self) {
+        self.hashes.0.clear();
+        self.divergence = None;
+    }
+}
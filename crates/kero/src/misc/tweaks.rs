@@ -0,0 +1,381 @@
+use crate::color::Rgba8;
+use crate::gfx::{Draw, Font};
+use crate::input::{Key, Keyboard};
+use crate::math::{RectF, Vec2F, vec2};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+
+/// Get the live value of a tweakable variable, registering it with its default the first
+/// time. See [`Tweaks`].
+///
+/// ```ignore
+/// let jump_height = tweak!("player.jump_height", 4.0);
+/// ```
+#[macro_export]
+macro_rules! tweak {
+    ($name:expr, $default:expr) => {
+        $crate::misc::Tweaks::lock().get($name, $default)
+    };
+}
+
+/// A value that can be tweaked at runtime.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TweakValue {
+    Bool(bool),
+    Int(i32),
+    Float(f32),
+    Vec2(Vec2F),
+    Color(Rgba8),
+}
+
+/// A type that can be used as a tweakable variable.
+pub trait Tweakable: Sized {
+    fn into_tweak(self) -> TweakValue;
+    fn from_tweak(value: TweakValue) -> Option<Self>;
+}
+
+macro_rules! impl_tweakable {
+    ($ty:ty, $var:ident) => {
+        impl Tweakable for $ty {
+            #[inline]
+            fn into_tweak(self) -> TweakValue {
+                TweakValue::$var(self)
+            }
+
+            #[inline]
+            fn from_tweak(value: TweakValue) -> Option<Self> {
+                match value {
+                    TweakValue::$var(value) => Some(value),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_tweakable!(bool, Bool);
+impl_tweakable!(i32, Int);
+impl_tweakable!(f32, Float);
+impl_tweakable!(Vec2F, Vec2);
+impl_tweakable!(Rgba8, Color);
+
+impl TweakValue {
+    /// If the value has the same type as the other.
+    #[inline]
+    pub fn same_type(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    /// Step the value up or down by `amount` steps. Floats step by a tenth of their default,
+    /// integers by one, and bools flip. Vectors and colors are left unchanged.
+    fn step(self, default: Self, amount: i32) -> Self {
+        match (self, default) {
+            (Self::Bool(v), _) => Self::Bool(v ^ (amount % 2 != 0)),
+            (Self::Int(v), _) => Self::Int(v.saturating_add(amount)),
+            (Self::Float(v), Self::Float(d)) => {
+                let step = match d.abs() > f32::EPSILON {
+                    true => d.abs() * 0.1,
+                    false => 0.1,
+                };
+                Self::Float(v + step * amount as f32)
+            }
+            (value, _) => value,
+        }
+    }
+}
+
+/// An error loading or saving [`Tweaks`].
+#[derive(Debug, thiserror::Error)]
+pub enum TweakError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// A registered tweakable variable.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Tweak {
+    pub value: TweakValue,
+    pub default: TweakValue,
+}
+
+/// A global registry of variables that can be adjusted while the game runs, to speed up
+/// tuning gameplay without recompiling.
+///
+/// Variables are registered the first time they are read with [`tweak!`], and return their
+/// live value every time after. While the overlay is enabled, variables can be selected with
+/// the up and down arrow keys, adjusted with left and right (hold `Shift` for bigger steps),
+/// and reset with `Backspace`.
+///
+/// If the game was built with
+/// [`GameBuilder::with_tweaks_file`](crate::core::GameBuilder::with_tweaks_file), changed
+/// values are saved to the file and loaded again on the next run.
+#[derive(Debug, Default)]
+pub struct Tweaks {
+    vars: BTreeMap<String, Tweak>,
+    loaded: BTreeMap<String, TweakValue>,
+    file: Option<PathBuf>,
+    changed: bool,
+    overlay: bool,
+    selected: usize,
+    font: Option<Font>,
+    build_label: String,
+}
+
+static TWEAKS: LazyLock<Mutex<Tweaks>> = LazyLock::new(Default::default);
+
+impl Tweaks {
+    /// Lock the global registry.
+    #[inline]
+    pub fn lock() -> MutexGuard<'static, Tweaks> {
+        TWEAKS.lock().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Get the value of a variable, registering it with the default if it doesn't exist. If
+    /// the variable exists with a different type, the default is returned.
+    pub fn get<T: Tweakable>(&mut self, name: &str, default: T) -> T {
+        let default = default.into_tweak();
+        let tweak = self.register(name, default);
+        T::from_tweak(tweak.value).unwrap_or_else(|| T::from_tweak(default).unwrap())
+    }
+
+    /// Register a variable if it doesn't exist, using its saved value if there is one.
+    pub fn register(&mut self, name: &str, default: TweakValue) -> Tweak {
+        if let Some(tweak) = self.vars.get(name) {
+            return *tweak;
+        }
+        let value = self
+            .loaded
+            .remove(name)
+            .filter(|value| value.same_type(&default))
+            .unwrap_or(default);
+        let tweak = Tweak { value, default };
+        self.vars.insert(name.to_string(), tweak);
+        tweak
+    }
+
+    /// Set the value of a registered variable. Returns false if it doesn't exist or has a
+    /// different type.
+    pub fn set(&mut self, name: &str, value: impl Tweakable) -> bool {
+        let value = value.into_tweak();
+        match self.vars.get_mut(name) {
+            Some(tweak) if tweak.value.same_type(&value) => {
+                self.changed |= tweak.value != value;
+                tweak.value = value;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Reset a variable to its default value.
+    pub fn reset(&mut self, name: &str) {
+        if let Some(tweak) = self.vars.get_mut(name) {
+            self.changed |= tweak.value != tweak.default;
+            tweak.value = tweak.default;
+        }
+    }
+
+    /// Reset all variables to their default values.
+    pub fn reset_all(&mut self) {
+        for tweak in self.vars.values_mut() {
+            self.changed |= tweak.value != tweak.default;
+            tweak.value = tweak.default;
+        }
+    }
+
+    /// Iterate over all registered variables, sorted by name.
+    #[inline]
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &Tweak)> {
+        self.vars.iter().map(|(name, tweak)| (name.as_str(), tweak))
+    }
+
+    /// Load saved values from a JSON file. Registered variables are changed immediately, and
+    /// the rest are applied when they are registered. A missing file is not an error.
+    pub fn load_file(&mut self, path: impl AsRef<Path>) -> Result<(), TweakError> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(err) => return Err(err.into()),
+        };
+        let values: BTreeMap<String, TweakValue> = serde_json::from_slice(&bytes)?;
+        for (name, value) in values {
+            match self.vars.get_mut(&name) {
+                Some(tweak) if tweak.value.same_type(&value) => tweak.value = value,
+                Some(_) => {}
+                None => {
+                    self.loaded.insert(name, value);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Save the values of all variables that differ from their defaults to a JSON file.
+    pub fn save_file(&self, path: impl AsRef<Path>) -> Result<(), TweakError> {
+        let mut values = self.loaded.clone();
+        for (name, tweak) in &self.vars {
+            if tweak.value != tweak.default {
+                values.insert(name.clone(), tweak.value);
+            }
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(&values)?)?;
+        Ok(())
+    }
+
+    /// The file values are persisted to.
+    #[inline]
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// Set the file values are persisted to, loading any values it already has.
+    pub fn set_file(&mut self, path: impl Into<PathBuf>) -> Result<(), TweakError> {
+        let path = path.into();
+        self.load_file(&path)?;
+        self.file = Some(path);
+        Ok(())
+    }
+
+    /// Save to the persisted file if any values changed since it was last saved.
+    pub fn save_changes(&mut self) -> Result<(), TweakError> {
+        if let (true, Some(path)) = (self.changed, &self.file) {
+            self.save_file(path)?;
+            self.changed = false;
+        }
+        Ok(())
+    }
+
+    /// If the overlay is drawn and listening to input.
+    #[inline]
+    pub fn overlay_enabled(&self) -> bool {
+        self.overlay
+    }
+
+    /// Set if the overlay is drawn and listening to input.
+    #[inline]
+    pub fn set_overlay_enabled(&mut self, enabled: bool) {
+        self.overlay = enabled;
+    }
+
+    /// Show the overlay if hidden, otherwise hide it.
+    #[inline]
+    pub fn toggle_overlay(&mut self) {
+        self.overlay = !self.overlay;
+    }
+
+    /// Set the font the overlay is drawn with. The overlay isn't drawn without one.
+    #[inline]
+    pub fn set_font(&mut self, font: Option<Font>) {
+        self.font = font;
+    }
+
+    /// Set the line describing the game's build, drawn at the top of the overlay.
+    #[inline]
+    pub(crate) fn set_build_label(&mut self, label: String) {
+        self.build_label = label;
+    }
+
+    /// Select and adjust variables with the keyboard, if the overlay is enabled.
+    pub fn update_overlay(&mut self, keyboard: &Keyboard) {
+        if !self.overlay || self.vars.is_empty() {
+            return;
+        }
+        let len = self.vars.len();
+        if keyboard.pressed_or_repeated(Key::ArrowUp) {
+            self.selected = (self.selected + len - 1) % len;
+        }
+        if keyboard.pressed_or_repeated(Key::ArrowDown) {
+            self.selected = (self.selected + 1) % len;
+        }
+        self.selected = self.selected.min(len - 1);
+
+        let mut amount = 0;
+        if keyboard.pressed_or_repeated(Key::ArrowLeft) {
+            amount -= 1;
+        }
+        if keyboard.pressed_or_repeated(Key::ArrowRight) {
+            amount += 1;
+        }
+        if keyboard.shift() {
+            amount *= 10;
+        }
+        let reset = keyboard.pressed(Key::Backspace);
+
+        if let Some(tweak) = self.vars.values_mut().nth(self.selected) {
+            let value = match reset {
+                true => tweak.default,
+                false => tweak.value.step(tweak.default, amount),
+            };
+            self.changed |= tweak.value != value;
+            tweak.value = value;
+        }
+    }
+
+    /// Draw the overlay with its top-left at `pos`, if it is enabled and has a font.
+    pub fn draw_overlay(&self, draw: &mut Draw, pos: impl Into<Vec2F>, color: Rgba8) {
+        let (true, Some(font)) = (self.overlay, &self.font) else {
+            return;
+        };
+        let pos = pos.into();
+        let header = usize::from(!self.build_label.is_empty());
+        let mut lines: Vec<String> = match self.vars.is_empty() {
+            true => vec!["no tweaks registered".to_string()],
+            false => self
+                .vars
+                .iter()
+                .map(|(name, tweak)| {
+                    let value = match tweak.value {
+                        TweakValue::Bool(v) => v.to_string(),
+                        TweakValue::Int(v) => v.to_string(),
+                        TweakValue::Float(v) => format!("{v:.3}"),
+                        TweakValue::Vec2(v) => format!("({v:.3})"),
+                        TweakValue::Color(v) => format!("#{:08X}", v.pack()),
+                    };
+                    let changed = if tweak.value != tweak.default {
+                        "*"
+                    } else {
+                        ""
+                    };
+                    format!("{name}: {value}{changed}")
+                })
+                .collect(),
+        };
+        if header > 0 {
+            lines.insert(0, self.build_label.clone());
+        }
+
+        let size = font.size();
+        let line_height = size * 1.25;
+        let width = lines
+            .iter()
+            .map(|line| font.text_width(line))
+            .fold(0.0, f32::max);
+        let pad = line_height * 0.25;
+        draw.rect(
+            RectF::new(
+                pos.x,
+                pos.y,
+                width + pad * 2.0,
+                line_height * lines.len() as f32 + pad * 2.0,
+            ),
+            Rgba8::new(0, 0, 0, 160),
+        );
+        if !self.vars.is_empty() {
+            let y = pos.y + pad + line_height * (header + self.selected) as f32;
+            draw.rect(
+                RectF::new(pos.x, y, width + pad * 2.0, line_height),
+                Rgba8::new(255, 255, 255, 40),
+            );
+        }
+        for (i, line) in lines.iter().enumerate() {
+            let at = pos + vec2(pad, pad + size + line_height * i as f32);
+            draw.text(line, at, font, color, None);
+        }
+    }
+}
@@ -0,0 +1,205 @@
+use crate::color::Rgba8;
+use crate::gfx::{BlendMode, SubTexture, TextureAtlas};
+use crate::math::{DegreesF, Vec2F, degs};
+use crate::particles::LifeCurve;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+/// An error loading an [`EmitterDef`] or resolving its textures.
+#[derive(Debug, thiserror::Error)]
+pub enum ParticleError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("texture {0:?} not found")]
+    MissingTexture(String),
+
+    #[error("particle effect {0:?} not found")]
+    MissingEffect(String),
+}
+
+/// The area particles spawn in, centered on the emitter.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmitterShape {
+    /// Every particle spawns at the emitter's position.
+    #[default]
+    Point,
+
+    /// Particles spawn inside a circle, or only on its edge.
+    Circle {
+        radius: f32,
+        #[serde(default)]
+        edge: bool,
+    },
+
+    /// Particles spawn inside a rectangle of this size.
+    Rect { size: Vec2F },
+}
+
+/// Describes how a [`ParticleEmitter`](super::ParticleEmitter) spawns, moves, and draws its
+/// particles.
+///
+/// Definitions can be saved and loaded as JSON, so effects can be tuned without recompiling.
+/// Any field left out of the file takes its default value. Angles are in degrees, and colors
+/// are premultiplied.
+///
+/// ```json
+/// {
+///     "rate": 40,
+///     "lifetime": { "start": 0.5, "end": 1.0 },
+///     "spread": 30,
+///     "speed": { "start": 80, "end": 120 },
+///     "gravity": [0, 200],
+///     "size_over_life": [[0, 1], [1, 0]],
+///     "color_over_life": [[0, "#FFC850FF"], [1, "#00000000"]],
+///     "textures": ["spark"],
+///     "blend_mode": "Add"
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EmitterDef {
+    /// How many particles spawn per second while emitting.
+    pub rate: f32,
+
+    /// How many particles spawn at once when the emitter starts.
+    pub burst: u32,
+
+    /// How many seconds the emitter emits for after starting, or forever if `None`.
+    pub duration: Option<f32>,
+
+    /// Most particles that will be alive at once.
+    pub max_particles: usize,
+
+    /// Range each particle's lifetime is randomly picked from, in seconds.
+    pub lifetime: RangeInclusive<f32>,
+
+    /// Where particles spawn.
+    pub shape: EmitterShape,
+
+    /// The direction particles move in, where `0` is right and `90` is down.
+    pub direction: DegreesF,
+
+    /// How far from [`direction`](Self::direction) particles can randomly move, in either
+    /// direction. `180` moves them in every direction.
+    pub spread: DegreesF,
+
+    /// Range each particle's starting speed is randomly picked from, in pixels per second.
+    pub speed: RangeInclusive<f32>,
+
+    /// Multiplies each particle's speed over its life.
+    pub speed_over_life: LifeCurve<f32>,
+
+    /// Acceleration applied to every particle, in pixels per second squared.
+    pub gravity: Vec2F,
+
+    /// How much of each particle's velocity is lost per second, from `0` to `1`.
+    pub drag: f32,
+
+    /// Range each particle's size is randomly picked from, in pixels. Textured particles are
+    /// scaled so their longest side is this size.
+    pub size: RangeInclusive<f32>,
+
+    /// Multiplies each particle's size over its life.
+    pub size_over_life: LifeCurve<f32>,
+
+    /// Each particle's color over its life.
+    pub color_over_life: LifeCurve<Rgba8>,
+
+    /// Range each particle's starting rotation is randomly picked from, in degrees.
+    pub rotation: RangeInclusive<f32>,
+
+    /// Range each particle's rotation speed is randomly picked from, in degrees per second.
+    pub spin: RangeInclusive<f32>,
+
+    /// Names of the textures each particle randomly picks from, looked up in an atlas. With
+    /// no textures, particles are drawn as solid squares.
+    pub textures: Vec<String>,
+
+    /// How the particles blend with what is behind them.
+    pub blend_mode: BlendMode,
+}
+
+impl Default for EmitterDef {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            rate: 20.0,
+            burst: 0,
+            duration: None,
+            max_particles: 1000,
+            lifetime: 1.0..=1.0,
+            shape: EmitterShape::Point,
+            direction: degs(-90.0),
+            spread: degs(180.0),
+            speed: 50.0..=100.0,
+            speed_over_life: LifeCurve::constant(1.0),
+            gravity: Vec2F::ZERO,
+            drag: 0.0,
+            size: 4.0..=4.0,
+            size_over_life: LifeCurve::constant(1.0),
+            color_over_life: LifeCurve::constant(Rgba8::WHITE),
+            rotation: 0.0..=0.0,
+            spin: 0.0..=0.0,
+            textures: Vec::new(),
+            blend_mode: BlendMode::Normal,
+        }
+    }
+}
+
+impl EmitterDef {
+    /// Parse a definition from JSON.
+    #[inline]
+    pub fn from_json(bytes: &[u8]) -> Result<Self, ParticleError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// Load a definition from a JSON file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, ParticleError> {
+        Self::from_json(&std::fs::read(path)?)
+    }
+
+    /// Serialize the definition as pretty-printed JSON.
+    #[inline]
+    pub fn to_json(&self) -> Result<Vec<u8>, ParticleError> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Save the definition to a JSON file.
+    #[inline]
+    pub fn save_file(&self, path: impl AsRef<Path>) -> Result<(), ParticleError> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+
+    /// Look up each of the definition's [`textures`](Self::textures) by name.
+    pub fn resolve_textures(
+        &self,
+        mut lookup: impl FnMut(&str) -> Option<SubTexture>,
+    ) -> Result<Vec<SubTexture>, ParticleError> {
+        self.textures
+            .iter()
+            .map(|name| lookup(name).ok_or_else(|| ParticleError::MissingTexture(name.clone())))
+            .collect()
+    }
+
+    /// Look up each of the definition's [`textures`](Self::textures) in an atlas.
+    #[inline]
+    pub fn resolve_atlas<K>(
+        &self,
+        atlas: &TextureAtlas<K>,
+    ) -> Result<Vec<SubTexture>, ParticleError>
+    where
+        K: Clone + Eq + Hash + Borrow<str>,
+    {
+        self.resolve_textures(|name| atlas.get(name).cloned())
+    }
+}
@@ -0,0 +1,122 @@
+use crate::color::Rgba8;
+use crate::math::Vec2F;
+use serde::{Deserialize, Serialize};
+
+/// A value that can be blended along a [`LifeCurve`].
+pub trait CurveLerp: Copy + Default {
+    /// Blend towards `target` by `t`, from `0` to `1`.
+    fn curve_lerp(self, target: Self, t: f32) -> Self;
+}
+
+impl CurveLerp for f32 {
+    #[inline]
+    fn curve_lerp(self, target: Self, t: f32) -> Self {
+        self + (target - self) * t
+    }
+}
+
+impl CurveLerp for Vec2F {
+    #[inline]
+    fn curve_lerp(self, target: Self, t: f32) -> Self {
+        self + (target - self) * t
+    }
+}
+
+impl CurveLerp for Rgba8 {
+    #[inline]
+    fn curve_lerp(self, target: Self, t: f32) -> Self {
+        let f = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+        Rgba8::new(
+            f(self.r, target.r),
+            f(self.g, target.g),
+            f(self.b, target.b),
+            f(self.a, target.a),
+        )
+    }
+}
+
+/// A value that changes over a particle's life, made of keys that are linearly blended
+/// between. Times go from `0` (when the particle spawns) to `1` (when it dies).
+///
+/// Curves are serialized as a list of `[time, value]` pairs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(
+    from = "Vec<(f32, T)>",
+    into = "Vec<(f32, T)>",
+    bound(
+        serialize = "T: CurveLerp + Serialize",
+        deserialize = "T: CurveLerp + Deserialize<'de>"
+    )
+)]
+pub struct LifeCurve<T: CurveLerp> {
+    keys: Vec<(f32, T)>,
+}
+
+impl<T: CurveLerp> LifeCurve<T> {
+    /// Create a curve from keys, which are sorted by time.
+    pub fn new(keys: impl IntoIterator<Item = (f32, T)>) -> Self {
+        let mut keys: Vec<_> = keys.into_iter().collect();
+        keys.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { keys }
+    }
+
+    /// Create a curve that stays at one value.
+    #[inline]
+    pub fn constant(value: T) -> Self {
+        Self {
+            keys: vec![(0.0, value)],
+        }
+    }
+
+    /// Create a curve that blends from one value to another.
+    #[inline]
+    pub fn linear(from: T, to: T) -> Self {
+        Self {
+            keys: vec![(0.0, from), (1.0, to)],
+        }
+    }
+
+    /// Return the curve with a key added.
+    #[inline]
+    pub fn with_key(mut self, time: f32, value: T) -> Self {
+        self.insert(time, value);
+        self
+    }
+
+    /// Add a key, keeping the keys sorted by time.
+    pub fn insert(&mut self, time: f32, value: T) {
+        let i = self.keys.partition_point(|k| k.0 <= time);
+        self.keys.insert(i, (time, value));
+    }
+
+    /// The curve's keys, sorted by time.
+    #[inline]
+    pub fn keys(&self) -> &[(f32, T)] {
+        &self.keys
+    }
+
+    /// The value at `time`. Times before the first key or after the last take that key's
+    /// value, and an empty curve is always the default value.
+    pub fn sample(&self, time: f32) -> T {
+        let i = self.keys.partition_point(|k| k.0 <= time);
+        match (i.checked_sub(1).map(|i| self.keys[i]), self.keys.get(i)) {
+            (Some((t0, a)), Some(&(t1, b))) => a.curve_lerp(b, (time - t0) / (t1 - t0)),
+            (Some((_, a)), None) | (None, Some(&(_, a))) => a,
+            (None, None) => T::default(),
+        }
+    }
+}
+
+impl<T: CurveLerp> From<Vec<(f32, T)>> for LifeCurve<T> {
+    #[inline]
+    fn from(keys: Vec<(f32, T)>) -> Self {
+        Self::new(keys)
+    }
+}
+
+impl<T: CurveLerp> From<LifeCurve<T>> for Vec<(f32, T)> {
+    #[inline]
+    fn from(curve: LifeCurve<T>) -> Self {
+        curve.keys
+    }
+}
@@ -0,0 +1,11 @@
+//! Data-driven particle effects.
+
+mod emitter_def;
+mod life_curve;
+mod particle_emitter;
+mod particle_system;
+
+pub use emitter_def::*;
+pub use life_curve::*;
+pub use particle_emitter::*;
+pub use particle_system::*;
@@ -0,0 +1,342 @@
+use crate::gfx::{ColorMode, Draw, SubTexture, Texture, TextureAtlas, Topology, Vertex};
+use crate::math::{Vec2F, vec2};
+use crate::particles::{EmitterDef, EmitterShape, ParticleError};
+use crate::rand::Rand;
+use std::borrow::Borrow;
+use std::f32::consts::TAU;
+use std::hash::Hash;
+use std::ops::RangeInclusive;
+
+#[cfg(feature = "lua")]
+pub type ParticleEmitterObj = fey_lua::UserDataOf<ParticleEmitter>;
+#[cfg(feature = "lua")]
+pub type ParticleEmitterRef = mlua::UserDataRef<ParticleEmitter>;
+#[cfg(feature = "lua")]
+pub type ParticleEmitterMut = mlua::UserDataRefMut<ParticleEmitter>;
+
+/// A particle simulated by a [`ParticleEmitter`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Particle {
+    pub pos: Vec2F,
+
+    /// Velocity before [`speed_over_life`](EmitterDef::speed_over_life) is applied.
+    pub vel: Vec2F,
+
+    /// Seconds the particle has been alive.
+    pub age: f32,
+
+    /// Seconds the particle lives for.
+    pub life: f32,
+
+    /// Size before [`size_over_life`](EmitterDef::size_over_life) is applied.
+    pub size: f32,
+
+    /// Rotation in radians.
+    pub rotation: f32,
+
+    /// Rotation speed in radians per second.
+    pub spin: f32,
+
+    /// Index of the particle's texture in its emitter's textures.
+    pub texture: usize,
+}
+
+impl Particle {
+    /// How far through its life the particle is, from `0` to `1`.
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        (self.age / self.life).clamp(0.0, 1.0)
+    }
+}
+
+/// Spawns, simulates, and draws particles as described by an [`EmitterDef`].
+///
+/// ```ignore
+/// let def = EmitterDef::from_file("assets/fire.json")?;
+/// let mut fire = ParticleEmitter::from_atlas(def, &atlas)?.with_pos(torch_pos);
+/// // every frame
+/// fire.update(time.delta());
+/// fire.draw(draw);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ParticleEmitter {
+    /// How the emitter spawns, moves, and draws its particles.
+    pub def: EmitterDef,
+
+    /// Where new particles spawn. Moving the emitter does not move particles that already
+    /// spawned.
+    pub pos: Vec2F,
+
+    textures: Vec<SubTexture>,
+    rand: Rand,
+    emitting: bool,
+    pending_burst: bool,
+    time: f32,
+    spawn: f32,
+    particles: Vec<Particle>,
+}
+
+impl ParticleEmitter {
+    /// Create an emitter that draws untextured particles, ignoring the definition's
+    /// [`textures`](EmitterDef::textures). It starts emitting on its first update.
+    pub fn new(def: EmitterDef) -> Self {
+        Self {
+            def,
+            pos: Vec2F::ZERO,
+            textures: Vec::new(),
+            rand: Rand::new(),
+            emitting: true,
+            pending_burst: true,
+            time: 0.0,
+            spawn: 0.0,
+            particles: Vec::new(),
+        }
+    }
+
+    /// Create an emitter, looking up the definition's textures in an atlas.
+    #[inline]
+    pub fn from_atlas<K>(def: EmitterDef, atlas: &TextureAtlas<K>) -> Result<Self, ParticleError>
+    where
+        K: Clone + Eq + Hash + Borrow<str>,
+    {
+        let textures = def.resolve_atlas(atlas)?;
+        Ok(Self::new(def).with_textures(textures))
+    }
+
+    /// Return the emitter at a different position.
+    #[inline]
+    pub fn with_pos(mut self, pos: impl Into<Vec2F>) -> Self {
+        self.pos = pos.into();
+        self
+    }
+
+    /// Return the emitter drawing particles with the textures.
+    #[inline]
+    pub fn with_textures(mut self, textures: Vec<SubTexture>) -> Self {
+        self.textures = textures;
+        self
+    }
+
+    /// Return the emitter using the random number generator.
+    #[inline]
+    pub fn with_rand(mut self, rand: Rand) -> Self {
+        self.rand = rand;
+        self
+    }
+
+    /// The textures particles randomly pick from.
+    #[inline]
+    pub fn textures(&self) -> &[SubTexture] {
+        &self.textures
+    }
+
+    /// Change the textures particles randomly pick from. Particles that already spawned keep
+    /// their texture index, so keep the same number of textures.
+    #[inline]
+    pub fn set_textures(&mut self, textures: Vec<SubTexture>) {
+        self.textures = textures;
+    }
+
+    /// Every live particle.
+    #[inline]
+    pub fn particles(&self) -> &[Particle] {
+        &self.particles
+    }
+
+    /// How many particles are alive.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.particles.len()
+    }
+
+    /// If the emitter is spawning particles.
+    #[inline]
+    pub fn is_emitting(&self) -> bool {
+        self.emitting
+    }
+
+    /// If the emitter has stopped and all its particles have died.
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        !self.emitting && self.particles.is_empty()
+    }
+
+    /// Seconds since the emitter started.
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Start emitting from the beginning, spawning the definition's
+    /// [`burst`](EmitterDef::burst) on the next update.
+    #[inline]
+    pub fn start(&mut self) {
+        self.emitting = true;
+        self.pending_burst = true;
+        self.time = 0.0;
+        self.spawn = 0.0;
+    }
+
+    /// Stop spawning particles. Particles that are alive keep moving until they die.
+    #[inline]
+    pub fn stop(&mut self) {
+        self.emitting = false;
+        self.pending_burst = false;
+    }
+
+    /// Remove every particle.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.particles.clear();
+    }
+
+    /// Immediately spawn particles, even if the emitter is stopped.
+    pub fn burst(&mut self, count: u32) {
+        for _ in 0..count {
+            if self.particles.len() >= self.def.max_particles {
+                break;
+            }
+            let part = self.spawn_particle();
+            self.particles.push(part);
+        }
+    }
+
+    fn spawn_particle(&mut self) -> Particle {
+        let def = &self.def;
+        let rand = &mut self.rand;
+        let offset = match def.shape {
+            EmitterShape::Point => Vec2F::ZERO,
+            EmitterShape::Circle { radius, edge } => {
+                // taking the root of the distance spreads particles evenly over the area
+                let dist = match edge {
+                    true => radius,
+                    false => radius * rand.range(0.0f32..=1.0).sqrt(),
+                };
+                let angle = rand.range(0.0..TAU);
+                vec2(angle.cos(), angle.sin()) * dist
+            }
+            EmitterShape::Rect { size } => {
+                vec2(rand.range(-0.5..=0.5), rand.range(-0.5..=0.5)) * size
+            }
+        };
+        let spread = def.spread.0.abs();
+        let angle = (def.direction.0 + rand.range(-spread..=spread)).to_radians();
+        let speed = random_in(rand, &def.speed);
+        Particle {
+            pos: self.pos + offset,
+            vel: vec2(angle.cos(), angle.sin()) * speed,
+            age: 0.0,
+            life: random_in(rand, &def.lifetime).max(f32::EPSILON),
+            size: random_in(rand, &def.size),
+            rotation: random_in(rand, &def.rotation).to_radians(),
+            spin: random_in(rand, &def.spin).to_radians(),
+            texture: match self.textures.len() {
+                0 => 0,
+                n => rand.range(0..n),
+            },
+        }
+    }
+
+    /// Spawn new particles and move the live ones, removing any that died.
+    pub fn update(&mut self, delta_time: f32) {
+        if self.emitting {
+            if std::mem::take(&mut self.pending_burst) {
+                self.burst(self.def.burst);
+            }
+            self.time += delta_time;
+            self.spawn += self.def.rate.max(0.0) * delta_time;
+            let count = self.spawn.floor();
+            self.spawn -= count;
+            self.burst(count as u32);
+            if self.def.duration.is_some_and(|d| self.time >= d) {
+                self.stop();
+            }
+        }
+
+        let def = &self.def;
+        let drag = (1.0 - def.drag * delta_time).clamp(0.0, 1.0);
+        self.particles.retain_mut(|part| {
+            part.age += delta_time;
+            if part.age >= part.life {
+                return false;
+            }
+            part.vel = (part.vel + def.gravity * delta_time) * drag;
+            part.pos += part.vel * def.speed_over_life.sample(part.progress()) * delta_time;
+            part.rotation += part.spin * delta_time;
+            true
+        });
+    }
+
+    /// Draw the particles, in the same space the emitter is positioned in. Particles that
+    /// share a texture, such as those packed into the same atlas, are drawn in one batch.
+    pub fn draw(&self, draw: &mut Draw) {
+        if self.particles.is_empty() {
+            return;
+        }
+        let prev_blend = draw.blend_mode();
+        draw.set_blend_mode(self.def.blend_mode);
+
+        let mut texture: Option<&Texture> = None;
+        let mut verts = Vec::with_capacity(self.particles.len() * 4);
+        let mut inds = Vec::with_capacity(self.particles.len() * 6);
+        for part in &self.particles {
+            let t = part.progress();
+            let color = self.def.color_over_life.sample(t);
+            let size = part.size * self.def.size_over_life.sample(t);
+            let sub = self.textures.get(part.texture);
+
+            // start a new batch whenever the texture changes
+            let tex = sub.map(|sub| &sub.texture);
+            if tex != texture && !verts.is_empty() {
+                flush(draw, texture, &mut verts, &mut inds);
+            }
+            texture = tex;
+
+            let (half, coords, mode) = match sub {
+                Some(sub) => {
+                    let dims = sub.rect.size();
+                    let scale = size / dims.x.max(dims.y).max(f32::EPSILON);
+                    (dims * (scale * 0.5), sub.coords, ColorMode::MULT)
+                }
+                None => (vec2(size, size) * 0.5, [Vec2F::ZERO; 4], ColorMode::VETO),
+            };
+            let (sin, cos) = part.rotation.sin_cos();
+            let corners = [
+                vec2(-1.0, -1.0),
+                vec2(1.0, -1.0),
+                vec2(1.0, 1.0),
+                vec2(-1.0, 1.0),
+            ];
+            let i = verts.len() as u32;
+            for (corner, tex) in corners.into_iter().zip(coords) {
+                let p = corner * half;
+                let p = vec2(p.x * cos - p.y * sin, p.x * sin + p.y * cos);
+                verts.push(Vertex::new(part.pos + p, tex, color, mode));
+            }
+            inds.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+        }
+        flush(draw, texture, &mut verts, &mut inds);
+        draw.set_blend_mode(prev_blend);
+    }
+}
+
+/// Draw a batch of particles and empty it.
+#[inline]
+fn flush(draw: &mut Draw, texture: Option<&Texture>, verts: &mut Vec<Vertex>, inds: &mut Vec<u32>) {
+    draw.custom(
+        texture.cloned(),
+        Topology::Triangles,
+        verts.drain(..),
+        inds.drain(..),
+    );
+}
+
+/// Randomly pick a value in the range, which may be reversed or empty.
+#[inline]
+fn random_in(rand: &mut Rand, range: &RangeInclusive<f32>) -> f32 {
+    let (a, b) = (*range.start(), *range.end());
+    match a < b {
+        true => rand.range(a..=b),
+        false => a,
+    }
+}
@@ -0,0 +1,220 @@
+use crate::gfx::{Draw, SubTexture, TextureAtlas};
+use crate::math::Vec2F;
+use crate::particles::{EmitterDef, ParticleEmitter, ParticleError};
+use crate::rand::Rand;
+use fnv::FnvHashMap;
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::path::Path;
+
+#[cfg(feature = "lua")]
+pub type ParticlesObj = fey_lua::UserDataOf<Particles>;
+#[cfg(feature = "lua")]
+pub type ParticlesRef = mlua::UserDataRef<Particles>;
+#[cfg(feature = "lua")]
+pub type ParticlesMut = mlua::UserDataRefMut<Particles>;
+
+/// Handle to an emitter spawned by [`Particles`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ParticleId(pub u64);
+
+/// A named particle effect that can be spawned by [`Particles`].
+#[derive(Debug, Clone)]
+pub struct ParticleEffect {
+    pub def: EmitterDef,
+    pub textures: Vec<SubTexture>,
+}
+
+/// A library of named particle effects, and every emitter spawned from them.
+///
+/// Effects are usually loaded from JSON files, so they can be tuned without recompiling.
+/// Spawned emitters are updated and drawn together, and are removed once they have stopped
+/// and all their particles have died, so one-shot effects like explosions can be spawned and
+/// forgotten.
+///
+/// ```ignore
+/// particles.load_file("explosion", "assets/explosion.json", &atlas)?;
+/// particles.spawn("explosion", enemy.pos)?;
+/// // every frame
+/// particles.update(time.delta());
+/// particles.draw(draw);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Particles {
+    effects: FnvHashMap<String, ParticleEffect>,
+    emitters: Vec<(ParticleId, ParticleEmitter)>,
+    next_id: u64,
+    rand: Rand,
+}
+
+impl Default for Particles {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Particles {
+    /// Create an empty particle system with a random seed.
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_rand(Rand::new())
+    }
+
+    /// Create an empty particle system that seeds its emitters from the random number
+    /// generator.
+    #[inline]
+    pub fn from_rand(rand: Rand) -> Self {
+        Self {
+            effects: FnvHashMap::default(),
+            emitters: Vec::new(),
+            next_id: 0,
+            rand,
+        }
+    }
+
+    /// Add an effect, replacing any effect that had the same name.
+    #[inline]
+    pub fn insert(&mut self, name: impl Into<String>, def: EmitterDef, textures: Vec<SubTexture>) {
+        self.effects
+            .insert(name.into(), ParticleEffect { def, textures });
+    }
+
+    /// Add an effect, looking up its textures in an atlas.
+    #[inline]
+    pub fn insert_atlas<K>(
+        &mut self,
+        name: impl Into<String>,
+        def: EmitterDef,
+        atlas: &TextureAtlas<K>,
+    ) -> Result<(), ParticleError>
+    where
+        K: Clone + Eq + Hash + Borrow<str>,
+    {
+        let textures = def.resolve_atlas(atlas)?;
+        self.insert(name, def, textures);
+        Ok(())
+    }
+
+    /// Load an effect from a JSON file, looking up its textures in an atlas.
+    #[inline]
+    pub fn load_file<K>(
+        &mut self,
+        name: impl Into<String>,
+        path: impl AsRef<Path>,
+        atlas: &TextureAtlas<K>,
+    ) -> Result<(), ParticleError>
+    where
+        K: Clone + Eq + Hash + Borrow<str>,
+    {
+        self.insert_atlas(name, EmitterDef::from_file(path)?, atlas)
+    }
+
+    /// Remove an effect. Emitters already spawned from it keep running.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<ParticleEffect> {
+        self.effects.remove(name)
+    }
+
+    /// Get an effect.
+    #[inline]
+    pub fn effect(&self, name: &str) -> Option<&ParticleEffect> {
+        self.effects.get(name)
+    }
+
+    /// Get a mutable reference to an effect, such as to tweak its definition. Emitters already
+    /// spawned from it are not changed.
+    #[inline]
+    pub fn effect_mut(&mut self, name: &str) -> Option<&mut ParticleEffect> {
+        self.effects.get_mut(name)
+    }
+
+    /// Spawn an emitter from an effect at the position.
+    pub fn spawn(
+        &mut self,
+        name: &str,
+        pos: impl Into<Vec2F>,
+    ) -> Result<ParticleId, ParticleError> {
+        let effect = self
+            .effects
+            .get(name)
+            .ok_or_else(|| ParticleError::MissingEffect(name.into()))?;
+        let emitter = ParticleEmitter::new(effect.def.clone())
+            .with_textures(effect.textures.clone())
+            .with_pos(pos)
+            .with_rand(Rand::from_seed(self.rand.random()));
+        Ok(self.add(emitter))
+    }
+
+    /// Add an emitter to be updated and drawn with the rest.
+    #[inline]
+    pub fn add(&mut self, emitter: ParticleEmitter) -> ParticleId {
+        let id = ParticleId(self.next_id);
+        self.next_id += 1;
+        self.emitters.push((id, emitter));
+        id
+    }
+
+    /// Get a spawned emitter, if it hasn't finished.
+    #[inline]
+    pub fn get(&self, id: ParticleId) -> Option<&ParticleEmitter> {
+        self.emitters.iter().find(|(i, _)| *i == id).map(|(_, e)| e)
+    }
+
+    /// Get a mutable reference to a spawned emitter, such as to move it with its owner.
+    #[inline]
+    pub fn get_mut(&mut self, id: ParticleId) -> Option<&mut ParticleEmitter> {
+        self.emitters
+            .iter_mut()
+            .find(|(i, _)| *i == id)
+            .map(|(_, e)| e)
+    }
+
+    /// Stop an emitter. It is removed once its particles have died.
+    #[inline]
+    pub fn stop(&mut self, id: ParticleId) {
+        if let Some(emitter) = self.get_mut(id) {
+            emitter.stop();
+        }
+    }
+
+    /// Immediately remove an emitter and its particles.
+    #[inline]
+    pub fn kill(&mut self, id: ParticleId) {
+        self.emitters.retain(|(i, _)| *i != id);
+    }
+
+    /// Remove every emitter.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.emitters.clear();
+    }
+
+    /// Every spawned emitter.
+    #[inline]
+    pub fn emitters(&self) -> impl ExactSizeIterator<Item = (ParticleId, &ParticleEmitter)> {
+        self.emitters.iter().map(|(id, e)| (*id, e))
+    }
+
+    /// How many particles are alive across every emitter.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.emitters.iter().map(|(_, e)| e.count()).sum()
+    }
+
+    /// Update every emitter, removing those that have finished.
+    pub fn update(&mut self, delta_time: f32) {
+        self.emitters.retain_mut(|(_, emitter)| {
+            emitter.update(delta_time);
+            !emitter.is_finished()
+        });
+    }
+
+    /// Draw every emitter, in the order they were spawned.
+    #[inline]
+    pub fn draw(&self, draw: &mut Draw) {
+        for (_, emitter) in &self.emitters {
+            emitter.draw(draw);
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Steamworks integration, enabled by the `steam` feature.
+//!
+//! The Steamworks library is loaded when the game starts, so the `steam_api` library from the
+//! Steamworks SDK must be shipped next to the game's executable.
+
+mod steam_analog_action;
+mod steam_api;
+mod steam_client;
+mod steam_cloud;
+mod steam_digital_action;
+mod steam_error;
+mod steam_input;
+
+pub use steam_analog_action::*;
+pub use steam_client::*;
+pub use steam_cloud::*;
+pub use steam_digital_action::*;
+pub use steam_error::*;
+pub use steam_input::*;
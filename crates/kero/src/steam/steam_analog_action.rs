@@ -0,0 +1,76 @@
+use crate::math::Vec2F;
+use std::cell::Cell;
+use std::ffi::CString;
+use std::rc::Rc;
+
+/// Handle to a Steam Input analog action, such as `move` or `camera`, which has a 2D value
+/// from a stick, trackpad, or gyro.
+///
+/// The action is read from every connected controller once per frame, and takes the value
+/// furthest from the center. Create one with [`SteamInput::analog_action`](crate::steam::SteamInput::analog_action).
+#[derive(Debug, Clone)]
+pub struct SteamAnalogAction(Rc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    name: CString,
+    handle: Cell<u64>,
+    value: Cell<Vec2F>,
+    prev_value: Cell<Vec2F>,
+}
+
+impl SteamAnalogAction {
+    #[inline]
+    pub(crate) fn new(name: CString) -> Self {
+        Self(Rc::new(Inner {
+            name,
+            handle: Cell::new(0),
+            value: Cell::new(Vec2F::ZERO),
+            prev_value: Cell::new(Vec2F::ZERO),
+        }))
+    }
+
+    /// The action's name in the game's Steam Input configuration.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.0.name.to_str().unwrap_or_default()
+    }
+
+    /// The action's value.
+    #[inline]
+    pub fn value(&self) -> Vec2F {
+        self.0.value.get()
+    }
+
+    /// The action's horizontal value.
+    #[inline]
+    pub fn x(&self) -> f32 {
+        self.value().x
+    }
+
+    /// The action's vertical value.
+    #[inline]
+    pub fn y(&self) -> f32 {
+        self.value().y
+    }
+
+    /// If the action's value changed this frame.
+    #[inline]
+    pub fn changed(&self) -> bool {
+        self.0.value.get() != self.0.prev_value.get()
+    }
+
+    /// The action's handle, looked up by name until Steam has loaded the configuration.
+    #[inline]
+    pub(crate) fn resolve(&self, lookup: impl FnOnce(&CString) -> u64) -> u64 {
+        if self.0.handle.get() == 0 {
+            self.0.handle.set(lookup(&self.0.name));
+        }
+        self.0.handle.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_value(&self, value: Vec2F) {
+        self.0.prev_value.set(self.0.value.replace(value));
+    }
+}
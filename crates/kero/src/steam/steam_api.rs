@@ -0,0 +1,205 @@
+use crate::steam::SteamError;
+use libloading::Library;
+use std::ffi::{c_char, c_void};
+
+pub(crate) type Iface = *mut c_void;
+
+/// Passed to every Steam Input function to apply it to every controller.
+pub(crate) const ALL_CONTROLLERS: u64 = u64::MAX;
+
+/// The most controllers Steam Input reports at once.
+pub(crate) const MAX_CONTROLLERS: usize = 16;
+
+/// The callback sent when the overlay is opened or closed.
+pub(crate) const GAME_OVERLAY_ACTIVATED: i32 = 331;
+
+#[repr(C)]
+pub(crate) struct CallbackMsg {
+    pub user: i32,
+    pub callback: i32,
+    pub param: *mut u8,
+    pub param_len: i32,
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub(crate) struct DigitalActionData {
+    pub state: bool,
+    pub active: bool,
+}
+
+#[repr(C, packed)]
+#[derive(Copy, Clone)]
+pub(crate) struct AnalogActionData {
+    pub mode: i32,
+    pub x: f32,
+    pub y: f32,
+    pub active: bool,
+}
+
+/// The flat Steamworks API, loaded from the `steam_api` library shipped next to the game.
+#[allow(clippy::type_complexity)]
+pub(crate) struct SteamApi {
+    pub shutdown: unsafe extern "C" fn(),
+    pub get_pipe: unsafe extern "C" fn() -> i32,
+    pub dispatch_init: unsafe extern "C" fn(),
+    pub dispatch_run_frame: unsafe extern "C" fn(i32),
+    pub dispatch_next: unsafe extern "C" fn(i32, *mut CallbackMsg) -> bool,
+    pub dispatch_free: unsafe extern "C" fn(i32),
+
+    pub user: unsafe extern "C" fn() -> Iface,
+    pub user_steam_id: unsafe extern "C" fn(Iface) -> u64,
+
+    pub utils: unsafe extern "C" fn() -> Iface,
+    pub utils_app_id: unsafe extern "C" fn(Iface) -> u32,
+    pub utils_overlay_enabled: unsafe extern "C" fn(Iface) -> bool,
+    pub utils_overlay_needs_present: unsafe extern "C" fn(Iface) -> bool,
+
+    pub storage: unsafe extern "C" fn() -> Iface,
+    pub storage_account_enabled: unsafe extern "C" fn(Iface) -> bool,
+    pub storage_app_enabled: unsafe extern "C" fn(Iface) -> bool,
+    pub storage_file_count: unsafe extern "C" fn(Iface) -> i32,
+    pub storage_file_name: unsafe extern "C" fn(Iface, i32, *mut i32) -> *const c_char,
+    pub storage_file_exists: unsafe extern "C" fn(Iface, *const c_char) -> bool,
+    pub storage_file_size: unsafe extern "C" fn(Iface, *const c_char) -> i32,
+    pub storage_file_read: unsafe extern "C" fn(Iface, *const c_char, *mut c_void, i32) -> i32,
+    pub storage_file_write: unsafe extern "C" fn(Iface, *const c_char, *const c_void, i32) -> bool,
+    pub storage_file_delete: unsafe extern "C" fn(Iface, *const c_char) -> bool,
+
+    pub input: unsafe extern "C" fn() -> Iface,
+    pub input_init: unsafe extern "C" fn(Iface, bool) -> bool,
+    pub input_shutdown: unsafe extern "C" fn(Iface) -> bool,
+    pub input_run_frame: unsafe extern "C" fn(Iface, bool),
+    pub input_controllers: unsafe extern "C" fn(Iface, *mut u64) -> i32,
+    pub input_action_set: unsafe extern "C" fn(Iface, *const c_char) -> u64,
+    pub input_activate_set: unsafe extern "C" fn(Iface, u64, u64),
+    pub input_digital_handle: unsafe extern "C" fn(Iface, *const c_char) -> u64,
+    pub input_digital_data: unsafe extern "C" fn(Iface, u64, u64) -> DigitalActionData,
+    pub input_analog_handle: unsafe extern "C" fn(Iface, *const c_char) -> u64,
+    pub input_analog_data: unsafe extern "C" fn(Iface, u64, u64) -> AnalogActionData,
+
+    _lib: Library,
+}
+
+#[cfg(all(windows, target_pointer_width = "64"))]
+const LIBRARY: &str = "steam_api64.dll";
+
+#[cfg(all(windows, not(target_pointer_width = "64")))]
+const LIBRARY: &str = "steam_api.dll";
+
+#[cfg(target_os = "macos")]
+const LIBRARY: &str = "libsteam_api.dylib";
+
+#[cfg(all(unix, not(target_os = "macos")))]
+const LIBRARY: &str = "libsteam_api.so";
+
+impl SteamApi {
+    /// Load the library, looking next to the game's executable first.
+    pub fn load() -> Result<Self, SteamError> {
+        let beside_exe = std::env::current_exe()
+            .ok()
+            .and_then(|exe| Some(exe.parent()?.join(LIBRARY)));
+
+        // SAFETY: the Steamworks library has no initialization routines with preconditions
+        let lib = match beside_exe.filter(|path| path.is_file()) {
+            Some(path) => unsafe { Library::new(path) },
+            None => unsafe { Library::new(LIBRARY) },
+        }
+        .map_err(|err| SteamError::Load(err.to_string()))?;
+
+        macro_rules! sym {
+            ($name:literal) => {
+                // SAFETY: the field types match the flat API's declarations
+                *unsafe { lib.get(concat!($name, "\0").as_bytes()) }
+                    .map_err(|_| SteamError::MissingFunction($name))?
+            };
+        }
+
+        Ok(Self {
+            shutdown: sym!("SteamAPI_Shutdown"),
+            get_pipe: sym!("SteamAPI_GetHSteamPipe"),
+            dispatch_init: sym!("SteamAPI_ManualDispatch_Init"),
+            dispatch_run_frame: sym!("SteamAPI_ManualDispatch_RunFrame"),
+            dispatch_next: sym!("SteamAPI_ManualDispatch_GetNextCallback"),
+            dispatch_free: sym!("SteamAPI_ManualDispatch_FreeLastCallback"),
+
+            user: sym!("SteamAPI_SteamUser_v023"),
+            user_steam_id: sym!("SteamAPI_ISteamUser_GetSteamID"),
+
+            utils: sym!("SteamAPI_SteamUtils_v010"),
+            utils_app_id: sym!("SteamAPI_ISteamUtils_GetAppID"),
+            utils_overlay_enabled: sym!("SteamAPI_ISteamUtils_IsOverlayEnabled"),
+            utils_overlay_needs_present: sym!("SteamAPI_ISteamUtils_BOverlayNeedsPresent"),
+
+            storage: sym!("SteamAPI_SteamRemoteStorage_v016"),
+            storage_account_enabled: sym!("SteamAPI_ISteamRemoteStorage_IsCloudEnabledForAccount"),
+            storage_app_enabled: sym!("SteamAPI_ISteamRemoteStorage_IsCloudEnabledForApp"),
+            storage_file_count: sym!("SteamAPI_ISteamRemoteStorage_GetFileCount"),
+            storage_file_name: sym!("SteamAPI_ISteamRemoteStorage_GetFileNameAndSize"),
+            storage_file_exists: sym!("SteamAPI_ISteamRemoteStorage_FileExists"),
+            storage_file_size: sym!("SteamAPI_ISteamRemoteStorage_GetFileSize"),
+            storage_file_read: sym!("SteamAPI_ISteamRemoteStorage_FileRead"),
+            storage_file_write: sym!("SteamAPI_ISteamRemoteStorage_FileWrite"),
+            storage_file_delete: sym!("SteamAPI_ISteamRemoteStorage_FileDelete"),
+
+            input: sym!("SteamAPI_SteamInput_v006"),
+            input_init: sym!("SteamAPI_ISteamInput_Init"),
+            input_shutdown: sym!("SteamAPI_ISteamInput_Shutdown"),
+            input_run_frame: sym!("SteamAPI_ISteamInput_RunFrame"),
+            input_controllers: sym!("SteamAPI_ISteamInput_GetConnectedControllers"),
+            input_action_set: sym!("SteamAPI_ISteamInput_GetActionSetHandle"),
+            input_activate_set: sym!("SteamAPI_ISteamInput_ActivateActionSet"),
+            input_digital_handle: sym!("SteamAPI_ISteamInput_GetDigitalActionHandle"),
+            input_digital_data: sym!("SteamAPI_ISteamInput_GetDigitalActionData"),
+            input_analog_handle: sym!("SteamAPI_ISteamInput_GetAnalogActionHandle"),
+            input_analog_data: sym!("SteamAPI_ISteamInput_GetAnalogActionData"),
+
+            _lib: lib,
+        })
+    }
+
+    /// Load the library and connect to the running Steam client.
+    pub fn init() -> Result<Self, SteamError> {
+        let api = Self::load()?;
+
+        // newer versions of the library report why initialization failed
+        // SAFETY: the error buffer is the size the flat API expects, and the symbols match
+        // their declarations
+        unsafe {
+            if let Ok(init) = api
+                ._lib
+                .get::<unsafe extern "C" fn(*mut [c_char; 1024]) -> i32>(b"SteamAPI_InitFlat\0")
+            {
+                let mut msg = [0 as c_char; 1024];
+                if init(&mut msg) != 0 {
+                    let msg = std::ffi::CStr::from_ptr(msg.as_ptr());
+                    return Err(SteamError::Init(msg.to_string_lossy().into_owned()));
+                }
+            } else {
+                let init = api
+                    ._lib
+                    .get::<unsafe extern "C" fn() -> bool>(b"SteamAPI_Init\0")
+                    .map_err(|_| SteamError::MissingFunction("SteamAPI_Init"))?;
+                if !init() {
+                    return Err(SteamError::Init(String::from("Steam is not running")));
+                }
+            }
+            (api.dispatch_init)();
+        }
+        Ok(api)
+    }
+
+    /// If the game was launched outside of Steam and Steam will relaunch it, in which case the
+    /// game should quit right away.
+    pub fn restart_app_if_necessary(app_id: u32) -> Result<bool, SteamError> {
+        let api = Self::load()?;
+        // SAFETY: the symbol matches its declaration, and takes no pointers
+        unsafe {
+            let restart = api
+                ._lib
+                .get::<unsafe extern "C" fn(u32) -> bool>(b"SteamAPI_RestartAppIfNecessary\0")
+                .map_err(|_| SteamError::MissingFunction("SteamAPI_RestartAppIfNecessary"))?;
+            Ok(restart(app_id))
+        }
+    }
+}
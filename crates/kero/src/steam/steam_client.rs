@@ -0,0 +1,272 @@
+use crate::math::{Vec2F, vec2};
+use crate::steam::steam_api::{
+    AnalogActionData, CallbackMsg, DigitalActionData, GAME_OVERLAY_ACTIVATED, Iface,
+    MAX_CONTROLLERS, SteamApi,
+};
+use crate::steam::{SteamAnalogAction, SteamCloud, SteamDigitalAction, SteamError, SteamInput};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::rc::Rc;
+
+/// Handle to the Steamworks API, available from
+/// [`Platform::steam`](crate::core::Platform::steam) when the game was started with
+/// [`GameBuilder::with_steam`](crate::core::GameBuilder::with_steam) and Steam is running.
+///
+/// Steam is started and shut down on the main thread along with the game, and its callbacks
+/// are processed once per frame.
+///
+/// This handle can be cloned and passed around freely to give objects access to Steam.
+#[derive(Clone)]
+pub struct Steam(pub(crate) Rc<SteamState>);
+
+pub(crate) struct SteamState {
+    pub api: SteamApi,
+    pub pipe: i32,
+    pub running: Cell<bool>,
+    pub overlay_active: Cell<bool>,
+    pub overlay_changed: Cell<bool>,
+    pub input_ready: Cell<bool>,
+    pub controllers: Cell<usize>,
+    pub digital: RefCell<Vec<SteamDigitalAction>>,
+    pub analog: RefCell<Vec<SteamAnalogAction>>,
+}
+
+impl Debug for Steam {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Steam").finish_non_exhaustive()
+    }
+}
+
+impl Steam {
+    /// Connect to the running Steam client.
+    pub(crate) fn init() -> Result<Self, SteamError> {
+        let api = SteamApi::init()?;
+        // SAFETY: steam was initialized above
+        let (pipe, input_ready) = unsafe {
+            let input = (api.input)();
+            let ready = !input.is_null() && (api.input_init)(input, true);
+            ((api.get_pipe)(), ready)
+        };
+        Ok(Self(Rc::new(SteamState {
+            api,
+            pipe,
+            running: Cell::new(true),
+            overlay_active: Cell::new(false),
+            overlay_changed: Cell::new(false),
+            input_ready: Cell::new(input_ready),
+            controllers: Cell::new(0),
+            digital: RefCell::new(Vec::new()),
+            analog: RefCell::new(Vec::new()),
+        })))
+    }
+
+    /// If the game was launched outside of Steam, Steam will launch it again through the
+    /// client and this returns `true`, meaning the game should quit right away.
+    ///
+    /// Returns `false` if a `steam_appid.txt` file is next to the executable, which is how
+    /// games are run during development.
+    pub fn restart_app_if_necessary(app_id: u32) -> bool {
+        match SteamApi::restart_app_if_necessary(app_id) {
+            Ok(restart) => restart,
+            Err(err) => {
+                log::warn!("{err}");
+                false
+            }
+        }
+    }
+
+    /// If Steam is still running. After the game exits, every function does nothing.
+    #[inline]
+    pub fn is_running(&self) -> bool {
+        self.0.running.get()
+    }
+
+    /// The game's app ID.
+    #[inline]
+    pub fn app_id(&self) -> u32 {
+        self.call(
+            |api| api.utils,
+            |api, utils| unsafe { (api.utils_app_id)(utils) },
+        )
+        .unwrap_or(0)
+    }
+
+    /// The player's 64-bit Steam ID.
+    #[inline]
+    pub fn user_id(&self) -> u64 {
+        self.call(
+            |api| api.user,
+            |api, user| unsafe { (api.user_steam_id)(user) },
+        )
+        .unwrap_or(0)
+    }
+
+    /// If the player has the Steam overlay enabled for the game.
+    #[inline]
+    pub fn overlay_enabled(&self) -> bool {
+        self.call(
+            |api| api.utils,
+            |api, utils| unsafe { (api.utils_overlay_enabled)(utils) },
+        )
+        .unwrap_or(false)
+    }
+
+    /// If the Steam overlay is open. Games should pause while it is, since the player is
+    /// using it and not the game.
+    #[inline]
+    pub fn overlay_active(&self) -> bool {
+        self.0.overlay_active.get()
+    }
+
+    /// If the Steam overlay was opened or closed this frame.
+    #[inline]
+    pub fn overlay_changed(&self) -> bool {
+        self.0.overlay_changed.get()
+    }
+
+    /// If the overlay needs the game to keep presenting frames to animate, such as when it
+    /// shows a notification. Games that stop rendering while idle should keep going while
+    /// this is true.
+    #[inline]
+    pub fn overlay_needs_present(&self) -> bool {
+        self.call(
+            |api| api.utils,
+            |api, utils| unsafe { (api.utils_overlay_needs_present)(utils) },
+        )
+        .unwrap_or(false)
+    }
+
+    /// Steam Cloud file storage, which save slots can be synced with.
+    #[inline]
+    pub fn cloud(&self) -> SteamCloud {
+        SteamCloud(self.clone())
+    }
+
+    /// Steam Input action sets and actions.
+    #[inline]
+    pub fn input(&self) -> SteamInput {
+        SteamInput(self.clone())
+    }
+
+    /// Call a function of one of the API's interfaces, or return `None` if Steam is not
+    /// running or the interface is not available.
+    pub(crate) fn call<R>(
+        &self,
+        iface: impl FnOnce(&SteamApi) -> unsafe extern "C" fn() -> Iface,
+        f: impl FnOnce(&SteamApi, Iface) -> R,
+    ) -> Option<R> {
+        if !self.is_running() {
+            return None;
+        }
+        let api = &self.0.api;
+        // SAFETY: steam is running, so its interface accessors are safe to call
+        let ptr = unsafe { iface(api)() };
+        (!ptr.is_null()).then(|| f(api, ptr))
+    }
+
+    /// Process Steam's callbacks and read Steam Input actions.
+    pub(crate) fn update(&self) {
+        if !self.is_running() {
+            return;
+        }
+        self.0.overlay_changed.set(false);
+
+        let api = &self.0.api;
+        let pipe = self.0.pipe;
+        // SAFETY: steam is running, and each callback is freed before fetching the next one
+        unsafe {
+            (api.dispatch_run_frame)(pipe);
+            let mut msg = std::mem::zeroed::<CallbackMsg>();
+            while (api.dispatch_next)(pipe, &mut msg) {
+                if msg.callback == GAME_OVERLAY_ACTIVATED && msg.param_len > 0 {
+                    let active = *msg.param != 0;
+                    if active != self.0.overlay_active.get() {
+                        self.0.overlay_active.set(active);
+                        self.0.overlay_changed.set(true);
+                    }
+                }
+                (api.dispatch_free)(pipe);
+            }
+        }
+
+        if self.0.input_ready.get() {
+            self.update_input();
+        }
+    }
+
+    /// Read every action, combining all connected controllers.
+    fn update_input(&self) {
+        self.call(
+            |api| api.input,
+            |api, input| {
+                let mut handles = [0u64; MAX_CONTROLLERS];
+                // SAFETY: the handle buffer holds as many controllers as steam reports at most
+                let count = unsafe {
+                    (api.input_run_frame)(input, false);
+                    (api.input_controllers)(input, handles.as_mut_ptr())
+                        .clamp(0, MAX_CONTROLLERS as i32) as usize
+                };
+                let handles = &handles[..count];
+                self.0.controllers.set(count);
+
+                for action in self.0.digital.borrow().iter() {
+                    let handle = action.resolve(|name| unsafe {
+                        (api.input_digital_handle)(input, name.as_ptr())
+                    });
+                    let down = handle != 0
+                        && handles.iter().any(|&pad| {
+                            let data: DigitalActionData =
+                                unsafe { (api.input_digital_data)(input, pad, handle) };
+                            data.active && data.state
+                        });
+                    action.set_down(down);
+                }
+
+                for action in self.0.analog.borrow().iter() {
+                    let handle = action
+                        .resolve(|name| unsafe { (api.input_analog_handle)(input, name.as_ptr()) });
+                    let mut value = Vec2F::ZERO;
+                    if handle != 0 {
+                        for &pad in handles {
+                            let data: AnalogActionData =
+                                unsafe { (api.input_analog_data)(input, pad, handle) };
+                            let (x, y) = (data.x, data.y);
+                            if data.active && x * x + y * y > value.sqr_len() {
+                                value = vec2(x, y);
+                            }
+                        }
+                    }
+                    action.set_value(value);
+                }
+            },
+        );
+    }
+
+    /// Shut down Steam. Called when the game exits.
+    pub(crate) fn shutdown(&self) {
+        if !self.0.running.replace(false) {
+            return;
+        }
+        let api = &self.0.api;
+        // SAFETY: steam was running, and is never called again after this
+        unsafe {
+            if self.0.input_ready.get() {
+                let input = (api.input)();
+                if !input.is_null() {
+                    (api.input_shutdown)(input);
+                }
+            }
+            (api.shutdown)();
+        }
+    }
+}
+
+impl Drop for SteamState {
+    fn drop(&mut self) {
+        if self.running.get() {
+            // SAFETY: steam was running, and is never called again after this
+            unsafe { (self.api.shutdown)() };
+        }
+    }
+}
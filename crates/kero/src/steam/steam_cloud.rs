@@ -0,0 +1,144 @@
+use crate::steam::Steam;
+use crate::storage::{CloudFiles, StorageError};
+use std::ffi::{CStr, CString, c_void};
+use std::io::{Error, ErrorKind};
+
+/// Steam Cloud file storage, which [save slots](crate::storage::SaveSlots) can be uploaded to
+/// and downloaded from.
+///
+/// ```ignore
+/// let mut cloud = steam.cloud();
+/// if cloud.is_enabled() {
+///     for id in slots.cloud_slots(&cloud)? {
+///         slots.download(&id, &cloud)?;
+///     }
+/// }
+///
+/// // after saving
+/// slots.upload("slot1", &mut cloud)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SteamCloud(pub(crate) Steam);
+
+impl SteamCloud {
+    /// If the player has Steam Cloud enabled, both for their account and for this game.
+    pub fn is_enabled(&self) -> bool {
+        self.0
+            .call(
+                |api| api.storage,
+                |api, storage| unsafe {
+                    (api.storage_account_enabled)(storage) && (api.storage_app_enabled)(storage)
+                },
+            )
+            .unwrap_or(false)
+    }
+}
+
+impl CloudFiles for SteamCloud {
+    fn list(&self) -> Result<Vec<String>, StorageError> {
+        self.0
+            .call(
+                |api| api.storage,
+                |api, storage| unsafe {
+                    let count = (api.storage_file_count)(storage);
+                    (0..count)
+                        .filter_map(|i| {
+                            let mut size = 0;
+                            let name = (api.storage_file_name)(storage, i, &mut size);
+                            (!name.is_null())
+                                .then(|| CStr::from_ptr(name).to_string_lossy().into_owned())
+                        })
+                        .collect()
+                },
+            )
+            .ok_or_else(shut_down)
+    }
+
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let name = c_name(name)?;
+        self.0
+            .call(
+                |api| api.storage,
+                |api, storage| unsafe {
+                    if !(api.storage_file_exists)(storage, name.as_ptr()) {
+                        return Ok(None);
+                    }
+                    let size = (api.storage_file_size)(storage, name.as_ptr()).max(0);
+                    let mut bytes = vec![0u8; size as usize];
+                    let read = (api.storage_file_read)(
+                        storage,
+                        name.as_ptr(),
+                        bytes.as_mut_ptr() as *mut c_void,
+                        size,
+                    );
+                    match read == size {
+                        true => Ok(Some(bytes)),
+                        false => Err(failed("read", &name)),
+                    }
+                },
+            )
+            .ok_or_else(shut_down)?
+    }
+
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<(), StorageError> {
+        let name = c_name(name)?;
+        let len = i32::try_from(bytes.len())
+            .map_err(|_| Error::new(ErrorKind::FileTooLarge, "file too large for steam cloud"))?;
+        let written = self
+            .0
+            .call(
+                |api| api.storage,
+                |api, storage| unsafe {
+                    (api.storage_file_write)(
+                        storage,
+                        name.as_ptr(),
+                        bytes.as_ptr() as *const c_void,
+                        len,
+                    )
+                },
+            )
+            .ok_or_else(shut_down)?;
+        match written {
+            true => Ok(()),
+            false => Err(failed("write", &name)),
+        }
+    }
+
+    fn delete(&mut self, name: &str) -> Result<(), StorageError> {
+        let name = c_name(name)?;
+        self.0
+            .call(
+                |api| api.storage,
+                |api, storage| unsafe {
+                    match !(api.storage_file_exists)(storage, name.as_ptr())
+                        || (api.storage_file_delete)(storage, name.as_ptr())
+                    {
+                        true => Ok(()),
+                        false => Err(failed("delete", &name)),
+                    }
+                },
+            )
+            .ok_or_else(shut_down)?
+    }
+}
+
+#[inline]
+fn c_name(name: &str) -> Result<CString, StorageError> {
+    CString::new(name).map_err(|_| {
+        Error::new(
+            ErrorKind::InvalidInput,
+            format!("invalid steam cloud file name [{name}]"),
+        )
+        .into()
+    })
+}
+
+#[inline]
+fn failed(action: &str, name: &CStr) -> StorageError {
+    Error::other(format!("failed to {action} steam cloud file {name:?}")).into()
+}
+
+#[inline]
+fn shut_down() -> StorageError {
+    Error::new(ErrorKind::NotConnected, "steam has been shut down").into()
+}
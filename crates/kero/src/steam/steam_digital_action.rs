@@ -0,0 +1,68 @@
+use std::cell::Cell;
+use std::ffi::CString;
+use std::rc::Rc;
+
+/// Handle to a Steam Input digital action, such as `jump`, which is either down or up.
+///
+/// The action is read from every connected controller once per frame, and is down if it is
+/// down on any of them. Create one with [`SteamInput::digital_action`](crate::steam::SteamInput::digital_action).
+#[derive(Debug, Clone)]
+pub struct SteamDigitalAction(Rc<Inner>);
+
+#[derive(Debug)]
+struct Inner {
+    name: CString,
+    handle: Cell<u64>,
+    down: Cell<bool>,
+    was_down: Cell<bool>,
+}
+
+impl SteamDigitalAction {
+    #[inline]
+    pub(crate) fn new(name: CString) -> Self {
+        Self(Rc::new(Inner {
+            name,
+            handle: Cell::new(0),
+            down: Cell::new(false),
+            was_down: Cell::new(false),
+        }))
+    }
+
+    /// The action's name in the game's Steam Input configuration.
+    #[inline]
+    pub fn name(&self) -> &str {
+        self.0.name.to_str().unwrap_or_default()
+    }
+
+    /// If the action is held down.
+    #[inline]
+    pub fn down(&self) -> bool {
+        self.0.down.get()
+    }
+
+    /// If the action was pressed this frame.
+    #[inline]
+    pub fn pressed(&self) -> bool {
+        self.0.down.get() && !self.0.was_down.get()
+    }
+
+    /// If the action was released this frame.
+    #[inline]
+    pub fn released(&self) -> bool {
+        !self.0.down.get() && self.0.was_down.get()
+    }
+
+    /// The action's handle, looked up by name until Steam has loaded the configuration.
+    #[inline]
+    pub(crate) fn resolve(&self, lookup: impl FnOnce(&CString) -> u64) -> u64 {
+        if self.0.handle.get() == 0 {
+            self.0.handle.set(lookup(&self.0.name));
+        }
+        self.0.handle.get()
+    }
+
+    #[inline]
+    pub(crate) fn set_down(&self, down: bool) {
+        self.0.was_down.set(self.0.down.replace(down));
+    }
+}
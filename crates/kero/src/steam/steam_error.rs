@@ -0,0 +1,18 @@
+/// An error starting or talking to Steam.
+#[derive(Debug, thiserror::Error)]
+pub enum SteamError {
+    #[error("failed to load the steam api library: {0}")]
+    Load(String),
+
+    #[error("the steam api library is missing [{0}]")]
+    MissingFunction(&'static str),
+
+    #[error("failed to connect to steam: {0}")]
+    Init(String),
+
+    #[error("steam has been shut down")]
+    ShutDown,
+
+    #[error("invalid steam name [{0}]")]
+    InvalidName(String),
+}
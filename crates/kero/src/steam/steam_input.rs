@@ -0,0 +1,83 @@
+use crate::steam::steam_api::ALL_CONTROLLERS;
+use crate::steam::{Steam, SteamAnalogAction, SteamDigitalAction, SteamError};
+use std::ffi::CString;
+
+/// Steam Input, which lets players rebind the game's actions for any controller through
+/// Steam, including ones the game knows nothing about.
+///
+/// Actions and action sets are defined in the game's Steam Input configuration file. Action
+/// sets group the actions used in one part of the game, such as `menu_controls` and
+/// `ship_controls`, and the active set decides which bindings are used.
+///
+/// Actions can be read directly, or fed into the game's [virtual inputs](crate::input) so
+/// the same code handles the keyboard, gamepads, and Steam Input.
+///
+/// ```ignore
+/// let input = steam.input();
+/// input.activate_set("ship_controls")?;
+///
+/// let controller = VirtualController::basic(ctx);
+/// controller.south.set_steam_action(input.digital_action("jump")?);
+/// controller.left_stick.set_steam_action(input.analog_action("move")?);
+///
+/// // during update
+/// if controller.south.pressed() {
+///     player.jump();
+/// }
+/// player.vel.x = controller.left_stick.x() * SPEED;
+/// ```
+#[derive(Debug, Clone)]
+pub struct SteamInput(pub(crate) Steam);
+
+impl SteamInput {
+    /// If Steam Input started, which requires the game to have an input configuration.
+    #[inline]
+    pub fn is_available(&self) -> bool {
+        self.0.is_running() && self.0.0.input_ready.get()
+    }
+
+    /// How many controllers are connected through Steam Input.
+    #[inline]
+    pub fn controllers(&self) -> usize {
+        self.0.0.controllers.get()
+    }
+
+    /// Make an action set active on every controller, switching their bindings to it.
+    pub fn activate_set(&self, name: &str) -> Result<(), SteamError> {
+        let name = c_name(name)?;
+        let found = self.0.call(
+            |api| api.input,
+            |api, input| unsafe {
+                let set = (api.input_action_set)(input, name.as_ptr());
+                if set != 0 {
+                    (api.input_activate_set)(input, ALL_CONTROLLERS, set);
+                }
+                set != 0
+            },
+        );
+        match found {
+            Some(true) => Ok(()),
+            Some(false) => Err(SteamError::InvalidName(name.to_string_lossy().into_owned())),
+            None => Err(SteamError::ShutDown),
+        }
+    }
+
+    /// Listen to a digital action, such as `jump`.
+    pub fn digital_action(&self, name: &str) -> Result<SteamDigitalAction, SteamError> {
+        let action = SteamDigitalAction::new(c_name(name)?);
+        self.0.0.digital.borrow_mut().push(action.clone());
+        Ok(action)
+    }
+
+    /// Listen to an analog action, such as `move`.
+    pub fn analog_action(&self, name: &str) -> Result<SteamAnalogAction, SteamError> {
+        let action = SteamAnalogAction::new(c_name(name)?);
+        self.0.0.analog.borrow_mut().push(action.clone());
+        Ok(action)
+    }
+}
+
+#[inline]
+fn c_name(name: &str) -> Result<CString, SteamError> {
+    CString::new(name).map_err(|_| SteamError::InvalidName(name.to_string()))
+}
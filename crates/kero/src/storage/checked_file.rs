@@ -0,0 +1,159 @@
+use crate::misc::StateHasher;
+use crate::storage::StorageError;
+use std::ffi::OsString;
+use std::fs::File;
+use std::hash::Hasher;
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Marks the start of a checked file.
+const MAGIC: &[u8; 4] = b"KCHK";
+
+/// Size of the header before a checked file's contents: the magic, the contents' length, and
+/// their hash.
+const HEADER_LEN: usize = 4 + 8 + 8;
+
+/// Write a file so it is never left half-written. The bytes are written to a temporary file next
+/// to it, flushed to disk, and then renamed over the original, so other programs watching the
+/// folder, such as cloud sync clients, only ever see a complete file.
+pub fn write_atomic(path: impl AsRef<Path>, bytes: &[u8]) -> std::io::Result<()> {
+    let path = path.as_ref();
+    let tmp = with_suffix(path, ".tmp");
+    let result = (|| {
+        let mut file = File::create(&tmp)?;
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+
+        // make sure the rename itself survives a crash
+        #[cfg(unix)]
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            File::open(dir)?.sync_all()?;
+        }
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// The path of a file's `n`th backup, where `1` is the newest.
+#[inline]
+pub fn backup_path(path: impl AsRef<Path>, n: u32) -> PathBuf {
+    with_suffix(path.as_ref(), &format!(".bak{n}"))
+}
+
+/// Wrap the bytes with a header holding their length and hash, so corruption can be detected
+/// when reading them back with [`decode_checked`].
+pub fn encode_checked(bytes: &[u8]) -> Vec<u8> {
+    let mut data = Vec::with_capacity(HEADER_LEN + bytes.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&(bytes.len() as u64).to_le_bytes());
+    data.extend_from_slice(&checksum(bytes).to_le_bytes());
+    data.extend_from_slice(bytes);
+    data
+}
+
+/// Unwrap bytes written by [`encode_checked`], or `None` if they are truncated or corrupt.
+pub fn decode_checked(data: &[u8]) -> Option<&[u8]> {
+    let (header, bytes) = data.split_at_checked(HEADER_LEN)?;
+    let len = u64::from_le_bytes(header[4..12].try_into().ok()?);
+    let hash = u64::from_le_bytes(header[12..20].try_into().ok()?);
+    (&header[..4] == MAGIC && len == bytes.len() as u64 && hash == checksum(bytes)).then_some(bytes)
+}
+
+/// Write a file [atomically](write_atomic) with a [checked](encode_checked) header, first
+/// copying the previous version into its backups. Up to `backups` old versions are kept, and
+/// the oldest is deleted. A corrupt previous version is overwritten rather than backed up.
+///
+/// The file itself is only ever replaced, never moved away, so cloud sync clients don't see
+/// it deleted between the backup and the new version.
+pub fn write_checked(
+    path: impl AsRef<Path>,
+    bytes: &[u8],
+    backups: u32,
+) -> Result<(), StorageError> {
+    let path = path.as_ref();
+    let previous = match backups {
+        0 => None,
+        _ => std::fs::read(path)
+            .ok()
+            .filter(|data| decode_checked(data).is_some()),
+    };
+    if let Some(previous) = previous {
+        for n in (1..backups).rev() {
+            let from = backup_path(path, n);
+            if from.exists() {
+                std::fs::rename(&from, backup_path(path, n + 1))?;
+            }
+        }
+        write_atomic(backup_path(path, 1), &previous)?;
+    }
+    Ok(write_atomic(path, &encode_checked(bytes))?)
+}
+
+/// Bytes read from a checked file by [`read_checked`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckedRead {
+    /// The contents of the file.
+    pub bytes: Vec<u8>,
+
+    /// If the file was missing or corrupt, which backup it was recovered from.
+    pub recovered_from: Option<u32>,
+}
+
+/// Read a file written by [`write_checked`]. If it is missing or corrupt, its backups are tried
+/// from newest to oldest, and the first valid one is restored in its place.
+///
+/// Returns a [`NotFound`](ErrorKind::NotFound) error if neither the file nor any backups exist,
+/// and [`StorageError::Corrupt`] if they exist but none are valid.
+pub fn read_checked(path: impl AsRef<Path>, backups: u32) -> Result<CheckedRead, StorageError> {
+    let path = path.as_ref();
+    let mut found = false;
+    for n in 0..=backups {
+        let file = match n {
+            0 => path.to_path_buf(),
+            n => backup_path(path, n),
+        };
+        let data = match std::fs::read(&file) {
+            Ok(data) => data,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(err.into()),
+        };
+        found = true;
+        let Some(bytes) = decode_checked(&data) else {
+            continue;
+        };
+        let bytes = bytes.to_vec();
+        if n == 0 {
+            return Ok(CheckedRead {
+                bytes,
+                recovered_from: None,
+            });
+        }
+        write_atomic(path, &data)?;
+        return Ok(CheckedRead {
+            bytes,
+            recovered_from: Some(n),
+        });
+    }
+    match found {
+        true => Err(StorageError::Corrupt(path.to_path_buf())),
+        false => Err(std::io::Error::from(ErrorKind::NotFound).into()),
+    }
+}
+
+#[inline]
+fn checksum(bytes: &[u8]) -> u64 {
+    let mut hasher = StateHasher::default();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+#[inline]
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = OsString::from(path.as_os_str());
+    name.push(suffix);
+    PathBuf::from(name)
+}
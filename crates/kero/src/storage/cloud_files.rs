@@ -0,0 +1,19 @@
+use crate::storage::StorageError;
+
+/// A remote file store that [save slots](crate::storage::SaveSlots) can be copied to and
+/// from, such as Steam Cloud.
+///
+/// File names use `/` to separate folders, such as `slot1/data.sav`.
+pub trait CloudFiles {
+    /// The names of every stored file.
+    fn list(&self) -> Result<Vec<String>, StorageError>;
+
+    /// Read a file, or `None` if it does not exist.
+    fn read(&self, name: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Write a file, replacing it if it already exists.
+    fn write(&mut self, name: &str, bytes: &[u8]) -> Result<(), StorageError>;
+
+    /// Delete a file. Deleting a file that does not exist is not an error.
+    fn delete(&mut self, name: &str) -> Result<(), StorageError>;
+}
@@ -0,0 +1,19 @@
+//! Saving game data to disk safely, with backups and save slots.
+
+mod checked_file;
+mod cloud_files;
+mod save_conflict;
+mod save_slot;
+mod save_slots;
+mod storage_error;
+mod version_vector;
+mod zip_writer;
+
+pub use checked_file::*;
+pub use cloud_files::*;
+pub use save_conflict::*;
+pub use save_slot::*;
+pub use save_slots::*;
+pub use storage_error::*;
+pub use version_vector::*;
+pub use zip_writer::*;
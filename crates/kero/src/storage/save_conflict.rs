@@ -0,0 +1,39 @@
+use crate::storage::{SlotMeta, VersionVector};
+use std::time::SystemTime;
+
+/// A save slot that was changed somewhere else, such as by a cloud sync from another computer,
+/// since it was last loaded or saved here.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveConflict {
+    /// The slot's name.
+    pub id: String,
+
+    /// The versions of the slot last loaded or saved here.
+    pub local: VersionVector,
+
+    /// The slot's metadata on disk, as changed elsewhere.
+    pub remote: SlotMeta,
+
+    /// When the slot's save data on disk was last modified, if known.
+    pub remote_modified: Option<SystemTime>,
+}
+
+impl SaveConflict {
+    /// If the slot was only changed elsewhere, so the remote save already includes everything
+    /// saved here. Otherwise both sides have progress the other does not.
+    #[inline]
+    pub fn is_fast_forward(&self) -> bool {
+        self.remote.versions.contains(&self.local)
+    }
+}
+
+/// How to resolve a [`SaveConflict`].
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum ConflictResolution {
+    /// Overwrite the slot with the data being saved here.
+    #[default]
+    KeepLocal,
+
+    /// Keep the slot as it is on disk and discard the data being saved here.
+    KeepRemote,
+}
@@ -0,0 +1,82 @@
+use crate::storage::VersionVector;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Information about a save slot, shown on a load screen without loading the whole save.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SlotMeta {
+    /// The name shown to the player.
+    pub label: String,
+
+    /// Total time played in the slot, in seconds.
+    pub play_time: f64,
+
+    /// When the slot was created, in seconds since the Unix epoch.
+    pub created_at: u64,
+
+    /// When the slot was last saved, in seconds since the Unix epoch.
+    pub saved_at: u64,
+
+    /// How many times the slot has been saved.
+    pub save_count: u32,
+
+    /// The device that last saved the slot.
+    pub device: String,
+
+    /// How many times each device has saved the slot, for detecting saves made elsewhere.
+    pub versions: VersionVector,
+}
+
+impl SlotMeta {
+    /// Create metadata for a new slot, created now.
+    #[inline]
+    pub fn new(label: impl Into<String>) -> Self {
+        let now = unix_now();
+        Self {
+            label: label.into(),
+            created_at: now,
+            saved_at: now,
+            ..Default::default()
+        }
+    }
+}
+
+/// A save slot found by [`SaveSlots::list`](crate::storage::SaveSlots::list).
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaveSlot {
+    /// The slot's name, which is also the name of its folder.
+    pub id: String,
+
+    /// The slot's metadata. If it could not be read, this is the default.
+    pub meta: SlotMeta,
+
+    /// If the slot's metadata is corrupt and could not be recovered from a backup.
+    pub corrupt: bool,
+
+    /// The slot's thumbnail image, if it has one.
+    pub thumbnail: Option<PathBuf>,
+}
+
+/// Save data loaded by [`SaveSlots::load`](crate::storage::SaveSlots::load).
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadedSlot<T> {
+    /// The loaded data.
+    pub data: T,
+
+    /// The slot's metadata.
+    pub meta: SlotMeta,
+
+    /// If the save was missing or corrupt, which backup it was recovered from, so the player
+    /// can be told some progress may have been lost.
+    pub recovered_from: Option<u32>,
+}
+
+/// The current time in seconds since the Unix epoch.
+#[inline]
+pub(crate) fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
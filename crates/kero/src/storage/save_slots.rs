@@ -0,0 +1,467 @@
+use crate::img::ImageRgba8;
+use crate::misc::Snapshot;
+use crate::storage::{
+    CloudFiles, ConflictResolution, LoadedSlot, SaveConflict, SaveSlot, SlotMeta, StorageError,
+    VersionVector, decode_checked, read_checked, unix_now, write_atomic, write_checked,
+};
+use fnv::FnvHashMap;
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+const DATA_FILE: &str = "data.sav";
+const META_FILE: &str = "meta.sav";
+const THUMBNAIL_FILE: &str = "thumbnail.png";
+
+/// Manages save slots in a folder, each holding a [`Snapshot`], its [metadata](SlotMeta), and
+/// an optional thumbnail.
+///
+/// Every save is written atomically with a checksum, and the previous few saves are kept as
+/// backups. If a save turns out to be corrupt when loading, the newest valid backup is
+/// restored in its place.
+///
+/// Each slot records how many times every device has saved it. If the save folder is synced
+/// between computers, such as by Steam Cloud or OneDrive, saving over a slot that was changed
+/// elsewhere since it was last loaded or saved here calls the
+/// [conflict handler](Self::on_conflict) to decide which version to keep.
+///
+/// ```ignore
+/// let slots = SaveSlots::new(ctx.data_dir().join("saves"))
+///     .with_device(load_device_id(ctx.data_local_dir())?)
+///     .on_conflict(|conflict| match conflict.is_fast_forward() {
+///         true => ConflictResolution::KeepRemote,
+///         false => ask_player(conflict),
+///     });
+///
+/// // on the load screen
+/// for slot in slots.list()? {
+///     println!("{}: {:.0}s played", slot.meta.label, slot.meta.play_time);
+/// }
+///
+/// // when saving
+/// slots.save("slot1", &save_data, play_time)?;
+/// slots.save_thumbnail("slot1", &ctx.graphics.download_texture(&screen).to_rgba8())?;
+///
+/// // when loading
+/// let loaded = slots.load::<SaveData>("slot1")?;
+/// ```
+pub struct SaveSlots {
+    root: PathBuf,
+    backups: u32,
+    device: String,
+    known: RefCell<FnvHashMap<String, VersionVector>>,
+    on_conflict: Option<ConflictHandler>,
+}
+
+type ConflictHandler = Box<dyn Fn(&SaveConflict) -> ConflictResolution>;
+
+impl Debug for SaveSlots {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SaveSlots")
+            .field("root", &self.root)
+            .field("backups", &self.backups)
+            .field("device", &self.device)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SaveSlots {
+    /// Manage save slots in the folder, keeping 3 backups of each save.
+    #[inline]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            backups: 3,
+            device: String::from("local"),
+            known: RefCell::new(FnvHashMap::default()),
+            on_conflict: None,
+        }
+    }
+
+    /// Return the manager keeping a different number of backups of each save.
+    #[inline]
+    pub fn with_backups(mut self, backups: u32) -> Self {
+        self.backups = backups;
+        self
+    }
+
+    /// Return the manager saving as the device, which should be unique to this computer, such
+    /// as one from [`load_device_id`](crate::storage::load_device_id).
+    #[inline]
+    pub fn with_device(mut self, device: impl Into<String>) -> Self {
+        self.device = device.into();
+        self
+    }
+
+    /// Return the manager with a function that decides which version to keep when saving over
+    /// a slot that was changed elsewhere. Without one, the data being saved always wins.
+    #[inline]
+    pub fn on_conflict(
+        mut self,
+        f: impl Fn(&SaveConflict) -> ConflictResolution + 'static,
+    ) -> Self {
+        self.on_conflict = Some(Box::new(f));
+        self
+    }
+
+    /// The folder holding the save slots.
+    #[inline]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// How many backups of each save are kept.
+    #[inline]
+    pub fn backups(&self) -> u32 {
+        self.backups
+    }
+
+    /// The folder of a slot. Slot names may only contain ASCII letters, digits, `-`, and `_`.
+    pub fn slot_dir(&self, id: &str) -> Result<PathBuf, StorageError> {
+        let valid = !id.is_empty()
+            && id.len() <= 64
+            && id
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+        match valid {
+            true => Ok(self.root.join(id)),
+            false => Err(StorageError::InvalidSlot(id.to_string())),
+        }
+    }
+
+    /// The device the manager saves as.
+    #[inline]
+    pub fn device(&self) -> &str {
+        &self.device
+    }
+
+    /// If the slot exists.
+    #[inline]
+    pub fn exists(&self, id: &str) -> bool {
+        self.slot_dir(id).is_ok_and(|dir| dir.is_dir())
+    }
+
+    /// All the save slots, most recently saved first.
+    pub fn list(&self) -> Result<Vec<SaveSlot>, StorageError> {
+        let entries = match std::fs::read_dir(&self.root) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        let mut slots = Vec::new();
+        for entry in entries {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let Some(id) = entry.file_name().to_str().map(str::to_string) else {
+                continue;
+            };
+            if self.slot_dir(&id).is_err() {
+                continue;
+            }
+            let (meta, corrupt) = match self.meta(&id) {
+                Ok(meta) => (meta, false),
+                Err(StorageError::Io(err)) if err.kind() == ErrorKind::NotFound => continue,
+                Err(_) => (SlotMeta::default(), true),
+            };
+            let thumbnail = self.thumbnail_path(&id);
+            slots.push(SaveSlot {
+                id,
+                meta,
+                corrupt,
+                thumbnail,
+            });
+        }
+        slots.sort_by(|a, b| {
+            b.meta
+                .saved_at
+                .cmp(&a.meta.saved_at)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        Ok(slots)
+    }
+
+    /// Create an empty slot with the label shown to the player.
+    pub fn create(&self, id: &str, label: impl Into<String>) -> Result<SlotMeta, StorageError> {
+        let dir = self.slot_dir(id)?;
+        if dir.is_dir() {
+            return Err(StorageError::SlotExists(id.to_string()));
+        }
+        std::fs::create_dir_all(&dir)?;
+        let mut meta = SlotMeta::new(label);
+        meta.device = self.device.clone();
+        meta.versions.increment(&self.device);
+        self.write_meta(&dir, &meta)?;
+        self.remember(id, &meta);
+        Ok(meta)
+    }
+
+    /// Delete a slot, along with its backups and thumbnail.
+    pub fn delete(&self, id: &str) -> Result<(), StorageError> {
+        let dir = self.slot_dir(id)?;
+        self.known.borrow_mut().remove(id);
+        match std::fs::remove_dir_all(&dir) {
+            Err(err) if err.kind() == ErrorKind::NotFound => {
+                Err(StorageError::SlotNotFound(id.to_string()))
+            }
+            result => Ok(result?),
+        }
+    }
+
+    /// The slot's metadata, recovered from a backup if it is corrupt.
+    pub fn meta(&self, id: &str) -> Result<SlotMeta, StorageError> {
+        let path = self.slot_dir(id)?.join(META_FILE);
+        Ok(serde_json::from_slice(
+            &read_checked(path, self.backups)?.bytes,
+        )?)
+    }
+
+    /// Change the label of a slot shown to the player.
+    pub fn set_label(&self, id: &str, label: impl Into<String>) -> Result<(), StorageError> {
+        let dir = self.existing_dir(id)?;
+        let mut meta = self.meta(id)?;
+        meta.label = label.into();
+        self.write_meta(&dir, &meta)
+    }
+
+    /// If the slot was changed elsewhere since it was last loaded or saved here. Useful to
+    /// check when the game regains focus, in case a sync happened in the background.
+    pub fn check(&self, id: &str) -> Result<Option<SaveConflict>, StorageError> {
+        let Some(local) = self.known.borrow().get(id).cloned() else {
+            return Ok(None);
+        };
+        let remote = match self.meta(id) {
+            Ok(remote) => remote,
+            Err(StorageError::Io(err)) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        if local.contains(&remote.versions) {
+            return Ok(None);
+        }
+        let remote_modified = self
+            .slot_dir(id)?
+            .join(DATA_FILE)
+            .metadata()
+            .and_then(|m| m.modified())
+            .ok();
+        Ok(Some(SaveConflict {
+            id: id.to_string(),
+            local,
+            remote,
+            remote_modified,
+        }))
+    }
+
+    /// Save data to the slot, creating it if it does not exist, and record the total time
+    /// played in it.
+    ///
+    /// If the slot was changed elsewhere and the [conflict handler](Self::on_conflict) chooses
+    /// to keep that version, nothing is saved and [`StorageError::Conflict`] is returned, so
+    /// the game can load the slot again.
+    pub fn save<T: Snapshot>(
+        &self,
+        id: &str,
+        data: &T,
+        play_time: f64,
+    ) -> Result<SlotMeta, StorageError> {
+        let dir = self.slot_dir(id)?;
+        if let Some(conflict) = self.check(id)? {
+            let resolution = self
+                .on_conflict
+                .as_ref()
+                .map_or(ConflictResolution::KeepLocal, |f| f(&conflict));
+            if resolution == ConflictResolution::KeepRemote {
+                self.remember(id, &conflict.remote);
+                return Err(StorageError::Conflict(id.to_string()));
+            }
+        }
+        let mut meta = match dir.is_dir() {
+            true => self.meta(id).unwrap_or_else(|_| SlotMeta::new(id)),
+            false => {
+                std::fs::create_dir_all(&dir)?;
+                SlotMeta::new(id)
+            }
+        };
+        write_checked(
+            dir.join(DATA_FILE),
+            &data.to_snapshot_bytes()?,
+            self.backups,
+        )?;
+        meta.play_time = play_time;
+        meta.saved_at = unix_now();
+        meta.save_count += 1;
+        meta.device = self.device.clone();
+        if let Some(known) = self.known.borrow().get(id) {
+            meta.versions.merge(known);
+        }
+        meta.versions.increment(&self.device);
+        self.write_meta(&dir, &meta)?;
+        self.remember(id, &meta);
+        Ok(meta)
+    }
+
+    /// Load the data saved in a slot. If the save is corrupt, it is recovered from the newest
+    /// valid backup.
+    pub fn load<T: Snapshot>(&self, id: &str) -> Result<LoadedSlot<T>, StorageError> {
+        let dir = self.existing_dir(id)?;
+        let read = read_checked(dir.join(DATA_FILE), self.backups)?;
+        let data = T::from_snapshot_bytes(&read.bytes)?;
+        let meta = self.meta(id).unwrap_or_else(|_| SlotMeta::new(id));
+        self.remember(id, &meta);
+        Ok(LoadedSlot {
+            data,
+            meta,
+            recovered_from: read.recovered_from,
+        })
+    }
+
+    /// Save a thumbnail image for the slot, such as a screenshot of where the player saved.
+    pub fn save_thumbnail(&self, id: &str, image: &ImageRgba8) -> Result<(), StorageError> {
+        let dir = self.existing_dir(id)?;
+        let mut png = Vec::new();
+        image.save_png(&mut png)?;
+        Ok(write_atomic(dir.join(THUMBNAIL_FILE), &png)?)
+    }
+
+    /// The path of the slot's thumbnail image, if it has one.
+    #[inline]
+    pub fn thumbnail_path(&self, id: &str) -> Option<PathBuf> {
+        let path = self.slot_dir(id).ok()?.join(THUMBNAIL_FILE);
+        path.is_file().then_some(path)
+    }
+
+    /// The names of the slots stored in the cloud.
+    pub fn cloud_slots(&self, cloud: &dyn CloudFiles) -> Result<Vec<String>, StorageError> {
+        let mut ids: Vec<String> = cloud
+            .list()?
+            .into_iter()
+            .filter_map(|name| {
+                let (id, file) = name.split_once('/')?;
+                (file == META_FILE && self.slot_dir(id).is_ok()).then(|| id.to_string())
+            })
+            .collect();
+        ids.sort();
+        Ok(ids)
+    }
+
+    /// The metadata of a slot stored in the cloud, or `None` if it has not been uploaded.
+    pub fn cloud_meta(
+        &self,
+        id: &str,
+        cloud: &dyn CloudFiles,
+    ) -> Result<Option<SlotMeta>, StorageError> {
+        self.slot_dir(id)?;
+        let Some(data) = cloud.read(&format!("{id}/{META_FILE}"))? else {
+            return Ok(None);
+        };
+        let bytes = decode_checked(&data)
+            .ok_or_else(|| StorageError::Corrupt(format!("{id}/{META_FILE}").into()))?;
+        Ok(Some(serde_json::from_slice(bytes)?))
+    }
+
+    /// Copy a slot's files to the cloud.
+    ///
+    /// If the cloud copy has saves from another device that this slot does not, the
+    /// [conflict handler](Self::on_conflict) decides which to keep. Keeping the cloud's
+    /// version uploads nothing and returns [`StorageError::Conflict`], so the game can
+    /// [download](Self::download) it instead.
+    pub fn upload(&self, id: &str, cloud: &mut dyn CloudFiles) -> Result<(), StorageError> {
+        let dir = self.existing_dir(id)?;
+        let local = self.meta(id)?;
+        if let Some(remote) = self.cloud_meta(id, cloud)?
+            && !local.versions.contains(&remote.versions)
+            && self.resolve(id, &local, remote) == ConflictResolution::KeepRemote
+        {
+            return Err(StorageError::Conflict(id.to_string()));
+        }
+
+        // the metadata goes last, so an interrupted upload is never mistaken for a newer save
+        for file in [DATA_FILE, THUMBNAIL_FILE, META_FILE] {
+            let name = format!("{id}/{file}");
+            match std::fs::read(dir.join(file)) {
+                Ok(bytes) => cloud.write(&name, &bytes)?,
+                Err(err) if err.kind() == ErrorKind::NotFound => cloud.delete(&name)?,
+                Err(err) => return Err(err.into()),
+            }
+        }
+        Ok(())
+    }
+
+    /// Copy a slot's files from the cloud, returning `true` if the cloud had newer saves.
+    ///
+    /// The local files are backed up first as if they were saved over. If this slot has saves
+    /// the cloud copy does not, the [conflict handler](Self::on_conflict) decides which to keep,
+    /// and keeping the local version downloads nothing.
+    pub fn download(&self, id: &str, cloud: &dyn CloudFiles) -> Result<bool, StorageError> {
+        let dir = self.slot_dir(id)?;
+        let Some(remote) = self.cloud_meta(id, cloud)? else {
+            return Ok(false);
+        };
+        if let Ok(local) = self.meta(id) {
+            if local.versions.contains(&remote.versions) {
+                return Ok(false);
+            }
+            if !remote.versions.contains(&local.versions)
+                && self.resolve(id, &local, remote.clone()) == ConflictResolution::KeepLocal
+            {
+                return Ok(false);
+            }
+        }
+
+        let name = format!("{id}/{DATA_FILE}");
+        let data = cloud
+            .read(&name)?
+            .ok_or_else(|| StorageError::Corrupt(name.clone().into()))?;
+        let data = decode_checked(&data).ok_or_else(|| StorageError::Corrupt(name.into()))?;
+        std::fs::create_dir_all(&dir)?;
+        write_checked(dir.join(DATA_FILE), data, self.backups)?;
+        match cloud.read(&format!("{id}/{THUMBNAIL_FILE}"))? {
+            Some(png) => write_atomic(dir.join(THUMBNAIL_FILE), &png)?,
+            None => match std::fs::remove_file(dir.join(THUMBNAIL_FILE)) {
+                Err(err) if err.kind() != ErrorKind::NotFound => return Err(err.into()),
+                _ => {}
+            },
+        }
+        self.write_meta(&dir, &remote)?;
+        self.remember(id, &remote);
+        Ok(true)
+    }
+
+    /// Ask the conflict handler which of two diverged versions of a slot to keep.
+    fn resolve(&self, id: &str, local: &SlotMeta, remote: SlotMeta) -> ConflictResolution {
+        let conflict = SaveConflict {
+            id: id.to_string(),
+            local: local.versions.clone(),
+            remote,
+            remote_modified: None,
+        };
+        self.on_conflict
+            .as_ref()
+            .map_or(ConflictResolution::KeepLocal, |f| f(&conflict))
+    }
+
+    /// The folder of a slot that must already exist.
+    fn existing_dir(&self, id: &str) -> Result<PathBuf, StorageError> {
+        let dir = self.slot_dir(id)?;
+        match dir.is_dir() {
+            true => Ok(dir),
+            false => Err(StorageError::SlotNotFound(id.to_string())),
+        }
+    }
+
+    /// Remember the versions of a slot last seen here.
+    #[inline]
+    fn remember(&self, id: &str, meta: &SlotMeta) {
+        self.known
+            .borrow_mut()
+            .insert(id.to_string(), meta.versions.clone());
+    }
+
+    #[inline]
+    fn write_meta(&self, dir: &Path, meta: &SlotMeta) -> Result<(), StorageError> {
+        let bytes = serde_json::to_vec_pretty(meta)?;
+        write_checked(dir.join(META_FILE), &bytes, self.backups)
+    }
+}
@@ -0,0 +1,34 @@
+use crate::img::ImageError;
+use crate::misc::SnapshotError;
+use std::path::PathBuf;
+
+/// An error reading or writing stored data.
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0}")]
+    Snapshot(#[from] SnapshotError),
+
+    #[error("{0}")]
+    Image(#[from] ImageError),
+
+    #[error("file {0:?} is corrupt and has no valid backup")]
+    Corrupt(PathBuf),
+
+    #[error("invalid save slot name [{0}]")]
+    InvalidSlot(String),
+
+    #[error("save slot [{0}] already exists")]
+    SlotExists(String),
+
+    #[error("save slot [{0}] was changed elsewhere, and that version was kept")]
+    Conflict(String),
+
+    #[error("save slot [{0}] does not exist")]
+    SlotNotFound(String),
+}
@@ -0,0 +1,96 @@
+use crate::guid::Guid;
+use crate::storage::{StorageError, write_atomic};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::Path;
+
+/// Counts how many times each device has modified a file, so that changes made elsewhere, such
+/// as by a cloud sync from another computer, can be told apart from our own.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct VersionVector(BTreeMap<String, u64>);
+
+impl VersionVector {
+    /// Create an empty vector.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many modifications the device has made.
+    #[inline]
+    pub fn get(&self, device: &str) -> u64 {
+        self.0.get(device).copied().unwrap_or(0)
+    }
+
+    /// Record a modification by the device.
+    #[inline]
+    pub fn increment(&mut self, device: &str) {
+        *self.0.entry(device.to_string()).or_default() += 1;
+    }
+
+    /// Combine with another vector, keeping the highest count for each device.
+    pub fn merge(&mut self, other: &Self) {
+        for (device, &count) in &other.0 {
+            let entry = self.0.entry(device.clone()).or_default();
+            *entry = (*entry).max(count);
+        }
+    }
+
+    /// How this vector relates to another. `None` if each has modifications the other has not
+    /// seen, meaning they were changed separately and conflict.
+    pub fn compare(&self, other: &Self) -> Option<Ordering> {
+        let mut less = false;
+        let mut greater = false;
+        for device in self.0.keys().chain(other.0.keys()) {
+            match self.get(device).cmp(&other.get(device)) {
+                Ordering::Less => less = true,
+                Ordering::Greater => greater = true,
+                Ordering::Equal => {}
+            }
+        }
+        match (less, greater) {
+            (false, false) => Some(Ordering::Equal),
+            (true, false) => Some(Ordering::Less),
+            (false, true) => Some(Ordering::Greater),
+            (true, true) => None,
+        }
+    }
+
+    /// If every modification in the other vector has been seen by this one.
+    #[inline]
+    pub fn contains(&self, other: &Self) -> bool {
+        matches!(
+            self.compare(other),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    }
+
+    /// Iterate over each device and how many modifications it has made.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.0
+            .iter()
+            .map(|(device, &count)| (device.as_str(), count))
+    }
+}
+
+/// Load this device's ID from a file in the folder, creating a new random one the first time.
+///
+/// The folder should not be synced between computers, so use a local one such as
+/// [`Context::data_local_dir`](crate::core::Context::data_local_dir).
+pub fn load_device_id(dir: impl AsRef<Path>) -> Result<String, StorageError> {
+    let path = dir.as_ref().join("device_id");
+    match std::fs::read_to_string(&path) {
+        Ok(id) if !id.trim().is_empty() => return Ok(id.trim().to_string()),
+        Ok(_) => {}
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    let id = Guid::new().to_string();
+    std::fs::create_dir_all(dir.as_ref())?;
+    write_atomic(path, id.as_bytes())?;
+    Ok(id)
+}
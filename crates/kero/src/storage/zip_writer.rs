@@ -0,0 +1,121 @@
+use flate2::Compression;
+use flate2::Crc;
+use flate2::write::DeflateEncoder;
+use std::io::Write;
+
+/// Builds a zip archive in memory, compressing each file with deflate.
+///
+/// ```ignore
+/// let mut zip = ZipWriter::new();
+/// zip.add_file("notes.txt", b"hello")?;
+/// write_atomic("notes.zip", &zip.finish())?;
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct ZipWriter {
+    data: Vec<u8>,
+    entries: Vec<ZipEntry>,
+}
+
+#[derive(Debug, Clone)]
+struct ZipEntry {
+    name: String,
+    crc: u32,
+    compressed: u32,
+    size: u32,
+    offset: u32,
+}
+
+/// Jan 1 1980, the earliest date a zip can hold.
+const DOS_DATE: u16 = 0x21;
+
+/// Marks names as UTF-8.
+const UTF8_FLAG: u16 = 0x0800;
+
+/// The deflate compression method.
+const DEFLATE: u16 = 8;
+
+impl ZipWriter {
+    /// Create an empty archive.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many files have been added.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// If no files have been added.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add a file to the archive. Use `/` to put it in a folder.
+    pub fn add_file(&mut self, name: &str, bytes: &[u8]) -> std::io::Result<()> {
+        let mut crc = Crc::new();
+        crc.update(bytes);
+        let mut enc = DeflateEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(bytes)?;
+        let compressed = enc.finish()?;
+
+        let entry = ZipEntry {
+            name: name.to_string(),
+            crc: crc.sum(),
+            compressed: compressed.len() as u32,
+            size: bytes.len() as u32,
+            offset: self.data.len() as u32,
+        };
+
+        let d = &mut self.data;
+        d.extend_from_slice(&0x04034b50u32.to_le_bytes());
+        d.extend_from_slice(&20u16.to_le_bytes());
+        d.extend_from_slice(&UTF8_FLAG.to_le_bytes());
+        d.extend_from_slice(&DEFLATE.to_le_bytes());
+        d.extend_from_slice(&0u16.to_le_bytes());
+        d.extend_from_slice(&DOS_DATE.to_le_bytes());
+        d.extend_from_slice(&entry.crc.to_le_bytes());
+        d.extend_from_slice(&entry.compressed.to_le_bytes());
+        d.extend_from_slice(&entry.size.to_le_bytes());
+        d.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        d.extend_from_slice(&0u16.to_le_bytes());
+        d.extend_from_slice(name.as_bytes());
+        d.extend_from_slice(&compressed);
+
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    /// Finish the archive and return its bytes.
+    pub fn finish(self) -> Vec<u8> {
+        let Self { mut data, entries } = self;
+        let dir_offset = data.len() as u32;
+        for entry in &entries {
+            data.extend_from_slice(&0x02014b50u32.to_le_bytes());
+            data.extend_from_slice(&20u16.to_le_bytes());
+            data.extend_from_slice(&20u16.to_le_bytes());
+            data.extend_from_slice(&UTF8_FLAG.to_le_bytes());
+            data.extend_from_slice(&DEFLATE.to_le_bytes());
+            data.extend_from_slice(&0u16.to_le_bytes());
+            data.extend_from_slice(&DOS_DATE.to_le_bytes());
+            data.extend_from_slice(&entry.crc.to_le_bytes());
+            data.extend_from_slice(&entry.compressed.to_le_bytes());
+            data.extend_from_slice(&entry.size.to_le_bytes());
+            data.extend_from_slice(&(entry.name.len() as u16).to_le_bytes());
+            data.extend_from_slice(&[0; 12]);
+            data.extend_from_slice(&entry.offset.to_le_bytes());
+            data.extend_from_slice(entry.name.as_bytes());
+        }
+        let dir_size = data.len() as u32 - dir_offset;
+        data.extend_from_slice(&0x06054b50u32.to_le_bytes());
+        data.extend_from_slice(&[0; 4]);
+        data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        data.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+        data.extend_from_slice(&dir_size.to_le_bytes());
+        data.extend_from_slice(&dir_offset.to_le_bytes());
+        data.extend_from_slice(&0u16.to_le_bytes());
+        data
+    }
+}
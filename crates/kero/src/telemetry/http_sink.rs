@@ -0,0 +1,119 @@
+use crate::telemetry::{JsonLinesSink, TelemetryError, TelemetryEvent, TelemetrySink};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+
+/// Sends HTTP requests for an [`HttpSink`], so any HTTP client can be plugged in.
+///
+/// Implemented for closures taking the URL and request body. Called from a background thread,
+/// so it can block until the request finishes.
+pub trait HttpSender: Send + 'static {
+    /// Post the body to the URL. The body is JSON lines, one event per line.
+    fn post(&mut self, url: &str, body: &[u8]) -> Result<(), TelemetryError>;
+}
+
+impl<F> HttpSender for F
+where
+    F: FnMut(&str, &[u8]) -> Result<(), TelemetryError> + Send + 'static,
+{
+    #[inline]
+    fn post(&mut self, url: &str, body: &[u8]) -> Result<(), TelemetryError> {
+        self(url, body)
+    }
+}
+
+/// Posts batches of events to a server. Batches that fail to send, such as while offline, can
+/// be kept in a local queue file and retried with the next batch.
+///
+/// ```ignore
+/// let sink = HttpSink::new("https://example.com/events", |url: &str, body: &[u8]| {
+///     ureq::post(url)
+///         .send(body)
+///         .map(|_| ())
+///         .map_err(|err| TelemetryError::Send(err.to_string()))
+/// })
+/// .with_queue(ctx.data_local_dir().join("telemetry_queue.jsonl"));
+/// ```
+pub struct HttpSink<S> {
+    url: String,
+    sender: S,
+    queue: Option<JsonLinesSink>,
+    max_queued: usize,
+}
+
+impl<S> Debug for HttpSink<S> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HttpSink")
+            .field("url", &self.url)
+            .field("queue", &self.queue)
+            .field("max_queued", &self.max_queued)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: HttpSender> HttpSink<S> {
+    /// Create a sink posting to the URL with the sender.
+    #[inline]
+    pub fn new(url: impl Into<String>, sender: S) -> Self {
+        Self {
+            url: url.into(),
+            sender,
+            queue: None,
+            max_queued: 10000,
+        }
+    }
+
+    /// Return the sink keeping batches that fail to send in a local file, to retry them later.
+    #[inline]
+    pub fn with_queue(mut self, path: impl Into<PathBuf>) -> Self {
+        self.queue = Some(JsonLinesSink::new(path));
+        self
+    }
+
+    /// Return the sink keeping at most this many events in its queue, dropping the oldest.
+    /// Defaults to `10000`.
+    #[inline]
+    pub fn with_max_queued(mut self, max_queued: usize) -> Self {
+        self.max_queued = max_queued;
+        self
+    }
+
+    /// The URL events are posted to.
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+}
+
+impl<S: HttpSender> TelemetrySink for HttpSink<S> {
+    fn send(&mut self, events: &[TelemetryEvent]) -> Result<(), TelemetryError> {
+        let mut batch = match &self.queue {
+            Some(queue) => queue.read()?,
+            None => Vec::new(),
+        };
+        batch.extend_from_slice(events);
+        if batch.is_empty() {
+            return Ok(());
+        }
+
+        let mut body = Vec::new();
+        for event in &batch {
+            serde_json::to_writer(&mut body, event)?;
+            body.push(b'\n');
+        }
+
+        match self.sender.post(&self.url, &body) {
+            Ok(()) => match &self.queue {
+                Some(queue) => queue.clear(),
+                None => Ok(()),
+            },
+            Err(err) => {
+                if let Some(queue) = &mut self.queue {
+                    let keep = batch.len().saturating_sub(self.max_queued);
+                    queue.clear()?;
+                    queue.send(&batch[keep..])?;
+                }
+                Err(err)
+            }
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use crate::telemetry::{TelemetryError, TelemetryEvent, TelemetrySink};
+use std::fs::OpenOptions;
+use std::io::{BufWriter, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+/// Appends events to a local file, one JSON object per line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonLinesSink {
+    path: PathBuf,
+}
+
+impl JsonLinesSink {
+    /// Create a sink appending to the file, which is created if it does not exist.
+    #[inline]
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// The file events are appended to.
+    #[inline]
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read back every event in the file. Lines that fail to parse, such as one cut short by a
+    /// crash, are skipped.
+    pub fn read(&self) -> Result<Vec<TelemetryEvent>, TelemetryError> {
+        let text = match std::fs::read_to_string(&self.path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(text
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Delete the file.
+    pub fn clear(&self) -> Result<(), TelemetryError> {
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl TelemetrySink for JsonLinesSink {
+    fn send(&mut self, events: &[TelemetryEvent]) -> Result<(), TelemetryError> {
+        if let Some(dir) = self.path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let mut w = BufWriter::new(file);
+        for event in events {
+            serde_json::to_writer(&mut w, event)?;
+            w.write_all(b"\n")?;
+        }
+        w.flush()?;
+        Ok(())
+    }
+}
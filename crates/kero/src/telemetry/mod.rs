@@ -0,0 +1,15 @@
+//! Opt-in analytics events for playtests, sent in batches to a pluggable backend.
+
+mod http_sink;
+mod json_lines_sink;
+mod telemetry_client;
+mod telemetry_error;
+mod telemetry_event;
+mod telemetry_sink;
+
+pub use http_sink::*;
+pub use json_lines_sink::*;
+pub use telemetry_client::*;
+pub use telemetry_error::*;
+pub use telemetry_event::*;
+pub use telemetry_sink::*;
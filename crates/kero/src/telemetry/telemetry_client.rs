@@ -0,0 +1,210 @@
+use crate::guid::Guid;
+use crate::storage::write_atomic;
+use crate::telemetry::{TelemetryError, TelemetryEvent, TelemetrySink};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::fmt::{Debug, Formatter};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::mpsc::{Sender, channel};
+use std::thread::JoinHandle;
+
+/// Handle to the game's telemetry, which records [events](TelemetryEvent) such as reaching a
+/// level or quitting a menu, so playtests can be studied afterward.
+///
+/// Nothing is recorded unless a sink was given to
+/// [`GameBuilder::with_telemetry`](crate::core::GameBuilder::with_telemetry) and the player has
+/// opted in with [`set_enabled`](Self::set_enabled), which is remembered between runs. Events
+/// are sent in batches, either once enough have queued up or every so often, by a background
+/// thread so a slow sink never holds up the game.
+///
+/// ```ignore
+/// // in the settings menu
+/// ctx.telemetry.set_enabled(share_data_checkbox)?;
+///
+/// // during play
+/// ctx.telemetry.track_event(
+///     TelemetryEvent::new("level_complete")
+///         .with("level", 3)
+///         .with("deaths", deaths),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct Telemetry(pub(crate) Rc<TelemetryState>);
+
+pub(crate) struct TelemetryState {
+    enabled: Cell<bool>,
+    worker: RefCell<Option<Worker>>,
+    pending: RefCell<Vec<TelemetryEvent>>,
+    session: String,
+    batch_size: Cell<usize>,
+    flush_interval: Cell<f32>,
+    timer: Cell<f32>,
+    consent_file: PathBuf,
+}
+
+/// The background thread batches are sent to the sink from.
+struct Worker {
+    batches: Sender<Vec<TelemetryEvent>>,
+    thread: JoinHandle<()>,
+}
+
+impl Worker {
+    fn spawn(mut sink: Box<dyn TelemetrySink>) -> Self {
+        let (batches, received) = channel::<Vec<TelemetryEvent>>();
+        let thread = std::thread::spawn(move || {
+            for events in received {
+                if let Err(err) = sink.send(&events) {
+                    log::warn!("failed to send {} telemetry events: {err}", events.len());
+                }
+            }
+        });
+        Self { batches, thread }
+    }
+}
+
+/// The on-disk layout of the opt-in file.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default)]
+struct Consent {
+    enabled: bool,
+}
+
+impl Debug for Telemetry {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Telemetry").finish_non_exhaustive()
+    }
+}
+
+impl Telemetry {
+    /// Create telemetry sending to the sink, with the player's choice to opt in remembered in
+    /// the file.
+    pub(crate) fn new(sink: Option<Box<dyn TelemetrySink>>, consent_file: PathBuf) -> Self {
+        let enabled = std::fs::read(&consent_file)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice::<Consent>(&bytes).ok())
+            .is_some_and(|consent| consent.enabled);
+        Self(Rc::new(TelemetryState {
+            enabled: Cell::new(enabled),
+            worker: RefCell::new(sink.map(Worker::spawn)),
+            pending: RefCell::new(Vec::new()),
+            session: Guid::new().to_string(),
+            batch_size: Cell::new(50),
+            flush_interval: Cell::new(30.0),
+            timer: Cell::new(0.0),
+            consent_file,
+        }))
+    }
+
+    /// If the player has opted in to telemetry. Defaults to `false`.
+    #[inline]
+    pub fn enabled(&self) -> bool {
+        self.0.enabled.get()
+    }
+
+    /// Opt in or out of telemetry, remembering the choice between runs. Opting out drops any
+    /// events that have not been sent yet.
+    pub fn set_enabled(&self, enabled: bool) -> Result<(), TelemetryError> {
+        self.0.enabled.set(enabled);
+        if !enabled {
+            self.0.pending.borrow_mut().clear();
+        }
+        if let Some(dir) = self.0.consent_file.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let bytes = serde_json::to_vec_pretty(&Consent { enabled })?;
+        Ok(write_atomic(&self.0.consent_file, &bytes)?)
+    }
+
+    /// If events are being recorded, which requires both a sink and the player opting in.
+    #[inline]
+    pub fn is_recording(&self) -> bool {
+        self.enabled() && self.0.worker.borrow().is_some()
+    }
+
+    /// A random ID for this run of the game, attached to every event so each playthrough can
+    /// be followed on its own.
+    #[inline]
+    pub fn session(&self) -> &str {
+        &self.0.session
+    }
+
+    /// Record an event with no details.
+    #[inline]
+    pub fn track(&self, name: impl Into<String>) {
+        self.track_event(TelemetryEvent::new(name));
+    }
+
+    /// Record an event. Does nothing if telemetry is not [recording](Self::is_recording).
+    pub fn track_event(&self, mut event: TelemetryEvent) {
+        if !self.is_recording() {
+            return;
+        }
+        event.session.clone_from(&self.0.session);
+        self.0.pending.borrow_mut().push(event);
+    }
+
+    /// How many events are waiting to be sent.
+    #[inline]
+    pub fn pending(&self) -> usize {
+        self.0.pending.borrow().len()
+    }
+
+    /// How many events queue up before they are sent. Defaults to `50`.
+    #[inline]
+    pub fn batch_size(&self) -> usize {
+        self.0.batch_size.get()
+    }
+
+    /// Set how many events queue up before they are sent.
+    #[inline]
+    pub fn set_batch_size(&self, batch_size: usize) {
+        self.0.batch_size.set(batch_size.max(1));
+    }
+
+    /// How often queued events are sent even if the batch is not full, in seconds. Defaults
+    /// to `30`.
+    #[inline]
+    pub fn flush_interval(&self) -> f32 {
+        self.0.flush_interval.get()
+    }
+
+    /// Set how often queued events are sent even if the batch is not full.
+    #[inline]
+    pub fn set_flush_interval(&self, flush_interval: f32) {
+        self.0.flush_interval.set(flush_interval);
+    }
+
+    /// Hand all queued events to the background thread to be sent. This happens automatically
+    /// when the game exits. Events that fail to send are logged as warnings.
+    pub fn flush(&self) {
+        self.0.timer.set(0.0);
+        let events = std::mem::take(&mut *self.0.pending.borrow_mut());
+        if events.is_empty() {
+            return;
+        }
+        if let Some(worker) = self.0.worker.borrow().as_ref() {
+            _ = worker.batches.send(events);
+        }
+    }
+
+    /// Send queued events if the batch is full or the flush interval has passed.
+    pub(crate) fn update(&self, dt: f32) {
+        let timer = self.0.timer.get() + dt;
+        self.0.timer.set(timer);
+        let pending = self.pending();
+        if pending >= self.batch_size() || (pending > 0 && timer >= self.flush_interval()) {
+            self.flush();
+        }
+    }
+
+    /// Send the remaining events and wait for the background thread to finish sending them.
+    pub(crate) fn finish(&self) {
+        self.flush();
+        if let Some(Worker { batches, thread }) = self.0.worker.take() {
+            drop(batches);
+            _ = thread.join();
+        }
+    }
+}
@@ -0,0 +1,12 @@
+/// An error sending or storing telemetry events.
+#[derive(Debug, thiserror::Error)]
+pub enum TelemetryError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("failed to send telemetry: {0}")]
+    Send(String),
+}
@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Something that happened in the game, such as reaching a level or quitting a menu, recorded
+/// by [`Telemetry`](crate::telemetry::Telemetry).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TelemetryEvent {
+    /// What happened, such as `"level_start"`.
+    pub name: String,
+
+    /// The session the event happened in. Filled in when the event is tracked.
+    #[serde(default)]
+    pub session: String,
+
+    /// When the event happened, in milliseconds since the Unix epoch.
+    pub time: u64,
+
+    /// Extra details about the event.
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub props: Map<String, Value>,
+}
+
+impl TelemetryEvent {
+    /// Create an event that happened now.
+    #[inline]
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            session: String::new(),
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_millis() as u64),
+            props: Map::new(),
+        }
+    }
+
+    /// Return the event with a detail added.
+    #[inline]
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.props.insert(key.into(), value.into());
+        self
+    }
+}
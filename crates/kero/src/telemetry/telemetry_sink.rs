@@ -0,0 +1,15 @@
+use crate::telemetry::{TelemetryError, TelemetryEvent};
+
+/// Where [`Telemetry`](crate::telemetry::Telemetry) sends batches of events.
+///
+/// [`JsonLinesSink`](crate::telemetry::JsonLinesSink) writes them to a local file, and
+/// [`HttpSink`](crate::telemetry::HttpSink) posts them to a server. Implement this to send them
+/// anywhere else.
+///
+/// Sinks are called from a background thread, so they can block, such as while waiting on a
+/// slow network.
+pub trait TelemetrySink: Send + 'static {
+    /// Send a batch of events. If this fails, the events are dropped unless the sink keeps
+    /// them to retry itself.
+    fn send(&mut self, events: &[TelemetryEvent]) -> Result<(), TelemetryError>;
+}
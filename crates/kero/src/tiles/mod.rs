@@ -0,0 +1,16 @@
+//! Tile maps drawn from tile sets, with chunked rendering for large maps.
+
+mod tile;
+mod tile_layer;
+mod tile_map;
+mod tile_set;
+mod tiled;
+mod tiled_parse;
+
+pub use tile::*;
+pub use tile_layer::*;
+pub use tile_map::*;
+pub use tile_set::*;
+pub use tiled::*;
+
+pub(crate) use tiled_parse::*;
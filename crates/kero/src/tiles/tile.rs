@@ -0,0 +1,144 @@
+use crate::math::Vec2F;
+use std::fmt::{Debug, Formatter};
+
+/// A cell of a [`TileLayer`](crate::tiles::TileLayer), which is either empty or refers to a
+/// tile in a [`TileSet`](crate::tiles::TileSet) and how it is flipped.
+///
+/// Tiles are packed into a `u32` the same way [Tiled](https://www.mapeditor.org) stores them,
+/// with the flip flags in the top 3 bits and the tile's index plus one in the rest, so `0` is
+/// an empty cell.
+#[derive(Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct Tile(pub u32);
+
+impl Debug for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.index() {
+            Some(index) => f
+                .debug_struct("Tile")
+                .field("index", &index)
+                .field("flip_x", &self.flip_x())
+                .field("flip_y", &self.flip_y())
+                .field("flip_diagonal", &self.flip_diagonal())
+                .finish(),
+            None => f.write_str("Tile::EMPTY"),
+        }
+    }
+}
+
+impl Tile {
+    /// An empty cell.
+    pub const EMPTY: Self = Self(0);
+
+    const FLIP_X: u32 = 1 << 31;
+    const FLIP_Y: u32 = 1 << 30;
+    const FLIP_DIAGONAL: u32 = 1 << 29;
+    const FLAGS: u32 = Self::FLIP_X | Self::FLIP_Y | Self::FLIP_DIAGONAL;
+
+    /// The largest tile index a tile can refer to.
+    pub const MAX_INDEX: u32 = !Self::FLAGS - 1;
+
+    /// A tile showing the tile set's tile at the index, unflipped.
+    #[inline]
+    pub const fn new(index: u32) -> Self {
+        assert!(index <= Self::MAX_INDEX);
+        Self(index + 1)
+    }
+
+    /// If the cell is empty.
+    #[inline]
+    pub const fn is_empty(self) -> bool {
+        self.0 & !Self::FLAGS == 0
+    }
+
+    /// Index of the tile in its tile set, or `None` if the cell is empty.
+    #[inline]
+    pub const fn index(self) -> Option<u32> {
+        match self.0 & !Self::FLAGS {
+            0 => None,
+            n => Some(n - 1),
+        }
+    }
+
+    /// If the tile is flipped horizontally.
+    #[inline]
+    pub const fn flip_x(self) -> bool {
+        self.0 & Self::FLIP_X != 0
+    }
+
+    /// If the tile is flipped vertically.
+    #[inline]
+    pub const fn flip_y(self) -> bool {
+        self.0 & Self::FLIP_Y != 0
+    }
+
+    /// If the tile is flipped across its top-left to bottom-right diagonal. This is applied
+    /// before the other flips, and combined with them makes for 90° rotations.
+    #[inline]
+    pub const fn flip_diagonal(self) -> bool {
+        self.0 & Self::FLIP_DIAGONAL != 0
+    }
+
+    #[inline]
+    const fn with_flag(self, flag: u32, on: bool) -> Self {
+        match on {
+            true => Self(self.0 | flag),
+            false => Self(self.0 & !flag),
+        }
+    }
+
+    /// Return the tile flipped horizontally or not.
+    #[inline]
+    pub const fn with_flip_x(self, flip: bool) -> Self {
+        self.with_flag(Self::FLIP_X, flip)
+    }
+
+    /// Return the tile flipped vertically or not.
+    #[inline]
+    pub const fn with_flip_y(self, flip: bool) -> Self {
+        self.with_flag(Self::FLIP_Y, flip)
+    }
+
+    /// Return the tile flipped diagonally or not.
+    #[inline]
+    pub const fn with_flip_diagonal(self, flip: bool) -> Self {
+        self.with_flag(Self::FLIP_DIAGONAL, flip)
+    }
+
+    /// Return the tile rotated 90° clockwise.
+    #[inline]
+    pub const fn rotated_cw(self) -> Self {
+        // transposing and then mirroring horizontally rotates clockwise
+        let (x, y) = (self.flip_x(), self.flip_y());
+        self.with_flip_diagonal(!self.flip_diagonal())
+            .with_flip_x(!y)
+            .with_flip_y(x)
+    }
+
+    /// Return the tile rotated 90° counter-clockwise.
+    #[inline]
+    pub const fn rotated_ccw(self) -> Self {
+        let (x, y) = (self.flip_x(), self.flip_y());
+        self.with_flip_diagonal(!self.flip_diagonal())
+            .with_flip_x(y)
+            .with_flip_y(!x)
+    }
+
+    /// Reorder the texture coordinates of a quad's corners, given from top-left clockwise, so
+    /// the texture is drawn with the tile's flips.
+    #[inline]
+    pub fn flip_coords(self, coords: [Vec2F; 4]) -> [Vec2F; 4] {
+        let [mut tl, mut tr, mut br, mut bl] = coords;
+        if self.flip_diagonal() {
+            std::mem::swap(&mut tr, &mut bl);
+        }
+        if self.flip_x() {
+            std::mem::swap(&mut tl, &mut tr);
+            std::mem::swap(&mut bl, &mut br);
+        }
+        if self.flip_y() {
+            std::mem::swap(&mut tl, &mut bl);
+            std::mem::swap(&mut tr, &mut br);
+        }
+        [tl, tr, br, bl]
+    }
+}
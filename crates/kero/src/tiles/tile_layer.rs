@@ -0,0 +1,243 @@
+use crate::color::Rgba8;
+use crate::gfx::{ColorMode, IndexBuffer, Vertex, VertexBuffer};
+use crate::grid::{Grid, GridMut, VecGrid};
+use crate::math::{Affine2F, RectF, Vec2F, Vec2U, vec2};
+use crate::tiles::{Tile, TileSet};
+
+/// A layer of tiles in a [`TileMap`](crate::tiles::TileMap), all drawn from the same
+/// [`TileSet`].
+///
+/// The layer is split into square chunks, which are each baked into vertex buffers the first
+/// time they are drawn and only rebuilt when one of their tiles changes.
+#[derive(Debug, Clone)]
+pub struct TileLayer {
+    name: String,
+    tiles: VecGrid<Tile>,
+    tileset: usize,
+
+    /// Offset of the layer from the map's origin, in pixels.
+    pub offset: Vec2F,
+
+    /// Color the layer's tiles are multiplied by.
+    pub color: Rgba8,
+
+    /// If the layer is drawn.
+    pub visible: bool,
+
+    chunk_size: u32,
+    pub(crate) chunks: VecGrid<TileChunk>,
+}
+
+impl TileLayer {
+    /// Create an empty layer of the size in tiles, split into chunks of `chunk_size` by
+    /// `chunk_size` tiles.
+    pub fn new(
+        name: impl Into<String>,
+        size: impl Into<Vec2U>,
+        tileset: usize,
+        chunk_size: u32,
+    ) -> Self {
+        let size = size.into();
+        let chunk_size = chunk_size.max(1);
+        Self {
+            name: name.into(),
+            tiles: VecGrid::new(size),
+            tileset,
+            offset: Vec2F::ZERO,
+            color: Rgba8::WHITE,
+            visible: true,
+            chunk_size,
+            chunks: VecGrid::new(size.map(|n| n.div_ceil(chunk_size))),
+        }
+    }
+
+    /// The layer's name.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Size of the layer, in tiles.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.tiles.size()
+    }
+
+    /// The layer's tiles.
+    #[inline]
+    pub fn tiles(&self) -> &VecGrid<Tile> {
+        &self.tiles
+    }
+
+    /// Index of the [`TileSet`] in the map that the layer is drawn with.
+    #[inline]
+    pub fn tileset(&self) -> usize {
+        self.tileset
+    }
+
+    /// Change which of the map's tile sets the layer is drawn with.
+    #[inline]
+    pub fn set_tileset(&mut self, tileset: usize) {
+        if tileset != self.tileset {
+            self.tileset = tileset;
+            self.mark_all_dirty();
+        }
+    }
+
+    /// Width and height of each chunk, in tiles.
+    #[inline]
+    pub fn chunk_size(&self) -> u32 {
+        self.chunk_size
+    }
+
+    /// The tile at the position, or `None` if it's outside the layer.
+    #[inline]
+    pub fn get(&self, pos: impl Into<Vec2U>) -> Option<Tile> {
+        self.tiles.get_at(pos.into()).copied()
+    }
+
+    /// Set the tile at the position, returning the tile that was there.
+    #[inline]
+    pub fn set(&mut self, pos: impl Into<Vec2U>, tile: Tile) -> Option<Tile> {
+        let pos = pos.into();
+        let prev = self.tiles.set_at(pos, tile)?;
+        if prev != tile {
+            self.mark_dirty(pos);
+        }
+        Some(prev)
+    }
+
+    /// Set every tile of the layer.
+    #[inline]
+    pub fn fill(&mut self, tile: Tile) {
+        self.tiles.fill(tile);
+        self.mark_all_dirty();
+    }
+
+    /// Empty every tile of the layer.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.fill(Tile::EMPTY);
+    }
+
+    /// Set tiles by calling `f` with each tile's position. Returning `None` leaves it as is.
+    pub fn update_tiles(&mut self, mut f: impl FnMut(Vec2U, Tile) -> Option<Tile>) {
+        let size = self.size();
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let pos = vec2(x, y);
+                if let Some(tile) = f(pos, self.tiles[pos]) {
+                    self.set(pos, tile);
+                }
+            }
+        }
+    }
+
+    /// Rebuild the chunk containing the tile the next time it's drawn.
+    #[inline]
+    pub fn mark_dirty(&mut self, pos: Vec2U) {
+        if let Some(chunk) = self.chunks.get_mut_at(pos / self.chunk_size) {
+            chunk.dirty = true;
+        }
+    }
+
+    /// Rebuild every chunk the next time they're drawn.
+    #[inline]
+    pub fn mark_all_dirty(&mut self) {
+        for (chunk, _) in self.chunks.iter_mut() {
+            chunk.dirty = true;
+        }
+    }
+
+    /// Rebuild the chunk's geometry if any of its tiles changed.
+    pub(crate) fn bake_chunk(&mut self, chunk: Vec2U, tile_size: Vec2F, tileset: &TileSet) {
+        let start = chunk * self.chunk_size;
+        let end = (start + Vec2U::splat(self.chunk_size)).min(self.size());
+        let Some(c) = self.chunks.get_mut_at(chunk) else {
+            return;
+        };
+        if !c.dirty {
+            return;
+        }
+        c.dirty = false;
+        c.vertices.clear();
+        c.indices.clear();
+        c.animated.clear();
+        c.uploaded = None;
+        for y in start.y..end.y {
+            for x in start.x..end.x {
+                let tile = self.tiles[vec2(x, y)];
+                let Some(index) = tile.index() else {
+                    continue;
+                };
+                if tileset.is_animated(index) {
+                    c.animated.push((vec2(x, y), tile));
+                    continue;
+                }
+                if let Some(coords) = tileset.coords(index) {
+                    let pos = vec2(x as f32, y as f32) * tile_size;
+                    push_quad(
+                        &mut c.vertices,
+                        &mut c.indices,
+                        RectF::pos_size(pos, tile_size),
+                        tile.flip_coords(coords),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Add a tile's quad to a mesh.
+#[inline]
+pub(crate) fn push_quad(
+    vertices: &mut Vec<Vertex>,
+    indices: &mut Vec<u32>,
+    rect: RectF,
+    coords: [Vec2F; 4],
+) {
+    let i = vertices.len() as u32;
+    for (pos, tex) in rect.corners().into_iter().zip(coords) {
+        vertices.push(Vertex::new(pos, tex, Rgba8::WHITE, ColorMode::MULT));
+    }
+    indices.extend_from_slice(&[i, i + 1, i + 2, i, i + 2, i + 3]);
+}
+
+/// A square region of a [`TileLayer`], baked into vertex buffers.
+#[derive(Debug)]
+pub(crate) struct TileChunk {
+    pub dirty: bool,
+    pub vertices: Vec<Vertex>,
+    pub indices: Vec<u32>,
+    pub animated: Vec<(Vec2U, Tile)>,
+    pub buffers: Option<(VertexBuffer, IndexBuffer)>,
+    pub uploaded: Option<(Affine2F, Rgba8)>,
+}
+
+impl Default for TileChunk {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            dirty: true,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            animated: Vec::new(),
+            buffers: None,
+            uploaded: None,
+        }
+    }
+}
+
+impl Clone for TileChunk {
+    /// Clones the chunk's geometry. The clone gets its own GPU buffers when it's first drawn.
+    fn clone(&self) -> Self {
+        Self {
+            dirty: self.dirty,
+            vertices: self.vertices.clone(),
+            indices: self.indices.clone(),
+            animated: self.animated.clone(),
+            buffers: None,
+            uploaded: None,
+        }
+    }
+}
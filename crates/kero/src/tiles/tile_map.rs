@@ -0,0 +1,335 @@
+use crate::gfx::{Draw, DrawError, Topology, Vertex};
+use crate::grid::Grid;
+use crate::math::{RectF, Vec2F, Vec2U, vec2};
+use crate::tiles::{Tile, TileLayer, TileSet, push_quad};
+
+/// A grid-based map made of [layers](TileLayer) of tiles, each drawn from one of the map's
+/// [tile sets](TileSet).
+///
+/// Large maps draw efficiently: layers are split into chunks that are baked into vertex
+/// buffers and only rebuilt when their tiles change, and chunks outside the view aren't drawn
+/// at all. Animated tiles are drawn separately every frame, so they don't cause rebuilds.
+///
+/// ```ignore
+/// let mut map = TileMap::new((256, 256), (16.0, 16.0));
+/// let terrain = map.add_tileset(TileSet::from_sheet(texture, (16, 16), 0, 0));
+/// let ground = map.add_layer("ground", terrain);
+/// map.layer_mut(ground).unwrap().set((3, 4), Tile::new(12).with_flip_x(true));
+///
+/// // every frame
+/// map.update(ctx.dt());
+/// camera.apply(draw);
+/// map.draw(draw, camera.visible_rect())?;
+/// camera.pop(draw)?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct TileMap {
+    size: Vec2U,
+    tile_size: Vec2F,
+    chunk_size: u32,
+    tilesets: Vec<TileSet>,
+    layers: Vec<TileLayer>,
+    time: f32,
+    scratch: Vec<Vertex>,
+    anim_vertices: Vec<Vertex>,
+    anim_indices: Vec<u32>,
+}
+
+impl TileMap {
+    /// The default width and height of chunks, in tiles.
+    pub const DEFAULT_CHUNK_SIZE: u32 = 32;
+
+    /// Create an empty map of the size in tiles, where each tile is `tile_size` pixels.
+    #[inline]
+    pub fn new(size: impl Into<Vec2U>, tile_size: impl Into<Vec2F>) -> Self {
+        Self {
+            size: size.into(),
+            tile_size: tile_size.into(),
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            tilesets: Vec::new(),
+            layers: Vec::new(),
+            time: 0.0,
+            scratch: Vec::new(),
+            anim_vertices: Vec::new(),
+            anim_indices: Vec::new(),
+        }
+    }
+
+    /// Return the map with layers added after this split into chunks of a different size.
+    /// Smaller chunks rebuild faster when tiles change often, larger chunks mean fewer draw
+    /// calls.
+    #[inline]
+    pub fn with_chunk_size(mut self, chunk_size: u32) -> Self {
+        self.chunk_size = chunk_size.max(1);
+        self
+    }
+
+    /// Size of the map, in tiles.
+    #[inline]
+    pub fn size(&self) -> Vec2U {
+        self.size
+    }
+
+    /// Size of each tile, in pixels.
+    #[inline]
+    pub fn tile_size(&self) -> Vec2F {
+        self.tile_size
+    }
+
+    /// Set the size of each tile, in pixels.
+    #[inline]
+    pub fn set_tile_size(&mut self, tile_size: impl Into<Vec2F>) {
+        self.tile_size = tile_size.into();
+        for layer in &mut self.layers {
+            layer.mark_all_dirty();
+        }
+    }
+
+    /// Size of the map, in pixels.
+    #[inline]
+    pub fn pixel_size(&self) -> Vec2F {
+        vec2(self.size.x as f32, self.size.y as f32) * self.tile_size
+    }
+
+    /// The map's area, in pixels.
+    #[inline]
+    pub fn bounds(&self) -> RectF {
+        RectF::sized(self.pixel_size())
+    }
+
+    /// Seconds of animation played, advanced by [`update`](Self::update).
+    #[inline]
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Set how many seconds of animation have played.
+    #[inline]
+    pub fn set_time(&mut self, time: f32) {
+        self.time = time;
+    }
+
+    /// Advance tile animations. Call this once per frame.
+    #[inline]
+    pub fn update(&mut self, delta_time: f32) {
+        self.time += delta_time;
+    }
+
+    /// Add a tile set, returning its index.
+    #[inline]
+    pub fn add_tileset(&mut self, tileset: TileSet) -> usize {
+        self.tilesets.push(tileset);
+        self.tilesets.len() - 1
+    }
+
+    /// The map's tile sets.
+    #[inline]
+    pub fn tilesets(&self) -> &[TileSet] {
+        &self.tilesets
+    }
+
+    /// A tile set.
+    #[inline]
+    pub fn tileset(&self, index: usize) -> Option<&TileSet> {
+        self.tilesets.get(index)
+    }
+
+    /// Replace a tile set. Every layer drawn with it is rebuilt.
+    pub fn set_tileset(&mut self, index: usize, tileset: TileSet) {
+        self.tilesets[index] = tileset;
+        for layer in &mut self.layers {
+            if layer.tileset() == index {
+                layer.mark_all_dirty();
+            }
+        }
+    }
+
+    /// Add an empty layer on top of the others, drawn with the tile set, returning its index.
+    #[inline]
+    pub fn add_layer(&mut self, name: impl Into<String>, tileset: usize) -> usize {
+        let layer = TileLayer::new(name, self.size, tileset, self.chunk_size);
+        self.layers.push(layer);
+        self.layers.len() - 1
+    }
+
+    /// Remove a layer.
+    #[inline]
+    pub fn remove_layer(&mut self, index: usize) -> TileLayer {
+        self.layers.remove(index)
+    }
+
+    /// The map's layers, from bottom to top.
+    #[inline]
+    pub fn layers(&self) -> &[TileLayer] {
+        &self.layers
+    }
+
+    /// A layer.
+    #[inline]
+    pub fn layer(&self, index: usize) -> Option<&TileLayer> {
+        self.layers.get(index)
+    }
+
+    /// A mutable layer.
+    #[inline]
+    pub fn layer_mut(&mut self, index: usize) -> Option<&mut TileLayer> {
+        self.layers.get_mut(index)
+    }
+
+    /// Index of the first layer with the name.
+    #[inline]
+    pub fn layer_index(&self, name: &str) -> Option<usize> {
+        self.layers.iter().position(|l| l.name() == name)
+    }
+
+    /// The tile at the position in a layer.
+    #[inline]
+    pub fn get(&self, layer: usize, pos: impl Into<Vec2U>) -> Option<Tile> {
+        self.layers.get(layer)?.get(pos)
+    }
+
+    /// Set the tile at the position in a layer, returning the tile that was there.
+    #[inline]
+    pub fn set(&mut self, layer: usize, pos: impl Into<Vec2U>, tile: Tile) -> Option<Tile> {
+        self.layers.get_mut(layer)?.set(pos, tile)
+    }
+
+    /// The tile position containing the point, or `None` if it's outside the map.
+    #[inline]
+    pub fn tile_at(&self, pos: impl Into<Vec2F>) -> Option<Vec2U> {
+        let p = (pos.into() / self.tile_size).floor();
+        let inside =
+            p.x >= 0.0 && p.y >= 0.0 && p.x < self.size.x as f32 && p.y < self.size.y as f32;
+        inside.then(|| vec2(p.x as u32, p.y as u32))
+    }
+
+    /// The area of a tile, in pixels.
+    #[inline]
+    pub fn tile_rect(&self, pos: impl Into<Vec2U>) -> RectF {
+        let pos = pos.into();
+        RectF::pos_size(
+            vec2(pos.x as f32, pos.y as f32) * self.tile_size,
+            self.tile_size,
+        )
+    }
+
+    /// Draw every visible layer, skipping chunks that don't overlap `view`, the area of the
+    /// map that can be seen, such as a camera's [visible rect](crate::gfx::Camera2D::visible_rect).
+    ///
+    /// Chunks keep their vertices in GPU buffers, which are only re-uploaded when the current
+    /// transform changes, so draw a map once per frame and use separate maps for split-screen.
+    pub fn draw(&mut self, draw: &mut Draw, view: RectF) -> Result<(), DrawError> {
+        for i in 0..self.layers.len() {
+            self.draw_layer(draw, i, view)?;
+        }
+        Ok(())
+    }
+
+    /// Draw a single layer, skipping chunks that don't overlap `view`. Useful to draw
+    /// characters between layers.
+    pub fn draw_layer(
+        &mut self,
+        draw: &mut Draw,
+        index: usize,
+        view: RectF,
+    ) -> Result<(), DrawError> {
+        let Some(layer) = self.layers.get_mut(index) else {
+            return Ok(());
+        };
+        let Some(tileset) = self.tilesets.get(layer.tileset()) else {
+            return Ok(());
+        };
+        if !layer.visible || layer.color.a == 0 {
+            return Ok(());
+        }
+
+        draw.push_translation(layer.offset);
+        let matrix = *draw.transform();
+        let view = view.translate(&-layer.offset);
+        let color = layer.color;
+        self.anim_vertices.clear();
+        self.anim_indices.clear();
+
+        for chunk in visible_chunks(layer, self.tile_size, view) {
+            layer.bake_chunk(chunk, self.tile_size, tileset);
+            let c = &mut layer.chunks[chunk];
+
+            // animated tiles are drawn together after the chunks
+            for &(pos, tile) in &c.animated {
+                let index = tileset.animated_tile(tile.index().unwrap_or(0), self.time);
+                if let Some(coords) = tileset.coords(index) {
+                    let tile_pos = vec2(pos.x as f32, pos.y as f32) * self.tile_size;
+                    push_quad(
+                        &mut self.anim_vertices,
+                        &mut self.anim_indices,
+                        RectF::pos_size(tile_pos, self.tile_size),
+                        tile.flip_coords(coords),
+                    );
+                }
+            }
+
+            if c.indices.is_empty() {
+                continue;
+            }
+            let fits = c.buffers.as_ref().is_some_and(|(v, i)| {
+                v.capacity() >= c.vertices.len() && i.capacity() >= c.indices.len()
+            });
+            if !fits {
+                let (vbuf, ibuf) = draw.create_buffers(c.vertices.len(), c.indices.len());
+                c.buffers = Some((vbuf, ibuf));
+                c.uploaded = None;
+            }
+            let (vbuf, ibuf) = c.buffers.as_ref().unwrap();
+            if c.uploaded != Some((matrix, color)) {
+                if c.uploaded.is_none() {
+                    ibuf.upload(&c.indices).unwrap();
+                }
+                self.scratch.clear();
+                self.scratch.extend(c.vertices.iter().map(|v| Vertex {
+                    pos: matrix.transform_pos2(v.pos),
+                    col: color,
+                    ..*v
+                }));
+                vbuf.upload(&self.scratch).unwrap();
+                c.uploaded = Some((matrix, color));
+            }
+            draw.buffers(
+                Some(tileset.texture().clone()),
+                Topology::Triangles,
+                vbuf,
+                ibuf,
+            );
+        }
+
+        if !self.anim_indices.is_empty() {
+            for v in &mut self.anim_vertices {
+                v.col = color;
+            }
+            draw.custom(
+                Some(tileset.texture().clone()),
+                Topology::Triangles,
+                self.anim_vertices.drain(..),
+                self.anim_indices.drain(..),
+            );
+        }
+        draw.pop_transform()
+    }
+}
+
+/// The chunks of the layer that overlap the view.
+fn visible_chunks(layer: &TileLayer, tile_size: Vec2F, view: RectF) -> Vec<Vec2U> {
+    let chunk_px = tile_size * layer.chunk_size() as f32;
+    let count = layer.chunks.size();
+    let range = |min: f32, max: f32, size: f32, count: u32| {
+        if size <= 0.0 || count == 0 {
+            return 0..0;
+        }
+        let start = (min / size).floor().clamp(0.0, count as f32) as u32;
+        let end = (max / size).ceil().clamp(0.0, count as f32) as u32;
+        start..end
+    };
+    let xs = range(view.x, view.right(), chunk_px.x, count.x);
+    let ys = range(view.y, view.bottom(), chunk_px.y, count.y);
+    ys.flat_map(|y| xs.clone().map(move |x| vec2(x, y)))
+        .collect()
+}
@@ -0,0 +1,203 @@
+use crate::gfx::{SubTexture, Texture};
+use crate::math::{Numeric, RectF, Vec2F, Vec2U, vec2};
+use fnv::FnvHashMap;
+
+/// An error creating a [`TileSet`].
+#[derive(Debug, Clone, thiserror::Error)]
+pub enum TileSetError {
+    #[error("tile set has no tiles")]
+    Empty,
+
+    #[error("tile {0} is on a different texture than the rest of the tile set")]
+    MixedTextures(usize),
+}
+
+/// A frame of a [`TileAnimation`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct TileFrame {
+    /// Index of the tile shown during the frame.
+    pub tile: u32,
+
+    /// How long the frame is shown, in seconds.
+    pub duration: f32,
+}
+
+/// An animation that cycles a tile through other tiles of its [`TileSet`], such as water or
+/// torches.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileAnimation {
+    frames: Vec<TileFrame>,
+    length: f32,
+}
+
+impl TileAnimation {
+    /// Create an animation from its frames.
+    pub fn new(frames: impl IntoIterator<Item = TileFrame>) -> Self {
+        let frames: Vec<_> = frames.into_iter().collect();
+        let length = frames.iter().map(|f| f.duration.max(0.0)).sum();
+        Self { frames, length }
+    }
+
+    /// Create an animation that shows each tile for the same number of seconds.
+    #[inline]
+    pub fn uniform(tiles: impl IntoIterator<Item = u32>, duration: f32) -> Self {
+        Self::new(tiles.into_iter().map(|tile| TileFrame { tile, duration }))
+    }
+
+    /// The animation's frames.
+    #[inline]
+    pub fn frames(&self) -> &[TileFrame] {
+        &self.frames
+    }
+
+    /// How long the animation takes to play once, in seconds.
+    #[inline]
+    pub fn length(&self) -> f32 {
+        self.length
+    }
+
+    /// The tile shown `time` seconds into the looping animation.
+    pub fn tile_at(&self, time: f32) -> Option<u32> {
+        if self.length <= 0.0 {
+            return self.frames.first().map(|f| f.tile);
+        }
+        let mut t = time.rem_euclid(self.length);
+        for frame in &self.frames {
+            let duration = frame.duration.max(0.0);
+            if t < duration {
+                return Some(frame.tile);
+            }
+            t -= duration;
+        }
+        self.frames.last().map(|f| f.tile)
+    }
+}
+
+/// The tiles a [`TileLayer`](crate::tiles::TileLayer) is drawn with, which all come from the
+/// same texture so whole chunks of a map can be drawn at once.
+///
+/// ```ignore
+/// let mut tiles = TileSet::from_sheet(texture, (16, 16), 0, 0);
+/// tiles.set_animation(48, TileAnimation::uniform([48, 49, 50, 51], 0.2));
+/// ```
+#[derive(Debug, Clone)]
+pub struct TileSet {
+    texture: Texture,
+    tiles: Vec<SubTexture>,
+    animations: FnvHashMap<u32, TileAnimation>,
+}
+
+impl TileSet {
+    /// Create a tile set from subtextures, which must all be on the same texture. A packed
+    /// [`TextureAtlas`](crate::gfx::TextureAtlas) is a good way to get these.
+    pub fn new(tiles: impl IntoIterator<Item = SubTexture>) -> Result<Self, TileSetError> {
+        let tiles: Vec<_> = tiles.into_iter().collect();
+        let texture = tiles.first().ok_or(TileSetError::Empty)?.texture.clone();
+        if let Some(i) = tiles.iter().position(|t| t.texture != texture) {
+            return Err(TileSetError::MixedTextures(i));
+        }
+        Ok(Self {
+            texture,
+            tiles,
+            animations: FnvHashMap::default(),
+        })
+    }
+
+    /// Create a tile set by cutting a texture into a grid of tiles, ordered left to right and
+    /// then top to bottom. `margin` is the space around the sheet, and `spacing` is the space
+    /// between tiles, in pixels.
+    pub fn from_sheet(
+        texture: Texture,
+        tile_size: impl Into<Vec2U>,
+        margin: u32,
+        spacing: u32,
+    ) -> Self {
+        let tile_size = tile_size.into().max(Vec2U::ONE);
+        let usable = texture.size().map(|n| n.saturating_sub(margin * 2));
+        let step = tile_size + Vec2U::splat(spacing);
+        let cols = (usable.x + spacing) / step.x;
+        let rows = (usable.y + spacing) / step.y;
+        let mut tiles = Vec::with_capacity((cols * rows) as usize);
+        for y in 0..rows {
+            for x in 0..cols {
+                let pos = vec2(margin + x * step.x, margin + y * step.y);
+                let rect = RectF::pos_size(pos.to_f32(), tile_size.to_f32());
+                tiles.push(SubTexture::new(texture.clone(), rect));
+            }
+        }
+        Self {
+            texture,
+            tiles,
+            animations: FnvHashMap::default(),
+        }
+    }
+
+    /// The texture every tile is on.
+    #[inline]
+    pub fn texture(&self) -> &Texture {
+        &self.texture
+    }
+
+    /// How many tiles are in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.tiles.len()
+    }
+
+    /// If the set has no tiles.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty()
+    }
+
+    /// The tile at the index.
+    #[inline]
+    pub fn get(&self, index: u32) -> Option<&SubTexture> {
+        self.tiles.get(index as usize)
+    }
+
+    /// All tiles in the set.
+    #[inline]
+    pub fn tiles(&self) -> &[SubTexture] {
+        &self.tiles
+    }
+
+    /// Texture coordinates of the tile's corners, from top-left clockwise.
+    #[inline]
+    pub fn coords(&self, index: u32) -> Option<[Vec2F; 4]> {
+        self.get(index).map(|t| t.coords)
+    }
+
+    /// The animation of a tile.
+    #[inline]
+    pub fn animation(&self, index: u32) -> Option<&TileAnimation> {
+        self.animations.get(&index)
+    }
+
+    /// If the tile is animated.
+    #[inline]
+    pub fn is_animated(&self, index: u32) -> bool {
+        self.animations.contains_key(&index)
+    }
+
+    /// Animate a tile, so wherever it's placed it cycles through the animation's tiles.
+    #[inline]
+    pub fn set_animation(&mut self, index: u32, animation: TileAnimation) {
+        self.animations.insert(index, animation);
+    }
+
+    /// Stop animating a tile.
+    #[inline]
+    pub fn remove_animation(&mut self, index: u32) -> Option<TileAnimation> {
+        self.animations.remove(&index)
+    }
+
+    /// The tile shown in place of the tile at `time` seconds, following its animation if it
+    /// has one.
+    #[inline]
+    pub fn animated_tile(&self, index: u32, time: f32) -> u32 {
+        self.animation(index)
+            .and_then(|anim| anim.tile_at(time))
+            .unwrap_or(index)
+    }
+}
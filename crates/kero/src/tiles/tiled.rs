@@ -0,0 +1,588 @@
+use crate::color::Rgba8;
+use crate::gfx::{Graphics, Texture};
+use crate::grid::{Grid, VecGrid};
+use crate::img::ImageError;
+use crate::math::{
+    Affine2F, CircleF, DegreesF, DynShapeF, PolygonF, PolylineF, QuadF, RectF, TriangleF, Vec2,
+    Vec2F, Vec2U, triangulate, vec2,
+};
+use crate::tiles::{Tile, TileAnimation, TileFrame, TileMap, TileSet};
+use fnv::FnvHashMap;
+use std::path::{Path, PathBuf};
+
+/// An error loading a [Tiled](https://www.mapeditor.org) map or tile set.
+#[derive(Debug, thiserror::Error)]
+pub enum TiledError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Xml(#[from] quick_xml::Error),
+
+    #[error("{0}")]
+    Utf8(#[from] std::str::Utf8Error),
+
+    #[error("{0}")]
+    Image(#[from] ImageError),
+
+    #[error("invalid {0}")]
+    Invalid(String),
+
+    #[error("missing <{0}> element")]
+    Missing(&'static str),
+
+    #[error("unsupported {0}")]
+    Unsupported(String),
+
+    #[error("tile set {0:?} is an image collection, which can't be drawn as a tile map")]
+    ImageCollection(String),
+
+    #[error("expected a texture for each of the {0} tile sets")]
+    TextureCount(usize),
+}
+
+/// The value of a custom property set in Tiled.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledProperty {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Color(Rgba8),
+
+    /// A path to a file, relative to the file the property is in.
+    File(String),
+
+    /// The id of an object in the map, or `0` for none.
+    Object(u32),
+
+    /// A custom class, whose members are properties.
+    Class(TiledProperties),
+}
+
+/// Custom properties set on a Tiled map, layer, tile set, tile or object.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TiledProperties(pub FnvHashMap<String, TiledProperty>);
+
+impl TiledProperties {
+    /// A property.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&TiledProperty> {
+        self.0.get(name)
+    }
+
+    /// If there are no properties.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// A string or file property.
+    #[inline]
+    pub fn str(&self, name: &str) -> Option<&str> {
+        match self.get(name)? {
+            TiledProperty::String(s) | TiledProperty::File(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// An int or object property.
+    #[inline]
+    pub fn int(&self, name: &str) -> Option<i64> {
+        match self.get(name)? {
+            TiledProperty::Int(n) => Some(*n),
+            TiledProperty::Object(n) => Some(*n as i64),
+            _ => None,
+        }
+    }
+
+    /// A float or int property.
+    #[inline]
+    pub fn float(&self, name: &str) -> Option<f64> {
+        match self.get(name)? {
+            TiledProperty::Float(n) => Some(*n),
+            TiledProperty::Int(n) => Some(*n as f64),
+            _ => None,
+        }
+    }
+
+    /// A bool property.
+    #[inline]
+    pub fn bool(&self, name: &str) -> Option<bool> {
+        match self.get(name)? {
+            TiledProperty::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// A color property.
+    #[inline]
+    pub fn color(&self, name: &str) -> Option<Rgba8> {
+        match self.get(name)? {
+            TiledProperty::Color(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// A class property.
+    #[inline]
+    pub fn class(&self, name: &str) -> Option<&TiledProperties> {
+        match self.get(name)? {
+            TiledProperty::Class(c) => Some(c),
+            _ => None,
+        }
+    }
+}
+
+/// Extra data for a tile in a [`TiledTileset`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TiledTile {
+    /// The tile's class, called its type in older versions of Tiled.
+    pub class: String,
+
+    /// The tile's own image, if it's in an image collection tile set.
+    pub image: Option<PathBuf>,
+
+    /// The tile's animation, using indices of tiles in the same tile set.
+    pub animation: Vec<TileFrame>,
+
+    /// Collision shapes drawn on the tile in Tiled's collision editor, relative to its
+    /// top-left.
+    pub objects: Vec<TiledObject>,
+
+    /// The tile's custom properties.
+    pub properties: TiledProperties,
+}
+
+/// A tile set in a Tiled map, loaded from the map or an external `.tsx` file.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TiledTileset {
+    /// The global id of the tile set's first tile in the map it's used by.
+    pub first_gid: u32,
+
+    pub name: String,
+    pub class: String,
+
+    /// Size of each tile, in pixels.
+    pub tile_size: Vec2U,
+
+    /// Space around the edge of the image, in pixels.
+    pub margin: u32,
+
+    /// Space between tiles in the image, in pixels.
+    pub spacing: u32,
+
+    /// How many tiles there are.
+    pub tile_count: u32,
+
+    /// How many columns of tiles the image has.
+    pub columns: u32,
+
+    /// Offset tiles are drawn at, in pixels.
+    pub tile_offset: Vec2F,
+
+    /// Path of the image the tiles are cut from, or `None` if this is an image collection.
+    pub image: Option<PathBuf>,
+
+    /// Size of the image, in pixels.
+    pub image_size: Vec2U,
+
+    /// Tiles with extra data, by their index in the tile set.
+    pub tiles: FnvHashMap<u32, TiledTile>,
+
+    /// The tile set's custom properties.
+    pub properties: TiledProperties,
+}
+
+impl TiledTileset {
+    /// Load an external `.tsx` tile set. Paths in it are resolved relative to the file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, TiledError> {
+        let path = path.as_ref();
+        let src = std::fs::read(path)?;
+        let dir = path.parent().unwrap_or(Path::new(""));
+        crate::tiles::parse_tileset(std::str::from_utf8(&src)?, dir, 1)
+    }
+
+    /// If the tile set's global ids include the id.
+    #[inline]
+    pub fn contains_gid(&self, gid: u32) -> bool {
+        gid >= self.first_gid && gid - self.first_gid < self.tile_count
+    }
+
+    /// The pixel area of a tile in the image.
+    #[inline]
+    pub fn tile_rect(&self, index: u32) -> RectF {
+        let cols = self.columns.max(1);
+        let (x, y) = (index % cols, index / cols);
+        let pos = vec2(
+            self.margin + x * (self.tile_size.x + self.spacing),
+            self.margin + y * (self.tile_size.y + self.spacing),
+        );
+        RectF::new(
+            pos.x as f32,
+            pos.y as f32,
+            self.tile_size.x as f32,
+            self.tile_size.y as f32,
+        )
+    }
+
+    /// Create a [`TileSet`] of the tiles cut from the texture, with their animations.
+    pub fn to_tile_set(&self, texture: &Texture) -> Result<TileSet, TiledError> {
+        if self.image.is_none() {
+            return Err(TiledError::ImageCollection(self.name.clone()));
+        }
+        let mut set =
+            TileSet::from_sheet(texture.clone(), self.tile_size, self.margin, self.spacing);
+        for (&index, tile) in &self.tiles {
+            if !tile.animation.is_empty() {
+                set.set_animation(index, TileAnimation::new(tile.animation.iter().copied()));
+            }
+        }
+        Ok(set)
+    }
+}
+
+/// The shape of a [`TiledObject`], relative to the object's position and before its rotation.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TiledShape {
+    Rect(Vec2F),
+    Ellipse(Vec2F),
+    Point,
+    Polygon(Vec<Vec2F>),
+    Polyline(Vec<Vec2F>),
+
+    /// A tile placed as an object, drawn with the tile. Unlike other objects, a tile object's
+    /// position is its bottom-left corner.
+    Tile(Tile, Vec2F),
+
+    /// A text box of the size.
+    Text(String, Vec2F),
+}
+
+/// An object placed in an object layer, such as a spawn point, trigger area or wall.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledObject {
+    pub id: u32,
+    pub name: String,
+
+    /// The object's class, called its type in older versions of Tiled.
+    pub class: String,
+
+    /// Position of the object, in pixels.
+    pub pos: Vec2F,
+
+    /// Clockwise rotation of the object around its position.
+    pub rotation: DegreesF,
+
+    pub visible: bool,
+    pub shape: TiledShape,
+    pub properties: TiledProperties,
+}
+
+impl TiledObject {
+    /// The transform from the object's shape to the map, including its rotation.
+    #[inline]
+    pub fn transform(&self) -> Affine2F {
+        Affine2F::translation(self.pos) * Affine2F::rotation(self.rotation)
+    }
+
+    /// The points outlining the object in the map: the corners of rectangles and tiles, the
+    /// points of polygons and polylines, or the object's position for points.
+    pub fn points(&self) -> Vec<Vec2F> {
+        let m = self.transform();
+        let local = match &self.shape {
+            TiledShape::Rect(size) | TiledShape::Ellipse(size) | TiledShape::Text(_, size) => {
+                RectF::sized(*size).corners().to_vec()
+            }
+            TiledShape::Tile(_, size) => RectF::pos_size(vec2(0.0, -size.y), *size)
+                .corners()
+                .to_vec(),
+            TiledShape::Point => vec![Vec2F::ZERO],
+            TiledShape::Polygon(points) | TiledShape::Polyline(points) => points.clone(),
+        };
+        local.into_iter().map(|p| m.transform_pos2(p)).collect()
+    }
+
+    /// The object as a polyline, if it is one.
+    #[inline]
+    pub fn polyline(&self) -> Option<PolylineF> {
+        matches!(self.shape, TiledShape::Polyline(_)).then(|| PolylineF::from_vec(self.points()))
+    }
+
+    /// The object as shapes in the map that can be used for collision. Rectangles become rects,
+    /// or quads if they're rotated, and circles become circles. Concave polygons are split
+    /// into triangles. Points, polylines, text, and ellipses that aren't circles have no shapes.
+    pub fn shapes(&self) -> Vec<DynShapeF> {
+        let rotated = self.rotation.0 != 0.0;
+        match &self.shape {
+            TiledShape::Rect(size) | TiledShape::Tile(_, size) if !rotated => {
+                let y = match self.shape {
+                    TiledShape::Tile(..) => self.pos.y - size.y,
+                    _ => self.pos.y,
+                };
+                vec![RectF::pos_size(vec2(self.pos.x, y), *size).into()]
+            }
+            TiledShape::Rect(_) | TiledShape::Tile(..) => {
+                let [a, b, c, d] = self.points()[..] else {
+                    return Vec::new();
+                };
+                vec![QuadF::new(a, b, c, d).into()]
+            }
+            TiledShape::Ellipse(size) if size.x == size.y => {
+                let center = self.transform().transform_pos2(*size * 0.5);
+                vec![CircleF::new(center, size.x * 0.5).into()]
+            }
+            TiledShape::Polygon(_) => polygon_shapes(self.points()),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// Split a polygon into triangles, unless it's already convex.
+fn polygon_shapes(points: Vec<Vec2F>) -> Vec<DynShapeF> {
+    if points.len() < 3 {
+        return Vec::new();
+    }
+    if is_convex(&points) {
+        return vec![PolygonF::from_vec(points).into()];
+    }
+    triangulate(&points)
+        .into_iter()
+        .map(|[a, b, c]| TriangleF::new(points[a], points[b], points[c]).into())
+        .collect()
+}
+
+/// If every turn of the polygon is in the same direction.
+fn is_convex(points: &[Vec2F]) -> bool {
+    let mut sign = 0.0;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let c = points[(i + 2) % points.len()];
+        let cross = (b - a).cross(c - b);
+        if cross != 0.0 {
+            if sign != 0.0 && cross.signum() != sign {
+                return false;
+            }
+            sign = cross.signum();
+        }
+    }
+    true
+}
+
+/// A layer of tiles in a [`TiledMap`].
+#[derive(Debug, Clone)]
+pub struct TiledTileLayer {
+    /// Global ids of the tiles, with Tiled's flip flags, where `0` is empty.
+    pub gids: VecGrid<u32>,
+}
+
+/// A layer of objects in a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledObjectLayer {
+    /// Color objects are shown with in Tiled.
+    pub color: Option<Rgba8>,
+    pub objects: Vec<TiledObject>,
+}
+
+/// A layer showing a single image in a [`TiledMap`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TiledImageLayer {
+    pub image: Option<PathBuf>,
+    pub repeat_x: bool,
+    pub repeat_y: bool,
+}
+
+/// The contents of a [`TiledLayer`].
+#[derive(Debug, Clone)]
+pub enum TiledLayerKind {
+    Tiles(TiledTileLayer),
+    Objects(TiledObjectLayer),
+    Image(TiledImageLayer),
+}
+
+/// A layer of a [`TiledMap`]. Group layers are flattened, so their children have the group's
+/// offset, opacity and visibility applied, and names like `group/child`.
+#[derive(Debug, Clone)]
+pub struct TiledLayer {
+    pub id: u32,
+    pub name: String,
+    pub class: String,
+
+    /// Offset of the layer, in pixels.
+    pub offset: Vec2F,
+
+    /// How fast the layer scrolls relative to the camera, where `1` moves with the map.
+    pub parallax: Vec2F,
+
+    pub opacity: f32,
+    pub visible: bool,
+
+    /// Color the layer is multiplied by.
+    pub tint: Rgba8,
+
+    pub kind: TiledLayerKind,
+    pub properties: TiledProperties,
+}
+
+impl TiledLayer {
+    /// The layer's tiles, if it's a tile layer.
+    #[inline]
+    pub fn tiles(&self) -> Option<&TiledTileLayer> {
+        match &self.kind {
+            TiledLayerKind::Tiles(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    /// The layer's objects, if it's an object layer.
+    #[inline]
+    pub fn objects(&self) -> Option<&TiledObjectLayer> {
+        match &self.kind {
+            TiledLayerKind::Objects(layer) => Some(layer),
+            _ => None,
+        }
+    }
+
+    /// The color a [`TileLayer`](crate::tiles::TileLayer) should be drawn with to match the
+    /// layer's tint and opacity.
+    #[inline]
+    pub fn color(&self) -> Rgba8 {
+        self.tint
+            .un_mul((self.opacity.clamp(0.0, 1.0) * 255.0).round() as u8)
+    }
+}
+
+/// A map made in the [Tiled](https://www.mapeditor.org) level editor, loaded from a `.tmx`
+/// file.
+///
+/// Tile layers can be turned into a [`TileMap`], and objects into shapes for collision or
+/// spawning entities.
+///
+/// ```ignore
+/// let tmx = TiledMap::from_file("assets/levels/forest.tmx")?;
+/// let map = tmx.load_tile_map(&ctx.graphics)?;
+/// for obj in tmx.layer("walls").and_then(|l| l.objects()).iter().flat_map(|l| &l.objects) {
+///     walls.extend(obj.shapes());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TiledMap {
+    /// Size of the map, in tiles.
+    pub size: Vec2U,
+
+    /// Size of each tile, in pixels.
+    pub tile_size: Vec2U,
+
+    pub class: String,
+    pub background: Option<Rgba8>,
+    pub tilesets: Vec<TiledTileset>,
+
+    /// Every layer, from bottom to top.
+    pub layers: Vec<TiledLayer>,
+
+    pub properties: TiledProperties,
+}
+
+impl TiledMap {
+    /// Load a map, along with any external tile sets it uses. Paths in the map are resolved
+    /// relative to its file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, TiledError> {
+        let path = path.as_ref();
+        let src = std::fs::read(path)?;
+        Self::parse(
+            std::str::from_utf8(&src)?,
+            path.parent().unwrap_or(Path::new("")),
+        )
+    }
+
+    /// Parse a map from its source text. Paths in the map, such as to external tile sets, are
+    /// resolved relative to `dir`.
+    #[inline]
+    pub fn parse(src: &str, dir: impl AsRef<Path>) -> Result<Self, TiledError> {
+        crate::tiles::parse_map(src, dir.as_ref())
+    }
+
+    /// The first layer with the name.
+    #[inline]
+    pub fn layer(&self, name: &str) -> Option<&TiledLayer> {
+        self.layers.iter().find(|l| l.name == name)
+    }
+
+    /// Every object in the map's object layers.
+    #[inline]
+    pub fn objects(&self) -> impl Iterator<Item = &TiledObject> {
+        self.layers
+            .iter()
+            .filter_map(TiledLayer::objects)
+            .flat_map(|l| &l.objects)
+    }
+
+    /// The tile set a global tile id belongs to, along with the tile's index in it.
+    pub fn tileset_of(&self, gid: u32) -> Option<(usize, u32)> {
+        let id = Tile(gid).index()? + 1;
+        let i = self.tilesets.iter().rposition(|t| t.first_gid <= id)?;
+        let set = &self.tilesets[i];
+        set.contains_gid(id).then(|| (i, id - set.first_gid))
+    }
+
+    /// Load the images of the map's tile sets, then create a [`TileMap`] of its tile layers.
+    pub fn load_tile_map(&self, gfx: &Graphics) -> Result<TileMap, TiledError> {
+        let mut textures = Vec::with_capacity(self.tilesets.len());
+        for set in &self.tilesets {
+            let path = set
+                .image
+                .as_ref()
+                .ok_or_else(|| TiledError::ImageCollection(set.name.clone()))?;
+            textures.push(gfx.load_texture_from_file(path, true)?);
+        }
+        self.to_tile_map(&textures)
+    }
+
+    /// Create a [`TileMap`] of the map's tile layers, using a texture for each of its tile
+    /// sets. Each [`TileLayer`](crate::tiles::TileLayer) can only use one tile set, so tile
+    /// layers using several are split into a layer for each, all with the same name.
+    pub fn to_tile_map(&self, textures: &[Texture]) -> Result<TileMap, TiledError> {
+        if textures.len() != self.tilesets.len() {
+            return Err(TiledError::TextureCount(self.tilesets.len()));
+        }
+        let tile_size = Vec2::new(self.tile_size.x as f32, self.tile_size.y as f32);
+        let mut map = TileMap::new(self.size, tile_size);
+        for (set, texture) in self.tilesets.iter().zip(textures) {
+            map.add_tileset(set.to_tile_set(texture)?);
+        }
+        for layer in &self.layers {
+            let Some(tiles) = layer.tiles() else {
+                continue;
+            };
+            let mut by_set: Vec<(usize, Vec<(Vec2U, Tile)>)> = Vec::new();
+            for (&gid, pos) in tiles.gids.iter() {
+                let Some((set, index)) = self.tileset_of(gid) else {
+                    continue;
+                };
+                // keep the flip flags, but point at the tile's index in its own set
+                let flags = Tile(gid);
+                let tile = Tile::new(index)
+                    .with_flip_x(flags.flip_x())
+                    .with_flip_y(flags.flip_y())
+                    .with_flip_diagonal(flags.flip_diagonal());
+                match by_set.iter_mut().find(|(s, _)| *s == set) {
+                    Some((_, cells)) => cells.push((pos, tile)),
+                    None => by_set.push((set, vec![(pos, tile)])),
+                }
+            }
+            for (set, cells) in by_set {
+                let index = map.add_layer(&layer.name, set);
+                let offset = self.tilesets[set].tile_offset;
+                let out = map.layer_mut(index).unwrap();
+                out.offset = layer.offset + offset;
+                out.color = layer.color();
+                out.visible = layer.visible;
+                for (pos, tile) in cells {
+                    out.set(pos, tile);
+                }
+            }
+        }
+        Ok(map)
+    }
+}
@@ -0,0 +1,531 @@
+use crate::color::Rgba8;
+use crate::grid::{GridMut, VecGrid};
+use crate::math::{Degrees, Vec2F, Vec2U, vec2};
+use crate::tiles::{
+    Tile, TileFrame, TiledError, TiledImageLayer, TiledLayer, TiledLayerKind, TiledMap,
+    TiledObject, TiledObjectLayer, TiledProperties, TiledProperty, TiledShape, TiledTile,
+    TiledTileLayer, TiledTileset,
+};
+use flate2::read::{GzDecoder, ZlibDecoder};
+use quick_xml::Reader;
+use quick_xml::events::{BytesStart, Event};
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Tiled's flag for tiles rotated 120° in hexagonal maps, which tile maps don't support.
+const FLIP_HEX: u32 = 1 << 28;
+
+/// An XML element, with everything inside of it.
+#[derive(Debug, Default)]
+struct Node {
+    name: String,
+    attrs: Vec<(String, String)>,
+    children: Vec<Node>,
+    text: String,
+}
+
+impl Node {
+    fn from_elem(elem: &BytesStart) -> Result<Self, TiledError> {
+        let mut attrs = Vec::new();
+        for attr in elem.attributes() {
+            let attr = attr.map_err(quick_xml::Error::from)?;
+            let key = String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+            attrs.push((key, attr.unescape_value()?.into_owned()));
+        }
+        Ok(Self {
+            name: String::from_utf8_lossy(elem.local_name().as_ref()).into_owned(),
+            attrs,
+            children: Vec::new(),
+            text: String::new(),
+        })
+    }
+
+    fn attr(&self, key: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn str(&self, key: &str) -> String {
+        self.attr(key).unwrap_or_default().to_string()
+    }
+
+    fn get<T: FromStr>(&self, key: &str) -> Result<Option<T>, TiledError> {
+        self.attr(key)
+            .map(|v| {
+                v.trim().parse().map_err(|_| {
+                    TiledError::Invalid(format!("<{}> attribute {key}={v:?}", self.name))
+                })
+            })
+            .transpose()
+    }
+
+    fn get_or<T: FromStr>(&self, key: &str, default: T) -> Result<T, TiledError> {
+        Ok(self.get(key)?.unwrap_or(default))
+    }
+
+    fn bool(&self, key: &str, default: bool) -> Result<bool, TiledError> {
+        Ok(self.get::<u8>(key)?.map_or(default, |n| n != 0))
+    }
+
+    fn color(&self, key: &str) -> Result<Option<Rgba8>, TiledError> {
+        self.attr(key)
+            .map(|v| {
+                parse_color(v).ok_or_else(|| {
+                    TiledError::Invalid(format!("<{}> attribute {key}={v:?}", self.name))
+                })
+            })
+            .transpose()
+    }
+
+    fn child(&self, name: &str) -> Option<&Node> {
+        self.children.iter().find(|c| c.name == name)
+    }
+
+    fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Node> {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+}
+
+/// Parse an XML document into its root element.
+fn parse_xml(src: &str) -> Result<Node, TiledError> {
+    let mut reader = Reader::from_str(src);
+    let mut stack = vec![Node::default()];
+    loop {
+        match reader.read_event()? {
+            Event::Start(elem) => stack.push(Node::from_elem(&elem)?),
+            Event::Empty(elem) => {
+                let node = Node::from_elem(&elem)?;
+                stack.last_mut().unwrap().children.push(node);
+            }
+            Event::End(_) => {
+                let node = stack.pop().unwrap();
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(node),
+                    None => return Err(TiledError::Invalid("XML nesting".into())),
+                }
+            }
+            Event::Text(text) => {
+                let text = text.decode().map_err(quick_xml::Error::from)?;
+                stack.last_mut().unwrap().text.push_str(&text);
+            }
+            Event::CData(text) => {
+                let text = text.decode().map_err(quick_xml::Error::from)?;
+                stack.last_mut().unwrap().text.push_str(&text);
+            }
+            Event::GeneralRef(entity) => {
+                let c = match entity.resolve_char_ref()? {
+                    Some(c) => c,
+                    None => match entity.decode().map_err(quick_xml::Error::from)?.as_ref() {
+                        "amp" => '&',
+                        "lt" => '<',
+                        "gt" => '>',
+                        "quot" => '"',
+                        "apos" => '\'',
+                        name => return Err(TiledError::Invalid(format!("entity &{name};"))),
+                    },
+                };
+                stack.last_mut().unwrap().text.push(c);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    let mut doc = stack.pop().unwrap();
+    match doc.children.pop() {
+        Some(root) if stack.is_empty() => Ok(root),
+        _ => Err(TiledError::Invalid("XML nesting".into())),
+    }
+}
+
+/// Parse a color like `#AARRGGBB` or `#RRGGBB`.
+fn parse_color(s: &str) -> Option<Rgba8> {
+    let hex = s.trim().trim_start_matches('#');
+    let n = u32::from_str_radix(hex, 16).ok()?;
+    let [a, r, g, b] = match hex.len() {
+        6 => (n | 0xFF000000).to_be_bytes(),
+        8 => n.to_be_bytes(),
+        _ => return None,
+    };
+    Some(Rgba8::new(r, g, b, a))
+}
+
+/// Parse a Tiled map from its source text.
+pub(crate) fn parse_map(src: &str, dir: &Path) -> Result<TiledMap, TiledError> {
+    let root = parse_xml(src)?;
+    if root.name != "map" {
+        return Err(TiledError::Missing("map"));
+    }
+    if root.bool("infinite", false)? {
+        return Err(TiledError::Unsupported("infinite map".into()));
+    }
+
+    let mut tilesets = Vec::new();
+    for node in root.children("tileset") {
+        let first_gid = node.get_or("firstgid", 1)?;
+        let tileset = match node.attr("source") {
+            Some(source) => {
+                let path = dir.join(source);
+                let src = std::fs::read(&path)?;
+                let dir = path.parent().unwrap_or(Path::new(""));
+                parse_tileset(std::str::from_utf8(&src)?, dir, first_gid)?
+            }
+            None => tileset_from_node(node, dir, first_gid)?,
+        };
+        tilesets.push(tileset);
+    }
+    tilesets.sort_by_key(|t| t.first_gid);
+
+    let size = vec2(root.get_or("width", 0)?, root.get_or("height", 0)?);
+    tile_count(size, "map")?;
+    let mut layers = Vec::new();
+    let parent = Parent {
+        name: String::new(),
+        offset: Vec2F::ZERO,
+        opacity: 1.0,
+        visible: true,
+        tint: Rgba8::WHITE,
+    };
+    parse_layers(&root, &parent, size, dir, &mut layers)?;
+
+    Ok(TiledMap {
+        size,
+        tile_size: vec2(root.get_or("tilewidth", 0)?, root.get_or("tileheight", 0)?),
+        class: root.str("class"),
+        background: root.color("backgroundcolor")?,
+        tilesets,
+        layers,
+        properties: parse_properties(&root)?,
+    })
+}
+
+/// Parse a `.tsx` tile set from its source text.
+pub(crate) fn parse_tileset(
+    src: &str,
+    dir: &Path,
+    first_gid: u32,
+) -> Result<TiledTileset, TiledError> {
+    let root = parse_xml(src)?;
+    if root.name != "tileset" {
+        return Err(TiledError::Missing("tileset"));
+    }
+    tileset_from_node(&root, dir, first_gid)
+}
+
+fn tileset_from_node(node: &Node, dir: &Path, first_gid: u32) -> Result<TiledTileset, TiledError> {
+    let image = node.child("image");
+    let mut tiles = fnv::FnvHashMap::default();
+    for tile in node.children("tile") {
+        let id: u32 = tile.get_or("id", 0)?;
+        let mut animation = Vec::new();
+        if let Some(anim) = tile.child("animation") {
+            for frame in anim.children("frame") {
+                animation.push(TileFrame {
+                    tile: frame.get_or("tileid", 0)?,
+                    duration: frame.get_or::<f32>("duration", 0.0)? / 1000.0,
+                });
+            }
+        }
+        let objects = match tile.child("objectgroup") {
+            Some(group) => parse_objects(group)?,
+            None => Vec::new(),
+        };
+        let data = TiledTile {
+            class: class_of(tile),
+            image: tile.child("image").map(|i| dir.join(i.str("source"))),
+            animation,
+            objects,
+            properties: parse_properties(tile)?,
+        };
+        tiles.insert(id, data);
+    }
+    let offset = node.child("tileoffset");
+    Ok(TiledTileset {
+        first_gid,
+        name: node.str("name"),
+        class: node.str("class"),
+        tile_size: vec2(node.get_or("tilewidth", 0)?, node.get_or("tileheight", 0)?),
+        margin: node.get_or("margin", 0)?,
+        spacing: node.get_or("spacing", 0)?,
+        tile_count: node.get_or("tilecount", 0)?,
+        columns: node.get_or("columns", 0)?,
+        tile_offset: match offset {
+            Some(o) => vec2(o.get_or("x", 0.0)?, o.get_or("y", 0.0)?),
+            None => Vec2F::ZERO,
+        },
+        image: image.map(|i| dir.join(i.str("source"))),
+        image_size: match image {
+            Some(i) => vec2(i.get_or("width", 0)?, i.get_or("height", 0)?),
+            None => Vec2U::ZERO,
+        },
+        tiles,
+        properties: parse_properties(node)?,
+    })
+}
+
+/// Properties inherited by the layers in a group.
+struct Parent {
+    name: String,
+    offset: Vec2F,
+    opacity: f32,
+    visible: bool,
+    tint: Rgba8,
+}
+
+fn parse_layers(
+    node: &Node,
+    parent: &Parent,
+    size: Vec2U,
+    dir: &Path,
+    layers: &mut Vec<TiledLayer>,
+) -> Result<(), TiledError> {
+    for child in &node.children {
+        let name = match parent.name.is_empty() {
+            true => child.str("name"),
+            false => format!("{}/{}", parent.name, child.str("name")),
+        };
+        let offset =
+            parent.offset + vec2(child.get_or("offsetx", 0.0)?, child.get_or("offsety", 0.0)?);
+        let opacity = parent.opacity * child.get_or("opacity", 1.0)?;
+        let visible = parent.visible && child.bool("visible", true)?;
+        let tint = match child.color("tintcolor")? {
+            Some(tint) => parent.tint.mul_color(tint),
+            None => parent.tint,
+        };
+        let kind = match child.name.as_str() {
+            "layer" => TiledLayerKind::Tiles(parse_tile_layer(child, size)?),
+            "objectgroup" => TiledLayerKind::Objects(TiledObjectLayer {
+                color: child.color("color")?,
+                objects: parse_objects(child)?,
+            }),
+            "imagelayer" => TiledLayerKind::Image(TiledImageLayer {
+                image: child.child("image").map(|i| dir.join(i.str("source"))),
+                repeat_x: child.bool("repeatx", false)?,
+                repeat_y: child.bool("repeaty", false)?,
+            }),
+            "group" => {
+                let group = Parent {
+                    name,
+                    offset,
+                    opacity,
+                    visible,
+                    tint,
+                };
+                parse_layers(child, &group, size, dir, layers)?;
+                continue;
+            }
+            _ => continue,
+        };
+        layers.push(TiledLayer {
+            id: child.get_or("id", 0)?,
+            name,
+            class: child.str("class"),
+            offset,
+            parallax: vec2(
+                child.get_or("parallaxx", 1.0)?,
+                child.get_or("parallaxy", 1.0)?,
+            ),
+            opacity,
+            visible,
+            tint,
+            kind,
+            properties: parse_properties(child)?,
+        });
+    }
+    Ok(())
+}
+
+fn parse_tile_layer(node: &Node, map_size: Vec2U) -> Result<TiledTileLayer, TiledError> {
+    let size = vec2(
+        node.get_or("width", map_size.x)?,
+        node.get_or("height", map_size.y)?,
+    );
+    let count = tile_count(size, "layer")?;
+    let data = node.child("data").ok_or(TiledError::Missing("data"))?;
+    if data.child("chunk").is_some() {
+        return Err(TiledError::Unsupported("chunked tile layer data".into()));
+    }
+
+    let gids: Vec<u32> = match data.attr("encoding") {
+        None => data
+            .children("tile")
+            .map(|t| t.get_or("gid", 0))
+            .collect::<Result<_, _>>()?,
+        Some("csv") => data
+            .text
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse()
+                    .map_err(|_| TiledError::Invalid(format!("tile gid {s:?}")))
+            })
+            .collect::<Result<_, _>>()?,
+        Some("base64") => {
+            let bytes = decode_base64(&data.text)
+                .ok_or_else(|| TiledError::Invalid("base64 tile data".into()))?;
+            // stop inflating just past the expected size, which is enough to know it's wrong
+            let limit = count as u64 * 4 + 1;
+            let bytes = match data.attr("compression") {
+                None | Some("") => bytes,
+                Some("zlib") => inflate(ZlibDecoder::new(bytes.as_slice()).take(limit))?,
+                Some("gzip") => inflate(GzDecoder::new(bytes.as_slice()).take(limit))?,
+                Some(other) => {
+                    return Err(TiledError::Unsupported(format!("{other} compression")));
+                }
+            };
+            bytes
+                .chunks_exact(4)
+                .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                .collect()
+        }
+        Some(other) => return Err(TiledError::Unsupported(format!("{other} encoding"))),
+    };
+
+    if gids.len() != count as usize {
+        return Err(TiledError::Invalid(format!(
+            "tile data, expected {count} tiles but found {}",
+            gids.len()
+        )));
+    }
+    let mut grid = VecGrid::new(size);
+    for (i, gid) in gids.into_iter().enumerate() {
+        let i = i as u32;
+        grid.set(i % size.x, i / size.x, gid & !FLIP_HEX);
+    }
+    Ok(TiledTileLayer { gids: grid })
+}
+
+/// Most tiles a map or tile layer can have, so a corrupt size can't allocate gigabytes.
+const MAX_TILES: u32 = 1 << 24;
+
+/// Check that a map or layer isn't too big, returning how many tiles it has.
+fn tile_count(size: Vec2U, what: &str) -> Result<u32, TiledError> {
+    size.x
+        .checked_mul(size.y)
+        .filter(|&count| count <= MAX_TILES)
+        .ok_or_else(|| {
+            TiledError::Invalid(format!(
+                "{what} size {}x{}, which is over {MAX_TILES} tiles",
+                size.x, size.y
+            ))
+        })
+}
+
+fn inflate(mut reader: impl Read) -> Result<Vec<u8>, TiledError> {
+    let mut out = Vec::new();
+    reader.read_to_end(&mut out)?;
+    Ok(out)
+}
+
+/// Decode standard base64, ignoring whitespace.
+fn decode_base64(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 3 / 4);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in text.bytes() {
+        let value = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'+' => 62,
+            b'/' => 63,
+            b'=' => break,
+            c if c.is_ascii_whitespace() => continue,
+            _ => return None,
+        };
+        acc = (acc << 6) | value as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    Some(out)
+}
+
+/// The class of an element, which older versions of Tiled called its type.
+fn class_of(node: &Node) -> String {
+    node.attr("class")
+        .or_else(|| node.attr("type"))
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn parse_objects(node: &Node) -> Result<Vec<TiledObject>, TiledError> {
+    let mut objects = Vec::new();
+    for obj in node.children("object") {
+        let size = vec2(obj.get_or("width", 0.0)?, obj.get_or("height", 0.0)?);
+        let shape = if let Some(gid) = obj.get::<u32>("gid")? {
+            TiledShape::Tile(Tile(gid & !FLIP_HEX), size)
+        } else if obj.child("ellipse").is_some() {
+            TiledShape::Ellipse(size)
+        } else if obj.child("point").is_some() {
+            TiledShape::Point
+        } else if let Some(poly) = obj.child("polygon") {
+            TiledShape::Polygon(parse_points(poly)?)
+        } else if let Some(line) = obj.child("polyline") {
+            TiledShape::Polyline(parse_points(line)?)
+        } else if let Some(text) = obj.child("text") {
+            TiledShape::Text(text.text.clone(), size)
+        } else {
+            TiledShape::Rect(size)
+        };
+        objects.push(TiledObject {
+            id: obj.get_or("id", 0)?,
+            name: obj.str("name"),
+            class: class_of(obj),
+            pos: vec2(obj.get_or("x", 0.0)?, obj.get_or("y", 0.0)?),
+            rotation: Degrees(obj.get_or("rotation", 0.0)?),
+            visible: obj.bool("visible", true)?,
+            shape,
+            properties: parse_properties(obj)?,
+        });
+    }
+    Ok(objects)
+}
+
+fn parse_points(node: &Node) -> Result<Vec<Vec2F>, TiledError> {
+    node.attr("points")
+        .unwrap_or_default()
+        .split_whitespace()
+        .map(|pair| {
+            let (x, y) = pair
+                .split_once(',')
+                .ok_or_else(|| TiledError::Invalid(format!("point {pair:?}")))?;
+            match (x.parse(), y.parse()) {
+                (Ok(x), Ok(y)) => Ok(vec2(x, y)),
+                _ => Err(TiledError::Invalid(format!("point {pair:?}"))),
+            }
+        })
+        .collect()
+}
+
+fn parse_properties(node: &Node) -> Result<TiledProperties, TiledError> {
+    let mut props = TiledProperties::default();
+    let Some(list) = node.child("properties") else {
+        return Ok(props);
+    };
+    for prop in list.children("property") {
+        // multi-line strings are stored as the element's text instead of an attribute
+        let value = prop.attr("value").unwrap_or(&prop.text);
+        let invalid = || TiledError::Invalid(format!("property {:?}", prop.str("name")));
+        let value = match prop.attr("type").unwrap_or("string") {
+            "int" => TiledProperty::Int(value.parse().map_err(|_| invalid())?),
+            "float" => TiledProperty::Float(value.parse().map_err(|_| invalid())?),
+            "bool" => TiledProperty::Bool(value.parse().map_err(|_| invalid())?),
+            "color" => TiledProperty::Color(match value {
+                "" => Rgba8::TRANSPARENT,
+                value => parse_color(value).ok_or_else(invalid)?,
+            }),
+            "file" => TiledProperty::File(value.to_string()),
+            "object" => TiledProperty::Object(value.parse().map_err(|_| invalid())?),
+            "class" => TiledProperty::Class(parse_properties(prop)?),
+            _ => TiledProperty::String(value.to_string()),
+        };
+        props.0.insert(prop.str("name"), value);
+    }
+    Ok(props)
+}
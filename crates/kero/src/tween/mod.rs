@@ -0,0 +1,8 @@
+//! Tweening values over time along easing curves.
+
+mod tween_segment;
+mod tweener;
+
+pub use crate::math::ease;
+pub use tween_segment::*;
+pub use tweener::*;
@@ -0,0 +1,48 @@
+use crate::math::Interp;
+use crate::math::ease::{self, EaseFn};
+
+/// A single animation from one value to another over a duration, along an easing curve.
+#[derive(Debug, Copy, Clone)]
+pub struct Tween<T> {
+    /// The value at the start.
+    pub from: T,
+
+    /// The value at the end.
+    pub to: T,
+
+    /// How long the tween takes, in seconds.
+    pub duration: f32,
+
+    /// The easing curve.
+    pub ease: EaseFn,
+}
+
+impl<T: Interp<Factor = f32> + Copy> Tween<T> {
+    /// Create a linear tween.
+    #[inline]
+    pub fn new(from: T, to: T, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            ease: ease::linear,
+        }
+    }
+
+    /// Return the tween with an easing curve.
+    #[inline]
+    pub fn with_ease(mut self, ease: EaseFn) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// The value `time` seconds into the tween.
+    #[inline]
+    pub fn sample(&self, time: f32) -> T {
+        let t = match self.duration > 0.0 {
+            true => (time / self.duration).clamp(0.0, 1.0),
+            false => 1.0,
+        };
+        self.from.lerp(self.to, (self.ease)(t))
+    }
+}
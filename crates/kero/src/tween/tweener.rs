@@ -0,0 +1,276 @@
+use crate::math::Interp;
+use crate::math::ease::{self, EaseFn};
+use crate::tween::Tween;
+use std::fmt::{Debug, Formatter};
+
+/// What a [`Tweener`] does once it reaches the end of its tweens.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum TweenMode {
+    /// Stop at the end.
+    #[default]
+    Once,
+
+    /// Jump back to the start and play again.
+    Loop,
+
+    /// Play backward to the start, then forward again.
+    PingPong,
+}
+
+/// Animates a value through a chain of [tweens](Tween), one after another.
+///
+/// Works with any type that implements [`Interp`], such as `f32`, [`Vec2F`](crate::math::Vec2F),
+/// [`Rgba8`](crate::color::Rgba8), and [`RectF`](crate::math::RectF).
+///
+/// ```ignore
+/// let mut pos = Tweener::new(vec2(0.0, 0.0))
+///     .to(vec2(100.0, 0.0), 0.5, ease::back_out)
+///     .wait(0.25)
+///     .to(vec2(100.0, 80.0), 0.5, ease::bounce_out)
+///     .on_complete(|| println!("landed"));
+///
+/// // every update
+/// pos.update(ctx.dt());
+/// draw.circle((pos.value(), 8.0), Rgba8::WHITE, None);
+/// ```
+pub struct Tweener<T> {
+    start: T,
+    tweens: Vec<Tween<T>>,
+    mode: TweenMode,
+    speed: f32,
+    paused: bool,
+    time: f32,
+    cycles: u32,
+    value: T,
+    finished: bool,
+    on_complete: Option<Box<dyn FnMut()>>,
+}
+
+impl<T: Debug> Debug for Tweener<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Tweener")
+            .field("tweens", &self.tweens.len())
+            .field("mode", &self.mode)
+            .field("time", &self.time)
+            .field("value", &self.value)
+            .field("finished", &self.finished)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: Interp<Factor = f32> + Copy + 'static> Tweener<T> {
+    /// Create a tweener resting at the value, with no tweens.
+    #[inline]
+    pub fn new(start: T) -> Self {
+        Self {
+            start,
+            tweens: Vec::new(),
+            mode: TweenMode::Once,
+            speed: 1.0,
+            paused: false,
+            time: 0.0,
+            cycles: 0,
+            value: start,
+            finished: false,
+            on_complete: None,
+        }
+    }
+
+    /// The value the last tween ends on, or the starting value if there are none.
+    #[inline]
+    fn end(&self) -> T {
+        self.tweens.last().map_or(self.start, |tw| tw.to)
+    }
+
+    /// Return the tweener with a tween added to the chain, from where the previous one ends.
+    #[inline]
+    pub fn to(self, target: T, duration: f32, ease: EaseFn) -> Self {
+        let from = self.end();
+        self.then(Tween::new(from, target, duration).with_ease(ease))
+    }
+
+    /// Return the tweener with a tween added to the chain.
+    #[inline]
+    pub fn then(mut self, tween: Tween<T>) -> Self {
+        self.tweens.push(tween);
+        self.finished = false;
+        self
+    }
+
+    /// Return the tweener with a pause added to the chain, holding where the previous tween
+    /// ends.
+    #[inline]
+    pub fn wait(self, duration: f32) -> Self {
+        let end = self.end();
+        self.then(Tween::new(end, end, duration).with_ease(ease::linear))
+    }
+
+    /// Return the tweener with what it does when reaching the end.
+    #[inline]
+    pub fn with_mode(mut self, mode: TweenMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Return the tweener with a function called every time it reaches the end, or for
+    /// [`PingPong`](TweenMode::PingPong), every time it reaches either end.
+    #[inline]
+    pub fn on_complete(mut self, f: impl FnMut() + 'static) -> Self {
+        self.on_complete = Some(Box::new(f));
+        self
+    }
+
+    /// The current value.
+    #[inline]
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    /// The tweens in the chain.
+    #[inline]
+    pub fn tweens(&self) -> &[Tween<T>] {
+        &self.tweens
+    }
+
+    /// What the tweener does once it reaches the end.
+    #[inline]
+    pub fn mode(&self) -> TweenMode {
+        self.mode
+    }
+
+    /// Set what the tweener does once it reaches the end.
+    #[inline]
+    pub fn set_mode(&mut self, mode: TweenMode) {
+        self.mode = mode;
+    }
+
+    /// How fast time passes for the tweener, where `1` is normal speed.
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Set how fast time passes for the tweener.
+    #[inline]
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed;
+    }
+
+    /// If the tweener is paused.
+    #[inline]
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Pause or unpause the tweener.
+    #[inline]
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    /// How long one pass through the chain takes, in seconds.
+    #[inline]
+    pub fn duration(&self) -> f32 {
+        self.tweens.iter().map(|tw| tw.duration.max(0.0)).sum()
+    }
+
+    /// How far through the current pass the tweener is, from `0` to `1`.
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        let duration = self.duration();
+        match duration > 0.0 {
+            true => (self.time / duration).clamp(0.0, 1.0),
+            false => 1.0,
+        }
+    }
+
+    /// How many times the tweener has reached an end.
+    #[inline]
+    pub fn cycles(&self) -> u32 {
+        self.cycles
+    }
+
+    /// If the tweener has stopped at the end. Only happens with [`Once`](TweenMode::Once).
+    #[inline]
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Jump back to the start.
+    pub fn reset(&mut self) {
+        self.time = 0.0;
+        self.cycles = 0;
+        self.finished = false;
+        self.value = self.start;
+    }
+
+    /// The value `time` seconds into a forward pass through the chain.
+    pub fn sample(&self, mut time: f32) -> T {
+        for tween in &self.tweens {
+            if time < tween.duration {
+                return tween.sample(time);
+            }
+            time -= tween.duration.max(0.0);
+        }
+        self.end()
+    }
+
+    /// Advance time by `dt` seconds, returning the new value.
+    pub fn update(&mut self, dt: f32) -> T {
+        if self.paused || self.finished {
+            return self.value;
+        }
+
+        let duration = self.duration();
+        if duration <= 0.0 {
+            self.value = self.end();
+            self.finished = self.mode == TweenMode::Once;
+            return self.value;
+        }
+
+        // ping-pong plays each pass in both directions, so a full cycle is twice as long
+        let cycle = match self.mode {
+            TweenMode::PingPong => duration * 2.0,
+            _ => duration,
+        };
+
+        self.time += dt * self.speed;
+        let mut completed = 0;
+        match self.mode {
+            TweenMode::Once => {
+                if self.time >= duration {
+                    self.time = duration;
+                    self.finished = true;
+                    completed = 1;
+                }
+            }
+            TweenMode::Loop | TweenMode::PingPong => {
+                // count each time an end is reached, including the middle of a ping-pong
+                let before = self.cycles_at(self.time - dt * self.speed, duration);
+                let after = self.cycles_at(self.time, duration);
+                completed = after.saturating_sub(before);
+                self.time = self.time.rem_euclid(cycle);
+            }
+        }
+
+        let time = match self.mode {
+            TweenMode::PingPong if self.time > duration => cycle - self.time,
+            _ => self.time,
+        };
+        self.value = self.sample(time);
+
+        for _ in 0..completed {
+            self.cycles += 1;
+            if let Some(f) = &mut self.on_complete {
+                f();
+            }
+        }
+        self.value
+    }
+
+    /// How many ends have been reached by the time.
+    #[inline]
+    fn cycles_at(&self, time: f32, duration: f32) -> u32 {
+        (time.max(0.0) / duration).floor() as u32
+    }
+}
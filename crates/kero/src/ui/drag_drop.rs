@@ -0,0 +1,237 @@
+use crate::gfx::Draw;
+use crate::math::{RectF, Vec2F};
+use crate::ui::{Ui, UiId};
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::hash::Hash;
+use std::rc::Rc;
+
+/// How far the mouse has to move while pressed on a drag source before it starts dragging, so
+/// clicks on it still work.
+pub const DRAG_THRESHOLD: f32 = 4.0;
+
+/// Something that happened to a drag started from a [drag source](Ui::drag_source). Events are
+/// reported on the frame after they happen.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum DragEvent {
+    /// The payload was dropped on a target that accepted it.
+    Accepted {
+        /// The drop target.
+        target: UiId,
+    },
+
+    /// The drag was cancelled by letting go away from any target, pressing `Escape` or the
+    /// right mouse button, or by [`cancel_drag`](Ui::cancel_drag).
+    Cancelled,
+}
+
+/// What happened to a [drag source](Ui::drag_source) this frame.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct DragSourceResponse {
+    /// If the mouse is over the source.
+    pub hovered: bool,
+
+    /// If the source's payload is being dragged. The source is usually drawn faded or empty
+    /// while this is `true`, since its [ghost](Ui::draw_drag_ghost) follows the mouse.
+    pub dragging: bool,
+
+    /// If dragging started this frame.
+    pub started: bool,
+
+    /// How a previous drag from this source ended, if it ended last frame.
+    pub ended: Option<DragEvent>,
+}
+
+/// What happened to a [drop target](Ui::drop_target) this frame.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DropResponse<T> {
+    /// If a payload the target accepts is being dragged over it, usually shown by highlighting
+    /// the target.
+    pub hovered: bool,
+
+    /// If a payload the target accepts is being dragged anywhere.
+    pub can_accept: bool,
+
+    /// The payload dropped on the target this frame.
+    pub dropped: Option<T>,
+}
+
+impl<T> Default for DropResponse<T> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            hovered: false,
+            can_accept: false,
+            dropped: None,
+        }
+    }
+}
+
+/// A drag in progress, kept in [`UiMemory`](crate::ui::UiMemory).
+#[derive(Clone)]
+pub(crate) struct DragState {
+    pub source: UiId,
+    pub payload: Rc<dyn Any>,
+    pub rect: RectF,
+    pub press: Vec2F,
+    pub active: bool,
+}
+
+impl Debug for DragState {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DragState")
+            .field("source", &self.source)
+            .field("rect", &self.rect)
+            .field("press", &self.press)
+            .field("active", &self.active)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> Ui<'a> {
+    /// Make the rectangle something that can be dragged, carrying a payload such as the id of
+    /// the item in an inventory slot. Dragging starts once the mouse moves a few pixels while
+    /// pressed on it, and ends when it is let go of, either over a [drop
+    /// target](Self::drop_target) that accepts payloads of its type or not.
+    ///
+    /// Scroll areas containing drag sources stop scrolling with the mouse once a payload
+    /// starts being dragged.
+    ///
+    /// ```ignore
+    /// let slot = ui.drag_source(("slot", i), rect, item.id);
+    /// if let Some(DragEvent::Accepted { .. }) = slot.ended {
+    ///     sfx.play(&ctx.audio, "drop");
+    /// }
+    /// if !slot.dragging {
+    ///     draw_item(ui.draw, item, rect);
+    /// }
+    /// ```
+    pub fn drag_source<T: 'static>(
+        &mut self,
+        id: impl Hash,
+        rect: impl Into<RectF>,
+        payload: T,
+    ) -> DragSourceResponse {
+        let id = UiId::new(id);
+        let rect = rect.into();
+        let hovered = self.is_hovered(rect);
+        self.add_magnet(rect);
+        let ended = match self.memory.drag_event {
+            Some((source, event)) if source == id => Some(event),
+            _ => None,
+        };
+
+        if hovered && self.mouse.left_pressed() && self.memory.drag.is_none() {
+            self.memory.drag = Some(DragState {
+                source: id,
+                payload: Rc::new(payload),
+                rect: rect.translate(&self.origin),
+                press: self.pointer,
+                active: false,
+            });
+        }
+
+        let mut started = false;
+        let mut dragging = false;
+        if let Some(drag) = &mut self.memory.drag
+            && drag.source == id
+        {
+            if !drag.active && self.pointer.dist(drag.press) >= DRAG_THRESHOLD {
+                drag.active = true;
+                started = true;
+            }
+            dragging = drag.active;
+        }
+
+        DragSourceResponse {
+            hovered,
+            dragging,
+            started,
+            ended,
+        }
+    }
+
+    /// Make the rectangle a place payloads of type `T` can be dropped on. When one is dropped
+    /// on it, the response has the payload and the drag source is told the drop was accepted.
+    ///
+    /// ```ignore
+    /// let slot = ui.drop_target::<ItemId>(("slot", i), rect);
+    /// if slot.hovered {
+    ///     ui.draw.rect_outline(rect, Rgba8::YELLOW);
+    /// }
+    /// if let Some(item) = slot.dropped {
+    ///     inventory.move_item(item, i);
+    /// }
+    /// ```
+    pub fn drop_target<T: Clone + 'static>(
+        &mut self,
+        id: impl Hash,
+        rect: impl Into<RectF>,
+    ) -> DropResponse<T> {
+        let id = UiId::new(id);
+        let can_accept = self
+            .memory
+            .drag
+            .as_ref()
+            .is_some_and(|d| d.active && d.payload.is::<T>());
+        if !can_accept {
+            return DropResponse::default();
+        }
+        let rect = rect.into();
+        let hovered = self.is_hovered(rect);
+        self.add_magnet(rect);
+        let mut response = DropResponse {
+            hovered,
+            can_accept,
+            dropped: None,
+        };
+        if hovered
+            && self.mouse.left_released()
+            && let Some(drag) = self.memory.drag.take()
+        {
+            response.dropped = drag.payload.downcast_ref::<T>().cloned();
+            self.memory.next_drag_event = Some((drag.source, DragEvent::Accepted { target: id }));
+        }
+        response
+    }
+
+    /// If a payload is being dragged.
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.memory.drag.as_ref().is_some_and(|d| d.active)
+    }
+
+    /// The payload being dragged, if there is one of type `T`.
+    #[inline]
+    pub fn drag_payload<T: 'static>(&self) -> Option<&T> {
+        self.memory
+            .drag
+            .as_ref()
+            .filter(|d| d.active)
+            .and_then(|d| d.payload.downcast_ref())
+    }
+
+    /// Cancel the current drag, if there is one.
+    #[inline]
+    pub fn cancel_drag(&mut self) {
+        if let Some(drag) = self.memory.drag.take() {
+            self.memory.next_drag_event = Some((drag.source, DragEvent::Cancelled));
+        }
+    }
+
+    /// Draw the "ghost" of the payload being dragged, if there is one of type `T`, by calling
+    /// `f` with the rectangle of its drag source moved to follow the mouse. Call this after
+    /// every other widget so it's drawn on top of them.
+    pub fn draw_drag_ghost<T: 'static>(&mut self, f: impl FnOnce(&mut Draw, RectF, &T)) {
+        let Some(drag) = self.memory.drag.as_ref().filter(|d| d.active) else {
+            return;
+        };
+        let Some(payload) = drag.payload.downcast_ref::<T>() else {
+            return;
+        };
+        let rect = drag
+            .rect
+            .translate(&(self.pointer - drag.press - self.origin));
+        f(self.draw, rect, payload);
+    }
+}
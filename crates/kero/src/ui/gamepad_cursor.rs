@@ -0,0 +1,202 @@
+use crate::input::{Gamepad, GamepadAxis};
+use crate::math::{RectF, Vec2F, vec2};
+use serde::{Deserialize, Serialize};
+
+/// How a [`GamepadCursor`] moves. Each UI can have its own, so a dense inventory grid can pull
+/// the cursor less strongly than a main menu with a few big buttons.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CursorSettings {
+    /// How fast the cursor moves with the stick tilted all the way, in pixels per second.
+    pub max_speed: f32,
+
+    /// How quickly the cursor speeds up to the speed the stick is asking for. Higher is
+    /// snappier, lower feels heavier.
+    pub acceleration: f32,
+
+    /// How quickly the cursor stops once the stick is let go. Lower glides further.
+    pub deceleration: f32,
+
+    /// How far the stick can be tilted, from `0` to `1`, before the cursor moves.
+    pub dead_zone: f32,
+
+    /// The power tilt is raised to after the dead zone, so small tilts can move the cursor
+    /// precisely. `1` is linear.
+    pub response_curve: f32,
+
+    /// How far from a target's edge the cursor starts being pulled toward it, in pixels.
+    pub magnet_radius: f32,
+
+    /// How strongly targets pull the cursor toward their center. `0` turns magnetism off.
+    pub magnet_strength: f32,
+
+    /// How much the cursor slows down while over a target, from `0` to `1`, to make it
+    /// easier to stop on.
+    pub magnet_friction: f32,
+}
+
+impl Default for CursorSettings {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            max_speed: 900.0,
+            acceleration: 12.0,
+            deceleration: 20.0,
+            dead_zone: 0.15,
+            response_curve: 2.0,
+            magnet_radius: 48.0,
+            magnet_strength: 6.0,
+            magnet_friction: 0.4,
+        }
+    }
+}
+
+/// A cursor moved by a thumbstick, for controlling mouse-style UIs with a gamepad.
+///
+/// The cursor speeds up and glides to a stop instead of moving at the exact speed the stick
+/// is tilted, and is gently pulled toward nearby targets such as buttons, so they're easy to
+/// land on. Pass its position to [`Ui::with_pointer`](crate::ui::Ui::with_pointer) to use it
+/// in place of the mouse.
+///
+/// ```ignore
+/// let targets = self.ui.magnets().iter().copied();
+/// self.cursor.update_gamepad(&pad, ctx.dt(), screen_rect, targets);
+/// let mut ui = Ui::new(ctx, &mut self.ui, draw, screen_rect).with_pointer(self.cursor.pos());
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct GamepadCursor {
+    /// How the cursor moves.
+    pub settings: CursorSettings,
+
+    pos: Vec2F,
+    velocity: Vec2F,
+}
+
+impl GamepadCursor {
+    /// Create a cursor at the position.
+    #[inline]
+    pub fn new(pos: impl Into<Vec2F>) -> Self {
+        Self {
+            settings: CursorSettings::default(),
+            pos: pos.into(),
+            velocity: Vec2F::ZERO,
+        }
+    }
+
+    /// Return the cursor with different settings.
+    #[inline]
+    pub fn with_settings(mut self, settings: CursorSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// The cursor's position.
+    #[inline]
+    pub fn pos(&self) -> Vec2F {
+        self.pos
+    }
+
+    /// Move the cursor, such as to where the mouse was when switching to a gamepad.
+    #[inline]
+    pub fn set_pos(&mut self, pos: impl Into<Vec2F>) {
+        self.pos = pos.into();
+    }
+
+    /// How fast the cursor is moving, in pixels per second.
+    #[inline]
+    pub fn velocity(&self) -> Vec2F {
+        self.velocity
+    }
+
+    /// Stop the cursor from gliding.
+    #[inline]
+    pub fn stop(&mut self) {
+        self.velocity = Vec2F::ZERO;
+    }
+
+    /// Move the cursor with the gamepad's left stick. See [`update`](Self::update).
+    #[inline]
+    pub fn update_gamepad(
+        &mut self,
+        gamepad: &Gamepad,
+        dt: f32,
+        bounds: impl Into<RectF>,
+        targets: impl IntoIterator<Item = RectF>,
+    ) {
+        let stick = vec2(
+            gamepad.axis(GamepadAxis::LeftX),
+            gamepad.axis(GamepadAxis::LeftY),
+        );
+        self.update(stick, dt, bounds, targets);
+    }
+
+    /// Move the cursor with a stick, where `y` is positive when tilted down, keeping it within
+    /// the bounds. The cursor is pulled toward the nearest of the targets within reach, less so
+    /// the further the stick is tilted, so the player can always push past them.
+    pub fn update(
+        &mut self,
+        stick: impl Into<Vec2F>,
+        dt: f32,
+        bounds: impl Into<RectF>,
+        targets: impl IntoIterator<Item = RectF>,
+    ) {
+        let s = &self.settings;
+        let stick = stick.into();
+        let bounds = bounds.into();
+
+        // remove the dead zone and apply the response curve
+        let tilt = stick.len().min(1.0);
+        let input = match tilt > s.dead_zone {
+            true => {
+                let amount = (tilt - s.dead_zone) / (1.0 - s.dead_zone).max(f32::EPSILON);
+                stick.norm_safe() * amount.powf(s.response_curve.max(0.01))
+            }
+            false => Vec2F::ZERO,
+        };
+
+        // find the nearest target in reach, where any target the cursor is over is nearest
+        let mut nearest: Option<(RectF, f32)> = None;
+        for target in targets {
+            let dist = self
+                .pos
+                .clamp(target.min_pos(), target.max_pos())
+                .dist(self.pos);
+            if dist <= s.magnet_radius && nearest.is_none_or(|(_, d)| dist < d) {
+                nearest = Some((target, dist));
+            }
+        }
+        let over = nearest.is_some_and(|(_, dist)| dist <= 0.0);
+
+        // ease the velocity toward what the stick wants, slowing down over targets
+        let mut wanted = input * s.max_speed;
+        if over {
+            wanted *= 1.0 - s.magnet_friction.clamp(0.0, 1.0);
+        }
+        let rate = match input == Vec2F::ZERO {
+            true => s.deceleration,
+            false => s.acceleration,
+        };
+        self.velocity += (wanted - self.velocity) * (1.0 - (-rate * dt).exp());
+        self.pos += self.velocity * dt;
+
+        // pull toward the target's center, more when closer and when the stick is eased off
+        if let Some((target, dist)) = nearest
+            && s.magnet_strength > 0.0
+        {
+            let closeness = 1.0 - dist / s.magnet_radius.max(f32::EPSILON);
+            let ease = (1.0 - input.len().min(1.0)).powi(4);
+            let t = 1.0 - (-s.magnet_strength * closeness * ease * dt).exp();
+            self.pos += (target.center() - self.pos) * t;
+        }
+
+        // stay in bounds, stopping along the edges hit
+        let clamped = self.pos.clamp(bounds.min_pos(), bounds.max_pos());
+        if clamped.x != self.pos.x {
+            self.velocity.x = 0.0;
+        }
+        if clamped.y != self.pos.y {
+            self.velocity.y = 0.0;
+        }
+        self.pos = clamped;
+    }
+}
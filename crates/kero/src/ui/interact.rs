@@ -0,0 +1,56 @@
+use crate::math::RectF;
+use crate::ui::{Ui, UiId};
+use std::hash::Hash;
+
+/// How a widget is being interacted with, returned by [`Ui::interact`].
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Interaction {
+    /// The mouse is over the widget.
+    pub hovered: bool,
+
+    /// The widget is focused, either by being clicked or with
+    /// [`UiMemory::set_focus`](crate::ui::UiMemory::set_focus).
+    pub focused: bool,
+
+    /// The mouse is held down on the widget.
+    pub pressed: bool,
+
+    /// The widget was clicked this frame, or enter or space was pressed while it was focused.
+    pub clicked: bool,
+}
+
+impl<'a> Ui<'a> {
+    /// Make the rectangle something that can be clicked or focused, such as a button. Widgets
+    /// drawn by other crates use this to pick how they look.
+    ///
+    /// ```ignore
+    /// let rect = ui.allocate(vec2(120.0, 24.0));
+    /// let play = ui.interact("play", rect);
+    /// ui.draw.rect(rect, if play.hovered { hover_color } else { color });
+    /// if play.clicked {
+    ///     start_game();
+    /// }
+    /// ```
+    pub fn interact(&mut self, id: impl Hash, rect: impl Into<RectF>) -> Interaction {
+        let id = UiId::new(id);
+        let rect = rect.into();
+        let hovered = self.is_hovered(rect);
+        self.add_magnet(rect);
+        if hovered && self.mouse.left_pressed() {
+            self.memory.focus = Some(id);
+        }
+        let focused = self.memory.focus == Some(id);
+        Interaction {
+            hovered,
+            focused,
+            pressed: hovered && self.mouse.left_down(),
+            clicked: (hovered && self.mouse.left_released()) || (focused && self.activate),
+        }
+    }
+
+    /// If the widget is focused.
+    #[inline]
+    pub fn is_focused(&self, id: impl Hash) -> bool {
+        self.memory.focus == Some(UiId::new(id))
+    }
+}
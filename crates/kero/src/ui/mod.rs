@@ -0,0 +1,19 @@
+//! User interface widgets, either immediate-mode or as a retained tree loaded from data.
+
+mod drag_drop;
+mod gamepad_cursor;
+mod interact;
+mod scroll_area;
+mod ui_context;
+mod ui_id;
+mod ui_node_def;
+mod ui_tree;
+
+pub use drag_drop::*;
+pub use gamepad_cursor::*;
+pub use interact::*;
+pub use scroll_area::*;
+pub use ui_context::*;
+pub use ui_id::*;
+pub use ui_node_def::*;
+pub use ui_tree::*;
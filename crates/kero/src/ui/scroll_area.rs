@@ -0,0 +1,439 @@
+use crate::color::Rgba8;
+use crate::gfx::DrawError;
+use crate::math::{RectF, Vec2F, vec2};
+use crate::ui::{Ui, UiId};
+use std::hash::Hash;
+use std::ops::Range;
+
+/// How far a scroll area is scrolled, kept in [`UiMemory`](crate::ui::UiMemory) between
+/// frames.
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub struct ScrollState {
+    /// How far the content is scrolled from its top-left.
+    pub offset: Vec2F,
+
+    /// Size of the content, measured the last time the area was shown.
+    pub content_size: Vec2F,
+
+    /// Size of the area the content is shown in.
+    pub view_size: Vec2F,
+
+    pub(crate) velocity: Vec2F,
+    pub(crate) dragging: bool,
+}
+
+impl ScrollState {
+    /// The furthest the content can be scrolled.
+    #[inline]
+    pub fn max_offset(&self) -> Vec2F {
+        (self.content_size - self.view_size).max(Vec2F::ZERO)
+    }
+
+    /// How far the content is scrolled, from `0` at the top-left to `1` at the bottom-right.
+    /// Axes that can't be scrolled are `0`.
+    #[inline]
+    pub fn progress(&self) -> Vec2F {
+        let max = self.max_offset();
+        let axis = |offset: f32, max: f32| if max > 0.0 { offset / max } else { 0.0 };
+        vec2(axis(self.offset.x, max.x), axis(self.offset.y, max.y))
+    }
+
+    /// If the content is being dragged by the mouse.
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
+    /// If the content is still moving after being flung.
+    #[inline]
+    pub fn is_flinging(&self) -> bool {
+        self.velocity != Vec2F::ZERO
+    }
+
+    /// Keep the offset within the content, stopping any fling along axes that hit an edge.
+    pub fn clamp(&mut self) {
+        let max = self.max_offset();
+        let clamped = self.offset.max(Vec2F::ZERO).min(max);
+        if clamped.x != self.offset.x {
+            self.velocity.x = 0.0;
+        }
+        if clamped.y != self.offset.y {
+            self.velocity.y = 0.0;
+        }
+        self.offset = clamped;
+    }
+
+    /// Scroll as little as possible so that the rectangle, in content space, is in view.
+    pub fn scroll_into_view(&mut self, rect: RectF) {
+        let axis = |offset: f32, view: f32, min: f32, max: f32| {
+            if max - min > view || min < offset {
+                min
+            } else if max > offset + view {
+                max - view
+            } else {
+                offset
+            }
+        };
+        self.offset = vec2(
+            axis(self.offset.x, self.view_size.x, rect.x, rect.right()),
+            axis(self.offset.y, self.view_size.y, rect.y, rect.bottom()),
+        );
+        self.velocity = Vec2F::ZERO;
+        self.clamp();
+    }
+
+    /// The range of rows that are at least partly in view, for content made of `count` rows
+    /// that are each `row_height` tall.
+    #[inline]
+    pub fn visible_rows(&self, row_height: f32, count: usize) -> Range<usize> {
+        visible_range(self.offset.y, self.view_size.y, row_height, count)
+    }
+}
+
+/// The range of items that are at least partly within `size` pixels from `offset`, where each
+/// item is `item_size` pixels long and there are `count` of them.
+#[inline]
+pub fn visible_range(offset: f32, size: f32, item_size: f32, count: usize) -> Range<usize> {
+    if item_size <= 0.0 || count == 0 {
+        return 0..0;
+    }
+    let start = (offset / item_size).floor().max(0.0) as usize;
+    let end = ((offset + size) / item_size).ceil().max(0.0) as usize;
+    start.min(count)..end.min(count)
+}
+
+/// Options for how a scroll area scrolls and looks.
+///
+/// ```ignore
+/// ScrollArea::vertical()
+///     .with_scrollbar(Rgba8::new(255, 255, 255, 128))
+///     .show_list(&mut ui, "inventory", vec2(240.0, 400.0), 32.0, items.len(), |ui, i, rect| {
+///         draw_item(ui.draw, &items[i], rect);
+///     })?;
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ScrollArea {
+    /// If the content can be scrolled left and right.
+    pub horizontal: bool,
+
+    /// If the content can be scrolled up and down.
+    pub vertical: bool,
+
+    /// How many pixels one line of mouse wheel scrolling moves the content.
+    pub wheel_speed: f32,
+
+    /// If the content can be dragged with the left mouse button, and flung by letting go of
+    /// it while it's moving.
+    pub drag: bool,
+
+    /// How quickly a flung area slows down. Higher values stop sooner.
+    pub friction: f32,
+
+    /// Color of the scrollbars, or `None` to not draw them.
+    pub scrollbar: Option<Rgba8>,
+
+    /// Thickness of the scrollbars.
+    pub scrollbar_width: f32,
+}
+
+impl Default for ScrollArea {
+    #[inline]
+    fn default() -> Self {
+        Self::vertical()
+    }
+}
+
+impl ScrollArea {
+    /// A scroll area that scrolls up and down.
+    #[inline]
+    pub fn vertical() -> Self {
+        Self {
+            horizontal: false,
+            vertical: true,
+            wheel_speed: 40.0,
+            drag: true,
+            friction: 6.0,
+            scrollbar: Some(Rgba8::new(128, 128, 128, 128)),
+            scrollbar_width: 4.0,
+        }
+    }
+
+    /// A scroll area that scrolls left and right.
+    #[inline]
+    pub fn horizontal() -> Self {
+        Self {
+            horizontal: true,
+            vertical: false,
+            ..Self::vertical()
+        }
+    }
+
+    /// A scroll area that scrolls in both directions.
+    #[inline]
+    pub fn both() -> Self {
+        Self {
+            horizontal: true,
+            ..Self::vertical()
+        }
+    }
+
+    /// Return the options with a different wheel speed.
+    #[inline]
+    pub fn with_wheel_speed(mut self, wheel_speed: f32) -> Self {
+        self.wheel_speed = wheel_speed;
+        self
+    }
+
+    /// Return the options with drag scrolling turned on or off.
+    #[inline]
+    pub fn with_drag(mut self, drag: bool) -> Self {
+        self.drag = drag;
+        self
+    }
+
+    /// Return the options with a different fling friction.
+    #[inline]
+    pub fn with_friction(mut self, friction: f32) -> Self {
+        self.friction = friction;
+        self
+    }
+
+    /// Return the options with scrollbars drawn in a different color, or not at all.
+    #[inline]
+    pub fn with_scrollbar(mut self, color: impl Into<Option<Rgba8>>) -> Self {
+        self.scrollbar = color.into();
+        self
+    }
+
+    /// Return the options with a different scrollbar thickness.
+    #[inline]
+    pub fn with_scrollbar_width(mut self, width: f32) -> Self {
+        self.scrollbar_width = width;
+        self
+    }
+
+    /// Show a scroll area of the size, laying out the widgets added by `f` inside of it.
+    #[inline]
+    pub fn show<R>(
+        &self,
+        ui: &mut Ui,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        f: impl FnOnce(&mut Ui) -> R,
+    ) -> Result<R, DrawError> {
+        self.show_inner(ui, UiId::new(id), size.into(), None, f)
+    }
+
+    /// Show a scroll area of the size containing `count` rows that are each `row_height` tall.
+    /// Only rows that are in view are drawn, by calling `f` with their index and rectangle, so
+    /// lists can have thousands of items.
+    pub fn show_list(
+        &self,
+        ui: &mut Ui,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        row_height: f32,
+        count: usize,
+        mut f: impl FnMut(&mut Ui, usize, RectF),
+    ) -> Result<(), DrawError> {
+        let size = size.into();
+        let content = vec2(size.x, row_height * count as f32);
+        self.show_inner(ui, UiId::new(id), size, Some(content), |ui| {
+            let view = ui.visible_rect();
+            for i in visible_range(view.y, view.h, row_height, count) {
+                f(
+                    ui,
+                    i,
+                    RectF::new(0.0, i as f32 * row_height, size.x, row_height),
+                );
+            }
+        })
+    }
+
+    /// Show a scroll area of the size containing `count` cells laid out in a grid, left to
+    /// right and then top to bottom, with as many columns as fit. Only cells that are in view
+    /// are drawn, by calling `f` with their index and rectangle.
+    pub fn show_grid(
+        &self,
+        ui: &mut Ui,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        cell_size: impl Into<Vec2F>,
+        count: usize,
+        mut f: impl FnMut(&mut Ui, usize, RectF),
+    ) -> Result<(), DrawError> {
+        let size = size.into();
+        let cell = cell_size.into();
+        let columns = match cell.x > 0.0 {
+            true => ((size.x / cell.x).floor() as usize).max(1),
+            false => 1,
+        };
+        let rows = count.div_ceil(columns);
+        let content = vec2(size.x, cell.y * rows as f32);
+        self.show_inner(ui, UiId::new(id), size, Some(content), |ui| {
+            let view = ui.visible_rect();
+            for row in visible_range(view.y, view.h, cell.y, rows) {
+                let start = row * columns;
+                for i in start..(start + columns).min(count) {
+                    let pos = vec2((i - start) as f32 * cell.x, row as f32 * cell.y);
+                    f(ui, i, RectF::pos_size(pos, cell));
+                }
+            }
+        })
+    }
+
+    fn show_inner<R>(
+        &self,
+        ui: &mut Ui,
+        id: UiId,
+        size: Vec2F,
+        content_size: Option<Vec2F>,
+        f: impl FnOnce(&mut Ui) -> R,
+    ) -> Result<R, DrawError> {
+        let rect = ui.allocate(size);
+        let mut state = ui.memory.scroll.get(&id).copied().unwrap_or_default();
+        state.view_size = size;
+        if let Some(content_size) = content_size {
+            state.content_size = content_size;
+        }
+
+        self.scroll(ui, id, rect, &mut state);
+
+        // lay the content out from the top-left of the area, moved by the scroll offset
+        let offset = rect.top_left() - state.offset.round();
+        ui.draw.push_clip_rect(rect);
+        ui.draw.push_translation(offset);
+        let width = match self.horizontal {
+            true => f32::INFINITY,
+            false => size.x,
+        };
+        let mut child = ui.child(RectF::new(0.0, 0.0, width, f32::INFINITY), rect, offset);
+        let result = f(&mut child);
+        let extent = child.extent;
+        ui.draw.pop_transform()?;
+
+        if content_size.is_none() {
+            state.content_size = extent;
+        }
+        state.clamp();
+        self.draw_scrollbars(ui, rect, &state);
+        ui.draw.pop_clip_rect()?;
+
+        // the innermost area under the mouse gets the wheel next frame
+        if ui.is_hovered(rect) && ui.memory.next_wheel_target.is_none() {
+            ui.memory.next_wheel_target = Some(id);
+        }
+        ui.memory.scroll.insert(id, state);
+        Ok(result)
+    }
+
+    fn scroll(&self, ui: &mut Ui, id: UiId, rect: RectF, state: &mut ScrollState) {
+        let axes = vec2(self.horizontal as u8 as f32, self.vertical as u8 as f32);
+        let targeted = ui.is_hovered(rect) && ui.memory.wheel_target == Some(id);
+
+        let wheel = ui.mouse.scroll();
+        if targeted && wheel != Vec2F::ZERO {
+            // positive scrolling moves up and left, so towards the start of the content
+            state.offset -= wheel * self.wheel_speed * axes;
+            state.velocity = Vec2F::ZERO;
+        }
+
+        state.dragging = false;
+        if self.drag {
+            if targeted && ui.mouse.left_pressed() && ui.memory.drag_scroll.is_none() {
+                ui.memory.drag_scroll = Some((id, ui.pointer));
+                state.velocity = Vec2F::ZERO;
+            }
+            match ui.memory.drag_scroll {
+                Some((drag_id, last)) if drag_id == id => {
+                    if ui.mouse.left_down() && !ui.is_dragging() {
+                        let delta = (ui.pointer - last) * axes;
+                        state.offset -= delta;
+                        state.dragging = true;
+                        if ui.dt > 0.0 {
+                            // smooth the velocity so uneven frames don't make flings jerky
+                            state.velocity += (-delta / ui.dt - state.velocity) * 0.5;
+                        }
+                        ui.memory.drag_scroll = Some((id, ui.pointer));
+                    } else {
+                        ui.memory.drag_scroll = None;
+                    }
+                }
+                _ => {
+                    state.offset += state.velocity * ui.dt;
+                    state.velocity *= (-self.friction.max(0.0) * ui.dt).exp();
+                    if state.velocity.len() < 1.0 {
+                        state.velocity = Vec2F::ZERO;
+                    }
+                }
+            }
+        }
+        state.clamp();
+    }
+
+    fn draw_scrollbars(&self, ui: &mut Ui, rect: RectF, state: &ScrollState) {
+        let Some(color) = self.scrollbar else {
+            return;
+        };
+        let w = self.scrollbar_width;
+        let max = state.max_offset();
+        let progress = state.progress();
+        if self.vertical && max.y > 0.0 {
+            let len = (rect.h * rect.h / state.content_size.y)
+                .max(w * 2.0)
+                .min(rect.h);
+            let y = rect.y + (rect.h - len) * progress.y;
+            ui.draw.rect(RectF::new(rect.right() - w, y, w, len), color);
+        }
+        if self.horizontal && max.x > 0.0 {
+            let len = (rect.w * rect.w / state.content_size.x)
+                .max(w * 2.0)
+                .min(rect.w);
+            let x = rect.x + (rect.w - len) * progress.x;
+            ui.draw
+                .rect(RectF::new(x, rect.bottom() - w, len, w), color);
+        }
+    }
+}
+
+impl<'a> Ui<'a> {
+    /// Show a [vertical](ScrollArea::vertical) scroll area of the size, laying out the widgets
+    /// added by `f` inside of it. It can be scrolled with the mouse wheel or by dragging.
+    #[inline]
+    pub fn scroll_area<R>(
+        &mut self,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        f: impl FnOnce(&mut Ui) -> R,
+    ) -> Result<R, DrawError> {
+        ScrollArea::vertical().show(self, id, size, f)
+    }
+
+    /// Show a vertical scroll area containing `count` rows that are each `row_height` tall,
+    /// only drawing the ones in view. See [`ScrollArea::show_list`].
+    #[inline]
+    pub fn scroll_list(
+        &mut self,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        row_height: f32,
+        count: usize,
+        f: impl FnMut(&mut Ui, usize, RectF),
+    ) -> Result<(), DrawError> {
+        ScrollArea::vertical().show_list(self, id, size, row_height, count, f)
+    }
+
+    /// Show a vertical scroll area containing `count` cells laid out in a grid, only drawing
+    /// the ones in view. See [`ScrollArea::show_grid`].
+    #[inline]
+    pub fn scroll_grid(
+        &mut self,
+        id: impl Hash,
+        size: impl Into<Vec2F>,
+        cell_size: impl Into<Vec2F>,
+        count: usize,
+        f: impl FnMut(&mut Ui, usize, RectF),
+    ) -> Result<(), DrawError> {
+        ScrollArea::vertical().show_grid(self, id, size, cell_size, count, f)
+    }
+}
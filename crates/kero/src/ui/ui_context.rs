@@ -0,0 +1,284 @@
+use crate::core::Context;
+use crate::gfx::Draw;
+use crate::input::{Key, Mouse};
+use crate::math::{RectF, Vec2F, vec2};
+use crate::ui::{DragEvent, DragState, ScrollState, UiId};
+use fnv::FnvHashMap;
+use std::hash::Hash;
+
+/// Widget state remembered between frames, such as how far each scroll area is scrolled.
+///
+/// Keep one of these in your game state and pass it to [`Ui::new`] every frame.
+#[derive(Debug, Default, Clone)]
+pub struct UiMemory {
+    pub(crate) scroll: FnvHashMap<UiId, ScrollState>,
+    pub(crate) drag_scroll: Option<(UiId, Vec2F)>,
+    pub(crate) wheel_target: Option<UiId>,
+    pub(crate) next_wheel_target: Option<UiId>,
+    pub(crate) drag: Option<DragState>,
+    pub(crate) drag_event: Option<(UiId, DragEvent)>,
+    pub(crate) next_drag_event: Option<(UiId, DragEvent)>,
+    pub(crate) magnets: Vec<RectF>,
+    pub(crate) next_magnets: Vec<RectF>,
+    pub(crate) focus: Option<UiId>,
+}
+
+impl UiMemory {
+    /// Create empty memory.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Forget the state of every widget.
+    #[inline]
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// The state of a scroll area, if it has been shown.
+    #[inline]
+    pub fn scroll_state(&self, id: impl Hash) -> Option<&ScrollState> {
+        self.scroll.get(&UiId::new(id))
+    }
+
+    /// Set how far a scroll area is scrolled, stopping any fling it was doing.
+    #[inline]
+    pub fn scroll_to(&mut self, id: impl Hash, offset: impl Into<Vec2F>) {
+        let state = self.scroll.entry(UiId::new(id)).or_default();
+        state.offset = offset.into();
+        state.velocity = Vec2F::ZERO;
+        state.clamp();
+    }
+
+    /// Scroll a scroll area as little as possible so that the rectangle, in the area's content
+    /// space, is in view. Useful for keeping the selected item visible when using a gamepad.
+    #[inline]
+    pub fn scroll_into_view(&mut self, id: impl Hash, rect: impl Into<RectF>) {
+        let state = self.scroll.entry(UiId::new(id)).or_default();
+        state.scroll_into_view(rect.into());
+    }
+
+    /// Rectangles of the widgets that could be interacted with last frame, in the space of the
+    /// mouse, for a [`GamepadCursor`](crate::ui::GamepadCursor) to be pulled toward.
+    #[inline]
+    pub fn magnets(&self) -> &[RectF] {
+        &self.magnets
+    }
+
+    /// The widget that is focused, if any.
+    #[inline]
+    pub fn focus(&self) -> Option<UiId> {
+        self.focus
+    }
+
+    /// Focus a widget, such as when a gamepad or the keyboard moves the selection in a menu.
+    /// Focused widgets are activated by pressing enter or space.
+    #[inline]
+    pub fn set_focus(&mut self, id: impl Hash) {
+        self.focus = Some(UiId::new(id));
+    }
+
+    /// Unfocus the focused widget.
+    #[inline]
+    pub fn clear_focus(&mut self) {
+        self.focus = None;
+    }
+
+    fn begin_frame(&mut self, left_down: bool, cancel: bool) {
+        self.wheel_target = self.next_wheel_target.take();
+        self.drag_event = self.next_drag_event.take();
+        std::mem::swap(&mut self.magnets, &mut self.next_magnets);
+        self.next_magnets.clear();
+
+        // drags let go of last frame weren't dropped on any target, and presses that never
+        // moved far enough to start dragging were just clicks
+        if let Some(drag) = self.drag.take_if(|_| !left_down || cancel)
+            && drag.active
+        {
+            self.drag_event = Some((drag.source, DragEvent::Cancelled));
+        }
+    }
+}
+
+/// Immediate-mode UI for one frame, which lays out widgets from top to bottom.
+///
+/// Widgets are drawn as they are added, and any state they need between frames is kept in a
+/// [`UiMemory`]. Positions are in the same space the mouse is in, so the draw transform should
+/// be the same as the window's when the UI is created.
+///
+/// ```ignore
+/// let mut ui = Ui::new(ctx, &mut self.ui, draw, screen_rect);
+/// ui.scroll_list("inventory", vec2(240.0, 400.0), 32.0, items.len(), |ui, i, rect| {
+///     let item = ui.interact(("item", i), rect);
+///     if item.hovered || item.focused {
+///         ui.draw.rect(rect, Rgba8::WHITE);
+///     }
+///     if item.clicked {
+///         selected = Some(i);
+///     }
+/// })?;
+/// ```
+pub struct Ui<'a> {
+    /// The draw context widgets are drawn with.
+    pub draw: &'a mut Draw,
+
+    pub(crate) memory: &'a mut UiMemory,
+    pub(crate) mouse: Mouse,
+    pub(crate) pointer: Vec2F,
+    pub(crate) dt: f32,
+    pub(crate) rect: RectF,
+    pub(crate) visible: RectF,
+    pub(crate) origin: Vec2F,
+    pub(crate) clip: RectF,
+    pub(crate) cursor: f32,
+    pub(crate) extent: Vec2F,
+    pub(crate) spacing: f32,
+    pub(crate) activate: bool,
+}
+
+impl<'a> Ui<'a> {
+    /// Start the UI for this frame, laying out widgets within the area.
+    pub fn new(ctx: &Context, memory: &'a mut UiMemory, draw: &'a mut Draw, area: RectF) -> Self {
+        let cancel = ctx.keyboard.pressed(Key::Escape) || ctx.mouse.right_pressed();
+        memory.begin_frame(ctx.mouse.left_down(), cancel);
+        Self {
+            draw,
+            memory,
+            mouse: ctx.mouse.clone(),
+            pointer: ctx.mouse.pos(),
+            dt: ctx.time.unscaled_delta(),
+            rect: area,
+            visible: area,
+            origin: Vec2F::ZERO,
+            clip: area,
+            cursor: area.y,
+            extent: Vec2F::ZERO,
+            spacing: 4.0,
+            activate: ctx.keyboard.pressed(Key::Enter) || ctx.keyboard.pressed(Key::Space),
+        }
+    }
+
+    /// Return the UI with the mouse at a different position, such as when the game is drawn
+    /// to a scaled-up screen and the mouse has been converted into its space.
+    #[inline]
+    pub fn with_pointer(mut self, pos: impl Into<Vec2F>) -> Self {
+        self.pointer = pos.into();
+        self
+    }
+
+    /// Return the UI with a different space between widgets.
+    #[inline]
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// The widget memory.
+    #[inline]
+    pub fn memory(&mut self) -> &mut UiMemory {
+        self.memory
+    }
+
+    /// The mouse.
+    #[inline]
+    pub fn mouse(&self) -> &Mouse {
+        &self.mouse
+    }
+
+    /// The mouse position in the UI's space.
+    #[inline]
+    pub fn pointer(&self) -> Vec2F {
+        self.pointer - self.origin
+    }
+
+    /// The area widgets are laid out in.
+    #[inline]
+    pub fn rect(&self) -> RectF {
+        self.rect
+    }
+
+    /// The part of the UI that can be seen. Inside a scroll area, this is the part of its
+    /// content currently scrolled into view.
+    #[inline]
+    pub fn visible_rect(&self) -> RectF {
+        self.visible
+    }
+
+    /// Space between widgets.
+    #[inline]
+    pub fn spacing(&self) -> f32 {
+        self.spacing
+    }
+
+    /// Set the space between widgets.
+    #[inline]
+    pub fn set_spacing(&mut self, spacing: f32) {
+        self.spacing = spacing;
+    }
+
+    /// The rectangle left below the widgets added so far.
+    #[inline]
+    pub fn available(&self) -> RectF {
+        let h = (self.rect.bottom() - self.cursor).max(0.0);
+        RectF::new(self.rect.x, self.cursor, self.rect.w, h)
+    }
+
+    /// Take space for a widget below the ones added so far, returning where it goes.
+    pub fn allocate(&mut self, size: impl Into<Vec2F>) -> RectF {
+        let rect = RectF::pos_size(vec2(self.rect.x, self.cursor), size.into());
+        self.cursor = rect.bottom() + self.spacing;
+        self.extent = self.extent.max(rect.bottom_right() - self.rect.top_left());
+        rect
+    }
+
+    /// Add empty space below the widgets added so far.
+    #[inline]
+    pub fn add_space(&mut self, amount: f32) {
+        self.cursor += amount;
+    }
+
+    /// If the mouse is over the rectangle and it isn't clipped away.
+    #[inline]
+    pub fn is_hovered(&self, rect: impl Into<RectF>) -> bool {
+        self.clip.contains(self.pointer) && rect.into().contains(self.pointer())
+    }
+
+    /// Mark the rectangle as something that can be interacted with, so a
+    /// [`GamepadCursor`](crate::ui::GamepadCursor) is pulled toward it. Drag sources and drop
+    /// targets that can accept the current drag do this already.
+    pub fn add_magnet(&mut self, rect: impl Into<RectF>) {
+        let rect = rect.into().translate(&self.origin);
+        if let Some(rect) = rect.overlap(&self.clip) {
+            self.memory.next_magnets.push(rect);
+        }
+    }
+
+    /// Lay out widgets inside the rectangle, such as the content area of a panel, without
+    /// taking any space for it.
+    pub fn show_in<R>(&mut self, rect: impl Into<RectF>, f: impl FnOnce(&mut Ui) -> R) -> R {
+        let rect = rect.into();
+        f(&mut self.child(rect, rect, Vec2F::ZERO))
+    }
+
+    /// Create a UI for the contents of a child widget, which are drawn with `offset` added to
+    /// their positions and laid out in `rect`. Its contents are only shown within `view`.
+    pub(crate) fn child(&mut self, rect: RectF, view: RectF, offset: Vec2F) -> Ui<'_> {
+        let screen_view = view.translate(&self.origin);
+        Ui {
+            draw: self.draw,
+            memory: self.memory,
+            mouse: self.mouse.clone(),
+            pointer: self.pointer,
+            dt: self.dt,
+            rect,
+            visible: view.translate(&-offset),
+            origin: self.origin + offset,
+            clip: self.clip.overlap(&screen_view).unwrap_or_default(),
+            cursor: rect.y,
+            extent: Vec2F::ZERO,
+            spacing: self.spacing,
+            activate: self.activate,
+        }
+    }
+}
@@ -0,0 +1,25 @@
+use fnv::FnvHasher;
+use std::hash::{Hash, Hasher};
+
+/// Identifies a widget, so its state can be remembered between frames.
+///
+/// Ids are created by hashing any value, such as a name or an index, so they must be unique
+/// among the widgets drawn each frame.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct UiId(pub u64);
+
+impl UiId {
+    /// Create an id by hashing the value.
+    #[inline]
+    pub fn new(value: impl Hash) -> Self {
+        let mut hasher = FnvHasher::default();
+        value.hash(&mut hasher);
+        Self(hasher.finish())
+    }
+
+    /// Create an id for a child of this widget, such as an item in a list.
+    #[inline]
+    pub fn with(self, value: impl Hash) -> Self {
+        Self::new((self.0, value))
+    }
+}
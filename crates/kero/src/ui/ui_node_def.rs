@@ -0,0 +1,237 @@
+use crate::math::Vec2F;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// An error loading a [`UiNodeDef`].
+#[derive(Debug, thiserror::Error)]
+pub enum UiTreeError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// What a node in a [`UiTree`](crate::ui::UiTree) is. The tree only lays out and interacts
+/// with nodes, so what each kind looks like is up to whatever draws the tree.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UiNodeKind {
+    /// An invisible container that only lays out its children.
+    #[default]
+    Group,
+
+    /// A container drawn with a background.
+    Panel,
+
+    /// A line of text.
+    Label,
+
+    /// Something that can be clicked or activated, which emits its action when it is.
+    Button,
+
+    /// An image, usually named by the node's `image`.
+    Image,
+
+    /// Empty space.
+    Spacer,
+}
+
+/// How a node in a [`UiTree`](crate::ui::UiTree) lays out its children.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UiDirection {
+    /// Top to bottom.
+    #[default]
+    Column,
+
+    /// Left to right.
+    Row,
+
+    /// On top of each other, each filling the node.
+    Stack,
+}
+
+/// The data file format of a [`UiTree`](crate::ui::UiTree) node and its children. Any field
+/// left out takes its default value.
+///
+/// Text can include bound values by name, like `"HP: {hp}"`, and `visible` and `enabled` name
+/// a bound value that decides if the node is shown or can be interacted with. Prefixing the
+/// name with `!` inverts it. Values are bound with [`UiTree::set`](crate::ui::UiTree::set).
+///
+/// ```json
+/// {
+///     "kind": "panel",
+///     "padding": [8, 8],
+///     "spacing": 4,
+///     "children": [
+///         { "kind": "label", "text": "Gold: {gold}" },
+///         { "kind": "button", "text": "Continue", "action": "continue", "visible": "has_save" },
+///         { "kind": "button", "text": "New Game", "action": "new_game" },
+///         { "kind": "spacer", "grow": 1 },
+///         { "kind": "button", "text": "Quit", "action": "quit" }
+///     ]
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiNodeDef {
+    /// A name to find the node by.
+    pub id: Option<String>,
+
+    /// What the node is.
+    pub kind: UiNodeKind,
+
+    /// Text of labels and buttons.
+    pub text: String,
+
+    /// What clicking the node emits, usually only set on buttons.
+    pub action: Option<String>,
+
+    /// Name of the image the node is drawn with.
+    pub image: Option<String>,
+
+    /// The node's size. Axes left at `0` are sized to fit the node's contents, except across
+    /// its parent's direction, where they fill the parent.
+    pub size: Vec2F,
+
+    /// How much of the space left over in its parent the node takes, relative to its
+    /// siblings.
+    pub grow: f32,
+
+    /// Space between the edge of the node and its children.
+    pub padding: Vec2F,
+
+    /// Space between children.
+    pub spacing: f32,
+
+    /// How children are laid out.
+    pub direction: UiDirection,
+
+    /// Where children smaller than the node are placed, from `0` (left/top) to `1`
+    /// (right/bottom) on each axis.
+    pub align: Vec2F,
+
+    /// The bound value that decides if the node is shown.
+    pub visible: Option<String>,
+
+    /// The bound value that decides if the node can be interacted with.
+    pub enabled: Option<String>,
+
+    /// The node's children.
+    pub children: Vec<UiNodeDef>,
+}
+
+impl UiNodeDef {
+    /// Parse a node and its children from JSON.
+    #[inline]
+    pub fn from_json(bytes: &[u8]) -> Result<Self, UiTreeError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// Load a node and its children from a JSON file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, UiTreeError> {
+        Self::from_json(&std::fs::read(path)?)
+    }
+
+    /// Create a node of the kind.
+    #[inline]
+    pub fn new(kind: UiNodeKind) -> Self {
+        Self {
+            kind,
+            ..Default::default()
+        }
+    }
+
+    /// Return the node with an id to find it by.
+    #[inline]
+    pub fn with_id(mut self, id: impl Into<String>) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    /// Return the node with text.
+    #[inline]
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Return the node with an action it emits when clicked.
+    #[inline]
+    pub fn with_action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+
+    /// Return the node with an image.
+    #[inline]
+    pub fn with_image(mut self, image: impl Into<String>) -> Self {
+        self.image = Some(image.into());
+        self
+    }
+
+    /// Return the node with a size.
+    #[inline]
+    pub fn with_size(mut self, size: impl Into<Vec2F>) -> Self {
+        self.size = size.into();
+        self
+    }
+
+    /// Return the node with a share of its parent's left over space.
+    #[inline]
+    pub fn with_grow(mut self, grow: f32) -> Self {
+        self.grow = grow;
+        self
+    }
+
+    /// Return the node with padding around its children.
+    #[inline]
+    pub fn with_padding(mut self, padding: impl Into<Vec2F>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+
+    /// Return the node with space between its children.
+    #[inline]
+    pub fn with_spacing(mut self, spacing: f32) -> Self {
+        self.spacing = spacing;
+        self
+    }
+
+    /// Return the node with its children laid out in a direction.
+    #[inline]
+    pub fn with_direction(mut self, direction: UiDirection) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Return the node with its children aligned.
+    #[inline]
+    pub fn with_align(mut self, align: impl Into<Vec2F>) -> Self {
+        self.align = align.into();
+        self
+    }
+
+    /// Return the node shown only when the bound value is true.
+    #[inline]
+    pub fn with_visible(mut self, binding: impl Into<String>) -> Self {
+        self.visible = Some(binding.into());
+        self
+    }
+
+    /// Return the node enabled only when the bound value is true.
+    #[inline]
+    pub fn with_enabled(mut self, binding: impl Into<String>) -> Self {
+        self.enabled = Some(binding.into());
+        self
+    }
+
+    /// Return the node with a child added.
+    #[inline]
+    pub fn with_child(mut self, child: UiNodeDef) -> Self {
+        self.children.push(child);
+        self
+    }
+}
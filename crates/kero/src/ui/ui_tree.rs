@@ -0,0 +1,657 @@
+use crate::input::Mouse;
+use crate::math::{RectF, Vec2F, vec2};
+use crate::ui::{UiDirection, UiNodeDef, UiNodeKind, UiTreeError};
+use fnv::FnvHashMap;
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+/// A value bound to a [`UiTree`], which its nodes' text and visibility can refer to by name.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+impl UiValue {
+    /// If the value counts as true when deciding if a node is visible or enabled, which it
+    /// does unless it's `false`, zero, or empty text.
+    #[inline]
+    pub fn is_true(&self) -> bool {
+        match self {
+            Self::Bool(b) => *b,
+            Self::Int(n) => *n != 0,
+            Self::Float(n) => *n != 0.0,
+            Self::Text(s) => !s.is_empty(),
+        }
+    }
+}
+
+impl Display for UiValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(b) => b.fmt(f),
+            Self::Int(n) => n.fmt(f),
+            Self::Float(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($ty:ty => $var:ident $(as $cast:ty)?),*) => {
+        $(
+            impl From<$ty> for UiValue {
+                #[inline]
+                fn from(value: $ty) -> Self {
+                    Self::$var(value $(as $cast)?)
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    bool => Bool,
+    i32 => Int as i64,
+    i64 => Int,
+    u32 => Int as i64,
+    usize => Int as i64,
+    f32 => Float as f64,
+    f64 => Float
+);
+
+impl From<String> for UiValue {
+    #[inline]
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for UiValue {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_string())
+    }
+}
+
+/// Identifies a node in a [`UiTree`].
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct UiNodeId(pub usize);
+
+/// An action emitted by a [`UiTree`] node when it was clicked or activated.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct UiAction {
+    /// The node that emitted the action.
+    pub node: UiNodeId,
+
+    /// The node's action.
+    pub action: String,
+}
+
+/// A node in a [`UiTree`], with where it was laid out and what state it's in.
+#[derive(Debug, Clone)]
+pub struct UiNode {
+    def: UiNodeDef,
+    text: String,
+    parent: Option<UiNodeId>,
+    children: Vec<UiNodeId>,
+    rect: RectF,
+    content: Vec2F,
+    visible: bool,
+    enabled: bool,
+    hovered: bool,
+    pressed: bool,
+    focused: bool,
+}
+
+impl UiNode {
+    /// The node's definition. Its children are left out, see [`children`](Self::children).
+    #[inline]
+    pub fn def(&self) -> &UiNodeDef {
+        &self.def
+    }
+
+    /// What the node is.
+    #[inline]
+    pub fn kind(&self) -> UiNodeKind {
+        self.def.kind
+    }
+
+    /// The name the node can be found by.
+    #[inline]
+    pub fn id(&self) -> Option<&str> {
+        self.def.id.as_deref()
+    }
+
+    /// The node's text, with bound values filled in.
+    #[inline]
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// What the node emits when clicked.
+    #[inline]
+    pub fn action(&self) -> Option<&str> {
+        self.def.action.as_deref()
+    }
+
+    /// Name of the image the node is drawn with.
+    #[inline]
+    pub fn image(&self) -> Option<&str> {
+        self.def.image.as_deref()
+    }
+
+    /// The node's parent, unless it is the root.
+    #[inline]
+    pub fn parent(&self) -> Option<UiNodeId> {
+        self.parent
+    }
+
+    /// The node's children.
+    #[inline]
+    pub fn children(&self) -> &[UiNodeId] {
+        &self.children
+    }
+
+    /// Where the node was laid out.
+    #[inline]
+    pub fn rect(&self) -> RectF {
+        self.rect
+    }
+
+    /// If the node and all its ancestors are visible.
+    #[inline]
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// If the node and all its ancestors are enabled.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// If the mouse is over the node.
+    #[inline]
+    pub fn is_hovered(&self) -> bool {
+        self.hovered
+    }
+
+    /// If the node is being pressed.
+    #[inline]
+    pub fn is_pressed(&self) -> bool {
+        self.pressed
+    }
+
+    /// If the node is selected by a gamepad or keyboard.
+    #[inline]
+    pub fn is_focused(&self) -> bool {
+        self.focused
+    }
+
+    /// If the node can be clicked and focused, which buttons and nodes with actions can.
+    #[inline]
+    pub fn is_interactive(&self) -> bool {
+        self.def.kind == UiNodeKind::Button || self.def.action.is_some()
+    }
+
+    #[inline]
+    fn can_interact(&self) -> bool {
+        self.visible && self.enabled && self.is_interactive()
+    }
+}
+
+/// A retained tree of widgets, described by a [`UiNodeDef`] that is usually loaded from a data
+/// file, for menus too complex to comfortably build with [`Ui`](crate::ui::Ui) every frame.
+///
+/// Game state is bound to the tree by name with [`set`](Self::set), and nodes' text and
+/// visibility update to match. Only changes cause the tree to be laid out again. The tree
+/// doesn't draw itself; draw its [visible nodes](Self::visible_nodes) with a theme instead.
+///
+/// ```ignore
+/// let mut menu = UiTree::from_file("assets/ui/pause.json")?;
+///
+/// // every frame
+/// menu.set("gold", player.gold);
+/// menu.set("has_save", saves.any());
+/// menu.layout(screen_rect, |node| theme.measure(node));
+/// if let Some(action) = menu.update(&ctx.mouse, ctx.mouse.pos()) {
+///     match action.action.as_str() { "quit" => ctx.quit(), _ => {} }
+/// }
+/// for node in menu.visible_nodes() {
+///     theme.draw_node(draw, node);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UiTree {
+    nodes: Vec<UiNode>,
+    ids: FnvHashMap<String, UiNodeId>,
+    values: FnvHashMap<String, UiValue>,
+    area: RectF,
+    dirty: bool,
+    pressed: Option<UiNodeId>,
+    focused: Option<UiNodeId>,
+}
+
+impl UiTree {
+    /// Create a tree from the root node's definition.
+    pub fn new(root: &UiNodeDef) -> Self {
+        let mut tree = Self {
+            nodes: Vec::new(),
+            ids: FnvHashMap::default(),
+            values: FnvHashMap::default(),
+            area: RectF::default(),
+            dirty: true,
+            pressed: None,
+            focused: None,
+        };
+        tree.add(root, None);
+        tree
+    }
+
+    /// Parse a tree from JSON.
+    #[inline]
+    pub fn from_json(bytes: &[u8]) -> Result<Self, UiTreeError> {
+        Ok(Self::new(&UiNodeDef::from_json(bytes)?))
+    }
+
+    /// Load a tree from a JSON file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, UiTreeError> {
+        Ok(Self::new(&UiNodeDef::from_file(path)?))
+    }
+
+    fn add(&mut self, def: &UiNodeDef, parent: Option<UiNodeId>) -> UiNodeId {
+        let id = UiNodeId(self.nodes.len());
+        if let Some(name) = &def.id {
+            self.ids.insert(name.clone(), id);
+        }
+        self.nodes.push(UiNode {
+            def: UiNodeDef {
+                children: Vec::new(),
+                ..def.clone()
+            },
+            text: def.text.clone(),
+            parent,
+            children: Vec::new(),
+            rect: RectF::default(),
+            content: Vec2F::ZERO,
+            visible: true,
+            enabled: true,
+            hovered: false,
+            pressed: false,
+            focused: false,
+        });
+        for child in &def.children {
+            let child = self.add(child, Some(id));
+            self.nodes[id.0].children.push(child);
+        }
+        id
+    }
+
+    /// The root node.
+    #[inline]
+    pub fn root(&self) -> &UiNode {
+        &self.nodes[0]
+    }
+
+    /// The node.
+    #[inline]
+    pub fn node(&self, id: UiNodeId) -> Option<&UiNode> {
+        self.nodes.get(id.0)
+    }
+
+    /// Find a node by the id it was given in its definition.
+    #[inline]
+    pub fn find(&self, id: &str) -> Option<UiNodeId> {
+        self.ids.get(id).copied()
+    }
+
+    /// All nodes, parents before their children.
+    #[inline]
+    pub fn nodes(&self) -> &[UiNode] {
+        &self.nodes
+    }
+
+    /// Visible nodes in the order they should be drawn.
+    #[inline]
+    pub fn visible_nodes(&self) -> impl Iterator<Item = &UiNode> {
+        self.nodes.iter().filter(|n| n.visible)
+    }
+
+    /// Rectangles of the nodes that can be interacted with, for a
+    /// [`GamepadCursor`](crate::ui::GamepadCursor) to be pulled toward.
+    #[inline]
+    pub fn magnets(&self) -> impl Iterator<Item = RectF> {
+        self.nodes
+            .iter()
+            .filter(|n| n.can_interact())
+            .map(|n| n.rect)
+    }
+
+    /// Bind a value by name. If it changed, nodes that refer to it update the next time the
+    /// tree is laid out.
+    pub fn set(&mut self, name: &str, value: impl Into<UiValue>) {
+        let value = value.into();
+        match self.values.get_mut(name) {
+            Some(old) if *old == value => {}
+            Some(old) => {
+                *old = value;
+                self.dirty = true;
+            }
+            None => {
+                self.values.insert(name.to_string(), value);
+                self.dirty = true;
+            }
+        }
+    }
+
+    /// A bound value.
+    #[inline]
+    pub fn value(&self, name: &str) -> Option<&UiValue> {
+        self.values.get(name)
+    }
+
+    /// Unbind a value.
+    #[inline]
+    pub fn remove(&mut self, name: &str) -> Option<UiValue> {
+        self.dirty = true;
+        self.values.remove(name)
+    }
+
+    /// Make the tree lay itself out again next time, such as after the font its text is
+    /// measured with changed.
+    #[inline]
+    pub fn mark_dirty(&mut self) {
+        self.dirty = true;
+    }
+
+    /// Lay the tree out to fill the area, if it or any bound values changed since it was last
+    /// laid out. `measure` returns the size of a node without children, such as the size of
+    /// its text.
+    pub fn layout(&mut self, area: impl Into<RectF>, mut measure: impl FnMut(&UiNode) -> Vec2F) {
+        let area = area.into();
+        if !self.dirty && area == self.area {
+            return;
+        }
+        self.dirty = false;
+        self.area = area;
+        self.bind();
+
+        // parents come before their children, so in reverse every child is measured before
+        // its parent is
+        for i in (0..self.nodes.len()).rev() {
+            let node = &self.nodes[i];
+            let content = match node.children.is_empty() {
+                true => measure(node),
+                false => self.children_size(node),
+            };
+            self.nodes[i].content = content;
+        }
+        self.place(UiNodeId(0), area);
+
+        if self
+            .focused
+            .is_some_and(|id| !self.nodes[id.0].can_interact())
+        {
+            self.set_focus(None);
+        }
+    }
+
+    /// Resolve every node's text and visibility from the bound values.
+    fn bind(&mut self) {
+        for i in 0..self.nodes.len() {
+            let (visible, enabled) = match self.nodes[i].parent {
+                Some(parent) => (self.nodes[parent.0].visible, self.nodes[parent.0].enabled),
+                None => (true, true),
+            };
+            let node = &self.nodes[i];
+            let visible = visible && self.condition(node.def.visible.as_deref());
+            let enabled = enabled && self.condition(node.def.enabled.as_deref());
+            let text = self.fill_text(&node.def.text);
+            let node = &mut self.nodes[i];
+            node.visible = visible;
+            node.enabled = enabled;
+            node.text = text;
+        }
+    }
+
+    fn condition(&self, binding: Option<&str>) -> bool {
+        let Some(binding) = binding else {
+            return true;
+        };
+        let (name, invert) = match binding.strip_prefix('!') {
+            Some(name) => (name, true),
+            None => (binding, false),
+        };
+        self.values.get(name.trim()).is_some_and(UiValue::is_true) != invert
+    }
+
+    /// Replace each `{name}` in the text with the bound value. Unbound names are left empty.
+    fn fill_text(&self, text: &str) -> String {
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                break;
+            };
+            out.push_str(&rest[..start]);
+            if let Some(value) = self.values.get(rest[start + 1..start + len].trim()) {
+                out.push_str(&value.to_string());
+            }
+            rest = &rest[start + len + 1..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// The node's size, where it was given one, and otherwise the size of its contents.
+    fn preferred_size(node: &UiNode) -> Vec2F {
+        let size = node.def.size;
+        vec2(
+            if size.x > 0.0 { size.x } else { node.content.x },
+            if size.y > 0.0 { size.y } else { node.content.y },
+        )
+    }
+
+    /// The size the node's visible children take up together, including its padding.
+    fn children_size(&self, node: &UiNode) -> Vec2F {
+        let mut size = Vec2F::ZERO;
+        let mut count = 0usize;
+        for child in node.children.iter().map(|c| &self.nodes[c.0]) {
+            if !child.visible {
+                continue;
+            }
+            let child = Self::preferred_size(child);
+            size = match node.def.direction {
+                UiDirection::Column => vec2(size.x.max(child.x), size.y + child.y),
+                UiDirection::Row => vec2(size.x + child.x, size.y.max(child.y)),
+                UiDirection::Stack => size.max(child),
+            };
+            count += 1;
+        }
+        let gaps = node.def.spacing * count.saturating_sub(1) as f32;
+        size += match node.def.direction {
+            UiDirection::Column => vec2(0.0, gaps),
+            UiDirection::Row => vec2(gaps, 0.0),
+            UiDirection::Stack => Vec2F::ZERO,
+        };
+        size + node.def.padding * 2.0
+    }
+
+    fn place(&mut self, id: UiNodeId, rect: RectF) {
+        self.nodes[id.0].rect = rect;
+        let node = &self.nodes[id.0];
+        let def = &node.def;
+        let (direction, align, spacing) = (def.direction, def.align, def.spacing);
+        let mut inner = rect.inset(def.padding);
+
+        let children: Vec<_> = node
+            .children
+            .iter()
+            .copied()
+            .filter(|c| self.nodes[c.0].visible)
+            .collect();
+        let sizes: Vec<_> = children
+            .iter()
+            .map(|c| Self::preferred_size(&self.nodes[c.0]))
+            .collect();
+        let grows: Vec<_> = children
+            .iter()
+            .map(|c| self.nodes[c.0].def.grow.max(0.0))
+            .collect();
+        let total_grow: f32 = grows.iter().sum();
+        let gaps = spacing * children.len().saturating_sub(1) as f32;
+
+        let mut rects = Vec::with_capacity(children.len());
+        match direction {
+            UiDirection::Stack => {
+                for (&child, &size) in children.iter().zip(&sizes) {
+                    let fixed = self.nodes[child.0].def.size;
+                    let size = vec2(
+                        if fixed.x > 0.0 { size.x } else { inner.w },
+                        if fixed.y > 0.0 { size.y } else { inner.h },
+                    );
+                    rects.push(inner.align(size, align));
+                }
+            }
+            UiDirection::Column => {
+                let used: f32 = sizes.iter().map(|s| s.y).sum::<f32>() + gaps;
+                let left = (inner.h - used).max(0.0);
+                if total_grow <= 0.0 {
+                    inner.cut_top(left * align.y);
+                }
+                for (i, &child) in children.iter().enumerate() {
+                    let grow = match total_grow > 0.0 {
+                        true => left * grows[i] / total_grow,
+                        false => 0.0,
+                    };
+                    let slot = inner.cut_top(sizes[i].y + grow);
+                    inner.cut_top(spacing);
+                    let w = match self.nodes[child.0].def.size.x > 0.0 {
+                        true => sizes[i].x,
+                        false => slot.w,
+                    };
+                    rects.push(slot.align(vec2(w, slot.h), vec2(align.x, 0.0)));
+                }
+            }
+            UiDirection::Row => {
+                let used: f32 = sizes.iter().map(|s| s.x).sum::<f32>() + gaps;
+                let left = (inner.w - used).max(0.0);
+                if total_grow <= 0.0 {
+                    inner.cut_left(left * align.x);
+                }
+                for (i, &child) in children.iter().enumerate() {
+                    let grow = match total_grow > 0.0 {
+                        true => left * grows[i] / total_grow,
+                        false => 0.0,
+                    };
+                    let slot = inner.cut_left(sizes[i].x + grow);
+                    inner.cut_left(spacing);
+                    let h = match self.nodes[child.0].def.size.y > 0.0 {
+                        true => sizes[i].y,
+                        false => slot.h,
+                    };
+                    rects.push(slot.align(vec2(slot.w, h), vec2(0.0, align.y)));
+                }
+            }
+        }
+
+        for (child, rect) in children.into_iter().zip(rects) {
+            self.place(child, rect);
+        }
+    }
+
+    /// Update which node the mouse is over and which is pressed, returning the action of the
+    /// node that was clicked, if any. `pointer` is the mouse position in the tree's space.
+    pub fn update(&mut self, mouse: &Mouse, pointer: impl Into<Vec2F>) -> Option<UiAction> {
+        let pointer = pointer.into();
+
+        // nodes are drawn in order, so the last one under the mouse is on top
+        let hovered = self
+            .nodes
+            .iter()
+            .rposition(|n| n.can_interact() && n.rect.contains(pointer))
+            .map(UiNodeId);
+
+        if mouse.left_pressed() {
+            self.pressed = hovered;
+        }
+        let mut clicked = None;
+        if mouse.left_released() {
+            if hovered.is_some() && self.pressed == hovered {
+                clicked = hovered.and_then(|id| self.action_of(id));
+            }
+            self.pressed = None;
+        }
+
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            node.hovered = hovered == Some(UiNodeId(i));
+            node.pressed = node.hovered && self.pressed == Some(UiNodeId(i));
+        }
+        clicked
+    }
+
+    fn action_of(&self, id: UiNodeId) -> Option<UiAction> {
+        let node = self.nodes.get(id.0).filter(|n| n.can_interact())?;
+        Some(UiAction {
+            node: id,
+            action: node.def.action.clone().unwrap_or_default(),
+        })
+    }
+
+    /// The node selected by a gamepad or keyboard.
+    #[inline]
+    pub fn focused(&self) -> Option<UiNodeId> {
+        self.focused
+    }
+
+    /// Select a node with a gamepad or keyboard.
+    pub fn set_focus(&mut self, id: Option<UiNodeId>) {
+        if let Some(old) = self.focused.take() {
+            self.nodes[old.0].focused = false;
+        }
+        if let Some(id) = id.filter(|id| self.nodes.get(id.0).is_some_and(|n| n.can_interact())) {
+            self.nodes[id.0].focused = true;
+            self.focused = Some(id);
+        }
+    }
+
+    /// Focus the next node that can be interacted with, wrapping around to the first.
+    #[inline]
+    pub fn focus_next(&mut self) {
+        self.focus_step(true);
+    }
+
+    /// Focus the previous node that can be interacted with, wrapping around to the last.
+    #[inline]
+    pub fn focus_prev(&mut self) {
+        self.focus_step(false);
+    }
+
+    fn focus_step(&mut self, forward: bool) {
+        let len = self.nodes.len();
+        let start = match (self.focused, forward) {
+            (Some(id), _) => id.0,
+            (None, true) => len - 1,
+            (None, false) => 0,
+        };
+        let next = (1..=len)
+            .map(|step| match forward {
+                true => (start + step) % len,
+                false => (start + len - step) % len,
+            })
+            .find(|&i| self.nodes[i].can_interact());
+        if let Some(i) = next {
+            self.set_focus(Some(UiNodeId(i)));
+        }
+    }
+
+    /// Activate the focused node as if it was clicked, returning its action.
+    #[inline]
+    pub fn activate(&self) -> Option<UiAction> {
+        self.focused.and_then(|id| self.action_of(id))
+    }
+}
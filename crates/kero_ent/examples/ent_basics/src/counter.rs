@@ -1,7 +1,7 @@
 use kero::prelude::*;
 use kero_ent::{ComponentOf, ComponentType};
 use mlua::prelude::LuaResult;
-use mlua::{AnyUserData, Lua, UserDataMethods, UserDataRef, UserDataRefMut};
+use mlua::{AnyUserData, Lua, Table, UserDataMethods, UserDataRef, UserDataRefMut};
 
 /// Reference to a `Counter` component stored inside `Lua`.
 pub type CounterObj = UserDataOf<ComponentOf<Counter>>;
@@ -65,6 +65,10 @@ impl ComponentType for Counter {
             }
             Ok(())
         });
+    const PREFAB_FN: Option<fn(&Lua, &Table) -> LuaResult<CounterObj>> = Some(|lua, props| {
+        let flags = props.get::<Option<u64>>("flags")?.unwrap_or(0);
+        Ok(Self::new(lua, flags))
+    });
 
     fn methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         // define a constructor
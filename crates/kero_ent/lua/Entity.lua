@@ -41,6 +41,18 @@ function methods.world(self) end
 ---@param self Entity
 function methods.remove_self(self) end
 
+---Name of the prefab the entity was instantiated from.
+---@param self Entity
+---@return string?
+---@nodiscard
+function methods.prefab(self) end
+
+---Overrides the entity was instantiated from its prefab with.
+---@param self Entity
+---@return table?
+---@nodiscard
+function methods.prefab_overrides(self) end
+
 ---@param self Entity
 ---@param val Vec2
 function methods.set_pos(self, val) end
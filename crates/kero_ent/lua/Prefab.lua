@@ -0,0 +1,50 @@
+---@meta
+
+---@class PrefabComponentDef
+---@field type string Registered name of the component type.
+
+---@class PrefabDef
+---@field pos Vec2?
+---@field x number?
+---@field y number?
+---@field active boolean?
+---@field visible boolean?
+---@field components PrefabComponentDef[]?
+
+---@class PrefabClass
+local module = {}
+
+---Define a prefab, replacing any existing prefab with the same name.
+---@param name string
+---@param def PrefabDef
+function module.define(name, def) end
+
+---Define every prefab in the table, keyed by name.
+---@param defs table<string, PrefabDef>
+function module.define_all(defs) end
+
+---Load a Lua data file that returns a table of prefab definitions, keyed by name.
+---@param path string
+function module.load(path) end
+
+---Returns true if a prefab with the name is defined.
+---@param name string
+---@return boolean
+---@nodiscard
+function module.has(name) end
+
+---Names of all defined prefabs.
+---@return string[]
+---@nodiscard
+function module.names() end
+
+---Create a new entity from the prefab. The entity is not added to a world. Overrides can replace
+---the entity's `pos`, `x`, `y`, `active` or `visible`, and a table keyed by a component's type
+---name will override properties of that component.
+---@param name string
+---@param overrides table?
+---@return Entity
+---@nodiscard
+function module.instantiate(name, overrides) end
+
+return module
@@ -1,7 +1,7 @@
 use crate::{ComponentObj, ComponentOf};
 use kero::math::Vec2F;
 use mlua::prelude::{LuaResult, LuaString};
-use mlua::{AnyUserData, Lua, UserDataFields, UserDataMethods};
+use mlua::{AnyUserData, Lua, Table, UserDataFields, UserDataMethods};
 
 pub trait ComponentType: Sized + 'static {
     const NAME: &'static str;
@@ -14,6 +14,10 @@ pub trait ComponentType: Sized + 'static {
     const UPDATE_FN: Option<fn(this: &AnyUserData, lua: &Lua) -> LuaResult<()>> = None;
     const RENDER_FN: Option<fn(this: &AnyUserData, lua: &Lua, pos: Vec2F) -> LuaResult<()>> = None;
 
+    /// Create the component from a prefab's properties. Components without this can't be used
+    /// in prefabs.
+    const PREFAB_FN: Option<fn(lua: &Lua, props: &Table) -> LuaResult<ComponentObj<Self>>> = None;
+
     #[inline]
     fn tostring(this: ComponentObj<Self>, lua: &Lua) -> LuaResult<LuaString> {
         let ptr = this.ptr() as usize;
@@ -1,4 +1,7 @@
-use crate::{Component, ComponentObj, ComponentType, EntityObj, IntoComponent, Registry, WorldObj};
+use crate::{
+    Component, ComponentObj, ComponentType, EntityObj, IntoComponent, PrefabLink, Registry,
+    WorldObj,
+};
 use kero::lua::UserDataOf;
 use kero::math::Vec2F;
 use mlua::Lua;
@@ -22,6 +25,7 @@ pub struct Entity {
     pub(crate) world: Option<WorldObj>,
     pub(crate) components: Vec<Option<Component>>,
     pub(crate) cleanup: bool,
+    pub(crate) prefab: Option<PrefabLink>,
     pub active: bool,
     pub visible: bool,
     pos: Vec2F,
@@ -42,6 +46,7 @@ impl Entity {
                 world: None,
                 components: Vec::new(),
                 cleanup: false,
+                prefab: None,
                 active: true,
                 visible: true,
                 pos,
@@ -94,6 +99,12 @@ impl Entity {
         self.version
     }
 
+    /// The prefab this entity was instantiated from, if any.
+    #[inline]
+    pub fn prefab(&self) -> Option<&PrefabLink> {
+        self.prefab.as_ref()
+    }
+
     #[inline]
     fn index_of_ptr(&self, ptr: *const c_void) -> Option<usize> {
         self.components
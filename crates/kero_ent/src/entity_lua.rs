@@ -83,6 +83,13 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         }
     });
 
+    methods.add_function("prefab", |_, this: EntityRef| {
+        Ok(this.prefab().map(|link| link.name.clone()))
+    });
+    methods.add_function("prefab_overrides", |_, this: EntityRef| {
+        Ok(this.prefab().and_then(|link| link.overrides.clone()))
+    });
+
     methods.add_function(
         "set_pos",
         |_, (mut this, x, y): (EntityMut, Either<Vec2F, f32>, Option<f32>)| {
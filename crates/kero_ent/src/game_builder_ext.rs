@@ -1,5 +1,7 @@
 use crate::registry::Registry;
-use crate::{ComponentModule, ComponentOfModule, ComponentType, EntityModule, WorldModule};
+use crate::{
+    ComponentModule, ComponentOfModule, ComponentType, EntityModule, PrefabModule, WorldModule,
+};
 use kero::core::GameError;
 use kero::prelude::GameBuilder;
 
@@ -15,7 +17,8 @@ impl GameBuilderExt for GameBuilder {
             self = self
                 .with_module::<WorldModule>()?
                 .with_module::<EntityModule>()?
-                .with_module::<ComponentModule>()?;
+                .with_module::<ComponentModule>()?
+                .with_module::<PrefabModule>()?;
         }
 
         // register the rust component
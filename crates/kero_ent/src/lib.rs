@@ -8,6 +8,8 @@ mod entity;
 mod entity_lua;
 mod game_builder_ext;
 mod into_component;
+mod prefab;
+mod prefab_lua;
 mod registry;
 mod world;
 mod world_lua;
@@ -20,6 +22,8 @@ pub use entity::*;
 pub use entity_lua::*;
 pub use game_builder_ext::*;
 pub use into_component::*;
+pub use prefab::*;
+pub use prefab_lua::*;
 pub use registry::*;
 pub use world::*;
 pub use world_lua::*;
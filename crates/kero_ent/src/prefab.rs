@@ -0,0 +1,260 @@
+use crate::registry::Index;
+use crate::{Component, Entity, EntityExt, EntityObj, IntoComponent, Registry};
+use kero::math::{Vec2F, vec2};
+use mlua::prelude::{LuaError, LuaResult};
+use mlua::{AppDataRef, Lua, Table, Value};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A named template for an entity and its components.
+///
+/// Prefabs are usually defined in Lua, either by calling `Prefab.define` or by loading a data
+/// file that returns a table of definitions:
+///
+/// ```lua
+/// return {
+///     Goblin = {
+///         x = 0, y = 0,
+///         components = {
+///             { type = "Health", max = 10 },
+///             { type = "Mover", flags = 1, speed = 3 },
+///         },
+///     },
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Prefab {
+    pub name: String,
+    pub pos: Vec2F,
+    pub active: bool,
+    pub visible: bool,
+    pub components: Vec<PrefabComponent>,
+}
+
+/// A component in a [`Prefab`].
+#[derive(Debug, Clone)]
+pub struct PrefabComponent {
+    /// Registered name of the component type.
+    pub type_name: String,
+
+    /// Properties the component is created with. Lua components have these copied onto their
+    /// table, Rust components receive them in [`PREFAB_FN`](crate::ComponentType::PREFAB_FN).
+    pub props: Table,
+}
+
+/// Link from an entity back to the [`Prefab`] it was instantiated from, so editors can save the
+/// entity as its prefab plus the overrides.
+#[derive(Debug, Clone)]
+pub struct PrefabLink {
+    pub name: String,
+    pub overrides: Option<Table>,
+}
+
+impl Prefab {
+    /// Parse a prefab definition table.
+    pub fn from_table(lua: &Lua, name: impl Into<String>, def: &Table) -> LuaResult<Self> {
+        let name = name.into();
+        let mut components = Vec::new();
+        if let Some(list) = def.get::<Option<Table>>("components")? {
+            for comp in list.sequence_values::<Table>() {
+                let comp = comp?;
+                let type_name = comp.get::<Option<String>>("type")?.ok_or_else(|| {
+                    LuaError::runtime(format!("prefab [{name}] has a component without a type"))
+                })?;
+                let props = copy_table(lua, &comp)?;
+                props.raw_set("type", Value::Nil)?;
+                components.push(PrefabComponent { type_name, props });
+            }
+        }
+        Ok(Self {
+            pos: read_pos(def)?.unwrap_or(Vec2F::ZERO),
+            active: def.get::<Option<bool>>("active")?.unwrap_or(true),
+            visible: def.get::<Option<bool>>("visible")?.unwrap_or(true),
+            name,
+            components,
+        })
+    }
+
+    /// Create a new entity from the prefab. The entity is not added to a world.
+    ///
+    /// The `overrides` table can replace the entity's `pos`, `x`, `y`, `active` or `visible`, and
+    /// a table keyed by a component's type name will override properties of that component.
+    pub fn instantiate(&self, lua: &Lua, overrides: Option<&Table>) -> LuaResult<EntityObj> {
+        let mut pos = self.pos;
+        let mut active = self.active;
+        let mut visible = self.visible;
+        if let Some(overrides) = overrides {
+            if let Some(p) = overrides.get::<Option<Vec2F>>("pos")? {
+                pos = p;
+            }
+            if let Some(x) = overrides.get::<Option<f32>>("x")? {
+                pos.x = x;
+            }
+            if let Some(y) = overrides.get::<Option<f32>>("y")? {
+                pos.y = y;
+            }
+            active = overrides.get::<Option<bool>>("active")?.unwrap_or(active);
+            visible = overrides.get::<Option<bool>>("visible")?.unwrap_or(visible);
+        }
+
+        let ent = Entity::new_at(lua, pos);
+        {
+            let mut ent = ent.get_mut();
+            ent.active = active;
+            ent.visible = visible;
+            ent.prefab = Some(PrefabLink {
+                name: self.name.clone(),
+                overrides: overrides.cloned(),
+            });
+        }
+
+        for comp in &self.components {
+            let props = copy_table(lua, &comp.props)?;
+            if let Some(over) = overrides
+                .map(|o| o.get::<Option<Table>>(comp.type_name.as_str()))
+                .transpose()?
+                .flatten()
+            {
+                for pair in over.pairs::<Value, Value>() {
+                    let (key, val) = pair?;
+                    props.raw_set(key, copy_value(lua, val)?)?;
+                }
+            }
+            ent.add(lua, create_component(lua, &comp.type_name, props)?)?;
+        }
+
+        Ok(ent)
+    }
+}
+
+/// All prefabs defined in the Lua state.
+#[derive(Debug, Default)]
+pub struct PrefabRegistry {
+    prefabs: HashMap<String, Arc<Prefab>>,
+}
+
+impl PrefabRegistry {
+    #[inline]
+    fn init(lua: &Lua) {
+        if lua.app_data_ref::<Self>().is_none() {
+            lua.set_app_data(Self::default());
+        }
+    }
+
+    #[inline]
+    fn get_or_init(lua: &Lua) -> AppDataRef<'_, Self> {
+        Self::init(lua);
+        lua.app_data_ref::<Self>().unwrap()
+    }
+
+    /// Define a prefab, replacing any existing prefab with the same name.
+    #[inline]
+    pub fn define(lua: &Lua, prefab: Prefab) {
+        Self::init(lua);
+        let mut reg = lua.app_data_mut::<Self>().unwrap();
+        reg.prefabs.insert(prefab.name.clone(), Arc::new(prefab));
+    }
+
+    /// Define every prefab in a table of definitions, keyed by name.
+    pub fn define_all(lua: &Lua, defs: &Table) -> LuaResult<()> {
+        for pair in defs.pairs::<String, Table>() {
+            let (name, def) = pair?;
+            Self::define(lua, Prefab::from_table(lua, name, &def)?);
+        }
+        Ok(())
+    }
+
+    /// Load a Lua data file that returns a table of prefab definitions, keyed by name.
+    #[inline]
+    pub fn load(lua: &Lua, path: impl AsRef<Path>) -> LuaResult<()> {
+        let defs = lua.load(path.as_ref()).eval::<Table>()?;
+        Self::define_all(lua, &defs)
+    }
+
+    /// Get the prefab with the name.
+    #[inline]
+    pub fn get(lua: &Lua, name: &str) -> Option<Arc<Prefab>> {
+        Self::get_or_init(lua).prefabs.get(name).cloned()
+    }
+
+    /// Names of all defined prefabs.
+    #[inline]
+    pub fn names(lua: &Lua) -> Vec<String> {
+        Self::get_or_init(lua).prefabs.keys().cloned().collect()
+    }
+
+    /// Create a new entity from the prefab with the name. See [`Prefab::instantiate`].
+    #[inline]
+    pub fn instantiate(lua: &Lua, name: &str, overrides: Option<&Table>) -> LuaResult<EntityObj> {
+        Self::get(lua, name)
+            .ok_or_else(|| LuaError::runtime(format!("prefab not found with the name [{name}]")))?
+            .instantiate(lua, overrides)
+    }
+}
+
+#[inline]
+fn read_pos(table: &Table) -> LuaResult<Option<Vec2F>> {
+    if let Some(pos) = table.get::<Option<Vec2F>>("pos")? {
+        return Ok(Some(pos));
+    }
+    let x = table.get::<Option<f32>>("x")?;
+    let y = table.get::<Option<f32>>("y")?;
+    Ok((x.is_some() || y.is_some()).then(|| vec2(x.unwrap_or(0.0), y.unwrap_or(0.0))))
+}
+
+/// Create a component of the registered type from its properties.
+fn create_component(lua: &Lua, type_name: &str, props: Table) -> LuaResult<Component> {
+    let index = Registry::get(lua)
+        .name_lookup
+        .get(type_name)
+        .copied()
+        .ok_or_else(|| LuaError::runtime(format!("type not found with the name [{type_name}]")))?;
+    match index {
+        Index::Rust(i) => {
+            let prefab_fn = Registry::get(lua).rust_types[i].prefab_fn.ok_or_else(|| {
+                LuaError::runtime(format!(
+                    "rust component type [{type_name}] cannot be created from a prefab"
+                ))
+            })?;
+            let comp = prefab_fn(lua, &props)?.into_component(lua)?;
+            if let Some(active) = props.get::<Option<bool>>("active")? {
+                comp.set_active(active);
+            }
+            if let Some(visible) = props.get::<Option<bool>>("visible")? {
+                comp.set_visible(visible);
+            }
+            if let Some(flags) = props.get::<Option<u64>>("flags")? {
+                comp.set_flags(flags);
+            }
+            if let Some(depth) = props.get::<Option<f64>>("depth")? {
+                comp.set_depth(depth);
+            }
+            Ok(comp)
+        }
+        Index::Lua(i) => {
+            let class = Registry::get(lua).lua_types[i].class.clone();
+            props.set_metatable(Some(class))?;
+            props.into_component(lua)
+        }
+    }
+}
+
+/// Copy a table, so instances don't share tables with their prefab.
+fn copy_table(lua: &Lua, table: &Table) -> LuaResult<Table> {
+    let copy = lua.create_table()?;
+    for pair in table.pairs::<Value, Value>() {
+        let (key, val) = pair?;
+        copy.raw_set(key, copy_value(lua, val)?)?;
+    }
+    copy.set_metatable(table.metatable())?;
+    Ok(copy)
+}
+
+#[inline]
+fn copy_value(lua: &Lua, value: Value) -> LuaResult<Value> {
+    match value {
+        Value::Table(table) => copy_table(lua, &table).map(Value::Table),
+        value => Ok(value),
+    }
+}
@@ -0,0 +1,46 @@
+use crate::{Prefab, PrefabRegistry};
+use kero::lua::LuaModule;
+use mlua::prelude::LuaResult;
+use mlua::{BorrowedStr, Lua, Table, Value};
+
+pub struct PrefabModule;
+
+impl LuaModule for PrefabModule {
+    const PATH: &'static str = "Prefab";
+
+    fn load(lua: &Lua) -> LuaResult<Value> {
+        let module = lua.create_table()?;
+        module.set(
+            "define",
+            lua.create_function(|lua, (name, def): (String, Table)| {
+                PrefabRegistry::define(lua, Prefab::from_table(lua, name, &def)?);
+                Ok(())
+            })?,
+        )?;
+        module.set(
+            "define_all",
+            lua.create_function(|lua, defs: Table| PrefabRegistry::define_all(lua, &defs))?,
+        )?;
+        module.set(
+            "load",
+            lua.create_function(|lua, path: String| PrefabRegistry::load(lua, path))?,
+        )?;
+        module.set(
+            "has",
+            lua.create_function(|lua, name: BorrowedStr| {
+                Ok(PrefabRegistry::get(lua, name.as_ref()).is_some())
+            })?,
+        )?;
+        module.set(
+            "names",
+            lua.create_function(|lua, _: ()| Ok(PrefabRegistry::names(lua)))?,
+        )?;
+        module.set(
+            "instantiate",
+            lua.create_function(|lua, (name, overrides): (BorrowedStr, Option<Table>)| {
+                PrefabRegistry::instantiate(lua, name.as_ref(), overrides.as_ref())
+            })?,
+        )?;
+        Ok(Value::Table(module))
+    }
+}
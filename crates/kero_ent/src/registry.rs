@@ -87,6 +87,10 @@ impl Registry {
             despawned_fn: T::DESPAWNED_FN,
             update_fn: T::UPDATE_FN,
             render_fn: T::RENDER_FN,
+            prefab_fn: T::PREFAB_FN.map(|_| {
+                (|lua: &Lua, props: &Table| (T::PREFAB_FN.unwrap())(lua, props).map(Into::into))
+                    as fn(&Lua, &Table) -> LuaResult<AnyUserData>
+            }),
         }));
     }
 
@@ -96,6 +100,7 @@ impl Registry {
         self.module_lookup.insert(module.to_pointer(), idx);
         self.lua_types.push(Arc::new(LuaType {
             type_name: name,
+            class: module.clone(),
             added_fn: module.get::<Option<Function>>("added")?,
             removed_fn: module.get::<Option<Function>>("removed")?,
             spawned_fn: module.get::<Option<Function>>("spawned")?,
@@ -182,11 +187,13 @@ pub struct RustType {
     pub despawned_fn: Option<fn(&AnyUserData, &Lua) -> LuaResult<()>>,
     pub update_fn: Option<fn(&AnyUserData, &Lua) -> LuaResult<()>>,
     pub render_fn: Option<fn(&AnyUserData, &Lua, Vec2F) -> LuaResult<()>>,
+    pub prefab_fn: Option<fn(&Lua, &Table) -> LuaResult<AnyUserData>>,
 }
 
 #[derive(Debug)]
 pub struct LuaType {
     pub type_name: String,
+    pub class: Table,
     pub added_fn: Option<Function>,
     pub removed_fn: Option<Function>,
     pub spawned_fn: Option<Function>,
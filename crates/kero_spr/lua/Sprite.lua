@@ -36,6 +36,24 @@ function methods.offset(self) end
 ---@nodiscard
 function methods.size(self) end
 
+---The sprite's pivot, relative to the top-left of its untrimmed frame.
+---@param self Sprite
+---@return Vec2
+---@nodiscard
+function methods.pivot(self) end
+
+---The sprite's closed collision outlines, relative to the top-left of its untrimmed frame.
+---Only generated if the packer had collision tracing enabled.
+---@param self Sprite
+---@return Vec2[][]
+---@nodiscard
+function methods.collision(self) end
+
+---Set the sprite's pivot, relative to the top-left of its untrimmed frame.
+---@param self Sprite
+---@param pivot Vec2
+function methods.set_pivot(self, pivot) end
+
 ---The sprite's texture coordinates.
 ---@param self Sprite
 ---@return Vec2
@@ -45,7 +63,7 @@ function methods.size(self) end
 ---@nodiscard
 function methods.coords(self) end
 
----Draw this sprite at the provided position.
+---Draw this sprite with its pivot at the provided position. Flipping mirrors it around the pivot.
 ---@param self Sprite
 ---@param pos Vec2
 ---@param color Color?
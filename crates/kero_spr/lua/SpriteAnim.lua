@@ -33,6 +33,17 @@ function methods.width(self) end
 ---@nodiscard
 function methods.height(self) end
 
+---The animation's pivot, relative to the top-left of its canvas.
+---@param self SpriteAnim
+---@return Vec2
+---@nodiscard
+function methods.pivot(self) end
+
+---Set the animation's pivot, relative to the top-left of its canvas.
+---@param self SpriteAnim
+---@param pivot Vec2
+function methods.set_pivot(self, pivot) end
+
 ---The animation's frame count.
 ---@param self SpriteAnim
 ---@return integer
@@ -125,6 +136,18 @@ function methods.layer_mask(self, name) end
 ---@nodiscard
 function methods.layer_masks(self, names) end
 
+---Export the animation as a looping GIF, with each frame shown for its duration.
+---@param self SpriteAnim
+---@param path string
+---@param layers integer?
+function methods.export_gif(self, path, layers) end
+
+---Export the animation as a PNG with every frame laid out left to right.
+---@param self SpriteAnim
+---@param path string
+---@param layers integer?
+function methods.export_strip(self, path, layers) end
+
 ---Draw a frame of the animation.
 ---@param self SpriteAnim
 ---@param frame integer
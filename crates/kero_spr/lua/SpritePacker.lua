@@ -28,13 +28,49 @@ local methods = {}
 ---@nodiscard
 function module.new() end
 
+---Trace collision outlines for each sprite when packing, retrieved with `Sprite:collision()`.
+---Pixels with alpha above `alpha_threshold` are solid, and outlines are simplified to within
+---`tolerance` pixels (default `1`). Pass `nil` to disable tracing.
+---@param self SpritePacker
+---@param alpha_threshold integer? `0-255`
+---@param tolerance number?
+function methods.set_collision(self, alpha_threshold, tolerance) end
+
+---Set how many iterations of alpha bleeding to apply to the packed atlas, which bleeds the
+---color of sprites into surrounding transparent pixels to prevent dark halos when filtered.
+---Only use this if the added images are not premultiplied.
+---@param self SpritePacker
+---@param iterations integer
+function methods.set_alpha_bleed(self, iterations) end
+
+---@alias PackHeuristic
+---     |"tree"
+---     |"best_area_fit"
+---     |"best_short_side_fit"
+---     |"bottom_left"
+---     |"skyline"
+
+---Pack the atlas with each of the heuristics and keep the tightest result. By default only
+---`"tree"` is used.
+---@param self SpritePacker
+---@param ... PackHeuristic
+function methods.set_heuristics(self, ...) end
+
+---The heuristic that produced the last packed atlas, and how well it packed. `occupancy` is
+---the fraction of the atlas covered by images, from `0` to `1`.
+---@param self SpritePacker
+---@return { heuristic: PackHeuristic, pages: integer, occupancy: number, wasted_area: integer }?
+---@nodiscard
+function methods.report(self) end
+
 ---Add a sprite (a single image) to be packed.
 ---@param self SpritePacker
 ---@param id string
 ---@param file string
 ---@param premultiply boolean
 ---@param trim_threshold integer? `0-255`
-function methods.add_sprite(self, id, file, premultiply, trim_threshold) end
+---@param pivot Vec2? Pivot relative to the image's top-left.
+function methods.add_sprite(self, id, file, premultiply, trim_threshold, pivot) end
 
 ---Adds all images found in the directory as sprites. Their IDs will be set
 ---to their filenames without extensions.
@@ -81,6 +117,14 @@ function methods.add_font(self, id, file, size, chars) end
 ---@param chars string[]?
 function methods.add_fonts_in(self, directory, size, chars) end
 
+---Add a BMFont (AngelCode `.fnt`, text or XML) to be packed, along with its page images from
+---the same directory. Each glyph will be packed individually.
+---@param self SpritePacker
+---@param id string
+---@param file string
+---@param premultiply boolean
+function methods.add_bmfont(self, id, file, premultiply) end
+
 ---Add a 9-patch to be packed.
 ---@param self SpritePacker
 ---@param id string
@@ -0,0 +1,263 @@
+use crate::SpritePatch;
+use kero::prelude::*;
+
+/// How a health or progress bar is drawn.
+#[derive(Debug, Clone)]
+pub struct BarStyle {
+    /// Which way the bar fills as its value increases.
+    pub direction: Cardinal,
+
+    /// The color of the filled part of the bar.
+    pub fill: Rgba8,
+
+    /// The color drawn behind the fill, if any.
+    pub background: Option<Rgba8>,
+
+    /// The color of the trailing fill left behind when a [`Bar`] drops.
+    pub trail: Rgba8,
+
+    /// A patch drawn around the bar, if any.
+    pub border: Option<SpritePatch>,
+
+    /// How far the fill is inset from the bar's edges, to fit inside the border.
+    pub padding: f32,
+
+    /// How many segments the bar is split into by ticks. `0` or `1` draws no ticks.
+    pub segments: u32,
+
+    /// The color of the ticks between segments.
+    pub tick_color: Rgba8,
+
+    /// How thick the ticks between segments are.
+    pub tick_width: f32,
+}
+
+impl Default for BarStyle {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            direction: Cardinal::East,
+            fill: Rgba8::GREEN,
+            background: Some(Rgba8::BLACK),
+            trail: Rgba8::WHITE,
+            border: None,
+            padding: 0.0,
+            segments: 0,
+            tick_color: Rgba8::BLACK,
+            tick_width: 1.0,
+        }
+    }
+}
+
+impl BarStyle {
+    /// Create a style that fills left to right with the color.
+    #[inline]
+    pub fn new(fill: Rgba8) -> Self {
+        Self {
+            fill,
+            ..Default::default()
+        }
+    }
+
+    /// Return the style filling in a different direction.
+    #[inline]
+    pub fn with_direction(mut self, direction: Cardinal) -> Self {
+        self.direction = direction;
+        self
+    }
+
+    /// Return the style with a different background, or none.
+    #[inline]
+    pub fn with_background(mut self, background: Option<Rgba8>) -> Self {
+        self.background = background;
+        self
+    }
+
+    /// Return the style with a different trailing fill color.
+    #[inline]
+    pub fn with_trail(mut self, trail: Rgba8) -> Self {
+        self.trail = trail;
+        self
+    }
+
+    /// Return the style with a border, and the fill inset by `padding` to fit inside it.
+    #[inline]
+    pub fn with_border(mut self, border: SpritePatch, padding: f32) -> Self {
+        self.border = Some(border);
+        self.padding = padding;
+        self
+    }
+
+    /// Return the style split into segments by ticks.
+    #[inline]
+    pub fn with_segments(mut self, segments: u32, tick_color: Rgba8, tick_width: f32) -> Self {
+        self.segments = segments;
+        self.tick_color = tick_color;
+        self.tick_width = tick_width;
+        self
+    }
+
+    /// The part of the inner rectangle filled `t` of the way, from `0` to `1`.
+    fn fill_rect(&self, mut inner: RectF, t: f32) -> RectF {
+        let t = t.clamp(0.0, 1.0);
+        match self.direction {
+            Cardinal::East => inner.cut_left(inner.w * t),
+            Cardinal::West => inner.cut_right(inner.w * t),
+            Cardinal::South => inner.cut_top(inner.h * t),
+            Cardinal::North => inner.cut_bottom(inner.h * t),
+        }
+    }
+
+    /// Draw the bar filled `t` of the way, from `0` to `1`.
+    #[inline]
+    pub fn draw(&self, draw: &mut Draw, rect: impl Into<RectF>, t: f32) {
+        self.draw_trail(draw, rect, t, t);
+    }
+
+    /// Draw the bar filled `t` of the way, with the trailing fill drawn behind it up to
+    /// `trail`.
+    pub fn draw_trail(&self, draw: &mut Draw, rect: impl Into<RectF>, t: f32, trail: f32) {
+        let rect = rect.into();
+        let inner = rect.inset(vec2(self.padding, self.padding));
+
+        if let Some(background) = self.background {
+            draw.rect(inner, background);
+        }
+        if trail > t {
+            draw.rect(self.fill_rect(inner, trail), self.trail);
+        }
+        if t > 0.0 {
+            draw.rect(self.fill_rect(inner, t), self.fill);
+        }
+
+        if self.segments > 1 {
+            let horizontal = matches!(self.direction, Cardinal::East | Cardinal::West);
+            let half = self.tick_width * 0.5;
+            for i in 1..self.segments {
+                let f = i as f32 / self.segments as f32;
+                let tick = match horizontal {
+                    true => RectF::new(
+                        inner.x + inner.w * f - half,
+                        inner.y,
+                        self.tick_width,
+                        inner.h,
+                    ),
+                    false => RectF::new(
+                        inner.x,
+                        inner.y + inner.h * f - half,
+                        inner.w,
+                        self.tick_width,
+                    ),
+                };
+                draw.rect(tick, self.tick_color);
+            }
+        }
+
+        if let Some(border) = &self.border {
+            border.draw(draw, rect);
+        }
+    }
+}
+
+/// The state of a health bar that leaves a trailing fill behind when its value drops, which
+/// waits a moment and then drains away, so the player can see how much was lost.
+///
+/// ```ignore
+/// self.health_bar.set(player.health / player.max_health);
+/// self.health_bar.update(ctx.dt());
+/// self.health_bar.draw(draw, rect(8.0, 8.0, 120.0, 10.0), &self.bar_style);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bar {
+    /// How long the trailing fill waits after a drop before draining, in seconds.
+    pub delay: f32,
+
+    /// How fast the trailing fill drains, in fractions of the bar per second.
+    pub drain_speed: f32,
+
+    value: f32,
+    trail: f32,
+    timer: f32,
+}
+
+impl Default for Bar {
+    #[inline]
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+impl Bar {
+    /// Create a bar filled to the value, from `0` to `1`.
+    #[inline]
+    pub fn new(value: f32) -> Self {
+        let value = value.clamp(0.0, 1.0);
+        Self {
+            delay: 0.5,
+            drain_speed: 1.0,
+            value,
+            trail: value,
+            timer: 0.0,
+        }
+    }
+
+    /// Return the bar with a different delay before the trailing fill drains.
+    #[inline]
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Return the bar with a different trailing fill drain speed.
+    #[inline]
+    pub fn with_drain_speed(mut self, drain_speed: f32) -> Self {
+        self.drain_speed = drain_speed;
+        self
+    }
+
+    /// How full the bar is, from `0` to `1`.
+    #[inline]
+    pub fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// How far the trailing fill reaches, from `0` to `1`.
+    #[inline]
+    pub fn trail(&self) -> f32 {
+        self.trail
+    }
+
+    /// Set how full the bar is. If it drops, the trailing fill stays where it was and waits to
+    /// drain again. If it rises past the trailing fill, the trailing fill catches up.
+    pub fn set(&mut self, value: f32) {
+        let value = value.clamp(0.0, 1.0);
+        if value < self.value {
+            self.timer = self.delay;
+        }
+        self.value = value;
+        self.trail = self.trail.max(value);
+    }
+
+    /// Set how full the bar is, without leaving a trailing fill behind.
+    #[inline]
+    pub fn snap(&mut self, value: f32) {
+        self.value = value.clamp(0.0, 1.0);
+        self.trail = self.value;
+        self.timer = 0.0;
+    }
+
+    /// Drain the trailing fill.
+    pub fn update(&mut self, dt: f32) {
+        if self.timer > 0.0 {
+            self.timer -= dt;
+            return;
+        }
+        self.trail = (self.trail - self.drain_speed * dt).max(self.value);
+    }
+
+    /// Draw the bar with the style.
+    #[inline]
+    pub fn draw(&self, draw: &mut Draw, rect: impl Into<RectF>, style: &BarStyle) {
+        style.draw_trail(draw, rect, self.value, self.trail);
+    }
+}
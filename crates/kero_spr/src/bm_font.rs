@@ -0,0 +1,259 @@
+use kero::prelude::*;
+use std::path::Path;
+
+/// A bitmap font description in the BMFont (AngelCode) format, as exported by tools like
+/// BMFont, Hiero, or Glyph Designer.
+///
+/// Both the text and XML versions of `.fnt` files are supported, but not the binary version.
+/// The glyphs themselves live in the font's page images, which can be packed into an atlas
+/// with [`SpritePacker::add_bmfont`](crate::SpritePacker::add_bmfont), or used as textures
+/// directly with [`SpriteFont::from_bmfont`](crate::SpriteFont::from_bmfont).
+#[derive(Debug, Clone, PartialEq)]
+pub struct BmFont {
+    /// Name of the font's typeface.
+    pub face: String,
+
+    /// Size the font was rendered at.
+    pub size: f32,
+
+    /// Distance between the tops of consecutive lines.
+    pub line_height: i32,
+
+    /// Distance from the top of a line to its baseline.
+    pub base: i32,
+
+    /// File names of the page images, relative to the `.fnt` file.
+    pub pages: Vec<String>,
+    pub chars: Vec<BmChar>,
+    pub kerning: Vec<(char, char, i32)>,
+}
+
+/// A glyph of a [`BmFont`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BmChar {
+    pub chr: char,
+
+    /// Where the glyph is in its page image.
+    pub rect: RectU,
+
+    /// Offset from the pen position, relative to the top of the line, to draw the glyph at.
+    pub offset: Vec2I,
+
+    /// How far to advance the pen after the glyph.
+    pub advance: i32,
+
+    /// Index of the page image the glyph is in.
+    pub page: usize,
+}
+
+/// An error loading a BMFont.
+#[derive(Debug, thiserror::Error)]
+pub enum BmFontError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Image(#[from] ImageError),
+
+    #[error("binary BMFont files are not supported, export as text or XML instead")]
+    Binary,
+
+    #[error("missing `{1}` in `{0}`")]
+    MissingField(&'static str, &'static str),
+
+    #[error("invalid value `{1}` for `{0}`")]
+    InvalidValue(&'static str, String),
+
+    #[error("glyph {0:?} is outside of page {1}")]
+    InvalidGlyph(char, usize),
+}
+
+impl BmFont {
+    /// Load a font description from a `.fnt` file.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, BmFontError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+
+    /// Parse a font description from the contents of a `.fnt` file.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, BmFontError> {
+        if bytes.starts_with(b"BMF") {
+            return Err(BmFontError::Binary);
+        }
+        Self::parse(&String::from_utf8_lossy(bytes))
+    }
+
+    /// Parse a font description in the text or XML format.
+    pub fn parse(src: &str) -> Result<Self, BmFontError> {
+        let src = src.trim_start_matches('\u{feff}').trim_start();
+        let tags: Vec<&str> = match src.starts_with('<') {
+            true => src
+                .split('<')
+                .filter_map(|tag| tag.split('>').next())
+                .map(|tag| tag.trim_end_matches('/'))
+                .filter(|tag| !tag.starts_with(['?', '!', '/']))
+                .collect(),
+            false => src.lines().collect(),
+        };
+
+        let mut font = Self {
+            face: String::new(),
+            size: 0.0,
+            line_height: 0,
+            base: 0,
+            pages: Vec::new(),
+            chars: Vec::new(),
+            kerning: Vec::new(),
+        };
+        let mut has_common = false;
+        for tag in tags {
+            let tag = tag.trim();
+            let (name, rest) = tag.split_once(char::is_whitespace).unwrap_or((tag, ""));
+            let attrs = Attrs(parse_attrs(rest));
+            match name {
+                "info" => {
+                    font.face = attrs.get("face").unwrap_or_default().to_string();
+                    font.size = attrs.parse::<f32>("info", "size").unwrap_or(0.0).abs();
+                }
+                "common" => {
+                    font.line_height = attrs.parse("common", "lineHeight")?;
+                    font.base = attrs.parse("common", "base")?;
+                    has_common = true;
+                }
+                "page" => {
+                    let id: usize = attrs.parse("page", "id")?;
+                    let file = attrs
+                        .get("file")
+                        .ok_or(BmFontError::MissingField("page", "file"))?;
+                    if font.pages.len() <= id {
+                        font.pages.resize(id + 1, String::new());
+                    }
+                    font.pages[id] = file.to_string();
+                }
+                "char" => {
+                    // some tools write a fallback glyph with an id of -1
+                    let id: i64 = attrs.parse("char", "id")?;
+                    let Some(chr) = u32::try_from(id).ok().and_then(char::from_u32) else {
+                        continue;
+                    };
+                    font.chars.push(BmChar {
+                        chr,
+                        rect: RectU::new(
+                            attrs.parse("char", "x")?,
+                            attrs.parse("char", "y")?,
+                            attrs.parse("char", "width")?,
+                            attrs.parse("char", "height")?,
+                        ),
+                        offset: vec2(
+                            attrs.parse("char", "xoffset")?,
+                            attrs.parse("char", "yoffset")?,
+                        ),
+                        advance: attrs.parse("char", "xadvance")?,
+                        page: attrs.parse("char", "page").unwrap_or(0),
+                    });
+                }
+                "kerning" => {
+                    let first: u32 = attrs.parse("kerning", "first")?;
+                    let second: u32 = attrs.parse("kerning", "second")?;
+                    let amount: i32 = attrs.parse("kerning", "amount")?;
+                    if let (Some(a), Some(b)) = (char::from_u32(first), char::from_u32(second)) {
+                        font.kerning.push((a, b, amount));
+                    }
+                }
+                _ => {}
+            }
+        }
+        if !has_common {
+            return Err(BmFontError::MissingField("common", "lineHeight"));
+        }
+        Ok(font)
+    }
+
+    /// Check that every glyph is within its page, given the size of each page.
+    pub(crate) fn validate(&self, page_sizes: &[Vec2U]) -> Result<(), BmFontError> {
+        for c in &self.chars {
+            let fits = page_sizes.get(c.page).is_some_and(|size| {
+                c.rect.area() == 0 || (c.rect.right() <= size.x && c.rect.bottom() <= size.y)
+            });
+            if !fits {
+                return Err(BmFontError::InvalidGlyph(c.chr, c.page));
+            }
+        }
+        Ok(())
+    }
+
+    /// Load the page images, relative to the directory of the `.fnt` file.
+    pub(crate) fn load_pages(
+        &self,
+        fnt_path: &Path,
+        premultiply: bool,
+    ) -> Result<Vec<ImageRgba8>, BmFontError> {
+        let dir = fnt_path.parent().unwrap_or(Path::new(""));
+        self.pages
+            .iter()
+            .map(|file| {
+                let mut img = DynImage::load_file(dir.join(file))?.to_rgba8();
+                if premultiply {
+                    img.premultiply();
+                }
+                Ok(img)
+            })
+            .collect()
+    }
+}
+
+struct Attrs(Vec<(String, String)>);
+
+impl Attrs {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn parse<T: std::str::FromStr>(
+        &self,
+        tag: &'static str,
+        key: &'static str,
+    ) -> Result<T, BmFontError> {
+        let value = self.get(key).ok_or(BmFontError::MissingField(tag, key))?;
+        value
+            .parse()
+            .map_err(|_| BmFontError::InvalidValue(key, value.to_string()))
+    }
+}
+
+/// Parse `key=value` pairs, where values may be quoted and contain spaces.
+fn parse_attrs(mut src: &str) -> Vec<(String, String)> {
+    let mut attrs = Vec::new();
+    while let Some(eq) = src.find('=') {
+        let key = src[..eq].trim().to_string();
+        let rest = src[eq + 1..].trim_start();
+        let (value, next) = match rest.strip_prefix('"') {
+            Some(quoted) => match quoted.find('"') {
+                Some(end) => (&quoted[..end], &quoted[end + 1..]),
+                None => (quoted, ""),
+            },
+            None => match rest.find(char::is_whitespace) {
+                Some(end) => (&rest[..end], &rest[end..]),
+                None => (rest, ""),
+            },
+        };
+        attrs.push((key, unescape(value)));
+        src = next;
+    }
+    attrs
+}
+
+/// Decode the XML entities that may appear in attribute values.
+fn unescape(value: &str) -> String {
+    if !value.contains('&') {
+        return value.to_string();
+    }
+    value
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
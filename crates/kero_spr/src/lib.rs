@@ -1,6 +1,12 @@
 #[cfg(feature = "lua")]
 mod lua;
 
+mod bar;
+mod bm_font;
+mod skeleton;
+mod skeleton_anim;
+mod skeleton_data;
+mod spine;
 mod sprite;
 mod sprite_anim;
 mod sprite_atlas;
@@ -9,10 +15,16 @@ mod sprite_glyph;
 mod sprite_packer;
 mod sprite_patch;
 mod sprite_sheet;
+mod ui_theme;
 
 #[cfg(feature = "lua")]
 pub use lua::*;
 
+pub use bar::*;
+pub use bm_font::*;
+pub use skeleton::*;
+pub use skeleton_anim::*;
+pub use skeleton_data::*;
 pub use sprite::*;
 pub use sprite_anim::*;
 pub use sprite_atlas::*;
@@ -21,3 +33,4 @@ pub use sprite_glyph::*;
 pub use sprite_packer::*;
 pub use sprite_patch::*;
 pub use sprite_sheet::*;
+pub use ui_theme::*;
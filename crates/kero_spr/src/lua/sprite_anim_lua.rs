@@ -82,6 +82,14 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
     methods.add_function("size", |_, this: SpriteAnimRef| Ok(this.size));
     methods.add_function("width", |_, this: SpriteAnimRef| Ok(this.size.x));
     methods.add_function("height", |_, this: SpriteAnimRef| Ok(this.size.y));
+    methods.add_function("pivot", |_, this: SpriteAnimRef| Ok(this.pivot));
+    methods.add_function(
+        "set_pivot",
+        |_, (mut this, pivot): (SpriteAnimMut, Vec2F)| {
+            this.pivot = pivot;
+            Ok(())
+        },
+    );
     methods.add_function("num_frames", |_, this: SpriteAnimRef| Ok(this.frames.len()));
     methods.add_function(
         "frame_duration",
@@ -155,6 +163,22 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
         }
         Ok(mask)
     });
+    methods.add_function(
+        "export_gif",
+        |lua, (this, path, layers): (SpriteAnimRef, BorrowedStr, Option<u64>)| {
+            let ctx = Context::from_lua(lua);
+            this.export_gif(&ctx.graphics, path.as_ref(), layers.unwrap_or(u64::MAX))
+                .map_err(LuaError::external)
+        },
+    );
+    methods.add_function(
+        "export_strip",
+        |lua, (this, path, layers): (SpriteAnimRef, BorrowedStr, Option<u64>)| {
+            let ctx = Context::from_lua(lua);
+            this.export_strip(&ctx.graphics, path.as_ref(), layers.unwrap_or(u64::MAX))
+                .map_err(LuaError::external)
+        },
+    );
     methods.add_function(
         "draw",
         |lua,
@@ -179,7 +203,7 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
                 (fx, fy) => {
                     let fx = fx.unwrap_or(false);
                     let fy = fy.unwrap_or(false);
-                    this.draw_flipped(draw, frame, pos, layers, col, mode, (fx, fy));
+                    this.draw_flipped_ext(draw, frame, pos, layers, col, mode, (fx, fy));
                 }
             }
             Ok(())
@@ -42,6 +42,18 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
     methods.add_function("rect", |_, this: SpriteRef| Ok(this.sub.rect));
     methods.add_function("offset", |_, this: SpriteRef| Ok(this.sub.offset));
     methods.add_function("size", |_, this: SpriteRef| Ok(this.sub.size));
+    methods.add_function("pivot", |_, this: SpriteRef| Ok(this.pivot));
+    methods.add_function("collision", |lua, this: SpriteRef| {
+        let t = lua.create_table()?;
+        for outline in &this.collision {
+            t.raw_push(lua.create_sequence_from(outline.points().iter().copied())?)?;
+        }
+        Ok(t)
+    });
+    methods.add_function("set_pivot", |_, (mut this, pivot): (SpriteMut, Vec2F)| {
+        this.pivot = pivot;
+        Ok(())
+    });
     methods.add_function("coords", |_, this: SpriteRef| {
         let [a, b, c, d] = this.sub.coords;
         Ok((a, b, c, d))
@@ -62,12 +74,12 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             let draw = Draw::from_lua(lua)?;
             match (fx, fy) {
                 (None, None) => {
-                    draw.subtexture_at_ext(&this.sub, pos, col, mode);
+                    this.draw_ext(draw, pos, col, mode);
                 }
                 (fx, fy) => {
                     let fx = fx.unwrap_or(false);
                     let fy = fy.unwrap_or(false);
-                    draw.subtexture_at_flipped(&this.sub, pos, col, mode, (fx, fy));
+                    this.draw_flipped(draw, pos, col, mode, (fx, fy));
                 }
             }
             Ok(())
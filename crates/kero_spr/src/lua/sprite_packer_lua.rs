@@ -1,9 +1,10 @@
-use crate::SpritePacker;
+use crate::{PackHeuristic, SpritePacker};
 use fey_lua::{LuaModule, UserDataOf};
 use kero::prelude::*;
 use mlua::prelude::{LuaError, LuaResult};
 use mlua::{
     BorrowedStr, Either, Lua, UserData, UserDataMethods, UserDataRef, UserDataRefMut, Value,
+    Variadic,
 };
 
 pub struct SpritePackerModule;
@@ -55,17 +56,71 @@ impl UserData for SpritePacker<String> {
 // }
 
 fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
+    methods.add_function(
+        "set_heuristics",
+        |_, (mut this, names): (SpritePackerMut, Variadic<BorrowedStr>)| {
+            let heuristics = names
+                .iter()
+                .map(|name| match name.as_ref() {
+                    "tree" => Ok(PackHeuristic::Tree),
+                    "best_area_fit" => Ok(PackHeuristic::BestAreaFit),
+                    "best_short_side_fit" => Ok(PackHeuristic::BestShortSideFit),
+                    "bottom_left" => Ok(PackHeuristic::BottomLeft),
+                    "skyline" => Ok(PackHeuristic::Skyline),
+                    name => Err(LuaError::runtime(format!("invalid heuristic [{name}]"))),
+                })
+                .collect::<LuaResult<Vec<_>>>()?;
+            this.set_heuristics(heuristics);
+            Ok(())
+        },
+    );
+    methods.add_function("report", |lua, this: SpritePackerRef| {
+        let Some((heuristic, report)) = this.report() else {
+            return Ok(None);
+        };
+        let t = lua.create_table()?;
+        t.set(
+            "heuristic",
+            match heuristic {
+                PackHeuristic::Tree => "tree",
+                PackHeuristic::BestAreaFit => "best_area_fit",
+                PackHeuristic::BestShortSideFit => "best_short_side_fit",
+                PackHeuristic::BottomLeft => "bottom_left",
+                PackHeuristic::Skyline => "skyline",
+            },
+        )?;
+        t.set("pages", report.pages)?;
+        t.set("occupancy", report.occupancy())?;
+        t.set("wasted_area", report.wasted_area())?;
+        Ok(Some(t))
+    });
+    methods.add_function(
+        "set_collision",
+        |_, (mut this, threshold, tolerance): (SpritePackerMut, Option<u8>, Option<f32>)| {
+            this.set_collision(threshold.map(|t| (t, tolerance.unwrap_or(1.0))));
+            Ok(())
+        },
+    );
+    methods.add_function(
+        "set_alpha_bleed",
+        |_, (mut this, iterations): (SpritePackerMut, u32)| {
+            this.set_alpha_bleed(iterations);
+            Ok(())
+        },
+    );
     methods.add_function(
         "add_sprite",
         |_,
-         (mut this, id, file, premult, thresh): (
+         (mut this, id, file, premult, thresh, pivot): (
             SpritePackerMut,
             String,
             BorrowedStr,
             bool,
             Option<u8>,
+            Option<Vec2I>,
         )| {
-            this.add_sprite_file(id, file.as_ref(), premult, thresh)
+            let pivot = pivot.unwrap_or(Vec2I::ZERO);
+            this.add_sprite_file_ext(id, file.as_ref(), premult, thresh, pivot)
                 .map_err(LuaError::external)
         },
     );
@@ -117,7 +172,7 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
             f32,
             Option<Vec<char>>,
         )| {
-            let chars = chars.unwrap_or_else(|| BASIC_LATIN.chars().collect());
+            let chars = chars.unwrap_or_else(|| Charset::BASIC_LATIN.chars().collect());
             this.add_font_file(id, file.as_ref(), size, chars)
                 .map_err(LuaError::external)
         },
@@ -131,11 +186,18 @@ fn add_methods<T, M: UserDataMethods<T>>(methods: &mut M) {
              f32,
              Option<Vec<char>>,
          )| {
-            let chars = chars.unwrap_or_else(|| BASIC_LATIN.chars().collect());
+            let chars = chars.unwrap_or_else(|| Charset::BASIC_LATIN.chars().collect());
             this.add_font_files(dir.as_ref(), size, chars)
                 .map_err(LuaError::external)
         },
     );
+    methods.add_function(
+        "add_bmfont",
+        |_, (mut this, id, file, premult): (SpritePackerMut, String, BorrowedStr, bool)| {
+            this.add_bmfont_file(id, file.as_ref(), premult)
+                .map_err(LuaError::external)
+        },
+    );
     methods.add_function(
         "add_patch",
         |_,
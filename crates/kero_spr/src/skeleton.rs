@@ -0,0 +1,230 @@
+use crate::{SkeletonData, Sprite, sample_keys};
+use kero::prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A posable, drawable instance of a [`SkeletonData`].
+///
+/// Each attachment is drawn as a [`Mesh2D`] that is skinned to the skeleton's bones, and textured
+/// with the sprite that has the attachment's path as its name. Sprites should be packed without
+/// trimming, since their UVs are mapped across their full size.
+#[derive(Debug, Clone)]
+pub struct Skeleton {
+    data: Arc<SkeletonData>,
+    parts: HashMap<(usize, usize, String), SkeletonPart>,
+    skin: Option<usize>,
+    default_skin: Option<usize>,
+    bones: Vec<BonePose>,
+    slots: Vec<SlotPose>,
+    world: Vec<Affine2F>,
+    skinning: Vec<Affine2F>,
+}
+
+/// The current local transform of a skeleton's bone.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BonePose {
+    pub pos: Vec2F,
+
+    /// Rotation in degrees, clockwise.
+    pub rotation: f32,
+    pub scale: Vec2F,
+}
+
+/// The current state of a skeleton's slot.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotPose {
+    pub attachment: Option<String>,
+    pub color: Rgba8,
+}
+
+#[derive(Debug, Clone)]
+struct SkeletonPart {
+    mesh: Mesh2D,
+    texture: Texture,
+    color: Rgba8,
+}
+
+impl Skeleton {
+    /// Create a skeleton in its setup pose, looking up the sprite for each attachment by name.
+    /// Attachments without a sprite are not drawn.
+    pub fn new(data: Arc<SkeletonData>, mut sprite: impl FnMut(&str) -> Option<Sprite>) -> Self {
+        let mut parts = HashMap::new();
+        for (skin_idx, skin) in data.skins.iter().enumerate() {
+            for ((slot, name), att) in &skin.attachments {
+                let Some(sprite) = sprite(&att.path) else {
+                    continue;
+                };
+                let sub = &sprite.sub;
+                let tex_size = sub.texture.size().to_f32();
+                let top_left = sub.rect.top_left() - sub.offset;
+                let uvs = att
+                    .uvs
+                    .iter()
+                    .map(|&uv| (top_left + uv * sub.size) / tex_size)
+                    .collect();
+                let mut mesh = Mesh2D::new(att.positions.clone(), uvs, att.indices.clone());
+                mesh.set_weights(att.weights.clone());
+                parts.insert(
+                    (skin_idx, *slot, name.clone()),
+                    SkeletonPart {
+                        mesh,
+                        texture: sub.texture.clone(),
+                        color: att.color,
+                    },
+                );
+            }
+        }
+        let default_skin = data.skin_idx("default");
+        let mut skel = Self {
+            parts,
+            skin: None,
+            default_skin,
+            bones: Vec::with_capacity(data.bones.len()),
+            slots: Vec::with_capacity(data.slots.len()),
+            world: Vec::with_capacity(data.bones.len()),
+            skinning: Vec::with_capacity(data.bones.len()),
+            data,
+        };
+        skel.set_to_setup_pose();
+        skel
+    }
+
+    /// The skeleton's shared data.
+    #[inline]
+    pub fn data(&self) -> &Arc<SkeletonData> {
+        &self.data
+    }
+
+    /// Set the active skin. Attachments not found in it fall back to the default skin.
+    /// Returns `false` if no skin has the name.
+    pub fn set_skin(&mut self, name: &str) -> bool {
+        self.skin = self.data.skin_idx(name);
+        self.skin.is_some()
+    }
+
+    /// Current local pose of each bone.
+    #[inline]
+    pub fn bones(&self) -> &[BonePose] {
+        &self.bones
+    }
+
+    /// Mutable access to the bone poses. Call [`update_world`](Self::update_world) after
+    /// modifying them.
+    #[inline]
+    pub fn bones_mut(&mut self) -> &mut [BonePose] {
+        &mut self.bones
+    }
+
+    /// Current state of each slot.
+    #[inline]
+    pub fn slots(&self) -> &[SlotPose] {
+        &self.slots
+    }
+
+    /// Mutable access to the slot states.
+    #[inline]
+    pub fn slots_mut(&mut self) -> &mut [SlotPose] {
+        &mut self.slots
+    }
+
+    /// World transform of each bone, relative to the skeleton's origin.
+    #[inline]
+    pub fn world_transforms(&self) -> &[Affine2F] {
+        &self.world
+    }
+
+    /// World transform of the bone with the name, eg. to attach other objects to it.
+    #[inline]
+    pub fn bone_world(&self, name: &str) -> Option<Affine2F> {
+        self.data.bone_idx(name).map(|i| self.world[i])
+    }
+
+    /// Reset all bones and slots to the setup pose.
+    pub fn set_to_setup_pose(&mut self) {
+        self.bones.clear();
+        self.bones.extend(self.data.bones.iter().map(|b| BonePose {
+            pos: b.pos,
+            rotation: b.rotation,
+            scale: b.scale,
+        }));
+        self.slots.clear();
+        self.slots.extend(self.data.slots.iter().map(|s| SlotPose {
+            attachment: s.attachment.clone(),
+            color: s.color,
+        }));
+        self.update_world();
+    }
+
+    /// Pose the skeleton using the animation at the time, in seconds. If `looping` is true, the
+    /// time wraps around the animation's duration. Returns `false` if no animation has the name.
+    pub fn apply(&mut self, anim: &str, time: f32, looping: bool) -> bool {
+        let data = self.data.clone();
+        let Some(anim) = data.anim(anim) else {
+            return false;
+        };
+        let time = if looping && anim.duration > 0.0 {
+            time.rem_euclid(anim.duration)
+        } else {
+            time
+        };
+
+        self.set_to_setup_pose();
+        for tl in &anim.bones {
+            let (setup, pose) = (&data.bones[tl.bone], &mut self.bones[tl.bone]);
+            if let Some(rot) = sample_keys(&tl.rotate, time) {
+                pose.rotation = setup.rotation + rot;
+            }
+            if let Some(off) = sample_keys(&tl.translate, time) {
+                pose.pos = setup.pos + off;
+            }
+            if let Some(scale) = sample_keys(&tl.scale, time) {
+                pose.scale = setup.scale * scale;
+            }
+        }
+        for tl in &anim.slots {
+            let slot = &mut self.slots[tl.slot];
+            if let Some((_, name)) = tl.attachment.iter().rev().find(|k| k.0 <= time) {
+                slot.attachment = name.clone();
+            }
+            if let Some(c) = sample_keys(&tl.color, time) {
+                let c = (c * 255.0).round();
+                slot.color = Rgba8::new(c.x as u8, c.y as u8, c.z as u8, c.w as u8);
+            }
+        }
+        self.update_world();
+        true
+    }
+
+    /// Recalculate the world transform of each bone from their local poses.
+    pub fn update_world(&mut self) {
+        self.world.clear();
+        self.skinning.clear();
+        for (bone, pose) in self.data.bones.iter().zip(&self.bones) {
+            let local = Affine2F::trs(pose.pos, degs(pose.rotation), pose.scale);
+            let world = bone.parent.map_or(local, |p| self.world[p] * local);
+            self.world.push(world);
+            let setup_inv = bone.world.inverse().unwrap_or(Affine2F::IDENTITY);
+            self.skinning.push(world * setup_inv);
+        }
+    }
+
+    /// Draw the skeleton in its current pose, with its origin at the draw transform's origin.
+    pub fn draw(&mut self, draw: &mut Draw) {
+        for (i, slot) in self.slots.iter().enumerate() {
+            let Some(name) = &slot.attachment else {
+                continue;
+            };
+            let key = [self.skin, self.default_skin]
+                .into_iter()
+                .flatten()
+                .map(|skin| (skin, i, name.clone()))
+                .find(|key| self.parts.contains_key(key));
+            let Some(part) = key.and_then(|key| self.parts.get_mut(&key)) else {
+                continue;
+            };
+            part.mesh.skin(&self.skinning);
+            part.mesh.set_color(slot.color.mul_color(part.color));
+            draw.mesh(&mut part.mesh, Some(&part.texture));
+        }
+    }
+}
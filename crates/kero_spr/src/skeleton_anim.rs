@@ -0,0 +1,152 @@
+use crate::SkeletonError;
+use crate::skeleton_data::parse_color;
+use crate::spine::{SpineAnim, SpineKey};
+use kero::prelude::*;
+
+/// A skeletal animation, made of keyframed timelines for bones and slots.
+#[derive(Debug, Clone)]
+pub struct SkeletonAnim {
+    pub name: String,
+
+    /// Length of the animation in seconds.
+    pub duration: f32,
+    pub bones: Vec<BoneTimeline>,
+    pub slots: Vec<SlotTimeline>,
+}
+
+/// Keyframes animating a bone. Values are relative to the bone's setup pose.
+#[derive(Debug, Clone)]
+pub struct BoneTimeline {
+    pub bone: usize,
+
+    /// Rotation in degrees, clockwise, added to the setup rotation.
+    pub rotate: Vec<Keyframe<f32>>,
+
+    /// Offset added to the setup position.
+    pub translate: Vec<Keyframe<Vec2F>>,
+
+    /// Scale multiplied with the setup scale.
+    pub scale: Vec<Keyframe<Vec2F>>,
+}
+
+/// Keyframes animating a slot.
+#[derive(Debug, Clone)]
+pub struct SlotTimeline {
+    pub slot: usize,
+
+    /// Attachment changes, in seconds.
+    pub attachment: Vec<(f32, Option<String>)>,
+
+    /// Color, with channels from `0` to `1`.
+    pub color: Vec<Keyframe<Vec4F>>,
+}
+
+/// A value at a point in time.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Keyframe<T> {
+    /// Time of the key, in seconds.
+    pub time: f32,
+    pub value: T,
+
+    /// If true, the value is held until the next key instead of interpolating to it.
+    pub stepped: bool,
+}
+
+impl SkeletonAnim {
+    pub(crate) fn from_spine(
+        name: String,
+        anim: SpineAnim,
+        bone_idx: impl Fn(&str) -> Result<usize, SkeletonError>,
+        slot_idx: impl Fn(&str) -> Result<usize, SkeletonError>,
+    ) -> Result<Self, SkeletonError> {
+        fn keys<T>(keys: &[SpineKey], f: impl Fn(&SpineKey) -> T) -> Vec<Keyframe<T>> {
+            keys.iter()
+                .map(|key| Keyframe {
+                    time: key.time,
+                    value: f(key),
+                    stepped: key.stepped(),
+                })
+                .collect()
+        }
+
+        let mut bones = Vec::with_capacity(anim.bones.len());
+        for (name, timelines) in anim.bones {
+            bones.push(BoneTimeline {
+                bone: bone_idx(&name)?,
+                rotate: keys(&timelines.rotate, |k| -k.value.or(k.angle).unwrap_or(0.0)),
+                translate: keys(&timelines.translate, |k| {
+                    vec2(k.x.unwrap_or(0.0), -k.y.unwrap_or(0.0))
+                }),
+                scale: keys(&timelines.scale, |k| {
+                    vec2(k.x.unwrap_or(1.0), k.y.unwrap_or(1.0))
+                }),
+            });
+        }
+        bones.sort_by_key(|t| t.bone);
+
+        let mut slots = Vec::with_capacity(anim.slots.len());
+        for (name, timelines) in anim.slots {
+            let mut color = Vec::with_capacity(timelines.color.len());
+            for key in &timelines.color {
+                let c = key.color.as_deref().map_or(Ok(Rgba8::WHITE), parse_color)?;
+                color.push(Keyframe {
+                    time: key.time,
+                    value: vec4(c.r, c.g, c.b, c.a).to_f32() / 255.0,
+                    stepped: key.stepped(),
+                });
+            }
+            slots.push(SlotTimeline {
+                slot: slot_idx(&name)?,
+                attachment: timelines
+                    .attachment
+                    .into_iter()
+                    .map(|k| (k.time, k.name))
+                    .collect(),
+                color,
+            });
+        }
+        slots.sort_by_key(|t| t.slot);
+
+        let duration = bones
+            .iter()
+            .flat_map(|t| {
+                let last = |k: Option<f32>| k.unwrap_or(0.0);
+                [
+                    last(t.rotate.last().map(|k| k.time)),
+                    last(t.translate.last().map(|k| k.time)),
+                    last(t.scale.last().map(|k| k.time)),
+                ]
+            })
+            .chain(slots.iter().flat_map(|t| {
+                [
+                    t.attachment.last().map_or(0.0, |k| k.0),
+                    t.color.last().map_or(0.0, |k| k.time),
+                ]
+            }))
+            .fold(0.0, f32::max);
+
+        Ok(Self {
+            name,
+            duration,
+            bones,
+            slots,
+        })
+    }
+}
+
+/// Sample keyframes at the time, interpolating between them. Returns `None` if there are no keys.
+pub fn sample_keys<T: Interp<Factor = f32> + Copy>(keys: &[Keyframe<T>], time: f32) -> Option<T> {
+    let next = keys.iter().position(|k| k.time > time);
+    match next {
+        None => keys.last().map(|k| k.value),
+        Some(0) => Some(keys[0].value),
+        Some(i) => {
+            let (a, b) = (&keys[i - 1], &keys[i]);
+            if a.stepped {
+                return Some(a.value);
+            }
+            let t = (time - a.time) / (b.time - a.time);
+            Some(a.value.lerp(b.value, t))
+        }
+    }
+}
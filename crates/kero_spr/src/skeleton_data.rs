@@ -0,0 +1,338 @@
+use crate::SkeletonAnim;
+use crate::spine::{SpineAttachment, SpineJson};
+use kero::prelude::*;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Shared data for a skeletal animation, loaded from a Spine JSON export.
+///
+/// Coordinates are converted to Kero's y-down space on load, so positive rotations
+/// are clockwise. Create a [`Skeleton`](crate::Skeleton) from it to pose and draw it.
+#[derive(Debug, Clone)]
+pub struct SkeletonData {
+    /// Bones, ordered so that parents always come before their children.
+    pub bones: Vec<BoneData>,
+
+    /// Slots, in drawing order.
+    pub slots: Vec<SlotData>,
+
+    /// Skins. The skin named `default` is used when an attachment isn't in the active skin.
+    pub skins: Vec<SkinData>,
+
+    /// Animations.
+    pub anims: Vec<SkeletonAnim>,
+}
+
+/// A bone in its setup pose.
+#[derive(Debug, Clone)]
+pub struct BoneData {
+    pub name: String,
+    pub parent: Option<usize>,
+    pub pos: Vec2F,
+
+    /// Rotation in degrees, clockwise.
+    pub rotation: f32,
+    pub scale: Vec2F,
+
+    /// World transform of the bone in the setup pose.
+    pub world: Affine2F,
+}
+
+/// A slot, which holds one of its bone's attachments at a time.
+#[derive(Debug, Clone)]
+pub struct SlotData {
+    pub name: String,
+    pub bone: usize,
+    pub color: Rgba8,
+
+    /// The attachment visible in the setup pose.
+    pub attachment: Option<String>,
+}
+
+/// A named set of attachments.
+#[derive(Debug, Clone)]
+pub struct SkinData {
+    pub name: String,
+
+    /// Attachments by slot index and attachment name.
+    pub attachments: HashMap<(usize, String), AttachmentData>,
+}
+
+/// A textured attachment, stored as a mesh in the skeleton's setup pose.
+///
+/// Region attachments are converted into 4-vertex meshes on load.
+#[derive(Debug, Clone)]
+pub struct AttachmentData {
+    /// Name of the sprite to texture the attachment with.
+    pub path: String,
+    pub color: Rgba8,
+
+    /// Vertex positions in the skeleton's setup pose.
+    pub positions: Vec<Vec2F>,
+
+    /// Texture coordinates, from `0` to `1` across the attachment's sprite.
+    pub uvs: Vec<Vec2F>,
+    pub indices: Vec<u32>,
+    pub weights: Vec<BoneWeights>,
+}
+
+/// An error loading a skeleton.
+#[derive(Debug, thiserror::Error)]
+pub enum SkeletonError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("unknown bone `{0}`")]
+    UnknownBone(String),
+
+    #[error("unknown slot `{0}`")]
+    UnknownSlot(String),
+
+    #[error("invalid color `{0}`")]
+    InvalidColor(String),
+
+    #[error("invalid mesh data for attachment `{0}`")]
+    InvalidMesh(String),
+}
+
+impl SkeletonData {
+    /// Load a skeleton from a Spine JSON file.
+    pub fn from_spine_file(path: impl AsRef<Path>) -> Result<Self, SkeletonError> {
+        Self::from_spine_json(&std::fs::read_to_string(path)?)
+    }
+
+    /// Parse a skeleton from Spine JSON. Bones, slots, region and mesh attachments, skins,
+    /// and bone/attachment/color timelines are supported. Constraints, deform timelines and
+    /// draw order changes are ignored.
+    pub fn from_spine_json(src: &str) -> Result<Self, SkeletonError> {
+        let json: SpineJson = serde_json::from_str(src)?;
+
+        let mut bones: Vec<BoneData> = Vec::with_capacity(json.bones.len());
+        for bone in json.bones {
+            let parent = match bone.parent {
+                Some(parent) => Some(
+                    bones
+                        .iter()
+                        .position(|b| b.name == parent)
+                        .ok_or(SkeletonError::UnknownBone(parent))?,
+                ),
+                None => None,
+            };
+            let pos = vec2(bone.x, -bone.y);
+            let rotation = -bone.rotation;
+            let scale = vec2(bone.scale_x, bone.scale_y);
+            let local = Affine2F::trs(pos, degs(rotation), scale);
+            let world = parent.map_or(local, |p| bones[p].world * local);
+            bones.push(BoneData {
+                name: bone.name,
+                parent,
+                pos,
+                rotation,
+                scale,
+                world,
+            });
+        }
+        let bone_idx = |name: &str| {
+            bones
+                .iter()
+                .position(|b| b.name == name)
+                .ok_or_else(|| SkeletonError::UnknownBone(name.to_string()))
+        };
+
+        let mut slots = Vec::with_capacity(json.slots.len());
+        for slot in json.slots {
+            slots.push(SlotData {
+                bone: bone_idx(&slot.bone)?,
+                color: slot
+                    .color
+                    .as_deref()
+                    .map_or(Ok(Rgba8::WHITE), parse_color)?,
+                attachment: slot.attachment,
+                name: slot.name,
+            });
+        }
+        let slot_idx = |name: &str| {
+            slots
+                .iter()
+                .position(|s| s.name == name)
+                .ok_or_else(|| SkeletonError::UnknownSlot(name.to_string()))
+        };
+
+        let mut skins = Vec::new();
+        for skin in json.skins.into_skins() {
+            let mut attachments = HashMap::new();
+            for (slot_name, slot_attachments) in skin.attachments {
+                let slot = slot_idx(&slot_name)?;
+                for (name, att) in slot_attachments {
+                    if let Some(att) = attachment(&bones, slots[slot].bone, &name, att)? {
+                        attachments.insert((slot, name), att);
+                    }
+                }
+            }
+            skins.push(SkinData {
+                name: skin.name,
+                attachments,
+            });
+        }
+
+        let mut anims = Vec::with_capacity(json.animations.len());
+        for (name, anim) in json.animations {
+            anims.push(SkeletonAnim::from_spine(name, anim, bone_idx, slot_idx)?);
+        }
+        anims.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(Self {
+            bones,
+            slots,
+            skins,
+            anims,
+        })
+    }
+
+    /// Index of the bone with the name.
+    #[inline]
+    pub fn bone_idx(&self, name: &str) -> Option<usize> {
+        self.bones.iter().position(|b| b.name == name)
+    }
+
+    /// Index of the slot with the name.
+    #[inline]
+    pub fn slot_idx(&self, name: &str) -> Option<usize> {
+        self.slots.iter().position(|s| s.name == name)
+    }
+
+    /// Index of the skin with the name.
+    #[inline]
+    pub fn skin_idx(&self, name: &str) -> Option<usize> {
+        self.skins.iter().position(|s| s.name == name)
+    }
+
+    /// The animation with the name.
+    #[inline]
+    pub fn anim(&self, name: &str) -> Option<&SkeletonAnim> {
+        self.anims.iter().find(|a| a.name == name)
+    }
+}
+
+/// Parse a Spine `RRGGBBAA` or `RRGGBB` hex color.
+pub(crate) fn parse_color(hex: &str) -> Result<Rgba8, SkeletonError> {
+    let err = || SkeletonError::InvalidColor(hex.to_string());
+    let packed = u32::from_str_radix(hex, 16).map_err(|_| err())?;
+    match hex.len() {
+        8 => Ok(rgba(packed)),
+        6 => Ok(rgb(packed)),
+        _ => Err(err()),
+    }
+}
+
+/// Convert a Spine attachment into a setup-pose mesh. Unsupported attachment types return `None`.
+fn attachment(
+    bones: &[BoneData],
+    bone: usize,
+    name: &str,
+    att: SpineAttachment,
+) -> Result<Option<AttachmentData>, SkeletonError> {
+    let color = att.color.as_deref().map_or(Ok(Rgba8::WHITE), parse_color)?;
+    let path = att.path.or(att.name).unwrap_or_else(|| name.to_string());
+    let invalid = || SkeletonError::InvalidMesh(name.to_string());
+
+    match att.kind.as_deref().unwrap_or("region") {
+        "region" => {
+            let local = bones[bone].world
+                * Affine2F::trs(
+                    vec2(att.x, -att.y),
+                    degs(-att.rotation),
+                    vec2(att.scale_x, att.scale_y),
+                );
+            let half = vec2(att.width, att.height) * 0.5;
+            let uvs = vec![Vec2F::ZERO, Vec2F::X_AXIS, Vec2F::ONE, Vec2F::Y_AXIS];
+            Ok(Some(AttachmentData {
+                path,
+                color,
+                positions: uvs
+                    .iter()
+                    .map(|&uv| local.transform_pos2(uv * half * 2.0 - half))
+                    .collect(),
+                uvs,
+                indices: vec![0, 1, 2, 0, 2, 3],
+                weights: vec![BoneWeights::single(bone as u16); 4],
+            }))
+        }
+        "mesh" => {
+            let uvs: Vec<Vec2F> = att
+                .uvs
+                .chunks_exact(2)
+                .map(|uv| vec2(uv[0], uv[1]))
+                .collect();
+            let count = uvs.len();
+            if att.triangles.iter().any(|&i| i as usize >= count) {
+                return Err(invalid());
+            }
+            let (positions, weights) = if att.vertices.len() == count * 2 {
+                // unweighted vertices are relative to the slot's bone
+                let world = &bones[bone].world;
+                let positions = att
+                    .vertices
+                    .chunks_exact(2)
+                    .map(|v| world.transform_pos2(vec2(v[0], -v[1])))
+                    .collect();
+                (positions, vec![BoneWeights::single(bone as u16); count])
+            } else {
+                weighted_vertices(bones, &att.vertices, count).ok_or_else(invalid)?
+            };
+            Ok(Some(AttachmentData {
+                path,
+                color,
+                positions,
+                uvs,
+                indices: att.triangles,
+                weights,
+            }))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Parse weighted mesh vertices, where each vertex is a bone count followed by a
+/// `(bone, x, y, weight)` for each bone. Only the two most influential bones are kept.
+fn weighted_vertices(
+    bones: &[BoneData],
+    data: &[f32],
+    count: usize,
+) -> Option<(Vec<Vec2F>, Vec<BoneWeights>)> {
+    let mut positions = Vec::with_capacity(count);
+    let mut weights = Vec::with_capacity(count);
+    let mut data = data.iter().copied();
+    for _ in 0..count {
+        let bone_count = data.next()? as usize;
+        let mut pos = Vec2F::ZERO;
+        let mut infl = [(0u16, 0.0f32); 2];
+        for _ in 0..bone_count {
+            let (b, x, y, w) = (
+                data.next()? as usize,
+                data.next()?,
+                data.next()?,
+                data.next()?,
+            );
+            pos += bones.get(b)?.world.transform_pos2(vec2(x, -y)) * w;
+            if w > infl[0].1 {
+                infl = [(b as u16, w), infl[0]];
+            } else if w > infl[1].1 {
+                infl[1] = (b as u16, w);
+            }
+        }
+        let total = infl[0].1 + infl[1].1;
+        if total <= 0.0 {
+            return None;
+        }
+        positions.push(pos);
+        weights.push(BoneWeights {
+            bones: [infl[0].0, infl[1].0],
+            weights: [infl[0].1 / total, infl[1].1 / total],
+        });
+    }
+    data.next().is_none().then_some((positions, weights))
+}
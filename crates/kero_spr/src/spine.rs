@@ -0,0 +1,161 @@
+//! Raw Spine JSON structures, converted into [`SkeletonData`](crate::SkeletonData) on load.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+
+#[derive(Deserialize)]
+pub(crate) struct SpineJson {
+    pub bones: Vec<SpineBone>,
+    #[serde(default)]
+    pub slots: Vec<SpineSlot>,
+    #[serde(default)]
+    pub skins: SpineSkins,
+    #[serde(default)]
+    pub animations: HashMap<String, SpineAnim>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineBone {
+    pub name: String,
+    pub parent: Option<String>,
+    #[serde(default)]
+    pub x: f32,
+    #[serde(default)]
+    pub y: f32,
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default = "one", rename = "scaleX")]
+    pub scale_x: f32,
+    #[serde(default = "one", rename = "scaleY")]
+    pub scale_y: f32,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineSlot {
+    pub name: String,
+    pub bone: String,
+    pub color: Option<String>,
+    pub attachment: Option<String>,
+}
+
+/// Spine 3.8+ stores skins in a list, older versions in a map.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub(crate) enum SpineSkins {
+    List(Vec<SpineSkin>),
+    Map(HashMap<String, SpineSkinAttachments>),
+}
+
+impl Default for SpineSkins {
+    #[inline]
+    fn default() -> Self {
+        Self::List(Vec::new())
+    }
+}
+
+impl SpineSkins {
+    pub fn into_skins(self) -> Vec<SpineSkin> {
+        match self {
+            Self::List(skins) => skins,
+            Self::Map(skins) => skins
+                .into_iter()
+                .map(|(name, attachments)| SpineSkin { name, attachments })
+                .collect(),
+        }
+    }
+}
+
+/// Attachments of a skin, by slot name and then attachment name.
+pub(crate) type SpineSkinAttachments = HashMap<String, HashMap<String, SpineAttachment>>;
+
+#[derive(Deserialize)]
+pub(crate) struct SpineSkin {
+    pub name: String,
+    #[serde(default)]
+    pub attachments: SpineSkinAttachments,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineAttachment {
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+    pub name: Option<String>,
+    pub path: Option<String>,
+    pub color: Option<String>,
+    #[serde(default)]
+    pub x: f32,
+    #[serde(default)]
+    pub y: f32,
+    #[serde(default)]
+    pub rotation: f32,
+    #[serde(default = "one", rename = "scaleX")]
+    pub scale_x: f32,
+    #[serde(default = "one", rename = "scaleY")]
+    pub scale_y: f32,
+    #[serde(default)]
+    pub width: f32,
+    #[serde(default)]
+    pub height: f32,
+    #[serde(default)]
+    pub uvs: Vec<f32>,
+    #[serde(default)]
+    pub triangles: Vec<u32>,
+    #[serde(default)]
+    pub vertices: Vec<f32>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineAnim {
+    #[serde(default)]
+    pub bones: HashMap<String, SpineBoneTimelines>,
+    #[serde(default)]
+    pub slots: HashMap<String, SpineSlotTimelines>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineBoneTimelines {
+    #[serde(default)]
+    pub rotate: Vec<SpineKey>,
+    #[serde(default)]
+    pub translate: Vec<SpineKey>,
+    #[serde(default)]
+    pub scale: Vec<SpineKey>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineSlotTimelines {
+    #[serde(default)]
+    pub attachment: Vec<SpineKey>,
+    #[serde(default, alias = "rgba")]
+    pub color: Vec<SpineKey>,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct SpineKey {
+    #[serde(default)]
+    pub time: f32,
+
+    /// Rotation in Spine 4.0+.
+    pub value: Option<f32>,
+
+    /// Rotation in Spine 3.x.
+    pub angle: Option<f32>,
+
+    pub x: Option<f32>,
+    pub y: Option<f32>,
+    pub name: Option<String>,
+    pub color: Option<String>,
+    pub curve: Option<serde_json::Value>,
+}
+
+impl SpineKey {
+    #[inline]
+    pub fn stepped(&self) -> bool {
+        matches!(&self.curve, Some(serde_json::Value::String(s)) if s == "stepped")
+    }
+}
+
+#[inline]
+fn one() -> f32 {
+    1.0
+}
@@ -7,9 +7,18 @@ use kero::prelude::*;
 /// Texture coordinates of sprites are paired with offsets, so the
 /// sprite can be positioned ("framed"), which allows you do trim
 /// the sprite, but still render it as if it was its full size.
+///
+/// Sprites are drawn so that their pivot, relative to the top-left
+/// of their untrimmed frame, lands on the provided position.
 #[derive(Debug, Clone)]
 pub struct Sprite {
     pub sub: SubTexture,
+    pub pivot: Vec2F,
+
+    /// Closed collision outlines traced from the sprite's image, relative to the top-left of
+    /// its untrimmed frame. Only generated if the packer was configured with
+    /// [`with_collision`](crate::SpritePacker::with_collision).
+    pub collision: Vec<PolylineF>,
 }
 
 impl Sprite {
@@ -19,6 +28,8 @@ impl Sprite {
     pub fn new_ext(texture: Texture, rect: RectF, offset: Vec2F, size: Vec2F) -> Self {
         Self {
             sub: SubTexture::new_ext(texture, rect, offset, size),
+            pivot: Vec2F::ZERO,
+            collision: Vec::new(),
         }
     }
 
@@ -27,10 +38,49 @@ impl Sprite {
     pub fn new(texture: Texture, rect: impl Into<RectF>) -> Self {
         Self {
             sub: SubTexture::new(texture, rect),
+            pivot: Vec2F::ZERO,
+            collision: Vec::new(),
         }
     }
 
-    /// Draw this sprite at the provided position.
+    /// Return the sprite with its pivot changed.
+    #[inline]
+    pub fn with_pivot(mut self, pivot: impl Into<Vec2F>) -> Self {
+        self.pivot = pivot.into();
+        self
+    }
+
+    /// Return the sprite with its collision outlines changed.
+    #[inline]
+    pub fn with_collision(mut self, collision: Vec<PolylineF>) -> Self {
+        self.collision = collision;
+        self
+    }
+
+    /// Draw the sprite as part of a frame whose top-left is at `frame_pos`. When flipped, the
+    /// sprite's trimmed offset is mirrored within `frame_size`.
+    pub fn draw_in_frame(
+        &self,
+        draw: &mut Draw,
+        frame_pos: Vec2F,
+        frame_size: Vec2F,
+        color: Rgba8,
+        mode: ColorMode,
+        flip: Vec2<bool>,
+    ) {
+        let size = self.sub.rect.size();
+        let mut off = self.sub.offset;
+        if flip.x {
+            off.x = frame_size.x - off.x - size.x;
+        }
+        if flip.y {
+            off.y = frame_size.y - off.y - size.y;
+        }
+        let dst = RectF::pos_size(frame_pos + off, size);
+        draw.subtextured_quad_flipped(&self.sub, dst, color, mode, flip);
+    }
+
+    /// Draw this sprite at the provided position, mirrored around its pivot.
     #[inline]
     pub fn draw_flipped(
         &self,
@@ -40,18 +90,29 @@ impl Sprite {
         mode: ColorMode,
         flip: impl Into<Vec2<bool>>,
     ) {
-        draw.subtexture_at_flipped(&self.sub, pos, color, mode, flip);
+        let flip = flip.into();
+        let frame_pos = pos.into() - flipped_pivot(self.pivot, self.sub.size, flip);
+        self.draw_in_frame(draw, frame_pos, self.sub.size, color, mode, flip);
     }
 
     /// Draw this sprite at the provided position.
     #[inline]
     pub fn draw_ext(&self, draw: &mut Draw, pos: impl Into<Vec2F>, color: Rgba8, mode: ColorMode) {
-        draw.subtexture_at_ext(&self.sub, pos, color, mode);
+        draw.subtexture_at_ext(&self.sub, pos.into() - self.pivot, color, mode);
     }
 
     /// Draw this sprite at the provided position.
     #[inline]
     pub fn draw(&self, draw: &mut Draw, pos: impl Into<Vec2F>) {
-        draw.subtexture_at(&self.sub, pos);
+        draw.subtexture_at(&self.sub, pos.into() - self.pivot);
     }
 }
+
+/// The pivot of a frame of size `size`, after the frame is flipped.
+#[inline]
+pub(crate) fn flipped_pivot(pivot: Vec2F, size: Vec2F, flip: Vec2<bool>) -> Vec2F {
+    vec2(
+        if flip.x { size.x - pivot.x } else { pivot.x },
+        if flip.y { size.y - pivot.y } else { pivot.y },
+    )
+}
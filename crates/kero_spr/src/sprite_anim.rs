@@ -1,7 +1,9 @@
 use crate::Sprite;
+use crate::sprite::flipped_pivot;
 use fey_ase::LoopDir;
 use kero::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// A renderable sprite animation.
 ///
@@ -10,9 +12,13 @@ use serde::{Deserialize, Serialize};
 /// combo in order, composing a sprite. This allows you to toggle certain
 /// layers on/off, being able to do things like swappable equipment, or drawing
 /// certain layers on different render phases (eg. a glow phase).
+///
+/// The anim is drawn so that its pivot, relative to the top-left of the
+/// canvas, lands on the provided position.
 #[derive(Clone, Debug)]
 pub struct SpriteAnim {
     pub size: Vec2F,
+    pub pivot: Vec2F,
     pub frames: Vec<AnimFrame>,
     pub sprites: Vec<Sprite>,
     pub tags: Vec<AnimTag>,
@@ -32,6 +38,7 @@ impl SpriteAnim {
     pub fn new() -> Self {
         Self {
             size: Vec2::ZERO,
+            pivot: Vec2::ZERO,
             frames: Vec::new(),
             sprites: Vec::new(),
             tags: Vec::new(),
@@ -49,8 +56,10 @@ impl SpriteAnim {
         self.tags.iter().find(|t| frame >= t.from && frame <= t.to)
     }
 
+    /// Draw a frame of the animation, mirrored around the anim's pivot. Trimmed cels are
+    /// mirrored within the anim's canvas, so they stay in place relative to each other.
     #[inline]
-    pub fn draw_flipped(
+    pub fn draw_flipped_ext(
         &self,
         draw: &mut Draw,
         frame_index: usize,
@@ -60,16 +69,37 @@ impl SpriteAnim {
         mode: ColorMode,
         flip: impl Into<Vec2<bool>>,
     ) {
-        let pos = pos.into();
         let flip = flip.into();
+        let frame_pos = pos.into() - flipped_pivot(self.pivot, self.size, flip);
         let f = &self.frames[frame_index % self.frames.len()];
         for cel in &f.cels {
             if (layers & (1 << cel.layer)) != 0 {
-                self.sprites[cel.index].draw_flipped(draw, pos, color, mode, flip);
+                self.sprites[cel.index]
+                    .draw_in_frame(draw, frame_pos, self.size, color, mode, flip);
             }
         }
     }
 
+    /// Draw a frame of the animation with all layers visible, mirrored around the anim's pivot.
+    #[inline]
+    pub fn draw_flipped(
+        &self,
+        draw: &mut Draw,
+        frame_index: usize,
+        pos: impl Into<Vec2F>,
+        flip: impl Into<Vec2<bool>>,
+    ) {
+        self.draw_flipped_ext(
+            draw,
+            frame_index,
+            pos,
+            u64::MAX,
+            Rgba8::WHITE,
+            ColorMode::MULT,
+            flip,
+        );
+    }
+
     #[inline]
     pub fn draw_ext(
         &self,
@@ -80,7 +110,7 @@ impl SpriteAnim {
         color: Rgba8,
         mode: ColorMode,
     ) {
-        let pos = pos.into();
+        let pos = pos.into() - self.pivot;
         let f = &self.frames[frame_index % self.frames.len()];
         for cel in &f.cels {
             if (layers & (1 << cel.layer)) != 0 {
@@ -101,6 +131,112 @@ impl SpriteAnim {
         );
     }
 
+    /// Render a frame of the animation into an image the size of the anim's canvas, reading
+    /// its cels back from their textures. Cels are composed in order, the same way they are
+    /// drawn, with only the layers in the `layers` mask visible.
+    ///
+    /// Reading textures back blocks on the GPU, so this is meant for exporting and debugging.
+    #[inline]
+    pub fn render_frame(&self, gfx: &Graphics, frame_index: usize, layers: u64) -> ImageRgba8 {
+        self.render_frame_cached(gfx, &mut Vec::new(), frame_index, layers)
+    }
+
+    /// Render every frame of the animation. See [`render_frame`](Self::render_frame).
+    pub fn render_frames(&self, gfx: &Graphics, layers: u64) -> Vec<ImageRgba8> {
+        let mut pages = Vec::new();
+        (0..self.frames.len())
+            .map(|i| self.render_frame_cached(gfx, &mut pages, i, layers))
+            .collect()
+    }
+
+    fn render_frame_cached(
+        &self,
+        gfx: &Graphics,
+        pages: &mut Vec<(Texture, ImageRgba8)>,
+        frame_index: usize,
+        layers: u64,
+    ) -> ImageRgba8 {
+        let size = self.size.map(|n| n.max(0.0).ceil() as u32);
+        let mut img = ImageRgba8::new_vec(size, Rgba8::TRANSPARENT);
+        let Some(frame) = self.frames.get(frame_index % self.frames.len().max(1)) else {
+            return img;
+        };
+        for cel in &frame.cels {
+            if (layers & (1 << cel.layer)) == 0 {
+                continue;
+            }
+            let sprite = &self.sprites[cel.index];
+            let page = match pages.iter().position(|(tex, _)| *tex == sprite.sub.texture) {
+                Some(i) => &pages[i].1,
+                None => {
+                    let page = gfx.download_texture(&sprite.sub.texture).to_rgba8();
+                    pages.push((sprite.sub.texture.clone(), page));
+                    &pages[pages.len() - 1].1
+                }
+            };
+            let src = sprite.sub.rect.map(|n| n.round() as i32);
+            let dst = (sprite.sub.offset - sprite.pivot).map(|n| n.round() as i32);
+            for y in 0..src.h {
+                for x in 0..src.w {
+                    let (dx, dy) = (dst.x + x, dst.y + y);
+                    if dx < 0 || dy < 0 {
+                        continue;
+                    }
+                    let (Some(&px), Some(out)) = (
+                        page.get((src.x + x) as u32, (src.y + y) as u32),
+                        img.get_mut(dx as u32, dy as u32),
+                    ) else {
+                        continue;
+                    };
+                    *out = blend_over(*out, px);
+                }
+            }
+        }
+        img
+    }
+
+    /// Export the animation as a looping GIF, with each frame shown for its duration. GIFs
+    /// only support fully transparent or opaque pixels, so partial alpha is rounded.
+    pub fn export_gif(
+        &self,
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        layers: u64,
+    ) -> Result<(), ImageError> {
+        let images = self.render_frames(gfx, layers);
+        save_gif_to_file(
+            path,
+            images
+                .iter()
+                .zip(&self.frames)
+                .map(|(img, frame)| (img, frame.duration)),
+        )
+    }
+
+    /// Export the animation as a PNG with every frame laid out left to right.
+    pub fn export_strip(
+        &self,
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        layers: u64,
+    ) -> Result<(), ImageError> {
+        let images = self.render_frames(gfx, layers);
+        let frame_size = images.first().map(|img| img.size()).unwrap_or_default();
+        let mut strip = ImageRgba8::new_vec(
+            vec2(frame_size.x * images.len() as u32, frame_size.y),
+            Rgba8::TRANSPARENT,
+        );
+        for (i, img) in images.iter().enumerate() {
+            strip
+                .view_mut_at(RectU::pos_size(
+                    vec2(frame_size.x * i as u32, 0),
+                    frame_size,
+                ))
+                .draw_copied(img);
+        }
+        strip.save_png_to_file(path)
+    }
+
     /// Index of the layer with the name.
     #[inline]
     pub fn layer_idx(&self, name: &str) -> Option<usize> {
@@ -126,6 +262,23 @@ impl SpriteAnim {
     }
 }
 
+/// Compose `src` over `dst`, both with straight alpha.
+fn blend_over(dst: Rgba8, src: Rgba8) -> Rgba8 {
+    let sa = src.a as u32;
+    let da = dst.a as u32 * (255 - sa) / 255;
+    let a = sa + da;
+    if a == 0 {
+        return Rgba8::TRANSPARENT;
+    }
+    let mix = |s: u8, d: u8| ((s as u32 * sa + d as u32 * da + a / 2) / a) as u8;
+    Rgba8::new(
+        mix(src.r, dst.r),
+        mix(src.g, dst.g),
+        mix(src.b, dst.b),
+        a as u8,
+    )
+}
+
 /// A frame of the sprite animation.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AnimFrame {
@@ -5,6 +5,7 @@ use crate::{
 use kero::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::hash::Hash;
 
 // Represents a packed sprite atlas.
@@ -24,6 +25,10 @@ pub struct AtlasSprite<I> {
     pub size: Vec2U,
     pub rect: RectU,
     pub off: Vec2<i32>,
+    #[serde(default)]
+    pub pivot: Vec2<i32>,
+    #[serde(default)]
+    pub collision: Vec<PolylineF>,
 }
 
 /// A packed sheet.
@@ -76,6 +81,8 @@ pub struct AtlasPatch<I> {
 pub struct AtlasAnim<I> {
     pub id: I,
     pub size: Vec2U,
+    #[serde(default)]
+    pub pivot: Vec2<i32>,
     pub cels: Vec<AtlasCel>,
     pub frames: Vec<AnimFrame>,
     pub tags: Vec<AnimTag>,
@@ -124,6 +131,44 @@ pub struct AtlasGraphicsMapped<I> {
     pub anims: HashMap<I, SpriteAnim>,
 }
 
+impl<I: Display> SpriteAtlas<I> {
+    /// Names and rectangles of everything packed into the atlas, for registering it with the
+    /// [`AtlasDebug`] overlay. Tiles, glyphs and cels are named after what they belong to.
+    ///
+    /// ```ignore
+    /// gfx.atlas_debug().add_page("sprites", texture.clone(), atlas.debug_rects());
+    /// ```
+    pub fn debug_rects(&self) -> Vec<(String, RectF)> {
+        let mut rects = Vec::new();
+        for sprite in &self.sprites {
+            rects.push((sprite.id.to_string(), sprite.rect.to_f32()));
+        }
+        for sheet in &self.sheets {
+            let width = sheet.size.x.max(1) as usize;
+            for (i, tile) in sheet.tiles.iter().enumerate() {
+                if let Some(tile) = tile {
+                    let name = format!("{} [{}, {}]", sheet.id, i % width, i / width);
+                    rects.push((name, tile.rect.to_f32()));
+                }
+            }
+        }
+        for font in &self.fonts {
+            for glyph in font.glyphs.iter().filter(|g| g.rect.area() > 0) {
+                rects.push((format!("{} {:?}", font.id, glyph.chr), glyph.rect.to_f32()));
+            }
+        }
+        for patch in &self.patches {
+            rects.push((patch.id.to_string(), patch.outer.to_f32()));
+        }
+        for anim in &self.anims {
+            for (i, cel) in anim.cels.iter().enumerate() {
+                rects.push((format!("{} #{i}", anim.id), cel.rect.to_f32()));
+            }
+        }
+        rects
+    }
+}
+
 impl<I> SpriteAtlas<I> {
     /// Create renderable graphics assets from this sprite atlas.
     pub fn create_graphics(self, texture: Texture) -> AtlasGraphics<I> {
@@ -138,7 +183,9 @@ impl<I> SpriteAtlas<I> {
                         sprite.rect.to_f32(),
                         sprite.off.to_f32(),
                         sprite.size.to_f32(),
-                    ),
+                    )
+                    .with_pivot(sprite.pivot.to_f32())
+                    .with_collision(sprite.collision),
                 )
             })
             .collect();
@@ -232,6 +279,7 @@ impl<I> SpriteAtlas<I> {
                 (anim.id, {
                     SpriteAnim {
                         size: anim.size.to_f32(),
+                        pivot: anim.pivot.to_f32(),
                         frames: anim.frames,
                         sprites: {
                             anim.cels
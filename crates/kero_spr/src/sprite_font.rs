@@ -1,7 +1,8 @@
 use fnv::FnvHashMap;
 use kero::prelude::*;
 
-use crate::SpriteGlyph;
+use crate::{BmFont, BmFontError, Sprite, SpriteGlyph};
+use std::path::Path;
 
 // A collection of glyphs to be rendered as text.
 ///
@@ -30,6 +31,46 @@ impl SpriteFont {
         }
     }
 
+    /// Create a font from a BMFont, using its page textures directly.
+    pub fn from_bmfont(font: &BmFont, pages: &[Texture]) -> Result<Self, BmFontError> {
+        font.validate(&pages.iter().map(|p| p.size()).collect::<Vec<_>>())?;
+        let mut result = Self::new(font.base as f32, (font.base - font.line_height) as f32, 0.0);
+        for c in &font.chars {
+            // glyphs are drawn relative to the baseline, not the top of the line
+            let sprite = (c.rect.area() > 0).then(|| {
+                let offset = vec2(c.offset.x, c.offset.y - font.base).to_f32();
+                let size = c.rect.size().to_f32();
+                Sprite::new_ext(pages[c.page].clone(), c.rect.to_f32(), offset, size)
+            });
+            result
+                .glyphs
+                .insert(c.chr, SpriteGlyph::new(sprite, c.advance as f32));
+        }
+        result.kerning = font
+            .kerning
+            .iter()
+            .map(|&(a, b, k)| ((a, b), k as f32))
+            .collect();
+        Ok(result)
+    }
+
+    /// Load a font from a BMFont `.fnt` file, loading its page images from the same directory
+    /// as textures.
+    pub fn from_bmfont_file(
+        gfx: &Graphics,
+        path: impl AsRef<Path>,
+        premultiply: bool,
+    ) -> Result<Self, BmFontError> {
+        let path = path.as_ref();
+        let font = BmFont::from_file(path)?;
+        let pages: Vec<Texture> = font
+            .load_pages(path, premultiply)?
+            .iter()
+            .map(|img| gfx.create_texture(img.size(), img.pixels()))
+            .collect();
+        Self::from_bmfont(&font, &pages)
+    }
+
     /// The font's height (`ascent - descent`).
     #[inline]
     pub fn height(&self) -> f32 {
@@ -1,10 +1,12 @@
 use crate::{
     AnimCel, AnimFrame, AnimLayer, AnimTag, AtlasAnim, AtlasCel, AtlasFont, AtlasGlyph,
-    AtlasGraphicsMapped, AtlasPatch, AtlasSheet, AtlasSprite, AtlasTile, SpriteAtlas,
+    AtlasGraphicsMapped, AtlasPatch, AtlasSheet, AtlasSprite, AtlasTile, BmFont, BmFontError,
+    SpriteAtlas,
 };
-use fey_ase::{Ase, CelType, Format};
+use fey_ase::{Ase, CelType, Format, SliceType};
 use fey_font::{Font as FeyFont, FontError};
 use fey_packer::{Item, Packed, RectPacker};
+pub use fey_packer::{PackHeuristic, PackReport};
 use fnv::FnvHashMap;
 use kero::prelude::*;
 use std::ffi::OsStr;
@@ -20,6 +22,10 @@ pub struct SpritePacker<I> {
     fonts: Vec<PackFont<I>>,
     patches: Vec<PackPatch<I>>,
     anims: Vec<PackAnim<I>>,
+    alpha_bleed: u32,
+    collision: Option<(u8, f32)>,
+    heuristics: Vec<PackHeuristic>,
+    report: Option<(PackHeuristic, PackReport)>,
 }
 
 impl<I: Hash + Eq> SpritePacker<I> {
@@ -33,9 +39,68 @@ impl<I: Hash + Eq> SpritePacker<I> {
             fonts: Vec::new(),
             patches: Vec::new(),
             anims: Vec::new(),
+            alpha_bleed: 0,
+            collision: None,
+            heuristics: vec![PackHeuristic::Tree],
+            report: None,
         }
     }
 
+    /// Bleed the color of sprites into surrounding transparent pixels of the atlas, repeated
+    /// `iterations` times, to prevent dark halos when the atlas is filtered. See
+    /// [`Image::bleed_alpha`]. Only use this if the added images are not premultiplied.
+    #[inline]
+    pub fn with_alpha_bleed(mut self, iterations: u32) -> Self {
+        self.set_alpha_bleed(iterations);
+        self
+    }
+
+    /// Trace collision outlines for each sprite when packing, which can be retrieved from
+    /// [`Sprite::collision`](crate::Sprite::collision). Pixels with alpha above
+    /// `alpha_threshold` are solid, and the outlines are simplified so that they deviate no more
+    /// than `tolerance` pixels from the traced shape.
+    #[inline]
+    pub fn with_collision(mut self, alpha_threshold: u8, tolerance: f32) -> Self {
+        self.set_collision(Some((alpha_threshold, tolerance)));
+        self
+    }
+
+    /// Set the `(alpha_threshold, tolerance)` used to trace collision outlines for each sprite,
+    /// or `None` to not trace them. See [`with_collision`](Self::with_collision).
+    #[inline]
+    pub fn set_collision(&mut self, collision: Option<(u8, f32)>) {
+        self.collision = collision;
+    }
+
+    /// Pack the atlas with each of the heuristics and keep the tightest result. For example,
+    /// pass [`PackHeuristic::ALL`] to try them all. By default only
+    /// [`Tree`](PackHeuristic::Tree) is used.
+    #[inline]
+    pub fn with_heuristics(mut self, heuristics: impl IntoIterator<Item = PackHeuristic>) -> Self {
+        self.set_heuristics(heuristics);
+        self
+    }
+
+    /// Set the heuristics to pack the atlas with. See
+    /// [`with_heuristics`](Self::with_heuristics).
+    #[inline]
+    pub fn set_heuristics(&mut self, heuristics: impl IntoIterator<Item = PackHeuristic>) {
+        self.heuristics = heuristics.into_iter().collect();
+    }
+
+    /// The heuristic that produced the last packed atlas, and a report of how well it packed.
+    #[inline]
+    pub fn report(&self) -> Option<(PackHeuristic, PackReport)> {
+        self.report
+    }
+
+    /// Set how many iterations of alpha bleeding to apply to the packed atlas. See
+    /// [`with_alpha_bleed`](Self::with_alpha_bleed).
+    #[inline]
+    pub fn set_alpha_bleed(&mut self, iterations: u32) {
+        self.alpha_bleed = iterations;
+    }
+
     fn add_image(
         &mut self,
         img: ImageRgba8,
@@ -70,8 +135,23 @@ impl<I: Hash + Eq> SpritePacker<I> {
 
     /// Add a sprite (a single image) to be packed.
     pub fn add_sprite(&mut self, id: I, img: ImageRgba8, trim_threshold: Option<u8>) {
+        self.add_sprite_ext(id, img, trim_threshold, Vec2::ZERO);
+    }
+
+    /// Add a sprite (a single image) to be packed, with a pivot relative to the image's top-left.
+    pub fn add_sprite_ext(
+        &mut self,
+        id: I,
+        img: ImageRgba8,
+        trim_threshold: Option<u8>,
+        pivot: impl Into<Vec2I>,
+    ) {
         let img = self.add_image(img, trim_threshold, Vec2::ZERO);
-        self.sprites.push(PackSprite { id, img });
+        self.sprites.push(PackSprite {
+            id,
+            img,
+            pivot: pivot.into(),
+        });
     }
 
     /// Add a sprite (a single image) to be packed from a PNG/QOI file.
@@ -90,6 +170,24 @@ impl<I: Hash + Eq> SpritePacker<I> {
         Ok(())
     }
 
+    /// Add a sprite (a single image) to be packed from a PNG/QOI file, with a pivot relative to
+    /// the image's top-left.
+    pub fn add_sprite_file_ext(
+        &mut self,
+        id: I,
+        path: impl AsRef<Path>,
+        premultiply: bool,
+        trim_threshold: Option<u8>,
+        pivot: impl Into<Vec2I>,
+    ) -> Result<(), ImageError> {
+        let mut img = DynImage::load_file(path)?.to_rgba8();
+        if premultiply {
+            img.premultiply();
+        }
+        self.add_sprite_ext(id, img, trim_threshold, pivot);
+        Ok(())
+    }
+
     /// Add a tile sheet to be packed. The sheet will be split up and tiles will be
     /// individually packed in order to fit them in better.
     pub fn add_sheet(
@@ -198,6 +296,60 @@ impl<I: Hash + Eq> SpritePacker<I> {
         Ok(())
     }
 
+    /// Add a BMFont to be packed, cutting each glyph out of the font's page images.
+    pub fn add_bmfont(
+        &mut self,
+        id: I,
+        font: &BmFont,
+        pages: &[ImageRgba8],
+    ) -> Result<(), BmFontError> {
+        font.validate(&pages.iter().map(|p| p.size()).collect::<Vec<_>>())?;
+        let glyphs = font
+            .chars
+            .iter()
+            .map(|c| {
+                let img = (c.rect.area() > 0)
+                    .then(|| {
+                        let (x, y, w, h) = (c.rect.x, c.rect.y, c.rect.w, c.rect.h);
+                        let sub = ImageRgba8::from_grid(&pages[c.page].view(x, y, w, h));
+                        // glyphs are drawn relative to the baseline, not the top of the line
+                        self.add_image(sub, None, vec2(-c.offset.x, font.base - c.offset.y))
+                    })
+                    .flatten();
+                (
+                    c.chr,
+                    PackGlyph {
+                        img,
+                        adv: c.advance,
+                    },
+                )
+            })
+            .collect();
+        self.fonts.push(PackFont {
+            id,
+            ascent: font.base,
+            descent: font.base - font.line_height,
+            line_gap: 0,
+            glyphs,
+            kerning: font.kerning.clone(),
+        });
+        Ok(())
+    }
+
+    /// Add a BMFont to be packed from a `.fnt` file, loading its page images from the same
+    /// directory.
+    pub fn add_bmfont_file(
+        &mut self,
+        id: I,
+        path: impl AsRef<Path>,
+        premultiply: bool,
+    ) -> Result<(), BmFontError> {
+        let path = path.as_ref();
+        let font = BmFont::from_file(path)?;
+        let pages = font.load_pages(path, premultiply)?;
+        self.add_bmfont(id, &font, &pages)
+    }
+
     /// Add a 9-patch to be packed.
     pub fn add_patch(&mut self, id: I, img: ImageRgba8, inner: impl Into<RectU>) {
         let img = self.add_image(img, None, Vec2::ZERO);
@@ -223,6 +375,9 @@ impl<I: Hash + Eq> SpritePacker<I> {
 
     /// Add an aseprite animation to be packed. The individual cels of the animation
     /// will be packed individually to better fit them into the atlas.
+    ///
+    /// If the file has a slice named `pivot`, the pivot point of its first key is used
+    /// as the animation's pivot.
     pub fn add_ase(&mut self, id: I, ase: &Ase) {
         let make_img = |size: Vec2<usize>, data: &[u8]| match ase.format {
             Format::Rgba => ImageRgba8::new_slice(size.to_u32(), data).to_owned(),
@@ -317,9 +472,21 @@ impl<I: Hash + Eq> SpritePacker<I> {
             })
             .collect();
 
+        let pivot = ase
+            .slices
+            .iter()
+            .find(|slice| slice.name == "pivot")
+            .and_then(|slice| match &slice.ty {
+                SliceType::Rect(keys) => keys.first(),
+                SliceType::Nine(keys) => keys.first().map(|key| &key.key),
+            })
+            .map(|key| key.origin + key.pivot)
+            .unwrap_or(Vec2::ZERO);
+
         self.anims.push(PackAnim {
             id,
             size: ase.size,
+            pivot,
             images,
             frames,
             tags,
@@ -350,26 +517,36 @@ impl<I: Hash + Eq> SpritePacker<I> {
 
     /// Pack all the items into a sprite atlas.
     pub fn pack_atlas(&mut self, max_size: u32) -> Option<(ImageRgba8, SpriteAtlas<I>)> {
-        let (size, mut packed) = RectPacker::new()
+        let (heuristic, pages) = RectPacker::new()
             .with_max_size(max_size)
             .with_spacing(1)
             .with_padding(2)
             .with_power_of_two()
-            .pack(
+            .pack_best(
                 self.images
                     .iter()
                     .enumerate()
                     .map(|(i, img)| Item::new(img.trim.size(), i))
                     .collect(),
+                &self.heuristics,
             )?;
+        if pages.pages.len() > 1 {
+            return None;
+        }
+        self.report = Some((heuristic, pages.report));
+        let size = pages.pages.first().copied().unwrap_or(Vec2U::ZERO);
+        let mut packed = pages.items;
         packed.sort_by_key(|p| p.data);
 
         let mut image = ImageRgba8::new_vec(size, Rgba8::TRANSPARENT);
-        for &Packed { data, pos } in &packed {
+        for &Packed { data, pos, .. } in &packed {
             let src = self.images[data].view();
             let mut dst = image.view_mut(pos.x, pos.y, src.width(), src.height());
             dst.draw_copied(&src);
         }
+        if self.alpha_bleed > 0 {
+            image.bleed_alpha(self.alpha_bleed);
+        }
 
         let img_data = |img: PackImage| {
             let size = self.images[img.img_data].trim.size();
@@ -378,17 +555,30 @@ impl<I: Hash + Eq> SpritePacker<I> {
             (img.orig_size, rect, img.offset)
         };
 
+        let collision = |img: &PackImage| match self.collision {
+            Some((threshold, tolerance)) => self.images[img.img_data]
+                .img
+                .trace_outlines(|p| p.a > threshold)
+                .into_iter()
+                .map(|outline| outline.simplify(tolerance))
+                .collect(),
+            None => Vec::new(),
+        };
+
         let sprites: Vec<AtlasSprite<I>> = self
             .sprites
             .drain(..)
             .flat_map(|spr| {
                 spr.img.map(|img| {
+                    let collision = collision(&img);
                     let (size, rect, off) = img_data(img);
                     AtlasSprite {
                         id: spr.id,
                         size,
                         rect,
                         off,
+                        pivot: spr.pivot,
+                        collision,
                     }
                 })
             })
@@ -478,6 +668,7 @@ impl<I: Hash + Eq> SpritePacker<I> {
                 AtlasAnim {
                     id: anim.id,
                     size: anim.size.to_u32(),
+                    pivot: anim.pivot,
                     cels,
                     frames: anim.frames,
                     tags: anim.tags,
@@ -592,6 +783,7 @@ struct PackImage {
 struct PackSprite<I> {
     id: I,
     img: Option<PackImage>,
+    pivot: Vec2I,
 }
 
 struct PackSheet<I> {
@@ -623,6 +815,7 @@ struct PackPatch<I> {
 struct PackAnim<I> {
     id: I,
     size: Vec2<u16>,
+    pivot: Vec2I,
     images: Vec<PackImage>,
     frames: Vec<AnimFrame>,
     tags: Vec<AnimTag>,
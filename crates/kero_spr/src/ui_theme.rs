@@ -0,0 +1,428 @@
+use crate::{AtlasGraphicsMapped, SpriteFont, SpritePatch};
+use kero::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::hash::Hash;
+use std::path::Path;
+
+/// An error loading a [`UiTheme`].
+#[derive(Debug, thiserror::Error)]
+pub enum UiThemeError {
+    #[error("{0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("patch {0:?} not found")]
+    MissingPatch(String),
+
+    #[error("font {0:?} not found")]
+    MissingFont(String),
+}
+
+/// The state a widget is drawn in.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum WidgetState {
+    #[default]
+    Normal,
+
+    /// The mouse is over the widget.
+    Hover,
+
+    /// The widget is selected by a gamepad or keyboard, even when the mouse is elsewhere.
+    Focused,
+
+    /// The widget is being pressed.
+    Pressed,
+
+    /// The widget can't be interacted with.
+    Disabled,
+}
+
+impl From<Interaction> for WidgetState {
+    fn from(interaction: Interaction) -> Self {
+        if interaction.pressed {
+            Self::Pressed
+        } else if interaction.focused {
+            Self::Focused
+        } else if interaction.hovered {
+            Self::Hover
+        } else {
+            Self::Normal
+        }
+    }
+}
+
+/// Names of the patches a [`UiTheme`] draws a widget with in each state. States left out fall
+/// back to `normal`, except `focused`, which falls back to `hover` first.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PatchSetDef {
+    pub normal: Option<String>,
+    pub hover: Option<String>,
+    pub focused: Option<String>,
+    pub pressed: Option<String>,
+    pub disabled: Option<String>,
+}
+
+/// Text colors for each [`WidgetState`]. States left out use `normal`.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TextColors {
+    pub normal: Rgba8,
+    pub hover: Option<Rgba8>,
+    pub focused: Option<Rgba8>,
+    pub pressed: Option<Rgba8>,
+    pub disabled: Option<Rgba8>,
+}
+
+impl Default for TextColors {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            normal: Rgba8::WHITE,
+            hover: None,
+            focused: None,
+            pressed: None,
+            disabled: Some(Rgba8::new(128, 128, 128, 255)),
+        }
+    }
+}
+
+impl TextColors {
+    /// The text color for the state.
+    #[inline]
+    pub fn get(&self, state: WidgetState) -> Rgba8 {
+        match state {
+            WidgetState::Normal => None,
+            WidgetState::Hover => self.hover,
+            WidgetState::Focused => self.focused.or(self.hover),
+            WidgetState::Pressed => self.pressed,
+            WidgetState::Disabled => self.disabled,
+        }
+        .unwrap_or(self.normal)
+    }
+}
+
+/// The data file format of a [`UiTheme`], which refers to patches and fonts by their names in
+/// a packed sprite atlas. Colors are premultiplied hex strings like `"#FFFFFFFF"`.
+///
+/// ```json
+/// {
+///     "font": "menu",
+///     "panel": "panel",
+///     "button": { "normal": "button", "hover": "button_hover", "pressed": "button_down" },
+///     "button_text": { "normal": "#20202AFF", "focused": "#FFFFFFFF" },
+///     "button_padding": [8, 4]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiThemeDef {
+    /// Name of the font used for all text.
+    pub font: Option<String>,
+
+    /// Name of the patch drawn behind panels.
+    pub panel: Option<String>,
+
+    /// Patches drawn behind buttons.
+    pub button: PatchSetDef,
+
+    /// Color of text drawn on panels.
+    pub text: Rgba8,
+
+    /// Color of button labels.
+    pub button_text: TextColors,
+
+    /// Space between the edge of a panel and its contents.
+    pub panel_padding: Vec2F,
+
+    /// Space between the edge of a button and its label.
+    pub button_padding: Vec2F,
+
+    /// Space between widgets laid out next to each other.
+    pub spacing: f32,
+}
+
+impl Default for UiThemeDef {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            font: None,
+            panel: None,
+            button: PatchSetDef::default(),
+            text: Rgba8::WHITE,
+            button_text: TextColors::default(),
+            panel_padding: vec2(8.0, 8.0),
+            button_padding: vec2(6.0, 3.0),
+            spacing: 4.0,
+        }
+    }
+}
+
+impl UiThemeDef {
+    /// Parse a theme from JSON.
+    #[inline]
+    pub fn from_json(bytes: &[u8]) -> Result<Self, UiThemeError> {
+        Ok(serde_json::from_slice(bytes)?)
+    }
+
+    /// Load a theme from a JSON file.
+    #[inline]
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, UiThemeError> {
+        Self::from_json(&std::fs::read(path)?)
+    }
+}
+
+/// A patch for each [`WidgetState`], resolved from a [`PatchSetDef`].
+#[derive(Debug, Default, Clone)]
+pub struct PatchSet {
+    pub normal: Option<SpritePatch>,
+    pub hover: Option<SpritePatch>,
+    pub focused: Option<SpritePatch>,
+    pub pressed: Option<SpritePatch>,
+    pub disabled: Option<SpritePatch>,
+}
+
+impl PatchSet {
+    /// The patch for the state.
+    #[inline]
+    pub fn get(&self, state: WidgetState) -> Option<&SpritePatch> {
+        match state {
+            WidgetState::Normal => None,
+            WidgetState::Hover => self.hover.as_ref(),
+            WidgetState::Focused => self.focused.as_ref().or(self.hover.as_ref()),
+            WidgetState::Pressed => self.pressed.as_ref(),
+            WidgetState::Disabled => self.disabled.as_ref(),
+        }
+        .or(self.normal.as_ref())
+    }
+}
+
+/// The look of menus and other UI, so they can be restyled by editing a data file instead of
+/// code.
+///
+/// Themes are loaded from a [`UiThemeDef`] and the sprite atlas its patches and font were
+/// packed into. Widgets are drawn with a [`WidgetState`], so buttons selected with a gamepad
+/// show their focused look even when the mouse is somewhere else. To use a theme with the
+/// immediate-mode [`Ui`], see [`ThemedUi`].
+///
+/// ```ignore
+/// let theme = UiTheme::from_file("assets/theme.json", &graphics)?;
+/// theme.draw_panel(draw, menu_rect);
+/// let state = if selected == 0 { WidgetState::Focused } else { WidgetState::Normal };
+/// theme.draw_button(draw, play_rect, "Play", state);
+/// ```
+#[derive(Debug, Clone)]
+pub struct UiTheme {
+    pub font: Option<SpriteFont>,
+    pub panel: Option<SpritePatch>,
+    pub button: PatchSet,
+    pub text: Rgba8,
+    pub button_text: TextColors,
+    pub panel_padding: Vec2F,
+    pub button_padding: Vec2F,
+    pub spacing: f32,
+}
+
+impl UiTheme {
+    /// Create a theme, looking up its patches and font in the atlas graphics.
+    pub fn from_def<I>(def: &UiThemeDef, gfx: &AtlasGraphicsMapped<I>) -> Result<Self, UiThemeError>
+    where
+        I: Eq + Hash + Borrow<str>,
+    {
+        let patch = |name: &Option<String>| match name {
+            Some(name) => gfx
+                .patches
+                .get(name.as_str())
+                .cloned()
+                .map(Some)
+                .ok_or_else(|| UiThemeError::MissingPatch(name.clone())),
+            None => Ok(None),
+        };
+        let font = match &def.font {
+            Some(name) => Some(
+                gfx.fonts
+                    .get(name.as_str())
+                    .cloned()
+                    .ok_or_else(|| UiThemeError::MissingFont(name.clone()))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            font,
+            panel: patch(&def.panel)?,
+            button: PatchSet {
+                normal: patch(&def.button.normal)?,
+                hover: patch(&def.button.hover)?,
+                focused: patch(&def.button.focused)?,
+                pressed: patch(&def.button.pressed)?,
+                disabled: patch(&def.button.disabled)?,
+            },
+            text: def.text,
+            button_text: def.button_text,
+            panel_padding: def.panel_padding,
+            button_padding: def.button_padding,
+            spacing: def.spacing,
+        })
+    }
+
+    /// Load a theme from a JSON file, looking up its patches and font in the atlas graphics.
+    #[inline]
+    pub fn from_file<I>(
+        path: impl AsRef<Path>,
+        gfx: &AtlasGraphicsMapped<I>,
+    ) -> Result<Self, UiThemeError>
+    where
+        I: Eq + Hash + Borrow<str>,
+    {
+        Self::from_def(&UiThemeDef::from_file(path)?, gfx)
+    }
+
+    /// The area inside a panel where its contents go.
+    #[inline]
+    pub fn panel_content(&self, rect: RectF) -> RectF {
+        rect.inset(self.panel_padding)
+    }
+
+    /// The size of a button that fits the label.
+    #[inline]
+    pub fn button_size(&self, label: &str) -> Vec2F {
+        let text = match &self.font {
+            Some(font) => vec2(font.text_width(label), font.height()),
+            None => Vec2F::ZERO,
+        };
+        text + self.button_padding * 2.0
+    }
+
+    /// Draw a panel.
+    #[inline]
+    pub fn draw_panel(&self, draw: &mut Draw, rect: impl Into<RectF>) {
+        if let Some(patch) = &self.panel {
+            patch.draw(draw, rect);
+        }
+    }
+
+    /// Draw text on a panel, with its top-left at `pos`.
+    #[inline]
+    pub fn draw_text(&self, draw: &mut Draw, text: &str, pos: impl Into<Vec2F>) {
+        if let Some(font) = &self.font {
+            font.draw_text(draw, text, pos.into() + vec2(0.0, font.ascent), self.text);
+        }
+    }
+
+    /// Draw a button with its label centered inside it.
+    pub fn draw_button(
+        &self,
+        draw: &mut Draw,
+        rect: impl Into<RectF>,
+        label: &str,
+        state: WidgetState,
+    ) {
+        let rect = rect.into();
+        if let Some(patch) = self.button.get(state) {
+            patch.draw(draw, rect);
+        }
+        if let Some(font) = &self.font {
+            let size = vec2(font.text_width(label), font.height());
+            let pos = (rect.center() - size * 0.5).round() + vec2(0.0, font.ascent);
+            font.draw_text(draw, label, pos, self.button_text.get(state));
+        }
+    }
+
+    /// The size of a [`UiTree`] node without children, to lay the tree out with.
+    pub fn measure(&self, node: &UiNode) -> Vec2F {
+        match node.kind() {
+            UiNodeKind::Button => self.button_size(node.text()),
+            UiNodeKind::Label => match &self.font {
+                Some(font) => vec2(font.text_width(node.text()), font.height()),
+                None => Vec2F::ZERO,
+            },
+            _ => Vec2F::ZERO,
+        }
+    }
+
+    /// Draw a [`UiTree`] node. Images are left to the caller, since the theme doesn't know
+    /// them by name.
+    pub fn draw_node(&self, draw: &mut Draw, node: &UiNode) {
+        let state = if !node.is_enabled() {
+            WidgetState::Disabled
+        } else if node.is_pressed() {
+            WidgetState::Pressed
+        } else if node.is_focused() {
+            WidgetState::Focused
+        } else if node.is_hovered() {
+            WidgetState::Hover
+        } else {
+            WidgetState::Normal
+        };
+        match node.kind() {
+            UiNodeKind::Panel => self.draw_panel(draw, node.rect()),
+            UiNodeKind::Button => self.draw_button(draw, node.rect(), node.text(), state),
+            UiNodeKind::Label => self.draw_text(draw, node.text(), node.rect().top_left()),
+            _ => {}
+        }
+    }
+}
+
+/// Widgets for the immediate-mode [`Ui`] drawn with a [`UiTheme`]. Buttons are drawn hovered,
+/// pressed or focused as the UI's mouse and [focus](UiMemory::set_focus) say, and are pulled
+/// toward by a [`GamepadCursor`].
+///
+/// ```ignore
+/// let mut ui = Ui::new(ctx, &mut self.ui, draw, screen_rect);
+/// ui.themed_panel(&theme, vec2(160.0, 120.0), |ui| {
+///     ui.themed_label(&theme, "Paused");
+///     if ui.themed_button(&theme, "resume", "Resume") {
+///         self.paused = false;
+///     }
+/// });
+/// ```
+pub trait ThemedUi {
+    /// Add a panel and lay out widgets inside its padding, spaced apart by the theme.
+    fn themed_panel<R>(
+        &mut self,
+        theme: &UiTheme,
+        size: impl Into<Vec2F>,
+        f: impl FnOnce(&mut Ui) -> R,
+    ) -> R;
+
+    /// Add a line of text.
+    fn themed_label(&mut self, theme: &UiTheme, text: &str);
+
+    /// Add a button sized to fit its label, returning if it was clicked.
+    fn themed_button(&mut self, theme: &UiTheme, id: impl Hash, label: &str) -> bool;
+}
+
+impl ThemedUi for Ui<'_> {
+    fn themed_panel<R>(
+        &mut self,
+        theme: &UiTheme,
+        size: impl Into<Vec2F>,
+        f: impl FnOnce(&mut Ui) -> R,
+    ) -> R {
+        let rect = self.allocate(size);
+        theme.draw_panel(self.draw, rect);
+        self.show_in(theme.panel_content(rect), |ui| {
+            ui.set_spacing(theme.spacing);
+            f(ui)
+        })
+    }
+
+    fn themed_label(&mut self, theme: &UiTheme, text: &str) {
+        let size = match &theme.font {
+            Some(font) => vec2(font.text_width(text), font.height()),
+            None => Vec2F::ZERO,
+        };
+        let rect = self.allocate(size);
+        theme.draw_text(self.draw, text, rect.top_left());
+    }
+
+    fn themed_button(&mut self, theme: &UiTheme, id: impl Hash, label: &str) -> bool {
+        let rect = self.allocate(theme.button_size(label));
+        let interaction = self.interact(id, rect);
+        theme.draw_button(self.draw, rect, label, interaction.into());
+        interaction.clicked
+    }
+}